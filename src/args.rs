@@ -0,0 +1,421 @@
+use super::subcommands::{
+    AuthAction, CacheAction, ConfigAction, ExportAction, HookAction, ImportAction, LockAction,
+    MigrateAction, SbomArgs, TrustAction, VerifyArgs,
+};
+use super::upgrade_args::UpgradeArgs;
+use clap::{Parser, Subcommand};
+use gx::infra::git::Changes;
+use gx::tidy;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "gx")]
+#[command(about = "CLI to manage Github Actions dependencies", long_about = None)]
+#[command(version)]
+/// CLI argument parser for the gx binary.
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each bool is an independent CLI flag"
+)]
+pub struct Cli {
+    /// Forbid network access: work from gx.lock alone and fail on anything it lacks.
+    /// `GX_OFFLINE=1` does the same.
+    #[arg(long, global = true, visible_alias = "locked")]
+    pub offline: bool,
+    /// Print each registry request with its status and timing on stderr; `-vv` adds
+    /// the headers, with credentials redacted.
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+    /// Fail on keys of gx.toml that gx does not know instead of warning about them.
+    #[arg(long, global = true)]
+    pub strict: bool,
+    /// Save a `.bak` copy of gx.toml, gx.lock, and each workflow before rewriting it.
+    #[arg(long, global = true)]
+    pub backup: bool,
+    /// Refuse to lock commits that gx-trust.toml, imported with `gx trust import`, does
+    /// not approve.
+    #[arg(long, global = true)]
+    pub trusted_only: bool,
+    /// Record every registry request and response in a HAR file, credentials redacted.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub har: Option<PathBuf>,
+    /// Work on the checkout at PATH instead of the repository around the working
+    /// directory.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub repo_root: Option<PathBuf>,
+    /// The subcommand to execute.
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+/// Available subcommands for the gx CLI.
+#[derive(Clone, Subcommand)]
+pub enum Commands {
+    /// Ensure the manifest and lock matches the workflow code.
+    Tidy {
+        /// Commit the changed files to the current branch.
+        #[arg(long)]
+        commit: bool,
+        /// Message for the `--commit` commit.
+        #[arg(long, value_name = "MESSAGE", requires = "commit")]
+        commit_message: Option<String>,
+        /// Rewrite actions whose repository was renamed to use the new name.
+        #[arg(long)]
+        fix: bool,
+        /// Write nothing and exit with 1 when gx.toml, gx.lock, or a workflow is out of date.
+        #[arg(long, conflicts_with = "commit")]
+        check: bool,
+        /// Limit the rewritten workflows to those changed in git.
+        #[command(flatten)]
+        changes: ChangesArgs,
+        /// Output format of the report.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Create manifest and lock files from current workflows.
+    Init,
+    /// Upgrade actions to newer versions.
+    Upgrade(UpgradeArgs),
+    /// Apply an upgrade plan saved with `gx upgrade --save-plan`.
+    Apply {
+        /// Path to the saved plan JSON.
+        #[arg(value_name = "PLAN")]
+        plan: PathBuf,
+        /// Apply the plan even if gx.toml or gx.lock changed since it was saved.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Revert the file changes of the most recent gx run.
+    Undo,
+    /// Remove the `.bak` copies saved by `--backup`.
+    CleanBackups,
+    /// Log in to GitHub or show which credential gx uses.
+    Auth {
+        /// The auth action to run.
+        #[command(subcommand)]
+        action: AuthAction,
+    },
+    /// Inspect or purge the cache shared by every repository on this machine.
+    Cache {
+        /// The cache action to run.
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Run `gx lint` and `gx tidy --check` on changed workflows before each commit.
+    Hook {
+        /// The hook action to run.
+        #[command(subcommand)]
+        action: HookAction,
+    },
+    /// Bring the settings of another dependency tool into gx.toml.
+    Import {
+        /// The tool to import from.
+        #[command(subcommand)]
+        action: ImportAction,
+    },
+    /// Generate rules that keep another update bot away from the actions gx manages.
+    Export {
+        /// The bot to generate rules for.
+        #[command(subcommand)]
+        action: ExportAction,
+    },
+    /// Switch to gx from another pinning tool.
+    Migrate {
+        /// The tool to migrate from.
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+    /// Compare or merge gx.lock files.
+    Lock {
+        /// The lock action to run.
+        #[command(subcommand)]
+        action: LockAction,
+    },
+    /// Validate gx.toml or print its JSON Schema.
+    Config {
+        /// The config action to run.
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Pin the refs of GitLab CI includes to commit SHAs.
+    #[cfg(feature = "gitlab")]
+    Gitlab {
+        /// The GitLab action to run.
+        #[command(subcommand)]
+        action: super::subcommands::GitlabAction,
+    },
+    /// List the actions that locked composite actions use, and whether they are pinned.
+    Tree,
+    /// Run lint checks on workflows.
+    ///
+    /// Reports action-hygiene issues (sha-mismatch, unpinned, stale-comment,
+    /// unsynced-manifest) and workflow-security issues (missing-permissions,
+    /// excessive-permissions, dangerous-trigger, pr-head-checkout,
+    /// missing-concurrency, unprotected-secrets). Configure per-rule severity
+    /// and ignores under `[lint.rules]` in `.github/gx.toml`. See
+    /// `docs/lint-rules.md`.
+    Lint {
+        /// Limit the reported workflows to those changed in git.
+        #[command(flatten)]
+        changes: ChangesArgs,
+    },
+    /// Check, without network calls, that gx.lock covers every action in gx.toml.
+    Verify(VerifyArgs),
+    /// Check the actions gx.lock pins against the GitHub Advisory Database, failing when
+    /// an advisory affects one.
+    Audit,
+    /// List the license of the repository of every action gx.lock pins, failing on those
+    /// `[licenses] deny` names.
+    Licenses,
+    /// Print a software bill of materials listing every action gx.lock pins.
+    Sbom(SbomArgs),
+    /// Export or import the approved commits that `--trusted-only` lets into gx.lock.
+    Trust {
+        /// The trust action to run.
+        #[command(subcommand)]
+        action: TrustAction,
+    },
+    /// Print a digest of gx.toml and gx.lock, for a CI cache key.
+    Hash {
+        /// Output format of the report.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t)]
+        format: Format,
+    },
+}
+
+/// How a command prints its report.
+#[derive(Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Human-readable lines and tables.
+    #[default]
+    Text,
+    /// The report as a JSON document on stdout.
+    Json,
+    /// One JSON event per line on stdout, for wrapper tooling and bots.
+    Ndjson,
+}
+
+impl Commands {
+    /// The subcommand name, as typed on the command line.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Tidy { .. } => "tidy",
+            Self::Init => "init",
+            Self::Upgrade(_) => "upgrade",
+            Self::Apply { .. } => "apply",
+            Self::Undo => "undo",
+            Self::CleanBackups => "clean-backups",
+            Self::Auth { .. } => "auth",
+            Self::Cache { .. } => "cache",
+            Self::Hook { .. } => "hook",
+            Self::Import { .. } => "import",
+            Self::Export { .. } => "export",
+            Self::Migrate { .. } => "migrate",
+            Self::Lock { .. } => "lock",
+            Self::Config { .. } => "config",
+            Self::Tree => "tree",
+            #[cfg(feature = "gitlab")]
+            Self::Gitlab { .. } => "gitlab",
+            Self::Lint { .. } => "lint",
+            Self::Verify(_) => "verify",
+            Self::Audit => "audit",
+            Self::Licenses => "licenses",
+            Self::Sbom(_) => "sbom",
+            Self::Trust { .. } => "trust",
+            Self::Hash { .. } => "hash",
+        }
+    }
+
+    /// Whether the command may rewrite files, so its changes go to the undo journal.
+    #[must_use]
+    pub fn writes_files(&self) -> bool {
+        match self {
+            Self::Tidy { .. }
+            | Self::Init
+            | Self::Upgrade(_)
+            | Self::Apply { .. }
+            | Self::Import { .. }
+            | Self::Migrate { .. }
+            | Self::Lock {
+                action: LockAction::Convert,
+            }
+            | Self::Trust {
+                action: TrustAction::Import { .. },
+            } => true,
+            #[cfg(feature = "gitlab")]
+            Self::Gitlab { .. } => true,
+            Self::Undo
+            | Self::CleanBackups
+            | Self::Auth { .. }
+            | Self::Cache { .. }
+            | Self::Hook { .. }
+            | Self::Export { .. }
+            | Self::Lock { .. }
+            | Self::Config { .. }
+            | Self::Tree
+            | Self::Lint { .. }
+            | Self::Verify(_)
+            | Self::Audit
+            | Self::Licenses
+            | Self::Sbom(_)
+            | Self::Trust { .. }
+            | Self::Hash { .. } => false,
+        }
+    }
+
+    /// The output format the command was asked for; text for commands without `--format`.
+    #[must_use]
+    pub fn format(&self) -> Format {
+        match self {
+            Self::Tidy { format, .. }
+            | Self::Upgrade(UpgradeArgs { format, .. })
+            | Self::Lock {
+                action: LockAction::Diff { format, .. } | LockAction::List { format },
+            }
+            | Self::Hash { format } => *format,
+            Self::Config {
+                action: ConfigAction::Schema,
+            } => Format::Json,
+            Self::Init
+            | Self::Apply { .. }
+            | Self::Undo
+            | Self::CleanBackups
+            | Self::Auth { .. }
+            | Self::Cache { .. }
+            | Self::Hook { .. }
+            | Self::Import { .. }
+            | Self::Export { .. }
+            | Self::Migrate { .. }
+            | Self::Lock {
+                action: LockAction::Merge { .. } | LockAction::Convert,
+            }
+            | Self::Config {
+                action: ConfigAction::Validate,
+            }
+            | Self::Tree
+            | Self::Lint { .. }
+            | Self::Verify(_)
+            | Self::Audit
+            | Self::Licenses
+            | Self::Sbom(_)
+            | Self::Trust { .. } => Format::Text,
+            #[cfg(feature = "gitlab")]
+            Self::Gitlab { .. } => Format::Text,
+        }
+    }
+
+    /// Whether the command only makes sense inside a repository with a `.github` folder.
+    #[must_use]
+    pub fn needs_repo(&self) -> bool {
+        match self {
+            Self::Auth { .. }
+            | Self::Cache { .. }
+            | Self::Config {
+                action: ConfigAction::Schema,
+            } => false,
+            #[cfg(feature = "gitlab")]
+            Self::Gitlab { .. } => false,
+            Self::Config {
+                action: ConfigAction::Validate,
+            }
+            | Self::Tidy { .. }
+            | Self::Init
+            | Self::Upgrade(_)
+            | Self::Apply { .. }
+            | Self::Undo
+            | Self::CleanBackups
+            | Self::Hook { .. }
+            | Self::Import { .. }
+            | Self::Export { .. }
+            | Self::Migrate { .. }
+            | Self::Lock { .. }
+            | Self::Tree
+            | Self::Lint { .. }
+            | Self::Verify(_)
+            | Self::Audit
+            | Self::Licenses
+            | Self::Sbom(_)
+            | Self::Trust { .. }
+            | Self::Hash { .. } => true,
+        }
+    }
+
+    /// Whether the command also runs in each sub-project that keeps its own manifest under
+    /// `[projects]`. Applying a plan stays with the manifest the plan was saved from.
+    #[must_use]
+    pub fn covers_projects(&self) -> bool {
+        match self {
+            Self::Tidy { .. }
+            | Self::Init
+            | Self::Upgrade(_)
+            | Self::Undo
+            | Self::CleanBackups
+            | Self::Tree
+            | Self::Lint { .. }
+            | Self::Verify(_)
+            | Self::Audit
+            | Self::Licenses
+            | Self::Hash { .. } => true,
+            Self::Apply { .. }
+            | Self::Auth { .. }
+            | Self::Cache { .. }
+            | Self::Hook { .. }
+            | Self::Import { .. }
+            | Self::Export { .. }
+            | Self::Migrate { .. }
+            | Self::Sbom(_)
+            | Self::Trust { .. }
+            | Self::Lock { .. }
+            | Self::Config { .. } => false,
+            #[cfg(feature = "gitlab")]
+            Self::Gitlab { .. } => false,
+        }
+    }
+}
+
+/// The `gx tidy` run the flags of `Commands::Tidy` ask for.
+#[must_use]
+pub fn tidy_command(
+    commit: bool,
+    commit_message: Option<String>,
+    fix: bool,
+    check: bool,
+    changes: ChangesArgs,
+) -> tidy::Tidy {
+    tidy::Tidy {
+        commit_message: commit
+            .then(|| commit_message.unwrap_or_else(|| tidy::DEFAULT_COMMIT_MESSAGE.to_owned())),
+        fix,
+        changes: changes.into_changes(),
+        check,
+    }
+}
+
+/// Flags that limit a command to the workflow files changed in git, for pre-commit hooks.
+#[derive(Clone, clap::Args)]
+pub struct ChangesArgs {
+    /// Only the workflows staged in git.
+    #[arg(long, conflicts_with = "changed")]
+    staged: bool,
+    /// Only the workflows that differ from REF, such as `origin/main` (default: HEAD).
+    #[arg(
+        long,
+        value_name = "REF",
+        num_args = 0..=1,
+        default_missing_value = "HEAD"
+    )]
+    changed: Option<String>,
+}
+
+impl ChangesArgs {
+    /// The changes the command is limited to, if any.
+    #[must_use]
+    pub fn into_changes(self) -> Option<Changes> {
+        if self.staged {
+            Some(Changes::Staged)
+        } else {
+            self.changed.map(Changes::Since)
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use super::report::Finding;
+use crate::domain::action::identity::{ActionId, CommitSha, Version};
+use crate::domain::action::specifier::parse_semver;
+use crate::infra::github::Advisory;
+
+/// The findings of `advisories` for the action `id` locked to `version` at `sha`: an
+/// advisory whose description names the locked commit, as for a compromised tag, or
+/// whose affected range holds the locked version.
+pub(super) fn findings(
+    id: &ActionId,
+    version: &Version,
+    sha: &CommitSha,
+    advisories: &[Advisory],
+) -> Vec<Finding> {
+    let repository = id.base_repo();
+    let locked = parse_semver(version.as_str());
+    let mut found = Vec::new();
+    for advisory in advisories {
+        let names_commit = advisory
+            .description
+            .as_deref()
+            .is_some_and(|description| description.contains(sha.as_str()));
+        let affected = advisory
+            .vulnerabilities
+            .iter()
+            .filter(|vulnerability| {
+                vulnerability.package.as_ref().is_some_and(|package| {
+                    package.name.eq_ignore_ascii_case(repository.as_str())
+                        || package.name.eq_ignore_ascii_case(id.as_str())
+                })
+            })
+            .find(|vulnerability| {
+                names_commit
+                    || vulnerability
+                        .vulnerable_version_range
+                        .as_deref()
+                        .zip(locked.as_ref())
+                        .is_some_and(|(range, semver)| in_range(range, semver))
+            });
+        if !names_commit && affected.is_none() {
+            continue;
+        }
+        let patched =
+            affected.and_then(|vulnerability| vulnerability.first_patched_version.clone());
+        found.push(Finding {
+            action: format!("{id}@{version}"),
+            sha: sha.to_string(),
+            advisory: advisory.ghsa_id.clone(),
+            severity: advisory.severity.clone(),
+            summary: advisory.summary.clone(),
+            url: advisory.html_url.clone(),
+            remedy: remedy(id, names_commit, patched.as_deref()),
+        });
+    }
+    found
+}
+
+/// Whether `version` lies in an advisory range such as `>= 1.0.0, < 45.0.8`.
+fn in_range(range: &str, version: &semver::Version) -> bool {
+    semver::VersionReq::parse(range).is_ok_and(|req| req.matches(version))
+}
+
+/// What to do about a finding for `id`.
+fn remedy(id: &ActionId, names_commit: bool, patched: Option<&str>) -> String {
+    match (names_commit, patched) {
+        (true, _) => format!(
+            "the locked commit is named in the advisory; re-pin with `gx upgrade {id}` or remove the action"
+        ),
+        (false, Some(fixed)) => {
+            format!("fixed in {fixed}; upgrade with `gx upgrade {id}`")
+        }
+        (false, None) => "no fixed version yet; replace or remove the action".to_owned(),
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    clippy::indexing_slicing,
+    reason = "tests use unwrap and indexing freely"
+)]
+mod tests {
+    use super::findings;
+    use crate::domain::action::identity::{ActionId, CommitSha, Version};
+    use crate::infra::github::Advisory;
+
+    const SHA: &str = "0e58ed8671d6b60d0890c21b07f8835ace038e67";
+
+    fn advisory(description: &str, range: &str, patched: Option<&str>) -> Advisory {
+        serde_json::from_value(serde_json::json!({
+            "ghsa_id": "GHSA-mrrh-fwg8-r2c3",
+            "html_url": "https://github.com/advisories/GHSA-mrrh-fwg8-r2c3",
+            "summary": "tj-actions changed-files through 45.0.7 allows remote attackers to discover secrets",
+            "description": description,
+            "severity": "high",
+            "vulnerabilities": [{
+                "package": { "ecosystem": "actions", "name": "tj-actions/changed-files" },
+                "vulnerable_version_range": range,
+                "first_patched_version": patched,
+            }],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn a_locked_version_in_the_affected_range_is_reported_with_the_fix() {
+        let id = ActionId::from("tj-actions/changed-files");
+        let advisories = [advisory("", "<= 45.0.7", Some("46.0.1"))];
+
+        let found = findings(
+            &id,
+            &Version::from("v45.0.7"),
+            &CommitSha::from(SHA),
+            &advisories,
+        );
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].advisory, "GHSA-mrrh-fwg8-r2c3");
+        assert!(found[0].remedy.starts_with("fixed in 46.0.1"));
+
+        let fixed = findings(
+            &id,
+            &Version::from("v46.0.1"),
+            &CommitSha::from(SHA),
+            &advisories,
+        );
+        assert!(fixed.is_empty());
+        let other = ActionId::from("actions/checkout");
+        assert!(
+            findings(
+                &other,
+                &Version::from("v4"),
+                &CommitSha::from(SHA),
+                &advisories
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn a_locked_commit_the_advisory_names_is_reported_whatever_its_version() {
+        let id = ActionId::from("tj-actions/changed-files");
+        let advisories = [advisory(
+            &format!("The tags were moved to the malicious commit {SHA}."),
+            ">= 1.0.0, < 2.0.0",
+            None,
+        )];
+
+        let found = findings(
+            &id,
+            &Version::from("v46"),
+            &CommitSha::from(SHA),
+            &advisories,
+        );
+
+        assert_eq!(found.len(), 1);
+        assert!(found[0].remedy.contains("named in the advisory"));
+    }
+}
@@ -0,0 +1,80 @@
+use super::advisory::findings;
+use super::report::Report;
+use super::scorecard::scores;
+use crate::command::Command;
+use crate::config::Config;
+use crate::infra::github::{Error as GithubError, Registry as GithubRegistry};
+use crate::infra::scorecard::{Client as ScorecardClient, Error as ScorecardError};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use thiserror::Error;
+
+/// Errors that can occur during `gx audit`.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("gx audit reads the GitHub Advisory Database, which --offline disables")]
+    Offline,
+    #[error(transparent)]
+    Registry(#[from] GithubError),
+    #[error(transparent)]
+    Scorecard(#[from] ScorecardError),
+}
+
+/// `gx audit`: check every action gx.lock pins against the GitHub Advisory Database,
+/// reporting the locked versions an advisory's range covers and the locked commits an
+/// advisory names, and failing when there are any. The Scorecard of each
+/// action's repository is listed too, checked against `[audit] min_score`.
+pub struct Audit;
+
+impl Command for Audit {
+    type Report = Report;
+    type Error = Error;
+
+    fn run(
+        &self,
+        _repo_root: &Path,
+        config: Config,
+        on_progress: &mut dyn FnMut(&str),
+    ) -> Result<Report, Error> {
+        if config.settings.offline {
+            return Err(Error::Offline);
+        }
+        let registry = GithubRegistry::from_settings(&config.settings)?;
+        let mut locked: Vec<_> = config
+            .lock
+            .entries()
+            .map(|(spec, entry)| {
+                (
+                    spec.id.clone(),
+                    entry.version.clone(),
+                    entry.commit.sha.clone(),
+                )
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        locked.sort_by(|a, b| (a.0.as_str(), a.1.as_str()).cmp(&(b.0.as_str(), b.1.as_str())));
+        let mut advisories = HashMap::new();
+        let mut report = Report {
+            checked: locked.len(),
+            min_score: config.audit.min_score,
+            ..Report::default()
+        };
+        let mut repositories = Vec::new();
+        for (id, version, sha) in &locked {
+            let repository = id.base_repo();
+            if !advisories.contains_key(&repository) {
+                on_progress(&format!("Checking advisories for {repository}..."));
+                let found = registry.advisories(&repository)?;
+                advisories.insert(repository.clone(), found);
+                repositories.push(repository.clone());
+            }
+            let known = advisories.get(&repository).map_or(&[][..], Vec::as_slice);
+            report.findings.extend(findings(id, version, sha, known));
+        }
+        let client = ScorecardClient::from_settings(&config.settings)?;
+        (report.scores, report.unscored) =
+            scores(&client, &repositories, &config.audit, on_progress);
+        Ok(report)
+    }
+}
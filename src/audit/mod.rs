@@ -0,0 +1,11 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// Matching locked actions against advisories.
+mod advisory;
+/// Audit command: error types, struct, and `Command` implementation.
+mod command;
+pub mod report;
+/// Reading the Scorecard of each action's repository.
+mod scorecard;
+
+pub use command::{Audit, Error};
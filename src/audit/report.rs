@@ -0,0 +1,135 @@
+use crate::command::CommandReport;
+use crate::config::Level;
+use crate::output::lines::Line as OutputLine;
+
+/// A locked action an advisory affects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// The action and its locked version, as `owner/repo@v1.2.3`.
+    pub action: String,
+    /// The locked commit.
+    pub sha: String,
+    /// The advisory's GHSA identifier.
+    pub advisory: String,
+    /// The advisory's severity.
+    pub severity: String,
+    /// The advisory's summary.
+    pub summary: String,
+    /// The advisory's web URL.
+    pub url: String,
+    /// What to do about it.
+    pub remedy: String,
+}
+
+/// The Scorecard result of an action's repository.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    /// The repository, as `owner/repo`.
+    pub repository: String,
+    /// Its aggregate score, from 0 to 10.
+    pub score: f64,
+    /// The checks scoring below half marks, as `Name score`, lowest first.
+    pub failing: Vec<String>,
+    /// The level of the diagnostic when it scores below `[audit] min_score`.
+    pub below: Option<Level>,
+}
+
+/// Report from `gx audit`.
+#[derive(Debug, Default)]
+pub struct Report {
+    /// How many locked actions were checked.
+    pub checked: usize,
+    /// The advisories that affect them.
+    pub findings: Vec<Finding>,
+    /// The Scorecard result of each of their repositories that has one.
+    pub scores: Vec<Score>,
+    /// The repositories without a Scorecard result, with why.
+    pub unscored: Vec<(String, String)>,
+    /// The minimum score `[audit]` sets, if any.
+    pub min_score: Option<f64>,
+}
+
+impl CommandReport for Report {
+    fn render(&self) -> Vec<OutputLine> {
+        let mut lines: Vec<OutputLine> = self
+            .findings
+            .iter()
+            .map(|finding| OutputLine::Warning {
+                message: format!(
+                    "{} ({}) is affected by {} ({}): {}\n    {}\n    {}",
+                    finding.action,
+                    finding.sha,
+                    finding.advisory,
+                    finding.severity,
+                    finding.summary,
+                    finding.remedy,
+                    finding.url
+                ),
+            })
+            .collect();
+        if !self.scores.is_empty() {
+            lines.push(OutputLine::Table {
+                headers: vec![
+                    "repository".to_owned(),
+                    "scorecard".to_owned(),
+                    "failing checks".to_owned(),
+                ],
+                rows: self
+                    .scores
+                    .iter()
+                    .map(|score| {
+                        vec![
+                            score.repository.clone(),
+                            format!("{:.1}", score.score),
+                            score.failing.join(", "),
+                        ]
+                    })
+                    .collect(),
+            });
+        }
+        lines.extend(
+            self.unscored
+                .iter()
+                .map(|(repository, reason)| OutputLine::Skipped {
+                    action: repository.clone(),
+                    reason: reason.clone(),
+                }),
+        );
+        for score in &self.scores {
+            if let (Some(level), Some(min)) = (score.below, self.min_score) {
+                lines.push(OutputLine::LintDiag {
+                    level,
+                    workflow: None,
+                    line: None,
+                    rule: "scorecard".to_owned(),
+                    message: format!(
+                        "{} scores {:.1} on OpenSSF Scorecard, below the minimum of {min:.1}",
+                        score.repository, score.score
+                    ),
+                });
+            }
+        }
+        let text = if self.findings.is_empty() {
+            format!(
+                "No known advisories affect the {} locked action(s)",
+                self.checked
+            )
+        } else {
+            format!(
+                "{} advisory match(es) among {} locked action(s)",
+                self.findings.len(),
+                self.checked
+            )
+        };
+        lines.push(OutputLine::Summary { text });
+        lines
+    }
+
+    fn exit_code(&self) -> i32 {
+        let below_error = self
+            .scores
+            .iter()
+            .any(|score| score.below == Some(Level::Error));
+        i32::from(!self.findings.is_empty() || below_error)
+    }
+}
@@ -0,0 +1,84 @@
+use super::report::Score;
+use crate::config::{Audit as Policy, Level};
+use crate::domain::action::identity::Repository;
+use crate::infra::scorecard::{Client, Scorecard};
+
+/// The Scorecard rows of `gx audit`: one per repository with a published result, and
+/// the repositories without one, or whose result could not be fetched, with why.
+pub(super) fn scores(
+    client: &Client,
+    repositories: &[Repository],
+    policy: &Policy,
+    on_progress: &mut dyn FnMut(&str),
+) -> (Vec<Score>, Vec<(String, String)>) {
+    let mut scores = Vec::new();
+    let mut unscored = Vec::new();
+    for repository in repositories {
+        on_progress(&format!("Reading the Scorecard of {repository}..."));
+        match client.result(repository) {
+            Ok(Some(scorecard)) => scores.push(score(repository, &scorecard, policy)),
+            Ok(None) => unscored.push((repository.to_string(), "not scored by OpenSSF".to_owned())),
+            Err(error) => unscored.push((repository.to_string(), error.to_string())),
+        }
+    }
+    (scores, unscored)
+}
+
+/// The row of `repository`, whose result is `scorecard`, with the level `policy` gives it
+/// when it scores below the minimum.
+fn score(repository: &Repository, scorecard: &Scorecard, policy: &Policy) -> Score {
+    let below = policy
+        .min_score
+        .filter(|min| scorecard.score < *min && policy.level != Level::Off)
+        .map(|_| policy.level);
+    Score {
+        repository: repository.to_string(),
+        score: scorecard.score,
+        failing: scorecard
+            .failing_checks()
+            .iter()
+            .map(|check| format!("{} {}", check.name, check.score))
+            .collect(),
+        below,
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::score;
+    use crate::config::{Audit, Level};
+    use crate::domain::action::identity::Repository;
+    use crate::infra::scorecard::Scorecard;
+
+    fn scorecard() -> Scorecard {
+        serde_json::from_value(serde_json::json!({
+            "score": 4.2,
+            "checks": [
+                { "name": "Maintained", "score": 10, "reason": "30 commits" },
+                { "name": "Code-Review", "score": 3, "reason": "few reviews" },
+                { "name": "Branch-Protection", "score": 0, "reason": "none" },
+                { "name": "Packaging", "score": -1, "reason": "not applicable" },
+            ],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn a_score_below_the_minimum_takes_the_configured_level() {
+        let repository = Repository::from("tj-actions/changed-files");
+        let policy = Audit {
+            min_score: Some(5.0),
+            level: Level::Error,
+        };
+
+        let row = score(&repository, &scorecard(), &policy);
+
+        assert_eq!(row.failing, ["Branch-Protection 0", "Code-Review 3"]);
+        assert_eq!(row.below, Some(Level::Error));
+        assert_eq!(
+            score(&repository, &scorecard(), &Audit::default()).below,
+            None
+        );
+    }
+}
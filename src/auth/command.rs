@@ -0,0 +1,88 @@
+use super::report::{Login as LoginReport, Status as StatusReport};
+use crate::command::Command;
+use crate::config::Config;
+use crate::infra::auth::device::{Error as DeviceError, Flow as DeviceFlow};
+use crate::infra::auth::{Chain, KeyringError, store};
+use crate::infra::github::Registry;
+use std::env;
+use std::path::Path;
+use thiserror::Error;
+
+/// Environment variable holding the client ID of the OAuth app used by `gx auth login`.
+pub const CLIENT_ID_VAR: &str = "GX_OAUTH_CLIENT_ID";
+
+/// Errors that can occur during the auth commands.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(
+        "gx auth login needs the client ID of a GitHub OAuth app with device flow enabled, which gx does not ship: pass --client-id or set {CLIENT_ID_VAR}, or run `gh auth login` and gx will use that token"
+    )]
+    ClientIdRequired,
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    Keyring(#[from] KeyringError),
+}
+
+/// `gx auth login`: get a token with the OAuth device flow and store it in the OS keyring.
+pub struct Login {
+    /// Client ID of the OAuth app; falls back to [`CLIENT_ID_VAR`].
+    pub client_id: Option<String>,
+}
+
+impl Command for Login {
+    type Report = LoginReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        _repo_root: &Path,
+        config: Config,
+        on_progress: &mut dyn FnMut(&str),
+    ) -> Result<LoginReport, Error> {
+        let client_id = self
+            .client_id
+            .clone()
+            .or_else(|| env::var(CLIENT_ID_VAR).ok())
+            .filter(|id| !id.is_empty())
+            .ok_or(Error::ClientIdRequired)?;
+        let host = config.settings.github_host();
+
+        let flow = DeviceFlow::new(&host, client_id)?;
+        let code = flow.request_code()?;
+        on_progress(&format!(
+            "Open {} and enter the code {}",
+            code.verification_uri, code.user_code
+        ));
+        let token = flow.wait_for_token(&code)?;
+        store(&host, &token)?;
+        Ok(LoginReport { host })
+    }
+}
+
+/// `gx auth status`: show which credential gx would use.
+pub struct Status;
+
+impl Command for Status {
+    type Report = StatusReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        _repo_root: &Path,
+        config: Config,
+        on_progress: &mut dyn FnMut(&str),
+    ) -> Result<StatusReport, Error> {
+        on_progress("Looking for GitHub tokens...");
+        let host = config.settings.github_host();
+        let source = Chain::standard().resolve(&host).map(|(name, _)| name);
+        Ok(StatusReport {
+            host,
+            source,
+            scoped: config.settings.github_tokens.keys().cloned().collect(),
+            budget: Registry::from_settings(&config.settings)
+                .ok()
+                .and_then(|registry| registry.fetch_budget()),
+        })
+    }
+}
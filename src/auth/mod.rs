@@ -0,0 +1,7 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// Auth commands: error types, structs, and `Command` implementations.
+mod command;
+pub mod report;
+
+pub use command::{CLIENT_ID_VAR, Error, Login, Status};
@@ -0,0 +1,99 @@
+use crate::command::CommandReport;
+use crate::infra::github::Budget;
+use crate::output::lines::Line as OutputLine;
+
+/// Report from `gx auth login`.
+#[derive(Debug, Default)]
+pub struct Login {
+    /// The host the token was stored for.
+    pub host: String,
+}
+
+impl CommandReport for Login {
+    fn render(&self) -> Vec<OutputLine> {
+        vec![OutputLine::Summary {
+            text: format!("Logged in to {}; token stored in the OS keyring", self.host),
+        }]
+    }
+}
+
+/// Report from `gx auth status`.
+#[derive(Debug, Default)]
+pub struct Status {
+    /// The host the GitHub API belongs to.
+    pub host: String,
+    /// The provider the default token comes from; `None` when there is no token.
+    pub source: Option<String>,
+    /// Owners and hosts with their own token from `[registry.tokens]`.
+    pub scoped: Vec<String>,
+    /// The API rate limit budget of the default credential, when the API answered.
+    pub budget: Option<Budget>,
+}
+
+impl CommandReport for Status {
+    fn render(&self) -> Vec<OutputLine> {
+        let mut lines = vec![match &self.source {
+            Some(source) => OutputLine::Summary {
+                text: format!("{}: using token from {source}", self.host),
+            },
+            None => OutputLine::Warning {
+                message: format!(
+                    "{}: no token found, GitHub API requests are limited to 60 per hour",
+                    self.host
+                ),
+            },
+        }];
+        lines.extend(self.scoped.iter().map(|key| OutputLine::Detail {
+            text: format!("{key}: using token from [registry.tokens]"),
+        }));
+        lines.extend(self.budget.map(|budget| OutputLine::Detail {
+            text: format!("API rate limit: {budget}"),
+        }));
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Budget, CommandReport as _, OutputLine, Status};
+
+    #[test]
+    fn render_status_names_the_token_source() {
+        let report = Status {
+            host: "github.com".to_owned(),
+            source: Some("gh CLI".to_owned()),
+            scoped: vec!["my-org".to_owned()],
+            budget: Some(Budget {
+                limit: 5000,
+                remaining: 4990,
+                reset: 1_800_000_000,
+            }),
+        };
+        assert_eq!(
+            report.render(),
+            vec![
+                OutputLine::Summary {
+                    text: "github.com: using token from gh CLI".to_owned(),
+                },
+                OutputLine::Detail {
+                    text: "my-org: using token from [registry.tokens]".to_owned(),
+                },
+                OutputLine::Detail {
+                    text: "API rate limit: 4990/5000 requests left, resets at 08:00 UTC".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn render_status_warns_without_token() {
+        let report = Status {
+            host: "github.com".to_owned(),
+            ..Status::default()
+        };
+        assert!(matches!(
+            report.render().as_slice(),
+            [OutputLine::Warning { message }] if message.starts_with("github.com: no token found")
+        ));
+    }
+}
@@ -0,0 +1,65 @@
+use super::report::{Clean as CleanReport, Info as InfoReport};
+use crate::command::Command;
+use crate::config::Config;
+use crate::infra::cache::{clean, sections, user_dir};
+use crate::infra::github::RESOLUTION_TTL;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Errors that can occur during the cache commands.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("no cache directory: set XDG_CACHE_HOME or HOME")]
+    NoCacheDir,
+    #[error("failed to remove {}", path.display())]
+    Remove {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// `gx cache info`: show where the cache is and what it holds.
+pub struct Info;
+
+impl Command for Info {
+    type Report = InfoReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        _repo_root: &Path,
+        _config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<InfoReport, Error> {
+        let dir = user_dir().ok_or(Error::NoCacheDir)?;
+        Ok(InfoReport {
+            sections: sections(&dir),
+            dir,
+            ttl: RESOLUTION_TTL,
+        })
+    }
+}
+
+/// `gx cache clean`: remove every cached response and resolution.
+pub struct Clean;
+
+impl Command for Clean {
+    type Report = CleanReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        _repo_root: &Path,
+        _config: Config,
+        on_progress: &mut dyn FnMut(&str),
+    ) -> Result<CleanReport, Error> {
+        let dir = user_dir().ok_or(Error::NoCacheDir)?;
+        on_progress(&format!("Removing {}...", dir.display()));
+        let removed = clean(&dir).map_err(|source| Error::Remove {
+            path: dir.clone(),
+            source,
+        })?;
+        Ok(CleanReport { dir, removed })
+    }
+}
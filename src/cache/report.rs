@@ -0,0 +1,153 @@
+use crate::command::CommandReport;
+use crate::infra::cache::Usage;
+use crate::output::lines::Line as OutputLine;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Report from `gx cache info`.
+#[derive(Debug, Default)]
+pub struct Info {
+    /// The cache directory.
+    pub dir: PathBuf,
+    /// What each kind of entry takes up, by subdirectory name.
+    pub sections: Vec<(String, Usage)>,
+    /// How long a stored resolution is reused.
+    pub ttl: Duration,
+}
+
+impl CommandReport for Info {
+    fn render(&self) -> Vec<OutputLine> {
+        if self.sections.is_empty() {
+            return vec![OutputLine::Summary {
+                text: format!("Cache at {} is empty", self.dir.display()),
+            }];
+        }
+        let mut lines = vec![OutputLine::Summary {
+            text: format!("Cache at {}", self.dir.display()),
+        }];
+        lines.extend(
+            self.sections
+                .iter()
+                .map(|(name, usage)| OutputLine::Detail {
+                    text: format!("{name}: {}", describe(*usage)),
+                }),
+        );
+        lines.push(OutputLine::Detail {
+            text: format!(
+                "resolutions are reused for {} minutes",
+                self.ttl.as_secs() / 60
+            ),
+        });
+        lines
+    }
+}
+
+/// Report from `gx cache clean`.
+#[derive(Debug, Default)]
+pub struct Clean {
+    /// The cache directory.
+    pub dir: PathBuf,
+    /// What the removed entries took up.
+    pub removed: Usage,
+}
+
+impl CommandReport for Clean {
+    fn render(&self) -> Vec<OutputLine> {
+        vec![OutputLine::Summary {
+            text: format!(
+                "Removed {} from {}",
+                describe(self.removed),
+                self.dir.display()
+            ),
+        }]
+    }
+}
+
+/// `usage` for humans, e.g. `12 entries, 3.4 KiB`.
+fn describe(usage: Usage) -> String {
+    let size = match usage.bytes {
+        0..1_024 => format!("{} B", usage.bytes),
+        1_024..1_048_576 => format!("{} KiB", tenths(usage.bytes, 1_024)),
+        _ => format!("{} MiB", tenths(usage.bytes, 1_048_576)),
+    };
+    let noun = if usage.entries == 1 {
+        "entry"
+    } else {
+        "entries"
+    };
+    format!("{} {noun}, {size}", usage.entries)
+}
+
+/// `bytes` in `unit`s, rounded down to a tenth.
+fn tenths(bytes: u64, unit: u64) -> String {
+    let tenths = bytes
+        .saturating_mul(10)
+        .checked_div(unit)
+        .unwrap_or_default();
+    format!("{}.{}", tenths / 10, tenths % 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Clean, CommandReport as _, Info, OutputLine, Usage};
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn render_info_lists_each_section() {
+        let report = Info {
+            dir: PathBuf::from("/home/me/.cache/gx"),
+            sections: vec![
+                (
+                    "http".to_owned(),
+                    Usage {
+                        entries: 12,
+                        bytes: 3_500,
+                    },
+                ),
+                (
+                    "resolutions".to_owned(),
+                    Usage {
+                        entries: 1,
+                        bytes: 200,
+                    },
+                ),
+            ],
+            ttl: Duration::from_hours(1),
+        };
+        assert_eq!(
+            report.render(),
+            vec![
+                OutputLine::Summary {
+                    text: "Cache at /home/me/.cache/gx".to_owned(),
+                },
+                OutputLine::Detail {
+                    text: "http: 12 entries, 3.4 KiB".to_owned(),
+                },
+                OutputLine::Detail {
+                    text: "resolutions: 1 entry, 200 B".to_owned(),
+                },
+                OutputLine::Detail {
+                    text: "resolutions are reused for 60 minutes".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn render_clean_reports_what_was_removed() {
+        let report = Clean {
+            dir: PathBuf::from("/tmp/gx"),
+            removed: Usage {
+                entries: 3,
+                bytes: 2_097_152,
+            },
+        };
+        assert_eq!(
+            report.render(),
+            vec![OutputLine::Summary {
+                text: "Removed 3 entries, 2.0 MiB from /tmp/gx".to_owned(),
+            }]
+        );
+    }
+}
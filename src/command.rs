@@ -1,4 +1,5 @@
 use crate::config::Config;
+use crate::output::events::Event;
 use crate::output::lines::Line as OutputLine;
 use std::fmt::Debug;
 use std::path::Path;
@@ -12,6 +13,11 @@ pub trait CommandReport: Debug + Default {
     /// Render the report into output lines.
     fn render(&self) -> Vec<OutputLine>;
 
+    /// Machine-readable events for `--format ndjson`; defaults to none.
+    fn events(&self) -> Vec<Event> {
+        Vec::new()
+    }
+
     /// Exit code to use after rendering; defaults to `0`.
     fn exit_code(&self) -> i32 {
         0
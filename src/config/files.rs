@@ -0,0 +1,23 @@
+use crate::infra::lock::FileFormat;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The `[files]` section of `gx.toml`: where gx keeps its files, and how it treats the
+/// files it rewrites.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Files {
+    /// Save a `.bak` copy of gx.toml, gx.lock, and each workflow before a run rewrites it,
+    /// as `--backup` does.
+    #[serde(default)]
+    pub backup: bool,
+    /// The manifest gx reads instead of this file, relative to the repository root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<PathBuf>,
+    /// The lock file, relative to the repository root; `gx.lock` beside the manifest
+    /// when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock: Option<PathBuf>,
+    /// The format gx writes the lock in; a lock in either format is read.
+    #[serde(default)]
+    pub lock_format: FileFormat,
+}
@@ -0,0 +1,487 @@
+use crate::domain::manifest::aliases::Aliases;
+use crate::domain::workflow_actions::WorkflowPath;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Severity level for a lint rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    /// Rule violation is an error.
+    Error,
+    /// Rule violation is a warning.
+    Warn,
+    /// Rule is disabled.
+    Off,
+}
+
+/// Ignore target for a lint rule: action, workflow, and/or job.
+/// All specified keys must match for the ignore to apply (intersection semantics).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IgnoreTarget {
+    /// Action ID (e.g., "actions/checkout").
+    pub action: Option<String>,
+    /// Workflow file path (e.g., ".github/workflows/ci.yml").
+    pub workflow: Option<String>,
+    /// Job name within a workflow.
+    pub job: Option<String>,
+}
+
+/// Configuration for a single lint rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rule {
+    /// Severity level (error, warn, off).
+    pub level: Level,
+    /// Targets to ignore (intersection semantics).
+    #[serde(default)]
+    pub ignore: Vec<IgnoreTarget>,
+}
+
+/// The `[audit]` section of `gx.toml`: the Scorecard score below which
+/// `gx audit` reports the repository of an action as a diagnostic, and at what level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Audit {
+    /// The lowest acceptable score, from 0 to 10; no score is reported when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_score: Option<f64>,
+    /// The level of a repository scoring below `min_score`; `error` fails `gx audit`.
+    #[serde(default = "Audit::default_level")]
+    pub level: Level,
+}
+
+impl Audit {
+    /// Repositories scoring below `min_score` are warnings unless configured otherwise.
+    const fn default_level() -> Level {
+        Level::Warn
+    }
+}
+
+impl Default for Audit {
+    fn default() -> Self {
+        Self {
+            min_score: None,
+            level: Self::default_level(),
+        }
+    }
+}
+
+/// The `[licenses]` section of `gx.toml`: the licenses `gx licenses` reports the actions
+/// under as a diagnostic, and at what level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Licenses {
+    /// SPDX identifiers of denied licenses, such as `AGPL-3.0`. An identifier also denies
+    /// its variants: `AGPL` denies `AGPL-3.0`, and `AGPL-3.0` denies `AGPL-3.0-only`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    /// The level of an action under a denied license; `error` fails `gx licenses`.
+    #[serde(default = "Licenses::default_level")]
+    pub level: Level,
+}
+
+impl Licenses {
+    /// Actions under a denied license are errors unless configured otherwise.
+    const fn default_level() -> Level {
+        Level::Error
+    }
+
+    /// The entry of `deny` that denies the license `spdx_id`, compared case-insensitively.
+    #[must_use]
+    pub fn denied_by(&self, spdx_id: &str) -> Option<&str> {
+        let license = spdx_id.to_ascii_lowercase();
+        self.deny.iter().map(String::as_str).find(|entry| {
+            license
+                .strip_prefix(&entry.to_ascii_lowercase())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+        })
+    }
+}
+
+impl Default for Licenses {
+    fn default() -> Self {
+        Self {
+            deny: Vec::new(),
+            level: Self::default_level(),
+        }
+    }
+}
+
+/// Configuration for all lint rules.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Lint {
+    /// Per-rule configuration, keyed by rule name.
+    #[serde(default)]
+    pub rules: BTreeMap<crate::lint::RuleName, Rule>,
+    /// Per-path level overrides, keyed by a workflow glob pattern
+    /// (e.g., `"workflows/experimental-*.yml"`).
+    #[serde(default)]
+    pub paths: BTreeMap<String, BTreeMap<crate::lint::RuleName, Level>>,
+}
+
+impl Lint {
+    /// Get the effective configuration for a rule, applying defaults if not explicitly configured.
+    /// Each rule has its own default level; unconfigured rules use their defaults.
+    #[must_use]
+    pub fn get_rule(&self, name: crate::lint::RuleName, default_level: Level) -> Rule {
+        self.rules.get(&name).cloned().unwrap_or(Rule {
+            level: default_level,
+            ignore: Vec::new(),
+        })
+    }
+
+    /// Effective level for a rule in a given workflow.
+    ///
+    /// Among the `[lint.paths]` patterns that match the workflow and set this rule, the
+    /// most specific one wins: the pattern with the most literal characters, i.e. those
+    /// outside `*`, `?` and `[...]`. So `workflows/*-nightly.yml` beats `workflows/*.yml`
+    /// wherever both are written in `gx.toml`; equally specific patterns fall back to the
+    /// one that sorts last. A workflow with no matching pattern, or no workflow at all,
+    /// gets the rule's global level.
+    #[must_use]
+    pub fn level_for(
+        &self,
+        name: crate::lint::RuleName,
+        default_level: Level,
+        workflow: Option<&str>,
+    ) -> Level {
+        let global = self.get_rule(name, default_level).level;
+        let Some(path) = workflow.map(WorkflowPath::new) else {
+            return global;
+        };
+        self.paths
+            .iter()
+            .filter(|(pattern, _)| path.matches(pattern))
+            .filter_map(|(pattern, levels)| Some((pattern, *levels.get(&name)?)))
+            .max_by_key(|(pattern, _)| specificity(pattern))
+            .map_or(global, |(_, level)| level)
+    }
+
+    /// True when the rule can emit diagnostics anywhere: either its global level is not
+    /// `off`, or some `[lint.paths]` pattern turns it on.
+    #[must_use]
+    pub fn is_active(&self, name: crate::lint::RuleName, default_level: Level) -> bool {
+        self.get_rule(name, default_level).level != Level::Off
+            || self
+                .paths
+                .values()
+                .any(|levels| levels.get(&name).is_some_and(|l| *l != Level::Off))
+    }
+
+    /// Replace each ignore target's action alias with the action it names.
+    pub fn expand_aliases(&mut self, aliases: &Aliases) {
+        let targets = self.rules.values_mut().flat_map(|rule| &mut rule.ignore);
+        for action in targets.filter_map(|target| target.action.as_mut()) {
+            aliases.expand(action).as_str().clone_into(action);
+        }
+    }
+}
+
+/// Number of literal characters in a glob pattern, the measure [`Lint::level_for`] uses to
+/// rank overlapping `[lint.paths]` patterns.
+fn specificity(pattern: &str) -> usize {
+    let mut in_class = false;
+    pattern
+        .chars()
+        .filter(|c| match c {
+            '[' => {
+                in_class = true;
+                false
+            }
+            ']' if in_class => {
+                in_class = false;
+                false
+            }
+            '*' | '?' => false,
+            _ => !in_class,
+        })
+        .count()
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    clippy::indexing_slicing,
+    reason = "tests use unwrap, indexing, and other patterns freely"
+)]
+mod tests {
+    use super::{IgnoreTarget, Level, Licenses, Lint, Rule};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct LevelWrapper {
+        level: Level,
+    }
+
+    #[test]
+    fn level_deserializes_from_string() {
+        assert_eq!(
+            toml::from_str::<LevelWrapper>("level = \"error\"")
+                .unwrap()
+                .level,
+            Level::Error
+        );
+        assert_eq!(
+            toml::from_str::<LevelWrapper>("level = \"warn\"")
+                .unwrap()
+                .level,
+            Level::Warn
+        );
+        assert_eq!(
+            toml::from_str::<LevelWrapper>("level = \"off\"")
+                .unwrap()
+                .level,
+            Level::Off
+        );
+    }
+
+    #[test]
+    fn level_rejects_invalid_values() {
+        assert!(toml::from_str::<LevelWrapper>("level = \"invalid\"").is_err());
+    }
+
+    #[test]
+    fn rule_config_parses_with_level_only() {
+        let toml_str = r#"
+            level = "error"
+        "#;
+        let config: Rule = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.level, Level::Error);
+        assert!(config.ignore.is_empty());
+    }
+
+    #[test]
+    fn rule_config_parses_with_ignore_targets() {
+        let toml_str = r#"
+            level = "warn"
+            ignore = [
+                { action = "actions/checkout" },
+                { workflow = ".github/workflows/ci.yml" },
+            ]
+        "#;
+        let config: Rule = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.level, Level::Warn);
+        assert_eq!(config.ignore.len(), 2);
+        assert_eq!(config.ignore[0].action, Some("actions/checkout".to_owned()));
+        assert_eq!(
+            config.ignore[1].workflow,
+            Some(".github/workflows/ci.yml".to_owned())
+        );
+    }
+
+    #[test]
+    fn ignore_target_with_intersection() {
+        let toml_str = r#"
+action = "actions/checkout"
+workflow = ".github/workflows/ci.yml"
+job = "build"
+        "#;
+        let target: IgnoreTarget = toml::from_str(toml_str).unwrap();
+        assert_eq!(target.action, Some("actions/checkout".to_owned()));
+        assert_eq!(target.workflow, Some(".github/workflows/ci.yml".to_owned()));
+        assert_eq!(target.job, Some("build".to_owned()));
+    }
+
+    #[test]
+    fn lint_config_parses_multiple_rules() {
+        let toml_str = r#"
+            [rules]
+            sha-mismatch = { level = "error" }
+            unpinned = { level = "error", ignore = [{ action = "actions/internal-tool" }] }
+            stale-comment = { level = "off" }
+        "#;
+        let config: Lint = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.rules.len(), 3);
+        assert_eq!(
+            config.rules[&crate::lint::RuleName::ShaMismatch].level,
+            Level::Error
+        );
+        assert_eq!(
+            config.rules[&crate::lint::RuleName::Unpinned].level,
+            Level::Error
+        );
+        assert_eq!(
+            config.rules[&crate::lint::RuleName::Unpinned].ignore.len(),
+            1
+        );
+        assert_eq!(
+            config.rules[&crate::lint::RuleName::StaleComment].level,
+            Level::Off
+        );
+    }
+
+    #[test]
+    fn ignore_targets_name_actions_by_alias() {
+        let mut config: Lint = toml::from_str(
+            r#"
+            [rules.unpinned]
+            level = "error"
+            ignore = [{ action = "checkout" }, { action = "actions/cache" }]
+            "#,
+        )
+        .unwrap();
+        let aliases = [("checkout", "actions/checkout")].into_iter().collect();
+
+        config.expand_aliases(&aliases);
+
+        let ignore = &config.rules[&crate::lint::RuleName::Unpinned].ignore;
+        assert_eq!(ignore[0].action.as_deref(), Some("actions/checkout"));
+        assert_eq!(ignore[1].action.as_deref(), Some("actions/cache"));
+    }
+
+    #[test]
+    fn lint_config_default_is_empty() {
+        let config = Lint::default();
+        assert!(config.rules.is_empty());
+    }
+
+    #[test]
+    fn lint_config_get_rule_uses_default_when_unconfigured() {
+        let config = Lint::default();
+        let rule = config.get_rule(crate::lint::RuleName::ShaMismatch, Level::Error);
+        assert_eq!(rule.level, Level::Error);
+        assert!(rule.ignore.is_empty());
+    }
+
+    #[test]
+    fn lint_config_get_rule_returns_configured_value() {
+        let mut config = Lint::default();
+        config.rules.insert(
+            crate::lint::RuleName::Unpinned,
+            Rule {
+                level: Level::Warn,
+                ignore: vec![IgnoreTarget {
+                    action: Some("actions/checkout".to_owned()),
+                    workflow: None,
+                    job: None,
+                }],
+            },
+        );
+        let rule = config.get_rule(crate::lint::RuleName::Unpinned, Level::Error);
+        assert_eq!(rule.level, Level::Warn);
+        assert_eq!(rule.ignore.len(), 1);
+    }
+
+    #[test]
+    fn lint_config_get_rule_respects_off_level() {
+        let mut config = Lint::default();
+        config.rules.insert(
+            crate::lint::RuleName::StaleComment,
+            Rule {
+                level: Level::Off,
+                ignore: vec![],
+            },
+        );
+        let rule = config.get_rule(crate::lint::RuleName::StaleComment, Level::Warn);
+        assert_eq!(rule.level, Level::Off);
+    }
+
+    #[test]
+    fn level_for_applies_matching_path_override() {
+        let config: Lint = toml::from_str(
+            r#"
+            [rules]
+            unpinned = { level = "error" }
+
+            [paths."workflows/experimental-*.yml"]
+            unpinned = "warn"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.level_for(
+                crate::lint::RuleName::Unpinned,
+                Level::Error,
+                Some(".github/workflows/experimental-build.yml")
+            ),
+            Level::Warn
+        );
+        assert_eq!(
+            config.level_for(
+                crate::lint::RuleName::Unpinned,
+                Level::Error,
+                Some(".github/workflows/ci.yml")
+            ),
+            Level::Error
+        );
+        assert_eq!(
+            config.level_for(crate::lint::RuleName::Unpinned, Level::Error, None),
+            Level::Error
+        );
+    }
+
+    #[test]
+    fn level_for_star_does_not_cross_directories() {
+        let config: Lint = toml::from_str(
+            r#"
+            [paths."workflows/*.yml"]
+            unpinned = "off"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.level_for(
+                crate::lint::RuleName::Unpinned,
+                Level::Error,
+                Some(".github/workflows/vendor/ci.yml")
+            ),
+            Level::Error
+        );
+    }
+
+    #[test]
+    fn level_for_prefers_the_most_specific_pattern_over_key_order() {
+        // Written broad-first, but `*.yml` sorts after `*-nightly.yml`.
+        let config: Lint = toml::from_str(
+            r#"
+            [paths."workflows/*.yml"]
+            unpinned = "off"
+
+            [paths."workflows/*-nightly.yml"]
+            unpinned = "warn"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.level_for(
+                crate::lint::RuleName::Unpinned,
+                Level::Error,
+                Some(".github/workflows/build-nightly.yml")
+            ),
+            Level::Warn
+        );
+        assert_eq!(
+            config.level_for(
+                crate::lint::RuleName::Unpinned,
+                Level::Error,
+                Some(".github/workflows/ci.yml")
+            ),
+            Level::Off
+        );
+    }
+
+    #[test]
+    fn is_active_when_only_a_path_enables_the_rule() {
+        let config: Lint = toml::from_str(
+            r#"
+            [rules]
+            missing-concurrency = { level = "off" }
+
+            [paths."workflows/release.yml"]
+            missing-concurrency = "error"
+        "#,
+        )
+        .unwrap();
+        assert!(config.is_active(crate::lint::RuleName::MissingConcurrency, Level::Warn));
+        assert!(!config.is_active(crate::lint::RuleName::StaleComment, Level::Off));
+    }
+
+    #[test]
+    fn denied_licenses_cover_their_variants() {
+        let licenses: Licenses = toml::from_str(r#"deny = ["agpl", "GPL-3.0"]"#).unwrap();
+        assert_eq!(licenses.level, Level::Error);
+        assert_eq!(licenses.denied_by("AGPL-3.0"), Some("agpl"));
+        assert_eq!(licenses.denied_by("GPL-3.0-or-later"), Some("GPL-3.0"));
+        assert_eq!(licenses.denied_by("LGPL-3.0"), None);
+        assert_eq!(licenses.denied_by("GPL-3.01"), None);
+        assert_eq!(licenses.denied_by("MIT"), None);
+    }
+}
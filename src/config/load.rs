@@ -0,0 +1,349 @@
+use super::{Audit, Files, Licenses, Lint, Projects, Settings, Workflows};
+use crate::domain::lock::Lock;
+use crate::domain::manifest::Manifest;
+use crate::infra::lock::{
+    Error as LockFileError, LOCK_FILE_NAME, LOCK_FILE_VERSION, Store as LockStore,
+};
+use crate::infra::manifest::{
+    Document as ManifestDocument, Error as ManifestError, MANIFEST_FILE_NAME, combined_hash, hash,
+    parse_audit_config, parse_files_config, parse_licenses_config, parse_lint_config,
+    parse_projects_config, parse_registry_config, parse_workflows_config, unknown_keys,
+};
+use crate::infra::trust::TRUST_FILE_NAME;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Errors that can occur when loading configuration.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The manifest file cannot be parsed.
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+
+    /// The lock file cannot be parsed.
+    #[error(transparent)]
+    Lock(#[from] LockFileError), // LockFileError is now crate::infra::lock::Error
+
+    /// The manifest has keys gx does not know, and `--strict` forbids them.
+    #[error("{} has unknown keys: {}", path.display(), keys.join(", "))]
+    UnknownKeys { path: PathBuf, keys: Vec<String> },
+}
+
+/// All application configuration, loaded once at startup.
+#[derive(Debug)]
+pub struct Config {
+    pub settings: Settings,
+    pub manifest: Manifest,
+    pub lock: Lock,
+    pub lint_config: Lint,
+    /// Which files are read and rewritten as workflows, from `[workflows]`.
+    pub workflows: Workflows,
+    /// How rewritten files are treated, from `[files]` and `--backup`.
+    pub files: Files,
+    /// The Scorecard policy of `gx audit`, from `[audit]`.
+    pub audit: Audit,
+    /// The license denylist of `gx licenses`, from `[licenses]`.
+    pub licenses: Licenses,
+    /// Monorepo sub-projects, from `[projects]`. Shared ones are already part of
+    /// `workflows`.
+    pub projects: Projects,
+    pub manifest_path: PathBuf,
+    pub lock_path: PathBuf,
+    /// Whether the manifest was auto-migrated from v1 format on load.
+    pub manifest_migrated: bool,
+    /// Whether the lock was migrated from an older schema on load.
+    pub lock_migrated: bool,
+    /// The keys of the manifest gx does not know, such as a misspelled `lint.rulse`.
+    pub unknown_keys: Vec<String>,
+    /// The hashes of the other manifests sharing the lock, under `manifest = "shared-lock"`.
+    pub shared_lock_hashes: Vec<String>,
+}
+
+impl Config {
+    /// Load all configuration: settings from env, manifest and lock from disk.
+    /// The files are found as [`locate`] describes, and the `[registry]`, `[lint]`, and
+    /// `[files]` of the user's `~/.config/gx/config.toml` are defaults under the manifest's.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Manifest`] if the manifest file cannot be parsed.
+    /// Returns [`ConfigError::Lock`] if the lock file cannot be parsed.
+    pub fn load(repo_root: &Path) -> Result<Self, Error> {
+        let (manifest_path, lock_path, document) = locate(repo_root)?;
+        let parsed_manifest = crate::infra::manifest::parse(&manifest_path)?;
+        let lock_store = LockStore::new(&lock_path);
+        let parsed_lock = lock_store.parse()?;
+        let mut settings = Settings::from_env().with_registry(parse_registry_config(&document));
+        settings.ca_bundle = settings.ca_bundle.map(|path| repo_root.join(path));
+        let projects = parse_projects_config(&document)?;
+        let workflows =
+            parse_workflows_config(&document)?.including(projects.shared_workflow_patterns());
+        let mut lint_config = parse_lint_config(&document)?;
+        lint_config.expand_aliases(parsed_manifest.value.aliases());
+        Ok(Self {
+            settings,
+            manifest: parsed_manifest.value,
+            manifest_migrated: parsed_manifest.migrated,
+            lock: parsed_lock.value,
+            lock_migrated: parsed_lock.migrated,
+            lint_config,
+            workflows,
+            files: parse_files_config(&document),
+            audit: parse_audit_config(&document),
+            licenses: parse_licenses_config(&document),
+            projects,
+            unknown_keys: unknown_keys(&document),
+            manifest_path,
+            lock_path,
+            shared_lock_hashes: Vec::new(),
+        })
+    }
+
+    /// Use the lock at `lock_path`, shared with the `others` manifests: its entries are
+    /// reloaded from there, those the others need are kept when pruning, and its manifest
+    /// hash covers them all.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Lock`] if the shared lock cannot be parsed.
+    pub fn share_lock(&mut self, lock_path: &Path, others: &[&Manifest]) -> Result<(), Error> {
+        let parsed_lock = LockStore::new(lock_path).parse()?;
+        self.lock = parsed_lock.value;
+        self.lock_migrated = parsed_lock.migrated;
+        self.lock
+            .share(others.iter().flat_map(|manifest| manifest.lock_keys()));
+        lock_path.clone_into(&mut self.lock_path);
+        self.shared_lock_hashes = others.iter().map(|manifest| hash(manifest)).collect();
+        Ok(())
+    }
+
+    /// The manifest hash to record in the lock, given `own`, the hash of this manifest:
+    /// `own` itself, or one hash of every manifest when the lock is shared.
+    #[must_use]
+    pub fn lock_hash(&self, own: String) -> String {
+        if self.shared_lock_hashes.is_empty() {
+            return own;
+        }
+        let mut all = self.shared_lock_hashes.clone();
+        all.push(own);
+        combined_hash(&all)
+    }
+
+    /// The store of the lock, writing the format `[files] lock_format` names, and
+    /// checking new commits against the trust database under `--trusted-only`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`LockStore::with_trust`].
+    pub fn lock_store(&self) -> Result<LockStore, LockFileError> {
+        LockStore::new(&self.lock_path)
+            .with_format(self.files.lock_format)
+            .with_trust(self.settings.trusted_only.then(|| self.trust_path()))
+    }
+
+    /// The trust database of `gx trust` and `--trusted-only`, gx-trust.toml beside the
+    /// manifest.
+    #[must_use]
+    pub fn trust_path(&self) -> PathBuf {
+        self.manifest_path.with_file_name(TRUST_FILE_NAME)
+    }
+
+    /// A progress message for each file that was migrated from an older format on load.
+    #[must_use]
+    pub fn migration_notices(&self) -> Vec<String> {
+        let mut notices = Vec::new();
+        if self.manifest_migrated {
+            notices.push("migrated gx.toml → semver specifiers".to_owned());
+        }
+        if self.lock_migrated {
+            notices.push(format!("migrated gx.lock → schema {LOCK_FILE_VERSION}"));
+        }
+        notices
+    }
+
+    /// A warning for each key of the manifest gx does not know.
+    #[must_use]
+    pub fn unknown_key_warnings(&self) -> Vec<String> {
+        self.unknown_keys
+            .iter()
+            .map(|key| {
+                format!(
+                    "{}: unknown key {key} is ignored",
+                    self.manifest_path.display()
+                )
+            })
+            .collect()
+    }
+
+    /// Fail when the manifest has keys gx does not know, as `--strict` asks.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownKeys`] listing them.
+    pub fn deny_unknown_keys(&self) -> Result<(), Error> {
+        if self.unknown_keys.is_empty() {
+            return Ok(());
+        }
+        Err(Error::UnknownKeys {
+            path: self.manifest_path.clone(),
+            keys: self.unknown_keys.clone(),
+        })
+    }
+}
+
+/// The manifest of the repository or project at `root`, found as [`locate`] describes.
+///
+/// # Errors
+///
+/// Returns [`Error::Manifest`] if the manifest file cannot be parsed.
+pub fn project_manifest(root: &Path) -> Result<Manifest, Error> {
+    let (manifest_path, _, _) = locate(root)?;
+    Ok(crate::infra::manifest::parse(&manifest_path)?.value)
+}
+
+/// Where the manifest and lock of the repository at `repo_root` are.
+///
+/// gx reads `.github/gx.toml`, or `gx.toml` at the root when there is none, and
+/// `.github/gx.toml` is where `gx init` creates one. The `manifest` and `lock` keys of its
+/// `[files]` section point elsewhere, relative to the root. The lock is otherwise
+/// `gx.lock` beside the manifest. The manifest is returned read, as a [`ManifestDocument`].
+fn locate(repo_root: &Path) -> Result<(PathBuf, PathBuf, ManifestDocument), Error> {
+    let default = repo_root.join(".github").join(MANIFEST_FILE_NAME);
+    let found = [default.clone(), repo_root.join(MANIFEST_FILE_NAME)]
+        .into_iter()
+        .find(|path| path.is_file())
+        .unwrap_or(default);
+    let found_document = ManifestDocument::read(&found)?;
+    let files = parse_files_config(&found_document);
+    let (manifest_path, lock, document) = match files.manifest {
+        Some(manifest) if repo_root.join(&manifest) != found => {
+            let manifest_path = repo_root.join(manifest);
+            let document = ManifestDocument::read(&manifest_path)?;
+            let lock = files.lock.or(parse_files_config(&document).lock);
+            (manifest_path, lock, document)
+        }
+        Some(_) | None => (found, files.lock, found_document),
+    };
+    let lock_path = lock.map_or_else(
+        || manifest_path.with_file_name(LOCK_FILE_NAME),
+        |path| repo_root.join(path),
+    );
+    Ok((manifest_path, lock_path, document))
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "tests use unwrap, indexing, and other patterns freely"
+)]
+mod tests {
+    use super::{
+        Audit, Config, Files, Licenses, Lint, Lock, Manifest, PathBuf, Projects, Workflows,
+    };
+    use crate::config::{GitHubToken, Settings};
+
+    #[test]
+    fn app_config_can_be_constructed_directly() {
+        let config = Config {
+            settings: Settings {
+                github_token: Some(GitHubToken::from("test_token".to_owned())),
+                ..Settings::default()
+            },
+            manifest: Manifest::default(),
+            lock: Lock::default(),
+            lint_config: Lint::default(),
+            workflows: Workflows::default(),
+            files: Files::default(),
+            audit: Audit::default(),
+            licenses: Licenses::default(),
+            projects: Projects::default(),
+            manifest_path: PathBuf::from("gx.toml"),
+            lock_path: PathBuf::from("gx.lock"),
+            manifest_migrated: false,
+            lock_migrated: false,
+            unknown_keys: Vec::new(),
+            shared_lock_hashes: Vec::new(),
+        };
+        assert_eq!(
+            config
+                .settings
+                .github_token
+                .as_ref()
+                .map(GitHubToken::as_str),
+            Some("test_token")
+        );
+    }
+
+    #[test]
+    fn app_config_load_returns_defaults_for_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        // No .github folder created — both files are missing
+        let config = Config::load(dir.path()).unwrap();
+        assert!(config.settings.github_token.is_none() || config.settings.github_token.is_some());
+        assert!(config.manifest.specs().next().is_none());
+        assert!(config.lock.is_empty());
+        assert!(config.manifest_path.ends_with("gx.toml"));
+        assert!(config.lock_path.ends_with("gx.lock"));
+    }
+
+    #[test]
+    fn shared_projects_add_their_workflows_to_the_root_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".github")).unwrap();
+        std::fs::write(
+            dir.path().join(".github/gx.toml"),
+            "[projects]\npaths = [\"vendor/lib\"]\n",
+        )
+        .unwrap();
+
+        let config = Config::load(dir.path()).unwrap();
+
+        let patterns = config.workflows.include_patterns();
+        assert!(patterns.contains(&".github/workflows/**/*.yml".to_owned()));
+        assert!(patterns.contains(&"vendor/lib/.github/workflows/**/*.yml".to_owned()));
+    }
+
+    #[test]
+    fn manifest_at_the_root_or_set_in_files_moves_both_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("gx.toml"), "[actions]\n").unwrap();
+
+        let at_root = Config::load(dir.path()).unwrap();
+        assert_eq!(at_root.manifest_path, dir.path().join("gx.toml"));
+        assert_eq!(at_root.lock_path, dir.path().join("gx.lock"));
+
+        std::fs::create_dir(dir.path().join(".github")).unwrap();
+        std::fs::write(
+            dir.path().join(".github/gx.toml"),
+            "[files]\nmanifest = \"gx.toml\"\nlock = \"locks/gx.lock\"\n",
+        )
+        .unwrap();
+
+        let redirected = Config::load(dir.path()).unwrap();
+        assert_eq!(redirected.manifest_path, dir.path().join("gx.toml"));
+        assert_eq!(redirected.lock_path, dir.path().join("locks/gx.lock"));
+    }
+
+    #[test]
+    fn unknown_keys_warn_and_fail_only_when_denied() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".github")).unwrap();
+        std::fs::write(
+            dir.path().join(".github/gx.toml"),
+            "[lint.rulse]\nunpinned = { level = \"off\" }\n",
+        )
+        .unwrap();
+
+        let config = Config::load(dir.path()).unwrap();
+
+        assert_eq!(config.unknown_keys, ["lint.rulse"]);
+        assert!(
+            config
+                .unknown_key_warnings()
+                .iter()
+                .all(|warning| warning.ends_with("unknown key lint.rulse is ignored"))
+        );
+        let error = config.deny_unknown_keys().unwrap_err();
+        assert!(error.to_string().contains("lint.rulse"), "{error}");
+    }
+}
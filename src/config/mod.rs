@@ -0,0 +1,24 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// How rewritten files are treated, from the `[files]` section of `gx.toml`.
+mod files;
+/// Lint rule configuration from the `[lint]` section of `gx.toml`, the Scorecard
+/// policy of `[audit]`, and the license denylist of `[licenses]`.
+mod lint;
+/// Loading the manifest, lock, lint configuration, and settings at startup.
+mod load;
+/// Monorepo sub-projects from the `[projects]` section of `gx.toml`.
+mod projects;
+/// The sections and keys `gx.toml` can hold.
+pub mod schema;
+/// Runtime settings: GitHub token, API URL, and the `[registry]` section.
+mod settings;
+/// Workflow discovery from the `[workflows]` section of `gx.toml`.
+mod workflows;
+
+pub use files::Files;
+pub use lint::{Audit, IgnoreTarget, Level, Licenses, Lint, Rule};
+pub use load::{Config, Error, project_manifest};
+pub use projects::{ManifestScope, Projects, pin_conflicts};
+pub use settings::{GitHubToken, Registry, Resolver, Retry, Settings};
+pub use workflows::Workflows;
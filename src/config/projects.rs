@@ -0,0 +1,170 @@
+use crate::domain::manifest::Manifest;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The `[projects]` section of `gx.toml`: sub-projects of a monorepo that keep their
+/// own `.github/workflows`, such as vendored subtrees.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Projects {
+    /// Sub-project directories, as globs relative to the repository root, such as
+    /// `services/*`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    /// Whether the sub-projects share the root gx.toml and gx.lock, keep their own, or
+    /// keep their own gx.toml and share the root gx.lock.
+    #[serde(default)]
+    pub manifest: ManifestScope,
+}
+
+/// Which gx.toml and gx.lock the workflows of a sub-project are tracked in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ManifestScope {
+    /// The root's `.github/gx.toml` and `.github/gx.lock` cover every sub-project.
+    #[default]
+    Shared,
+    /// Each sub-project has its own `.github/gx.toml` and `.github/gx.lock`, and gx runs
+    /// once for the root and once for each of them.
+    PerProject,
+    /// Each sub-project has its own `.github/gx.toml` but all share the root's gx.lock, so
+    /// an action and specifier resolve to one commit across the repository. gx runs once
+    /// for the root and once for each sub-project, as with [`Self::PerProject`].
+    SharedLock,
+}
+
+impl Projects {
+    /// The workflow patterns the sub-projects add to the root's `[workflows] include`
+    /// when they share its manifest; none when each keeps its own.
+    #[must_use]
+    pub fn shared_workflow_patterns(&self) -> Vec<String> {
+        if self.manifest != ManifestScope::Shared {
+            return Vec::new();
+        }
+        self.paths
+            .iter()
+            .flat_map(|path| {
+                let dir = path.trim_end_matches('/');
+                [
+                    format!("{dir}/.github/workflows/**/*.yml"),
+                    format!("{dir}/.github/workflows/**/*.yaml"),
+                ]
+            })
+            .collect()
+    }
+
+    /// The sub-project directories under `repo_root` that gx runs in separately, sorted:
+    /// those a path matches that have a `.github` folder, when each keeps its own
+    /// manifest, and none otherwise.
+    #[must_use]
+    pub fn separate_roots(&self, repo_root: &Path) -> Vec<PathBuf> {
+        if self.manifest == ManifestScope::Shared {
+            return Vec::new();
+        }
+        let root = glob::Pattern::escape(&repo_root.to_string_lossy());
+        let mut roots: Vec<PathBuf> = self
+            .paths
+            .iter()
+            .filter_map(|path| glob::glob(&format!("{root}/{path}")).ok())
+            .flat_map(Iterator::flatten)
+            .filter(|dir| dir.join(".github").is_dir() && dir.as_path() != repo_root)
+            .collect();
+        roots.sort();
+        roots.dedup();
+        roots
+    }
+
+    /// The first `paths` entry that is not a valid glob, if any.
+    #[must_use]
+    pub fn invalid_pattern(&self) -> Option<&str> {
+        self.paths
+            .iter()
+            .find(|pattern| glob::Pattern::new(pattern).is_err())
+            .map(String::as_str)
+    }
+}
+
+/// A warning for each action that the manifests sharing a lock pin to different
+/// specifiers, naming each project (by its label) with the specifier it asks for.
+#[must_use]
+pub fn pin_conflicts(members: &[(String, &Manifest)]) -> Vec<String> {
+    let mut pins: BTreeMap<&str, Vec<(String, &str)>> = BTreeMap::new();
+    for (label, manifest) in members {
+        for spec in manifest.specs() {
+            pins.entry(spec.id.as_str())
+                .or_default()
+                .push((spec.specifier.to_string(), label.as_str()));
+        }
+    }
+    pins.into_iter()
+        .filter(|(_, demands)| {
+            let first = demands.first().map(|(specifier, _)| specifier);
+            demands
+                .iter()
+                .any(|(specifier, _)| Some(specifier) != first)
+        })
+        .map(|(id, demands)| {
+            let each: Vec<String> = demands
+                .iter()
+                .map(|(specifier, label)| format!("{specifier} in {label}"))
+                .collect();
+            format!(
+                "{id} is pinned to {} but the projects share gx.lock",
+                each.join(" and ")
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{ManifestScope, Projects, pin_conflicts};
+    use crate::domain::action::identity::ActionId;
+    use crate::domain::action::specifier::Specifier;
+    use crate::domain::manifest::Manifest;
+    use std::fs;
+
+    #[test]
+    fn shared_projects_add_workflow_patterns_and_per_project_ones_add_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("services/api/.github/workflows")).unwrap();
+        fs::create_dir_all(root.join("services/docs")).unwrap();
+        let mut projects = Projects {
+            paths: vec!["services/*".to_owned()],
+            manifest: ManifestScope::Shared,
+        };
+
+        assert_eq!(
+            projects.shared_workflow_patterns(),
+            [
+                "services/*/.github/workflows/**/*.yml",
+                "services/*/.github/workflows/**/*.yaml"
+            ]
+        );
+        assert!(projects.separate_roots(root).is_empty());
+
+        projects.manifest = ManifestScope::PerProject;
+        assert!(projects.shared_workflow_patterns().is_empty());
+        assert_eq!(projects.separate_roots(root), [root.join("services/api")]);
+    }
+
+    #[test]
+    fn manifests_sharing_a_lock_conflict_only_on_differing_specifiers() {
+        let mut root = Manifest::default();
+        root.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+        root.set(ActionId::from("actions/cache"), Specifier::parse("^4"));
+        let mut api = Manifest::default();
+        api.set(ActionId::from("actions/checkout"), Specifier::parse("^3"));
+        api.set(ActionId::from("actions/cache"), Specifier::parse("^4"));
+
+        assert_eq!(
+            pin_conflicts(&[(".".to_owned(), &root), ("services/api".to_owned(), &api)]),
+            [
+                "actions/checkout is pinned to ^4 in . and ^3 in services/api but the projects \
+              share gx.lock"
+            ]
+        );
+    }
+}
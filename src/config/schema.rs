@@ -0,0 +1,322 @@
+use serde_json::json;
+use toml::{Table, Value};
+
+/// The JSON Schema draft [`Node::json_schema`] follows, which TOML editor plugins read.
+const JSON_SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// The shape of a value in `gx.toml`.
+#[derive(Debug, Clone, Copy)]
+pub enum Node {
+    /// A string.
+    String,
+    /// A whole number.
+    Integer,
+    /// A number, whole or not.
+    Number,
+    /// `true` or `false`.
+    Boolean,
+    /// One of these strings.
+    OneOf(&'static [&'static str]),
+    /// A table with the given keys and, when `other` is set, any other key holding it.
+    Table {
+        /// The keys gx knows, with the shape of their value.
+        keys: &'static [(&'static str, Node)],
+        /// The shape of the value of any other key; other keys are unknown when unset.
+        other: Option<&'static Node>,
+    },
+    /// An array of this.
+    List(&'static Node),
+    /// Either of these; a table takes whichever is a table.
+    Either(&'static Node, &'static Node),
+}
+
+/// A table whose keys are all free-form, each holding `value`.
+const fn map(value: &'static Node) -> Node {
+    Node::Table {
+        keys: &[],
+        other: Some(value),
+    }
+}
+
+/// A table with only the given keys.
+const fn table(keys: &'static [(&'static str, Node)]) -> Node {
+    Node::Table { keys, other: None }
+}
+
+/// The level of a lint rule.
+const LEVEL: Node = Node::OneOf(&["error", "warn", "off"]);
+
+/// A `[lint.rules]` entry.
+const RULE: Node = table(&[
+    ("level", LEVEL),
+    (
+        "ignore",
+        Node::List(&table(&[
+            ("action", Node::String),
+            ("workflow", Node::String),
+            ("job", Node::String),
+        ])),
+    ),
+]);
+
+/// An `[actions]` entry: a specifier, or a table with per-action settings.
+const ACTION: Node = Node::Either(
+    &Node::String,
+    &table(&[
+        ("version", Node::String),
+        ("hold", Node::Boolean),
+        ("allow_prerelease", Node::Boolean),
+        ("require_signed", Node::Boolean),
+        ("reason", Node::String),
+        ("owner", Node::String),
+    ]),
+);
+
+/// An `[actions.overrides]` entry.
+const OVERRIDE: Node = table(&[
+    ("workflow", Node::String),
+    ("job", Node::String),
+    ("step", Node::Either(&Node::Integer, &Node::String)),
+    ("version", Node::String),
+]);
+
+/// The `[registry]` section.
+const REGISTRY: Node = table(&[
+    ("api_url", Node::String),
+    ("tokens", map(&Node::String)),
+    ("resolver", Node::OneOf(&["auto", "api", "git"])),
+    (
+        "retry",
+        table(&[
+            ("retries", Node::Integer),
+            ("base_delay_ms", Node::Integer),
+            ("max_delay_ms", Node::Integer),
+        ]),
+    ),
+    ("concurrency", Node::Integer),
+    ("ca_bundle", Node::String),
+    ("timeout_secs", Node::Integer),
+    ("max_requests", Node::Integer),
+    ("mirrors", map(&Node::String)),
+]);
+
+/// Every section and key of `gx.toml`.
+pub const MANIFEST: Node = table(&[
+    ("gx", table(&[("min_version", Node::String)])),
+    (
+        "actions",
+        Node::Table {
+            keys: &[("overrides", map(&Node::List(&OVERRIDE)))],
+            other: Some(&ACTION),
+        },
+    ),
+    (
+        "lint",
+        table(&[("rules", map(&RULE)), ("paths", map(&map(&LEVEL)))]),
+    ),
+    ("groups", map(&Node::List(&Node::String))),
+    ("aliases", map(&Node::String)),
+    ("registry", REGISTRY),
+    (
+        "workflows",
+        table(&[
+            ("include", Node::List(&Node::String)),
+            ("exclude", Node::List(&Node::String)),
+        ]),
+    ),
+    (
+        "files",
+        table(&[
+            ("backup", Node::Boolean),
+            ("manifest", Node::String),
+            ("lock", Node::String),
+            ("lock_format", Node::OneOf(&["toml", "json"])),
+        ]),
+    ),
+    (
+        "audit",
+        table(&[("min_score", Node::Number), ("level", LEVEL)]),
+    ),
+    (
+        "licenses",
+        table(&[("deny", Node::List(&Node::String)), ("level", LEVEL)]),
+    ),
+    (
+        "projects",
+        table(&[
+            ("paths", Node::List(&Node::String)),
+            (
+                "manifest",
+                Node::OneOf(&["shared", "per-project", "shared-lock"]),
+            ),
+        ]),
+    ),
+]);
+
+impl Node {
+    /// This shape as a JSON Schema document, for editors that complete and check TOML.
+    #[must_use]
+    pub fn json_schema(&self) -> serde_json::Value {
+        let mut schema = self.json_shape();
+        if let serde_json::Value::Object(fields) = &mut schema {
+            fields.insert("$schema".to_owned(), json!(JSON_SCHEMA_DRAFT));
+            fields.insert("title".to_owned(), json!("gx.toml"));
+        }
+        schema
+    }
+
+    /// The JSON Schema of a value of this shape.
+    fn json_shape(&self) -> serde_json::Value {
+        match self {
+            Self::String => json!({ "type": "string" }),
+            Self::Integer => json!({ "type": "integer", "minimum": 0 }),
+            Self::Number => json!({ "type": "number", "minimum": 0 }),
+            Self::Boolean => json!({ "type": "boolean" }),
+            Self::OneOf(choices) => json!({ "type": "string", "enum": choices }),
+            Self::Table { keys, other } => {
+                let properties: serde_json::Map<String, serde_json::Value> = keys
+                    .iter()
+                    .map(|(key, node)| ((*key).to_owned(), node.json_shape()))
+                    .collect();
+                json!({
+                    "type": "object",
+                    "properties": properties,
+                    "additionalProperties": other.map_or(json!(false), Self::json_shape),
+                })
+            }
+            Self::List(node) => json!({ "type": "array", "items": node.json_shape() }),
+            Self::Either(first, second) => {
+                json!({ "anyOf": [first.json_shape(), second.json_shape()] })
+            }
+        }
+    }
+
+    /// The keys of `table` this shape does not have, as dotted paths such as `lint.rulse`.
+    #[must_use]
+    pub fn unknown_keys(&self, table: &Table) -> Vec<String> {
+        let mut found = Vec::new();
+        self.collect_in_table(table, "", &mut found);
+        found
+    }
+
+    /// Add the dotted path of each key under `value`, itself at `path`, this shape does
+    /// not have to `found`.
+    fn collect_unknown(&self, value: &Value, path: &str, found: &mut Vec<String>) {
+        match (self, value) {
+            (Self::Table { .. }, Value::Table(entries)) => {
+                self.collect_in_table(entries, path, found);
+            }
+            (Self::List(node), Value::Array(items)) => {
+                for item in items {
+                    node.collect_unknown(item, path, found);
+                }
+            }
+            (Self::Either(first, second), _) => {
+                let node = if value.is_table() && !matches!(first, Self::Table { .. }) {
+                    second
+                } else {
+                    first
+                };
+                node.collect_unknown(value, path, found);
+            }
+            _ => {}
+        }
+    }
+
+    /// [`Self::collect_unknown`] for the entries of a table this shape describes.
+    fn collect_in_table(&self, entries: &Table, path: &str, found: &mut Vec<String>) {
+        let Self::Table { keys, other } = self else {
+            return;
+        };
+        for (key, inner) in entries {
+            let child = join(path, key);
+            let known = keys
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, node)| node)
+                .or(*other);
+            match known {
+                Some(node) => node.collect_unknown(inner, &child, found),
+                None => found.push(child),
+            }
+        }
+    }
+}
+
+/// `key` under the dotted path `path`, quoted unless it is a bare TOML key.
+fn join(path: &str, key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    let quoted = if bare {
+        key.to_owned()
+    } else {
+        format!("\"{key}\"")
+    };
+    if path.is_empty() {
+        quoted
+    } else {
+        format!("{path}.{quoted}")
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    clippy::indexing_slicing,
+    reason = "tests use unwrap and indexing freely"
+)]
+mod tests {
+    use super::MANIFEST;
+    use toml::Table;
+
+    #[test]
+    fn json_schema_lists_the_sections_and_closes_fixed_tables() {
+        let schema = MANIFEST.json_schema();
+
+        assert_eq!(schema["title"], "gx.toml");
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(
+            schema["properties"]["registry"]["properties"]["resolver"]["enum"],
+            serde_json::json!(["auto", "api", "git"])
+        );
+        assert_eq!(
+            schema["properties"]["actions"]["additionalProperties"]["anyOf"][0]["type"],
+            "string"
+        );
+    }
+
+    #[test]
+    fn misspelled_sections_and_keys_are_found_with_their_path() {
+        let manifest: Table = toml::from_str(
+            r#"
+            [actions]
+            "actions/checkout" = { version = "^4", hodl = true }
+            "actions/setup-node" = "^4"
+
+            [actions.overrides]
+            "actions/checkout" = [{ workflow = "ci.yml", step = 2, version = "^3" }]
+
+            [lint.rulse]
+            unpinned = { level = "off" }
+
+            [lint.rules]
+            stale-comment = { level = "warn", ignore = [{ job = "x", wokflow = "ci.yml" }] }
+
+            [registry.retry]
+            retries = 1
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            MANIFEST.unknown_keys(&manifest),
+            [
+                "actions.\"actions/checkout\".hodl",
+                "lint.rules.stale-comment.ignore.wokflow",
+                "lint.rulse",
+            ]
+        );
+    }
+}
@@ -0,0 +1,381 @@
+use crate::infra::auth::Chain;
+use crate::infra::manifest::env_flag;
+use crate::infra::trace::Tracer;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+
+/// Runtime settings loaded from environment variables.
+#[derive(Debug, Clone, Default)]
+pub struct Settings {
+    /// Github API token for authenticated requests, from `GITHUB_TOKEN`; the registry
+    /// asks `gh` and the OS keyring when it is unset.
+    pub github_token: Option<GitHubToken>,
+    /// GitHub API base URL; `None` means `https://api.github.com`.
+    pub github_api_url: Option<String>,
+    /// Tokens for specific owners or API hosts, keyed in lowercase; they take precedence
+    /// over `github_token`.
+    pub github_tokens: BTreeMap<String, GitHubToken>,
+    /// How action versions are resolved.
+    pub resolver: Resolver,
+    /// How failed GitHub API requests are retried.
+    pub retry: Retry,
+    /// How many actions are resolved at once; `None` uses the built-in default.
+    pub concurrency: Option<usize>,
+    /// PEM bundle of extra CA certificates to trust, e.g. a TLS-intercepting proxy's.
+    pub ca_bundle: Option<PathBuf>,
+    /// Timeout of each GitHub API request in seconds; `None` uses the built-in default.
+    pub timeout_secs: Option<u64>,
+    /// HTTP requests allowed per run; `None` means no limit.
+    pub max_requests: Option<usize>,
+    /// HTTP requests sent so far by every client built from these settings, counted
+    /// against `max_requests`; clones share it.
+    pub requests: Arc<AtomicUsize>,
+    /// Forbid network access, set by `--offline` or `GX_OFFLINE`: commands work from
+    /// gx.lock alone.
+    pub offline: bool,
+    /// Refuse to lock commits gx-trust.toml does not approve, set by `--trusted-only`.
+    pub trusted_only: bool,
+    /// API base URLs of mirrors, keyed by lowercase owner or `owner/repo`; they are
+    /// consulted before `github_api_url`.
+    pub mirrors: BTreeMap<String, String>,
+    /// Traces registry requests, set by `-v` and `--har`.
+    pub tracer: Tracer,
+}
+
+/// How action versions are resolved: through the GitHub API, `git ls-remote`, or both.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Resolver {
+    /// The GitHub API, falling back to `git ls-remote` when it is rate limited or
+    /// rejects the request.
+    #[default]
+    Auto,
+    /// Only the GitHub API.
+    Api,
+    /// Only `git ls-remote`, which needs no API token.
+    Git,
+}
+
+/// The `[registry.retry]` section of `gx.toml`: how requests that fail on a timeout, a
+/// dropped connection, or a server error are retried.
+///
+/// The delay doubles after each attempt, starting at `base_delay_ms` and capped at
+/// `max_delay_ms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Retry {
+    /// Times a request is sent again; `0` disables retries.
+    pub retries: u32,
+    /// Delay before the first retry, in milliseconds.
+    pub base_delay_ms: u64,
+    /// Longest delay between retries, in milliseconds.
+    pub max_delay_ms: u64,
+}
+
+impl Default for Retry {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 8_000,
+        }
+    }
+}
+
+impl Retry {
+    /// Whether this is the default, left out when the section is written back.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Resolver {
+    /// Whether this is the default, left out when the section is written back.
+    #[must_use]
+    pub fn is_auto(&self) -> bool {
+        *self == Self::Auto
+    }
+}
+
+/// The `[registry]` section of `gx.toml`: where actions are resolved.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Registry {
+    /// GitHub API base URL, e.g. `https://ghes.example.com/api/v3` for GitHub Enterprise Server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+    /// Owners or API hosts mapped to the environment variable holding their token.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tokens: BTreeMap<String, String>,
+    /// How action versions are resolved; see [`Resolver`].
+    #[serde(default, skip_serializing_if = "Resolver::is_auto")]
+    pub resolver: Resolver,
+    /// How failed requests are retried; see [`Retry`].
+    #[serde(default, skip_serializing_if = "Retry::is_default")]
+    pub retry: Retry,
+    /// How many actions are resolved at once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+    /// PEM bundle of extra CA certificates to trust, relative to the repository root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<PathBuf>,
+    /// Timeout of each request, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// GitHub API requests allowed per run; resolution stops once they are used up.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_requests: Option<usize>,
+    /// Owners or `owner/repo` names mapped to the API of a host that mirrors them.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub mirrors: BTreeMap<String, String>,
+}
+
+/// A GitHub API token with masked debug output.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct GitHubToken(String);
+
+impl GitHubToken {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for GitHubToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("GitHubToken(***)")
+    }
+}
+
+impl From<String> for GitHubToken {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+impl Settings {
+    /// Load settings from environment variables. `GX_REGISTRY_API_URL` takes precedence
+    /// over `GITHUB_API_URL`, and `GX_OFFLINE` turns on offline mode.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self {
+            github_token: env::var("GITHUB_TOKEN").ok().map(GitHubToken::from),
+            github_api_url: ["GX_REGISTRY_API_URL", "GITHUB_API_URL"]
+                .into_iter()
+                .find_map(|name| env::var(name).ok().filter(|url| !url.is_empty())),
+            github_tokens: BTreeMap::new(),
+            resolver: Resolver::default(),
+            retry: Retry::default(),
+            concurrency: None,
+            ca_bundle: None,
+            timeout_secs: None,
+            max_requests: None,
+            requests: Arc::default(),
+            offline: env::var("GX_OFFLINE")
+                .ok()
+                .and_then(|value| env_flag(&value))
+                .unwrap_or_default(),
+            trusted_only: false,
+            mirrors: BTreeMap::new(),
+            tracer: Tracer::default(),
+        }
+    }
+
+    /// Host the GitHub API belongs to: `github.com`, or the GitHub Enterprise Server host.
+    #[must_use]
+    pub fn github_host(&self) -> String {
+        self.github_api_url
+            .as_deref()
+            .and_then(|url| url.split_once("://").map(|(_, rest)| rest))
+            .and_then(|rest| rest.split('/').next())
+            .filter(|host| !host.eq_ignore_ascii_case("api.github.com"))
+            .map_or_else(|| "github.com".to_owned(), str::to_lowercase)
+    }
+
+    /// Web root of the GitHub host, such as `https://github.com` or
+    /// `https://ghes.example.com`, where its repositories are browsed.
+    #[must_use]
+    pub fn github_web_url(&self) -> String {
+        let scheme = self
+            .github_api_url
+            .as_deref()
+            .and_then(|url| url.split_once("://"))
+            .map_or("https", |(scheme, _)| scheme);
+        format!("{scheme}://{}", self.github_host())
+    }
+
+    /// The token for the GitHub API: `github_token`, or else the one `gh` or the OS keyring
+    /// holds for the host; `None` offline.
+    ///
+    /// Only registries ask, when they are built: `gh` and the keyring are slow, and most
+    /// commands never reach the API.
+    #[must_use]
+    pub fn resolve_token(&self) -> Option<GitHubToken> {
+        if self.offline {
+            return self.github_token.clone();
+        }
+        self.github_token.clone().or_else(|| {
+            Chain::standard()
+                .resolve(&self.github_host())
+                .map(|(_, token)| token)
+        })
+    }
+
+    /// The settings for the mirror at `url` that serves `key`, an owner or `owner/repo`.
+    ///
+    /// A mirror is named by gx.toml, so it gets only the `tokens` entry for its host or
+    /// for the owner in `key`, never `github_token` or a credential of another host.
+    #[must_use]
+    pub fn mirror(&self, key: &str, url: &str) -> Self {
+        let mirror = Self {
+            github_api_url: Some(url.to_owned()),
+            github_token: None,
+            github_tokens: BTreeMap::new(),
+            mirrors: BTreeMap::new(),
+            ..self.clone()
+        };
+        let owner = key.split('/').next().unwrap_or(key);
+        let github_token = self
+            .github_tokens
+            .get(owner)
+            .or_else(|| self.github_tokens.get(&mirror.github_host()))
+            .cloned();
+        Self {
+            github_token,
+            ..mirror
+        }
+    }
+
+    /// Fill in the `[registry]` section of `gx.toml`.
+    ///
+    /// `GITHUB_API_URL` takes precedence over `api_url`. Each `tokens` entry is read from
+    /// the environment variable it names; entries whose variable is unset are skipped.
+    #[must_use]
+    pub fn with_registry(mut self, registry: Registry) -> Self {
+        if self.github_api_url.is_none() {
+            self.github_api_url = registry.api_url;
+        }
+        self.resolver = registry.resolver;
+        self.retry = registry.retry;
+        self.concurrency = registry.concurrency;
+        self.ca_bundle = registry.ca_bundle;
+        self.timeout_secs = registry.timeout_secs;
+        self.max_requests = registry.max_requests;
+        self.mirrors = registry
+            .mirrors
+            .into_iter()
+            .map(|(key, url)| (key.to_lowercase(), url))
+            .collect();
+        for (key, var) in registry.tokens {
+            if let Some(token) = env::var(&var).ok().filter(|token| !token.is_empty()) {
+                self.github_tokens
+                    .insert(key.to_lowercase(), GitHubToken::from(token));
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GitHubToken, Registry, Resolver, Retry, Settings};
+
+    #[test]
+    fn settings_default_has_no_token() {
+        let settings = Settings::default();
+        assert!(settings.github_token.is_none());
+    }
+
+    #[test]
+    fn resolve_token_offline_asks_no_credential_helper() {
+        let offline = Settings {
+            offline: true,
+            ..Settings::default()
+        };
+        assert!(offline.resolve_token().is_none());
+
+        let with_token = Settings {
+            github_token: Some(GitHubToken::from("ghp_env".to_owned())),
+            ..offline
+        };
+        assert_eq!(
+            with_token.resolve_token().as_ref().map(GitHubToken::as_str),
+            Some("ghp_env")
+        );
+    }
+
+    #[test]
+    fn with_registry_fills_api_url_and_skips_unset_token_variables() {
+        let registry = Registry {
+            api_url: Some("https://ghes.example.com".to_owned()),
+            tokens: [("Org".to_owned(), "GX_TEST_TOKEN_NOT_SET".to_owned())].into(),
+            resolver: Resolver::Git,
+            retry: Retry {
+                retries: 0,
+                ..Retry::default()
+            },
+            concurrency: Some(2),
+            ca_bundle: Some("certs/proxy.pem".into()),
+            timeout_secs: Some(5),
+            max_requests: Some(100),
+            mirrors: [("Actions".to_owned(), "https://ghes.example.com".to_owned())].into(),
+        };
+        let settings = Settings::default().with_registry(registry);
+        assert_eq!(
+            settings.github_api_url.as_deref(),
+            Some("https://ghes.example.com")
+        );
+        assert!(settings.github_tokens.is_empty());
+        assert_eq!(settings.resolver, Resolver::Git);
+        assert_eq!(settings.retry.retries, 0);
+        assert_eq!(settings.concurrency, Some(2));
+        assert_eq!(settings.timeout_secs, Some(5));
+        assert_eq!(settings.max_requests, Some(100));
+        assert_eq!(
+            settings.mirrors.get("actions").map(String::as_str),
+            Some("https://ghes.example.com")
+        );
+        assert_eq!(
+            settings.ca_bundle.as_deref(),
+            Some(std::path::Path::new("certs/proxy.pem"))
+        );
+    }
+
+    #[test]
+    fn github_host_follows_the_api_url() {
+        let host = |url: Option<&str>| {
+            Settings {
+                github_api_url: url.map(str::to_owned),
+                ..Settings::default()
+            }
+            .github_host()
+        };
+        assert_eq!(host(None), "github.com");
+        assert_eq!(host(Some("https://api.github.com")), "github.com");
+        assert_eq!(
+            host(Some("https://GHES.example.com/api/v3")),
+            "ghes.example.com"
+        );
+    }
+
+    #[test]
+    fn github_web_url_follows_the_api_url() {
+        let web = |url: Option<&str>| {
+            Settings {
+                github_api_url: url.map(str::to_owned),
+                ..Settings::default()
+            }
+            .github_web_url()
+        };
+        assert_eq!(web(None), "https://github.com");
+        assert_eq!(web(Some("https://api.github.com")), "https://github.com");
+        assert_eq!(
+            web(Some("https://ghes.example.com/api/v3")),
+            "https://ghes.example.com"
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+
+/// Patterns gx reads workflows from when `[workflows] include` is not set: the
+/// repository's own workflows, and the starter workflows an organization's `.github`
+/// repository shares under `workflow-templates`.
+const DEFAULT_INCLUDE: [&str; 4] = [
+    ".github/workflows/**/*.yml",
+    ".github/workflows/**/*.yaml",
+    "workflow-templates/*.yml",
+    "workflow-templates/*.yaml",
+];
+
+/// The `[workflows]` section of `gx.toml`: which files gx reads and rewrites as workflows.
+///
+/// Patterns are globs relative to the repository root. A `**` glued to a name, as in
+/// `ci/**.yml`, is read as `ci/**/*.yml`, and a trailing `**` as every file below.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Workflows {
+    /// Files to treat as workflows; every `.yml` and `.yaml` file under
+    /// `.github/workflows`, at any depth, and in `workflow-templates` when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// Files never read or rewritten, even when an `include` pattern matches them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+impl Workflows {
+    /// The include patterns in effect, normalized.
+    #[must_use]
+    pub fn include_patterns(&self) -> Vec<String> {
+        if self.include.is_empty() {
+            return DEFAULT_INCLUDE.iter().map(|&p| p.to_owned()).collect();
+        }
+        self.include.iter().map(|p| normalize(p)).collect()
+    }
+
+    /// These patterns with `patterns` added to the files included, on top of the defaults
+    /// when `include` is not set.
+    #[must_use]
+    pub fn including(mut self, patterns: Vec<String>) -> Self {
+        if !patterns.is_empty() && self.include.is_empty() {
+            self.include = DEFAULT_INCLUDE.iter().map(|&p| p.to_owned()).collect();
+        }
+        self.include.extend(patterns);
+        self
+    }
+
+    /// Whether the workflow at `path`, relative to the repository root and `/`-separated,
+    /// matches an `exclude` pattern. Invalid patterns never match (they are rejected at
+    /// load time).
+    #[must_use]
+    pub fn excludes(&self, path: &str) -> bool {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..glob::MatchOptions::new()
+        };
+        self.exclude
+            .iter()
+            .filter_map(|pattern| glob::Pattern::new(&normalize(pattern)).ok())
+            .any(|glob| glob.matches_with(path, options))
+    }
+
+    /// The first `include` or `exclude` pattern that is not a valid glob, if any.
+    #[must_use]
+    pub fn invalid_pattern(&self) -> Option<&str> {
+        self.include
+            .iter()
+            .chain(&self.exclude)
+            .find(|pattern| glob::Pattern::new(&normalize(pattern)).is_err())
+            .map(String::as_str)
+    }
+}
+
+/// Rewrite each path component written as `**name` into `**/*name`, and a trailing `**`
+/// into `**/*`, the forms the glob syntax reads as files at any depth.
+fn normalize(pattern: &str) -> String {
+    let normalized = pattern
+        .split('/')
+        .map(|component| match component.strip_prefix("**") {
+            Some(rest) if !rest.is_empty() => format!("**/*{rest}"),
+            _ => component.to_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    if normalized == "**" || normalized.ends_with("/**") {
+        format!("{normalized}/*")
+    } else {
+        normalized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Workflows;
+
+    fn workflows(include: &[&str], exclude: &[&str]) -> Workflows {
+        Workflows {
+            include: include.iter().map(|&p| p.to_owned()).collect(),
+            exclude: exclude.iter().map(|&p| p.to_owned()).collect(),
+        }
+    }
+
+    #[test]
+    fn include_defaults_to_the_workflows_directory_tree() {
+        assert_eq!(
+            Workflows::default().include_patterns(),
+            vec![
+                ".github/workflows/**/*.yml",
+                ".github/workflows/**/*.yaml",
+                "workflow-templates/*.yml",
+                "workflow-templates/*.yaml",
+            ]
+        );
+    }
+
+    #[test]
+    fn double_star_glued_to_a_name_spans_directories() {
+        let config = workflows(
+            &[".github/workflows/**", "ci/**.yml"],
+            &["**/generated-*.yml"],
+        );
+
+        assert_eq!(
+            config.include_patterns(),
+            vec![".github/workflows/**/*", "ci/**/*.yml"]
+        );
+        assert!(config.invalid_pattern().is_none());
+        assert!(config.excludes(".github/workflows/generated-ci.yml"));
+        assert!(config.excludes("ci/nested/generated-deploy.yml"));
+        assert!(!config.excludes(".github/workflows/ci.yml"));
+    }
+
+    #[test]
+    fn invalid_pattern_reports_the_first_bad_glob() {
+        let config = workflows(&["ci/*.yml"], &["[bad"]);
+
+        assert_eq!(config.invalid_pattern(), Some("[bad"));
+    }
+}
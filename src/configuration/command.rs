@@ -0,0 +1,87 @@
+use super::report::{Schema as SchemaReport, Validate as ValidateReport};
+use crate::command::Command;
+use crate::config::Config;
+use crate::config::schema::MANIFEST;
+use crate::domain::workflow::Error as WorkflowError;
+use crate::infra::workflow_scan::FileScanner;
+use std::path::Path;
+use thiserror::Error;
+
+/// Errors that can occur during the config commands.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Workflow(#[from] WorkflowError),
+}
+
+/// `gx config validate`: check gx.toml for keys gx does not know and for lint ignore
+/// targets that name no workflow. Malformed values and unknown rule names already fail
+/// to load.
+pub struct Validate;
+
+impl Command for Validate {
+    type Report = ValidateReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<ValidateReport, Error> {
+        let mut problems: Vec<String> = config
+            .unknown_keys
+            .iter()
+            .map(|key| format!("unknown key {key}"))
+            .collect();
+
+        let workflows: Vec<String> = FileScanner::new(repo_root)
+            .with_workflows(config.workflows.clone())
+            .find_workflows()?
+            .iter()
+            .map(|path| {
+                path.strip_prefix(repo_root)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        for (rule, setting) in &config.lint_config.rules {
+            let missing = setting
+                .ignore
+                .iter()
+                .filter_map(|target| target.workflow.as_deref())
+                .filter(|workflow| !workflows.iter().any(|path| path.ends_with(workflow)));
+            for workflow in missing {
+                problems.push(format!(
+                    "lint.rules.{rule} ignores workflow {workflow}, which matches no workflow"
+                ));
+            }
+        }
+
+        Ok(ValidateReport {
+            path: config.manifest_path,
+            problems,
+        })
+    }
+}
+
+/// `gx config schema`: print the JSON Schema of gx.toml, for editors to complete and
+/// check it.
+pub struct Schema;
+
+impl Command for Schema {
+    type Report = SchemaReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        _repo_root: &Path,
+        _config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<SchemaReport, Error> {
+        Ok(SchemaReport {
+            schema: MANIFEST.json_schema(),
+        })
+    }
+}
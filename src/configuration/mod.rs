@@ -0,0 +1,7 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// Config commands: error types, structs, and `Command` implementations.
+mod command;
+pub mod report;
+
+pub use command::{Error, Schema, Validate};
@@ -0,0 +1,77 @@
+use crate::command::CommandReport;
+use crate::output::lines::Line as OutputLine;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Report from `gx config validate`.
+#[derive(Debug, Default, Serialize)]
+pub struct Validate {
+    /// The manifest that was checked.
+    pub path: PathBuf,
+    /// What is wrong with it.
+    pub problems: Vec<String>,
+}
+
+impl CommandReport for Validate {
+    fn render(&self) -> Vec<OutputLine> {
+        let mut lines: Vec<OutputLine> = self
+            .problems
+            .iter()
+            .map(|problem| OutputLine::Warning {
+                message: problem.clone(),
+            })
+            .collect();
+        lines.push(if self.problems.is_empty() {
+            OutputLine::Summary {
+                text: format!("{} is valid", self.path.display()),
+            }
+        } else {
+            OutputLine::Warning {
+                message: format!(
+                    "{} has {} problem(s)",
+                    self.path.display(),
+                    self.problems.len()
+                ),
+            }
+        });
+        lines
+    }
+
+    fn exit_code(&self) -> i32 {
+        i32::from(!self.problems.is_empty())
+    }
+}
+
+/// Report from `gx config schema`: the JSON Schema itself.
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct Schema {
+    /// The JSON Schema of gx.toml.
+    pub schema: serde_json::Value,
+}
+
+impl CommandReport for Schema {
+    fn render(&self) -> Vec<OutputLine> {
+        vec![OutputLine::Summary {
+            text: self.schema.to_string(),
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Validate;
+    use crate::command::CommandReport as _;
+
+    #[test]
+    fn problems_fail_validation() {
+        let invalid = Validate {
+            problems: vec!["unknown key lint.rulse".to_owned()],
+            ..Validate::default()
+        };
+
+        assert_eq!(Validate::default().exit_code(), 0);
+        assert_eq!(invalid.exit_code(), 1);
+        assert_eq!(invalid.render().len(), 2);
+    }
+}
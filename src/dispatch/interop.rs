@@ -0,0 +1,121 @@
+use crate::subcommands::{ExportAction, ImportAction, MigrateAction, TrustAction};
+use crate::{GxError, run_and_print};
+use gx::config::Config;
+use gx::infra::github::Policy;
+use gx::output::log_file::LogFile;
+use gx::output::printer::Printer;
+use gx::{export, import, migrate, trust};
+use std::path::Path;
+
+/// Run a `gx import` subcommand and return its exit code.
+pub(super) fn run_import(
+    action: ImportAction,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    let ImportAction::Dependabot { file } = action;
+    run_and_print(
+        &import::Dependabot { file },
+        printer.spinner("Importing dependabot.yml..."),
+        printer,
+        repo_root,
+        config,
+        log_file,
+    )
+    .map_err(GxError::from)
+}
+
+/// Run a `gx trust` subcommand and return its exit code.
+pub(super) fn run_trust(
+    action: TrustAction,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    match action {
+        TrustAction::Export { file, key } => run_and_print(
+            &trust::Export { file, key },
+            None,
+            printer,
+            repo_root,
+            config,
+            log_file,
+        ),
+        TrustAction::Import {
+            file,
+            allowed_signers,
+            identity,
+            ..
+        } => run_and_print(
+            &trust::Import {
+                file,
+                signer: allowed_signers.zip(identity),
+            },
+            printer.spinner("Importing trust database..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        ),
+    }
+    .map_err(GxError::from)
+}
+
+/// Run a `gx migrate` subcommand and return its exit code.
+pub(super) fn run_migrate(
+    action: MigrateAction,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    let MigrateAction::Ratchet = action;
+    run_and_print(
+        &migrate::Ratchet,
+        printer.spinner("Migrating from ratchet..."),
+        printer,
+        repo_root,
+        config,
+        log_file,
+    )
+    .map_err(GxError::from)
+}
+
+/// Run a `gx export` subcommand and return its exit code.
+pub(super) fn run_export(
+    action: ExportAction,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    let (tool, file, apply) = match action {
+        ExportAction::Dependabot { file } => (export::Tool::Dependabot, file, None),
+        ExportAction::Renovate { file } => (export::Tool::Renovate, file, None),
+        ExportAction::AllowedActions {
+            file,
+            apply,
+            org,
+            repo,
+        } => {
+            let target = match (org, repo) {
+                (Some(login), _) => export::Apply::To(Policy::Organization(login)),
+                (None, Some(repository)) => export::Apply::To(Policy::Repository(repository)),
+                (None, None) => export::Apply::Origin,
+            };
+            (export::Tool::AllowedActions, file, apply.then_some(target))
+        }
+    };
+    run_and_print(
+        &export::Export { tool, file, apply },
+        printer.spinner("Generating rules..."),
+        printer,
+        repo_root,
+        config,
+        log_file,
+    )
+    .map_err(GxError::from)
+}
@@ -0,0 +1,393 @@
+use super::args::{Commands, Format, tidy_command};
+use super::subcommands::{
+    AuthAction, CacheAction, ConfigAction, HookAction, LockAction, SbomArgs, VerifyArgs,
+};
+use super::upgrade_args::UpgradeArgs;
+use super::{GxError, run_and_print, run_and_print_as};
+use gx::config::Config;
+use gx::output::log_file::LogFile;
+use gx::output::printer::Printer;
+use gx::{
+    audit, auth, cache, configuration, hash, hook, init, licenses, lint, lock, tree, undo, upgrade,
+};
+use interop::{run_export, run_import, run_migrate, run_trust};
+use std::path::Path;
+
+/// Dispatch of the commands that exchange files with other tools and reviewers.
+mod interop;
+
+/// Run `subcommand` and return its exit code.
+pub fn run_command(
+    subcommand: Commands,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    let code = match subcommand {
+        Commands::Tidy {
+            commit,
+            commit_message,
+            fix,
+            check,
+            changes,
+            format,
+        } => run_and_print_as(
+            &tidy_command(commit, commit_message, fix, check, changes),
+            format,
+            printer.spinner("Running tidy..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        Commands::Init => run_and_print(
+            &init::Init,
+            printer.spinner("Initializing..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        Commands::Upgrade(args) => run_upgrade(args, printer, repo_root, config, log_file)?,
+        Commands::Apply { plan, force } => run_and_print(
+            &upgrade::apply::Apply {
+                plan_path: plan,
+                force,
+            },
+            printer.spinner("Applying plan..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        Commands::Undo => run_and_print(
+            &undo::Undo,
+            printer.spinner("Undoing..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        Commands::CleanBackups => run_and_print(
+            &undo::CleanBackups,
+            printer.spinner("Removing backups..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        Commands::Auth { action } => run_auth(action, printer, repo_root, config, log_file)?,
+        Commands::Cache { action } => run_cache(action, printer, repo_root, config, log_file)?,
+        Commands::Hook { action } => run_hook(action, printer, repo_root, config, log_file)?,
+        Commands::Import { action } => run_import(action, printer, repo_root, config, log_file)?,
+        Commands::Export { action } => run_export(action, printer, repo_root, config, log_file)?,
+        Commands::Migrate { action } => run_migrate(action, printer, repo_root, config, log_file)?,
+        Commands::Lock { action } => run_lock(action, printer, repo_root, config, log_file)?,
+        Commands::Config { action } => run_config(action, printer, repo_root, config, log_file)?,
+        #[cfg(feature = "gitlab")]
+        Commands::Gitlab { action } => run_gitlab(action, printer, repo_root, config, log_file)?,
+        Commands::Tree => run_and_print(
+            &tree::Tree,
+            printer.spinner("Reading action metadata..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        Commands::Lint { changes } => run_and_print(
+            &lint::Lint {
+                changes: changes.into_changes(),
+            },
+            printer.spinner("Linting..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        Commands::Verify(args) => run_verify(args, printer, repo_root, config, log_file)?,
+        Commands::Audit => run_audit(printer, repo_root, config, log_file)?,
+        Commands::Licenses => run_licenses(printer, repo_root, config, log_file)?,
+        Commands::Sbom(args) => run_sbom(args, printer, repo_root, config, log_file)?,
+        Commands::Trust { action } => run_trust(action, printer, repo_root, config, log_file)?,
+        Commands::Hash { format } => run_and_print_as(
+            &hash::Hash,
+            format,
+            printer.spinner("Hashing gx.toml and gx.lock..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+    };
+    Ok(code)
+}
+
+/// Run `gx upgrade`, printing its report in the requested format, and return its exit code.
+fn run_upgrade(
+    args: UpgradeArgs,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    let spinner = printer.spinner("Checking actions...");
+    let format = args.format;
+    let command = args.into_command(spinner.clone(), printer.is_ci)?;
+    run_and_print_as(
+        &command, format, spinner, printer, repo_root, config, log_file,
+    )
+}
+
+/// Run a `gx auth` subcommand and return its exit code.
+fn run_auth(
+    action: AuthAction,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    let code = match action {
+        AuthAction::Login { client_id } => run_and_print(
+            &auth::Login { client_id },
+            printer.spinner("Logging in..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        AuthAction::Status => run_and_print(
+            &auth::Status,
+            printer.spinner("Checking credentials..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+    };
+    Ok(code)
+}
+
+/// Run a `gx cache` subcommand and return its exit code.
+fn run_cache(
+    action: CacheAction,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    let code = match action {
+        CacheAction::Info => run_and_print(
+            &cache::Info,
+            printer.spinner("Reading cache..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        CacheAction::Clean => run_and_print(
+            &cache::Clean,
+            printer.spinner("Cleaning cache..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+    };
+    Ok(code)
+}
+
+/// Run a `gx hook` subcommand and return its exit code.
+fn run_hook(
+    action: HookAction,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    let code = match action {
+        HookAction::Install { pre_commit_config } => run_and_print(
+            &hook::Install { pre_commit_config },
+            printer.spinner("Installing hook..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        HookAction::Uninstall => run_and_print(
+            &hook::Uninstall,
+            printer.spinner("Removing hook..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+    };
+    Ok(code)
+}
+
+/// Run a `gx lock` subcommand and return its exit code.
+fn run_lock(
+    action: LockAction,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    let code = match action {
+        LockAction::List { format } => run_and_print_as(
+            &lock::List,
+            format,
+            printer.spinner("Reading gx.lock..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        LockAction::Diff { old, new, format } => run_and_print_as(
+            &lock::Diff { old, new },
+            format,
+            printer.spinner("Comparing lock files..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        LockAction::Convert => run_and_print(
+            &lock::Convert,
+            printer.spinner("Converting gx.lock..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        LockAction::Merge { base, ours, theirs } => run_and_print(
+            &lock::Merge { base, ours, theirs },
+            printer.spinner("Merging lock files..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+    };
+    Ok(code)
+}
+
+/// Run `gx verify` and return its exit code.
+fn run_verify(
+    args: VerifyArgs,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    run_and_print(
+        &args.into_command(),
+        printer.spinner("Verifying gx.lock..."),
+        printer,
+        repo_root,
+        config,
+        log_file,
+    )
+    .map_err(GxError::from)
+}
+
+/// Run `gx audit` and return its exit code.
+fn run_audit(
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    run_and_print(
+        &audit::Audit,
+        printer.spinner("Checking advisories..."),
+        printer,
+        repo_root,
+        config,
+        log_file,
+    )
+    .map_err(GxError::from)
+}
+
+/// Run `gx licenses` and return its exit code.
+fn run_licenses(
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    run_and_print(
+        &licenses::Licenses,
+        printer.spinner("Reading licenses..."),
+        printer,
+        repo_root,
+        config,
+        log_file,
+    )
+    .map_err(GxError::from)
+}
+
+/// Run `gx sbom` and return its exit code.
+fn run_sbom(
+    args: SbomArgs,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    let command = args.into_command();
+    run_and_print(&command, None, printer, repo_root, config, log_file).map_err(GxError::from)
+}
+
+/// Run a `gx config` subcommand and return its exit code.
+fn run_config(
+    action: ConfigAction,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    let code = match action {
+        ConfigAction::Validate => run_and_print(
+            &configuration::Validate,
+            printer.spinner("Validating gx.toml..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+        ConfigAction::Schema => run_and_print_as(
+            &configuration::Schema,
+            Format::Json,
+            None,
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+    };
+    Ok(code)
+}
+
+/// Run a `gx gitlab` subcommand and return its exit code.
+#[cfg(feature = "gitlab")]
+fn run_gitlab(
+    action: super::subcommands::GitlabAction,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    let code = match action {
+        super::subcommands::GitlabAction::Pin => run_and_print(
+            &gx::gitlab::Pin,
+            printer.spinner("Pinning GitLab includes..."),
+            printer,
+            repo_root,
+            config,
+            log_file,
+        )?,
+    };
+    Ok(code)
+}
@@ -2,7 +2,7 @@ use super::specifier::higher_version;
 use std::fmt;
 
 /// Unique identifier for an action (e.g., "actions/checkout").
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize)]
 pub struct ActionId(pub String);
 
 impl ActionId {
@@ -17,6 +17,12 @@ impl ActionId {
     pub fn base_repo(&self) -> Repository {
         Repository::from(self.0.split('/').take(2).collect::<Vec<_>>().join("/"))
     }
+
+    /// The owning user or organization (e.g., "actions" for "actions/checkout").
+    #[must_use]
+    pub fn owner(&self) -> &str {
+        self.0.split('/').next().unwrap_or_default()
+    }
 }
 
 impl fmt::Display for ActionId {
@@ -254,6 +260,16 @@ impl CommitDate {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// The calendar day from the leading `YYYY-MM-DD`, or `None` if it is malformed.
+    #[must_use]
+    pub fn day(&self) -> Option<time::Date> {
+        let mut parts = self.0.get(..10)?.split('-');
+        let year = parts.next()?.parse().ok()?;
+        let month: u8 = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        time::Date::from_calendar_date(year, time::Month::try_from(month).ok()?, day).ok()
+    }
 }
 
 impl fmt::Display for CommitDate {
@@ -276,7 +292,18 @@ impl From<&str> for CommitDate {
 
 #[cfg(test)]
 mod tests {
-    use super::{ActionId, CommitSha, Version, VersionPrecision};
+    use super::{ActionId, CommitDate, CommitSha, Version, VersionPrecision};
+
+    #[test]
+    fn commit_date_day_reads_leading_date() {
+        let day = CommitDate::from("2026-03-07T12:30:00Z").day();
+        assert_eq!(
+            day,
+            time::Date::from_calendar_date(2026, time::Month::March, 7).ok()
+        );
+        assert_eq!(CommitDate::from("").day(), None);
+        assert_eq!(CommitDate::from("2026-13-01T00:00:00Z").day(), None);
+    }
 
     #[test]
     fn action_id_base_repo() {
@@ -285,6 +312,7 @@ mod tests {
 
         let subpath = ActionId::from("github/codeql-action/upload-sarif");
         assert_eq!(subpath.base_repo().as_str(), "github/codeql-action");
+        assert_eq!(subpath.owner(), "github");
     }
 
     #[test]
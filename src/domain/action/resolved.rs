@@ -8,6 +8,93 @@ pub struct Commit {
     pub repository: Repository,
     pub ref_type: Option<RefType>,
     pub date: CommitDate,
+    /// How and when gx resolved the commit.
+    pub provenance: Provenance,
+}
+
+/// Where gx found the commit a version points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Source {
+    /// The GitHub API.
+    #[serde(rename = "api")]
+    Api,
+    /// `git ls-remote`, without the API.
+    #[serde(rename = "git")]
+    Git,
+    /// A resolution an earlier run kept in the user cache.
+    #[serde(rename = "cache")]
+    Cache,
+}
+
+impl Source {
+    /// The name written to gx.lock.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Api => "api",
+            Self::Git => "git",
+            Self::Cache => "cache",
+        }
+    }
+
+    /// The source named `s` in gx.lock, if it is one.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "api" => Some(Self::Api),
+            "git" => Some(Self::Git),
+            "cache" => Some(Self::Cache),
+            _ => None,
+        }
+    }
+}
+
+/// How and when a commit was resolved, recorded with each entry of gx.lock.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Provenance {
+    /// Where the commit was found; `None` for entries locked before gx recorded it.
+    pub source: Option<Source>,
+    /// When it was resolved, as an RFC 3339 UTC timestamp; empty when unknown.
+    pub resolved_at: String,
+}
+
+impl Provenance {
+    /// A commit found through `source` at `unix_time`, in seconds since the Unix epoch.
+    #[must_use]
+    pub fn at(source: Source, unix_time: i64) -> Self {
+        let resolved_at = time::OffsetDateTime::from_unix_timestamp(unix_time)
+            .map(|at| {
+                format!(
+                    "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+                    at.year(),
+                    u8::from(at.month()),
+                    at.day(),
+                    at.hour(),
+                    at.minute(),
+                    at.second()
+                )
+            })
+            .unwrap_or_default();
+        Self {
+            source: Some(source),
+            resolved_at,
+        }
+    }
+
+    /// The day it was resolved, or `None` when unknown.
+    #[must_use]
+    pub fn resolved_on(&self) -> Option<time::Date> {
+        CommitDate::from(self.resolved_at.as_str()).day()
+    }
+}
+
+impl Commit {
+    /// The day gx locked this commit: when it was resolved, or, for entries locked before
+    /// gx recorded that, the commit's own date.
+    #[must_use]
+    pub fn locked_on(&self) -> Option<time::Date> {
+        self.provenance.resolved_on().or_else(|| self.date.day())
+    }
 }
 
 /// The result of resolving an action spec via the registry.
@@ -38,7 +125,9 @@ pub struct ResolvedAction {
 
 #[cfg(test)]
 mod tests {
-    use super::{Commit, CommitDate, CommitSha, RefType, Repository, Resolved, Version};
+    use super::{
+        Commit, CommitDate, CommitSha, Provenance, RefType, Repository, Resolved, Source, Version,
+    };
 
     #[test]
     fn resolved_holds_version_and_commit() {
@@ -49,6 +138,7 @@ mod tests {
                 repository: Repository::from("actions/checkout"),
                 ref_type: Some(RefType::Tag),
                 date: CommitDate::from("2026-01-01T00:00:00Z"),
+                provenance: Provenance::default(),
             },
         };
         assert_eq!(resolved.version.as_str(), "v4.2.1");
@@ -57,4 +147,22 @@ mod tests {
             CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
         );
     }
+
+    #[test]
+    fn locked_on_prefers_when_gx_resolved_the_commit() {
+        let mut commit = Commit {
+            sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            repository: Repository::from("actions/checkout"),
+            ref_type: Some(RefType::Tag),
+            date: CommitDate::from("2024-03-01T00:00:00Z"),
+            provenance: Provenance::default(),
+        };
+        let released = time::Date::from_calendar_date(2024, time::Month::March, 1).ok();
+        assert_eq!(commit.locked_on(), released);
+
+        // 2026-01-02T00:00:00Z
+        commit.provenance = Provenance::at(Source::Api, 1_767_312_000);
+        let resolved = time::Date::from_calendar_date(2026, time::Month::January, 2).ok();
+        assert_eq!(commit.locked_on(), resolved);
+    }
 }
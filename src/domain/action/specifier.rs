@@ -1,19 +1,24 @@
 use super::identity::{CommitSha, Version, VersionPrecision};
 use std::fmt;
 
+/// Manifest keyword for tracking the newest stable release.
+const LATEST: &str = "latest";
+
 /// A specifier for an action version in the manifest or lock key.
 ///
 /// This replaces `Version` in manifest entries, lock keys, and overrides.
 /// `Version` remains for concrete resolved tags in lock entries.
 #[derive(Debug, Clone)]
 pub enum Specifier {
-    /// Semver range: `"^6"`, `"~1.15.2"`, `"^0.5"`.
+    /// Semver range: `"^6"`, `"~1.15.2"`, `"^0.5"`, `">=4, <6"`.
     Range {
         /// For matching against resolved versions.
         req: semver::VersionReq,
         /// Raw specifier string for serialization roundtrip (e.g., `"^6"`).
         raw: String,
     },
+    /// The newest stable release, re-resolved on every run: `"latest"`.
+    Latest,
     /// Non-semver ref: `"main"`, `"develop"`.
     Ref(String),
     /// Direct 40-char hex SHA.
@@ -23,15 +28,14 @@ pub enum Specifier {
 impl Specifier {
     /// Parse a specifier string.
     ///
-    /// - `"^6"`, `"~1.15.2"` → `Range`
+    /// - `"^6"`, `"~1.15.2"`, `">=4, <6"` → `Range`
+    /// - `"latest"` → `Latest`
     /// - 40-char hex SHA → `Sha`
     /// - Anything else → `Ref`
     #[must_use]
     pub fn parse(s: &str) -> Self {
-        // Semver range: starts with ^ or ~
-        if s.strip_prefix('^')
-            .or_else(|| s.strip_prefix('~'))
-            .is_some()
+        // Semver range: starts with a comparison operator
+        if s.starts_with(['^', '~', '>', '<', '='])
             && let Ok(req) = semver::VersionReq::parse(s)
         {
             return Self::Range {
@@ -39,6 +43,9 @@ impl Specifier {
                 raw: s.to_owned(),
             };
         }
+        if s == LATEST {
+            return Self::Latest;
+        }
         // SHA
         if CommitSha::is_valid(s) {
             return Self::Sha(s.to_owned());
@@ -53,10 +60,16 @@ impl Specifier {
     /// - `"v6"` (Major) → `"^6"` (`Range`)
     /// - `"v4.2"` (Minor) → `"^4.2"` (`Range`)
     /// - `"v1.15.2"` (Patch) → `"~1.15.2"` (`Range`)
+    /// - `"^4.1"`, `">=4, <5"` → `Range`, as [`Specifier::parse`] reads them
+    /// - `"latest"` → `Latest`
     /// - `"main"` → `Ref("main")`
     /// - SHA → `Sha(...)`
     #[must_use]
     pub fn from_v1(v: &str) -> Self {
+        let parsed = Self::parse(v);
+        if matches!(parsed, Self::Range { .. } | Self::Latest) {
+            return parsed;
+        }
         let version = Version::from(v);
         if version.is_sha() {
             return Self::Sha(v.to_owned());
@@ -68,43 +81,82 @@ impl Specifier {
     }
 
     /// Check if this specifier matches a semver version.
+    ///
+    /// `Latest` matches every stable (non-prerelease) version.
     #[must_use]
     pub fn matches(&self, version: &semver::Version) -> bool {
         match self {
             Self::Range { req, .. } => req.matches(version),
+            Self::Latest => version.pre.is_empty(),
             Self::Ref(_) | Self::Sha(_) => false,
         }
     }
 
+    /// Pick the highest tag that satisfies this specifier.
+    ///
+    /// Returns None for Ref and Sha, or when no tag is in range.
+    #[must_use]
+    pub fn highest_match(&self, tags: &[Version]) -> Option<Version> {
+        tags.iter()
+            .filter_map(|tag| parse_semver(tag.as_str()).map(|parsed| (parsed, tag)))
+            .filter(|(parsed, _)| self.matches(parsed))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, tag)| tag.clone())
+    }
+
     /// Get the tag name used for GitHub API lookups (e.g., `"^6"` → `"v6"`).
+    ///
+    /// Compound ranges like `">=4, <6"` have no single floating tag and are
+    /// returned verbatim; check [`Specifier::is_floating`] before using the result.
     #[must_use]
     pub fn to_lookup_tag(&self) -> String {
         match self {
             Self::Range { raw, .. } => {
-                let rest = raw
-                    .strip_prefix('^')
-                    .or_else(|| raw.strip_prefix('~'))
-                    .unwrap_or(raw);
-                format!("v{rest}")
+                Self::floating_rest(raw).map_or_else(|| raw.clone(), |rest| format!("v{rest}"))
             }
+            Self::Latest => LATEST.to_owned(),
             Self::Ref(s) | Self::Sha(s) => s.clone(),
         }
     }
 
+    /// Returns true for `^`/`~` ranges, which map onto a floating tag like `v6`.
+    ///
+    /// Other ranges (`">=4, <6"`, `"<5"`) must be resolved against the full tag list.
+    #[must_use]
+    pub fn is_floating(&self) -> bool {
+        match self {
+            Self::Range { raw, .. } => Self::floating_rest(raw).is_some(),
+            Self::Latest | Self::Ref(_) | Self::Sha(_) => false,
+        }
+    }
+
+    /// Strip a leading `^` or `~` from a single-comparator range.
+    fn floating_rest(raw: &str) -> Option<&str> {
+        raw.strip_prefix('^')
+            .or_else(|| raw.strip_prefix('~'))
+            .filter(|rest| !rest.contains(','))
+    }
+
     /// Returns true if this specifier is a direct SHA.
     #[must_use]
     pub const fn is_sha(&self) -> bool {
         matches!(self, Self::Sha(_))
     }
 
-    /// Returns the precision of a Range specifier (Major/Minor/Patch).
-    /// Returns None for Ref and Sha.
+    /// Returns true if this specifier is the `latest` keyword.
+    #[must_use]
+    pub const fn is_latest(&self) -> bool {
+        matches!(self, Self::Latest)
+    }
+
+    /// Returns the precision of a floating `^`/`~` Range specifier (Major/Minor/Patch).
+    /// Returns None for Latest, Ref, Sha, and compound ranges.
     #[must_use]
     pub fn precision(&self) -> Option<VersionPrecision> {
         match self {
             Self::Range { raw, .. } => {
-                // Strip the operator (first char) and count dot-separated components
-                let rest = raw.get(1..)?;
+                // Strip the operator and count dot-separated components
+                let rest = Self::floating_rest(raw)?;
                 match rest.split('.').count() {
                     1 => Some(VersionPrecision::Major),
                     2 => Some(VersionPrecision::Minor),
@@ -112,16 +164,16 @@ impl Specifier {
                     _ => None,
                 }
             }
-            Self::Ref(_) | Self::Sha(_) => None,
+            Self::Latest | Self::Ref(_) | Self::Sha(_) => None,
         }
     }
 
-    /// Returns the range operator character ('^' or '~') for a Range specifier.
+    /// Returns the leading operator character (e.g. '^' or '~') for a Range specifier.
     #[must_use]
     pub fn operator(&self) -> Option<char> {
         match self {
             Self::Range { raw, .. } => raw.chars().next(),
-            Self::Ref(_) | Self::Sha(_) => None,
+            Self::Latest | Self::Ref(_) | Self::Sha(_) => None,
         }
     }
 
@@ -130,6 +182,7 @@ impl Specifier {
     pub const fn as_str(&self) -> &str {
         match self {
             Self::Range { raw, .. } => raw.as_str(),
+            Self::Latest => LATEST,
             Self::Ref(s) | Self::Sha(s) => s.as_str(),
         }
     }
@@ -141,9 +194,11 @@ impl PartialEq for Specifier {
             (Self::Range { raw: a, .. }, Self::Range { raw: b, .. })
             | (Self::Ref(a), Self::Ref(b))
             | (Self::Sha(a), Self::Sha(b)) => a == b,
-            (Self::Range { .. }, Self::Ref(_) | Self::Sha(_))
-            | (Self::Ref(_), Self::Range { .. } | Self::Sha(_))
-            | (Self::Sha(_), Self::Range { .. } | Self::Ref(_)) => false,
+            (Self::Latest, Self::Latest) => true,
+            (Self::Range { .. }, Self::Latest | Self::Ref(_) | Self::Sha(_))
+            | (Self::Latest, Self::Range { .. } | Self::Ref(_) | Self::Sha(_))
+            | (Self::Ref(_), Self::Range { .. } | Self::Latest | Self::Sha(_))
+            | (Self::Sha(_), Self::Range { .. } | Self::Latest | Self::Ref(_)) => false,
         }
     }
 }
@@ -165,14 +220,22 @@ impl std::hash::Hash for Specifier {
                 2_u8.hash(state);
                 s.hash(state);
             }
+            Self::Latest => 3_u8.hash(state),
         }
     }
 }
 
+impl serde::Serialize for Specifier {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
 impl fmt::Display for Specifier {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Range { raw, .. } => write!(f, "{raw}"),
+            Self::Latest => write!(f, "{LATEST}"),
             Self::Ref(s) | Self::Sha(s) => write!(f, "{s}"),
         }
     }
@@ -192,7 +255,8 @@ impl From<&str> for Specifier {
 
 /// Attempts to parse a version string into a semver `Version`.
 /// Handles common formats like `"v4"`, `"v4.1"`, `"v4.1.2"`, `"4.1.2"`.
-pub(super) fn parse_semver(version: &str) -> Option<semver::Version> {
+#[must_use]
+pub fn parse_semver(version: &str) -> Option<semver::Version> {
     // Strip leading 'v' or 'V' if present
     let normalized = version
         .strip_prefix('v')
@@ -246,7 +310,88 @@ pub(super) fn higher_version<'ver>(a: &'ver Version, b: &'ver Version) -> &'ver
     reason = "tests use unwrap, indexing, and other patterns freely"
 )]
 mod tests {
-    use super::{Version, parse_semver};
+    use super::{Specifier, Version, VersionPrecision, parse_semver};
+
+    #[test]
+    fn parse_compound_range() {
+        let spec = Specifier::parse(">=4, <6");
+        assert!(matches!(spec, Specifier::Range { .. }));
+        assert!(spec.matches(&semver::Version::new(5, 2, 0)));
+        assert!(!spec.matches(&semver::Version::new(6, 0, 0)));
+        assert!(!spec.is_floating());
+        assert!(spec.precision().is_none());
+        assert_eq!(spec.to_lookup_tag(), ">=4, <6");
+    }
+
+    #[test]
+    fn parse_caret_range_is_floating() {
+        let spec = Specifier::parse("^4.1");
+        assert!(spec.is_floating());
+        assert_eq!(spec.precision(), Some(VersionPrecision::Minor));
+        assert_eq!(spec.to_lookup_tag(), "v4.1");
+    }
+
+    #[test]
+    fn highest_match_picks_highest_tag_in_range() {
+        let tags = vec![
+            Version::from("v4.2.0"),
+            Version::from("v5.1.0"),
+            Version::from("v5"),
+            Version::from("v6.0.0"),
+            Version::from("main"),
+        ];
+        assert_eq!(
+            Specifier::parse(">=4, <6").highest_match(&tags),
+            Some(Version::from("v5.1.0"))
+        );
+        assert!(Specifier::parse(">=7").highest_match(&tags).is_none());
+    }
+
+    #[test]
+    fn parse_latest_keyword() {
+        assert_eq!(Specifier::parse("latest"), Specifier::Latest);
+        assert_eq!(Specifier::from_v1("latest"), Specifier::Latest);
+        assert_eq!(Specifier::Latest.to_string(), "latest");
+        assert!(Specifier::Latest.precision().is_none());
+    }
+
+    #[test]
+    fn from_v1_keeps_ranges_next_to_v_prefixed_versions() {
+        assert_eq!(Specifier::from_v1("v4"), Specifier::parse("^4"));
+        assert!(matches!(
+            Specifier::from_v1("^4.1"),
+            Specifier::Range { .. }
+        ));
+        assert!(matches!(
+            Specifier::from_v1(">=4, <5"),
+            Specifier::Range { .. }
+        ));
+        assert_eq!(
+            Specifier::from_v1("main"),
+            Specifier::Ref("main".to_owned())
+        );
+    }
+
+    #[test]
+    fn latest_picks_highest_stable_tag() {
+        let tags = vec![
+            Version::from("v4.2.0"),
+            Version::from("v5.1.0"),
+            Version::from("v6.0.0-beta.1"),
+        ];
+        assert_eq!(
+            Specifier::Latest.highest_match(&tags),
+            Some(Version::from("v5.1.0"))
+        );
+    }
+
+    #[test]
+    fn parse_invalid_range_is_ref() {
+        assert_eq!(
+            Specifier::parse(">=main"),
+            Specifier::Ref(">=main".to_owned())
+        );
+    }
 
     #[test]
     fn parse_semver_full() {
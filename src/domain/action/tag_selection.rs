@@ -1,6 +1,7 @@
 use super::identity::{ActionId, CommitSha, Version};
 use crate::domain::resolution::{Error as ResolutionError, ShaDescription, VersionRegistry};
 use std::collections::HashMap;
+use std::sync::Mutex;
 
 /// Accumulates `ShaDescription` results during a plan run, keyed by `(ActionId, CommitSha)`.
 /// Provides deduplication: `get_or_describe` calls the registry only on first access.
@@ -37,6 +38,31 @@ impl ShaIndex {
             }
         }
     }
+
+    /// [`ShaIndex::get_or_describe`] for an index shared between threads.
+    ///
+    /// The lock is not held while the registry is queried, so two threads may describe
+    /// the same commit once each; both store the same description.
+    ///
+    /// # Errors
+    ///
+    /// Propagates any error from `describe_sha`. On error, nothing is stored.
+    pub fn get_or_describe_shared<R: VersionRegistry>(
+        index: &Mutex<&mut Self>,
+        registry: &R,
+        id: &ActionId,
+        sha: &CommitSha,
+    ) -> Result<ShaDescription, ResolutionError> {
+        let key = (id.clone(), sha.clone());
+        if let Some(desc) = index.lock().ok().and_then(|i| i.cache.get(&key).cloned()) {
+            return Ok(desc);
+        }
+        let desc = registry.describe_sha(id, sha)?;
+        if let Ok(mut shared) = index.lock() {
+            shared.cache.insert(key, desc.clone());
+        }
+        Ok(desc)
+    }
 }
 
 impl Default for ShaIndex {
@@ -99,6 +99,7 @@ fn extract_at_precision(
 /// - `lock_version` — the resolved version from the lock file (if present, used as a floor)
 /// - `candidates` — all available version tags (these are actual tags, not parsed)
 /// - `allow_major` — if false (safe mode), constrain to same major version or major.minor range
+/// - `allow_prerelease` — if true, pre-release tags compete with stable ones on plain semver order
 ///
 /// # Returns
 ///
@@ -110,6 +111,7 @@ pub fn find_upgrade_candidate(
     lock_version: Option<&Version>,
     candidates: &[Version],
     allow_major: bool,
+    allow_prerelease: bool,
 ) -> Option<Action> {
     let precision = specifier.precision()?;
     let specifier_semver = parse_semver(specifier.as_str())?;
@@ -140,7 +142,7 @@ pub fn find_upgrade_candidate(
             }
 
             // Pre-release filtering: stable specifier excludes all pre-releases
-            if !manifest_is_prerelease && !parsed.pre.is_empty() {
+            if !manifest_is_prerelease && !allow_prerelease && !parsed.pre.is_empty() {
                 return None;
             }
 
@@ -166,6 +168,7 @@ pub fn find_upgrade_candidate(
         .max_by(|(_, a), (_, b)| {
             // Prefer stable over pre-release when specifier is pre-release
             match (a.pre.is_empty(), b.pre.is_empty()) {
+                _ if allow_prerelease => a.cmp(b),
                 (true, false) => std::cmp::Ordering::Greater,
                 (false, true) => std::cmp::Ordering::Less,
                 _ => a.cmp(b),
@@ -175,7 +178,10 @@ pub fn find_upgrade_candidate(
 
     // Determine if this is in-range or cross-range using VersionReq::matches
     if let Some(best_semver) = parse_semver(best_tag.as_str()) {
-        let is_in_range = specifier.matches(&best_semver);
+        // Semver ranges never match pre-releases, so judge an opted-in one by its release
+        let release = semver::Version::new(best_semver.major, best_semver.minor, best_semver.patch);
+        let is_in_range =
+            specifier.matches(&best_semver) || (allow_prerelease && specifier.matches(&release));
 
         if is_in_range {
             Some(Action::InRange {
@@ -194,6 +200,79 @@ pub fn find_upgrade_candidate(
     }
 }
 
+/// [`find_upgrade_candidate`] with the lock's own resolution as the floor and constraint.
+///
+/// `locked` is the specifier the lock entry was resolved under and its version. When that
+/// specifier is a range different from the manifest's (e.g. `~4.1.0` in the lock, `^4` in
+/// the manifest), safe mode only considers tags that satisfy both, so drifted precision
+/// does not jump further than the lock allowed.
+#[must_use]
+pub fn find_locked_candidate(
+    specifier: &Specifier,
+    locked: Option<(&Specifier, &Version)>,
+    candidates: &[Version],
+    allow_major: bool,
+    allow_prerelease: bool,
+) -> Option<Action> {
+    let Some((lock_specifier, lock_version)) = locked else {
+        return find_upgrade_candidate(specifier, None, candidates, allow_major, allow_prerelease);
+    };
+    if allow_major
+        || lock_specifier == specifier
+        || !matches!(lock_specifier, Specifier::Range { .. })
+    {
+        return find_upgrade_candidate(
+            specifier,
+            Some(lock_version),
+            candidates,
+            allow_major,
+            allow_prerelease,
+        );
+    }
+    let allowed: Vec<Version> = candidates
+        .iter()
+        .filter(|tag| parse_semver(tag.as_str()).is_some_and(|v| lock_specifier.matches(&v)))
+        .cloned()
+        .collect();
+    find_upgrade_candidate(
+        specifier,
+        Some(lock_version),
+        &allowed,
+        allow_major,
+        allow_prerelease,
+    )
+}
+
+/// Build the action for an explicit `ACTION@VERSION` pin, which may move in either direction.
+///
+/// The manifest specifier is derived from `target` the same way tidy derives it from a
+/// workflow ref, so pinning `v3` under `^4` yields `CrossRange` to `^3`. A pin whose
+/// specifier equals `current` stays `InRange`.
+#[must_use]
+pub fn pinned_action(current: &Specifier, target: &Version) -> Action {
+    let new_specifier = Specifier::from_v1(target.as_str());
+    if &new_specifier == current {
+        Action::InRange {
+            candidate: target.clone(),
+        }
+    } else {
+        Action::CrossRange {
+            candidate: target.clone(),
+            new_specifier,
+        }
+    }
+}
+
+/// True when `target` is a strictly lower semver than `floor`.
+/// Non-semver versions (branches, SHAs) are never considered lower.
+#[must_use]
+pub fn is_downgrade(floor: &Version, target: &Version) -> bool {
+    match (parse_semver(floor.as_str()), parse_semver(target.as_str())) {
+        (Some(floor_semver), Some(target_semver)) => target_semver < floor_semver,
+        _ => false,
+    }
+}
+
 /// Attempts to parse a version string into a semver Version.
 /// Handles common formats like "v4", "v4.1", "v4.1.2", "4.1.2".
 fn parse_semver(version: &str) -> Option<semver::Version> {
@@ -232,7 +311,42 @@ fn parse_semver(version: &str) -> Option<semver::Version> {
 
 #[cfg(test)]
 mod tests {
-    use super::{Action, ActionId, Candidate, Specifier, Version, find_upgrade_candidate};
+    use super::{
+        Action, ActionId, Candidate, Specifier, Version, find_locked_candidate,
+        find_upgrade_candidate, is_downgrade, pinned_action,
+    };
+
+    #[test]
+    fn pinned_action_to_lower_major_crosses_range() {
+        assert_eq!(
+            pinned_action(&Specifier::parse("^4"), &Version::from("v3")),
+            Action::CrossRange {
+                candidate: Version::from("v3"),
+                new_specifier: Specifier::parse("^3"),
+            }
+        );
+        assert_eq!(
+            pinned_action(&Specifier::parse("^4"), &Version::from("v4")),
+            Action::InRange {
+                candidate: Version::from("v4")
+            }
+        );
+    }
+
+    #[test]
+    fn is_downgrade_compares_semver() {
+        assert!(is_downgrade(
+            &Version::from("v4.2.0"),
+            &Version::from("v4.1.0")
+        ));
+        assert!(is_downgrade(&Version::from("v4"), &Version::from("v3")));
+        assert!(!is_downgrade(
+            &Version::from("v4.1.0"),
+            &Version::from("v4.1.0")
+        ));
+        assert!(!is_downgrade(&Version::from("v4"), &Version::from("v5")));
+        assert!(!is_downgrade(&Version::from("main"), &Version::from("v3")));
+    }
 
     #[test]
     fn find_upgrade_candidate_safe_mode_major_precision_in_range() {
@@ -247,7 +361,7 @@ mod tests {
         // Safe mode, major precision: stays within v4.x
         // Best candidate within major is v4.2.1 (in-range)
         assert_eq!(
-            find_upgrade_candidate(&specifier, None, &candidates, false),
+            find_upgrade_candidate(&specifier, None, &candidates, false, false),
             Some(Action::InRange {
                 candidate: Version::from("v4.2.1")
             })
@@ -265,7 +379,7 @@ mod tests {
         ];
         // Latest mode: no range constraint, returns highest (cross-range)
         assert_eq!(
-            find_upgrade_candidate(&specifier, None, &candidates, true),
+            find_upgrade_candidate(&specifier, None, &candidates, true, false),
             Some(Action::CrossRange {
                 candidate: Version::from("v6.1.0"),
                 new_specifier: Specifier::parse("^6"),
@@ -279,7 +393,7 @@ mod tests {
         let candidates = vec![Version::from("v5.0.0")];
         // Latest mode with minor precision: result should preserve minor precision
         assert_eq!(
-            find_upgrade_candidate(&specifier, None, &candidates, true),
+            find_upgrade_candidate(&specifier, None, &candidates, true, false),
             Some(Action::CrossRange {
                 candidate: Version::from("v5.0.0"),
                 new_specifier: Specifier::parse("^5.0"),
@@ -293,7 +407,7 @@ mod tests {
         let candidates = vec![Version::from("v5.0.0")];
         // Latest mode with patch precision (tilde): result should preserve tilde and patch precision
         assert_eq!(
-            find_upgrade_candidate(&specifier, None, &candidates, true),
+            find_upgrade_candidate(&specifier, None, &candidates, true, false),
             Some(Action::CrossRange {
                 candidate: Version::from("v5.0.0"),
                 new_specifier: Specifier::parse("~5.0.0"),
@@ -312,13 +426,39 @@ mod tests {
         ];
         // Safe mode with lock version as floor: v4.2.1 excluded, returns v4.3.0 (in-range)
         assert_eq!(
-            find_upgrade_candidate(&specifier, lock_version.as_ref(), &candidates, false),
+            find_upgrade_candidate(&specifier, lock_version.as_ref(), &candidates, false, false),
             Some(Action::InRange {
                 candidate: Version::from("v4.3.0")
             })
         );
     }
 
+    #[test]
+    fn find_locked_candidate_keeps_drifted_lock_precision() {
+        let candidates = vec![
+            Version::from("v4.1.0"),
+            Version::from("v4.1.3"),
+            Version::from("v4.6.0"),
+        ];
+        let lock_specifier = Specifier::parse("~4.1.0");
+        let lock_version = Version::from("v4.1.0");
+        let locked = Some((&lock_specifier, &lock_version));
+        // Safe mode stays within the lock's ~4.1.0 even though the manifest says ^4
+        assert_eq!(
+            find_locked_candidate(&Specifier::parse("^4"), locked, &candidates, false, false),
+            Some(Action::InRange {
+                candidate: Version::from("v4.1.3")
+            })
+        );
+        // Latest mode ignores the lock's range
+        assert_eq!(
+            find_locked_candidate(&Specifier::parse("^4"), locked, &candidates, true, false),
+            Some(Action::InRange {
+                candidate: Version::from("v4.6.0")
+            })
+        );
+    }
+
     #[test]
     fn find_upgrade_candidate_stable_filters_prerelease() {
         let specifier = Specifier::parse("^2");
@@ -329,7 +469,7 @@ mod tests {
         ];
         // Stable specifier: pre-releases filtered out
         assert_eq!(
-            find_upgrade_candidate(&specifier, None, &candidates, true),
+            find_upgrade_candidate(&specifier, None, &candidates, true, false),
             Some(Action::CrossRange {
                 candidate: Version::from("v3.0.0"),
                 new_specifier: Specifier::parse("^3"),
@@ -337,19 +477,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn find_upgrade_candidate_opted_in_prerelease() {
+        let specifier = Specifier::parse("^4");
+        let lock_version = Some(Version::from("v4.2.0"));
+        let candidates = vec![
+            Version::from("v4.2.0"),
+            Version::from("v4.2.1"),
+            Version::from("v4.3.0-beta.1"),
+        ];
+        // Opted in: the newest tag wins even though it is a pre-release, within range
+        assert_eq!(
+            find_upgrade_candidate(&specifier, lock_version.as_ref(), &candidates, false, true),
+            Some(Action::InRange {
+                candidate: Version::from("v4.3.0-beta.1")
+            })
+        );
+        // Not opted in: stable only
+        assert_eq!(
+            find_upgrade_candidate(&specifier, lock_version.as_ref(), &candidates, false, false),
+            Some(Action::InRange {
+                candidate: Version::from("v4.2.1")
+            })
+        );
+    }
+
     #[test]
     fn find_upgrade_candidate_non_semver_specifier() {
         let specifier = Specifier::Ref("main".to_owned());
         let candidates = vec![Version::from("v5")];
         // Non-semver specifier returns None (no precision)
-        assert!(find_upgrade_candidate(&specifier, None, &candidates, true).is_none());
+        assert!(find_upgrade_candidate(&specifier, None, &candidates, true, false).is_none());
     }
 
     #[test]
     fn find_upgrade_candidate_no_candidates() {
         let specifier = Specifier::parse("^4");
         let candidates: Vec<Version> = vec![];
-        assert!(find_upgrade_candidate(&specifier, None, &candidates, true).is_none());
+        assert!(find_upgrade_candidate(&specifier, None, &candidates, true, false).is_none());
     }
 
     #[test]
@@ -68,25 +68,30 @@ impl UsesRef {
     /// Interpret this reference into domain types.
     ///
     /// Rules applied:
-    /// - If comment exists, normalize it (add 'v' prefix if missing) and use as version
+    /// - If comment exists, take the version it names (see [`pinned_version`]), normalize
+    ///   it (add 'v' prefix if missing) and use as version
     /// - If comment exists and `uses_ref` is a 40-char hex SHA, store the SHA
     /// - If no comment, use `uses_ref` as version (could be tag like "v4" or SHA)
     #[must_use]
     pub fn interpret(&self) -> WorkflowAction {
-        let (version, sha) = self.comment.as_ref().map_or_else(
-            || {
-                // No comment, use the ref as-is, no SHA stored
-                (Version::from(self.uses_ref.as_str()), None)
-            },
-            |comment| {
-                // Has a comment - use normalized comment as version
-                let version = Version::normalized(comment);
-                // If ref is a SHA, store it
-                let sha = CommitSha::is_valid(&self.uses_ref)
-                    .then(|| CommitSha::from(self.uses_ref.as_str()));
-                (version, sha)
-            },
-        );
+        let (version, sha) = self
+            .comment
+            .as_deref()
+            .and_then(pinned_version)
+            .map_or_else(
+                || {
+                    // No comment, use the ref as-is, no SHA stored
+                    (Version::from(self.uses_ref.as_str()), None)
+                },
+                |comment| {
+                    // Has a comment - use normalized comment as version
+                    let version = Version::normalized(comment);
+                    // If ref is a SHA, store it
+                    let sha = CommitSha::is_valid(&self.uses_ref)
+                        .then(|| CommitSha::from(self.uses_ref.as_str()));
+                    (version, sha)
+                },
+            );
 
         WorkflowAction {
             id: ActionId::from(self.action_name.as_str()),
@@ -96,9 +101,28 @@ impl UsesRef {
     }
 }
 
+/// Prefix of the comments ratchet leaves after a pinned `uses:`, as in
+/// `# ratchet:actions/checkout@v4`.
+pub const RATCHET_PREFIX: &str = "ratchet:";
+
+/// The version a `uses:` comment names. gx and pinact write it bare (`# v4.1.0`);
+/// ratchet writes the original reference (`# ratchet:actions/checkout@v4`) and
+/// pin-github-action writes `# pin@v4`. `# ratchet:exclude` marks a reference left
+/// unpinned on purpose, and names no version.
+#[must_use]
+pub fn pinned_version(comment: &str) -> Option<&str> {
+    if let Some(original) = comment.strip_prefix(RATCHET_PREFIX) {
+        return original
+            .rsplit_once('@')
+            .map(|(_, version)| version)
+            .filter(|version| !version.is_empty());
+    }
+    Some(comment.strip_prefix("pin@").unwrap_or(comment))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{CommitSha, RefType, UsesRef};
+    use super::{CommitSha, RefType, UsesRef, pinned_version};
 
     #[test]
     fn ref_type_display() {
@@ -192,4 +216,34 @@ mod tests {
         assert_eq!(interpreted.version.as_str(), "v4");
         assert!(interpreted.sha.is_none()); // Short ref is not stored as SHA
     }
+
+    #[test]
+    fn pinned_version_reads_ratchet_and_pin_comments() {
+        assert_eq!(pinned_version("v4.1.0"), Some("v4.1.0"));
+        assert_eq!(pinned_version("ratchet:actions/checkout@v4"), Some("v4"));
+        assert_eq!(pinned_version("ratchet:actions/aws/ec2@main"), Some("main"));
+        assert_eq!(pinned_version("pin@v2"), Some("v2"));
+        assert_eq!(pinned_version("ratchet:exclude"), None);
+    }
+
+    #[test]
+    fn uses_ref_interpret_takes_the_version_from_a_ratchet_comment() {
+        let pinned = UsesRef::new(
+            "actions/checkout".to_owned(),
+            "abc123def456789012345678901234567890abcd".to_owned(),
+            Some("ratchet:actions/checkout@v4".to_owned()),
+        )
+        .interpret();
+        assert_eq!(pinned.version.as_str(), "v4");
+        assert!(pinned.sha.is_some());
+
+        let excluded = UsesRef::new(
+            "actions/checkout".to_owned(),
+            "main".to_owned(),
+            Some("ratchet:exclude".to_owned()),
+        )
+        .interpret();
+        assert_eq!(excluded.version.as_str(), "main");
+        assert!(excluded.sha.is_none());
+    }
 }
@@ -3,7 +3,8 @@ use super::action::resolved::ResolvedAction;
 use super::action::spec::Spec;
 use super::action::specifier::Specifier;
 use super::lock::LockEntry;
-use super::manifest::overrides::ActionOverride;
+use super::manifest::overrides::{self, ActionOverride};
+use super::workflow_actions::{JobId, StepIndex};
 use std::path::PathBuf;
 
 /// Describes the changes to apply to a manifest file.
@@ -29,6 +30,26 @@ impl ManifestDiff {
             && self.overrides_added.is_empty()
             && self.overrides_removed.is_empty()
     }
+
+    /// This diff with every list sorted by action ID, and the overrides of each action by
+    /// workflow, job, and step, so applying it writes the same file on every run.
+    #[must_use]
+    pub fn sorted(mut self) -> Self {
+        self.added
+            .sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        self.removed.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        self.updated
+            .sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        self.overrides_added.sort_by(|(a_id, a), (b_id, b)| {
+            (a_id.as_str(), a.canonical_key()).cmp(&(b_id.as_str(), b.canonical_key()))
+        });
+        for (_, removed) in &mut self.overrides_removed {
+            overrides::sort(removed);
+        }
+        self.overrides_removed
+            .sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        self
+    }
 }
 
 /// Patch for updating specific fields of a lock entry.
@@ -61,14 +82,25 @@ impl LockDiff {
 #[derive(Debug)]
 pub struct WorkflowPatch {
     pub path: PathBuf,
+    /// Pins for every step using the action.
     pub pins: Vec<ResolvedAction>,
+    /// Pins for single steps, which take precedence over `pins`.
+    pub steps: Vec<StepPin>,
+}
+
+/// A pin for one step, addressed by its job and its index among the job's steps.
+#[derive(Debug, Clone)]
+pub struct StepPin {
+    pub job: JobId,
+    pub step: StepIndex,
+    pub pin: ResolvedAction,
 }
 
 #[cfg(test)]
 mod tests {
     use super::{LockDiff, LockEntry, ManifestDiff};
     use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
-    use crate::domain::action::resolved::Commit;
+    use crate::domain::action::resolved::{Commit, Provenance};
     use crate::domain::action::spec::Spec;
     use crate::domain::action::specifier::Specifier;
     use crate::domain::action::uses_ref::RefType;
@@ -106,6 +138,7 @@ mod tests {
                         repository: Repository::from("actions/checkout"),
                         ref_type: Some(RefType::Tag),
                         date: CommitDate::from("2026-01-01T00:00:00Z"),
+                        provenance: Provenance::default(),
                     },
                 },
             )],
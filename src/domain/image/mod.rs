@@ -0,0 +1,9 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// Container image references (`alpine:3.19`, `ghcr.io/org/tool@sha256:…`) and digests.
+mod reference;
+/// Resolving image tags to digests, and the errors it can fail with.
+mod resolution;
+
+pub use reference::{Digest, Located, Reference};
+pub use resolution::{DigestRegistry, Error};
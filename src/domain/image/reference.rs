@@ -0,0 +1,188 @@
+use crate::domain::workflow_actions::Location;
+use std::fmt;
+
+/// Registry that serves images named without a host, such as `alpine` or `org/tool`.
+const DOCKER_HUB: &str = "registry-1.docker.io";
+
+/// A content digest identifying one image manifest, e.g. `sha256:9f86…`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Digest(String);
+
+impl Digest {
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Check if a string is a full `sha256:` digest (64 hexadecimal characters).
+    #[must_use]
+    pub fn is_valid(s: &str) -> bool {
+        s.strip_prefix("sha256:")
+            .is_some_and(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+    }
+}
+
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for Digest {
+    fn from(s: &str) -> Self {
+        Self(s.to_owned())
+    }
+}
+
+/// A container image reference as written in a workflow: a name with a tag, a
+/// digest, or both (`node:20`, `ghcr.io/org/tool@sha256:…`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    /// The image name, with its registry host when one is given.
+    name: String,
+    /// The tag, e.g. `3.19`; from the version comment when the reference is pinned.
+    tag: Option<String>,
+    /// The digest the reference is pinned to.
+    digest: Option<Digest>,
+}
+
+impl Reference {
+    /// Parse `name[:tag][@digest]`, taking the tag from `comment` when the reference
+    /// is pinned to a digest without one (`alpine@sha256:… # 3.19`).
+    ///
+    /// Returns `None` for an empty name or a malformed digest.
+    #[must_use]
+    pub fn parse(reference: &str, comment: Option<&str>) -> Option<Self> {
+        let (rest, digest) = match reference.split_once('@') {
+            Some((rest, digest)) if Digest::is_valid(digest) => (rest, Some(Digest::from(digest))),
+            Some(_) => return None,
+            None => (reference, None),
+        };
+        let (name, written_tag) = match rest.rsplit_once(':') {
+            Some((name, tag)) if !tag.contains('/') => (name, Some(tag.to_owned())),
+            Some(_) | None => (rest, None),
+        };
+        let tag = written_tag.or_else(|| {
+            digest
+                .as_ref()
+                .and(comment.map(str::trim))
+                .filter(|text| !text.is_empty() && !text.contains(' '))
+                .map(str::to_owned)
+        });
+        (!name.is_empty()).then(|| Self {
+            name: name.to_owned(),
+            tag,
+            digest,
+        })
+    }
+
+    /// The image name as written, e.g. `alpine` or `ghcr.io/org/tool`.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    #[must_use]
+    pub fn digest(&self) -> Option<&Digest> {
+        self.digest.as_ref()
+    }
+
+    /// The host serving the image; Docker Hub for names without one.
+    #[must_use]
+    pub fn registry(&self) -> &str {
+        match self.name.split_once('/') {
+            Some((host, _)) if host.contains(['.', ':']) || host == "localhost" => host,
+            Some(_) | None => DOCKER_HUB,
+        }
+    }
+
+    /// The repository on the registry, e.g. `library/alpine` for `alpine`.
+    #[must_use]
+    pub fn repository(&self) -> String {
+        match self.name.split_once('/') {
+            Some((host, path)) if host.contains(['.', ':']) || host == "localhost" => {
+                path.to_owned()
+            }
+            Some(_) => self.name.clone(),
+            None => format!("library/{}", self.name),
+        }
+    }
+
+    /// The key this image is locked under: `name:tag`, or `None` without a tag.
+    #[must_use]
+    pub fn lock_key(&self) -> Option<String> {
+        self.tag.as_ref().map(|tag| format!("{}:{tag}", self.name))
+    }
+
+    /// The reference pinned to `digest`: `name@digest`, with the tag left to a comment.
+    #[must_use]
+    pub fn pinned(&self, digest: &Digest) -> String {
+        format!("{}@{digest}", self.name)
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if let Some(tag) = &self.tag {
+            write!(f, ":{tag}")?;
+        }
+        if let Some(digest) = &self.digest {
+            write!(f, "@{digest}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An image reference with the workflow location it was found at.
+#[derive(Debug, Clone)]
+pub struct Located {
+    /// The image as referenced.
+    pub image: Reference,
+    /// Where the reference is.
+    pub location: Location,
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{Digest, Reference};
+
+    const DIGEST: &str = "sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn parse_splits_name_tag_and_registry() {
+        let hub = Reference::parse("alpine:3.19", None).unwrap();
+        let ghcr = Reference::parse("ghcr.io/org/tool:v1", None).unwrap();
+        let local = Reference::parse("localhost:5000/tool", None).unwrap();
+
+        assert_eq!(
+            (hub.registry(), hub.repository().as_str(), hub.tag()),
+            ("registry-1.docker.io", "library/alpine", Some("3.19"))
+        );
+        assert_eq!(
+            (ghcr.registry(), ghcr.repository().as_str(), ghcr.tag()),
+            ("ghcr.io", "org/tool", Some("v1"))
+        );
+        assert_eq!(
+            (local.registry(), local.repository().as_str(), local.tag()),
+            ("localhost:5000", "tool", None)
+        );
+        assert_eq!(hub.lock_key().as_deref(), Some("alpine:3.19"));
+    }
+
+    #[test]
+    fn parse_takes_the_tag_of_a_pinned_reference_from_its_comment() {
+        let pinned = Reference::parse(&format!("alpine@{DIGEST}"), Some("3.19")).unwrap();
+
+        assert_eq!(pinned.digest(), Some(&Digest::from(DIGEST)));
+        assert_eq!(pinned.lock_key().as_deref(), Some("alpine:3.19"));
+        assert_eq!(pinned.to_string(), format!("alpine:3.19@{DIGEST}"));
+        assert_eq!(Reference::parse("alpine@sha256:short", None), None);
+    }
+}
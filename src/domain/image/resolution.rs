@@ -0,0 +1,25 @@
+use super::reference::{Digest, Reference};
+use thiserror::Error;
+
+/// Errors that can occur when resolving an image tag to a digest.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Error {
+    /// Network access is disabled.
+    #[error("network access is disabled (--offline)")]
+    Offline,
+
+    /// The registry could not resolve the tag.
+    #[error("failed to resolve {image}: {reason}")]
+    ResolveFailed { image: String, reason: String },
+}
+
+/// A registry that resolves image tags to the digest they currently point to.
+pub trait DigestRegistry {
+    /// The digest of the manifest `image`'s tag points to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Offline`] if network access is disabled.
+    /// Returns [`Error::ResolveFailed`] if the registry cannot resolve the tag.
+    fn digest(&self, image: &Reference) -> Result<Digest, Error>;
+}
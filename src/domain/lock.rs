@@ -2,7 +2,8 @@ use super::action::identity::Version;
 use super::action::resolved::Commit;
 use super::action::spec::Spec;
 use super::diff::LockDiff;
-use std::collections::{HashMap, HashSet};
+use super::image::Digest;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// A single lock entry: resolved version + commit metadata.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -22,13 +23,24 @@ pub struct LockEntry {
 pub struct Lock {
     /// Flat map of specifier to resolved entry.
     entries: HashMap<Spec, LockEntry>,
+    /// Container image digests, keyed by `name:tag`.
+    images: BTreeMap<String, Digest>,
+    /// Hash of the manifest the lock was written for, if recorded.
+    manifest_hash: Option<String>,
+    /// When other manifests share the lock, the entries they need, which pruning keeps.
+    shared: Option<HashSet<Spec>>,
 }
 
 impl Lock {
     /// Create a `Lock` from a flat entry map.
     #[must_use]
     pub fn new(entries: HashMap<Spec, LockEntry>) -> Self {
-        Self { entries }
+        Self {
+            entries,
+            images: BTreeMap::new(),
+            manifest_hash: None,
+            shared: None,
+        }
     }
 
     /// Look up the lock entry for a spec.
@@ -73,9 +85,16 @@ impl Lock {
         }
     }
 
-    /// Retain only entries for the given specs, removing all others.
+    /// Share the lock with other manifests, whose entries `keys` are kept by
+    /// [`Self::retain`] and whose images [`Self::retain_images`] leaves alone.
+    pub fn share<I: IntoIterator<Item = Spec>>(&mut self, keys: I) {
+        self.shared.get_or_insert_default().extend(keys);
+    }
+
+    /// Retain only entries for the given specs, and those other manifests sharing the
+    /// lock need, removing all others.
     pub fn retain(&mut self, keys: &[Spec]) {
-        let keep: HashSet<&Spec> = keys.iter().collect();
+        let keep: HashSet<&Spec> = keys.iter().chain(self.shared.iter().flatten()).collect();
         self.entries.retain(|k, _| keep.contains(k));
     }
 
@@ -84,10 +103,45 @@ impl Lock {
         self.entries.iter()
     }
 
-    /// Check if the lock is empty (no entries).
+    /// Check if the lock is empty (no entries and no images).
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.entries.is_empty()
+        self.entries.is_empty() && self.images.is_empty()
+    }
+
+    /// The digest locked for the image `key` (`name:tag`).
+    #[must_use]
+    pub fn image(&self, key: &str) -> Option<&Digest> {
+        self.images.get(key)
+    }
+
+    /// Lock the image `key` (`name:tag`) to `digest`.
+    pub fn set_image(&mut self, key: String, digest: Digest) {
+        self.images.insert(key, digest);
+    }
+
+    /// Retain only the images with the given keys, removing all others. A shared lock
+    /// keeps every image, since the other manifests' workflows may use it.
+    pub fn retain_images(&mut self, keys: &[String]) {
+        if self.shared.is_none() {
+            self.images.retain(|key, _| keys.contains(key));
+        }
+    }
+
+    /// Iterate over locked images, sorted by key.
+    pub fn images(&self) -> impl Iterator<Item = (&String, &Digest)> {
+        self.images.iter()
+    }
+
+    /// The hash of the manifest this lock was written for, if recorded.
+    #[must_use]
+    pub fn manifest_hash(&self) -> Option<&str> {
+        self.manifest_hash.as_deref()
+    }
+
+    /// Record the hash of the manifest this lock is written for.
+    pub fn set_manifest_hash(&mut self, hash: String) {
+        self.manifest_hash = Some(hash);
     }
 
     /// Compute the diff between this lock (`before`) and `other` (`after`).
@@ -126,12 +180,27 @@ impl Lock {
             }
         }
 
+        let by_spec = |a: &Spec, b: &Spec| {
+            (a.id.as_str(), a.specifier.as_str()).cmp(&(b.id.as_str(), b.specifier.as_str()))
+        };
+        added.sort_by(|(a, _), (b, _)| by_spec(a, b));
+        removed.sort_by(by_spec);
         LockDiff {
             added,
             removed,
             updated: vec![],
         }
     }
+
+    /// Apply a diff produced by [`Lock::diff`]: drop removed specs, then insert added entries.
+    pub fn apply(&mut self, diff: &LockDiff) {
+        for spec in &diff.removed {
+            self.entries.remove(spec);
+        }
+        for (spec, entry) in &diff.added {
+            self.entries.insert(spec.clone(), entry.clone());
+        }
+    }
 }
 
 #[cfg(test)]
@@ -141,10 +210,10 @@ impl Lock {
     reason = "tests use unwrap, indexing, and other patterns freely"
 )]
 mod tests {
-    use super::Lock;
+    use super::{Digest, Lock};
     use crate::domain::action::identity::ActionId;
     use crate::domain::action::identity::{CommitDate, CommitSha, Repository, Version};
-    use crate::domain::action::resolved::Commit;
+    use crate::domain::action::resolved::{Commit, Provenance};
     use crate::domain::action::spec::Spec;
     use crate::domain::action::specifier::Specifier;
     use crate::domain::action::uses_ref::RefType;
@@ -159,6 +228,7 @@ mod tests {
             repository: Repository::from("actions/checkout"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         }
     }
 
@@ -245,6 +315,22 @@ mod tests {
         assert!(!lock.has(&make_key("actions/old-action", "^1")));
     }
 
+    #[test]
+    fn retain_keeps_what_the_manifests_sharing_the_lock_need() {
+        let mut lock = Lock::default();
+        set_action(&mut lock, "actions/checkout", "^4", "abc123", "v4.2.1");
+        set_action(&mut lock, "actions/checkout", "^3", "def456", "v3.6.0");
+        lock.set_image("node:20".to_owned(), Digest::from("sha256:aaa"));
+        lock.share([make_key("actions/checkout", "^3")]);
+
+        lock.retain(&[make_key("actions/checkout", "^4")]);
+        lock.retain_images(&[]);
+
+        assert!(lock.has(&make_key("actions/checkout", "^4")));
+        assert!(lock.has(&make_key("actions/checkout", "^3")));
+        assert!(lock.image("node:20").is_some());
+    }
+
     #[test]
     fn update_existing_sha() {
         let mut lock = Lock::default();
@@ -302,6 +388,7 @@ mod tests {
                 repository: Repository::from("actions/checkout"),
                 ref_type: Some(RefType::Branch),
                 date: CommitDate::from("2026-01-01T00:00:00Z"),
+                provenance: Provenance::default(),
             },
         );
         assert!(lock.is_complete(&spec));
@@ -416,4 +503,37 @@ mod tests {
             "replaced entry should appear in removed"
         );
     }
+
+    #[test]
+    fn apply_diff_reproduces_target_lock() {
+        let mut before = Lock::default();
+        set_action(
+            &mut before,
+            "actions/checkout",
+            "^4",
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "v4.0.0",
+        );
+        set_action(
+            &mut before,
+            "actions/cache",
+            "^3",
+            "cccccccccccccccccccccccccccccccccccccccc",
+            "v3.0.0",
+        );
+        let mut after = Lock::default();
+        set_action(
+            &mut after,
+            "actions/checkout",
+            "^4",
+            "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+            "v4.1.0",
+        );
+
+        let diff = before.diff(&after);
+        before.apply(&diff);
+        let entry = before.get(&make_key("actions/checkout", "^4")).unwrap();
+        assert_eq!(entry.version, Version::from("v4.1.0"));
+        assert!(before.get(&make_key("actions/cache", "^3")).is_none());
+    }
 }
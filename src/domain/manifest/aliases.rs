@@ -0,0 +1,68 @@
+use crate::domain::action::identity::ActionId;
+use std::collections::BTreeMap;
+
+/// Short names for actions, from the `[aliases]` section of the manifest: `checkout`
+/// for `actions/checkout`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Aliases(BTreeMap<String, ActionId>);
+
+impl Aliases {
+    /// The action `name` stands for: the action it is an alias of, or `name` itself.
+    #[must_use]
+    pub fn expand(&self, name: &str) -> ActionId {
+        self.0
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| ActionId::from(name))
+    }
+
+    /// How `id` is written where aliases are accepted: its alias when it has one, or the
+    /// action ID itself.
+    #[must_use]
+    pub fn spelling<'id>(&'id self, id: &'id ActionId) -> &'id str {
+        self.0
+            .iter()
+            .find(|(_, target)| *target == id)
+            .map_or(id.as_str(), |(name, _)| name.as_str())
+    }
+
+    /// Each alias with the action it stands for, sorted by alias.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ActionId)> {
+        self.0.iter().map(|(name, id)| (name.as_str(), id))
+    }
+
+    /// Whether no alias is declared.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, S)> for Aliases {
+    fn from_iter<I: IntoIterator<Item = (S, S)>>(aliases: I) -> Self {
+        Self(
+            aliases
+                .into_iter()
+                .map(|(name, id)| (name.into(), ActionId::from(id.into())))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Aliases;
+    use crate::domain::action::identity::ActionId;
+
+    #[test]
+    fn aliases_expand_to_their_action_and_other_names_stay_as_they_are() {
+        let aliases: Aliases = [("checkout", "actions/checkout")].into_iter().collect();
+        let checkout = ActionId::from("actions/checkout");
+        let cache = ActionId::from("actions/cache");
+
+        assert_eq!(aliases.expand("checkout"), checkout);
+        assert_eq!(aliases.expand("actions/cache"), cache);
+        assert_eq!(aliases.spelling(&checkout), "checkout");
+        assert_eq!(aliases.spelling(&cache), "actions/cache");
+    }
+}
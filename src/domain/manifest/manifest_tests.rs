@@ -0,0 +1,272 @@
+use super::{ActionId, ActionOverride, Manifest, Specifier};
+use crate::domain::workflow_actions::{JobId, Location, StepIndex, WorkflowPath};
+
+fn make_loc(workflow: &str, job: Option<&str>, step: Option<u16>) -> Location {
+    Location {
+        workflow: WorkflowPath::new(workflow),
+        job: job.map(JobId::from),
+        step: step.map(StepIndex::from),
+        step_name: None,
+        line: None,
+    }
+}
+
+#[test]
+fn set_and_get() {
+    let mut m = Manifest::default();
+    m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    assert_eq!(
+        m.get(&ActionId::from("actions/checkout")),
+        Some(&Specifier::parse("^4"))
+    );
+}
+
+#[test]
+fn has_and_remove() {
+    let mut m = Manifest::default();
+    m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    assert!(m.has(&ActionId::from("actions/checkout")));
+    m.remove(&ActionId::from("actions/checkout"));
+    assert!(!m.has(&ActionId::from("actions/checkout")));
+}
+
+#[test]
+fn remove_also_clears_overrides() {
+    let mut m = Manifest::default();
+    m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    m.add_override(
+        ActionId::from("actions/checkout"),
+        ActionOverride {
+            workflow: WorkflowPath::new(".github/workflows/ci.yml"),
+            job: None,
+            step: None,
+            version: Specifier::parse("^3"),
+        },
+    );
+    m.remove(&ActionId::from("actions/checkout"));
+    assert!(
+        m.overrides_for(&ActionId::from("actions/checkout"))
+            .is_empty()
+    );
+}
+
+#[test]
+fn is_empty() {
+    let mut m = Manifest::default();
+    assert!(m.is_empty());
+    m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    assert!(!m.is_empty());
+}
+
+#[test]
+fn specs() {
+    let mut m = Manifest::default();
+    m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    m.set(ActionId::from("actions/setup-node"), Specifier::parse("^3"));
+    assert_eq!(m.specs().count(), 2);
+}
+
+#[test]
+fn resolve_version_returns_global_when_no_override() {
+    let mut m = Manifest::default();
+    m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    let loc = make_loc(".github/workflows/ci.yml", Some("build"), Some(0));
+    assert_eq!(
+        m.resolve_version(&ActionId::from("actions/checkout"), &loc),
+        Some(&Specifier::parse("^4"))
+    );
+}
+
+#[test]
+fn resolve_version_returns_none_when_not_in_manifest() {
+    let m = Manifest::default();
+    assert_eq!(
+        m.resolve_version(
+            &ActionId::from("actions/checkout"),
+            &make_loc(".github/workflows/ci.yml", None, None)
+        ),
+        None
+    );
+}
+
+// --- Manifest::diff tests ---
+
+#[test]
+fn diff_empty_manifests_is_empty() {
+    let before = Manifest::default();
+    let after = Manifest::default();
+    assert!(before.diff(&after).is_empty());
+}
+
+#[test]
+fn diff_detects_added_action() {
+    let before = Manifest::default();
+    let mut after = Manifest::default();
+    after.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+
+    let diff = before.diff(&after);
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].0, ActionId::from("actions/checkout"));
+    assert_eq!(diff.added[0].1, Specifier::parse("^4"));
+    assert!(diff.removed.is_empty());
+    assert!(diff.updated.is_empty());
+}
+
+#[test]
+fn diff_detects_removed_action() {
+    let mut before = Manifest::default();
+    before.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    let after = Manifest::default();
+
+    let diff = before.diff(&after);
+    assert!(diff.added.is_empty());
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0], ActionId::from("actions/checkout"));
+    assert!(diff.updated.is_empty());
+}
+
+#[test]
+fn diff_detects_updated_action() {
+    let mut before = Manifest::default();
+    before.set(ActionId::from("actions/checkout"), Specifier::parse("^3"));
+    let mut after = Manifest::default();
+    after.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+
+    let diff = before.diff(&after);
+    assert!(diff.added.is_empty());
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.updated.len(), 1);
+    assert_eq!(diff.updated[0].0, ActionId::from("actions/checkout"));
+    assert_eq!(diff.updated[0].1, Specifier::parse("^4"));
+}
+
+#[test]
+fn diff_unchanged_action_not_in_diff() {
+    let mut before = Manifest::default();
+    before.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    let after = before.clone();
+
+    let diff = before.diff(&after);
+    assert!(diff.is_empty());
+}
+
+#[test]
+fn diff_detects_override_added() {
+    let mut before = Manifest::default();
+    before.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    let mut after = before.clone();
+    after.add_override(
+        ActionId::from("actions/checkout"),
+        ActionOverride {
+            workflow: WorkflowPath::new(".github/workflows/ci.yml"),
+            job: None,
+            step: None,
+            version: Specifier::parse("^3"),
+        },
+    );
+
+    let diff = before.diff(&after);
+    assert_eq!(diff.overrides_added.len(), 1);
+    assert!(diff.overrides_removed.is_empty());
+}
+
+#[test]
+fn diff_detects_override_removed() {
+    let mut before = Manifest::default();
+    before.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    before.add_override(
+        ActionId::from("actions/checkout"),
+        ActionOverride {
+            workflow: WorkflowPath::new(".github/workflows/ci.yml"),
+            job: None,
+            step: None,
+            version: Specifier::parse("^3"),
+        },
+    );
+    let mut after = Manifest::default();
+    after.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+
+    let diff = before.diff(&after);
+    assert!(diff.overrides_added.is_empty());
+    assert_eq!(diff.overrides_removed.len(), 1);
+}
+
+// --- lock_keys tests ---
+
+#[test]
+fn lock_keys_returns_global_keys() {
+    let mut m = Manifest::default();
+    m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    m.set(ActionId::from("actions/setup-node"), Specifier::parse("^3"));
+
+    let keys = m.lock_keys();
+    assert_eq!(keys.len(), 2);
+}
+
+#[test]
+fn lock_keys_includes_override_versions() {
+    let mut m = Manifest::default();
+    m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    m.add_override(
+        ActionId::from("actions/checkout"),
+        ActionOverride {
+            workflow: WorkflowPath::new(".github/workflows/windows.yml"),
+            job: None,
+            step: None,
+            version: Specifier::parse("^3"),
+        },
+    );
+
+    let keys = m.lock_keys();
+    assert_eq!(keys.len(), 2, "should have keys for ^4 and ^3");
+}
+
+#[test]
+fn lock_keys_deduplicates() {
+    let mut m = Manifest::default();
+    m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    m.add_override(
+        ActionId::from("actions/checkout"),
+        ActionOverride {
+            workflow: WorkflowPath::new(".github/workflows/ci.yml"),
+            job: None,
+            step: None,
+            version: Specifier::parse("^3"),
+        },
+    );
+    m.add_override(
+        ActionId::from("actions/checkout"),
+        ActionOverride {
+            workflow: WorkflowPath::new(".github/workflows/deploy.yml"),
+            job: None,
+            step: None,
+            version: Specifier::parse("^3"),
+        },
+    );
+
+    let keys = m.lock_keys();
+    assert_eq!(
+        keys.len(),
+        2,
+        "^4 and ^3 — duplicated ^3 overrides deduplicated"
+    );
+}
+
+#[test]
+fn restore_held_returns_only_the_held_actions_it_put_back() {
+    let mut original = Manifest::default();
+    original.set(ActionId::from("actions/cache"), Specifier::parse("^4"));
+    original.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    original.hold(ActionId::from("actions/cache"));
+    original.hold(ActionId::from("actions/checkout"));
+    let mut planned = original.clone();
+    planned.remove(&ActionId::from("actions/cache"));
+
+    let kept = planned.restore_held(&original);
+
+    assert_eq!(kept, vec![ActionId::from("actions/cache")]);
+    assert_eq!(
+        planned.get(&ActionId::from("actions/cache")),
+        Some(&Specifier::parse("^4"))
+    );
+}
@@ -1,3 +1,4 @@
+pub mod aliases;
 pub mod overrides;
 
 use super::action::identity::ActionId;
@@ -5,8 +6,19 @@ use super::action::spec::Spec;
 use super::action::specifier::Specifier;
 use super::diff::ManifestDiff;
 use super::workflow_actions::{ActionSet, Located, Location};
+use aliases::Aliases;
 use overrides::ActionOverride;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Notes kept with an action entry for the people reading gx.toml: why it is pinned as it
+/// is, and who to ask about it. gx only shows them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Annotation {
+    /// Why the action is pinned as it is, such as `v4 breaks cache keys`.
+    pub reason: Option<String>,
+    /// Who looks after the pin, such as `@platform-team`.
+    pub owner: Option<String>,
+}
 
 /// Domain entity owning the manifest's action→specifier mapping and all domain behaviour.
 /// No I/O — persistence is handled by infrastructure's file-backed save methods.
@@ -16,6 +28,19 @@ pub struct Manifest {
     actions: HashMap<ActionId, Spec>,
     /// Per-action override entries scoped to specific workflows, jobs, or steps.
     overrides: HashMap<ActionId, Vec<ActionOverride>>,
+    /// Named groups of actions that are upgraded together.
+    groups: BTreeMap<String, Vec<ActionId>>,
+    /// Actions marked `hold = true`: never upgraded and never corrected by tidy.
+    held: HashSet<ActionId>,
+    /// Actions marked `allow_prerelease = true`: pre-release tags are upgrade candidates.
+    prerelease: HashSet<ActionId>,
+    /// Actions marked `require_signed = true`: an unsigned locked commit or tag fails
+    /// verification.
+    signed: HashSet<ActionId>,
+    /// Short names for actions, accepted wherever an action ID is.
+    aliases: Aliases,
+    /// The `reason` and `owner` of the actions that have them.
+    annotations: HashMap<ActionId, Annotation>,
 }
 
 impl Manifest {
@@ -24,7 +49,7 @@ impl Manifest {
     pub fn new(actions: HashMap<ActionId, Spec>) -> Self {
         Self {
             actions,
-            overrides: HashMap::new(),
+            ..Self::default()
         }
     }
 
@@ -37,6 +62,7 @@ impl Manifest {
         Self {
             actions,
             overrides: new_overrides,
+            ..Self::default()
         }
     }
 
@@ -109,6 +135,124 @@ impl Manifest {
         &self.overrides
     }
 
+    /// Declare a named group of actions, replacing any group with the same name.
+    pub fn set_group(&mut self, name: String, members: Vec<ActionId>) {
+        self.groups.insert(name, members);
+    }
+
+    /// Get the members of a named group.
+    #[must_use]
+    pub fn group(&self, name: &str) -> Option<&[ActionId]> {
+        self.groups.get(name).map(Vec::as_slice)
+    }
+
+    /// Get all groups, keyed by name.
+    #[must_use]
+    pub fn groups(&self) -> &BTreeMap<String, Vec<ActionId>> {
+        &self.groups
+    }
+
+    /// Declare the short names of actions, replacing any declared before.
+    pub fn set_aliases(&mut self, aliases: Aliases) {
+        self.aliases = aliases;
+    }
+
+    /// The short names of actions declared under `[aliases]`.
+    #[must_use]
+    pub fn aliases(&self) -> &Aliases {
+        &self.aliases
+    }
+
+    /// Keep `annotation` with an action's entry, replacing any it had.
+    pub fn annotate(&mut self, id: ActionId, annotation: Annotation) {
+        self.annotations.insert(id, annotation);
+    }
+
+    /// The `reason` and `owner` written with an action's entry, if any.
+    #[must_use]
+    pub fn annotation(&self, id: &ActionId) -> Option<&Annotation> {
+        self.annotations.get(id)
+    }
+
+    /// Why an action is pinned as it is, when its entry says.
+    #[must_use]
+    pub fn reason(&self, id: &ActionId) -> Option<&str> {
+        self.annotation(id)?.reason.as_deref()
+    }
+
+    /// Why gx leaves an action alone, when it is held: `held in gx.toml`, followed by the
+    /// entry's `reason` when it gives one.
+    #[must_use]
+    pub fn hold_note(&self, id: &ActionId) -> Option<String> {
+        if !self.is_held(id) {
+            return None;
+        }
+        Some(self.reason(id).map_or_else(
+            || "held in gx.toml".to_owned(),
+            |reason| format!("held in gx.toml: {reason}"),
+        ))
+    }
+
+    /// Mark an action as held at its current version.
+    pub fn hold(&mut self, id: ActionId) {
+        self.held.insert(id);
+    }
+
+    /// Check if an action is held.
+    #[must_use]
+    pub fn is_held(&self, id: &ActionId) -> bool {
+        self.held.contains(id)
+    }
+
+    /// Let pre-release tags be upgrade candidates for an action.
+    pub fn allow_prerelease(&mut self, id: ActionId) {
+        self.prerelease.insert(id);
+    }
+
+    /// Check if an action accepts pre-release upgrade candidates.
+    #[must_use]
+    pub fn allows_prerelease(&self, id: &ActionId) -> bool {
+        self.prerelease.contains(id)
+    }
+
+    /// Require the locked commit or tag of an action to be signed.
+    pub fn require_signed(&mut self, id: ActionId) {
+        self.signed.insert(id);
+    }
+
+    /// Check if an action requires its locked commit or tag to be signed.
+    #[must_use]
+    pub fn requires_signed(&self, id: &ActionId) -> bool {
+        self.signed.contains(id)
+    }
+
+    /// Put every action held in `original` back to its entry and overrides there.
+    ///
+    /// Returns the held actions that had changed, sorted, i.e. the ones the hold kept.
+    pub fn restore_held(&mut self, original: &Manifest) -> Vec<ActionId> {
+        let mut kept: Vec<ActionId> = original
+            .held
+            .iter()
+            .filter(|id| {
+                self.actions.get(*id) != original.actions.get(*id)
+                    || self.overrides.get(*id) != original.overrides.get(*id)
+            })
+            .cloned()
+            .collect();
+        kept.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        for id in &original.held {
+            match original.actions.get(id) {
+                Some(spec) => self.actions.insert(id.clone(), spec.clone()),
+                None => self.actions.remove(id),
+            };
+            match original.overrides.get(id) {
+                Some(ovrs) => self.overrides.insert(id.clone(), ovrs.clone()),
+                None => self.overrides.remove(id),
+            };
+        }
+        kept
+    }
+
     /// Replace all overrides for an action (used by stale cleanup).
     pub fn replace_overrides(&mut self, id: ActionId, new_overrides: Vec<ActionOverride>) {
         if new_overrides.is_empty() {
@@ -149,7 +293,7 @@ impl Manifest {
 
     /// Compute the diff between this manifest (`before`) and `other` (`after`).
     ///
-    /// Detects added, removed, updated actions and override changes (added/removed).
+    /// Detects added, removed, updated actions and override changes, sorted by action.
     #[must_use]
     pub fn diff(&self, other: &Manifest) -> ManifestDiff {
         let before_ids: HashSet<ActionId> = self.specs().map(|s| s.id.clone()).collect();
@@ -206,6 +350,7 @@ impl Manifest {
             overrides_added,
             overrides_removed,
         }
+        .sorted()
     }
 }
 
@@ -214,257 +359,5 @@ impl Manifest {
     clippy::indexing_slicing,
     reason = "tests use unwrap, indexing, and other patterns freely"
 )]
-mod tests {
-    use super::{ActionId, ActionOverride, Manifest, Specifier};
-    use crate::domain::workflow_actions::{JobId, Location, StepIndex, WorkflowPath};
-
-    fn make_loc(workflow: &str, job: Option<&str>, step: Option<u16>) -> Location {
-        Location {
-            workflow: WorkflowPath::new(workflow),
-            job: job.map(JobId::from),
-            step: step.map(StepIndex::from),
-            line: None,
-        }
-    }
-
-    #[test]
-    fn set_and_get() {
-        let mut m = Manifest::default();
-        m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        assert_eq!(
-            m.get(&ActionId::from("actions/checkout")),
-            Some(&Specifier::parse("^4"))
-        );
-    }
-
-    #[test]
-    fn has_and_remove() {
-        let mut m = Manifest::default();
-        m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        assert!(m.has(&ActionId::from("actions/checkout")));
-        m.remove(&ActionId::from("actions/checkout"));
-        assert!(!m.has(&ActionId::from("actions/checkout")));
-    }
-
-    #[test]
-    fn remove_also_clears_overrides() {
-        let mut m = Manifest::default();
-        m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        m.add_override(
-            ActionId::from("actions/checkout"),
-            ActionOverride {
-                workflow: WorkflowPath::new(".github/workflows/ci.yml"),
-                job: None,
-                step: None,
-                version: Specifier::parse("^3"),
-            },
-        );
-        m.remove(&ActionId::from("actions/checkout"));
-        assert!(
-            m.overrides_for(&ActionId::from("actions/checkout"))
-                .is_empty()
-        );
-    }
-
-    #[test]
-    fn is_empty() {
-        let mut m = Manifest::default();
-        assert!(m.is_empty());
-        m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        assert!(!m.is_empty());
-    }
-
-    #[test]
-    fn specs() {
-        let mut m = Manifest::default();
-        m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        m.set(ActionId::from("actions/setup-node"), Specifier::parse("^3"));
-        assert_eq!(m.specs().count(), 2);
-    }
-
-    #[test]
-    fn resolve_version_returns_global_when_no_override() {
-        let mut m = Manifest::default();
-        m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        let loc = make_loc(".github/workflows/ci.yml", Some("build"), Some(0));
-        assert_eq!(
-            m.resolve_version(&ActionId::from("actions/checkout"), &loc),
-            Some(&Specifier::parse("^4"))
-        );
-    }
-
-    #[test]
-    fn resolve_version_returns_none_when_not_in_manifest() {
-        let m = Manifest::default();
-        assert_eq!(
-            m.resolve_version(
-                &ActionId::from("actions/checkout"),
-                &make_loc(".github/workflows/ci.yml", None, None)
-            ),
-            None
-        );
-    }
-
-    // --- Manifest::diff tests ---
-
-    #[test]
-    fn diff_empty_manifests_is_empty() {
-        let before = Manifest::default();
-        let after = Manifest::default();
-        assert!(before.diff(&after).is_empty());
-    }
-
-    #[test]
-    fn diff_detects_added_action() {
-        let before = Manifest::default();
-        let mut after = Manifest::default();
-        after.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-
-        let diff = before.diff(&after);
-        assert_eq!(diff.added.len(), 1);
-        assert_eq!(diff.added[0].0, ActionId::from("actions/checkout"));
-        assert_eq!(diff.added[0].1, Specifier::parse("^4"));
-        assert!(diff.removed.is_empty());
-        assert!(diff.updated.is_empty());
-    }
-
-    #[test]
-    fn diff_detects_removed_action() {
-        let mut before = Manifest::default();
-        before.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        let after = Manifest::default();
-
-        let diff = before.diff(&after);
-        assert!(diff.added.is_empty());
-        assert_eq!(diff.removed.len(), 1);
-        assert_eq!(diff.removed[0], ActionId::from("actions/checkout"));
-        assert!(diff.updated.is_empty());
-    }
-
-    #[test]
-    fn diff_detects_updated_action() {
-        let mut before = Manifest::default();
-        before.set(ActionId::from("actions/checkout"), Specifier::parse("^3"));
-        let mut after = Manifest::default();
-        after.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-
-        let diff = before.diff(&after);
-        assert!(diff.added.is_empty());
-        assert!(diff.removed.is_empty());
-        assert_eq!(diff.updated.len(), 1);
-        assert_eq!(diff.updated[0].0, ActionId::from("actions/checkout"));
-        assert_eq!(diff.updated[0].1, Specifier::parse("^4"));
-    }
-
-    #[test]
-    fn diff_unchanged_action_not_in_diff() {
-        let mut before = Manifest::default();
-        before.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        let after = before.clone();
-
-        let diff = before.diff(&after);
-        assert!(diff.is_empty());
-    }
-
-    #[test]
-    fn diff_detects_override_added() {
-        let mut before = Manifest::default();
-        before.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        let mut after = before.clone();
-        after.add_override(
-            ActionId::from("actions/checkout"),
-            ActionOverride {
-                workflow: WorkflowPath::new(".github/workflows/ci.yml"),
-                job: None,
-                step: None,
-                version: Specifier::parse("^3"),
-            },
-        );
-
-        let diff = before.diff(&after);
-        assert_eq!(diff.overrides_added.len(), 1);
-        assert!(diff.overrides_removed.is_empty());
-    }
-
-    #[test]
-    fn diff_detects_override_removed() {
-        let mut before = Manifest::default();
-        before.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        before.add_override(
-            ActionId::from("actions/checkout"),
-            ActionOverride {
-                workflow: WorkflowPath::new(".github/workflows/ci.yml"),
-                job: None,
-                step: None,
-                version: Specifier::parse("^3"),
-            },
-        );
-        let mut after = Manifest::default();
-        after.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-
-        let diff = before.diff(&after);
-        assert!(diff.overrides_added.is_empty());
-        assert_eq!(diff.overrides_removed.len(), 1);
-    }
-
-    // --- lock_keys tests ---
-
-    #[test]
-    fn lock_keys_returns_global_keys() {
-        let mut m = Manifest::default();
-        m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        m.set(ActionId::from("actions/setup-node"), Specifier::parse("^3"));
-
-        let keys = m.lock_keys();
-        assert_eq!(keys.len(), 2);
-    }
-
-    #[test]
-    fn lock_keys_includes_override_versions() {
-        let mut m = Manifest::default();
-        m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        m.add_override(
-            ActionId::from("actions/checkout"),
-            ActionOverride {
-                workflow: WorkflowPath::new(".github/workflows/windows.yml"),
-                job: None,
-                step: None,
-                version: Specifier::parse("^3"),
-            },
-        );
-
-        let keys = m.lock_keys();
-        assert_eq!(keys.len(), 2, "should have keys for ^4 and ^3");
-    }
-
-    #[test]
-    fn lock_keys_deduplicates() {
-        let mut m = Manifest::default();
-        m.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        m.add_override(
-            ActionId::from("actions/checkout"),
-            ActionOverride {
-                workflow: WorkflowPath::new(".github/workflows/ci.yml"),
-                job: None,
-                step: None,
-                version: Specifier::parse("^3"),
-            },
-        );
-        m.add_override(
-            ActionId::from("actions/checkout"),
-            ActionOverride {
-                workflow: WorkflowPath::new(".github/workflows/deploy.yml"),
-                job: None,
-                step: None,
-                version: Specifier::parse("^3"),
-            },
-        );
-
-        let keys = m.lock_keys();
-        assert_eq!(
-            keys.len(),
-            2,
-            "^4 and ^3 — duplicated ^3 overrides deduplicated"
-        );
-    }
-}
+#[path = "manifest_tests.rs"]
+mod tests;
@@ -0,0 +1,397 @@
+use super::{ActionOverride, LocatedAction, prune_stale, resolve_version, sync};
+use crate::domain::action::identity::{ActionId, Version};
+use crate::domain::action::spec::Spec;
+use crate::domain::action::specifier::Specifier;
+use crate::domain::workflow_actions::{
+    ActionSet as WorkflowActionSet, JobId, Location as WorkflowLocation, StepIndex, StepKey,
+    WorkflowPath,
+};
+use std::collections::HashMap;
+
+fn make_loc(workflow: &str, job: Option<&str>, step: Option<u16>) -> WorkflowLocation {
+    WorkflowLocation {
+        workflow: WorkflowPath::new(workflow),
+        job: job.map(JobId::from),
+        step: step.map(StepIndex::from),
+        step_name: None,
+        line: None,
+    }
+}
+
+fn make_located(workflow: &str, action: &str, version: &str) -> LocatedAction {
+    use crate::domain::workflow_actions::WorkflowAction;
+    LocatedAction {
+        action: WorkflowAction {
+            id: ActionId::from(action),
+            version: Version::from(version),
+            sha: None,
+        },
+        location: make_loc(workflow, None, None),
+    }
+}
+
+#[test]
+fn resolve_version_returns_none_when_no_overrides() {
+    let overrides: Vec<ActionOverride> = vec![];
+    let loc = make_loc(".github/workflows/ci.yml", Some("build"), Some(0));
+    assert_eq!(resolve_version(&overrides, &loc), None);
+}
+
+#[test]
+fn resolve_version_workflow_level() {
+    let overrides = vec![ActionOverride {
+        workflow: WorkflowPath::new(".github/workflows/ci.yml"),
+        job: None,
+        step: None,
+        version: Specifier::parse("^3"),
+    }];
+    let loc = make_loc(".github/workflows/ci.yml", Some("build"), Some(0));
+    assert_eq!(
+        resolve_version(&overrides, &loc),
+        Some(&Specifier::parse("^3"))
+    );
+}
+
+#[test]
+fn resolve_version_step_level_wins_over_workflow() {
+    let overrides = vec![
+        ActionOverride {
+            workflow: WorkflowPath::new(".github/workflows/ci.yml"),
+            job: None,
+            step: None,
+            version: Specifier::parse("^3"),
+        },
+        ActionOverride {
+            workflow: WorkflowPath::new(".github/workflows/ci.yml"),
+            job: Some(JobId::from("build")),
+            step: Some(StepIndex::from(0_u16).into()),
+            version: Specifier::parse("^2"),
+        },
+    ];
+    let loc = make_loc(".github/workflows/ci.yml", Some("build"), Some(0));
+    assert_eq!(
+        resolve_version(&overrides, &loc),
+        Some(&Specifier::parse("^2"))
+    );
+}
+
+#[test]
+fn resolve_version_step_name_survives_an_inserted_step_and_beats_the_index() {
+    let step_override = |step: StepKey, version: &str| ActionOverride {
+        workflow: WorkflowPath::new(".github/workflows/ci.yml"),
+        job: Some(JobId::from("build")),
+        step: Some(step),
+        version: Specifier::parse(version),
+    };
+    let overrides = vec![
+        step_override(StepKey::Index(StepIndex::from(1_u16)), "^2"),
+        step_override(StepKey::Name("Deploy".to_owned()), "^3"),
+    ];
+    let mut loc = make_loc(".github/workflows/ci.yml", Some("build"), Some(1));
+    loc.step_name = Some("Deploy".to_owned());
+    assert_eq!(
+        resolve_version(&overrides, &loc),
+        Some(&Specifier::parse("^3"))
+    );
+
+    // A step inserted above moves "Deploy" to index 2; the name still finds it,
+    // and the unnamed step now at index 1 falls back to the index override.
+    loc.step = Some(StepIndex::from(2_u16));
+    assert_eq!(
+        resolve_version(&overrides, &loc),
+        Some(&Specifier::parse("^3"))
+    );
+    let unnamed = make_loc(".github/workflows/ci.yml", Some("build"), Some(1));
+    assert_eq!(
+        resolve_version(&overrides, &unnamed),
+        Some(&Specifier::parse("^2"))
+    );
+}
+
+#[test]
+fn sync_keys_new_overrides_by_step_name_when_there_is_one() {
+    let mut actions_overrides: HashMap<ActionId, Vec<ActionOverride>> = HashMap::new();
+    let mut actions_global: HashMap<ActionId, Spec> = HashMap::new();
+    actions_global.insert(
+        ActionId::from("actions/checkout"),
+        Spec::new(ActionId::from("actions/checkout"), Specifier::parse("^4")),
+    );
+    let mut named = make_located(".github/workflows/ci.yml", "actions/checkout", "v3");
+    named.location = make_loc(".github/workflows/ci.yml", Some("build"), Some(2));
+    named.location.step_name = Some("Deploy".to_owned());
+    let located = vec![
+        make_located(".github/workflows/ci.yml", "actions/checkout", "v4"),
+        named,
+    ];
+    let action_set = WorkflowActionSet::from_located(&located);
+
+    // A second run finds the step already covered by its name.
+    for _ in 0..2 {
+        sync(
+            &mut actions_overrides,
+            &actions_global,
+            &located,
+            &action_set,
+        );
+    }
+
+    let overrides = &actions_overrides[&ActionId::from("actions/checkout")];
+    assert_eq!(overrides.len(), 1);
+    assert_eq!(overrides[0].step, Some(StepKey::Name("Deploy".to_owned())));
+}
+
+#[test]
+fn resolve_version_matches_workflow_and_job_globs_and_prefers_literals() {
+    let job_override = |workflow: &str, job: &str, version: &str| ActionOverride {
+        workflow: WorkflowPath::new(workflow),
+        job: Some(JobId::from(job)),
+        step: None,
+        version: Specifier::parse(version),
+    };
+    let overrides = vec![
+        job_override("*-windows.yml", "test-*", "^2"),
+        job_override(".github/workflows/build-windows.yml", "test-unit", "^3"),
+    ];
+
+    let glob_only = make_loc(
+        ".github/workflows/release-windows.yml",
+        Some("test-e2e"),
+        Some(0),
+    );
+    assert_eq!(
+        resolve_version(&overrides, &glob_only),
+        Some(&Specifier::parse("^2"))
+    );
+    let both = make_loc(
+        ".github/workflows/build-windows.yml",
+        Some("test-unit"),
+        Some(0),
+    );
+    assert_eq!(
+        resolve_version(&overrides, &both),
+        Some(&Specifier::parse("^3"))
+    );
+    let other_job = make_loc(".github/workflows/build-windows.yml", Some("lint"), Some(0));
+    assert_eq!(resolve_version(&overrides, &other_job), None);
+}
+
+#[test]
+fn prune_stale_keeps_a_glob_override_while_it_matches_a_workflow() {
+    let glob_override = ActionOverride {
+        workflow: WorkflowPath::new("*-windows.yml"),
+        job: None,
+        step: None,
+        version: Specifier::parse("^3"),
+    };
+    let mut actions_overrides =
+        HashMap::from([(ActionId::from("actions/checkout"), vec![glob_override])]);
+
+    let windows = make_located(".github/workflows/ci-windows.yml", "actions/checkout", "v3");
+    prune_stale(&mut actions_overrides, &[windows]);
+    assert_eq!(actions_overrides.len(), 1);
+
+    let linux = make_located(".github/workflows/ci-linux.yml", "actions/checkout", "v4");
+    prune_stale(&mut actions_overrides, &[linux]);
+    assert!(actions_overrides.is_empty());
+}
+
+#[test]
+fn sync_no_op_when_single_version() {
+    let mut actions_overrides: HashMap<ActionId, Vec<ActionOverride>> = HashMap::new();
+    let mut actions_global: HashMap<ActionId, Spec> = HashMap::new();
+    actions_global.insert(
+        ActionId::from("actions/checkout"),
+        Spec::new(ActionId::from("actions/checkout"), Specifier::parse("^4")),
+    );
+
+    let mut action_set = WorkflowActionSet::new();
+    let located = vec![make_located(
+        ".github/workflows/ci.yml",
+        "actions/checkout",
+        "v4",
+    )];
+    for a in &located {
+        action_set.add(&a.action);
+    }
+
+    sync(
+        &mut actions_overrides,
+        &actions_global,
+        &located,
+        &action_set,
+    );
+    assert!(
+        actions_overrides
+            .get(&ActionId::from("actions/checkout"))
+            .is_none_or(Vec::is_empty)
+    );
+}
+
+#[test]
+fn sync_adds_override_for_minority_version() {
+    let mut actions_overrides: HashMap<ActionId, Vec<ActionOverride>> = HashMap::new();
+    let mut actions_global: HashMap<ActionId, Spec> = HashMap::new();
+    actions_global.insert(
+        ActionId::from("actions/checkout"),
+        Spec::new(ActionId::from("actions/checkout"), Specifier::parse("^4")),
+    );
+
+    let mut action_set = WorkflowActionSet::new();
+    let located = vec![
+        make_located(".github/workflows/ci.yml", "actions/checkout", "v4"),
+        make_located(".github/workflows/ci.yml", "actions/checkout", "v4"),
+        make_located(".github/workflows/windows.yml", "actions/checkout", "v3"),
+    ];
+    for a in &located {
+        action_set.add(&a.action);
+    }
+
+    sync(
+        &mut actions_overrides,
+        &actions_global,
+        &located,
+        &action_set,
+    );
+    let overrides = actions_overrides
+        .get(&ActionId::from("actions/checkout"))
+        .unwrap();
+    assert_eq!(overrides.len(), 1);
+    assert_eq!(
+        overrides[0].workflow,
+        WorkflowPath::new(".github/workflows/windows.yml")
+    );
+    assert_eq!(overrides[0].version, Specifier::from_v1("v3"));
+}
+
+#[test]
+fn prune_stale_removes_override_for_missing_workflow() {
+    let mut actions_overrides: HashMap<ActionId, Vec<ActionOverride>> = HashMap::new();
+    actions_overrides.insert(
+        ActionId::from("actions/checkout"),
+        vec![ActionOverride {
+            workflow: WorkflowPath::new(".github/workflows/deploy.yml"),
+            job: None,
+            step: None,
+            version: Specifier::parse("v3"),
+        }],
+    );
+
+    let located = vec![make_located(
+        ".github/workflows/ci.yml",
+        "actions/checkout",
+        "v4",
+    )];
+    prune_stale(&mut actions_overrides, &located);
+
+    assert!(
+        actions_overrides
+            .get(&ActionId::from("actions/checkout"))
+            .is_none_or(Vec::is_empty)
+    );
+}
+
+#[test]
+fn prune_stale_keeps_live_overrides() {
+    let mut actions_overrides: HashMap<ActionId, Vec<ActionOverride>> = HashMap::new();
+    actions_overrides.insert(
+        ActionId::from("actions/checkout"),
+        vec![ActionOverride {
+            workflow: WorkflowPath::new(".github/workflows/ci.yml"),
+            job: None,
+            step: None,
+            version: Specifier::parse("v3"),
+        }],
+    );
+
+    let located = vec![make_located(
+        ".github/workflows/ci.yml",
+        "actions/checkout",
+        "v4",
+    )];
+    prune_stale(&mut actions_overrides, &located);
+
+    assert_eq!(
+        actions_overrides
+            .get(&ActionId::from("actions/checkout"))
+            .map(Vec::len),
+        Some(1)
+    );
+}
+
+// --- Override lifecycle tests (migrated from tidy/tests.rs) ---
+
+/// Multiple workflows with v6.0.1 + one with v5 → `sync` creates override for v5.
+#[test]
+fn sync_multiple_sha_workflows_with_minority_version() {
+    let mut actions_overrides: HashMap<ActionId, Vec<ActionOverride>> = HashMap::new();
+    let mut actions_global: HashMap<ActionId, Spec> = HashMap::new();
+    // Global is v6.0.1 (dominant version)
+    actions_global.insert(
+        ActionId::from("actions/checkout"),
+        Spec::new(
+            ActionId::from("actions/checkout"),
+            Specifier::from_v1("v6.0.1"),
+        ),
+    );
+
+    let mut action_set = WorkflowActionSet::new();
+    let located = vec![
+        make_located(".github/workflows/ci.yml", "actions/checkout", "v6.0.1"),
+        make_located(".github/workflows/build.yml", "actions/checkout", "v6.0.1"),
+        make_located(".github/workflows/windows.yml", "actions/checkout", "v5"),
+    ];
+    for a in &located {
+        action_set.add(&a.action);
+    }
+
+    sync(
+        &mut actions_overrides,
+        &actions_global,
+        &located,
+        &action_set,
+    );
+
+    let overrides = actions_overrides
+        .get(&ActionId::from("actions/checkout"))
+        .expect("override must exist for minority version");
+    assert_eq!(overrides.len(), 1, "exactly one override for v5");
+    assert!(
+        overrides[0].workflow.as_str().ends_with("windows.yml"),
+        "override must be scoped to windows.yml"
+    );
+    assert_eq!(
+        overrides[0].version,
+        Specifier::from_v1("v5"),
+        "override version must be v5"
+    );
+}
+
+/// Stale override for deploy.yml (which no longer exists) is removed by prune.
+#[test]
+fn prune_stale_removes_deploy_yml_when_only_ci_exists() {
+    let mut actions_overrides: HashMap<ActionId, Vec<ActionOverride>> = HashMap::new();
+    actions_overrides.insert(
+        ActionId::from("actions/checkout"),
+        vec![ActionOverride {
+            workflow: WorkflowPath::new(".github/workflows/deploy.yml"),
+            job: None,
+            step: None,
+            version: Specifier::from_v1("v3"),
+        }],
+    );
+
+    // Only ci.yml is live — deploy.yml has been deleted
+    let located = vec![make_located(
+        ".github/workflows/ci.yml",
+        "actions/checkout",
+        "v4",
+    )];
+    prune_stale(&mut actions_overrides, &located);
+
+    assert!(
+        actions_overrides
+            .get(&ActionId::from("actions/checkout"))
+            .is_none_or(Vec::is_empty),
+        "stale deploy.yml override must be removed"
+    );
+}
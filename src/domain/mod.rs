@@ -1,9 +1,11 @@
 pub mod action;
 pub mod diff;
 pub mod event;
+pub mod image;
 pub mod lock;
 pub mod manifest;
 pub mod resolution;
+pub mod trust;
 pub mod workflow;
 pub mod workflow_actions;
 pub mod workflow_parsed;
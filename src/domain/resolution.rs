@@ -1,11 +1,17 @@
 use super::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
-use super::action::resolved::{Commit, Resolved};
+use super::action::resolved::{Commit, Provenance, Resolved};
 use super::action::spec::Spec as ActionSpec;
-
+use super::action::specifier::Specifier;
 use super::action::tag_selection::{ShaIndex, select_most_specific_tag};
 use super::action::uses_ref::RefType;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
 use thiserror::Error;
 
+/// Lookups run at once when the registry does not set its own limit.
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
 /// Errors that can occur during version resolution.
 #[derive(Debug, Clone, Error)]
 pub enum Error {
@@ -20,6 +26,12 @@ pub enum Error {
 
     #[error("GitHub API authorization required")]
     AuthRequired,
+
+    #[error("GitHub API request limit of {limit} reached")]
+    RequestLimit { limit: usize },
+
+    #[error("network access is disabled (--offline)")]
+    Offline,
 }
 
 impl Error {
@@ -36,10 +48,14 @@ pub struct ShaDescription {
     pub tags: Vec<Version>,
     pub repository: Repository,
     pub date: CommitDate,
+    /// How and when the description was fetched.
+    pub provenance: Provenance,
 }
 
 /// Trait for querying available versions and commit SHAs from a remote registry.
-pub trait VersionRegistry {
+///
+/// Registries are shared between the threads of [`ActionResolver::resolve_all`].
+pub trait VersionRegistry: Sync {
     /// Look up the commit SHA and metadata for a version reference.
     ///
     /// # Errors
@@ -67,6 +83,19 @@ pub trait VersionRegistry {
     ///
     /// Returns an error if the commit lookup fails (tag lookup failure is non-fatal, returns empty tags).
     fn describe_sha(&self, id: &ActionId, sha: &CommitSha) -> Result<ShaDescription, Error>;
+
+    /// How many lookups may run at once.
+    fn concurrency(&self) -> usize {
+        DEFAULT_CONCURRENCY
+    }
+}
+
+/// An action spec waiting to be resolved, with the commit its workflows pin it to.
+#[derive(Debug, Clone)]
+pub struct Pending {
+    pub spec: ActionSpec,
+    /// Commit SHA the workflows use for the spec, tried before the specifier.
+    pub sha: Option<CommitSha>,
 }
 
 /// Resolves actions to their correct version and commit SHA.
@@ -89,15 +118,50 @@ impl<'reg, R: VersionRegistry> ActionResolver<'reg, R> {
 
     /// Resolve an action spec to a commit SHA.
     ///
+    /// Floating specifiers (`^6`, `~1.15.2`) resolve through their floating tag, or to the
+    /// highest tag satisfying them when the repository has no such tag (`^4.1` without
+    /// `v4.1`). Other ranges (`>=4, <6`) and `latest` resolve to the highest tag
+    /// satisfying them.
+    ///
     /// # Errors
     ///
-    /// Returns `Error` if the registry lookup fails.
+    /// Returns `Error` if the registry lookup fails or no tag satisfies the range.
     pub fn resolve(&self, spec: &ActionSpec) -> Result<Resolved, Error> {
-        let version = Version::from(spec.specifier.to_lookup_tag());
+        let version = match &spec.specifier {
+            Specifier::Range { .. } if !spec.specifier.is_floating() => {
+                self.highest_in_range(spec)?
+            }
+            Specifier::Latest => self.highest_in_range(spec)?,
+            Specifier::Range { .. } => {
+                let floating = Version::from(spec.specifier.to_lookup_tag());
+                match self.registry.lookup_sha(&spec.id, &floating) {
+                    Ok(commit) => {
+                        return Ok(Resolved {
+                            version: floating,
+                            commit,
+                        });
+                    }
+                    Err(Error::ResolveFailed { .. }) => self.highest_in_range(spec)?,
+                    Err(e) => return Err(e),
+                }
+            }
+            Specifier::Ref(_) | Specifier::Sha(_) => Version::from(spec.specifier.to_lookup_tag()),
+        };
         let commit = self.registry.lookup_sha(&spec.id, &version)?;
         Ok(Resolved { version, commit })
     }
 
+    /// Find the highest tag of the action's repository that satisfies the spec's range.
+    fn highest_in_range(&self, spec: &ActionSpec) -> Result<Version, Error> {
+        let tags = self.registry.all_tags(&spec.id)?;
+        spec.specifier
+            .highest_match(&tags)
+            .ok_or_else(|| Error::ResolveFailed {
+                spec: spec.clone(),
+                reason: "no tag satisfies the range".to_owned(),
+            })
+    }
+
     /// Resolve an action from a known commit SHA.
     /// Derives version (most specific tag) and `ref_type` from tags for the SHA.
     ///
@@ -111,22 +175,64 @@ impl<'reg, R: VersionRegistry> ActionResolver<'reg, R> {
         sha_index: &mut ShaIndex,
     ) -> Result<Resolved, Error> {
         let desc = sha_index.get_or_describe(self.registry, id, sha)?;
-        let version =
-            select_most_specific_tag(&desc.tags).unwrap_or_else(|| Version::from(sha.as_str()));
-        let ref_type = if desc.tags.is_empty() {
-            Some(RefType::Commit)
-        } else {
-            Some(RefType::Tag)
+        Ok(from_description(sha, desc))
+    }
+
+    /// Resolve every pending spec, running up to [`VersionRegistry::concurrency`]
+    /// lookups at once.
+    ///
+    /// A spec pinned to a commit resolves from that SHA first, then from its specifier.
+    /// Results come back in the order of `pending`, whichever lookup finishes first.
+    pub fn resolve_all(
+        &self,
+        pending: &[Pending],
+        sha_index: &mut ShaIndex,
+    ) -> Vec<Result<Resolved, Error>> {
+        let next = AtomicUsize::new(0);
+        let shared = Mutex::new(sha_index);
+        let workers = self.registry.concurrency().clamp(1, pending.len().max(1));
+        let mut results: Vec<(usize, Result<Resolved, Error>)> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let position = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(item) = pending.get(position) else {
+                                return done;
+                            };
+                            done.push((position, self.resolve_pending(item, &shared)));
+                        }
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        });
+        results.sort_by_key(|(position, _)| *position);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Resolve one pending spec, sharing SHA descriptions with the other workers.
+    fn resolve_pending(
+        &self,
+        item: &Pending,
+        sha_index: &Mutex<&mut ShaIndex>,
+    ) -> Result<Resolved, Error> {
+        let Some(sha) = &item.sha else {
+            return self.resolve(&item.spec);
         };
-        Ok(Resolved {
-            version,
-            commit: Commit {
-                sha: sha.clone(),
-                repository: desc.repository.clone(),
-                ref_type,
-                date: desc.date.clone(),
-            },
-        })
+        let described =
+            ShaIndex::get_or_describe_shared(sha_index, self.registry, &item.spec.id, sha);
+        described
+            .map(|desc| from_description(sha, &desc))
+            .or_else(|_| self.resolve(&item.spec))
     }
 
     /// Correct a version based on the commit SHA it points to.
@@ -158,6 +264,28 @@ impl<'reg, R: VersionRegistry> ActionResolver<'reg, R> {
     }
 }
 
+/// The resolution of `sha` from its description: the most specific tag pointing to it,
+/// or the SHA itself when no tag does.
+fn from_description(sha: &CommitSha, desc: &ShaDescription) -> Resolved {
+    let version =
+        select_most_specific_tag(&desc.tags).unwrap_or_else(|| Version::from(sha.as_str()));
+    let ref_type = if desc.tags.is_empty() {
+        Some(RefType::Commit)
+    } else {
+        Some(RefType::Tag)
+    };
+    Resolved {
+        version,
+        commit: Commit {
+            sha: sha.clone(),
+            repository: desc.repository.clone(),
+            ref_type,
+            date: desc.date.clone(),
+            provenance: desc.provenance.clone(),
+        },
+    }
+}
+
 #[cfg(test)]
 #[path = "resolution_testutil.rs"]
 pub(crate) mod testutil;
@@ -169,50 +297,26 @@ pub(crate) mod testutil;
     reason = "tests use unwrap, indexing, and other patterns freely"
 )]
 mod tests {
+    use super::testutil::MockRegistry;
     use super::{
-        ActionId, ActionResolver, ActionSpec, Commit, CommitDate, CommitSha, Error, RefType,
-        Repository, ShaDescription, ShaIndex, Version, VersionRegistry,
+        ActionId, ActionResolver, ActionSpec, Commit, CommitDate, CommitSha, Error, Provenance,
+        RefType, Repository, ShaIndex, Specifier, Version,
     };
-    use crate::domain::action::specifier::Specifier;
-
-    struct MockRegistry {
-        resolve_result: Result<Commit, Error>,
-        tags_result: Result<Vec<Version>, Error>,
-    }
-
-    impl VersionRegistry for MockRegistry {
-        fn lookup_sha(&self, _id: &ActionId, _version: &Version) -> Result<Commit, Error> {
-            self.resolve_result.clone()
-        }
-
-        fn tags_for_sha(&self, _id: &ActionId, _sha: &CommitSha) -> Result<Vec<Version>, Error> {
-            self.tags_result.clone()
-        }
-
-        fn all_tags(&self, _id: &ActionId) -> Result<Vec<Version>, Error> {
-            self.tags_result.clone()
-        }
 
-        fn describe_sha(&self, _id: &ActionId, _sha: &CommitSha) -> Result<ShaDescription, Error> {
-            let meta = self.resolve_result.clone()?;
-            let tags = self.tags_result.clone().unwrap_or_default();
-            Ok(ShaDescription {
-                tags,
-                repository: meta.repository,
-                date: meta.date,
-            })
+    fn checkout_tag() -> Commit {
+        Commit {
+            sha: CommitSha::from("abc123def456789012345678901234567890abcd"),
+            repository: Repository::from("actions/checkout"),
+            ref_type: Some(RefType::Tag),
+            date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         }
     }
 
     #[test]
     fn resolve_success() {
         let mock_registry = MockRegistry {
-            resolve_result: Ok(Commit {
-                sha: CommitSha::from("abc123def456789012345678901234567890abcd"),
-                repository: Repository::from("actions/checkout"),
-                ref_type: Some(RefType::Tag),
-                date: CommitDate::from("2026-01-01T00:00:00Z"),
-            }),
+            resolve_result: Ok(checkout_tag()),
             tags_result: Ok(vec![]),
         };
         let service = ActionResolver::new(&mock_registry);
@@ -228,6 +332,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolve_compound_range_picks_highest_matching_tag() {
+        let mock_registry = MockRegistry {
+            resolve_result: Ok(checkout_tag()),
+            tags_result: Ok(vec![
+                Version::from("v4.3.1"),
+                Version::from("v5.0.0"),
+                Version::from("v6.0.0"),
+            ]),
+        };
+        let service = ActionResolver::new(&mock_registry);
+
+        let spec = ActionSpec::new(
+            ActionId::from("actions/checkout"),
+            Specifier::parse(">=4, <6"),
+        );
+        let resolved = service.resolve(&spec).expect("Expected Ok result");
+        assert_eq!(resolved.version.as_str(), "v5.0.0");
+    }
+
+    #[test]
+    fn resolve_floating_range_without_its_tag_picks_highest_matching_tag() {
+        let mock_registry = MockRegistry {
+            resolve_result: Ok(checkout_tag()),
+            tags_result: Ok(vec![
+                Version::from("v4.0.0"),
+                Version::from("v4.1.0"),
+                Version::from("v4.2.2"),
+                Version::from("v5.0.0"),
+            ]),
+        };
+        let service = ActionResolver::new(&mock_registry);
+
+        let spec = ActionSpec::new(ActionId::from("actions/checkout"), Specifier::parse("^4.1"));
+        let resolved = service.resolve(&spec).expect("Expected Ok result");
+        assert_eq!(resolved.version.as_str(), "v4.2.2");
+    }
+
+    #[test]
+    fn resolve_compound_range_without_matching_tag_fails() {
+        let mock_registry = MockRegistry {
+            resolve_result: Err(Error::AuthRequired),
+            tags_result: Ok(vec![Version::from("v3.0.0")]),
+        };
+        let service = ActionResolver::new(&mock_registry);
+
+        let spec = ActionSpec::new(
+            ActionId::from("actions/checkout"),
+            Specifier::parse(">=4, <6"),
+        );
+        assert!(matches!(
+            service.resolve(&spec),
+            Err(Error::ResolveFailed { .. })
+        ));
+    }
+
     #[test]
     fn resolve_failure() {
         let registry = MockRegistry {
@@ -248,12 +408,7 @@ mod tests {
     #[test]
     fn correct_version_no_correction_needed() {
         let registry = MockRegistry {
-            resolve_result: Ok(Commit {
-                sha: CommitSha::from("abc123def456789012345678901234567890abcd"),
-                repository: Repository::from("actions/checkout"),
-                ref_type: Some(RefType::Tag),
-                date: CommitDate::from("2026-01-01T00:00:00Z"),
-            }),
+            resolve_result: Ok(checkout_tag()),
             tags_result: Ok(vec![Version::from("v4"), Version::from("v4.0.0")]),
         };
         let service = ActionResolver::new(&registry);
@@ -272,12 +427,7 @@ mod tests {
     #[test]
     fn correct_version_correction_needed() {
         let registry = MockRegistry {
-            resolve_result: Ok(Commit {
-                sha: CommitSha::from("abc123def456789012345678901234567890abcd"),
-                repository: Repository::from("actions/checkout"),
-                ref_type: Some(RefType::Tag),
-                date: CommitDate::from("2026-01-01T00:00:00Z"),
-            }),
+            resolve_result: Ok(checkout_tag()),
             tags_result: Ok(vec![Version::from("v5"), Version::from("v5.0.0")]),
         };
         let service = ActionResolver::new(&registry);
@@ -302,6 +452,7 @@ mod tests {
                 repository: Repository::from("owner/repo"),
                 ref_type: Some(RefType::Commit),
                 date: CommitDate::from("2026-01-01T00:00:00Z"),
+                provenance: Provenance::default(),
             }),
             tags_result: Ok(vec![
                 Version::from("v3"),
@@ -332,6 +483,7 @@ mod tests {
                 repository: Repository::from("owner/repo"),
                 ref_type: Some(RefType::Commit),
                 date: CommitDate::from("2026-01-01T00:00:00Z"),
+                provenance: Provenance::default(),
             }),
             tags_result: Ok(vec![]),
         };
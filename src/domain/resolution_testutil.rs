@@ -1,8 +1,58 @@
 use super::{Error as ResolutionError, ShaDescription, VersionRegistry};
 use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Version};
-use crate::domain::action::resolved::Commit;
+use crate::domain::action::resolved::{Commit, Provenance};
+use crate::domain::action::spec::Spec as ActionSpec;
+use crate::domain::action::specifier::Specifier;
 use crate::domain::action::uses_ref::RefType;
 
+/// Registry returning fixed results; when `tags_result` lists tags, looking up a
+/// version outside them fails as a missing tag does.
+pub struct MockRegistry {
+    pub resolve_result: Result<Commit, ResolutionError>,
+    pub tags_result: Result<Vec<Version>, ResolutionError>,
+}
+
+impl VersionRegistry for MockRegistry {
+    fn lookup_sha(&self, id: &ActionId, version: &Version) -> Result<Commit, ResolutionError> {
+        match &self.tags_result {
+            Ok(tags) if !tags.is_empty() && !tags.contains(version) => {
+                Err(ResolutionError::ResolveFailed {
+                    spec: ActionSpec::new(id.clone(), Specifier::from_v1(version.as_str())),
+                    reason: "no such tag".to_owned(),
+                })
+            }
+            _ => self.resolve_result.clone(),
+        }
+    }
+
+    fn tags_for_sha(
+        &self,
+        _id: &ActionId,
+        _sha: &CommitSha,
+    ) -> Result<Vec<Version>, ResolutionError> {
+        self.tags_result.clone()
+    }
+
+    fn all_tags(&self, _id: &ActionId) -> Result<Vec<Version>, ResolutionError> {
+        self.tags_result.clone()
+    }
+
+    fn describe_sha(
+        &self,
+        _id: &ActionId,
+        _sha: &CommitSha,
+    ) -> Result<ShaDescription, ResolutionError> {
+        let meta = self.resolve_result.clone()?;
+        let tags = self.tags_result.clone().unwrap_or_default();
+        Ok(ShaDescription {
+            tags,
+            repository: meta.repository,
+            date: meta.date,
+            provenance: Provenance::default(),
+        })
+    }
+}
+
 /// Registry that always fails with `AuthRequired` on every method.
 pub struct AuthRequiredRegistry;
 
@@ -98,6 +148,7 @@ impl VersionRegistry for FakeRegistry {
             repository: id.base_repo(),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         })
     }
 
@@ -144,6 +195,7 @@ impl VersionRegistry for FakeRegistry {
             tags,
             repository: id.base_repo(),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         })
     }
 }
@@ -0,0 +1,143 @@
+use crate::domain::action::identity::{ActionId, CommitSha, Version};
+use crate::domain::lock::Lock;
+use std::collections::BTreeSet;
+
+/// A trust database: the actions, versions, and commits a security team approved, which
+/// `--trusted-only` lets into gx.lock.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Trust {
+    /// The `(action, version, sha)` of each approved commit.
+    approved: BTreeSet<(String, String, String)>,
+}
+
+impl Trust {
+    /// A database approving every action, version, and commit `lock` pins.
+    #[must_use]
+    pub fn from_lock(lock: &Lock) -> Self {
+        let mut trust = Self::default();
+        for (spec, entry) in lock.entries() {
+            trust.approve(&spec.id, &entry.version, &entry.commit.sha);
+        }
+        trust
+    }
+
+    /// Approve `sha` as `version` of the action `id`; `false` if it already was.
+    pub fn approve(&mut self, id: &ActionId, version: &Version, sha: &CommitSha) -> bool {
+        self.approved
+            .insert((id.to_string(), version.to_string(), sha.to_string()))
+    }
+
+    /// Approve everything `other` approves, and return how many entries were new.
+    pub fn merge(&mut self, other: &Self) -> usize {
+        let before = self.approved.len();
+        self.approved.extend(other.approved.iter().cloned());
+        self.approved.len().saturating_sub(before)
+    }
+
+    /// Whether `sha` is approved as `version` of the action `id`.
+    #[must_use]
+    pub fn is_approved(&self, id: &ActionId, version: &Version, sha: &CommitSha) -> bool {
+        self.approved
+            .contains(&(id.to_string(), version.to_string(), sha.to_string()))
+    }
+
+    /// The approved `(action, version, sha)` entries, sorted.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str, &str)> {
+        self.approved
+            .iter()
+            .map(|(action, version, sha)| (action.as_str(), version.as_str(), sha.as_str()))
+    }
+
+    /// How many entries are approved.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.approved.len()
+    }
+
+    /// Whether nothing is approved.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.approved.is_empty()
+    }
+
+    /// The `action@version (sha)` of each entry `next` adds to `previous` that this
+    /// database does not approve, sorted. Entries already in `previous` stay trusted.
+    #[must_use]
+    pub fn untrusted(&self, previous: &Lock, next: &Lock) -> Vec<String> {
+        let known = Self::from_lock(previous);
+        let untrusted: BTreeSet<String> = next
+            .entries()
+            .filter(|(spec, entry)| {
+                let (version, sha) = (&entry.version, &entry.commit.sha);
+                !known.is_approved(&spec.id, version, sha)
+                    && !self.is_approved(&spec.id, version, sha)
+            })
+            .map(|(spec, entry)| format!("{}@{} ({})", spec.id, entry.version, entry.commit.sha))
+            .collect();
+        untrusted.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Trust;
+    use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
+    use crate::domain::action::resolved::{Commit, Provenance};
+    use crate::domain::action::spec::Spec;
+    use crate::domain::action::specifier::Specifier;
+    use crate::domain::lock::Lock;
+
+    /// A lock pinning `actions/checkout` `^4` to `version` at `sha`.
+    fn checkout(version: &str, sha: &str) -> Lock {
+        let mut lock = Lock::default();
+        lock.set(
+            &Spec::new(ActionId::from("actions/checkout"), Specifier::parse("^4")),
+            Version::from(version),
+            Commit {
+                sha: CommitSha::from(sha),
+                repository: Repository::from("actions/checkout"),
+                ref_type: None,
+                date: CommitDate::from("2026-01-01T00:00:00Z"),
+                provenance: Provenance::default(),
+            },
+        );
+        lock
+    }
+
+    #[test]
+    fn only_new_commits_outside_the_database_are_untrusted() {
+        let previous = checkout("v4.1.0", "aaa111");
+        let mut trust = Trust::default();
+        trust.approve(
+            &ActionId::from("actions/checkout"),
+            &Version::from("v4.2.0"),
+            &CommitSha::from("bbb222"),
+        );
+
+        assert!(trust.untrusted(&previous, &previous).is_empty());
+        assert!(
+            trust
+                .untrusted(&previous, &checkout("v4.2.0", "bbb222"))
+                .is_empty()
+        );
+        assert_eq!(
+            trust.untrusted(&previous, &checkout("v4.2.0", "ccc333")),
+            ["actions/checkout@v4.2.0 (ccc333)"]
+        );
+    }
+
+    #[test]
+    fn merge_counts_new_entries() {
+        let mut trust = Trust::from_lock(&checkout("v4.1.0", "aaa111"));
+
+        assert_eq!(
+            trust.merge(&Trust::from_lock(&checkout("v4.1.0", "aaa111"))),
+            0
+        );
+        assert_eq!(
+            trust.merge(&Trust::from_lock(&checkout("v4.2.0", "bbb222"))),
+            1
+        );
+        assert_eq!(trust.len(), 2);
+    }
+}
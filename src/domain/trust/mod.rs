@@ -0,0 +1,6 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// The set of approved actions, versions, and commits, and the lock entries outside it.
+mod database;
+
+pub use database::Trust;
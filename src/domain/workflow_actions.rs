@@ -109,6 +109,33 @@ impl WorkflowPath {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Whether the glob `pattern` matches this path or any of its `/`-separated
+    /// suffixes, so `"*-windows.yml"` matches `".github/workflows/test-windows.yml"`.
+    /// `*` does not cross `/`. An invalid pattern never matches.
+    #[must_use]
+    pub fn matches(&self, pattern: &str) -> bool {
+        let Ok(glob) = glob::Pattern::new(pattern) else {
+            return false;
+        };
+        std::iter::successors(Some(self.as_str()), |rest| {
+            rest.split_once('/').map(|(_, tail)| tail)
+        })
+        .any(|suffix| glob.matches_with(suffix, SEPARATOR_AWARE))
+    }
+}
+
+/// Glob options under which `*` and `?` stop at a `/`.
+const SEPARATOR_AWARE: glob::MatchOptions = glob::MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Whether `pattern` uses glob syntax rather than naming a single workflow or job.
+#[must_use]
+pub fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
 }
 
 impl std::fmt::Display for WorkflowPath {
@@ -126,6 +153,13 @@ impl JobId {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Whether the glob `pattern`, such as `test-*`, matches this job id. An invalid
+    /// pattern never matches.
+    #[must_use]
+    pub fn matches(&self, pattern: &str) -> bool {
+        glob::Pattern::new(pattern).is_ok_and(|glob| glob.matches_with(&self.0, SEPARATOR_AWARE))
+    }
 }
 
 impl std::fmt::Display for JobId {
@@ -150,7 +184,7 @@ impl From<&str> for JobId {
 ///
 /// Wraps `u16` to make `From<StepIndex> for i64` infallible,
 /// eliminating `expect("step index overflow")` in TOML serialization.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct StepIndex(u16);
 
 impl StepIndex {
@@ -199,6 +233,42 @@ impl std::fmt::Display for StepIndex {
     }
 }
 
+/// How an override picks out a step of a job: by the step's `id:` or `name:`, which
+/// survives steps being inserted above it, or by its 0-based index.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StepKey {
+    Index(StepIndex),
+    Name(String),
+}
+
+impl StepKey {
+    /// The key an override created for `location` should use: the step's name when it
+    /// has one, its index otherwise.
+    #[must_use]
+    pub fn for_location(location: &Location) -> Option<Self> {
+        location
+            .step_name
+            .clone()
+            .map(Self::Name)
+            .or_else(|| location.step.map(Self::Index))
+    }
+
+    /// Whether this key picks out the step at `location`.
+    #[must_use]
+    pub fn matches(&self, location: &Location) -> bool {
+        match self {
+            Self::Index(index) => location.step == Some(*index),
+            Self::Name(name) => location.step_name.as_ref() == Some(name),
+        }
+    }
+}
+
+impl From<StepIndex> for StepKey {
+    fn from(index: StepIndex) -> Self {
+        Self::Index(index)
+    }
+}
+
 /// The precise location of a `uses:` reference within the workflow tree.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Location {
@@ -208,6 +278,8 @@ pub struct Location {
     pub job: Option<JobId>,
     /// 0-based step index within the job.
     pub step: Option<StepIndex>,
+    /// The step's `id:`, or its `name:` when it has no id, if either is declared.
+    pub step_name: Option<String>,
     /// 1-based source line of the `uses:` scalar, when known. `None` for locations
     /// synthesized outside a parse (e.g. manifest-derived entries).
     pub line: Option<u32>,
@@ -268,12 +340,14 @@ mod tests {
             workflow: WorkflowPath::new(".github/workflows/ci.yml"),
             job: Some(JobId::from("build")),
             step: Some(StepIndex::from(0_u16)),
+            step_name: None,
             line: None,
         };
         let loc2 = Location {
             workflow: WorkflowPath::new(".github/workflows/ci.yml"),
             job: Some(JobId::from("build")),
             step: Some(StepIndex::from(0_u16)),
+            step_name: None,
             line: None,
         };
         assert_eq!(loc1, loc2);
@@ -285,6 +359,7 @@ mod tests {
             workflow: WorkflowPath::new(".github/workflows/ci.yml"),
             job: Some(JobId::from("build")),
             step: Some(StepIndex::from(0_u16)),
+            step_name: None,
             line: None,
         };
         let action = Located {
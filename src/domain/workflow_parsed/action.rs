@@ -0,0 +1,53 @@
+//! The `runs:` block of an action's `action.yml`, for the steps of composite actions.
+
+use super::Step;
+use serde::Deserialize;
+
+/// Metadata of an action, from its `action.yml`. Only `runs:` is captured.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ActionMetadata {
+    /// How the action runs.
+    #[serde(default)]
+    pub runs: Runs,
+}
+
+/// The `runs:` block of an action.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Runs {
+    /// The runtime: `composite`, `docker`, or a Node.js version such as `node20`.
+    #[serde(default)]
+    pub using: String,
+    /// The steps of a composite action; empty for other runtimes.
+    #[serde(default)]
+    pub steps: Vec<Step>,
+}
+
+impl ActionMetadata {
+    /// Parse the content of an `action.yml`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying `serde_saphyr` error if the YAML cannot be deserialized.
+    pub fn from_yaml(content: &str) -> Result<Self, Box<serde_saphyr::Error>> {
+        serde_saphyr::from_str(content).map_err(Box::new)
+    }
+
+    /// Whether the action is a composite action, whose steps can use other actions.
+    #[must_use]
+    pub fn is_composite(&self) -> bool {
+        self.runs.using == "composite"
+    }
+
+    /// The `uses:` references of the composite steps, with their version comments.
+    #[must_use]
+    pub fn uses(&self) -> Vec<(&str, Option<&str>)> {
+        if !self.is_composite() {
+            return Vec::new();
+        }
+        self.runs
+            .steps
+            .iter()
+            .filter_map(|step| Some((step.uses_ref()?, step.uses_comment())))
+            .collect()
+    }
+}
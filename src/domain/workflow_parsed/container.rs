@@ -0,0 +1,101 @@
+//! The `container:` and `services:` images of a job.
+
+use super::ScalarSpan;
+use serde::Deserialize;
+use serde::de::{Deserializer, MapAccess, Visitor};
+use serde_saphyr::{Commented, Spanned};
+use std::fmt;
+
+/// A job's `container:` or one of its `services:`. Accepts the scalar
+/// (`container: node:20`) and map (`container: { image: node:20 }`) forms.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(transparent)]
+pub struct Container(
+    /// The value with the comment and location of the scalar form.
+    Spanned<Commented<Value>>,
+);
+
+/// The two shapes of a `container:` value.
+#[derive(Debug, Clone)]
+enum Value {
+    /// `container: node:20`.
+    Image(String),
+    /// `container: { image: node:20, ... }`; the other keys are not captured, and the
+    /// image is absent when only `credentials:` and the like are set.
+    Map(Option<Spanned<Commented<String>>>),
+}
+
+impl Container {
+    /// The image reference without its comment, if present.
+    #[must_use]
+    pub fn image(&self) -> Option<&str> {
+        match &self.0.value.0 {
+            Value::Image(image) => Some(image),
+            Value::Map(image) => image.as_ref().map(|s| s.value.0.as_str()),
+        }
+    }
+
+    /// The inline comment after the image (e.g. the tag of a digest-pinned image), if any.
+    #[must_use]
+    pub fn image_comment(&self) -> Option<&str> {
+        match &self.0.value.0 {
+            Value::Image(_) => Some(self.0.value.1.as_str()),
+            Value::Map(image) => image.as_ref().map(|s| s.value.1.as_str()),
+        }
+        .filter(|c| !c.is_empty())
+    }
+
+    /// The byte ranges of the image scalar and trailing comment in `content`, the
+    /// workflow text the job was parsed from.
+    #[must_use]
+    pub fn image_span(&self, content: &str) -> Option<ScalarSpan> {
+        let location = match &self.0.value.0 {
+            Value::Image(_) => self.0.defined,
+            Value::Map(image) => image.as_ref()?.defined,
+        };
+        ScalarSpan::locate(&location, content)
+    }
+
+    /// The 1-based source line of the image scalar, if known.
+    #[must_use]
+    pub fn image_line(&self) -> Option<u32> {
+        match &self.0.value.0 {
+            Value::Image(_) => Some(self.0.referenced.line()),
+            Value::Map(image) => image.as_ref().map(|s| s.referenced.line()),
+        }
+        .filter(|&line| line != 0)
+        .and_then(|line| u32::try_from(line).ok())
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = Value;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an image reference or a container map")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Value, E> {
+                Ok(Value::Image(v.to_owned()))
+            }
+            fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Value, E> {
+                Ok(Value::Image(v))
+            }
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+                let mut image = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == "image" {
+                        image = Some(map.next_value()?);
+                    } else {
+                        map.next_value::<serde::de::IgnoredAny>()?;
+                    }
+                }
+                Ok(Value::Map(image))
+            }
+        }
+        de.deserialize_any(V)
+    }
+}
@@ -3,13 +3,20 @@
 use super::workflow_actions::WorkflowPath;
 use serde::de::{Deserializer, MapAccess, Visitor};
 use serde::{Deserialize, Serialize};
-use serde_saphyr::{Commented, Spanned};
 use std::collections::BTreeMap;
 use std::fmt;
 
+mod action;
+mod container;
 mod de;
+mod span;
+mod step;
 mod trigger;
 
+pub use action::{ActionMetadata, Runs};
+pub use container::Container;
+pub use span::ScalarSpan;
+pub use step::Step;
 pub use trigger::Trigger;
 
 use de::deserialize_needs;
@@ -210,78 +217,6 @@ fn normalize_shell(raw: &str) -> String {
     raw.split_whitespace().next().unwrap_or("bash").to_owned()
 }
 
-/// A single step within a job, with the structural fields rule logic needs.
-#[derive(Debug, Clone, Deserialize)]
-pub struct Step {
-    #[serde(default)]
-    pub id: Option<String>,
-    /// The step's `uses:` reference with its inline version comment and source location.
-    /// The nested tuple is opaque; read it through the `uses_*` accessors.
-    #[serde(default)]
-    pub uses: Option<Spanned<Commented<String>>>,
-    #[serde(default, rename = "if")]
-    pub if_cond: Option<String>,
-    #[serde(default)]
-    pub with: BTreeMap<String, AnyScalar>,
-    #[serde(default)]
-    pub env: BTreeMap<String, AnyScalar>,
-    #[serde(default)]
-    pub run: Option<String>,
-    /// The step's `shell:`, if declared. The `run-shellcheck` rule uses this (with
-    /// `defaults.run.shell` as fallback) to decide whether the body is bash/sh.
-    #[serde(default)]
-    pub shell: Option<String>,
-}
-
-impl Step {
-    /// The step's `uses:` action reference without its version comment, if present.
-    #[must_use]
-    pub fn uses_ref(&self) -> Option<&str> {
-        self.uses.as_ref().map(|s| s.value.0.as_str())
-    }
-
-    /// The step's inline `uses:` version comment (e.g. `v4`), if any. saphyr yields an
-    /// empty string for no comment; this normalizes that to `None`.
-    #[must_use]
-    pub fn uses_comment(&self) -> Option<&str> {
-        self.uses
-            .as_ref()
-            .map(|s| s.value.1.as_str())
-            .filter(|c| !c.is_empty())
-    }
-
-    /// The 1-based source line of the step's `uses:` scalar, if present.
-    ///
-    /// saphyr reports line 0 for an unknown location; this normalizes that to `None`.
-    #[must_use]
-    pub fn uses_line(&self) -> Option<u32> {
-        self.uses
-            .as_ref()
-            .map(|s| s.referenced.line())
-            .filter(|&line| line != 0)
-            .and_then(|line| u32::try_from(line).ok())
-    }
-
-    /// All scalar text owned by this step (concatenated `with` values, `env` values, and
-    /// `run` body). Rules text-scan this for expression references like `secrets.NAME`.
-    #[must_use]
-    pub fn scalar_text(&self) -> String {
-        let mut out = String::new();
-        for v in self.with.values() {
-            out.push_str(v.as_str());
-            out.push('\n');
-        }
-        for v in self.env.values() {
-            out.push_str(v.as_str());
-            out.push('\n');
-        }
-        if let Some(run) = &self.run {
-            out.push_str(run);
-        }
-        out
-    }
-}
-
 /// A job within a workflow.
 #[derive(Debug, Clone, Deserialize)]
 pub struct Job {
@@ -302,6 +237,12 @@ pub struct Job {
     pub outputs: BTreeMap<String, String>,
     #[serde(default)]
     pub steps: Vec<Step>,
+    /// The job's `container:`, whose image tidy pins to a digest.
+    #[serde(default)]
+    pub container: Option<Container>,
+    /// The job's `services:`, keyed by service name.
+    #[serde(default)]
+    pub services: BTreeMap<String, Container>,
     #[serde(default)]
     pub secrets: Option<JobSecrets>,
     /// The job's `defaults:` block. Supplies the `run.shell` fallback for steps in this
@@ -390,6 +331,29 @@ impl Parsed {
     /// Returns the underlying `serde_saphyr` error if the YAML cannot be deserialized.
     pub fn from_yaml(path: WorkflowPath, content: &str) -> Result<Self, Box<serde_saphyr::Error>> {
         let wire: WireWorkflow = serde_saphyr::from_str(content).map_err(Box::new)?;
+        Ok(Self::from_wire(path, wire))
+    }
+
+    /// Parse every `---`-separated document of a workflow file, in order, as templating
+    /// tools sometimes write several to one file. Spans stay offsets into the whole
+    /// `content`, and empty documents are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying `serde_saphyr` error if a document cannot be deserialized.
+    pub fn documents(
+        path: &WorkflowPath,
+        content: &str,
+    ) -> Result<Vec<Self>, Box<serde_saphyr::Error>> {
+        let wires: Vec<WireWorkflow> = serde_saphyr::from_multiple(content).map_err(Box::new)?;
+        Ok(wires
+            .into_iter()
+            .map(|wire| Self::from_wire(path.clone(), wire))
+            .collect())
+    }
+
+    /// The workflow at `path` that `wire` was deserialized from.
+    fn from_wire(path: WorkflowPath, wire: WireWorkflow) -> Self {
         let jobs = wire
             .jobs
             .into_iter()
@@ -398,14 +362,14 @@ impl Parsed {
                 job
             })
             .collect();
-        Ok(Self {
+        Self {
             path,
             on: wire.on.unwrap_or_default(),
             permissions: wire.permissions,
             concurrency: wire.concurrency,
             defaults: wire.defaults,
             jobs,
-        })
+        }
     }
 
     /// True if any trigger in `on` matches.
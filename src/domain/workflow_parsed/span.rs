@@ -0,0 +1,84 @@
+//! Byte ranges of scalars and their trailing comments in the workflow source.
+
+use serde_saphyr::Location;
+use std::ops::Range;
+
+/// Where a scalar and its trailing comment sit in the source text, so a rewrite can
+/// replace exactly those bytes and nothing around them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScalarSpan {
+    /// The scalar as written, including its quotes when quoted.
+    pub value: Range<usize>,
+    /// The quote character around the scalar, if any.
+    pub quote: Option<char>,
+    /// The trailing comment, from `#` to the last non-blank character of the line.
+    /// `None` when the scalar is followed by anything else, as in flow style.
+    pub comment: Option<Range<usize>>,
+}
+
+impl ScalarSpan {
+    /// Locate the scalar written at `location` in `content`, the text it was parsed
+    /// from. Pass the `defined` location of a `Spanned` value: for a value reached
+    /// through an alias, that is the anchored scalar rather than the `*alias`.
+    ///
+    /// Returns `None` when the location carries no byte offsets or does not fall on
+    /// character boundaries of `content`.
+    #[must_use]
+    pub fn locate(location: &Location, content: &str) -> Option<Self> {
+        let span = location.span();
+        let start = usize::try_from(span.byte_offset()?).ok()?;
+        let end = start.checked_add(usize::try_from(span.byte_len()?).ok()?)?;
+        let text = content.get(start..end)?;
+        let quote = text
+            .chars()
+            .next()
+            .filter(|c| matches!(c, '"' | '\''))
+            .filter(|&c| text.len() > 1 && text.ends_with(c));
+        Some(Self {
+            value: start..end,
+            quote,
+            comment: trailing_comment(content, end),
+        })
+    }
+
+    /// The scalar without its quotes.
+    #[must_use]
+    pub fn unquoted(&self) -> Range<usize> {
+        match self.quote {
+            Some(_) => self.value.start.saturating_add(1)..self.value.end.saturating_sub(1),
+            None => self.value.clone(),
+        }
+    }
+
+    /// The text of the trailing comment after `#`, trimmed; `None` when there is none or
+    /// it is blank.
+    #[must_use]
+    pub fn comment_text<'content>(&self, content: &'content str) -> Option<&'content str> {
+        let comment = content.get(self.comment.clone()?)?;
+        Some(comment.trim_start_matches('#').trim()).filter(|text| !text.is_empty())
+    }
+
+    /// Whether nothing but blanks or a comment follows the scalar on its line, so a
+    /// comment can be added after it. Not so in flow style, where a comment would
+    /// swallow the rest of the mapping.
+    #[must_use]
+    pub fn ends_line(&self, content: &str) -> bool {
+        self.comment.is_some()
+            || content
+                .get(self.value.end..)
+                .and_then(|rest| rest.split(['\n', '\r']).next())
+                .is_some_and(|line| line.trim().is_empty())
+    }
+}
+
+/// The `# comment` following byte `end` on the same line, trimmed of trailing blanks.
+fn trailing_comment(content: &str, end: usize) -> Option<Range<usize>> {
+    let rest = content.get(end..)?;
+    let line = rest.split(['\n', '\r']).next().unwrap_or_default();
+    let body = line.trim_start_matches([' ', '\t']);
+    if !body.starts_with('#') {
+        return None;
+    }
+    let start = end.saturating_add(line.len().saturating_sub(body.len()));
+    Some(start..start.saturating_add(body.trim_end().len()))
+}
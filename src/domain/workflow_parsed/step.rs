@@ -0,0 +1,106 @@
+//! A step of a job, with its `uses:` reference and the fields rules read.
+
+use super::{AnyScalar, ScalarSpan};
+use serde::Deserialize;
+use serde_saphyr::{Commented, Spanned};
+use std::collections::BTreeMap;
+
+/// A single step within a job, with the structural fields rule logic needs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Step {
+    #[serde(default)]
+    pub id: Option<String>,
+    /// The step's `name:`, if declared.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// The step's `uses:` reference with its inline version comment and source location.
+    /// The nested tuple is opaque; read it through the `uses_*` accessors.
+    #[serde(default)]
+    pub uses: Option<Spanned<Commented<String>>>,
+    #[serde(default, rename = "if")]
+    pub if_cond: Option<String>,
+    #[serde(default)]
+    pub with: BTreeMap<String, AnyScalar>,
+    #[serde(default)]
+    pub env: BTreeMap<String, AnyScalar>,
+    #[serde(default)]
+    pub run: Option<String>,
+    /// The step's `shell:`, if declared. The `run-shellcheck` rule uses this (with
+    /// `defaults.run.shell` as fallback) to decide whether the body is bash/sh.
+    #[serde(default)]
+    pub shell: Option<String>,
+}
+
+impl Step {
+    /// What an override can refer to this step by: its `id:`, or its `name:` when it
+    /// has no id.
+    #[must_use]
+    pub fn key(&self) -> Option<&str> {
+        self.id.as_deref().or(self.name.as_deref())
+    }
+
+    /// The step's `uses:` action reference without its version comment, if present.
+    #[must_use]
+    pub fn uses_ref(&self) -> Option<&str> {
+        self.uses.as_ref().map(|s| s.value.0.as_str())
+    }
+
+    /// Whether the step's `uses:` contains a `${{ }}` expression, as in
+    /// `actions/checkout@${{ inputs.ref }}`. Such a reference resolves only at run time,
+    /// so gx neither locks nor pins it.
+    #[must_use]
+    pub fn uses_is_dynamic(&self) -> bool {
+        self.uses_ref().is_some_and(|uses| uses.contains("${{"))
+    }
+
+    /// The step's inline `uses:` version comment (e.g. `v4`), if any. saphyr yields an
+    /// empty string for no comment; this normalizes that to `None`.
+    #[must_use]
+    pub fn uses_comment(&self) -> Option<&str> {
+        self.uses
+            .as_ref()
+            .map(|s| s.value.1.as_str())
+            .filter(|c| !c.is_empty())
+    }
+
+    /// The 1-based source line of the step's `uses:` scalar, if present.
+    ///
+    /// saphyr reports line 0 for an unknown location; this normalizes that to `None`.
+    #[must_use]
+    pub fn uses_line(&self) -> Option<u32> {
+        self.uses
+            .as_ref()
+            .map(|s| s.referenced.line())
+            .filter(|&line| line != 0)
+            .and_then(|line| u32::try_from(line).ok())
+    }
+
+    /// The byte ranges of the step's `uses:` scalar and trailing comment in `content`,
+    /// the workflow text the step was parsed from. For a step reached through an alias,
+    /// these are the bytes of the anchored scalar.
+    #[must_use]
+    pub fn uses_span(&self, content: &str) -> Option<ScalarSpan> {
+        self.uses
+            .as_ref()
+            .and_then(|s| ScalarSpan::locate(&s.defined, content))
+    }
+
+    /// All scalar text owned by this step (concatenated `with` values, `env` values, and
+    /// `run` body). Rules text-scan this for expression references like `secrets.NAME`.
+    #[must_use]
+    pub fn scalar_text(&self) -> String {
+        let mut out = String::new();
+        for v in self.with.values() {
+            out.push_str(v.as_str());
+            out.push('\n');
+        }
+        for v in self.env.values() {
+            out.push_str(v.as_str());
+            out.push('\n');
+        }
+        if let Some(run) = &self.run {
+            out.push_str(run);
+        }
+        out
+    }
+}
@@ -367,3 +367,128 @@ fn effective_shell_normalizes_template_forms() {
     // empty value falls back to bash
     assert_eq!(effective_shell(Some("   "), None, None), "bash");
 }
+
+#[test]
+fn parses_container_and_service_images_in_both_forms() {
+    let p = parse(
+        "on: push
+jobs:
+  build:
+    container: node:20
+    services:
+      db:
+        image: postgres@sha256:0123 # 16
+        ports: [5432]
+      cache:
+        credentials:
+          username: me
+",
+    );
+    let job = &p.jobs[0];
+    let container = job.container.as_ref().unwrap();
+    assert_eq!(container.image(), Some("node:20"));
+    assert_eq!(container.image_line(), Some(4));
+    let db = &job.services["db"];
+    assert_eq!(db.image(), Some("postgres@sha256:0123"));
+    assert_eq!(db.image_comment(), Some("16"));
+    assert_eq!(job.services["cache"].image(), None);
+}
+
+#[test]
+fn action_metadata_lists_the_uses_of_composite_steps_only() {
+    let composite = ActionMetadata::from_yaml(
+        "name: setup
+runs:
+  using: composite
+  steps:
+    - uses: actions/cache@0123456789abcdef0123456789abcdef01234567 # v4
+    - run: echo hi
+      shell: bash
+    - uses: ./helper
+",
+    )
+    .unwrap();
+    let node = ActionMetadata::from_yaml("runs:\n  using: node20\n  main: index.js\n").unwrap();
+
+    assert_eq!(
+        composite.uses(),
+        vec![
+            (
+                "actions/cache@0123456789abcdef0123456789abcdef01234567",
+                Some("v4")
+            ),
+            ("./helper", None),
+        ]
+    );
+    assert!(!node.is_composite());
+    assert!(node.uses().is_empty());
+}
+
+#[test]
+fn uses_span_covers_the_scalar_with_quotes_and_its_trailing_comment() {
+    let content = "jobs:
+  build:
+    steps:
+      - uses: \"actions/checkout@v4\"   # v4.1.0  
+      - { uses: 'actions/cache@v3', with: { key: x } } # not the uses comment
+      -   uses:    actions/setup-node@v4
+";
+    let parsed = parse(content);
+    let spans: Vec<ScalarSpan> = parsed.jobs[0]
+        .steps
+        .iter()
+        .map(|step| step.uses_span(content).unwrap())
+        .collect();
+
+    assert_eq!(
+        content.get(spans[0].value.clone()),
+        Some("\"actions/checkout@v4\"")
+    );
+    assert_eq!(
+        content.get(spans[0].unquoted()),
+        Some("actions/checkout@v4")
+    );
+    assert_eq!(
+        content.get(spans[0].comment.clone().unwrap()),
+        Some("# v4.1.0")
+    );
+    assert_eq!(spans[1].quote, Some('\''));
+    assert_eq!(content.get(spans[1].unquoted()), Some("actions/cache@v3"));
+    assert_eq!(spans[1].comment, None);
+    assert_eq!(
+        content.get(spans[2].value.clone()),
+        Some("actions/setup-node@v4")
+    );
+    assert_eq!(spans[2].comment, None);
+}
+
+#[test]
+fn step_key_prefers_the_id_over_the_name() {
+    let p = parse(
+        "on: push
+jobs:
+  build:
+    steps:
+      - name: Checkout
+        uses: actions/checkout@v4
+      - id: node
+        name: Set up Node
+        uses: actions/setup-node@v4
+      - run: make
+",
+    );
+    let keys: Vec<_> = p.jobs[0].steps.iter().map(Step::key).collect();
+    assert_eq!(keys, [Some("Checkout"), Some("node"), None]);
+}
+
+#[test]
+fn documents_parses_each_document_of_a_file() {
+    let content = "on: push\njobs:\n  a: {}\n---\n---\non: pull_request\njobs:\n  b: {}\n";
+    let documents =
+        Parsed::documents(&WorkflowPath::new(".github/workflows/x.yml"), content).unwrap();
+
+    assert_eq!(documents.len(), 2);
+    assert_eq!(documents[0].on, vec![Trigger::Push]);
+    assert_eq!(documents[1].jobs[0].id, "b");
+    Parsed::from_yaml(WorkflowPath::new("x.yml"), content).unwrap_err();
+}
@@ -0,0 +1,266 @@
+use super::report::Report;
+use crate::command::Command;
+use crate::config::Config;
+use crate::domain::lock::Lock;
+use crate::domain::manifest::Manifest;
+use crate::infra::atomic;
+use crate::infra::git::{Error as GitError, Git};
+use crate::infra::github::{Error as GithubError, Policy, Registry as GithubRegistry};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Errors that can occur during `gx export`.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("gx export reads the actions of gx.toml, which does not exist; run `gx init` first")]
+    NoManifest,
+
+    #[error("failed to write {}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("gx.lock has no actions to allow; run `gx tidy` first")]
+    EmptyLock,
+
+    #[error(
+        "gx export allowed-actions --apply sets the policy through the GitHub API, which --offline disables"
+    )]
+    Offline,
+
+    #[error(transparent)]
+    Git(#[from] GitError),
+
+    #[error(transparent)]
+    Github(#[from] GithubError),
+}
+
+/// What `gx export` generates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    /// Ignore rules for Dependabot, configured in `.github/dependabot.yml`.
+    Dependabot,
+    /// A package rule for Renovate, configured in `renovate.json`.
+    Renovate,
+    /// The `owner/action@sha` patterns of the "Allow specified actions" policy.
+    AllowedActions,
+}
+
+/// Where `gx export allowed-actions --apply` sets the policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Apply {
+    /// The GitHub repository of the `origin` remote.
+    Origin,
+    /// The given organization or repository.
+    To(Policy),
+}
+
+/// `gx export`: generate the configuration that keeps an update bot away from the
+/// actions gx.toml manages, so a repository moving between tools gets no pull requests
+/// from the bot that fight gx's pins, or the allowed actions policy gx.lock implies.
+#[derive(Debug)]
+pub struct Export {
+    /// The bot to generate rules for.
+    pub tool: Tool,
+    /// Where to write the configuration; it is printed when unset.
+    pub file: Option<PathBuf>,
+    /// Where to set the allowed actions policy; only for [`Tool::AllowedActions`].
+    pub apply: Option<Apply>,
+}
+
+impl Command for Export {
+    type Report = Report;
+    type Error = Error;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<Report, Error> {
+        if !config.manifest_path.exists() {
+            return Err(Error::NoManifest);
+        }
+        let repositories = managed_repositories(&config.manifest);
+        let patterns = allowed_patterns(&config.lock);
+        let (text, actions) = match self.tool {
+            Tool::Dependabot => (dependabot(&repositories), repositories.len()),
+            Tool::Renovate => (renovate(&repositories), repositories.len()),
+            Tool::AllowedActions if patterns.is_empty() => return Err(Error::EmptyLock),
+            Tool::AllowedActions => (allowed_actions(&patterns), patterns.len()),
+        };
+        let written = self.file.as_ref().map(|file| repo_root.join(file));
+        if let Some(path) = &written {
+            atomic::write(path, &text).map_err(|source| Error::Write {
+                path: path.clone(),
+                source,
+            })?;
+        }
+        let applied = match &self.apply {
+            Some(apply) if self.tool == Tool::AllowedActions => {
+                if config.settings.offline {
+                    return Err(Error::Offline);
+                }
+                let policy = match apply {
+                    Apply::Origin => {
+                        Policy::Repository(Git::new(repo_root).github_repository("origin")?)
+                    }
+                    Apply::To(policy) => policy.clone(),
+                };
+                GithubRegistry::from_settings(&config.settings)?
+                    .set_allowed_actions(&policy, &patterns)?;
+                Some(policy)
+            }
+            _ => None,
+        };
+        Ok(Report {
+            text,
+            written,
+            actions,
+            applied,
+        })
+    }
+}
+
+/// The `owner/action@sha` of every action gx.lock pins, sorted and deduplicated.
+fn allowed_patterns(lock: &Lock) -> Vec<String> {
+    let patterns: BTreeSet<String> = lock
+        .entries()
+        .map(|(spec, entry)| format!("{}@{}", spec.id, entry.commit.sha))
+        .collect();
+    patterns.into_iter().collect()
+}
+
+/// `patterns` as the comma-separated list the "Allow specified actions" setting takes,
+/// one per line.
+fn allowed_actions(patterns: &[String]) -> String {
+    let mut text = patterns.join(",\n");
+    text.push('\n');
+    text
+}
+
+/// The repositories of the actions in `manifest`, sorted, as the bots name them: an
+/// action in a subdirectory, such as `github/codeql-action/init`, by its repository.
+fn managed_repositories(manifest: &Manifest) -> Vec<String> {
+    let repositories: BTreeSet<String> = manifest
+        .specs()
+        .map(|spec| spec.id.base_repo().to_string())
+        .collect();
+    repositories.into_iter().collect()
+}
+
+/// A `.github/dependabot.yml` whose `github-actions` updates ignore `repositories`.
+fn dependabot(repositories: &[String]) -> String {
+    let header = [
+        "# gx manages these actions in gx.toml; merge the ignore rules into .github/dependabot.yml",
+        "version: 2",
+        "updates:",
+        "  - package-ecosystem: \"github-actions\"",
+        "    directory: \"/\"",
+        "    schedule:",
+        "      interval: \"weekly\"",
+        "    ignore:",
+    ];
+    let mut lines: Vec<String> = header.map(str::to_owned).to_vec();
+    lines.extend(
+        repositories
+            .iter()
+            .map(|repository| format!("      - dependency-name: \"{repository}\"")),
+    );
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// A `renovate.json` with a package rule disabling updates of `repositories`.
+fn renovate(repositories: &[String]) -> String {
+    let config = serde_json::json!({
+        "packageRules": [{
+            "description": "gx manages these actions in gx.toml",
+            "matchManagers": ["github-actions"],
+            "matchPackageNames": repositories,
+            "enabled": false,
+        }],
+    });
+    let mut text = serde_json::to_string_pretty(&config).unwrap_or_default();
+    text.push('\n');
+    text
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    clippy::indexing_slicing,
+    reason = "tests use unwrap and indexing freely"
+)]
+mod tests {
+    use super::{allowed_actions, allowed_patterns, dependabot, managed_repositories, renovate};
+    use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
+    use crate::domain::action::resolved::{Commit, Provenance};
+    use crate::domain::action::spec::Spec;
+    use crate::domain::action::specifier::Specifier;
+    use crate::domain::lock::Lock;
+    use crate::domain::manifest::Manifest;
+
+    #[test]
+    fn rules_name_each_repository_gx_manages_once() {
+        let mut manifest = Manifest::default();
+        for id in [
+            "github/codeql-action/init",
+            "github/codeql-action/analyze",
+            "actions/checkout",
+        ] {
+            manifest.set(ActionId::from(id), Specifier::parse("^3"));
+        }
+
+        let repositories = managed_repositories(&manifest);
+
+        assert_eq!(repositories, ["actions/checkout", "github/codeql-action"]);
+        let yaml = dependabot(&repositories);
+        assert!(yaml.contains("      - dependency-name: \"github/codeql-action\"\n"));
+        let json: serde_json::Value = serde_json::from_str(&renovate(&repositories)).unwrap();
+        assert_eq!(
+            json["packageRules"][0]["matchPackageNames"],
+            serde_json::json!(["actions/checkout", "github/codeql-action"])
+        );
+        assert_eq!(json["packageRules"][0]["enabled"], false);
+    }
+
+    #[test]
+    fn allowed_actions_list_each_locked_commit_once() {
+        let mut lock = Lock::default();
+        for (action, specifier, sha) in [
+            ("actions/checkout", "^4", "aaa111"),
+            ("actions/checkout", "^4.2", "aaa111"),
+            ("github/codeql-action/init", "^3", "bbb222"),
+        ] {
+            lock.set(
+                &Spec::new(ActionId::from(action), Specifier::parse(specifier)),
+                Version::from("v4"),
+                Commit {
+                    sha: CommitSha::from(sha),
+                    repository: Repository::from(action),
+                    ref_type: None,
+                    date: CommitDate::from("2026-01-01T00:00:00Z"),
+                    provenance: Provenance::default(),
+                },
+            );
+        }
+
+        let patterns = allowed_patterns(&lock);
+
+        assert_eq!(
+            patterns,
+            [
+                "actions/checkout@aaa111",
+                "github/codeql-action/init@bbb222"
+            ]
+        );
+        assert_eq!(
+            allowed_actions(&patterns),
+            "actions/checkout@aaa111,\ngithub/codeql-action/init@bbb222\n"
+        );
+    }
+}
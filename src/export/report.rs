@@ -0,0 +1,39 @@
+use crate::command::CommandReport;
+use crate::infra::github::Policy;
+use crate::output::lines::Line as OutputLine;
+use std::path::PathBuf;
+
+/// Report from `gx export`.
+#[derive(Debug, Default)]
+pub struct Report {
+    /// The generated configuration.
+    pub text: String,
+    /// The file it was written to, when it was not printed.
+    pub written: Option<PathBuf>,
+    /// How many repositories it keeps the bot away from, or actions it allows.
+    pub actions: usize,
+    /// Where the allowed actions policy was set, with `--apply`.
+    pub applied: Option<Policy>,
+}
+
+impl CommandReport for Report {
+    fn render(&self) -> Vec<OutputLine> {
+        if let Some(policy) = &self.applied {
+            return vec![OutputLine::Summary {
+                text: format!("Allowed {} action(s) in {policy}", self.actions),
+            }];
+        }
+        match &self.written {
+            Some(path) => vec![OutputLine::Summary {
+                text: format!(
+                    "Wrote rules for {} action(s) to {}",
+                    self.actions,
+                    path.display()
+                ),
+            }],
+            None => vec![OutputLine::Text {
+                text: self.text.trim_end().to_owned(),
+            }],
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use super::report::{Pin as PinReport, Pinned};
+use crate::command::Command;
+use crate::config::Config;
+use crate::infra::atomic;
+use crate::infra::gitlab::{CI_FILE, Error as RegistryError, Include, Registry, pin, scan};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Errors that can occur during `gx gitlab pin`.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{} not found", path.display())]
+    NoCiFile { path: PathBuf },
+    #[error("failed to read {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to write {}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("{count} include(s) need the GitLab API, which --offline disables")]
+    Offline { count: usize },
+    #[error(transparent)]
+    Registry(#[from] RegistryError),
+}
+
+/// `gx gitlab pin`: pin the refs of `include:component` and `include:project` in
+/// `.gitlab-ci.yml` to commit SHAs.
+pub struct Pin;
+
+impl Command for Pin {
+    type Report = PinReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        config: Config,
+        on_progress: &mut dyn FnMut(&str),
+    ) -> Result<PinReport, Error> {
+        let path = repo_root.join(CI_FILE);
+        if !path.exists() {
+            return Err(Error::NoCiFile { path });
+        }
+        let content = fs::read_to_string(&path).map_err(|source| Error::Read {
+            path: path.clone(),
+            source,
+        })?;
+        let (pinned, unpinned): (Vec<_>, Vec<_>) =
+            scan(&content).into_iter().partition(Include::is_pinned);
+        let mut report = PinReport {
+            already_pinned: pinned.len(),
+            ..PinReport::default()
+        };
+        if unpinned.is_empty() {
+            return Ok(report);
+        }
+        if config.settings.offline {
+            return Err(Error::Offline {
+                count: unpinned.len(),
+            });
+        }
+
+        let registry = Registry::from_settings(&config.settings)?;
+        let mut pins = Vec::new();
+        for include in unpinned {
+            let name = format!("{}@{}", include.project, include.reference);
+            on_progress(&format!("Resolving {name}..."));
+            match registry.resolve(&include.project, &include.reference) {
+                Ok(sha) => {
+                    report.pinned.push(Pinned {
+                        project: include.project.clone(),
+                        reference: include.reference.clone(),
+                        sha: sha.clone(),
+                    });
+                    pins.push((include, sha));
+                }
+                Err(e) => report.failed.push((name, e.to_string())),
+            }
+        }
+        if !pins.is_empty() {
+            atomic::write(&path, &pin(&content, &pins)).map_err(|source| Error::Write {
+                path: path.clone(),
+                source,
+            })?;
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{Error, Pin};
+    use crate::command::Command as _;
+    use crate::config::Config;
+    use std::fs;
+
+    #[test]
+    fn pin_fails_offline_without_touching_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = "include:\n  - project: my-org/templates\n    ref: v3\n";
+        fs::write(dir.path().join(".gitlab-ci.yml"), content).unwrap();
+        let mut config = Config::load(dir.path()).unwrap();
+        config.settings.offline = true;
+
+        let result = Pin.run(dir.path(), config, &mut |_| {});
+
+        assert!(matches!(result, Err(Error::Offline { count: 1 })));
+        assert_eq!(
+            fs::read_to_string(dir.path().join(".gitlab-ci.yml")).unwrap(),
+            content
+        );
+    }
+}
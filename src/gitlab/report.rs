@@ -0,0 +1,100 @@
+use crate::command::CommandReport;
+use crate::domain::action::identity::CommitSha;
+use crate::output::lines::Line as OutputLine;
+
+/// An include whose ref was pinned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pinned {
+    /// The project path, e.g. `my-org/templates`.
+    pub project: String,
+    /// The ref the include pointed at.
+    pub reference: String,
+    /// The commit SHA it now points at.
+    pub sha: CommitSha,
+}
+
+/// Report from `gx gitlab pin`.
+#[derive(Debug, Default)]
+pub struct Pin {
+    /// Includes pinned by this run.
+    pub pinned: Vec<Pinned>,
+    /// Includes that could not be resolved, as `project@ref` and the reason.
+    pub failed: Vec<(String, String)>,
+    /// Includes that were already pinned to a commit SHA.
+    pub already_pinned: usize,
+}
+
+impl CommandReport for Pin {
+    fn render(&self) -> Vec<OutputLine> {
+        let mut lines: Vec<OutputLine> = self
+            .pinned
+            .iter()
+            .map(|pinned| OutputLine::Changed {
+                action: pinned.project.clone(),
+                detail: format!("{} → {}", pinned.reference, pinned.sha),
+            })
+            .collect();
+        lines.extend(
+            self.failed
+                .iter()
+                .map(|(include, reason)| OutputLine::Skipped {
+                    action: include.clone(),
+                    reason: reason.clone(),
+                }),
+        );
+        let text = if self.pinned.is_empty() && self.failed.is_empty() {
+            format!(
+                "All {} include(s) in .gitlab-ci.yml are pinned",
+                self.already_pinned
+            )
+        } else {
+            format!("Pinned {} include(s) in .gitlab-ci.yml", self.pinned.len())
+        };
+        lines.push(OutputLine::Summary { text });
+        lines
+    }
+
+    fn exit_code(&self) -> i32 {
+        i32::from(!self.failed.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandReport as _, OutputLine, Pin, Pinned};
+    use crate::domain::action::identity::CommitSha;
+
+    #[test]
+    fn render_lists_pinned_and_failed_includes() {
+        let report = Pin {
+            pinned: vec![Pinned {
+                project: "my-org/templates".to_owned(),
+                reference: "v3".to_owned(),
+                sha: CommitSha::from("0123456789abcdef0123456789abcdef01234567"),
+            }],
+            failed: vec![(
+                "my-org/gone@v1".to_owned(),
+                "GitLab API returned status 404".to_owned(),
+            )],
+            already_pinned: 2,
+        };
+
+        assert_eq!(
+            report.render(),
+            vec![
+                OutputLine::Changed {
+                    action: "my-org/templates".to_owned(),
+                    detail: "v3 → 0123456789abcdef0123456789abcdef01234567".to_owned(),
+                },
+                OutputLine::Skipped {
+                    action: "my-org/gone@v1".to_owned(),
+                    reason: "GitLab API returned status 404".to_owned(),
+                },
+                OutputLine::Summary {
+                    text: "Pinned 1 include(s) in .gitlab-ci.yml".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(report.exit_code(), 1);
+    }
+}
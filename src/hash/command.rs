@@ -0,0 +1,99 @@
+use super::report::Report;
+use crate::command::Command;
+use crate::config::Config;
+use crate::domain::lock::Lock;
+use crate::infra::manifest;
+use sha1::{Digest as _, Sha1};
+use std::path::Path;
+use thiserror::Error;
+
+/// Errors that can occur during `gx hash`.
+#[derive(Debug, Error)]
+pub enum Error {}
+
+/// `gx hash`: print a digest of the dependency state, gx.toml and gx.lock as gx reads
+/// them, for use as a CI cache key or to tell whether two commits pin the same actions.
+///
+/// Comments, formatting, and key order of either file do not change it, nor do the
+/// release and resolution dates gx.lock records; what each specifier resolves to does.
+#[derive(Debug, Default)]
+pub struct Hash;
+
+impl Command for Hash {
+    type Report = Report;
+    type Error = Error;
+
+    fn run(
+        &self,
+        _repo_root: &Path,
+        config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<Report, Error> {
+        let manifest_hash = manifest::hash(&config.manifest);
+        let lock_hash = lock_digest(&config.lock);
+        let hash = format!(
+            "{:x}",
+            Sha1::digest(format!("manifest {manifest_hash}\nlock {lock_hash}\n").as_bytes())
+        );
+        Ok(Report {
+            hash,
+            manifest_hash,
+            lock_hash,
+        })
+    }
+}
+
+/// The hex SHA-1 of what each entry and image of `lock` resolves to, one sorted line each.
+fn lock_digest(lock: &Lock) -> String {
+    let mut lines: Vec<String> = lock
+        .entries()
+        .map(|(spec, entry)| format!("{spec} {} {}", entry.version, entry.commit.sha))
+        .chain(
+            lock.images()
+                .map(|(image, digest)| format!("image {image} {}", digest.as_str())),
+        )
+        .collect();
+    lines.sort();
+    format!("{:x}", Sha1::digest(lines.join("\n").as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lock_digest;
+    use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
+    use crate::domain::action::resolved::{Commit, Provenance};
+    use crate::domain::action::spec::Spec;
+    use crate::domain::action::specifier::Specifier;
+    use crate::domain::lock::Lock;
+
+    fn locked(sha: &str, date: &str) -> Lock {
+        let mut lock = Lock::default();
+        lock.set(
+            &Spec::new(ActionId::from("actions/checkout"), Specifier::parse("^4")),
+            Version::from("v4.2.0"),
+            Commit {
+                sha: CommitSha::from(sha),
+                repository: Repository::from("actions/checkout"),
+                ref_type: None,
+                date: CommitDate::from(date),
+                provenance: Provenance::default(),
+            },
+        );
+        lock
+    }
+
+    #[test]
+    fn lock_digest_follows_commits_and_ignores_dates() {
+        let locked_once = lock_digest(&locked("aaa", "2024-01-01T00:00:00Z"));
+
+        assert_eq!(
+            locked_once,
+            lock_digest(&locked("aaa", "2025-06-01T00:00:00Z"))
+        );
+        assert_ne!(
+            locked_once,
+            lock_digest(&locked("bbb", "2024-01-01T00:00:00Z"))
+        );
+        assert_ne!(locked_once, lock_digest(&Lock::default()));
+    }
+}
@@ -0,0 +1,7 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// Hash command: error types, struct, and `Command` implementation.
+mod command;
+pub mod report;
+
+pub use command::{Error, Hash};
@@ -0,0 +1,22 @@
+use crate::command::CommandReport;
+use crate::output::lines::Line as OutputLine;
+use serde::Serialize;
+
+/// Report from `gx hash`.
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    /// The digest of the whole dependency state.
+    pub hash: String,
+    /// The digest of gx.toml alone, as gx.lock records it.
+    pub manifest_hash: String,
+    /// The digest of gx.lock alone.
+    pub lock_hash: String,
+}
+
+impl CommandReport for Report {
+    fn render(&self) -> Vec<OutputLine> {
+        vec![OutputLine::Summary {
+            text: self.hash.clone(),
+        }]
+    }
+}
@@ -0,0 +1,168 @@
+use super::pre_commit::{HOOK_SCRIPT, add_entry, has_entry, is_gx_hook, remove_entry};
+use super::report::{Install as InstallReport, Uninstall as UninstallReport};
+use crate::command::Command;
+use crate::config::Config;
+use crate::infra::atomic;
+use crate::infra::git::{Error as GitError, Git};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// The pre-commit framework's configuration file, at the repository root.
+const PRE_COMMIT_CONFIG: &str = ".pre-commit-config.yaml";
+
+/// Errors that can occur during the hook commands.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Git(#[from] GitError),
+
+    #[error(
+        "{} is not the gx hook; remove it first, or add gx to the pre-commit framework with --pre-commit-config",
+        path.display()
+    )]
+    ForeignHook { path: PathBuf },
+
+    #[error("{} sets repos: in a form gx cannot edit; add the gx hooks by hand", path.display())]
+    UnsupportedConfig { path: PathBuf },
+
+    #[error("failed to update {}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// `gx hook install`: run `gx lint --changed` and `gx tidy --check --staged` before each
+/// commit.
+#[derive(Debug, Default)]
+pub struct Install {
+    /// Add the checks to `.pre-commit-config.yaml` for the pre-commit framework instead
+    /// of writing a git hook.
+    pub pre_commit_config: bool,
+}
+
+impl Command for Install {
+    type Report = InstallReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        _config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<InstallReport, Error> {
+        if self.pre_commit_config {
+            let path = repo_root.join(PRE_COMMIT_CONFIG);
+            let config = read(&path)?.unwrap_or_default();
+            if has_entry(&config) {
+                return Ok(InstallReport {
+                    path,
+                    already_installed: true,
+                });
+            }
+            let added = add_entry(&config)
+                .ok_or_else(|| Error::UnsupportedConfig { path: path.clone() })?;
+            write(&path, &added)?;
+            return Ok(InstallReport {
+                path,
+                already_installed: false,
+            });
+        }
+
+        let path = Git::new(repo_root).hooks_dir()?.join("pre-commit");
+        match read(&path)? {
+            Some(script) if is_gx_hook(&script) => Ok(InstallReport {
+                path,
+                already_installed: true,
+            }),
+            Some(_) => Err(Error::ForeignHook { path }),
+            None => {
+                write(&path, HOOK_SCRIPT)?;
+                make_executable(&path)?;
+                Ok(InstallReport {
+                    path,
+                    already_installed: false,
+                })
+            }
+        }
+    }
+}
+
+/// `gx hook uninstall`: remove the git hook and the pre-commit framework entry gx added,
+/// leaving hooks written by hand alone.
+pub struct Uninstall;
+
+impl Command for Uninstall {
+    type Report = UninstallReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        _config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<UninstallReport, Error> {
+        let mut removed = Vec::new();
+
+        let hook = Git::new(repo_root).hooks_dir()?.join("pre-commit");
+        if read(&hook)?.is_some_and(|script| is_gx_hook(&script)) {
+            fs::remove_file(&hook).map_err(|source| Error::Io {
+                path: hook.clone(),
+                source,
+            })?;
+            removed.push(hook);
+        }
+
+        let path = repo_root.join(PRE_COMMIT_CONFIG);
+        if let Some(config) = read(&path)?.filter(|config| has_entry(config)) {
+            match remove_entry(&config) {
+                (rest, true) => write(&path, &rest)?,
+                (_, false) => fs::remove_file(&path).map_err(|source| Error::Io {
+                    path: path.clone(),
+                    source,
+                })?,
+            }
+            removed.push(path);
+        }
+
+        Ok(UninstallReport { removed })
+    }
+}
+
+/// The content of the file at `path`, or `None` when it does not exist.
+fn read(path: &Path) -> Result<Option<String>, Error> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(source) => Err(Error::Io {
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+/// Write `content` to the file at `path`.
+fn write(path: &Path, content: &str) -> Result<(), Error> {
+    atomic::write(path, content).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Let git run the hook at `path`.
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<(), Error> {
+    use std::os::unix::fs::PermissionsExt as _;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Git for Windows runs hooks through its own shell, whatever their permissions.
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<(), Error> {
+    Ok(())
+}
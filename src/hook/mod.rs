@@ -0,0 +1,9 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// Hook commands: error types, structs, and `Command` implementations.
+mod command;
+/// The hook script and the `.pre-commit-config.yaml` entry gx installs.
+mod pre_commit;
+pub mod report;
+
+pub use command::{Error, Install, Uninstall};
@@ -0,0 +1,137 @@
+/// The line opening the gx entry in `.pre-commit-config.yaml`.
+const START_MARKER: &str = "# >>> gx hook";
+/// The line closing the gx entry in `.pre-commit-config.yaml`.
+const END_MARKER: &str = "# <<< gx hook";
+
+/// The `repos:` entry running gx, before it is indented as an item of the list.
+const ENTRY: [&str; 14] = [
+    START_MARKER,
+    "- repo: local",
+    "  hooks:",
+    "    - id: gx-lint",
+    "      name: gx lint",
+    "      entry: gx lint --changed",
+    "      language: system",
+    "      pass_filenames: false",
+    "    - id: gx-tidy",
+    "      name: gx tidy",
+    "      entry: gx tidy --check --staged",
+    "      language: system",
+    "      pass_filenames: false",
+    END_MARKER,
+];
+
+/// The git `pre-commit` hook gx installs. The marker comment is how `gx hook uninstall`
+/// tells it from a hook written by hand.
+pub(super) const HOOK_SCRIPT: &str = "#!/bin/sh
+# Installed by `gx hook install`; remove it with `gx hook uninstall`.
+gx lint --changed || exit $?
+exec gx tidy --check --staged
+";
+
+/// Whether a git hook is the one gx installed.
+pub(super) fn is_gx_hook(script: &str) -> bool {
+    script.contains("gx hook install")
+}
+
+/// Whether a `.pre-commit-config.yaml` already has the gx entry.
+pub(super) fn has_entry(config: &str) -> bool {
+    config.lines().any(|line| line.trim() == START_MARKER)
+}
+
+/// `config` with the gx entry added first under `repos:`, or `None` when `repos:` is
+/// written in a form that cannot take it, such as the flow style `repos: []`.
+///
+/// The entry is indented like the existing items of the list, so it stays one list.
+pub(super) fn add_entry(config: &str) -> Option<String> {
+    let lines: Vec<&str> = config.lines().collect();
+    let Some(repos) = lines.iter().position(|line| line.starts_with("repos:")) else {
+        let mut added = config.to_owned();
+        if !added.is_empty() && !added.ends_with('\n') {
+            added.push('\n');
+        }
+        added.push_str("repos:\n");
+        added.push_str(&indented_entry("  "));
+        return Some(added);
+    };
+    let rest = lines.get(repos)?.trim_start_matches("repos:").trim();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return None;
+    }
+    let indent = lines
+        .iter()
+        .skip(repos.saturating_add(1))
+        .map(|line| line.trim_end())
+        .find(|line| !line.is_empty() && !line.trim_start().starts_with('#'))
+        .filter(|line| line.trim_start().starts_with('-'))
+        .and_then(|line| line.strip_suffix(line.trim_start()))
+        .unwrap_or("  ");
+    let (head, tail) = lines.split_at(repos.saturating_add(1));
+    let mut added = head.join("\n");
+    added.push('\n');
+    added.push_str(&indented_entry(indent));
+    for line in tail {
+        added.push_str(line);
+        added.push('\n');
+    }
+    Some(added)
+}
+
+/// `config` without the gx entry, and whether anything but an empty `repos:` is left.
+pub(super) fn remove_entry(config: &str) -> (String, bool) {
+    let mut removed = String::new();
+    let mut inside = false;
+    for line in config.lines() {
+        match line.trim() {
+            START_MARKER => inside = true,
+            END_MARKER => inside = false,
+            _ if !inside => {
+                removed.push_str(line);
+                removed.push('\n');
+            }
+            _ => {}
+        }
+    }
+    let kept = removed
+        .lines()
+        .any(|line| !line.trim().is_empty() && line.trim() != "repos:");
+    (removed, kept)
+}
+
+/// The gx entry with every line indented by `indent`.
+fn indented_entry(indent: &str) -> String {
+    let mut entry = String::new();
+    for line in ENTRY {
+        entry.push_str(indent);
+        entry.push_str(line);
+        entry.push('\n');
+    }
+    entry
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{add_entry, has_entry, remove_entry};
+
+    #[test]
+    fn entry_is_added_under_repos_at_the_lists_indent_and_removed_again() {
+        let config = "repos:\n- repo: https://github.com/pre-commit/pre-commit-hooks\n  rev: v5.0.0\n  hooks:\n  - id: check-yaml\n";
+
+        let added = add_entry(config).unwrap();
+
+        assert!(has_entry(&added));
+        assert!(added.starts_with("repos:\n# >>> gx hook\n- repo: local\n  hooks:\n"));
+        assert!(added.ends_with(config.trim_start_matches("repos:\n")));
+        assert_eq!(remove_entry(&added), (config.to_owned(), true));
+    }
+
+    #[test]
+    fn entry_starts_a_new_config_and_leaves_nothing_behind() {
+        let added = add_entry("").unwrap();
+
+        assert!(added.starts_with("repos:\n  # >>> gx hook\n  - repo: local\n"));
+        assert!(!remove_entry(&added).1);
+        assert!(add_entry("repos: []\n").is_none());
+    }
+}
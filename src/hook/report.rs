@@ -0,0 +1,46 @@
+use crate::command::CommandReport;
+use crate::output::lines::Line as OutputLine;
+use std::path::PathBuf;
+
+/// Report from `gx hook install`.
+#[derive(Debug, Default)]
+pub struct Install {
+    /// The hook or pre-commit configuration file the checks run from.
+    pub path: PathBuf,
+    /// Whether gx was already set up there, so nothing was written.
+    pub already_installed: bool,
+}
+
+impl CommandReport for Install {
+    fn render(&self) -> Vec<OutputLine> {
+        let text = if self.already_installed {
+            format!("gx hook already installed in {}", self.path.display())
+        } else {
+            format!("Installed gx hook in {}", self.path.display())
+        };
+        vec![OutputLine::Summary { text }]
+    }
+}
+
+/// Report from `gx hook uninstall`.
+#[derive(Debug, Default)]
+pub struct Uninstall {
+    /// The files gx was removed from.
+    pub removed: Vec<PathBuf>,
+}
+
+impl CommandReport for Uninstall {
+    fn render(&self) -> Vec<OutputLine> {
+        if self.removed.is_empty() {
+            return vec![OutputLine::Summary {
+                text: "No gx hook installed".to_owned(),
+            }];
+        }
+        self.removed
+            .iter()
+            .map(|path| OutputLine::Summary {
+                text: format!("Removed gx hook from {}", path.display()),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,250 @@
+use super::dependabot::{self, Update};
+use super::report::Report;
+use crate::command::Command;
+use crate::config::Config;
+use crate::domain::action::identity::ActionId;
+use crate::domain::manifest::{Annotation, Manifest};
+use crate::infra::manifest::{Error as ManifestError, Store as ManifestStore};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// The reason recorded on the actions an ignore rule holds.
+const HOLD_REASON: &str = "ignored in dependabot.yml";
+
+/// Errors that can occur during `gx import`.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("gx import writes into gx.toml, which does not exist; run `gx init` first")]
+    NoManifest,
+
+    #[error("no Dependabot configuration at {}", path.display())]
+    NotFound { path: PathBuf },
+
+    #[error("failed to read {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse {}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: Box<serde_saphyr::Error>,
+    },
+
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+}
+
+/// `gx import dependabot`: translate the `github-actions` updates of a Dependabot
+/// configuration into gx.toml. Ignore rules become holds and groups become `[groups]`;
+/// what gx has no equivalent for is reported instead.
+#[derive(Debug, Default)]
+pub struct Dependabot {
+    /// The configuration to read, relative to the repository root; `.github/dependabot.yml`,
+    /// or `.github/dependabot.yaml`, when unset.
+    pub file: Option<PathBuf>,
+}
+
+impl Command for Dependabot {
+    type Report = Report;
+    type Error = Error;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<Report, Error> {
+        if !config.manifest_path.exists() {
+            return Err(Error::NoManifest);
+        }
+        let path = self.file.as_ref().map_or_else(
+            || {
+                let github = repo_root.join(".github");
+                let yml = github.join("dependabot.yml");
+                if yml.exists() {
+                    yml
+                } else {
+                    github.join("dependabot.yaml")
+                }
+            },
+            |file| repo_root.join(file),
+        );
+        if !path.is_file() {
+            return Err(Error::NotFound { path });
+        }
+        let content = fs::read_to_string(&path).map_err(|source| Error::Read {
+            path: path.clone(),
+            source,
+        })?;
+        let dependabot: dependabot::Config =
+            serde_saphyr::from_str(&content).map_err(|source| Error::Parse {
+                path: path.clone(),
+                source: Box::new(source),
+            })?;
+        let mut manifest = config.manifest;
+        let mut report = translate(&dependabot, &mut manifest);
+        if !report.held.is_empty() || !report.groups.is_empty() {
+            ManifestStore::new(&config.manifest_path).save(&manifest)?;
+        }
+        report.source = path.strip_prefix(repo_root).unwrap_or(&path).to_path_buf();
+        Ok(report)
+    }
+}
+
+/// Apply the `github-actions` updates of `dependabot` to `manifest`, and report what was
+/// imported and what was not.
+fn translate(dependabot: &dependabot::Config, manifest: &mut Manifest) -> Report {
+    let mut report = Report::default();
+    let ids: Vec<ActionId> = manifest.specs().map(|spec| spec.id.clone()).collect();
+    let matching = |patterns: &[String], excluded: &[String]| -> Vec<ActionId> {
+        ids.iter()
+            .filter(|id| patterns.iter().any(|p| dependabot::matches(p, id.as_str())))
+            .filter(|id| !excluded.iter().any(|p| dependabot::matches(p, id.as_str())))
+            .cloned()
+            .collect()
+    };
+    for update in dependabot.github_actions() {
+        for rule in &update.ignore {
+            let subject = format!("ignore {}", rule.dependency_name);
+            if !rule.versions.is_empty() || !rule.update_types.is_empty() {
+                report.skipped.push((
+                    subject,
+                    "ignoring only some versions has no gx equivalent; the specifier in \
+                     gx.toml bounds upgrades"
+                        .to_owned(),
+                ));
+                continue;
+            }
+            let held = matching(std::slice::from_ref(&rule.dependency_name), &[]);
+            if held.is_empty() {
+                report
+                    .skipped
+                    .push((subject, "matches no action in gx.toml".to_owned()));
+            }
+            for id in held {
+                if manifest.is_held(&id) {
+                    continue;
+                }
+                if manifest.annotation(&id).is_none() {
+                    manifest.annotate(
+                        id.clone(),
+                        Annotation {
+                            reason: Some(HOLD_REASON.to_owned()),
+                            owner: None,
+                        },
+                    );
+                }
+                manifest.hold(id.clone());
+                report.held.push(id.to_string());
+            }
+        }
+        for (name, group) in &update.groups {
+            let subject = format!("group {name}");
+            let members = matching(&group.patterns, &group.exclude_patterns);
+            if manifest.group(name).is_some() {
+                report
+                    .skipped
+                    .push((subject, "[groups] already has it".to_owned()));
+            } else if members.is_empty() {
+                report
+                    .skipped
+                    .push((subject, "matches no action in gx.toml".to_owned()));
+            } else {
+                report.groups.push((
+                    name.clone(),
+                    members.iter().map(ToString::to_string).collect(),
+                ));
+                manifest.set_group(name.clone(), members);
+            }
+        }
+        report.skipped.extend(unsupported(update));
+    }
+    report.held.sort();
+    report.held.dedup();
+    report
+}
+
+/// The settings of `update` gx has no equivalent for, with what to do instead.
+fn unsupported(update: &Update) -> Vec<(String, String)> {
+    let mut skipped = Vec::new();
+    if let Some(schedule) = &update.schedule {
+        skipped.push((
+            format!("schedule {}", schedule.interval),
+            "gx has no schedule; run `gx upgrade` from a scheduled workflow".to_owned(),
+        ));
+    }
+    if let Some(days) = update
+        .cooldown
+        .as_ref()
+        .and_then(|cooldown| cooldown.default_days)
+    {
+        skipped.push((
+            format!("cooldown {days} days"),
+            "gx has no cooldown; releases are taken as soon as they match".to_owned(),
+        ));
+    }
+    skipped
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{HOLD_REASON, translate};
+    use crate::domain::action::identity::ActionId;
+    use crate::domain::action::specifier::Specifier;
+    use crate::domain::manifest::Manifest;
+
+    #[test]
+    fn ignores_become_holds_and_groups_become_groups() {
+        let dependabot = serde_saphyr::from_str(
+            r#"
+version: 2
+updates:
+  - package-ecosystem: "github-actions"
+    directory: "/"
+    schedule:
+      interval: "weekly"
+    ignore:
+      - dependency-name: "docker/*"
+      - dependency-name: "actions/cache"
+        update-types: ["version-update:semver-major"]
+    groups:
+      official:
+        patterns: ["actions/*"]
+        exclude-patterns: ["actions/cache"]
+  - package-ecosystem: "npm"
+    directory: "/"
+    ignore:
+      - dependency-name: "actions/checkout"
+"#,
+        )
+        .unwrap();
+        let mut manifest = Manifest::default();
+        for id in ["actions/checkout", "actions/cache", "docker/login-action"] {
+            manifest.set(ActionId::from(id), Specifier::parse("^4"));
+        }
+
+        let report = translate(&dependabot, &mut manifest);
+
+        assert_eq!(report.held, ["docker/login-action"]);
+        let docker = ActionId::from("docker/login-action");
+        assert!(manifest.is_held(&docker));
+        assert_eq!(manifest.reason(&docker), Some(HOLD_REASON));
+        assert!(!manifest.is_held(&ActionId::from("actions/checkout")));
+        assert_eq!(
+            manifest.group("official"),
+            Some([ActionId::from("actions/checkout")].as_slice())
+        );
+        let skipped: Vec<&str> = report
+            .skipped
+            .iter()
+            .map(|(subject, _)| subject.as_str())
+            .collect();
+        assert_eq!(skipped, ["ignore actions/cache", "schedule weekly"]);
+    }
+}
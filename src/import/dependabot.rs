@@ -0,0 +1,90 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// The ecosystem of the Dependabot updates that cover GitHub Actions.
+const ECOSYSTEM: &str = "github-actions";
+
+/// A `.github/dependabot.yml`, with only what gx can translate.
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    /// One entry per ecosystem and directory Dependabot updates.
+    #[serde(default)]
+    pub updates: Vec<Update>,
+}
+
+/// An entry of `updates`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Update {
+    /// The ecosystem, such as `github-actions` or `npm`.
+    pub package_ecosystem: String,
+    /// When Dependabot checks for updates, such as `weekly`.
+    #[serde(default)]
+    pub schedule: Option<Schedule>,
+    /// The dependencies, or versions of them, Dependabot leaves alone.
+    #[serde(default)]
+    pub ignore: Vec<Ignore>,
+    /// Updates Dependabot opens as one pull request, by group name.
+    #[serde(default)]
+    pub groups: BTreeMap<String, Group>,
+    /// How long Dependabot waits after a release before updating to it.
+    #[serde(default)]
+    pub cooldown: Option<Cooldown>,
+}
+
+/// The `schedule` of an update.
+#[derive(Debug, Default, Deserialize)]
+pub struct Schedule {
+    /// How often, such as `daily` or `weekly`.
+    #[serde(default)]
+    pub interval: String,
+}
+
+/// An `ignore` rule.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Ignore {
+    /// The dependency, where `*` matches any text.
+    pub dependency_name: String,
+    /// Only these versions are ignored.
+    #[serde(default)]
+    pub versions: Vec<String>,
+    /// Only these kinds of update are ignored, such as `version-update:semver-major`.
+    #[serde(default)]
+    pub update_types: Vec<String>,
+}
+
+/// A group of `groups`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Group {
+    /// The dependencies in the group, where `*` matches any text.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    /// The dependencies left out of it, in the same form.
+    #[serde(default)]
+    pub exclude_patterns: Vec<String>,
+}
+
+/// The `cooldown` of an update.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Cooldown {
+    /// Days to wait after any release.
+    #[serde(default)]
+    pub default_days: Option<u32>,
+}
+
+impl Config {
+    /// The updates for GitHub Actions, in the order the file lists them.
+    pub fn github_actions(&self) -> impl Iterator<Item = &Update> {
+        self.updates
+            .iter()
+            .filter(|update| update.package_ecosystem == ECOSYSTEM)
+    }
+}
+
+/// Whether the Dependabot dependency pattern `pattern` matches `name`.
+pub fn matches(pattern: &str, name: &str) -> bool {
+    glob::Pattern::new(pattern).is_ok_and(|glob| glob.matches(name))
+}
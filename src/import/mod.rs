@@ -0,0 +1,9 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// Import command: error types, struct, and `Command` implementation.
+mod command;
+/// The `github-actions` settings of a Dependabot configuration.
+mod dependabot;
+pub mod report;
+
+pub use command::{Dependabot, Error};
@@ -0,0 +1,50 @@
+use crate::command::CommandReport;
+use crate::output::lines::Line as OutputLine;
+use std::path::PathBuf;
+
+/// Report from `gx import dependabot`.
+#[derive(Debug, Default)]
+pub struct Report {
+    /// The configuration imported, relative to the repository root.
+    pub source: PathBuf,
+    /// The actions now held, sorted.
+    pub held: Vec<String>,
+    /// The groups added to `[groups]`, with their members.
+    pub groups: Vec<(String, Vec<String>)>,
+    /// The settings not imported: (what, why).
+    pub skipped: Vec<(String, String)>,
+}
+
+impl CommandReport for Report {
+    fn render(&self) -> Vec<OutputLine> {
+        let mut lines: Vec<OutputLine> = self
+            .held
+            .iter()
+            .map(|action| OutputLine::Changed {
+                action: action.clone(),
+                detail: "held".to_owned(),
+            })
+            .collect();
+        lines.extend(self.groups.iter().map(|(name, members)| OutputLine::Added {
+            action: format!("[groups] {name}"),
+            version: members.join(", "),
+        }));
+        lines.extend(
+            self.skipped
+                .iter()
+                .map(|(subject, reason)| OutputLine::Skipped {
+                    action: subject.clone(),
+                    reason: reason.clone(),
+                }),
+        );
+        lines.push(OutputLine::Summary {
+            text: format!(
+                "Imported {} hold(s) and {} group(s) from {}",
+                self.held.len(),
+                self.groups.len(),
+                self.source.display()
+            ),
+        });
+        lines
+    }
+}
@@ -0,0 +1,112 @@
+use std::fs::{self, File};
+use std::io::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Taken for each [`write`], so [`hold`] can stop the writes of a run it is rolling back.
+static WRITES: Mutex<()> = Mutex::new(());
+
+/// Write `content` to `path` so that a crash leaves either the old file or the new one,
+/// never a truncated mix: the content goes to a temporary file beside it, is flushed to
+/// disk, and then renamed over `path`.
+///
+/// A symlink is written through, replacing its target rather than the link, an existing
+/// file keeps its permissions, and a missing parent directory is created.
+///
+/// # Errors
+///
+/// Returns the underlying I/O error if the temporary file cannot be written or renamed.
+/// The temporary file is removed again on failure.
+pub fn write(path: &Path, content: &str) -> io::Result<()> {
+    let _writing = hold();
+    replace(path, content)
+}
+
+/// Block every later [`write`], and wait for the one in progress, until the guard is
+/// dropped; an interrupted run holds it while its files are put back, and exits.
+pub fn hold() -> MutexGuard<'static, ()> {
+    WRITES.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// [`write`], without waiting for writes held by [`hold`], for putting files back while
+/// they are held.
+///
+/// # Errors
+///
+/// Returns the errors of [`write`].
+pub fn replace(path: &Path, content: &str) -> io::Result<()> {
+    let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let temp = temp_path(&target)?;
+    let result = write_then_rename(&temp, &target, content.as_bytes());
+    if result.is_err() {
+        // The write error is the one worth reporting; a leftover temporary file is harmless.
+        fs::remove_file(&temp).unwrap_or_default();
+    }
+    result
+}
+
+/// The temporary file `target` is first written to, in the same directory so the rename
+/// stays on one filesystem.
+fn temp_path(target: &Path) -> io::Result<PathBuf> {
+    let name = target.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a file path", target.display()),
+        )
+    })?;
+    let temp = format!(".{}.{}.tmp", name.to_string_lossy(), std::process::id());
+    Ok(target.with_file_name(temp))
+}
+
+/// Write `content` to `temp`, sync it, and move it over `target`.
+fn write_then_rename(temp: &Path, target: &Path, content: &[u8]) -> io::Result<()> {
+    if let Some(dir) = target.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = File::create(temp)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+    if let Ok(metadata) = fs::metadata(target) {
+        fs::set_permissions(temp, metadata.permissions())?;
+    }
+    fs::rename(temp, target)
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::write;
+    use std::fs;
+
+    #[test]
+    fn write_replaces_the_file_and_leaves_no_temporary_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gx.lock");
+        fs::write(&path, "old\n").unwrap();
+
+        write(&path, "new\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new\n");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_goes_through_a_symlink_to_its_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("shared.yml");
+        let link = dir.path().join("ci.yml");
+        fs::write(&target, "old\n").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        write(&link, "new\n").unwrap();
+
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new\n");
+    }
+}
@@ -0,0 +1,221 @@
+use crate::config::GitHubToken;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Scope requested for the token: read access to private action repositories.
+const SCOPE: &str = "repo";
+/// Grant type of the device flow token request.
+const GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+/// Seconds added to the polling interval when GitHub answers `slow_down`.
+const SLOW_DOWN_SECS: u64 = 5;
+
+/// Errors that can occur during the OAuth device flow.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to create HTTP client")]
+    ClientInit(#[source] reqwest::Error),
+
+    #[error("failed to request {url}")]
+    Request {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    #[error("failed to parse response from {url}")]
+    Parse {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    #[error("the device code expired before the login was approved")]
+    Expired,
+
+    #[error("the login was denied")]
+    Denied,
+
+    #[error("GitHub rejected the login: {0}")]
+    Rejected(String),
+}
+
+/// The code the user enters at `verification_uri` to approve the login.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Code {
+    /// Opaque code gx polls with.
+    pub device_code: String,
+    /// Short code shown to the user.
+    pub user_code: String,
+    /// Page where the user enters `user_code`.
+    pub verification_uri: String,
+    /// Minimum seconds between polls.
+    pub interval: u64,
+}
+
+/// Response of the token endpoint: a token, or an error code while the login is pending.
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    /// The token, once the user approved the login.
+    access_token: Option<String>,
+    /// `authorization_pending`, `slow_down`, `expired_token`, `access_denied`, ...
+    error: Option<String>,
+}
+
+/// What to do after one poll of the token endpoint.
+#[derive(Debug, PartialEq, Eq)]
+enum Poll {
+    /// The login was approved.
+    Token(String),
+    /// Ask again after the interval.
+    Pending,
+    /// Ask again after a longer interval.
+    SlowDown,
+}
+
+/// OAuth device flow against `github.com` or a GitHub Enterprise Server host.
+pub struct Flow {
+    /// HTTP client for the login endpoints.
+    client: reqwest::blocking::Client,
+    /// Web root of the host, e.g. `https://github.com`.
+    base_url: String,
+    /// Client ID of the OAuth app the token is issued to.
+    client_id: String,
+}
+
+impl Flow {
+    /// Start a device flow for `host` with the OAuth app `client_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ClientInit`] if the HTTP client cannot be created.
+    pub fn new(host: &str, client_id: String) -> Result<Self, Error> {
+        let client = reqwest::blocking::Client::builder()
+            .user_agent("gx-cli")
+            .build()
+            .map_err(Error::ClientInit)?;
+        Ok(Self {
+            client,
+            base_url: format!("https://{host}"),
+            client_id,
+        })
+    }
+
+    /// Ask GitHub for a device code and the page to enter it on.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the request fails or the response cannot be parsed.
+    pub fn request_code(&self) -> Result<Code, Error> {
+        let url = format!("{}/login/device/code", self.base_url);
+        self.post(&url, &[("client_id", &self.client_id), ("scope", SCOPE)])?
+            .json()
+            .map_err(|source| Error::Parse { url, source })
+    }
+
+    /// Poll until the user approves `code`, then return the token.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Expired`] or [`Error::Denied`] if the login was not approved.
+    /// Returns [`Error`] if a request fails or a response cannot be parsed.
+    pub fn wait_for_token(&self, code: &Code) -> Result<GitHubToken, Error> {
+        let url = format!("{}/login/oauth/access_token", self.base_url);
+        let mut interval = code.interval;
+        loop {
+            std::thread::sleep(Duration::from_secs(interval));
+            let response: TokenResponse = self
+                .post(
+                    &url,
+                    &[
+                        ("client_id", &self.client_id),
+                        ("device_code", &code.device_code),
+                        ("grant_type", GRANT_TYPE),
+                    ],
+                )?
+                .json()
+                .map_err(|source| Error::Parse {
+                    url: url.clone(),
+                    source,
+                })?;
+            match poll(response)? {
+                Poll::Token(token) => return Ok(GitHubToken::from(token)),
+                Poll::Pending => {}
+                Poll::SlowDown => interval = interval.saturating_add(SLOW_DOWN_SECS),
+            }
+        }
+    }
+
+    /// POST `params` as a JSON object to `url`, asking for a JSON response.
+    fn post(
+        &self,
+        url: &str,
+        params: &[(&str, &str)],
+    ) -> Result<reqwest::blocking::Response, Error> {
+        let body: BTreeMap<&str, &str> = params.iter().copied().collect();
+        self.client
+            .post(url)
+            .header("Accept", "application/json")
+            .json(&body)
+            .send()
+            .map_err(|source| Error::Request {
+                url: url.to_owned(),
+                source,
+            })
+    }
+}
+
+/// Interpret one token endpoint response.
+fn poll(response: TokenResponse) -> Result<Poll, Error> {
+    if let Some(token) = response.access_token.filter(|token| !token.is_empty()) {
+        return Ok(Poll::Token(token));
+    }
+    match response.error.as_deref() {
+        Some("authorization_pending") => Ok(Poll::Pending),
+        Some("slow_down") => Ok(Poll::SlowDown),
+        Some("expired_token") => Err(Error::Expired),
+        Some("access_denied") => Err(Error::Denied),
+        other => Err(Error::Rejected(other.unwrap_or("no token").to_owned())),
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "tests use unwrap, indexing, and other patterns freely"
+)]
+mod tests {
+    use super::{Error, Poll, TokenResponse, poll};
+
+    fn response(json: &str) -> TokenResponse {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn poll_waits_until_a_token_arrives() {
+        assert_eq!(
+            poll(response(r#"{"error":"authorization_pending"}"#)).unwrap(),
+            Poll::Pending
+        );
+        assert_eq!(
+            poll(response(r#"{"error":"slow_down","interval":10}"#)).unwrap(),
+            Poll::SlowDown
+        );
+        assert_eq!(
+            poll(response(
+                r#"{"access_token":"gho_abc","token_type":"bearer","scope":"repo"}"#
+            ))
+            .unwrap(),
+            Poll::Token("gho_abc".to_owned())
+        );
+    }
+
+    #[test]
+    fn poll_stops_when_the_login_fails() {
+        let expired = poll(response(r#"{"error":"expired_token"}"#)).unwrap_err();
+        assert!(matches!(expired, Error::Expired), "{expired}");
+        let denied = poll(response(r#"{"error":"access_denied"}"#)).unwrap_err();
+        assert!(matches!(denied, Error::Denied), "{denied}");
+    }
+}
@@ -0,0 +1,91 @@
+use super::TokenProvider;
+use crate::config::GitHubToken;
+use std::io::Write as _;
+use std::process::{Command, Stdio};
+use thiserror::Error;
+
+/// Service name gx stores its tokens under in the OS keyring.
+const SERVICE: &str = "gx";
+
+/// Errors that can occur when storing a token in the OS keyring.
+#[derive(Debug, Error)]
+pub enum KeyringError {
+    #[error("no OS keyring support on this platform")]
+    Unsupported,
+
+    #[error("failed to store the token in the OS keyring: {0}")]
+    Store(String),
+}
+
+/// A token stored in the OS keyring under the `gx` service, one per host.
+///
+/// Read with `security` on macOS and `secret-tool` (libsecret) on Linux; other
+/// platforms have no keyring provider yet.
+pub struct Keyring;
+
+impl TokenProvider for Keyring {
+    fn name(&self) -> String {
+        "OS keyring".to_owned()
+    }
+
+    fn token(&self, host: &str) -> Option<GitHubToken> {
+        let mut command = if cfg!(target_os = "macos") {
+            let mut security = Command::new("security");
+            security.args(["find-generic-password", "-s", SERVICE, "-a", host, "-w"]);
+            security
+        } else if cfg!(target_os = "linux") {
+            let mut secret_tool = Command::new("secret-tool");
+            secret_tool.args(["lookup", "service", SERVICE, "host", host]);
+            secret_tool
+        } else {
+            return None;
+        };
+        let output = command
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())?;
+        let token = String::from_utf8(output.stdout).ok()?.trim().to_owned();
+        (!token.is_empty()).then(|| GitHubToken::from(token))
+    }
+}
+
+/// Store `token` for `host` in the OS keyring, replacing any token stored before.
+///
+/// # Errors
+///
+/// Returns [`KeyringError::Unsupported`] on platforms without a keyring provider.
+/// Returns [`KeyringError::Store`] if the keyring tool cannot be run or fails.
+pub fn store(host: &str, token: &GitHubToken) -> Result<(), KeyringError> {
+    let label = format!("gx token for {host}");
+    let (mut command, input) = if cfg!(target_os = "macos") {
+        // With no value after the trailing `-w`, `security` prompts for the password and
+        // its confirmation, so the token is written to stdin and never shows up in the
+        // process list.
+        let mut security = Command::new("security");
+        security
+            .args(["add-generic-password", "-U", "-s", SERVICE, "-a", host])
+            .args(["-l", &label, "-w"]);
+        (security, format!("{0}\n{0}\n", token.as_str()))
+    } else if cfg!(target_os = "linux") {
+        let mut secret_tool = Command::new("secret-tool");
+        secret_tool.args(["store", "--label", &label, "service", SERVICE, "host", host]);
+        (secret_tool, token.as_str().to_owned())
+    } else {
+        return Err(KeyringError::Unsupported);
+    };
+    let status = command.stdin(Stdio::piped()).spawn().and_then(|mut child| {
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes())?;
+        }
+        child.wait()
+    });
+    match status {
+        Ok(exit) if exit.success() => Ok(()),
+        Ok(exit) => Err(KeyringError::Store(format!(
+            "keyring tool exited with {exit}"
+        ))),
+        Err(e) => Err(KeyringError::Store(e.to_string())),
+    }
+}
@@ -0,0 +1,17 @@
+//! Where gx finds a GitHub token when `GITHUB_TOKEN` is not enough.
+//!
+//! Each source is a [`TokenProvider`]; a [`Chain`] asks them in order and stops at the
+//! first token. The standard chain is `GITHUB_TOKEN` → `GH_TOKEN` → the `gh` CLI → the
+//! OS keyring, so most users who already ran `gh auth login` need no setup.
+
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// OAuth device flow login (`gx auth login`).
+pub mod device;
+/// Token lookup and storage in the OS keyring through the platform's command-line tool.
+mod keyring;
+/// The provider trait, the chain, and the environment and `gh` providers.
+mod provider;
+
+pub use keyring::{Keyring, KeyringError, store};
+pub use provider::{Chain, EnvVar, GhCli, TokenProvider};
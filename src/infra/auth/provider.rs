@@ -0,0 +1,182 @@
+use super::Keyring;
+use crate::config::GitHubToken;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// A source of GitHub tokens.
+pub trait TokenProvider {
+    /// Where the token comes from, as shown to the user, e.g. `GITHUB_TOKEN`.
+    fn name(&self) -> String;
+
+    /// The token for `host` (`github.com` or a GitHub Enterprise Server host), if this
+    /// source has one.
+    fn token(&self, host: &str) -> Option<GitHubToken>;
+}
+
+/// Token providers asked in order; the first token wins.
+pub struct Chain {
+    /// The providers, highest priority first.
+    providers: Vec<Box<dyn TokenProvider>>,
+}
+
+impl Chain {
+    #[must_use]
+    pub fn new(providers: Vec<Box<dyn TokenProvider>>) -> Self {
+        Self { providers }
+    }
+
+    /// `GITHUB_TOKEN`, then `GH_TOKEN`, then the `gh` CLI, then the OS keyring.
+    #[must_use]
+    pub fn standard() -> Self {
+        Self::new(vec![
+            Box::new(EnvVar("GITHUB_TOKEN")),
+            Box::new(EnvVar("GH_TOKEN")),
+            Box::new(GhCli),
+            Box::new(Keyring),
+        ])
+    }
+
+    /// The first token for `host`, with the name of the provider it came from.
+    #[must_use]
+    pub fn resolve(&self, host: &str) -> Option<(String, GitHubToken)> {
+        self.providers
+            .iter()
+            .find_map(|provider| provider.token(host).map(|token| (provider.name(), token)))
+    }
+}
+
+/// A token read from an environment variable.
+pub struct EnvVar(pub &'static str);
+
+impl TokenProvider for EnvVar {
+    fn name(&self) -> String {
+        self.0.to_owned()
+    }
+
+    fn token(&self, _host: &str) -> Option<GitHubToken> {
+        env::var(self.0)
+            .ok()
+            .filter(|token| !token.is_empty())
+            .map(GitHubToken::from)
+    }
+}
+
+/// The token the GitHub CLI is logged in with: `gh auth token`, or its `hosts.yml` when
+/// `gh` is not on `PATH`.
+pub struct GhCli;
+
+impl TokenProvider for GhCli {
+    fn name(&self) -> String {
+        "gh CLI".to_owned()
+    }
+
+    fn token(&self, host: &str) -> Option<GitHubToken> {
+        let cli_output = Command::new("gh")
+            .args(["auth", "token", "--hostname", host])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success());
+        cli_output
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|stdout| stdout.trim().to_owned())
+            .filter(|token| !token.is_empty())
+            .or_else(|| {
+                let content = std::fs::read_to_string(hosts_file()?).ok()?;
+                hosts_token(&content, host)
+            })
+            .map(GitHubToken::from)
+    }
+}
+
+/// One host entry of the GitHub CLI's `hosts.yml`.
+#[derive(Debug, Deserialize)]
+struct GhHost {
+    /// Token stored in plain text; absent when `gh` keeps it in the OS keyring.
+    oauth_token: Option<String>,
+}
+
+/// The token stored for `host` in the content of a `hosts.yml`.
+fn hosts_token(content: &str, host: &str) -> Option<String> {
+    let hosts: BTreeMap<String, GhHost> = serde_saphyr::from_str(content).ok()?;
+    hosts
+        .into_iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(host))
+        .and_then(|(_, entry)| entry.oauth_token)
+        .filter(|token| !token.is_empty())
+}
+
+/// Path of the GitHub CLI's `hosts.yml`, following its own lookup order.
+fn hosts_file() -> Option<PathBuf> {
+    let dir = env::var_os("GH_CONFIG_DIR")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("XDG_CONFIG_HOME").map(|dir| PathBuf::from(dir).join("gh")))
+        .or_else(|| {
+            cfg!(windows)
+                .then(|| env::var_os("AppData"))
+                .flatten()
+                .map(|dir| PathBuf::from(dir).join("GitHub CLI"))
+        })
+        .or_else(|| {
+            env::var_os("HOME").map(|home| PathBuf::from(home).join(".config").join("gh"))
+        })?;
+    Some(dir.join("hosts.yml"))
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "tests use unwrap, indexing, and other patterns freely"
+)]
+mod tests {
+    use super::{Chain, GitHubToken, TokenProvider, hosts_token};
+
+    /// A provider with a fixed token for one host.
+    struct Fixed(&'static str, &'static str);
+
+    impl TokenProvider for Fixed {
+        fn name(&self) -> String {
+            self.0.to_owned()
+        }
+
+        fn token(&self, host: &str) -> Option<GitHubToken> {
+            (host == self.1).then(|| GitHubToken::from(format!("{}-token", self.0)))
+        }
+    }
+
+    #[test]
+    fn chain_returns_the_first_provider_with_a_token() {
+        let chain = Chain::new(vec![
+            Box::new(Fixed("first", "ghes.example.com")),
+            Box::new(Fixed("second", "github.com")),
+            Box::new(Fixed("third", "github.com")),
+        ]);
+
+        let (source, token) = chain.resolve("github.com").unwrap();
+        assert_eq!(source, "second");
+        assert_eq!(token.as_str(), "second-token");
+        assert!(chain.resolve("example.org").is_none());
+    }
+
+    #[test]
+    fn hosts_token_reads_the_matching_host() {
+        let content = "\
+github.com:
+    user: octocat
+    oauth_token: gho_public
+    git_protocol: https
+ghes.example.com:
+    user: octocat
+";
+        assert_eq!(
+            hosts_token(content, "GitHub.com").as_deref(),
+            Some("gho_public")
+        );
+        assert_eq!(hosts_token(content, "ghes.example.com"), None);
+        assert_eq!(hosts_token("not: [valid", "github.com"), None);
+    }
+}
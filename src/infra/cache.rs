@@ -0,0 +1,134 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The user's gx cache: `$XDG_CACHE_HOME/gx`, or `~/.cache/gx`.
+///
+/// Shared by every repository on the machine; each kind of entry lives in its own
+/// subdirectory, such as `http` for API responses.
+#[must_use]
+pub fn user_dir() -> Option<PathBuf> {
+    let root = env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    Some(root.join("gx"))
+}
+
+/// How many entries a cache directory holds, and their size on disk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    /// Number of files.
+    pub entries: usize,
+    /// Total size of the files in bytes.
+    pub bytes: u64,
+}
+
+impl Usage {
+    /// The usage of `self` and `other` together.
+    #[must_use]
+    pub const fn add(self, other: Self) -> Self {
+        Self {
+            entries: self.entries.saturating_add(other.entries),
+            bytes: self.bytes.saturating_add(other.bytes),
+        }
+    }
+}
+
+/// The usage of each subdirectory of `dir`, by name; empty when `dir` does not exist.
+#[must_use]
+pub fn sections(dir: &Path) -> Vec<(String, Usage)> {
+    let Ok(children) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut sections: Vec<(String, Usage)> = children
+        .filter_map(Result::ok)
+        .filter(|child| child.path().is_dir())
+        .map(|child| {
+            let name = child.file_name().to_string_lossy().into_owned();
+            (name, usage(&child.path()))
+        })
+        .collect();
+    sections.sort_by(|a, b| a.0.cmp(&b.0));
+    sections
+}
+
+/// The files under `dir`, counted recursively.
+#[must_use]
+pub fn usage(dir: &Path) -> Usage {
+    let Ok(children) = fs::read_dir(dir) else {
+        return Usage::default();
+    };
+    children
+        .filter_map(Result::ok)
+        .map(|child| match child.metadata() {
+            Ok(meta) if meta.is_dir() => usage(&child.path()),
+            Ok(meta) => Usage {
+                entries: 1,
+                bytes: meta.len(),
+            },
+            Err(_) => Usage::default(),
+        })
+        .fold(Usage::default(), Usage::add)
+}
+
+/// Remove `dir` and everything in it, returning what it held.
+///
+/// # Errors
+///
+/// Returns [`io::Error`] if the directory exists but cannot be removed.
+pub fn clean(dir: &Path) -> io::Result<Usage> {
+    let removed = usage(dir);
+    match fs::remove_dir_all(dir) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        Ok(()) | Err(_) => Ok(removed),
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{Usage, clean, sections};
+    use std::fs;
+
+    #[test]
+    fn sections_count_files_per_subdirectory_and_clean_removes_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("gx");
+        fs::create_dir_all(root.join("http")).unwrap();
+        fs::create_dir_all(root.join("resolutions")).unwrap();
+        fs::write(root.join("http").join("a.json"), "1234").unwrap();
+        fs::write(root.join("http").join("b.json"), "56").unwrap();
+        fs::write(root.join("resolutions").join("c.json"), "7").unwrap();
+
+        assert_eq!(
+            sections(&root),
+            vec![
+                (
+                    "http".to_owned(),
+                    Usage {
+                        entries: 2,
+                        bytes: 6
+                    }
+                ),
+                (
+                    "resolutions".to_owned(),
+                    Usage {
+                        entries: 1,
+                        bytes: 1
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            clean(&root).unwrap(),
+            Usage {
+                entries: 3,
+                bytes: 7
+            }
+        );
+        assert!(!root.exists());
+        assert_eq!(clean(&root).unwrap(), Usage::default());
+    }
+}
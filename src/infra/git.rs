@@ -0,0 +1,322 @@
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error;
+
+/// Errors that can occur when running git in the repository.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to run git {command}")]
+    Spawn {
+        command: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("git {command} failed: {stderr}")]
+    Failed { command: String, stderr: String },
+
+    #[error("remote \"{remote}\" is not a GitHub repository: {url}")]
+    NotGithubRemote { remote: String, url: String },
+}
+
+/// Which changes in git a command is limited to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Changes {
+    /// The files staged in the index.
+    Staged,
+    /// The files that differ between the work tree and a ref, such as `origin/main`.
+    Since(String),
+}
+
+/// Runs the `git` CLI inside a repository's work tree.
+pub struct Git {
+    /// The repository work tree root.
+    root: PathBuf,
+}
+
+impl Git {
+    #[must_use]
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+        }
+    }
+
+    /// The name of the currently checked-out branch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Spawn`] or [`Error::Failed`] if git cannot report the branch.
+    pub fn current_branch(&self) -> Result<String, Error> {
+        self.run(&["rev-parse", "--abbrev-ref", "HEAD"])
+    }
+
+    /// The SHA of the commit checked out.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Failed`] if the root is not in a git repository or has no commit.
+    pub fn head(&self) -> Result<String, Error> {
+        self.run(&["rev-parse", "HEAD"])
+    }
+
+    /// Create `branch` from the current commit and check it out.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Failed`] if the branch already exists or cannot be checked out.
+    pub fn checkout_new_branch(&self, branch: &str) -> Result<(), Error> {
+        self.run(&["checkout", "-b", branch]).map(drop)
+    }
+
+    /// Stage exactly the given paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Failed`] if a path cannot be staged.
+    pub fn add(&self, paths: &[PathBuf]) -> Result<(), Error> {
+        let mut args: Vec<&OsStr> = vec!["add".as_ref(), "--".as_ref()];
+        args.extend(paths.iter().map(|path| path.as_os_str()));
+        self.run(&args).map(drop)
+    }
+
+    /// Commit the staged changes with `message`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Failed`] if nothing is staged or the commit is rejected by a hook.
+    pub fn commit(&self, message: &str) -> Result<(), Error> {
+        self.run(&["commit", "--quiet", "-m", message]).map(drop)
+    }
+
+    /// Stage `paths` and commit them with `message`, unless that leaves nothing to commit,
+    /// as when the files were rewritten with the content they had. Returns whether a
+    /// commit was made.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Failed`] if a path cannot be staged or the commit is rejected.
+    pub fn commit_paths(&self, paths: &[PathBuf], message: &str) -> Result<bool, Error> {
+        if paths.is_empty() {
+            return Ok(false);
+        }
+        self.add(paths)?;
+        if !self.has_staged_changes()? {
+            return Ok(false);
+        }
+        self.commit(message).map(|()| true)
+    }
+
+    /// Whether the index holds changes to commit, as `git diff --cached --quiet` tells.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Spawn`] or [`Error::Failed`] if git cannot compare the index.
+    pub fn has_staged_changes(&self) -> Result<bool, Error> {
+        let command = "diff --cached --quiet".to_owned();
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.root)
+            .args(["diff", "--cached", "--quiet"])
+            .output()
+            .map_err(|source| Error::Spawn {
+                command: command.clone(),
+                source,
+            })?;
+        match output.status.code() {
+            Some(0) => Ok(false),
+            Some(1) => Ok(true),
+            _ => Err(Error::Failed {
+                command,
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            }),
+        }
+    }
+
+    /// Push `branch` to `remote` and set it as the upstream.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Failed`] if the push is rejected.
+    pub fn push(&self, remote: &str, branch: &str) -> Result<(), Error> {
+        self.run(&["push", "--quiet", "--set-upstream", remote, branch])
+            .map(drop)
+    }
+
+    /// The `owner/repo` of a remote hosted on GitHub.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Failed`] if the remote does not exist.
+    /// Returns [`Error::NotGithubRemote`] if its URL does not point at github.com.
+    pub fn github_repository(&self, remote: &str) -> Result<String, Error> {
+        let url = self.run(&["remote", "get-url", remote])?;
+        github_repository(&url).ok_or_else(|| Error::NotGithubRemote {
+            remote: remote.to_owned(),
+            url,
+        })
+    }
+
+    /// The directory git runs hooks from, which follows `core.hooksPath` and, in a linked
+    /// worktree, the main repository.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Failed`] if the root is not in a git repository.
+    pub fn hooks_dir(&self) -> Result<PathBuf, Error> {
+        let dir = self.run(&["rev-parse", "--git-path", "hooks"])?;
+        Ok(self.root.join(dir))
+    }
+
+    /// The content of `object`, such as `HEAD:./.github/gx.lock`, as `git show` prints it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Failed`] if the object does not exist or the root is not in a git
+    /// repository.
+    pub fn show(&self, object: &str) -> Result<String, Error> {
+        self.run(&["show", object])
+    }
+
+    /// The existing files under the work tree root with `changes`, as paths joined to the
+    /// root. Deleted files are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Failed`] if the ref does not exist or the root is not in a git
+    /// repository.
+    pub fn changed_files(&self, changes: &Changes) -> Result<Vec<PathBuf>, Error> {
+        let mut args = vec!["diff", "--name-only", "-z", "--relative", "--diff-filter=d"];
+        match changes {
+            Changes::Staged => args.push("--cached"),
+            Changes::Since(reference) => args.extend([reference.as_str(), "--"]),
+        }
+        let output = self.run(&args)?;
+        Ok(output
+            .split('\0')
+            .filter(|name| !name.is_empty())
+            .map(|name| self.root.join(name))
+            .collect())
+    }
+
+    /// Run git with `args` and return its trimmed stdout.
+    fn run<S: AsRef<OsStr>>(&self, args: &[S]) -> Result<String, Error> {
+        let command = args
+            .iter()
+            .map(|arg| arg.as_ref().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.root)
+            .args(args)
+            .output()
+            .map_err(|source| Error::Spawn {
+                command: command.clone(),
+                source,
+            })?;
+        if !output.status.success() {
+            return Err(Error::Failed {
+                command,
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    }
+}
+
+/// Extract `owner/repo` from an SSH (`git@github.com:owner/repo.git`) or HTTPS
+/// (`https://github.com/owner/repo`) remote URL.
+fn github_repository(url: &str) -> Option<String> {
+    let path = url
+        .strip_prefix("git@github.com:")
+        .or_else(|| url.strip_prefix("ssh://git@github.com/"))
+        .or_else(|| url.strip_prefix("https://github.com/"))?;
+    let repo = path.trim_end_matches('/').trim_end_matches(".git");
+    let (owner, name) = repo.split_once('/')?;
+    (!owner.is_empty() && !name.is_empty() && !name.contains('/')).then(|| repo.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Git, github_repository};
+    use std::fs;
+    use std::process::Command;
+
+    #[test]
+    #[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+    fn has_staged_changes_only_when_the_index_differs_from_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(dir.path())
+                .args(["-c", "user.name=gx", "-c", "user.email=gx@example.com"])
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q"]);
+        fs::write(dir.path().join("ci.yml"), "on: push\n").unwrap();
+        git(&["add", "ci.yml"]);
+        git(&["commit", "-q", "-m", "init"]);
+        let repo = Git::new(dir.path());
+
+        repo.add(&[dir.path().join("ci.yml")]).unwrap();
+        assert!(!repo.has_staged_changes().unwrap());
+        fs::write(dir.path().join("ci.yml"), "on: pull_request\n").unwrap();
+        repo.add(&[dir.path().join("ci.yml")]).unwrap();
+        assert!(repo.has_staged_changes().unwrap());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    #[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+    fn add_stages_paths_that_are_not_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt as _;
+
+        let dir = tempfile::tempdir().unwrap();
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir.path())
+            .args(["init", "-q"])
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let path = dir.path().join(OsStr::from_bytes(b"ci-\xff.yml"));
+        fs::write(&path, "on: push\n").unwrap();
+        let repo = Git::new(dir.path());
+
+        repo.add(&[path]).unwrap();
+
+        assert!(repo.has_staged_changes().unwrap());
+    }
+
+    #[test]
+    fn github_repository_from_ssh_and_https_urls() {
+        assert_eq!(
+            github_repository("git@github.com:gmeligio/gx.git").as_deref(),
+            Some("gmeligio/gx")
+        );
+        assert_eq!(
+            github_repository("https://github.com/gmeligio/gx").as_deref(),
+            Some("gmeligio/gx")
+        );
+        assert_eq!(
+            github_repository("ssh://git@github.com/gmeligio/gx.git").as_deref(),
+            Some("gmeligio/gx")
+        );
+    }
+
+    #[test]
+    fn github_repository_rejects_other_hosts() {
+        assert_eq!(
+            github_repository("https://gitlab.com/gmeligio/gx.git"),
+            None
+        );
+        assert_eq!(github_repository("https://github.com/gmeligio"), None);
+    }
+}
@@ -0,0 +1,161 @@
+use super::registry::{Error, Registry};
+use super::responses::{
+    AttestationsResponse, CompareResponse, GitCommitResponse, GitRef, GitTagResponse,
+};
+use crate::domain::action::identity::{CommitSha, Repository, Version};
+use serde::de::DeserializeOwned;
+
+/// What vouches for a locked commit as the commit of its release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attestation {
+    /// An attestation names the commit.
+    Attested,
+    /// The release is immutable, yet no attestation names the commit: the commit is not
+    /// the one GitHub attested for the release.
+    Mismatch,
+    /// Neither an attestation nor an immutable release vouches for the commit.
+    Missing,
+}
+
+#[expect(
+    clippy::multiple_inherent_impl,
+    reason = "integrity checks are in a separate module for clarity"
+)]
+impl Registry {
+    /// Whether `commit` is the commit `reference` points to in `repo` or one in its
+    /// history, or `None` when the API finds either of them missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the API rejects it, or its response cannot
+    /// be parsed.
+    pub fn is_ancestor(
+        &self,
+        repo: &Repository,
+        commit: &CommitSha,
+        reference: &str,
+    ) -> Result<Option<bool>, Error> {
+        let url = format!(
+            "{}/repos/{repo}/compare/{commit}...{reference}?per_page=1",
+            self.api_url
+        );
+        let comparison: Option<CompareResponse> = self.get_json("compare", &url)?;
+        Ok(comparison.map(|found| matches!(found.status.as_str(), "identical" | "ahead")))
+    }
+
+    /// Whether the commit `sha` still exists in `repo`; history rewrites drop commits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the API rejects it, or its response cannot
+    /// be parsed.
+    pub fn has_commit(&self, repo: &Repository, sha: &CommitSha) -> Result<bool, Error> {
+        let url = format!("{}/repos/{repo}/git/commits/{sha}", self.api_url);
+        let commit: Option<GitCommitResponse> = self.get_json("commit", &url)?;
+        Ok(commit.is_some())
+    }
+
+    /// Whether the tag `tag` still exists in `repo`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the API rejects it, or its response cannot
+    /// be parsed.
+    pub fn has_tag(&self, repo: &Repository, tag: &Version) -> Result<bool, Error> {
+        let url = format!("{}/repos/{repo}/git/ref/tags/{tag}", self.api_url);
+        let git_ref: Option<GitRef> = self.get_json("ref", &url)?;
+        Ok(git_ref.is_some())
+    }
+
+    /// What vouches for `sha` as the commit of the release `tag` in `repo`: an attestation
+    /// whose subject is the commit, such as the one GitHub publishes for an immutable
+    /// release, or nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails, the API rejects it, or its response cannot
+    /// be parsed.
+    pub fn attestation(
+        &self,
+        repo: &Repository,
+        tag: &Version,
+        sha: &CommitSha,
+    ) -> Result<Attestation, Error> {
+        let url = format!("{}/repos/{repo}/attestations/sha1:{sha}", self.api_url);
+        let attestations: Option<AttestationsResponse> = self.get_json("attestations", &url)?;
+        if attestations.is_some_and(|found| !found.attestations.is_empty()) {
+            return Ok(Attestation::Attested);
+        }
+        match self.fetch_release(repo.as_str(), tag.as_str()) {
+            Ok(release) if release.immutable => Ok(Attestation::Mismatch),
+            Ok(_) | Err(Error::NotFound { .. }) => Ok(Attestation::Missing),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Whether GitHub verified a signature on commit `sha` of `repo`, or on the annotated
+    /// tag `tag` pointing to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails, the API rejects it, or its response cannot
+    /// be parsed.
+    pub fn is_signed(
+        &self,
+        repo: &Repository,
+        tag: Option<&Version>,
+        sha: &CommitSha,
+    ) -> Result<bool, Error> {
+        let commit_url = format!("{}/repos/{repo}/git/commits/{sha}", self.api_url);
+        let commit: Option<GitCommitResponse> = self.get_json("commit", &commit_url)?;
+        if commit
+            .and_then(|found| found.verification)
+            .is_some_and(|check| check.verified)
+        {
+            return Ok(true);
+        }
+        let Some(version) = tag else {
+            return Ok(false);
+        };
+        let ref_url = format!("{}/repos/{repo}/git/ref/tags/{version}", self.api_url);
+        let git_ref: Option<GitRef> = self.get_json("ref", &ref_url)?;
+        let Some(object) = git_ref
+            .map(|found| found.object)
+            .filter(|object| object.object_type == "tag")
+        else {
+            return Ok(false);
+        };
+        let tag_url = format!("{}/repos/{repo}/git/tags/{}", self.api_url, object.sha);
+        let annotated: Option<GitTagResponse> = self.get_json("tag", &tag_url)?;
+        Ok(annotated.is_some_and(|found| {
+            found.object.sha == sha.as_str()
+                && found.verification.is_some_and(|check| check.verified)
+        }))
+    }
+
+    /// The JSON response of `url`, or `None` when the API answers 404 Not Found.
+    pub(super) fn get_json<T: DeserializeOwned>(
+        &self,
+        operation: &'static str,
+        url: &str,
+    ) -> Result<Option<T>, Error> {
+        let response = self.send_get(url).map_err(|source| Error::Request {
+            operation,
+            url: url.to_owned(),
+            source,
+        })?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(Self::check_status(&response, url));
+        }
+        response
+            .json()
+            .map(Some)
+            .map_err(|source| Error::ParseResponse {
+                url: url.to_owned(),
+                source,
+            })
+    }
+}
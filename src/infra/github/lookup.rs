@@ -0,0 +1,370 @@
+use super::registry::{Error, Registry};
+use super::responses::{Advisory, ContentResponse, License, LicenseResponse, RepositoryResponse};
+use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
+use crate::domain::action::resolved::{Commit, Provenance, Source};
+use crate::domain::action::spec::Spec as ActionSpec;
+use crate::domain::action::specifier::Specifier;
+use crate::domain::action::uses_ref::RefType;
+use crate::domain::resolution::{Error as ResolutionError, ShaDescription, VersionRegistry};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use time::OffsetDateTime;
+
+#[expect(
+    clippy::multiple_inherent_impl,
+    reason = "API lookups are in a separate file for clarity"
+)]
+impl Registry {
+    /// Fetch the release notes body published for a tag.
+    ///
+    /// Returns `None` when the tag has no release, the release has no body, or the
+    /// request fails — release notes are informational and never block an upgrade.
+    #[must_use]
+    pub fn release_notes(&self, id: &ActionId, tag: &Version) -> Option<String> {
+        if self.is_offline() {
+            return None;
+        }
+        self.fetch_release(id.base_repo().as_str(), tag.as_str())
+            .ok()
+            .and_then(|release| release.body)
+            .filter(|body| !body.trim().is_empty())
+    }
+
+    /// Repositories the API redirected during this run, each with its current name.
+    ///
+    /// GitHub redirects requests for a renamed or transferred repository, so lookups
+    /// still succeed under the old name; the manifest and workflows should move to the
+    /// new one. Repositories whose current name cannot be fetched are left out.
+    #[must_use]
+    pub fn renamed_repositories(&self) -> Vec<(Repository, Repository)> {
+        self.redirected_repositories()
+            .into_iter()
+            .filter_map(|old| {
+                let url = format!("{}/repos/{old}", self.api_url);
+                let response = self
+                    .send_get(&url)
+                    .ok()
+                    .filter(|response| response.status().is_success())?;
+                let repo: RepositoryResponse = response.json().ok()?;
+                (!repo.full_name.eq_ignore_ascii_case(&old))
+                    .then(|| (Repository::from(old), Repository::from(repo.full_name)))
+            })
+            .collect()
+    }
+
+    /// The content of `path` in `repo` (`owner/repo`) at commit `sha`, or `None` when the
+    /// file does not exist there.
+    ///
+    /// Content at a commit SHA never changes: each file is fetched once per run and,
+    /// with a [`super::transport::Cache`], served from disk by later runs without a request.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Decode`] if the file is too large to be inlined by the API or
+    /// its content is not valid base64 or UTF-8.
+    /// Returns an error if the request fails or the API rejects it.
+    pub fn get_file(
+        &self,
+        repo: &Repository,
+        sha: &CommitSha,
+        path: &str,
+    ) -> Result<Option<String>, Error> {
+        self.memo().file(repo.as_str(), sha.as_str(), path, || {
+            let url = format!(
+                "{}/repos/{repo}/contents/{}?ref={sha}",
+                self.api_url,
+                path.trim_start_matches('/')
+            );
+            let response = self
+                .send_immutable_get(&url)
+                .map_err(|source| Error::Request {
+                    operation: "file",
+                    url: url.clone(),
+                    source,
+                })?;
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+            if !response.status().is_success() {
+                return Err(Self::check_status(&response, &url));
+            }
+            let file: ContentResponse = response.json().map_err(|source| Error::ParseResponse {
+                url: url.clone(),
+                source,
+            })?;
+            let encoded: String = file.content.split_whitespace().collect();
+            (file.encoding == "base64")
+                .then(|| STANDARD.decode(encoded).ok())
+                .flatten()
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .map(Some)
+                .ok_or(Error::Decode { url })
+        })
+    }
+
+    /// The advisories of the GitHub Advisory Database for the `actions` ecosystem that
+    /// name `repo`, reviewed ones and reports of malware alike.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails, the API rejects it, or its response cannot
+    /// be parsed.
+    pub fn advisories(&self, repo: &Repository) -> Result<Vec<Advisory>, Error> {
+        let mut advisories = Vec::new();
+        for kind in ["reviewed", "malware"] {
+            let url = format!(
+                "{}/advisories?ecosystem=actions&type={kind}&affects={repo}&per_page=100",
+                self.api_url
+            );
+            let response = self.send_get(&url).map_err(|source| Error::Request {
+                operation: "advisories",
+                url: url.clone(),
+                source,
+            })?;
+            if !response.status().is_success() {
+                return Err(Self::check_status(&response, &url));
+            }
+            let page: Vec<Advisory> = response
+                .json()
+                .map_err(|source| Error::ParseResponse { url, source })?;
+            advisories.extend(page);
+        }
+        Ok(advisories)
+    }
+
+    /// The license GitHub detected in `repo`, or `None` when it has no license file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the API rejects it, or its response cannot
+    /// be parsed.
+    pub fn license(&self, repo: &Repository) -> Result<Option<License>, Error> {
+        let url = format!("{}/repos/{repo}/license", self.api_url);
+        let response: Option<LicenseResponse> = self.get_json("license", &url)?;
+        Ok(response.and_then(|found| found.license))
+    }
+
+    /// Run `lookup` against the mirror configured for `id`, then against this registry
+    /// if there is no mirror or the mirror fails; when both fail, the mirror's error is
+    /// kept.
+    fn mirrored<T, F: Fn(&Self) -> Result<T, ResolutionError>>(
+        &self,
+        id: &ActionId,
+        lookup: F,
+    ) -> Result<T, ResolutionError> {
+        match self.mirror_for(id) {
+            Some(mirror) => lookup(mirror).or_else(|e| lookup(self).map_err(|_| e)),
+            None => lookup(self),
+        }
+    }
+
+    /// Look up the commit a version points to through the API.
+    pub(super) fn api_lookup_sha(
+        &self,
+        id: &ActionId,
+        version: &Version,
+    ) -> Result<Commit, ResolutionError> {
+        let base_repo = id.base_repo();
+        self.memo()
+            .commit(base_repo.as_str(), version.as_str(), || {
+                self.api_resolve_commit(id, version)
+            })
+    }
+
+    /// Resolve the commit a version points to, and its date, through the API.
+    fn api_resolve_commit(
+        &self,
+        id: &ActionId,
+        version: &Version,
+    ) -> Result<Commit, ResolutionError> {
+        let (sha, ref_type) =
+            self.resolve_ref(id.as_str(), version.as_str())
+                .map_err(|e| match e {
+                    Error::RateLimited { .. } => ResolutionError::RateLimited,
+                    Error::Unauthorized { .. } => ResolutionError::AuthRequired,
+                    Error::ClientInit(_)
+                    | Error::Runtime(_)
+                    | Error::CaBundle { .. }
+                    | Error::Request { .. }
+                    | Error::NotFound { .. }
+                    | Error::ApiError { .. }
+                    | Error::ParseResponse { .. }
+                    | Error::Decode { .. } => ResolutionError::ResolveFailed {
+                        spec: ActionSpec::new(id.clone(), Specifier::from_v1(version.as_str())),
+                        reason: e.to_string(),
+                    },
+                })?;
+
+        let base_repo = id.base_repo();
+        let base_repo_str = base_repo.as_str();
+
+        // Fetch date with priority: release > annotated tag > commit
+        let date = if ref_type == Some(RefType::Tag) {
+            // For tags, try release first, then tag object, then commit
+            self.fetch_release(base_repo_str, version.as_str())
+                .ok()
+                .and_then(|release| release.published_at)
+                .or_else(|| self.fetch_tag_date(base_repo_str, &sha).ok().flatten())
+                .or_else(|| self.fetch_commit_date(base_repo_str, &sha).ok().flatten())
+                .unwrap_or_default()
+        } else if ref_type == Some(RefType::Release) {
+            // For releases, try release first, then fall back to commit
+            self.fetch_release(base_repo_str, version.as_str())
+                .ok()
+                .and_then(|release| release.published_at)
+                .or_else(|| self.fetch_commit_date(base_repo_str, &sha).ok().flatten())
+                .unwrap_or_default()
+        } else {
+            // For branches and commits, just get the commit date
+            self.fetch_commit_date(base_repo_str, &sha)
+                .ok()
+                .flatten()
+                .unwrap_or_default()
+        };
+
+        Ok(Commit {
+            sha: CommitSha::from(sha),
+            repository: base_repo,
+            ref_type,
+            date: CommitDate::from(date),
+            provenance: Provenance::at(Source::Api, OffsetDateTime::now_utc().unix_timestamp()),
+        })
+    }
+
+    /// Get all tags that point to a SHA through the API.
+    pub(super) fn api_tags_for_sha(
+        &self,
+        id: &ActionId,
+        sha: &CommitSha,
+    ) -> Result<Vec<Version>, ResolutionError> {
+        self.get_tags_for_sha(id.as_str(), sha.as_str())
+            .map(|tags| tags.into_iter().map(Version::from).collect())
+            .map_err(|e| match e {
+                Error::RateLimited { .. } => ResolutionError::RateLimited,
+                Error::Unauthorized { .. } => ResolutionError::AuthRequired,
+                Error::ClientInit(_)
+                | Error::Runtime(_)
+                | Error::CaBundle { .. }
+                | Error::Request { .. }
+                | Error::NotFound { .. }
+                | Error::ApiError { .. }
+                | Error::ParseResponse { .. }
+                | Error::Decode { .. } => ResolutionError::NoTagsForSha {
+                    action: id.clone(),
+                    sha: sha.clone(),
+                },
+            })
+    }
+
+    /// Get all version tags of the action's repository through the API.
+    pub(super) fn api_all_tags(&self, id: &ActionId) -> Result<Vec<Version>, ResolutionError> {
+        self.get_version_tags(id.as_str())
+            .map(|tags| tags.into_iter().map(Version::from).collect())
+            .map_err(|e| match e {
+                Error::RateLimited { .. } => ResolutionError::RateLimited,
+                Error::Unauthorized { .. } => ResolutionError::AuthRequired,
+                Error::ClientInit(_)
+                | Error::Runtime(_)
+                | Error::CaBundle { .. }
+                | Error::Request { .. }
+                | Error::NotFound { .. }
+                | Error::ApiError { .. }
+                | Error::ParseResponse { .. }
+                | Error::Decode { .. } => ResolutionError::ResolveFailed {
+                    spec: ActionSpec::new(id.clone(), Specifier::Ref(String::new())),
+                    reason: e.to_string(),
+                },
+            })
+    }
+
+    /// Describe a known commit SHA through the API.
+    pub(super) fn api_describe_sha(
+        &self,
+        id: &ActionId,
+        sha: &CommitSha,
+    ) -> Result<ShaDescription, ResolutionError> {
+        let base_repo = id.base_repo();
+
+        // Fetch commit date directly — no tag/branch fallback chain needed since SHA is trusted
+        let date = self
+            .fetch_commit_date(base_repo.as_str(), sha.as_str())
+            .map_err(|e| match e {
+                Error::RateLimited { .. } => ResolutionError::RateLimited,
+                Error::Unauthorized { .. } => ResolutionError::AuthRequired,
+                Error::ClientInit(_)
+                | Error::Runtime(_)
+                | Error::CaBundle { .. }
+                | Error::Request { .. }
+                | Error::NotFound { .. }
+                | Error::ApiError { .. }
+                | Error::ParseResponse { .. }
+                | Error::Decode { .. } => ResolutionError::ResolveFailed {
+                    spec: ActionSpec::new(id.clone(), Specifier::Sha(sha.as_str().to_owned())),
+                    reason: e.to_string(),
+                },
+            })?
+            .unwrap_or_default();
+
+        // Tag lookup is non-fatal: return empty tags on failure
+        let tags = self
+            .get_tags_for_sha(id.as_str(), sha.as_str())
+            .unwrap_or_default()
+            .into_iter()
+            .map(Version::from)
+            .collect();
+
+        Ok(ShaDescription {
+            tags,
+            repository: base_repo,
+            date: CommitDate::from(date),
+            provenance: Provenance::at(Source::Api, OffsetDateTime::now_utc().unix_timestamp()),
+        })
+    }
+}
+
+impl VersionRegistry for Registry {
+    fn lookup_sha(&self, id: &ActionId, version: &Version) -> Result<Commit, ResolutionError> {
+        self.mirrored(id, |registry| {
+            registry.resolve_with(
+                || registry.api_lookup_sha(id, version),
+                |git| git.lookup_sha(id, version),
+            )
+        })
+    }
+
+    fn tags_for_sha(
+        &self,
+        id: &ActionId,
+        sha: &CommitSha,
+    ) -> Result<Vec<Version>, ResolutionError> {
+        self.mirrored(id, |registry| {
+            registry.resolve_with(
+                || registry.api_tags_for_sha(id, sha),
+                |git| git.tags_for_sha(id, sha),
+            )
+        })
+    }
+
+    fn all_tags(&self, id: &ActionId) -> Result<Vec<Version>, ResolutionError> {
+        self.mirrored(id, |registry| {
+            registry.resolve_with(|| registry.api_all_tags(id), |git| git.all_tags(id))
+        })
+    }
+
+    fn concurrency(&self) -> usize {
+        self.concurrency_limit()
+    }
+
+    fn describe_sha(
+        &self,
+        id: &ActionId,
+        sha: &CommitSha,
+    ) -> Result<ShaDescription, ResolutionError> {
+        self.mirrored(id, |registry| {
+            registry.resolve_with(
+                || registry.api_describe_sha(id, sha),
+                |git| git.describe_sha(id, sha),
+            )
+        })
+    }
+}
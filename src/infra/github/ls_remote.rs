@@ -0,0 +1,206 @@
+use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Version};
+use crate::domain::action::resolved::{Commit, Provenance, Source};
+use crate::domain::action::spec::Spec as ActionSpec;
+use crate::domain::action::specifier::Specifier;
+use crate::domain::action::uses_ref::RefType;
+use crate::domain::resolution::{Error as ResolutionError, ShaDescription, VersionRegistry};
+use std::collections::{BTreeMap, HashMap};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use time::OffsetDateTime;
+
+/// Suffix `git ls-remote` appends to the peeled ref of an annotated tag.
+const PEELED_SUFFIX: &str = "^{}";
+
+/// Tags and branches of one repository, each mapped to the commit SHA it points to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Refs {
+    /// Tag name to commit SHA, with annotated tags peeled to their commit.
+    tags: BTreeMap<String, String>,
+    /// Branch name to commit SHA.
+    heads: BTreeMap<String, String>,
+}
+
+/// Resolves actions with `git ls-remote` against the repository's web URL.
+///
+/// Needs no API token: public repositories are read anonymously and private ones through
+/// the user's git credentials. Commit dates are not available, so they stay empty.
+#[derive(Clone)]
+pub struct LsRemote {
+    /// Web root of the host, e.g. `https://github.com`.
+    web_url: String,
+    /// Refs already listed, keyed by `owner/repo`; shared between clones.
+    cache: Arc<Mutex<HashMap<String, Refs>>>,
+}
+
+impl LsRemote {
+    /// Resolve against repositories under `web_url`, e.g. `https://github.com`.
+    #[must_use]
+    pub fn new(web_url: String) -> Self {
+        Self {
+            web_url,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// The refs of `base_repo`, listed once per repository.
+    fn refs(&self, base_repo: &str) -> Result<Refs, String> {
+        if let Some(refs) = self
+            .cache
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(base_repo).cloned())
+        {
+            return Ok(refs);
+        }
+        let url = format!("{}/{base_repo}", self.web_url);
+        let output = Command::new("git")
+            .args(["ls-remote", "--tags", "--heads", &url])
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| format!("failed to run git ls-remote: {e}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "git ls-remote {url} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        let refs = parse(&String::from_utf8_lossy(&output.stdout));
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.insert(base_repo.to_owned(), refs.clone());
+        }
+        Ok(refs)
+    }
+}
+
+/// Parse `git ls-remote` output: one `<sha>\t<ref>` per line.
+fn parse(output: &str) -> Refs {
+    let mut refs = Refs::default();
+    for (sha, name) in output.lines().filter_map(|line| line.split_once('\t')) {
+        if let Some(tag) = name.strip_prefix("refs/tags/") {
+            match tag.strip_suffix(PEELED_SUFFIX) {
+                Some(peeled) => {
+                    refs.tags.insert(peeled.to_owned(), sha.to_owned());
+                }
+                None => {
+                    refs.tags
+                        .entry(tag.to_owned())
+                        .or_insert_with(|| sha.to_owned());
+                }
+            }
+        } else if let Some(branch) = name.strip_prefix("refs/heads/") {
+            refs.heads.insert(branch.to_owned(), sha.to_owned());
+        }
+    }
+    refs
+}
+
+/// Whether `reference` is a full commit SHA.
+fn is_sha(reference: &str) -> bool {
+    reference.len() == 40 && reference.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+impl VersionRegistry for LsRemote {
+    fn lookup_sha(&self, id: &ActionId, version: &Version) -> Result<Commit, ResolutionError> {
+        let base_repo = id.base_repo();
+        let failed = |reason: String| ResolutionError::ResolveFailed {
+            spec: ActionSpec::new(id.clone(), Specifier::from_v1(version.as_str())),
+            reason,
+        };
+        let refs = self.refs(base_repo.as_str()).map_err(failed)?;
+        let (sha, ref_type) = if let Some(sha) = refs.tags.get(version.as_str()) {
+            (sha.clone(), RefType::Tag)
+        } else if let Some(sha) = refs.heads.get(version.as_str()) {
+            (sha.clone(), RefType::Branch)
+        } else if is_sha(version.as_str()) {
+            (version.as_str().to_owned(), RefType::Commit)
+        } else {
+            return Err(failed(format!("no tag or branch named {version}")));
+        };
+        Ok(Commit {
+            sha: CommitSha::from(sha),
+            repository: base_repo,
+            ref_type: Some(ref_type),
+            date: CommitDate::from(String::new()),
+            provenance: Provenance::at(Source::Git, OffsetDateTime::now_utc().unix_timestamp()),
+        })
+    }
+
+    fn tags_for_sha(
+        &self,
+        id: &ActionId,
+        sha: &CommitSha,
+    ) -> Result<Vec<Version>, ResolutionError> {
+        let refs =
+            self.refs(id.base_repo().as_str())
+                .map_err(|_| ResolutionError::NoTagsForSha {
+                    action: id.clone(),
+                    sha: sha.clone(),
+                })?;
+        Ok(refs
+            .tags
+            .into_iter()
+            .filter(|(_, tagged)| tagged == sha.as_str())
+            .map(|(tag, _)| Version::from(tag))
+            .collect())
+    }
+
+    fn all_tags(&self, id: &ActionId) -> Result<Vec<Version>, ResolutionError> {
+        let refs = self.refs(id.base_repo().as_str()).map_err(|reason| {
+            ResolutionError::ResolveFailed {
+                spec: ActionSpec::new(id.clone(), Specifier::Ref(String::new())),
+                reason,
+            }
+        })?;
+        Ok(refs.tags.into_keys().map(Version::from).collect())
+    }
+
+    fn describe_sha(
+        &self,
+        id: &ActionId,
+        sha: &CommitSha,
+    ) -> Result<ShaDescription, ResolutionError> {
+        Ok(ShaDescription {
+            tags: self.tags_for_sha(id, sha).unwrap_or_default(),
+            repository: id.base_repo(),
+            date: CommitDate::from(String::new()),
+            provenance: Provenance::at(Source::Git, OffsetDateTime::now_utc().unix_timestamp()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_sha, parse};
+
+    #[test]
+    fn parse_peels_annotated_tags() {
+        let refs = parse(
+            "1111111111111111111111111111111111111111\trefs/heads/main\n\
+             2222222222222222222222222222222222222222\trefs/tags/v1\n\
+             3333333333333333333333333333333333333333\trefs/tags/v1^{}\n\
+             4444444444444444444444444444444444444444\trefs/tags/v1.0.0\n",
+        );
+        assert_eq!(
+            refs.heads.get("main").map(String::as_str),
+            Some("1111111111111111111111111111111111111111")
+        );
+        assert_eq!(
+            refs.tags.get("v1").map(String::as_str),
+            Some("3333333333333333333333333333333333333333")
+        );
+        assert_eq!(
+            refs.tags.get("v1.0.0").map(String::as_str),
+            Some("4444444444444444444444444444444444444444")
+        );
+        assert_eq!(refs.tags.len(), 2);
+    }
+
+    #[test]
+    fn is_sha_requires_a_full_hex_sha() {
+        assert!(is_sha("8e8c483db84b4bee98b60c0593521ed34d9990e8"));
+        assert!(!is_sha("8e8c483"));
+        assert!(!is_sha("main"));
+    }
+}
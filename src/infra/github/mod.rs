@@ -1,10 +1,26 @@
 #![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
 
-/// GitHub API client, error types, and `VersionRegistry` implementation.
+/// Reads that vouch for locked commits: comparisons with tags, attestations, and
+/// signatures.
+mod integrity;
+/// `VersionRegistry` for `Registry`: API lookups, falling back to `git ls-remote`, and
+/// other reads such as advisories.
+mod lookup;
+/// Version resolution with `git ls-remote`, without the GitHub API.
+mod ls_remote;
+/// GitHub API client, error types, and resolver selection.
 mod registry;
 /// Ref resolution and tag lookup against the GitHub API.
 mod resolve;
 /// GitHub API response deserialization types.
 mod responses;
+/// HTTP plumbing: response caching and memoization, and rate limit handling.
+mod transport;
+/// Writes to the GitHub API: pull requests and the allowed actions policy.
+mod write;
 
+pub use integrity::Attestation;
 pub use registry::{Error, Registry};
+pub use responses::{Advisory, License, Package, Vulnerability};
+pub use transport::{Budget, Http, RESOLUTION_TTL, SendError};
+pub use write::{Policy, PullRequest};
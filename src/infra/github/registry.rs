@@ -1,14 +1,21 @@
-use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Version};
-use crate::domain::action::resolved::Commit;
-use crate::domain::action::spec::Spec as ActionSpec;
-use crate::domain::action::specifier::Specifier;
-use crate::domain::action::uses_ref::RefType;
-use crate::domain::resolution::{Error as ResolutionError, ShaDescription, VersionRegistry};
+use super::ls_remote::LsRemote;
+use super::transport::{
+    Budget, Cache, GITHUB_API_BASE, Memo, RETRIES, Redirects, Resolutions, api_base, backoff,
+    buffer, build_client, build_runtime, delay, is_transient, reset_note, web_base,
+};
+use crate::config::{Resolver, Retry};
+use crate::domain::action::identity::ActionId;
+use crate::domain::resolution::{DEFAULT_CONCURRENCY, Error as ResolutionError};
+use crate::infra::trace::Tracer;
+use reqwest::{Method, header::IF_NONE_MATCH};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use thiserror::Error;
+use tokio::runtime::Runtime;
 
-/// HTTP User-Agent header value sent with all GitHub API requests.
-const USER_AGENT: &str = "gx-cli";
 /// Timeout in seconds for each HTTP request to the GitHub API.
 const REQUEST_TIMEOUT_SECS: u64 = 30;
 
@@ -18,6 +25,16 @@ pub enum Error {
     #[error("failed to create HTTP client")]
     ClientInit(#[source] reqwest::Error),
 
+    #[error("failed to start the HTTP runtime")]
+    Runtime(#[source] std::io::Error),
+
+    #[error("failed to load CA bundle {}", .path.display())]
+    CaBundle {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
     #[error("failed to fetch {operation} from {url}")]
     Request {
         operation: &'static str,
@@ -26,8 +43,8 @@ pub enum Error {
         source: reqwest::Error,
     },
 
-    #[error("GitHub API rate limit exceeded for {url}")]
-    RateLimited { url: String },
+    #[error("GitHub API rate limit exceeded for {url}{}", reset_note(.reset.as_ref()))]
+    RateLimited { url: String, reset: Option<i64> },
 
     #[error("GitHub API unauthorized for {url}")]
     Unauthorized { url: String },
@@ -44,15 +61,53 @@ pub enum Error {
         #[source]
         source: reqwest::Error,
     },
+
+    #[error("failed to decode file content from {url}")]
+    Decode { url: String },
 }
 
 /// GitHub API client for resolving action versions and commit SHAs.
 #[derive(Clone)]
 pub struct Registry {
-    /// The HTTP client used for API requests.
-    pub client: reqwest::blocking::Client,
+    /// The async HTTP client used for API requests; clones share its connection pool.
+    pub client: reqwest::Client,
+    /// The runtime that drives `client` while blocking callers wait; shared between clones.
+    runtime: Arc<Runtime>,
     /// Optional personal access token for authenticated requests.
     pub token: Option<crate::config::GitHubToken>,
+    /// Base URL of the REST API, without a trailing slash.
+    pub api_url: String,
+    /// Tokens for specific owners or API hosts, keyed in lowercase; see [`Registry::token_for`].
+    pub tokens: BTreeMap<String, crate::config::GitHubToken>,
+    /// Set once a lookup fails on the rate limit; shared between clones.
+    rate_limited: Arc<AtomicBool>,
+    /// Whether lookups go through the API, `git ls-remote`, or both.
+    resolver: Resolver,
+    /// The `git ls-remote` resolver for the same host.
+    ls_remote: LsRemote,
+    /// On-disk cache of GET responses; `None` sends every request in full.
+    cache: Option<Cache>,
+    /// The rate limit budget of the latest response; shared between clones.
+    budget: Arc<Mutex<Option<Budget>>>,
+    /// How GET requests that fail on transient errors are retried.
+    retry: Retry,
+    /// How many actions are resolved at once.
+    concurrency: usize,
+    /// Lookups already made this run, shared by actions of one repository.
+    memo: Memo,
+    /// Requests sent so far, retries included; shared between clones.
+    requests: Arc<AtomicUsize>,
+    /// Requests allowed per run; once they are used up, no further lookup starts.
+    max_requests: Option<usize>,
+    /// Repositories whose requests the API redirected.
+    redirects: Redirects,
+    /// Whether network access is forbidden; every lookup fails with
+    /// [`ResolutionError::Offline`].
+    offline: bool,
+    /// Registries of mirror hosts, keyed by lowercase owner or `owner/repo`.
+    mirrors: BTreeMap<String, Registry>,
+    /// Traces each request sent, for `-v` and `--har`.
+    tracer: Tracer,
 }
 
 impl Registry {
@@ -68,191 +123,319 @@ impl Registry {
     /// This method panics if called from within an async runtime. See docs on
     /// [`reqwest::blocking`][crate::blocking] for details.
     pub fn new(token: Option<crate::config::GitHubToken>) -> Result<Self, Error> {
-        let client = reqwest::blocking::Client::builder()
-            .user_agent(USER_AGENT)
-            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
-            .build()
-            .map_err(Error::ClientInit)?;
+        let client = build_client(Duration::from_secs(REQUEST_TIMEOUT_SECS), None)?;
 
-        Ok(Self { client, token })
+        Ok(Self {
+            client,
+            runtime: Arc::new(build_runtime()?),
+            token,
+            api_url: GITHUB_API_BASE.to_owned(),
+            tokens: BTreeMap::new(),
+            rate_limited: Arc::new(AtomicBool::new(false)),
+            resolver: Resolver::default(),
+            ls_remote: LsRemote::new(web_base(GITHUB_API_BASE)),
+            cache: None,
+            budget: Arc::new(Mutex::new(None)),
+            retry: Retry::default(),
+            concurrency: DEFAULT_CONCURRENCY,
+            memo: Memo::default(),
+            requests: Arc::new(AtomicUsize::new(0)),
+            max_requests: None,
+            redirects: Redirects::default(),
+            offline: false,
+            mirrors: BTreeMap::new(),
+            tracer: Tracer::default(),
+        })
     }
 
-    /// Build a GET request, attaching the Authorization header only if a token is set.
-    pub(super) fn authenticated_get(&self, url: &str) -> reqwest::blocking::RequestBuilder {
-        let req = self.client.get(url);
-        match &self.token {
-            Some(token) => req.header("Authorization", format!("Bearer {}", token.as_str())),
-            None => req,
-        }
+    /// Create a Github client for the token and API base URL in `settings`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the HTTP client cannot be created, as [`Registry::new`], or
+    /// the configured CA bundle cannot be read.
+    pub fn from_settings(settings: &crate::config::Settings) -> Result<Self, Error> {
+        Self::with_token(settings, settings.resolve_token())
     }
 
-    /// Classify a non-success HTTP response into the appropriate `Error` variant.
-    pub(super) fn check_status(response: &reqwest::blocking::Response, url: &str) -> Error {
-        let status = response.status();
-        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-            return Error::RateLimited {
-                url: url.to_owned(),
-            };
-        }
-        if status == reqwest::StatusCode::FORBIDDEN {
-            let remaining = response
-                .headers()
-                .get("x-ratelimit-remaining")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|v| v.parse::<u64>().ok())
-                .unwrap_or(1);
-            if remaining == 0 {
-                return Error::RateLimited {
-                    url: url.to_owned(),
-                };
-            }
-            return Error::Unauthorized {
-                url: url.to_owned(),
-            };
+    /// Create a Github client for `settings` that authenticates with `token`.
+    fn with_token(
+        settings: &crate::config::Settings,
+        token: Option<crate::config::GitHubToken>,
+    ) -> Result<Self, Error> {
+        let mut registry = Self::new(token)?;
+        if settings.ca_bundle.is_some() || settings.timeout_secs.is_some() {
+            let timeout = settings.timeout_secs.unwrap_or(REQUEST_TIMEOUT_SECS);
+            registry.client =
+                build_client(Duration::from_secs(timeout), settings.ca_bundle.as_deref())?;
         }
-        if status == reqwest::StatusCode::UNAUTHORIZED {
-            return Error::Unauthorized {
-                url: url.to_owned(),
-            };
+        registry.max_requests = settings.max_requests;
+        registry.requests = Arc::clone(&settings.requests);
+        registry.offline = settings.offline;
+        registry.tracer = settings.tracer.clone();
+        if let Some(url) = &settings.github_api_url {
+            registry.api_url = api_base(url);
+            registry.ls_remote = LsRemote::new(web_base(&registry.api_url));
         }
-        if status == reqwest::StatusCode::NOT_FOUND {
-            return Error::NotFound {
-                url: url.to_owned(),
-            };
+        registry.tokens.clone_from(&settings.github_tokens);
+        registry.resolver = settings.resolver;
+        registry.retry = settings.retry;
+        if let Some(concurrency) = settings.concurrency {
+            registry.concurrency = concurrency.max(1);
         }
-        Error::ApiError {
-            status: status.as_u16(),
-            url: url.to_owned(),
+        registry.cache = Cache::user();
+        registry.memo = Memo::persistent(Resolutions::user(&registry.api_url));
+        for (key, url) in &settings.mirrors {
+            let mirror_settings = settings.mirror(key, url);
+            let mut mirror =
+                Self::with_token(&mirror_settings, mirror_settings.github_token.clone())?;
+            mirror.runtime = Arc::clone(&registry.runtime);
+            registry.mirrors.insert(key.clone(), mirror);
         }
+        Ok(registry)
+    }
+
+    /// The token for a request to `url`: the token of the repository owner, then the
+    /// token of the API host, then the default token.
+    #[must_use]
+    pub fn token_for(&self, url: &str) -> Option<&crate::config::GitHubToken> {
+        let owner = url
+            .strip_prefix(&format!("{}/repos/", self.api_url))
+            .and_then(|path| path.split('/').next())
+            .map(str::to_lowercase);
+        let host = self
+            .api_url
+            .split_once("://")
+            .map_or(self.api_url.as_str(), |(_, rest)| rest)
+            .split('/')
+            .next()
+            .map(str::to_lowercase);
+        owner
+            .and_then(|key| self.tokens.get(&key))
+            .or_else(|| host.and_then(|key| self.tokens.get(&key)))
+            .or(self.token.as_ref())
     }
-}
 
-impl VersionRegistry for Registry {
-    fn lookup_sha(&self, id: &ActionId, version: &Version) -> Result<Commit, ResolutionError> {
-        let (sha, ref_type) =
-            self.resolve_ref(id.as_str(), version.as_str())
-                .map_err(|e| match e {
-                    Error::RateLimited { .. } => ResolutionError::RateLimited,
-                    Error::Unauthorized { .. } => ResolutionError::AuthRequired,
-                    Error::ClientInit(_)
-                    | Error::Request { .. }
-                    | Error::NotFound { .. }
-                    | Error::ApiError { .. }
-                    | Error::ParseResponse { .. } => ResolutionError::ResolveFailed {
-                        spec: ActionSpec::new(id.clone(), Specifier::from_v1(version.as_str())),
-                        reason: e.to_string(),
-                    },
-                })?;
-
-        let base_repo = id.base_repo();
-        let base_repo_str = base_repo.as_str();
-
-        // Fetch date with priority: release > annotated tag > commit
-        let date = if ref_type == Some(RefType::Tag) {
-            // For tags, try release first, then tag object, then commit
-            self.fetch_release_date(base_repo_str, version.as_str())
-                .ok()
-                .flatten()
-                .or_else(|| self.fetch_tag_date(base_repo_str, &sha).ok().flatten())
-                .or_else(|| self.fetch_commit_date(base_repo_str, &sha).ok().flatten())
-                .unwrap_or_default()
-        } else if ref_type == Some(RefType::Release) {
-            // For releases, try release first, then fall back to commit
-            self.fetch_release_date(base_repo_str, version.as_str())
-                .ok()
-                .flatten()
-                .or_else(|| self.fetch_commit_date(base_repo_str, &sha).ok().flatten())
-                .unwrap_or_default()
+    /// Warning to show when a request hit the rate limit, so results may be incomplete.
+    #[must_use]
+    pub fn rate_limit_warning(&self) -> Option<String> {
+        if !self.rate_limited.load(Ordering::Relaxed) {
+            return None;
+        }
+        let resets = self
+            .budget()
+            .map(|budget| format!(" It resets at {}.", budget.resets_at()))
+            .unwrap_or_default();
+        Some(if self.token.is_none() && self.tokens.is_empty() {
+            format!(
+                "GitHub API rate limit reached: unauthenticated requests are limited to 60 per hour, so some actions were not checked. Set GITHUB_TOKEN or run `gx auth login` to raise the limit.{resets}"
+            )
         } else {
-            // For branches and commits, just get the commit date
-            self.fetch_commit_date(base_repo_str, &sha)
-                .ok()
-                .flatten()
-                .unwrap_or_default()
+            format!("GitHub API rate limit reached, so some actions were not checked.{resets}")
+        })
+    }
+
+    /// The rate limit budget reported by the latest API response.
+    #[must_use]
+    pub fn budget(&self) -> Option<Budget> {
+        self.budget.lock().ok().and_then(|budget| *budget)
+    }
+
+    /// Ask the API for the current rate limit budget; checking it is free.
+    #[must_use]
+    pub fn fetch_budget(&self) -> Option<Budget> {
+        let url = (!self.offline).then(|| format!("{}/rate_limit", self.api_url))?;
+        let response = self.send(self.authenticated_get(&url)).ok()?;
+        Budget::from_headers(response.headers())
+    }
+
+    /// Send a GET request to `url`, blocking until its whole response is read.
+    ///
+    /// A token the API rejects (401) may be stale, e.g. a revoked `gh` login; the
+    /// request is retried once without it so public repositories still resolve.
+    /// With a [`Cache`], a cached response is revalidated with its `ETag` and served
+    /// from disk when the API answers `304 Not Modified`.
+    pub(super) fn send_get(&self, url: &str) -> reqwest::Result<reqwest::blocking::Response> {
+        let response = self.runtime.block_on(self.get(url))?;
+        let Some(cache) = &self.cache else {
+            return Ok(response);
         };
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED
+            && let Some(cached) = cache.replay(url)
+        {
+            return Ok(cached);
+        }
+        cache.store(url, response)
+    }
 
-        Ok(Commit {
-            sha: CommitSha::from(sha),
-            repository: base_repo,
-            ref_type,
-            date: CommitDate::from(date),
-        })
+    /// Like [`Registry::send_get`], but a cached response is served without asking the
+    /// API; only for URLs whose content cannot change, such as a file at a commit SHA.
+    pub(super) fn send_immutable_get(
+        &self,
+        url: &str,
+    ) -> reqwest::Result<reqwest::blocking::Response> {
+        match self.cache.as_ref().and_then(|cache| cache.replay(url)) {
+            Some(cached) => Ok(cached),
+            None => self.send_get(url),
+        }
     }
 
-    fn tags_for_sha(
+    /// Send `request` and block until its whole response is read.
+    pub(super) fn send(
         &self,
-        id: &ActionId,
-        sha: &CommitSha,
-    ) -> Result<Vec<Version>, ResolutionError> {
-        self.get_tags_for_sha(id.as_str(), sha.as_str())
-            .map(|tags| tags.into_iter().map(Version::from).collect())
-            .map_err(|e| match e {
-                Error::RateLimited { .. } => ResolutionError::RateLimited,
-                Error::Unauthorized { .. } => ResolutionError::AuthRequired,
-                Error::ClientInit(_)
-                | Error::Request { .. }
-                | Error::NotFound { .. }
-                | Error::ApiError { .. }
-                | Error::ParseResponse { .. } => ResolutionError::NoTagsForSha {
-                    action: id.clone(),
-                    sha: sha.clone(),
-                },
-            })
+        request: reqwest::RequestBuilder,
+    ) -> reqwest::Result<reqwest::blocking::Response> {
+        self.runtime
+            .block_on(async { buffer(self.tracer.send(request).await?).await })
     }
 
-    fn all_tags(&self, id: &ActionId) -> Result<Vec<Version>, ResolutionError> {
-        self.get_version_tags(id.as_str())
-            .map(|tags| tags.into_iter().map(Version::from).collect())
-            .map_err(|e| match e {
-                Error::RateLimited { .. } => ResolutionError::RateLimited,
-                Error::Unauthorized { .. } => ResolutionError::AuthRequired,
-                Error::ClientInit(_)
-                | Error::Request { .. }
-                | Error::NotFound { .. }
-                | Error::ApiError { .. }
-                | Error::ParseResponse { .. } => ResolutionError::ResolveFailed {
-                    spec: ActionSpec::new(id.clone(), Specifier::Ref(String::new())),
-                    reason: e.to_string(),
-                },
+    /// The async core of [`Registry::send_get`], up to the cache.
+    async fn get(&self, url: &str) -> reqwest::Result<reqwest::blocking::Response> {
+        let etag = self.cache.as_ref().and_then(|cache| cache.etag(url));
+        let mut response = self
+            .send_with_backoff(|| {
+                let request = self.authenticated_get(url);
+                match &etag {
+                    Some(tag) => request.header(IF_NONE_MATCH, tag),
+                    None => request,
+                }
             })
+            .await?;
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED && self.token_for(url).is_some() {
+            response = self.send_with_backoff(|| self.client.get(url)).await?;
+        }
+        if response.url().as_str() != url {
+            self.redirects.note(&self.api_url, url);
+        }
+        buffer(response).await
+    }
+
+    /// Repositories whose requests the API redirected so far, as `owner/repo`.
+    pub(super) fn redirected_repositories(&self) -> Vec<String> {
+        self.redirects.repositories()
     }
 
-    fn describe_sha(
+    /// Send the request `build` makes, waiting and sending it again while it is rate
+    /// limited and the limit resets soon enough.
+    async fn send_with_backoff(
         &self,
-        id: &ActionId,
-        sha: &CommitSha,
-    ) -> Result<ShaDescription, ResolutionError> {
-        let base_repo = id.base_repo();
-
-        // Fetch commit date directly — no tag/branch fallback chain needed since SHA is trusted
-        let date = self
-            .fetch_commit_date(base_repo.as_str(), sha.as_str())
-            .map_err(|e| match e {
-                Error::RateLimited { .. } => ResolutionError::RateLimited,
-                Error::Unauthorized { .. } => ResolutionError::AuthRequired,
-                Error::ClientInit(_)
-                | Error::Request { .. }
-                | Error::NotFound { .. }
-                | Error::ApiError { .. }
-                | Error::ParseResponse { .. } => ResolutionError::ResolveFailed {
-                    spec: ActionSpec::new(id.clone(), Specifier::Sha(sha.as_str().to_owned())),
-                    reason: e.to_string(),
-                },
-            })?
-            .unwrap_or_default();
+        build: impl Fn() -> reqwest::RequestBuilder,
+    ) -> reqwest::Result<reqwest::Response> {
+        for _ in 0..RETRIES {
+            let response = self.send_recorded(&build).await?;
+            match backoff(&response) {
+                Some(wait) => tokio::time::sleep(wait).await,
+                None => return Ok(response),
+            }
+        }
+        self.send_recorded(&build).await
+    }
 
-        // Tag lookup is non-fatal: return empty tags on failure
-        let tags = self
-            .get_tags_for_sha(id.as_str(), sha.as_str())
-            .unwrap_or_default()
-            .into_iter()
-            .map(Version::from)
-            .collect();
-
-        Ok(ShaDescription {
-            tags,
-            repository: base_repo,
-            date: CommitDate::from(date),
-        })
+    /// Send the request `build` makes, retrying transient failures as configured in
+    /// [`Retry`], and record the budget the response reports.
+    async fn send_recorded(
+        &self,
+        build: &impl Fn() -> reqwest::RequestBuilder,
+    ) -> reqwest::Result<reqwest::Response> {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let mut result = self.tracer.send(build()).await;
+        for attempt in 0..self.retry.retries {
+            if !is_transient(&result) {
+                break;
+            }
+            tokio::time::sleep(delay(&self.retry, attempt)).await;
+            self.requests.fetch_add(1, Ordering::Relaxed);
+            result = self.tracer.send(build()).await;
+        }
+        let response = result?;
+        if let Some(budget) = Budget::from_headers(response.headers())
+            && let Ok(mut latest) = self.budget.lock()
+        {
+            *latest = Some(budget);
+        }
+        Ok(response)
+    }
+
+    /// Build a GET request, attaching the Authorization header only if a token applies.
+    pub(super) fn authenticated_get(&self, url: &str) -> reqwest::RequestBuilder {
+        self.authorize(self.client.get(url), url)
+    }
+
+    /// Build a `method` request, attaching the Authorization header only if a token applies.
+    pub(super) fn authenticated(&self, method: Method, url: &str) -> reqwest::RequestBuilder {
+        self.authorize(self.client.request(method, url), url)
+    }
+
+    /// Attach the Authorization header for `url`, if a token applies.
+    fn authorize(&self, req: reqwest::RequestBuilder, url: &str) -> reqwest::RequestBuilder {
+        match self.token_for(url) {
+            Some(token) => req.header("Authorization", format!("Bearer {}", token.as_str())),
+            None => req,
+        }
+    }
+
+    /// Whether network access is forbidden.
+    pub(super) const fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Lookups already made this run, shared by actions of one repository.
+    pub(super) const fn memo(&self) -> &Memo {
+        &self.memo
+    }
+
+    /// The registry of the mirror for `id`: the one for its repository, then its owner's.
+    pub(super) fn mirror_for(&self, id: &ActionId) -> Option<&Self> {
+        let repo = id.base_repo().as_str().to_lowercase();
+        let owner = repo.split('/').next().unwrap_or_default();
+        self.mirrors.get(&repo).or_else(|| self.mirrors.get(owner))
+    }
+
+    /// How many actions are resolved at once.
+    pub(super) fn concurrency_limit(&self) -> usize {
+        self.concurrency
+    }
+
+    /// Run a lookup through the configured resolver.
+    ///
+    /// Under [`Resolver::Auto`], a lookup the API cannot serve (rate limited or
+    /// unauthorized) is retried with `git ls-remote`; if that fails too, the API error is
+    /// kept. A lookup that ends on the rate limit is remembered for
+    /// [`Registry::rate_limit_warning`]. Once the requests allowed per run are used up,
+    /// lookups fail with [`ResolutionError::RequestLimit`] without sending anything, and
+    /// offline every lookup fails with [`ResolutionError::Offline`].
+    pub(super) fn resolve_with<T>(
+        &self,
+        api: impl FnOnce() -> Result<T, ResolutionError>,
+        git: impl FnOnce(&LsRemote) -> Result<T, ResolutionError>,
+    ) -> Result<T, ResolutionError> {
+        if self.offline {
+            return Err(ResolutionError::Offline);
+        }
+        if let Some(limit) = self.max_requests
+            && self.requests.load(Ordering::Relaxed) >= limit
+        {
+            return Err(ResolutionError::RequestLimit { limit });
+        }
+        let result = match self.resolver {
+            Resolver::Git => git(&self.ls_remote),
+            Resolver::Api => api(),
+            Resolver::Auto => match api() {
+                Err(e) if e.is_recoverable() => git(&self.ls_remote).map_err(|_| e),
+                other => other,
+            },
+        };
+        if matches!(result, Err(ResolutionError::RateLimited)) {
+            self.rate_limited.store(true, Ordering::Relaxed);
+        }
+        result
     }
 }
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "tests use unwrap, indexing, and other patterns freely"
+)]
+#[path = "tests.rs"]
+mod tests;
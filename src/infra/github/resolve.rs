@@ -4,12 +4,10 @@ use super::responses::{
     CommitDetailResponse, CommitResponse, GitRef, GitRefEntry, GitTagResponse, ReleaseResponse,
     TagObjectResponse,
 };
+use super::transport::{Budget, is_rate_limited};
 use crate::domain::action::identity::CommitSha;
 use crate::domain::action::uses_ref::RefType;
 
-/// Base URL for the GitHub REST API.
-const GITHUB_API_BASE: &str = "https://api.github.com";
-
 #[expect(
     clippy::multiple_inherent_impl,
     reason = "resolution logic is in a separate file for clarity"
@@ -44,13 +42,13 @@ impl Registry {
         let base_repo = owner_repo.split('/').take(2).collect::<Vec<_>>().join("/");
 
         // Try to resolve as a tag first
-        let tag_url = format!("{GITHUB_API_BASE}/repos/{base_repo}/git/ref/tags/{ref_name}");
-        if let Ok(sha) = self.fetch_ref_commit(&tag_url) {
+        let tag_url = format!("{}/repos/{base_repo}/git/ref/tags/{ref_name}", self.api_url);
+        if let Ok(sha) = self.fetch_ref_commit(&base_repo, &tag_url) {
             // Check if this tag has a GitHub Release
             if self
-                .fetch_release_date(&base_repo, ref_name)
+                .fetch_release(&base_repo, ref_name)
                 .ok()
-                .flatten()
+                .and_then(|release| release.published_at)
                 .is_some()
             {
                 return Ok((sha, Some(RefType::Release)));
@@ -59,27 +57,31 @@ impl Registry {
         }
 
         // Try to resolve as a branch
-        let branch_url = format!("{GITHUB_API_BASE}/repos/{base_repo}/git/ref/heads/{ref_name}");
-        if let Ok(sha) = self.fetch_ref_commit(&branch_url) {
+        let branch_url = format!(
+            "{}/repos/{base_repo}/git/ref/heads/{ref_name}",
+            self.api_url
+        );
+        if let Ok(sha) = self.fetch_ref_commit(&base_repo, &branch_url) {
             return Ok((sha, Some(RefType::Branch)));
         }
 
         // Try to resolve as a direct commit
-        let commit_url = format!("{GITHUB_API_BASE}/repos/{base_repo}/commits/{ref_name}");
+        let commit_url = format!("{}/repos/{base_repo}/commits/{ref_name}", self.api_url);
         self.fetch_commit_sha(&commit_url)
             .map(|sha| (sha, Some(RefType::Commit)))
     }
 
-    /// Fetch the commit SHA for a git ref, dereferencing annotated tags if needed.
-    pub(super) fn fetch_ref_commit(&self, url: &str) -> Result<String, GithubError> {
-        let response =
-            self.authenticated_get(url)
-                .send()
-                .map_err(|source| GithubError::Request {
-                    operation: "ref",
-                    url: url.to_owned(),
-                    source,
-                })?;
+    /// Fetch the commit SHA for a git ref of `base_repo`, dereferencing annotated tags.
+    pub(super) fn fetch_ref_commit(
+        &self,
+        base_repo: &str,
+        url: &str,
+    ) -> Result<String, GithubError> {
+        let response = self.send_get(url).map_err(|source| GithubError::Request {
+            operation: "ref",
+            url: url.to_owned(),
+            source,
+        })?;
 
         if !response.status().is_success() {
             return Err(Self::check_status(&response, url));
@@ -93,59 +95,45 @@ impl Registry {
             })?;
 
         // For annotated tags, the object is a tag object, not a commit.
-        // Dereference via the git tags API to get the underlying commit SHA.
         if git_ref.object.object_type == "tag" {
-            let tag_url = format!(
-                "{GITHUB_API_BASE}/repos/{}/git/tags/{}",
-                // Extract owner/repo from the ref URL
-                url.strip_prefix(&format!("{GITHUB_API_BASE}/repos/"))
-                    .and_then(|s| {
-                        let mut split = s.splitn(3, '/');
-                        let owner = split.next()?;
-                        let repo = split.next()?;
-                        Some(format!("{owner}/{repo}"))
-                    })
-                    .unwrap_or_default(),
-                git_ref.object.sha
-            );
-
-            let tag_response =
-                self.authenticated_get(&tag_url)
-                    .send()
-                    .map_err(|source| GithubError::Request {
-                        operation: "tag dereference",
-                        url: tag_url.clone(),
-                        source,
-                    })?;
+            return self.peel_tag(base_repo, &git_ref.object.sha);
+        }
 
-            if !tag_response.status().is_success() {
-                return Err(Self::check_status(&tag_response, &tag_url));
-            }
+        Ok(git_ref.object.sha)
+    }
 
-            let tag_data: GitTagResponse =
-                tag_response
-                    .json()
-                    .map_err(|source| GithubError::ParseResponse {
-                        url: tag_url,
-                        source,
-                    })?;
+    /// The commit SHA an annotated tag object of `base_repo` points to.
+    ///
+    /// Tag objects are immutable, so each one is fetched from `git/tags/{sha}` once per run.
+    pub(super) fn peel_tag(&self, base_repo: &str, tag_sha: &str) -> Result<String, GithubError> {
+        self.memo().peeled(base_repo, tag_sha, || {
+            let url = format!("{}/repos/{base_repo}/git/tags/{tag_sha}", self.api_url);
+
+            let response = self.send_get(&url).map_err(|source| GithubError::Request {
+                operation: "tag dereference",
+                url: url.clone(),
+                source,
+            })?;
 
-            return Ok(tag_data.object.sha);
-        }
+            if !response.status().is_success() {
+                return Err(Self::check_status(&response, &url));
+            }
 
-        Ok(git_ref.object.sha)
+            let tag: GitTagResponse = response
+                .json()
+                .map_err(|source| GithubError::ParseResponse { url, source })?;
+
+            Ok(tag.object.sha)
+        })
     }
 
     /// Fetch the SHA from a commit endpoint URL.
     pub(super) fn fetch_commit_sha(&self, url: &str) -> Result<String, GithubError> {
-        let response =
-            self.authenticated_get(url)
-                .send()
-                .map_err(|source| GithubError::Request {
-                    operation: "commit",
-                    url: url.to_owned(),
-                    source,
-                })?;
+        let response = self.send_get(url).map_err(|source| GithubError::Request {
+            operation: "commit",
+            url: url.to_owned(),
+            source,
+        })?;
 
         if !response.status().is_success() {
             return Err(Self::check_status(&response, url));
@@ -166,8 +154,8 @@ impl Registry {
     ///
     /// Returns tag names without the "refs/tags/" prefix (e.g., `["v5", "v5.0.0"]`)
     /// Handles both lightweight tags (where `object.sha` is the commit SHA directly)
-    /// and annotated tags (where `object.sha` is the tag object SHA, requiring
-    /// dereferencing via `git/tags/{tag_sha}` to find the underlying commit SHA).
+    /// and annotated tags (where `object.sha` is the tag object SHA, peeled through
+    /// `git/tags/{tag_sha}` to the underlying commit SHA, see [`Registry::peel_tag`]).
     ///
     /// # Errors
     ///
@@ -180,71 +168,65 @@ impl Registry {
         // Handle subpath actions (e.g., "github/codeql-action/upload-sarif")
         let base_repo = owner_repo.split('/').take(2).collect::<Vec<_>>().join("/");
 
-        let url = format!("{GITHUB_API_BASE}/repos/{base_repo}/git/refs/tags");
-
-        let response =
-            self.authenticated_get(&url)
-                .send()
-                .map_err(|source| GithubError::Request {
-                    operation: "tags",
-                    url: url.clone(),
-                    source,
-                })?;
-
-        if !response.status().is_success() {
-            return Err(Self::check_status(&response, &url));
-        }
-
-        let refs: Vec<GitRefEntry> =
-            response
-                .json()
-                .map_err(|source| GithubError::ParseResponse {
-                    url: url.clone(),
-                    source,
-                })?;
+        let refs = self
+            .memo()
+            .tag_refs(&base_repo, || self.fetch_tag_refs(&base_repo))?;
 
         // Collect lightweight tag matches directly
         let mut tags = filter_refs_by_sha(&refs, sha);
 
-        // Dereference annotated tags to check if they point to the target commit
+        // Peel annotated tags to check if they point to the target commit. A tag that
+        // cannot be peeled is skipped rather than failing the whole lookup.
         for entry in &refs {
             if entry.object.object_type == "tag"
                 && entry.object.sha != sha
-                && let Some(tag_name) = self.dereference_tag(&base_repo, entry, sha)
+                && self
+                    .peel_tag(&base_repo, &entry.object.sha)
+                    .is_ok_and(|commit| commit == sha)
             {
-                tags.push(tag_name);
+                tags.push(tag_name(entry));
             }
         }
 
         Ok(tags)
     }
 
-    /// Dereference an annotated tag to check if it points to the given commit SHA.
-    /// Returns `Some(tag_name)` if the tag's underlying commit matches, `None` otherwise.
-    pub(super) fn dereference_tag(
-        &self,
-        base_repo: &str,
-        entry: &GitRefEntry,
-        commit_sha: &str,
-    ) -> Option<String> {
-        let tag_url = format!(
-            "{GITHUB_API_BASE}/repos/{base_repo}/git/tags/{}",
-            entry.object.sha
+    /// Fetch every tag ref of `base_repo`, following pagination.
+    fn fetch_tag_refs(&self, base_repo: &str) -> Result<Vec<GitRefEntry>, GithubError> {
+        let mut refs = Vec::new();
+        let mut url = format!(
+            "{}/repos/{base_repo}/git/refs/tags?per_page=100",
+            self.api_url
         );
-        let tag_response = self.authenticated_get(&tag_url).send().ok()?;
 
-        if !tag_response.status().is_success() {
-            return None;
-        }
+        loop {
+            let response = self.send_get(&url).map_err(|source| GithubError::Request {
+                operation: "tags",
+                url: url.clone(),
+                source,
+            })?;
 
-        let tag_data: GitTagResponse = tag_response.json().ok()?;
-        (tag_data.object.sha == commit_sha).then(|| {
-            entry
-                .ref_name
-                .strip_prefix("refs/tags/")
-                .unwrap_or(&entry.ref_name)
-                .to_owned()
-        })
+            if !response.status().is_success() {
+                return Err(Self::check_status(&response, &url));
+            }
+
+            let next_url = parse_next_link(response.headers());
+
+            let page: Vec<GitRefEntry> =
+                response
+                    .json()
+                    .map_err(|source| GithubError::ParseResponse {
+                        url: url.clone(),
+                        source,
+                    })?;
+
+            refs.extend(page);
+
+            match next_url {
+                Some(next) => url = next,
+                None => return Ok(refs),
+            }
+        }
     }
 
     /// Fetch all version-like tags using the matching-refs endpoint.
@@ -257,20 +239,24 @@ impl Registry {
     /// Returns an error if no token is set, the request fails, or the response cannot be parsed.
     pub fn get_version_tags(&self, owner_repo: &str) -> Result<Vec<String>, GithubError> {
         let base_repo = owner_repo.split('/').take(2).collect::<Vec<_>>().join("/");
+        self.memo()
+            .version_tags(&base_repo, || self.fetch_version_tags(&base_repo))
+    }
 
+    /// Fetch all version-like tags of `base_repo`, following pagination.
+    fn fetch_version_tags(&self, base_repo: &str) -> Result<Vec<String>, GithubError> {
         let mut all_refs: Vec<GitRefEntry> = Vec::new();
-        let mut url =
-            format!("{GITHUB_API_BASE}/repos/{base_repo}/git/matching-refs/tags/v?per_page=100");
+        let mut url = format!(
+            "{}/repos/{base_repo}/git/matching-refs/tags/v?per_page=100",
+            self.api_url
+        );
 
         loop {
-            let response =
-                self.authenticated_get(&url)
-                    .send()
-                    .map_err(|source| GithubError::Request {
-                        operation: "version tags",
-                        url: url.clone(),
-                        source,
-                    })?;
+            let response = self.send_get(&url).map_err(|source| GithubError::Request {
+                operation: "version tags",
+                url: url.clone(),
+                source,
+            })?;
 
             if !response.status().is_success() {
                 return Err(Self::check_status(&response, &url));
@@ -294,17 +280,7 @@ impl Registry {
             }
         }
 
-        let tags: Vec<String> = all_refs
-            .into_iter()
-            .map(|r| {
-                r.ref_name
-                    .strip_prefix("refs/tags/")
-                    .unwrap_or(&r.ref_name)
-                    .to_owned()
-            })
-            .collect();
-
-        Ok(tags)
+        Ok(all_refs.iter().map(tag_name).collect())
     }
 
     /// Fetch the commit date from a commit SHA.
@@ -317,16 +293,13 @@ impl Registry {
         base_repo: &str,
         sha: &str,
     ) -> Result<Option<String>, GithubError> {
-        let url = format!("{GITHUB_API_BASE}/repos/{base_repo}/commits/{sha}");
-
-        let response =
-            self.authenticated_get(&url)
-                .send()
-                .map_err(|source| GithubError::Request {
-                    operation: "commit details",
-                    url: url.clone(),
-                    source,
-                })?;
+        let url = format!("{}/repos/{base_repo}/commits/{sha}", self.api_url);
+
+        let response = self.send_get(&url).map_err(|source| GithubError::Request {
+            operation: "commit details",
+            url: url.clone(),
+            source,
+        })?;
 
         if !response.status().is_success() {
             return Err(Self::check_status(&response, &url));
@@ -339,36 +312,31 @@ impl Registry {
         Ok(commit.commit.committer.and_then(|c| c.date))
     }
 
-    /// Fetch the release date from a release tag.
+    /// Fetch the release published for a tag.
     ///
     /// # Errors
     ///
     /// Returns an error if the request fails or the response cannot be parsed.
-    pub(super) fn fetch_release_date(
+    pub(super) fn fetch_release(
         &self,
         base_repo: &str,
         tag: &str,
-    ) -> Result<Option<String>, GithubError> {
-        let url = format!("{GITHUB_API_BASE}/repos/{base_repo}/releases/tags/{tag}");
-
-        let response =
-            self.authenticated_get(&url)
-                .send()
-                .map_err(|source| GithubError::Request {
-                    operation: "release",
-                    url: url.clone(),
-                    source,
-                })?;
+    ) -> Result<ReleaseResponse, GithubError> {
+        let url = format!("{}/repos/{base_repo}/releases/tags/{tag}", self.api_url);
+
+        let response = self.send_get(&url).map_err(|source| GithubError::Request {
+            operation: "release",
+            url: url.clone(),
+            source,
+        })?;
 
         if !response.status().is_success() {
             return Err(Self::check_status(&response, &url));
         }
 
-        let release: ReleaseResponse = response
+        response
             .json()
-            .map_err(|source| GithubError::ParseResponse { url, source })?;
-
-        Ok(release.published_at)
+            .map_err(|source| GithubError::ParseResponse { url, source })
     }
 
     /// Fetch the tag date from an annotated tag object.
@@ -381,16 +349,13 @@ impl Registry {
         base_repo: &str,
         sha: &str,
     ) -> Result<Option<String>, GithubError> {
-        let url = format!("{GITHUB_API_BASE}/repos/{base_repo}/git/tags/{sha}");
-
-        let response =
-            self.authenticated_get(&url)
-                .send()
-                .map_err(|source| GithubError::Request {
-                    operation: "tag",
-                    url: url.clone(),
-                    source,
-                })?;
+        let url = format!("{}/repos/{base_repo}/git/tags/{sha}", self.api_url);
+
+        let response = self.send_get(&url).map_err(|source| GithubError::Request {
+            operation: "tag",
+            url: url.clone(),
+            source,
+        })?;
 
         if !response.status().is_success() {
             return Err(Self::check_status(&response, &url));
@@ -402,6 +367,36 @@ impl Registry {
 
         Ok(tag.tagger.and_then(|t| t.date))
     }
+
+    /// Classify a non-success HTTP response into the appropriate `Error` variant.
+    pub(super) fn check_status(response: &reqwest::blocking::Response, url: &str) -> GithubError {
+        let status = response.status();
+        if is_rate_limited(status, response.headers()) {
+            return GithubError::RateLimited {
+                url: url.to_owned(),
+                reset: Budget::from_headers(response.headers()).map(|budget| budget.reset),
+            };
+        }
+        if status == reqwest::StatusCode::FORBIDDEN {
+            return GithubError::Unauthorized {
+                url: url.to_owned(),
+            };
+        }
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return GithubError::Unauthorized {
+                url: url.to_owned(),
+            };
+        }
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return GithubError::NotFound {
+                url: url.to_owned(),
+            };
+        }
+        GithubError::ApiError {
+            status: status.as_u16(),
+            url: url.to_owned(),
+        }
+    }
 }
 
 /// Parse the `Link` header to find the `rel="next"` URL for pagination.
@@ -427,15 +422,19 @@ pub(super) fn parse_next_link(headers: &reqwest::header::HeaderMap) -> Option<St
 pub(super) fn filter_refs_by_sha(refs: &[GitRefEntry], sha: &str) -> Vec<String> {
     refs.iter()
         .filter(|r| r.object.sha == sha)
-        .map(|r| {
-            r.ref_name
-                .strip_prefix("refs/tags/")
-                .unwrap_or(&r.ref_name)
-                .to_owned()
-        })
+        .map(tag_name)
         .collect()
 }
 
+/// The tag name of a ref entry, without the "refs/tags/" prefix.
+fn tag_name(entry: &GitRefEntry) -> String {
+    entry
+        .ref_name
+        .strip_prefix("refs/tags/")
+        .unwrap_or(&entry.ref_name)
+        .to_owned()
+}
+
 #[cfg(test)]
 #[expect(
     clippy::unwrap_used,
@@ -8,7 +8,7 @@ pub struct GitRef {
 }
 
 /// Git object containing a SHA and type.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct GitObject {
     /// The object's SHA hash.
     pub sha: String,
@@ -18,7 +18,7 @@ pub struct GitObject {
 }
 
 /// Structure for git ref entries returned by the refs API.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct GitRefEntry {
     /// The full ref name (e.g., `"refs/tags/v4"`).
     #[serde(rename = "ref")]
@@ -40,6 +40,24 @@ pub(super) struct CommitResponse {
 pub(super) struct GitTagResponse {
     /// The tag's target object.
     pub object: GitObject,
+    /// The check of the tag's signature, if GitHub reported one.
+    #[serde(default)]
+    pub verification: Option<VerificationResponse>,
+}
+
+/// Response from `GET /repos/{owner}/{repo}/git/commits/{sha}`.
+#[derive(Debug, Deserialize)]
+pub(super) struct GitCommitResponse {
+    /// The check of the commit's signature, if GitHub reported one.
+    #[serde(default)]
+    pub verification: Option<VerificationResponse>,
+}
+
+/// GitHub's check of the GPG, SSH, or S/MIME signature of a commit or tag.
+#[derive(Debug, Deserialize)]
+pub(super) struct VerificationResponse {
+    /// Whether the object is signed by a key GitHub verified.
+    pub verified: bool,
 }
 
 /// Response for a release API call.
@@ -48,6 +66,13 @@ pub(super) struct ReleaseResponse {
     /// When the release was published.
     #[serde(rename = "published_at")]
     pub published_at: Option<String>,
+    /// Markdown release notes, if the release has any.
+    #[serde(default)]
+    pub body: Option<String>,
+    /// Whether the release is immutable: its tag and assets cannot change, and GitHub
+    /// published an attestation for it.
+    #[serde(default)]
+    pub immutable: bool,
 }
 
 /// Response for a commit details API call.
@@ -84,3 +109,95 @@ pub(super) struct TaggerInfo {
     /// RFC 3339 timestamp of the tag.
     pub date: Option<String>,
 }
+
+/// Response from `GET /repos/{owner}/{repo}`.
+#[derive(Debug, Deserialize)]
+pub(super) struct RepositoryResponse {
+    /// The repository's current `owner/repo` name.
+    pub full_name: String,
+}
+
+/// Response from `GET /repos/{owner}/{repo}/contents/{path}` for a file.
+#[derive(Debug, Deserialize)]
+pub(super) struct ContentResponse {
+    /// The file content, base64-encoded with line breaks.
+    pub content: String,
+    /// How `content` is encoded; `"none"` for files too large to be inlined.
+    pub encoding: String,
+}
+
+/// Response from `GET /repos/{owner}/{repo}/compare/{base}...{head}`.
+#[derive(Debug, Deserialize)]
+pub(super) struct CompareResponse {
+    /// How `head` relates to `base`: `identical`, `ahead`, `behind`, or `diverged`.
+    pub status: String,
+}
+
+/// Response from `GET /repos/{owner}/{repo}/attestations/{subject_digest}`.
+#[derive(Debug, Deserialize)]
+pub(super) struct AttestationsResponse {
+    /// The attestations whose subject has the digest, as Sigstore bundles.
+    #[serde(default)]
+    pub attestations: Vec<serde_json::Value>,
+}
+
+/// Response for a pull request creation API call.
+#[derive(Debug, Deserialize)]
+pub(super) struct PullRequestResponse {
+    /// The pull request's web URL.
+    pub html_url: String,
+}
+
+/// An advisory from `GET /advisories` in the GitHub Advisory Database.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Advisory {
+    /// The advisory's GHSA identifier.
+    pub ghsa_id: String,
+    /// The advisory's web URL.
+    pub html_url: String,
+    /// One-line summary.
+    pub summary: String,
+    /// Markdown description, which names the malicious commits of a compromise.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// `low`, `medium`, `high`, `critical`, or `unknown`.
+    pub severity: String,
+    /// The packages and versions it affects.
+    #[serde(default)]
+    pub vulnerabilities: Vec<Vulnerability>,
+}
+
+/// A package an [`Advisory`] affects.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Vulnerability {
+    /// The package, absent when the advisory withdrew it.
+    pub package: Option<Package>,
+    /// Affected versions, such as `>= 1.0.0, < 45.0.8`.
+    pub vulnerable_version_range: Option<String>,
+    /// The first version with the fix, if any.
+    pub first_patched_version: Option<String>,
+}
+
+/// Response for `GET /repos/{repo}/license`.
+#[derive(Debug, Deserialize)]
+pub(super) struct LicenseResponse {
+    /// The license GitHub detected in the repository's license file.
+    pub license: Option<License>,
+}
+
+/// A license GitHub detected in a repository.
+#[derive(Debug, Clone, Deserialize)]
+pub struct License {
+    /// The SPDX identifier, such as `MIT`; `NOASSERTION` when GitHub could not match one.
+    #[serde(default)]
+    pub spdx_id: Option<String>,
+    /// The full name, such as `MIT License`.
+    pub name: String,
+}
+
+/// A package in the GitHub Advisory Database; for actions, the `owner/repo`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Package {
+    /// The package name.
+    pub name: String,
+}
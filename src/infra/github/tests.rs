@@ -0,0 +1,465 @@
+use super::{Cache, Error, LsRemote, Registry, api_base, build_client};
+use crate::config::{GitHubToken, Resolver, Retry, Settings};
+use crate::domain::action::identity::{ActionId, CommitSha, Repository, Version};
+use crate::domain::resolution::{Error as ResolutionError, VersionRegistry as _};
+use std::io::{BufRead as _, BufReader, Write as _};
+use std::net::TcpListener;
+use std::path::Path;
+use std::process::Command;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Serve one canned HTTP response per connection, returning each request's headers.
+fn serve(responses: Vec<&'static str>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        responses
+            .into_iter()
+            .map(|response| {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut head = String::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    head.push_str(&line.to_lowercase());
+                }
+                stream.write_all(response.as_bytes()).unwrap();
+                head
+            })
+            .collect()
+    });
+    (base, handle)
+}
+
+#[test]
+fn send_get_retries_without_a_rejected_token() {
+    let (base, server) = serve(vec![
+        "HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}",
+    ]);
+    let registry = Registry::new(Some(GitHubToken::from("stale".to_owned()))).unwrap();
+
+    let response = registry.send_get(&format!("{base}/repos/a/b")).unwrap();
+
+    assert!(response.status().is_success());
+    let requests = server.join().unwrap();
+    assert!(
+        requests
+            .first()
+            .unwrap()
+            .contains("authorization: bearer stale")
+    );
+    assert!(!requests.last().unwrap().contains("authorization"));
+}
+
+/// A 403 response with no rate limit left.
+const RATE_LIMITED: &str = "HTTP/1.1 403 Forbidden\r\nX-RateLimit-Remaining: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+/// Run git with a throwaway identity in `dir`.
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=gx", "-c", "user.email=gx@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "git {args:?}");
+}
+
+#[test]
+fn rate_limited_lookups_produce_a_warning() {
+    let (base, server) = serve(vec![RATE_LIMITED]);
+    let mut registry = Registry::new(None).unwrap();
+    registry.api_url = base;
+    registry.resolver = Resolver::Api;
+    let shared = registry.clone();
+    assert_eq!(registry.rate_limit_warning(), None);
+
+    let error = shared.all_tags(&ActionId::from("a/b")).unwrap_err();
+    server.join().unwrap();
+
+    assert!(matches!(error, ResolutionError::RateLimited), "{error}");
+    let warning = registry.rate_limit_warning().unwrap();
+    assert!(warning.contains("60 per hour"), "{warning}");
+}
+
+#[test]
+fn send_get_waits_for_retry_after_and_retries() {
+    let (base, server) = serve(vec![
+        "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nX-RateLimit-Limit: 60\r\nX-RateLimit-Remaining: 59\r\nX-RateLimit-Reset: 1800000000\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+    ]);
+    let registry = Registry::new(None).unwrap();
+
+    let response = registry.send_get(&format!("{base}/repos/a/b")).unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(server.join().unwrap().len(), 2);
+    assert_eq!(registry.budget().map(|budget| budget.remaining), Some(59));
+}
+
+#[test]
+fn send_get_retries_server_errors() {
+    let (base, server) = serve(vec![
+        "HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+    ]);
+    let mut registry = Registry::new(None).unwrap();
+    registry.retry = Retry {
+        retries: 1,
+        base_delay_ms: 0,
+        max_delay_ms: 0,
+    };
+
+    let response = registry.send_get(&format!("{base}/repos/a/b")).unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(server.join().unwrap().len(), 2);
+}
+
+#[test]
+fn rate_limit_errors_name_the_reset_time() {
+    let (base, server) = serve(vec![
+        "HTTP/1.1 403 Forbidden\r\nX-RateLimit-Limit: 60\r\nX-RateLimit-Remaining: 0\r\nX-RateLimit-Reset: 1800000000\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+    ]);
+    let registry = Registry::new(None).unwrap();
+    let url = format!("{base}/repos/a/b");
+
+    let response = registry.send_get(&url).unwrap();
+    server.join().unwrap();
+
+    let error = Registry::check_status(&response, &url);
+    assert!(
+        error
+            .to_string()
+            .ends_with("rate limit resets at 08:00 UTC"),
+        "{error}"
+    );
+}
+
+#[test]
+fn auto_resolver_falls_back_to_ls_remote_when_rate_limited() {
+    let hosts = tempfile::tempdir().unwrap();
+    let repo = hosts.path().join("a").join("b");
+    std::fs::create_dir_all(&repo).unwrap();
+    git(&repo, &["init", "--quiet"]);
+    git(&repo, &["commit", "--quiet", "--allow-empty", "-m", "init"]);
+    git(&repo, &["tag", "v1.0.0"]);
+    let (base, server) = serve(vec![RATE_LIMITED]);
+    let mut registry = Registry::new(None).unwrap();
+    registry.api_url = base;
+    registry.ls_remote = LsRemote::new(hosts.path().display().to_string());
+
+    let tags = registry.all_tags(&ActionId::from("a/b")).unwrap();
+    server.join().unwrap();
+
+    assert_eq!(tags, vec![Version::from("v1.0.0")]);
+    assert_eq!(registry.rate_limit_warning(), None);
+}
+
+#[test]
+fn send_get_serves_not_modified_responses_from_the_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let (base, server) = serve(vec![
+        "HTTP/1.1 200 OK\r\nETag: \"abc\"\r\nContent-Length: 8\r\nConnection: close\r\n\r\n[\"v1.0\"]",
+        "HTTP/1.1 304 Not Modified\r\nETag: \"abc\"\r\nConnection: close\r\n\r\n",
+    ]);
+    let mut registry = Registry::new(None).unwrap();
+    registry.cache = Some(Cache::new(dir.path().to_path_buf()));
+    let url = format!("{base}/repos/a/b/tags");
+
+    let first: Vec<String> = registry.send_get(&url).unwrap().json().unwrap();
+    let second = registry.send_get(&url).unwrap();
+
+    assert!(second.status().is_success());
+    assert_eq!(second.json::<Vec<String>>().unwrap(), first);
+    let requests = server.join().unwrap();
+    assert!(!requests.first().unwrap().contains("if-none-match"));
+    assert!(requests.last().unwrap().contains("if-none-match: \"abc\""));
+}
+
+#[test]
+fn files_at_a_sha_are_fetched_once_and_then_served_from_the_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let (base, server) = serve(vec![
+        "HTTP/1.1 200 OK\r\nETag: \"f\"\r\nContent-Length: 56\r\nConnection: close\r\n\r\n{\"content\":\"bmFtZTog\\nQ2hlY2tvdXQK\",\"encoding\":\"base64\"}",
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+    ]);
+    let registry = || {
+        let mut registry = Registry::new(None).unwrap();
+        registry.api_url.clone_from(&base);
+        registry.cache = Some(Cache::new(dir.path().to_path_buf()));
+        registry
+    };
+    let (first, repo) = (registry(), Repository::from("actions/checkout"));
+    let sha = CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+
+    let content = first.get_file(&repo, &sha, "action.yml").unwrap();
+    let again = first.get_file(&repo, &sha, "action.yml").unwrap();
+    let missing = first.get_file(&repo, &sha, "action.yaml").unwrap();
+    let requests = server.join().unwrap();
+    let later_run = registry().get_file(&repo, &sha, "action.yml").unwrap();
+
+    assert_eq!(content.as_deref(), Some("name: Checkout\n"));
+    assert_eq!(again, content);
+    assert_eq!(missing, None);
+    assert_eq!(later_run, content);
+    assert!(
+        requests
+            .first()
+            .unwrap()
+            .starts_with("get /repos/actions/checkout/contents/action.yml?ref=aaaa")
+    );
+    assert_eq!(requests.len(), 2);
+}
+
+#[test]
+fn token_for_prefers_owner_then_host_then_default() {
+    let mut registry = Registry::new(Some(GitHubToken::from("default".to_owned()))).unwrap();
+    registry.api_url = "https://ghes.example.com/api/v3".to_owned();
+    registry.tokens.insert(
+        "ghes.example.com".to_owned(),
+        GitHubToken::from("host".to_owned()),
+    );
+    registry.tokens.insert(
+        "private-org".to_owned(),
+        GitHubToken::from("org".to_owned()),
+    );
+
+    let token = |client: &Registry, url: &str| {
+        client
+            .token_for(url)
+            .map(GitHubToken::as_str)
+            .map(str::to_owned)
+    };
+    assert_eq!(
+        token(
+            &registry,
+            "https://ghes.example.com/api/v3/repos/Private-Org/action/git/refs/tags"
+        ),
+        Some("org".to_owned())
+    );
+    assert_eq!(
+        token(
+            &registry,
+            "https://ghes.example.com/api/v3/repos/actions/checkout/commits/main"
+        ),
+        Some("host".to_owned())
+    );
+
+    registry.tokens.remove("ghes.example.com");
+    assert_eq!(
+        token(
+            &registry,
+            "https://ghes.example.com/api/v3/repos/actions/checkout/commits/main"
+        ),
+        Some("default".to_owned())
+    );
+}
+
+#[test]
+fn api_base_adds_the_ghes_path_to_bare_hosts() {
+    assert_eq!(
+        api_base("https://api.github.com/"),
+        "https://api.github.com"
+    );
+    assert_eq!(
+        api_base("https://ghes.example.com"),
+        "https://ghes.example.com/api/v3"
+    );
+    assert_eq!(
+        api_base("https://ghes.example.com/api/v3/"),
+        "https://ghes.example.com/api/v3"
+    );
+}
+
+#[test]
+fn subpath_actions_of_one_repository_share_its_tags() {
+    let (base, server) = serve(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 63\r\nConnection: close\r\n\r\n[{\"ref\":\"refs/tags/v3\",\"object\":{\"sha\":\"abc\",\"type\":\"commit\"}}]",
+    ]);
+    let mut registry = Registry::new(None).unwrap();
+    registry.api_url = base;
+    registry.resolver = Resolver::Api;
+
+    let init = registry
+        .all_tags(&ActionId::from("github/codeql-action/init"))
+        .unwrap();
+    let analyze = registry
+        .all_tags(&ActionId::from("github/codeql-action/analyze"))
+        .unwrap();
+
+    assert_eq!(server.join().unwrap().len(), 1);
+    assert_eq!(init, vec![Version::from("v3")]);
+    assert_eq!(analyze, init);
+}
+
+#[test]
+fn tags_for_sha_peels_annotated_tags_once() {
+    let (base, server) = serve(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 124\r\nConnection: close\r\n\r\n[{\"ref\":\"refs/tags/v1\",\"object\":{\"sha\":\"c0\",\"type\":\"commit\"}},{\"ref\":\"refs/tags/v1.0.0\",\"object\":{\"sha\":\"t1\",\"type\":\"tag\"}}]",
+        "HTTP/1.1 200 OK\r\nContent-Length: 39\r\nConnection: close\r\n\r\n{\"object\":{\"sha\":\"c0\",\"type\":\"commit\"}}",
+    ]);
+    let mut registry = Registry::new(None).unwrap();
+    registry.api_url = base;
+
+    let first = registry.get_tags_for_sha("a/b", "c0").unwrap();
+    let second = registry.get_tags_for_sha("a/b/sub", "c0").unwrap();
+
+    assert_eq!(server.join().unwrap().len(), 2);
+    assert_eq!(first, vec!["v1".to_owned(), "v1.0.0".to_owned()]);
+    assert_eq!(second, first);
+}
+
+#[test]
+fn build_client_rejects_unreadable_or_empty_ca_bundles() {
+    let dir = tempfile::tempdir().unwrap();
+    let empty = dir.path().join("empty.pem");
+    std::fs::write(&empty, "not a certificate\n").unwrap();
+
+    let timeout = Duration::from_secs(1);
+    let missing = build_client(timeout, Some(&dir.path().join("missing.pem"))).unwrap_err();
+    let invalid = build_client(timeout, Some(&empty)).unwrap_err();
+
+    assert!(matches!(missing, Error::CaBundle { .. }), "{missing}");
+    assert!(matches!(invalid, Error::CaBundle { .. }), "{invalid}");
+    assert!(invalid.to_string().contains("empty.pem"), "{invalid}");
+}
+
+#[test]
+fn lookups_stop_once_the_request_limit_is_used_up() {
+    let (base, server) = serve(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n[]",
+    ]);
+    let mut registry = Registry::new(None).unwrap();
+    registry.api_url = base;
+    registry.resolver = Resolver::Api;
+    registry.max_requests = Some(1);
+
+    let first = registry.all_tags(&ActionId::from("a/b"));
+    let second = registry.all_tags(&ActionId::from("c/d")).unwrap_err();
+
+    assert_eq!(server.join().unwrap().len(), 1);
+    assert_eq!(first.unwrap(), Vec::<Version>::new());
+    assert!(
+        matches!(second, ResolutionError::RequestLimit { limit: 1 }),
+        "{second}"
+    );
+}
+
+#[test]
+fn offline_lookups_fail_without_sending_anything() {
+    let settings = Settings {
+        github_api_url: Some("http://127.0.0.1:9".to_owned()),
+        resolver: Resolver::Git,
+        offline: true,
+        ..Settings::default()
+    };
+    let registry = Registry::from_settings(&settings).unwrap();
+
+    let err = registry
+        .lookup_sha(&ActionId::from("actions/checkout"), &Version::from("v4"))
+        .unwrap_err();
+
+    assert!(matches!(err, ResolutionError::Offline), "{err}");
+    assert_eq!(
+        registry.release_notes(&ActionId::from("a/b"), &Version::from("v1")),
+        None
+    );
+}
+
+#[test]
+fn mirrored_owners_resolve_on_the_mirror_before_github() {
+    let (mirror, mirror_server) = serve(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 63\r\nConnection: close\r\n\r\n[{\"ref\":\"refs/tags/v4\",\"object\":{\"sha\":\"abc\",\"type\":\"commit\"}}]",
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+    ]);
+    let (github, github_server) = serve(vec![
+        "HTTP/1.1 200 OK\r\nContent-Length: 63\r\nConnection: close\r\n\r\n[{\"ref\":\"refs/tags/v3\",\"object\":{\"sha\":\"def\",\"type\":\"commit\"}}]",
+    ]);
+    let settings = Settings {
+        github_api_url: Some(format!("{github}/api/v3")),
+        resolver: Resolver::Api,
+        mirrors: [("actions".to_owned(), format!("{mirror}/api/v3"))].into(),
+        ..Settings::default()
+    };
+    let registry = Registry::from_settings(&settings).unwrap();
+
+    let mirrored = registry
+        .all_tags(&ActionId::from("actions/checkout"))
+        .unwrap();
+    let fallback = registry.all_tags(&ActionId::from("actions/cache")).unwrap();
+
+    let mirror_requests = mirror_server.join().unwrap();
+    assert!(
+        matches!(
+            mirror_requests.as_slice(),
+            [checkout, cache] if checkout.contains("/repos/actions/checkout/")
+                && cache.contains("/repos/actions/cache/")
+        ),
+        "{mirror_requests:?}"
+    );
+    assert_eq!(github_server.join().unwrap().len(), 1);
+    assert_eq!(mirrored, vec![Version::from("v4")]);
+    assert_eq!(fallback, vec![Version::from("v3")]);
+}
+
+#[test]
+fn mirrors_get_only_their_own_token_never_the_default() {
+    let secret = |value: &str| crate::config::GitHubToken::from(value.to_owned());
+    let settings = Settings {
+        github_token: Some(secret("ghp_default")),
+        github_tokens: [
+            ("actions".to_owned(), secret("ghp_actions")),
+            ("other-org".to_owned(), secret("ghp_other")),
+        ]
+        .into(),
+        mirrors: [
+            ("actions".to_owned(), "https://ghes.example.com".to_owned()),
+            ("docker".to_owned(), "https://mirror.example.com".to_owned()),
+        ]
+        .into(),
+        ..Settings::default()
+    };
+    let registry = Registry::from_settings(&settings).unwrap();
+
+    let mirror_token = |action: &str| {
+        registry
+            .mirror_for(&ActionId::from(action))
+            .unwrap()
+            .token_for("https://mirror.example.com/repos/other-org/x")
+            .map(|token| token.as_str().to_owned())
+    };
+    assert_eq!(
+        mirror_token("actions/checkout").as_deref(),
+        Some("ghp_actions")
+    );
+    assert_eq!(mirror_token("docker/login-action"), None);
+}
+
+#[test]
+fn renamed_repositories_reports_redirected_repositories_under_their_new_name() {
+    let (base, server) = serve(vec![
+        "HTTP/1.1 301 Moved Permanently\r\nLocation: /repositories/1/git/matching-refs/tags/v\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n[]",
+        "HTTP/1.1 301 Moved Permanently\r\nLocation: /repositories/1\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nContent-Length: 27\r\nConnection: close\r\n\r\n{\"full_name\":\"new-org/act\"}",
+    ]);
+    let mut registry = Registry::new(None).unwrap();
+    registry.api_url = base;
+    registry.resolver = Resolver::Api;
+
+    registry.all_tags(&ActionId::from("old-org/act")).unwrap();
+    let renamed = registry.renamed_repositories();
+
+    assert_eq!(server.join().unwrap().len(), 4);
+    assert_eq!(renamed.len(), 1);
+    let (from, to) = renamed.first().unwrap();
+    assert_eq!((from.as_str(), to.as_str()), ("old-org/act", "new-org/act"));
+}
@@ -0,0 +1,108 @@
+use crate::infra::cache::user_dir;
+use reqwest::blocking::Response;
+use reqwest::header::{ETAG, HeaderValue, LINK};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest as _, Sha1};
+use std::fs;
+use std::path::PathBuf;
+
+/// A cached GET response, revalidated with its `ETag`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Entry {
+    /// The `ETag` the API sent with the response.
+    etag: String,
+    /// The `Link` header, which carries the next page of paginated responses.
+    link: Option<String>,
+    /// The response body.
+    body: String,
+}
+
+/// On-disk cache of GitHub API responses, one file per URL.
+///
+/// Cached responses are sent back with `If-None-Match`; a `304 Not Modified` answer is
+/// served from disk and does not count against the rate limit. The cache is best effort:
+/// entries that cannot be read or written are ignored.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    /// Directory holding the entries.
+    dir: PathBuf,
+}
+
+impl Cache {
+    /// Cache entries under `dir`.
+    #[must_use]
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The user's cache: `http` under `$XDG_CACHE_HOME/gx`, or `~/.cache/gx`.
+    #[must_use]
+    pub fn user() -> Option<Self> {
+        user_dir().map(|root| Self::new(root.join("http")))
+    }
+
+    /// Path of the entry for `url`.
+    fn path(&self, url: &str) -> PathBuf {
+        let key = format!("{:x}", Sha1::digest(url.as_bytes()));
+        self.dir.join(format!("{key}.json"))
+    }
+
+    /// The `If-None-Match` value for a cached `url`.
+    pub fn etag(&self, url: &str) -> Option<HeaderValue> {
+        HeaderValue::from_str(&self.read(url)?.etag).ok()
+    }
+
+    /// Replay the cached response for `url` after the API answered `304 Not Modified`.
+    pub fn replay(&self, url: &str) -> Option<Response> {
+        self.read(url).map(rebuild)
+    }
+
+    /// Store a successful response that carries an `ETag`, and hand back an equivalent one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`reqwest::Error`] if the body of a cacheable response cannot be read.
+    pub fn store(&self, url: &str, response: Response) -> reqwest::Result<Response> {
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        let (etag_header, link) = (header(ETAG), header(LINK));
+        let Some(etag) = etag_header.filter(|_| response.status() == reqwest::StatusCode::OK)
+        else {
+            return Ok(response);
+        };
+        let entry = Entry {
+            etag,
+            link,
+            body: response.text()?,
+        };
+        if let Ok(content) = serde_json::to_string(&entry) {
+            let written =
+                fs::create_dir_all(&self.dir).and_then(|()| fs::write(self.path(url), content));
+            drop(written);
+        }
+        Ok(rebuild(entry))
+    }
+
+    /// Read the entry for `url`.
+    fn read(&self, url: &str) -> Option<Entry> {
+        let content = fs::read_to_string(self.path(url)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+}
+
+/// A `200 OK` response with the body and `Link` header of `entry`.
+fn rebuild(entry: Entry) -> Response {
+    let mut response = http::Response::new(entry.body);
+    if let Some(link) = entry
+        .link
+        .and_then(|link| HeaderValue::from_str(&link).ok())
+    {
+        response.headers_mut().insert(LINK, link);
+    }
+    Response::from(response)
+}
@@ -0,0 +1,182 @@
+use crate::config::Settings;
+use crate::infra::github::Error;
+use crate::infra::trace::Tracer;
+use reqwest::{IntoUrl, Method};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::runtime::Runtime;
+
+/// HTTP User-Agent header value sent with all GitHub API requests.
+const USER_AGENT: &str = "gx-cli";
+/// Base URL for the GitHub REST API.
+pub const GITHUB_API_BASE: &str = "https://api.github.com";
+/// Worker threads of the runtime that drives requests; the blocking callers wait on it.
+const RUNTIME_THREADS: usize = 2;
+
+/// Build the HTTP client with a per-request `timeout`, trusting the certificates in
+/// `ca_bundle` on top of the built-in roots.
+///
+/// Proxies are taken from `HTTPS_PROXY`, `HTTP_PROXY`, and `ALL_PROXY`, with hosts
+/// in `NO_PROXY` reached directly. A proxy that intercepts TLS needs its CA in
+/// `ca_bundle`.
+///
+/// The client is async and pools its connections; clones share the pool.
+pub fn build_client(timeout: Duration, ca_bundle: Option<&Path>) -> Result<reqwest::Client, Error> {
+    let mut builder = reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(timeout);
+    if let Some(path) = ca_bundle {
+        builder = builder.tls_certs_merge(load_certificates(path)?);
+    }
+    builder.build().map_err(Error::ClientInit)
+}
+
+/// Start the runtime that drives the async client.
+///
+/// Any number of threads may wait on it at once with [`Runtime::block_on`], which is how
+/// the blocking API of [`crate::infra::github::Registry`] sends its requests.
+pub fn build_runtime() -> Result<Runtime, Error> {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(RUNTIME_THREADS)
+        .thread_name("gx-http")
+        .enable_all()
+        .build()
+        .map_err(Error::Runtime)
+}
+
+/// A blocking client for the HTTP APIs gx calls besides GitHub's: OCI registries, the
+/// Scorecard API, and GitLab.
+///
+/// It is built with [`build_client`], so it goes through the same proxy and trusts the
+/// same `ca_bundle` as the GitHub client, and its requests count against
+/// `max_requests` together with the GitHub ones.
+#[derive(Clone)]
+pub struct Http {
+    /// The async client; clones share its connection pool.
+    client: reqwest::Client,
+    /// The runtime the blocking callers wait on.
+    runtime: Arc<Runtime>,
+    /// Traces each request sent, for `-v` and `--har`.
+    tracer: Tracer,
+    /// Requests sent this run by every client of the same settings.
+    requests: Arc<AtomicUsize>,
+    /// Requests allowed per run; `None` means no limit.
+    max_requests: Option<usize>,
+}
+
+/// Why [`Http::send`] got no response.
+#[derive(Debug, Error)]
+pub enum SendError {
+    #[error("the {limit} requests max_requests allows this run are used up")]
+    RequestLimit { limit: usize },
+
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+}
+
+impl Http {
+    /// A client with the CA bundle, request limit, and tracer of `settings`, timing out
+    /// after `timeout_secs`, or `default_timeout` when that is not set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the CA bundle cannot be read, or the client or its runtime
+    /// cannot be created.
+    pub fn from_settings(settings: &Settings, default_timeout: Duration) -> Result<Self, Error> {
+        let timeout = settings
+            .timeout_secs
+            .map_or(default_timeout, Duration::from_secs);
+        Ok(Self {
+            client: build_client(timeout, settings.ca_bundle.as_deref())?,
+            runtime: Arc::new(build_runtime()?),
+            tracer: settings.tracer.clone(),
+            requests: Arc::clone(&settings.requests),
+            max_requests: settings.max_requests,
+        })
+    }
+
+    /// Start a `method` request to `url`.
+    pub fn request<U: IntoUrl>(&self, method: Method, url: U) -> reqwest::RequestBuilder {
+        self.client.request(method, url)
+    }
+
+    /// Send `request` and block until its whole response is read.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SendError::RequestLimit`] without sending anything once the requests
+    /// allowed per run are used up, or [`SendError::Request`] if the request fails.
+    pub fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::blocking::Response, SendError> {
+        if let Some(limit) = self.max_requests
+            && self.requests.load(Ordering::Relaxed) >= limit
+        {
+            return Err(SendError::RequestLimit { limit });
+        }
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let response = self
+            .runtime
+            .block_on(async { buffer(self.tracer.send(request).await?).await })?;
+        Ok(response)
+    }
+}
+
+/// Read the whole body of `response` and hand it back as a blocking response with the
+/// same status and headers, which callers can read without the runtime.
+///
+/// # Errors
+///
+/// Returns [`reqwest::Error`] if the body cannot be read.
+pub async fn buffer(response: reqwest::Response) -> reqwest::Result<reqwest::blocking::Response> {
+    let status = response.status();
+    let headers = response.headers().clone();
+    let mut buffered = http::Response::new(response.bytes().await?);
+    *buffered.status_mut() = status;
+    *buffered.headers_mut() = headers;
+    Ok(reqwest::blocking::Response::from(buffered))
+}
+
+/// Read the PEM certificates in the bundle at `path`.
+fn load_certificates(path: &Path) -> Result<Vec<reqwest::Certificate>, Error> {
+    let failed = |source| Error::CaBundle {
+        path: path.to_path_buf(),
+        source,
+    };
+    let pem = std::fs::read(path).map_err(failed)?;
+    let certificates = reqwest::Certificate::from_pem_bundle(&pem)
+        .map_err(|e| failed(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+    if certificates.is_empty() {
+        return Err(failed(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "no PEM certificates found",
+        )));
+    }
+    Ok(certificates)
+}
+
+/// Normalize a configured API URL into the REST API base.
+///
+/// A bare GitHub Enterprise Server host such as `https://ghes.example.com` gets the
+/// `/api/v3` path its REST API is served under; URLs with a path are kept as given.
+pub fn api_base(url: &str) -> String {
+    let trimmed = url.trim_end_matches('/');
+    let host = trimmed.split_once("://").map_or(trimmed, |(_, rest)| rest);
+    if host.contains('/') || host == "api.github.com" {
+        trimmed.to_owned()
+    } else {
+        format!("{trimmed}/api/v3")
+    }
+}
+
+/// Web root of the host an API base URL belongs to, where its repositories are cloned.
+pub fn web_base(api_url: &str) -> String {
+    if api_url == GITHUB_API_BASE {
+        return "https://github.com".to_owned();
+    }
+    api_url.trim_end_matches("/api/v3").to_owned()
+}
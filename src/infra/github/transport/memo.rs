@@ -0,0 +1,131 @@
+use super::Resolutions;
+use crate::domain::action::resolved::Commit;
+use crate::infra::github::responses::GitRefEntry;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+
+/// A file in a repository: `owner/repo`, commit SHA, and path.
+type FileKey = (String, String, String);
+
+/// Lookups already made during this run, keyed on the base repository.
+///
+/// Subpath actions such as `github/codeql-action/init` and `github/codeql-action/analyze`
+/// live in one repository, so they share its refs instead of fetching them again.
+/// Only successful lookups are kept; a failed one is retried by the next caller.
+/// Resolved commits are also kept in [`Resolutions`], when there is one, for later runs.
+#[derive(Clone, Default)]
+pub struct Memo {
+    /// The `git/refs/tags` listing, keyed by `owner/repo`; shared between clones.
+    tag_refs: Arc<Mutex<HashMap<String, Vec<GitRefEntry>>>>,
+    /// Version tag names, keyed by `owner/repo`; shared between clones.
+    version_tags: Arc<Mutex<HashMap<String, Vec<String>>>>,
+    /// Resolved commits, keyed by `owner/repo` and version; shared between clones.
+    commits: Arc<Mutex<HashMap<(String, String), Commit>>>,
+    /// Commit SHAs of annotated tag objects, keyed by `owner/repo` and tag object SHA.
+    peeled: Arc<Mutex<HashMap<(String, String), String>>>,
+    /// File contents, keyed by `owner/repo`, commit SHA, and path; `None` when missing.
+    files: Arc<Mutex<HashMap<FileKey, Option<String>>>>,
+    /// Resolved commits shared with other runs and repositories on this machine.
+    resolutions: Option<Resolutions>,
+}
+
+impl Memo {
+    /// A memo that also keeps resolved commits in `resolutions`.
+    #[must_use]
+    pub fn persistent(resolutions: Option<Resolutions>) -> Self {
+        Self {
+            resolutions,
+            ..Self::default()
+        }
+    }
+
+    /// The tag refs of `base_repo`, fetched with `fetch` the first time.
+    pub fn tag_refs<E, F: FnOnce() -> Result<Vec<GitRefEntry>, E>>(
+        &self,
+        base_repo: &str,
+        fetch: F,
+    ) -> Result<Vec<GitRefEntry>, E> {
+        remember(&self.tag_refs, base_repo.to_owned(), fetch)
+    }
+
+    /// The version tags of `base_repo`, fetched with `fetch` the first time.
+    pub fn version_tags<E, F: FnOnce() -> Result<Vec<String>, E>>(
+        &self,
+        base_repo: &str,
+        fetch: F,
+    ) -> Result<Vec<String>, E> {
+        remember(&self.version_tags, base_repo.to_owned(), fetch)
+    }
+
+    /// The commit `version` of `base_repo` resolves to, taken from [`Resolutions`] or
+    /// resolved with `fetch` the first time.
+    pub fn commit<E, F: FnOnce() -> Result<Commit, E>>(
+        &self,
+        base_repo: &str,
+        version: &str,
+        fetch: F,
+    ) -> Result<Commit, E> {
+        remember(
+            &self.commits,
+            (base_repo.to_owned(), version.to_owned()),
+            || {
+                let stored = self.resolutions.as_ref();
+                if let Some(commit) = stored.and_then(|store| store.get(base_repo, version)) {
+                    return Ok(commit);
+                }
+                let commit = fetch()?;
+                if let Some(store) = stored {
+                    store.put(base_repo, version, &commit);
+                }
+                Ok(commit)
+            },
+        )
+    }
+
+    /// The commit the tag object `tag_sha` of `base_repo` points to, peeled with `fetch`
+    /// the first time.
+    pub fn peeled<E, F: FnOnce() -> Result<String, E>>(
+        &self,
+        base_repo: &str,
+        tag_sha: &str,
+        fetch: F,
+    ) -> Result<String, E> {
+        remember(
+            &self.peeled,
+            (base_repo.to_owned(), tag_sha.to_owned()),
+            fetch,
+        )
+    }
+
+    /// The content of `path` in `repo` at commit `sha`, fetched with `fetch` the first time.
+    pub fn file<E, F: FnOnce() -> Result<Option<String>, E>>(
+        &self,
+        repo: &str,
+        sha: &str,
+        path: &str,
+        fetch: F,
+    ) -> Result<Option<String>, E> {
+        remember(
+            &self.files,
+            (repo.to_owned(), sha.to_owned(), path.to_owned()),
+            fetch,
+        )
+    }
+}
+
+/// The value stored under `key`, or the result of `fetch`, stored when it succeeds.
+fn remember<K: Eq + Hash, V: Clone, E, F: FnOnce() -> Result<V, E>>(
+    memo: &Mutex<HashMap<K, V>>,
+    key: K,
+    fetch: F,
+) -> Result<V, E> {
+    if let Some(value) = memo.lock().ok().and_then(|map| map.get(&key).cloned()) {
+        return Ok(value);
+    }
+    let value = fetch()?;
+    if let Ok(mut map) = memo.lock() {
+        map.insert(key, value.clone());
+    }
+    Ok(value)
+}
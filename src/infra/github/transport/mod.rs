@@ -0,0 +1,27 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// On-disk cache of API responses, revalidated with `ETag`.
+mod cache;
+/// Building the async HTTP client and the runtime that drives it: timeout, proxies,
+/// and extra CA certificates; and the blocking client of the other APIs gx calls.
+mod client;
+/// Per-run memoization of lookups shared by the actions of one repository.
+mod memo;
+/// Rate limit budget, and backing off from rate-limited responses.
+mod rate_limit;
+/// Repositories the API redirected, as it does for renamed ones.
+mod redirects;
+/// Resolved versions kept on disk and shared between runs and repositories.
+mod resolutions;
+/// Retrying requests that fail on transient network or server errors.
+mod retry;
+
+pub use cache::Cache;
+pub use client::{
+    GITHUB_API_BASE, Http, SendError, api_base, buffer, build_client, build_runtime, web_base,
+};
+pub use memo::Memo;
+pub use rate_limit::{Budget, RETRIES, backoff, is_rate_limited, reset_note};
+pub use redirects::Redirects;
+pub use resolutions::{RESOLUTION_TTL, Resolutions};
+pub use retry::{delay, is_transient};
@@ -0,0 +1,101 @@
+use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use time::OffsetDateTime;
+
+/// Times a rate-limited request is retried after waiting for the limit to reset.
+pub const RETRIES: u32 = 2;
+/// Longest wait, in seconds, for a rate limit to reset before giving up on a request.
+const MAX_BACKOFF_SECS: u64 = 60;
+
+/// Rate limit budget read from the `X-RateLimit-*` headers of an API response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    /// Requests allowed per window.
+    pub limit: u64,
+    /// Requests left in the current window.
+    pub remaining: u64,
+    /// When the window resets, in seconds since the Unix epoch.
+    pub reset: i64,
+}
+
+impl Budget {
+    /// Read the budget from response headers; `None` when the API sent none.
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name)?.to_str().ok()?.parse().ok();
+        Some(Self {
+            limit: header("x-ratelimit-limit")?,
+            remaining: header("x-ratelimit-remaining")?,
+            reset: header("x-ratelimit-reset")?.try_into().ok()?,
+        })
+    }
+
+    /// Time of day the window resets, e.g. `14:05 UTC`.
+    #[must_use]
+    pub fn resets_at(&self) -> String {
+        resets_at(self.reset)
+    }
+}
+
+impl fmt::Display for Budget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} requests left, resets at {}",
+            self.remaining,
+            self.limit,
+            self.resets_at()
+        )
+    }
+}
+
+/// Time of day of a Unix timestamp, e.g. `14:05 UTC`.
+pub fn resets_at(reset: i64) -> String {
+    OffsetDateTime::from_unix_timestamp(reset).map_or_else(
+        |_| "an unknown time".to_owned(),
+        |time| format!("{:02}:{:02} UTC", time.hour(), time.minute()),
+    )
+}
+
+/// When the rate limit resets, as a suffix for the rate limited error.
+pub fn reset_note(reset: Option<&i64>) -> String {
+    reset.map_or_else(String::new, |at| {
+        format!("; rate limit resets at {}", resets_at(*at))
+    })
+}
+
+/// Whether a response with `status` and `headers` was refused by the primary or a
+/// secondary rate limit.
+pub fn is_rate_limited(status: StatusCode, headers: &HeaderMap) -> bool {
+    let exhausted = headers
+        .get("x-ratelimit-remaining")
+        .is_some_and(|remaining| remaining == "0")
+        || headers.contains_key(RETRY_AFTER);
+    status == StatusCode::TOO_MANY_REQUESTS || (status == StatusCode::FORBIDDEN && exhausted)
+}
+
+/// How long to wait before retrying a rate-limited response: its `Retry-After`, or the
+/// time until its budget resets, plus up to a second of jitter so concurrent runs do
+/// not retry in lockstep. `None` when the response is not rate limited or the wait is
+/// longer than [`MAX_BACKOFF_SECS`].
+pub fn backoff(response: &reqwest::Response) -> Option<Duration> {
+    let headers = response.headers();
+    if !is_rate_limited(response.status(), headers) {
+        return None;
+    }
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+    let retry_after = headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    let wait = retry_after.or_else(|| {
+        let reset = u64::try_from(Budget::from_headers(headers)?.reset).ok()?;
+        Some(reset.saturating_sub(now.as_secs()))
+    })?;
+    (wait <= MAX_BACKOFF_SECS).then(|| {
+        Duration::from_secs(wait)
+            .saturating_add(Duration::from_millis(u64::from(now.subsec_millis())))
+    })
+}
@@ -0,0 +1,35 @@
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+
+/// Repositories whose requests the API redirected during this run, as `owner/repo`.
+///
+/// GitHub redirects requests for a renamed or transferred repository to its new
+/// location, so lookups under the old name keep working; these are the ones to rename.
+#[derive(Clone, Default)]
+pub struct Redirects {
+    /// Redirected repositories; shared between clones.
+    repos: Arc<Mutex<BTreeSet<String>>>,
+}
+
+impl Redirects {
+    /// Remember the repository of `url`, a request to `{api_url}/repos/{owner}/{repo}/...`.
+    pub fn note(&self, api_url: &str, url: &str) {
+        let repo = url
+            .strip_prefix(&format!("{api_url}/repos/"))
+            .map(|path| path.split('/').take(2).collect::<Vec<_>>().join("/"));
+        if let Some(name) = repo
+            && let Ok(mut repos) = self.repos.lock()
+        {
+            repos.insert(name);
+        }
+    }
+
+    /// The redirected repositories so far.
+    #[must_use]
+    pub fn repositories(&self) -> Vec<String> {
+        self.repos
+            .lock()
+            .map(|repos| repos.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
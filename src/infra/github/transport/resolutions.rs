@@ -0,0 +1,159 @@
+use crate::domain::action::identity::{CommitDate, CommitSha, Repository};
+use crate::domain::action::resolved::{Commit, Provenance, Source};
+use crate::domain::action::uses_ref::RefType;
+use crate::infra::cache::user_dir;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest as _, Sha1};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long a stored resolution is reused before the version is resolved again.
+///
+/// Kept short so a floating tag such as `v4` that moves is picked up within the hour.
+pub const RESOLUTION_TTL: Duration = Duration::from_hours(1);
+
+/// A resolved version, as stored on disk.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    /// The commit SHA the version pointed to.
+    sha: String,
+    /// The repository the commit belongs to.
+    repository: String,
+    /// Whether the version was a tag, a branch, or a commit.
+    ref_type: Option<RefType>,
+    /// The commit date.
+    date: String,
+    /// When the version was resolved, in seconds since the Unix epoch.
+    resolved_at: u64,
+}
+
+/// Versions resolved on this machine, shared by every repository that uses them.
+///
+/// One file per API host, repository, and version. Entries older than the TTL are
+/// ignored. Like [`super::Cache`], the store is best effort: entries that cannot be
+/// read or written are ignored.
+#[derive(Debug, Clone)]
+pub struct Resolutions {
+    /// Directory holding the entries.
+    dir: PathBuf,
+    /// API base URL the entries were resolved against, part of every key.
+    api_url: String,
+    /// How long an entry is reused.
+    ttl: Duration,
+}
+
+impl Resolutions {
+    /// Store entries for the API at `api_url` under `dir`, reusing them for `ttl`.
+    #[must_use]
+    pub fn new(dir: PathBuf, api_url: &str, ttl: Duration) -> Self {
+        Self {
+            dir,
+            api_url: api_url.to_owned(),
+            ttl,
+        }
+    }
+
+    /// The user's store: `resolutions` under the user cache, reused for [`RESOLUTION_TTL`].
+    #[must_use]
+    pub fn user(api_url: &str) -> Option<Self> {
+        user_dir().map(|root| Self::new(root.join("resolutions"), api_url, RESOLUTION_TTL))
+    }
+
+    /// Path of the entry for `version` of `base_repo`.
+    fn path(&self, base_repo: &str, version: &str) -> PathBuf {
+        let key = format!("{} {base_repo}@{version}", self.api_url);
+        self.dir
+            .join(format!("{:x}.json", Sha1::digest(key.as_bytes())))
+    }
+
+    /// The commit `version` of `base_repo` resolved to, if it was resolved within the TTL.
+    #[must_use]
+    pub fn get(&self, base_repo: &str, version: &str) -> Option<Commit> {
+        let content = fs::read_to_string(self.path(base_repo, version)).ok()?;
+        let entry: Entry = serde_json::from_str(&content).ok()?;
+        let age = now().checked_sub(entry.resolved_at)?;
+        (age < self.ttl.as_secs()).then(|| Commit {
+            sha: CommitSha::from(entry.sha),
+            repository: Repository::from(entry.repository),
+            ref_type: entry.ref_type,
+            date: CommitDate::from(entry.date),
+            provenance: Provenance::at(
+                Source::Cache,
+                i64::try_from(entry.resolved_at).unwrap_or_default(),
+            ),
+        })
+    }
+
+    /// Remember that `version` of `base_repo` resolved to `commit`.
+    pub fn put(&self, base_repo: &str, version: &str, commit: &Commit) {
+        let entry = Entry {
+            sha: commit.sha.as_str().to_owned(),
+            repository: commit.repository.as_str().to_owned(),
+            ref_type: commit.ref_type.clone(),
+            date: commit.date.as_str().to_owned(),
+            resolved_at: now(),
+        };
+        if let Ok(content) = serde_json::to_string(&entry) {
+            let written = fs::create_dir_all(&self.dir)
+                .and_then(|()| fs::write(self.path(base_repo, version), content));
+            drop(written);
+        }
+    }
+}
+
+/// Seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{
+        Commit, CommitDate, CommitSha, Duration, Provenance, RefType, Repository, Resolutions,
+        Source,
+    };
+    use crate::infra::github::transport::Memo;
+
+    fn commit() -> Commit {
+        Commit {
+            sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            repository: Repository::from("actions/checkout"),
+            ref_type: Some(RefType::Tag),
+            date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
+        }
+    }
+
+    #[test]
+    fn later_runs_reuse_resolutions_until_they_expire() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = |ttl| {
+            Some(Resolutions::new(
+                dir.path().to_path_buf(),
+                "https://api",
+                ttl,
+            ))
+        };
+        let first = Memo::persistent(store(Duration::from_hours(1)));
+        first
+            .commit("actions/checkout", "v4", || Ok::<_, ()>(commit()))
+            .unwrap();
+
+        let second = Memo::persistent(store(Duration::from_hours(1)));
+        let reused = second.commit("actions/checkout", "v4", || Err(())).unwrap();
+        let other_host = Resolutions::new(dir.path().to_path_buf(), "https://ghes", Duration::MAX);
+        let expired = Memo::persistent(store(Duration::ZERO));
+
+        assert_eq!(reused.sha, commit().sha);
+        assert_eq!(reused.provenance.source, Some(Source::Cache));
+        assert_eq!(other_host.get("actions/checkout", "v4"), None);
+        assert_eq!(
+            expired.commit("actions/checkout", "v4", || Err(())),
+            Err(())
+        );
+    }
+}
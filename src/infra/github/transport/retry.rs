@@ -0,0 +1,49 @@
+use crate::config::Retry;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Whether a request that ended with `result` failed in a way worth retrying: a timeout,
+/// a connection failure, or a server error.
+pub fn is_transient(result: &reqwest::Result<reqwest::Response>) -> bool {
+    match result {
+        Ok(response) => response.status().is_server_error(),
+        Err(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+    }
+}
+
+/// Delay before retry number `attempt` (from 0): `base_delay_ms` doubled per attempt, capped
+/// at `max_delay_ms`, with up to a quarter of it as jitter.
+pub fn delay(retry: &Retry, attempt: u32) -> Duration {
+    let exponential = retry
+        .base_delay_ms
+        .saturating_mul(2_u64.saturating_pow(attempt))
+        .min(retry.max_delay_ms);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| u64::from(now.subsec_nanos()));
+    let spread = (exponential >> 2).saturating_add(1);
+    let jitter = nanos.checked_rem(spread).unwrap_or(0);
+    Duration::from_millis(exponential.saturating_add(jitter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Retry, delay};
+    use std::time::Duration;
+
+    #[test]
+    fn delay_doubles_up_to_the_cap() {
+        let retry = Retry {
+            retries: 5,
+            base_delay_ms: 100,
+            max_delay_ms: 350,
+        };
+        let within = |attempt, low: u64| {
+            let wait = delay(&retry, attempt);
+            wait >= Duration::from_millis(low) && wait <= Duration::from_millis(low * 5 / 4 + 1)
+        };
+        assert!(within(0, 100));
+        assert!(within(1, 200));
+        assert!(within(2, 350));
+        assert!(within(10, 350));
+    }
+}
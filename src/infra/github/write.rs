@@ -0,0 +1,131 @@
+use super::Error as GithubError;
+use super::Registry;
+use super::responses::PullRequestResponse;
+use reqwest::Method;
+use serde::Serialize;
+
+/// A pull request to open with `POST /repos/{owner}/{repo}/pulls`.
+#[derive(Debug, Serialize)]
+pub struct PullRequest {
+    /// The pull request title.
+    pub title: String,
+    /// The branch holding the changes.
+    pub head: String,
+    /// The branch the changes should be merged into.
+    pub base: String,
+    /// Markdown description.
+    pub body: String,
+}
+
+/// Where the "Allow specified actions" policy of GitHub Actions is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Policy {
+    /// An organization, by login.
+    Organization(String),
+    /// A repository, as `owner/repo`.
+    Repository(String),
+}
+
+impl Policy {
+    /// The API path of the policy's selected actions.
+    fn path(&self) -> String {
+        match self {
+            Self::Organization(org) => format!("orgs/{org}/actions/permissions/selected-actions"),
+            Self::Repository(repository) => {
+                format!("repos/{repository}/actions/permissions/selected-actions")
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Policy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Organization(org) => write!(f, "organization {org}"),
+            Self::Repository(repository) => write!(f, "repository {repository}"),
+        }
+    }
+}
+
+/// The body of `PUT .../actions/permissions/selected-actions`.
+#[derive(Debug, Serialize)]
+#[expect(
+    clippy::struct_field_names,
+    reason = "the field names are those of the GitHub API"
+)]
+struct SelectedActions<'patterns> {
+    /// Whether every action GitHub owns is allowed besides the patterns.
+    github_owned_allowed: bool,
+    /// Whether every action of a verified creator is allowed besides the patterns.
+    verified_allowed: bool,
+    /// The `owner/action@ref` patterns allowed.
+    patterns_allowed: &'patterns [String],
+}
+
+#[expect(
+    clippy::multiple_inherent_impl,
+    reason = "API writes are in a separate file for clarity"
+)]
+impl Registry {
+    /// Open a pull request in `repository` (`owner/repo`) and return its web URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GithubError::Unauthorized`] if no token is set or it cannot create pull requests.
+    /// Returns an error if the request fails or the response cannot be parsed.
+    pub fn create_pull_request(
+        &self,
+        repository: &str,
+        pull_request: &PullRequest,
+    ) -> Result<String, GithubError> {
+        let url = format!("{}/repos/{repository}/pulls", self.api_url);
+        let response = self
+            .send(self.authenticated(Method::POST, &url).json(pull_request))
+            .map_err(|source| GithubError::Request {
+                operation: "pull request",
+                url: url.clone(),
+                source,
+            })?;
+
+        if !response.status().is_success() {
+            return Err(Self::check_status(&response, &url));
+        }
+
+        response
+            .json::<PullRequestResponse>()
+            .map(|created| created.html_url)
+            .map_err(|source| GithubError::ParseResponse { url, source })
+    }
+
+    /// Replace the allowed actions of `policy` with exactly `patterns`, leaving neither
+    /// GitHub-owned nor verified actions allowed beyond them. The policy only applies
+    /// while the organization or repository allows "selected actions".
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GithubError::Unauthorized`] if no token is set or it cannot administer
+    /// the policy. Returns an error if the request fails.
+    pub fn set_allowed_actions(
+        &self,
+        policy: &Policy,
+        patterns: &[String],
+    ) -> Result<(), GithubError> {
+        let url = format!("{}/{}", self.api_url, policy.path());
+        let body = SelectedActions {
+            github_owned_allowed: false,
+            verified_allowed: false,
+            patterns_allowed: patterns,
+        };
+        let response = self
+            .send(self.authenticated(Method::PUT, &url).json(&body))
+            .map_err(|source| GithubError::Request {
+                operation: "allowed actions",
+                url: url.clone(),
+                source,
+            })?;
+        if !response.status().is_success() {
+            return Err(Self::check_status(&response, &url));
+        }
+        Ok(())
+    }
+}
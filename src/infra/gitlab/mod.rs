@@ -0,0 +1,12 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// GitLab API client for resolving include refs to commit SHAs.
+mod registry;
+/// Finding `include:component` and `include:project` refs in `.gitlab-ci.yml`.
+mod scan;
+/// Rewriting include refs to pinned commit SHAs.
+mod update;
+
+pub use registry::{Error, Registry};
+pub use scan::{CI_FILE, Include, IncludeKind, scan};
+pub use update::pin;
@@ -0,0 +1,170 @@
+use crate::config::Settings;
+use crate::domain::action::identity::CommitSha;
+use crate::infra::github::{Error as ClientError, Http, SendError};
+use reqwest::Method;
+use serde::Deserialize;
+use std::env;
+use std::time::Duration;
+use thiserror::Error;
+
+/// API of gitlab.com, used when `CI_API_V4_URL` is not set.
+const GITLAB_API_BASE: &str = "https://gitlab.com/api/v4";
+
+/// Timeout in seconds for each HTTP request to the GitLab API.
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Errors that can occur when interacting with the GitLab API.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to create HTTP client")]
+    ClientInit(#[source] ClientError),
+
+    #[error("failed to fetch {url}")]
+    Request {
+        url: String,
+        #[source]
+        source: SendError,
+    },
+
+    #[error("GitLab API returned status {status} for {url}")]
+    ApiError { status: u16, url: String },
+
+    #[error("failed to parse response from {url}")]
+    ParseResponse {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+}
+
+/// Response from `GET /projects/{id}/repository/commits/{ref}`.
+#[derive(Deserialize)]
+struct CommitResponse {
+    /// The commit SHA.
+    id: String,
+}
+
+/// GitLab API client for resolving the refs of includes to commit SHAs.
+pub struct Registry {
+    /// HTTP client sharing the proxy, CA bundle, and request limit of the GitHub one.
+    http: Http,
+    /// API base URL, e.g. `https://gitlab.com/api/v4`.
+    api_url: String,
+    /// Personal, project, or group access token sent as `PRIVATE-TOKEN`.
+    token: Option<String>,
+}
+
+impl Registry {
+    /// A client for the API at `api_url`, authenticated with `token` when given, using
+    /// the `[registry]` timeout, CA bundle, and request limit of `settings`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ClientInit`] if the HTTP client cannot be created.
+    pub fn new(api_url: &str, token: Option<String>, settings: &Settings) -> Result<Self, Error> {
+        let timeout = Duration::from_secs(REQUEST_TIMEOUT_SECS);
+        Ok(Self {
+            http: Http::from_settings(settings, timeout).map_err(Error::ClientInit)?,
+            api_url: api_url.trim_end_matches('/').to_owned(),
+            token,
+        })
+    }
+
+    /// A client for the API in `CI_API_V4_URL`, which GitLab CI sets, or gitlab.com,
+    /// authenticated with `GITLAB_TOKEN` when it is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ClientInit`] if the HTTP client cannot be created.
+    pub fn from_settings(settings: &Settings) -> Result<Self, Error> {
+        let api_url = env::var("CI_API_V4_URL").unwrap_or_else(|_| GITLAB_API_BASE.to_owned());
+        let token = env::var("GITLAB_TOKEN")
+            .ok()
+            .filter(|token| !token.is_empty());
+        Self::new(&api_url, token, settings)
+    }
+
+    /// The commit `reference` of `project` (`group/project`) points to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the API rejects it, or the response
+    /// cannot be parsed.
+    pub fn resolve(&self, project: &str, reference: &str) -> Result<CommitSha, Error> {
+        let url = format!(
+            "{}/projects/{}/repository/commits/{}",
+            self.api_url,
+            encode(project),
+            encode(reference)
+        );
+        let request = self.http.request(Method::GET, &url);
+        let response = self
+            .http
+            .send(match &self.token {
+                Some(token) => request.header("PRIVATE-TOKEN", token),
+                None => request,
+            })
+            .map_err(|source| Error::Request {
+                url: url.clone(),
+                source,
+            })?;
+        if !response.status().is_success() {
+            return Err(Error::ApiError {
+                status: response.status().as_u16(),
+                url,
+            });
+        }
+        response
+            .json::<CommitResponse>()
+            .map(|commit| CommitSha::from(commit.id))
+            .map_err(|source| Error::ParseResponse { url, source })
+    }
+}
+
+/// `segment` with `/` percent-encoded, as the API expects project paths and refs.
+fn encode(segment: &str) -> String {
+    segment.replace('/', "%2F")
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{Registry, Settings};
+    use std::io::{BufRead as _, BufReader, Write as _};
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn resolve_fetches_the_commit_of_an_encoded_project_and_ref() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            reader.read_line(&mut request).unwrap();
+            let mut header = String::new();
+            while reader.read_line(&mut header).unwrap() > 2 {
+                header.clear();
+            }
+            let body = r#"{"id":"0123456789abcdef0123456789abcdef01234567"}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        let registry = Registry::new(&base, None, &Settings::default()).unwrap();
+
+        let sha = registry.resolve("my-org/templates", "release/v3").unwrap();
+
+        assert_eq!(sha.as_str(), "0123456789abcdef0123456789abcdef01234567");
+        assert!(
+            server
+                .join()
+                .unwrap()
+                .starts_with("GET /projects/my-org%2Ftemplates/repository/commits/release%2Fv3 ")
+        );
+    }
+}
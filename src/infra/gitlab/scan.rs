@@ -0,0 +1,165 @@
+use crate::domain::action::identity::CommitSha;
+use crate::regex::static_regex;
+
+/// The pipeline file GitLab reads, relative to the repository root.
+pub const CI_FILE: &str = ".gitlab-ci.yml";
+
+static_regex!(
+    COMPONENT_RE,
+    r#"^\s*(?:-\s+)?component:\s*["']?([^@\s"']+)@([^\s"'#]+)"#
+);
+static_regex!(PROJECT_RE, r#"^\s*(?:-\s+)?project:\s*["']?([^\s"'#]+)"#);
+static_regex!(REF_RE, r#"^\s*(?:-\s+)?ref:\s*["']?([^\s"'#]+)"#);
+
+/// How an include names the project it comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncludeKind {
+    /// `component: host/group/project/name@ref`, with the ref inline.
+    Component,
+    /// `project: group/project` with a `ref:` key next to it.
+    Project,
+}
+
+/// An `include:` in `.gitlab-ci.yml` that names a project and a ref.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Include {
+    /// Whether this is a component or a project include.
+    pub kind: IncludeKind,
+    /// The project path, e.g. `my-org/security-components`.
+    pub project: String,
+    /// The tag, branch, or commit SHA the include points at.
+    pub reference: String,
+    /// Zero-based index of the line holding the ref.
+    pub line: usize,
+}
+
+impl Include {
+    /// Whether the ref is already a full commit SHA.
+    #[must_use]
+    pub fn is_pinned(&self) -> bool {
+        CommitSha::is_valid(&self.reference)
+    }
+}
+
+/// Find the includes of the top-level `include:` block of a `.gitlab-ci.yml`.
+///
+/// Local, remote, and template includes carry no ref and are left out, as are
+/// includes whose ref GitLab resolves itself, such as `~latest` on a component.
+#[must_use]
+pub fn scan(content: &str) -> Vec<Include> {
+    let mut includes = Vec::new();
+    let mut in_include = false;
+    // Key column and path of the `project:` whose `ref:` has not been seen yet
+    let mut project: Option<(usize, String)> = None;
+    for (line, text) in content.lines().enumerate() {
+        let trimmed = text.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = text.len().saturating_sub(trimmed.len());
+        if indent == 0 {
+            in_include = trimmed.starts_with("include:");
+            project = None;
+        }
+        if !in_include {
+            continue;
+        }
+        if project.as_ref().is_some_and(|(column, _)| indent < *column) {
+            project = None;
+        }
+        if let Some(cap) = COMPONENT_RE.captures(text) {
+            let (address, reference) = (&cap[1], &cap[2]);
+            if let Some(path) = component_project(address)
+                && !reference.starts_with('~')
+            {
+                includes.push(Include {
+                    kind: IncludeKind::Component,
+                    project: path.to_owned(),
+                    reference: reference.to_owned(),
+                    line,
+                });
+            }
+        } else if let Some(cap) = PROJECT_RE.captures(text) {
+            project = Some((key_column(text), cap[1].to_owned()));
+        } else if let Some(cap) = REF_RE.captures(text)
+            && let Some((column, path)) = &project
+            && key_column(text) == *column
+        {
+            includes.push(Include {
+                kind: IncludeKind::Project,
+                project: path.clone(),
+                reference: cap[1].to_owned(),
+                line,
+            });
+            project = None;
+        }
+    }
+    includes
+}
+
+/// The project path of a component address: `host/group/project/name` → `group/project`.
+fn component_project(address: &str) -> Option<&str> {
+    let (_host, path) = address.split_once('/')?;
+    let (project, _name) = path.rsplit_once('/')?;
+    Some(project)
+}
+
+/// Column where the key of a YAML line starts, after its indent and any list dash.
+fn key_column(text: &str) -> usize {
+    let key = text.trim_start_matches([' ', '-']);
+    text.len().saturating_sub(key.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Include, IncludeKind, scan};
+
+    #[test]
+    fn scan_finds_component_and_project_refs_in_the_include_block() {
+        let content = "\
+include:
+  - local: /ci/build.yml
+  - component: $CI_SERVER_FQDN/my-org/security/secret-detection@1.2.0
+  - component: gitlab.com/my-org/lint/ruff@~latest
+  - project: 'my-org/templates'
+    file:
+      - /deploy.yml
+    ref: v3
+  - project: my-org/other
+    file: /other.yml
+stages: [build]
+deploy:
+  needs:
+    - project: my-org/upstream
+      ref: main
+";
+        assert_eq!(
+            scan(content),
+            vec![
+                Include {
+                    kind: IncludeKind::Component,
+                    project: "my-org/security".to_owned(),
+                    reference: "1.2.0".to_owned(),
+                    line: 2,
+                },
+                Include {
+                    kind: IncludeKind::Project,
+                    project: "my-org/templates".to_owned(),
+                    reference: "v3".to_owned(),
+                    line: 7,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_reads_a_single_include_written_as_a_map() {
+        let content = "include:\n  project: my-org/templates\n  ref: 0123456789abcdef0123456789abcdef01234567 # v3\n  file: /a.yml\n";
+        let includes = scan(content);
+
+        assert!(matches!(
+            includes.as_slice(),
+            [include] if include.is_pinned() && include.line == 2
+        ));
+    }
+}
@@ -0,0 +1,76 @@
+use super::scan::{Include, IncludeKind};
+use crate::domain::action::identity::CommitSha;
+
+/// Rewrite each include in `pins` to its commit SHA, keeping the ref it replaced as a
+/// trailing comment, e.g. `ref: v3` → `ref: 0123… # v3`.
+///
+/// Lines without a pin are kept as they are.
+#[must_use]
+pub fn pin(content: &str, pins: &[(Include, CommitSha)]) -> String {
+    let mut pinned: Vec<String> = content
+        .lines()
+        .enumerate()
+        .map(|(line, text)| {
+            pins.iter()
+                .find(|(include, _)| include.line == line)
+                .map_or_else(
+                    || text.to_owned(),
+                    |(include, sha)| rewrite(text, include, sha),
+                )
+        })
+        .collect();
+    if content.ends_with('\n') {
+        pinned.push(String::new());
+    }
+    pinned.join("\n")
+}
+
+/// `text` with the ref of `include` replaced by `sha` and the ref as its comment.
+fn rewrite(text: &str, include: &Include, sha: &CommitSha) -> String {
+    let code = text.split_once(" #").map_or(text, |(code, _comment)| code);
+    let reference = &include.reference;
+    let replaced = match include.kind {
+        IncludeKind::Component => code.replacen(&format!("@{reference}"), &format!("@{sha}"), 1),
+        IncludeKind::Project => match code.split_once("ref:") {
+            Some((key, value)) => {
+                format!("{key}ref:{}", value.replacen(reference, sha.as_str(), 1))
+            }
+            None => code.to_owned(),
+        },
+    };
+    format!("{} # {reference}", replaced.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pin;
+    use crate::domain::action::identity::CommitSha;
+    use crate::infra::gitlab::scan;
+
+    #[test]
+    fn pin_replaces_refs_and_keeps_them_as_comments() {
+        let content = "\
+include:
+  - component: 'gitlab.com/my-org/security/secret-detection@1.2.0'
+  - project: my-org/templates
+    ref: v3 # old note
+    file: /deploy.yml
+";
+        let sha = CommitSha::from("0123456789abcdef0123456789abcdef01234567");
+        let pins: Vec<_> = scan(content)
+            .into_iter()
+            .map(|include| (include, sha.clone()))
+            .collect();
+
+        assert_eq!(
+            pin(content, &pins),
+            "\
+include:
+  - component: 'gitlab.com/my-org/security/secret-detection@0123456789abcdef0123456789abcdef01234567' # 1.2.0
+  - project: my-org/templates
+    ref: 0123456789abcdef0123456789abcdef01234567 # v3
+    file: /deploy.yml
+"
+        );
+    }
+}
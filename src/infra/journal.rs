@@ -0,0 +1,442 @@
+use super::atomic;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest as _, Sha1};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File name of the change journal inside `.github`.
+pub const JOURNAL_FILE_NAME: &str = "gx-journal.json";
+
+/// Number of runs kept in the journal; older runs are dropped.
+const MAX_RUNS: usize = 10;
+
+/// Errors that can occur when reading, writing, or replaying the change journal.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to read {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse journal: {}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("failed to serialize journal: {}", path.display())]
+    Serialize {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("failed to write {}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("{} changed after gx wrote it; revert it with git instead", path.display())]
+    Modified { path: PathBuf },
+}
+
+/// Every run recorded in the journal, oldest first.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Journal {
+    /// Recorded runs.
+    runs: Vec<Run>,
+}
+
+/// The file mutations of one gx run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Run {
+    /// The subcommand that made the changes, e.g. `upgrade`.
+    pub command: String,
+    pub changes: Vec<Change>,
+}
+
+/// One file rewritten by a run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Change {
+    /// Path relative to the repository root.
+    pub path: PathBuf,
+    /// SHA-1 of the content before the run; `None` if the run created the file.
+    pub before: Option<String>,
+    /// SHA-1 of the content after the run; `None` if the run deleted the file.
+    pub after: Option<String>,
+    pub hunk: Hunk,
+}
+
+/// The changed lines of a file: `removed` was replaced by `added` at line `start`.
+///
+/// Lines keep their line endings, so replaying a hunk restores the file byte for byte.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hunk {
+    /// Zero-based index of the first changed line.
+    pub start: usize,
+    pub removed: Vec<String>,
+    pub added: Vec<String>,
+}
+
+/// The contents of a set of files before a run, to diff against afterwards.
+#[derive(Debug)]
+pub struct Snapshot {
+    /// The repository root the paths are recorded relative to.
+    root: PathBuf,
+    /// Each file and its content; `None` when it did not exist.
+    files: Vec<(PathBuf, Option<String>)>,
+}
+
+impl Snapshot {
+    /// Read the current content of `paths`.
+    #[must_use]
+    pub fn take(repo_root: &Path, paths: Vec<PathBuf>) -> Self {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let content = fs::read_to_string(&path).ok();
+                (path, content)
+            })
+            .collect();
+        Self {
+            root: repo_root.to_path_buf(),
+            files,
+        }
+    }
+
+    /// The files whose content differs from the snapshot now.
+    #[must_use]
+    pub fn changes(&self) -> Vec<Change> {
+        self.files
+            .iter()
+            .filter_map(|(path, before)| {
+                let after = fs::read_to_string(path).ok();
+                (after != *before).then(|| Change {
+                    path: path.strip_prefix(&self.root).unwrap_or(path).to_path_buf(),
+                    before: before.as_deref().map(hash),
+                    after: after.as_deref().map(hash),
+                    hunk: hunk(
+                        before.as_deref().unwrap_or_default(),
+                        after.as_deref().unwrap_or_default(),
+                    ),
+                })
+            })
+            .collect()
+    }
+
+    /// Save the content each changed file had when the snapshot was taken to its
+    /// [`backup_path`], and return the backups written. Files created since have none.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Write`] if a backup cannot be written.
+    pub fn backup(&self) -> Result<Vec<PathBuf>, Error> {
+        let mut written = Vec::new();
+        for (path, before) in &self.files {
+            let Some(content) = before else {
+                continue;
+            };
+            if fs::read_to_string(path).ok().as_ref() == Some(content) {
+                continue;
+            }
+            let backup = backup_path(path);
+            atomic::write(&backup, content).map_err(|source| Error::Write {
+                path: backup.clone(),
+                source,
+            })?;
+            written.push(backup);
+        }
+        Ok(written)
+    }
+
+    /// Put every file back as it was when the snapshot was taken, removing those created
+    /// since, after a run failed part way through writing them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Write`] if a file cannot be written back or removed.
+    pub fn restore(&self) -> Result<(), Error> {
+        for (path, before) in &self.files {
+            if fs::read_to_string(path).ok() == *before {
+                continue;
+            }
+            let result = match before {
+                Some(content) => atomic::replace(path, content),
+                None => fs::remove_file(path),
+            };
+            result.map_err(|source| Error::Write {
+                path: path.clone(),
+                source,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Where the backup of `path` is saved: beside it, with `.bak` appended to its name.
+#[must_use]
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
+/// Append `run` to the journal of the repository at `repo_root`, unless it changed nothing.
+///
+/// # Errors
+///
+/// Returns [`Error`] if the journal cannot be read or written.
+pub fn record(repo_root: &Path, run: Run) -> Result<(), Error> {
+    if run.changes.is_empty() {
+        return Ok(());
+    }
+    let path = journal_path(repo_root);
+    let mut journal = read(&path)?;
+    journal.runs.push(run);
+    let excess = journal.runs.len().saturating_sub(MAX_RUNS);
+    journal.runs.drain(..excess);
+    write(&path, &journal)
+}
+
+/// Revert the most recent run in the journal and drop it from the journal.
+///
+/// Returns the reverted run, or `None` when the journal is empty.
+///
+/// # Errors
+///
+/// Returns [`Error::Modified`] without touching any file if a file was edited after the run.
+/// Returns [`Error`] if the journal or a file cannot be read or written.
+pub fn undo(repo_root: &Path) -> Result<Option<Run>, Error> {
+    let path = journal_path(repo_root);
+    let mut journal = read(&path)?;
+    let Some(run) = journal.runs.pop() else {
+        return Ok(None);
+    };
+
+    let mut reverted = Vec::new();
+    for change in &run.changes {
+        let file = repo_root.join(&change.path);
+        let current = match fs::read_to_string(&file) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(source) => return Err(Error::Read { path: file, source }),
+        };
+        if current.as_deref().map(hash) != change.after {
+            return Err(Error::Modified {
+                path: change.path.clone(),
+            });
+        }
+        let restored = revert(current.as_deref().unwrap_or_default(), &change.hunk);
+        reverted.push((file, change.before.is_some().then_some(restored)));
+    }
+
+    for (file, restored) in reverted {
+        let result = match restored {
+            Some(content) => atomic::write(&file, &content),
+            None => fs::remove_file(&file),
+        };
+        result.map_err(|source| Error::Write { path: file, source })?;
+    }
+    write(&path, &journal)?;
+    Ok(Some(run))
+}
+
+/// Path of the journal for the repository at `repo_root`.
+fn journal_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(".github").join(JOURNAL_FILE_NAME)
+}
+
+/// Read the journal at `path`; a missing journal is empty.
+fn read(path: &Path) -> Result<Journal, Error> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Journal::default()),
+        Err(source) => {
+            return Err(Error::Read {
+                path: path.to_path_buf(),
+                source,
+            });
+        }
+    };
+    serde_json::from_str(&content).map_err(|source| Error::Parse {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Write the journal to `path`.
+fn write(path: &Path, journal: &Journal) -> Result<(), Error> {
+    let content = serde_json::to_string_pretty(journal).map_err(|source| Error::Serialize {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    atomic::write(path, &format!("{content}\n")).map_err(|source| Error::Write {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Hex SHA-1 of `content`.
+fn hash(content: &str) -> String {
+    format!("{:x}", Sha1::digest(content.as_bytes()))
+}
+
+/// The single hunk turning `before` into `after`: everything between their common
+/// leading and trailing lines.
+fn hunk(before: &str, after: &str) -> Hunk {
+    let old: Vec<&str> = before.split_inclusive('\n').collect();
+    let new: Vec<&str> = after.split_inclusive('\n').collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(old.len().min(new.len()).saturating_sub(prefix))
+        .take_while(|(a, b)| a == b)
+        .count();
+    let changed = |lines: &[&str]| -> Vec<String> {
+        lines
+            .iter()
+            .skip(prefix)
+            .take(lines.len().saturating_sub(prefix).saturating_sub(suffix))
+            .map(|&line| line.to_owned())
+            .collect()
+    };
+    Hunk {
+        start: prefix,
+        removed: changed(&old),
+        added: changed(&new),
+    }
+}
+
+/// Undo `hunk` on `content`, putting its removed lines back in place of the added ones.
+fn revert(content: &str, hunk: &Hunk) -> String {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let head = lines.iter().take(hunk.start).copied();
+    let tail = lines
+        .iter()
+        .skip(hunk.start.saturating_add(hunk.added.len()))
+        .copied();
+    head.chain(hunk.removed.iter().map(String::as_str))
+        .chain(tail)
+        .collect()
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "tests use unwrap, indexing, and other patterns freely"
+)]
+mod tests {
+    use super::{Error, Hunk, Run, Snapshot, hunk, record, revert, undo};
+    use std::fs;
+
+    #[test]
+    fn hunk_covers_only_changed_lines() {
+        let before = "a\nb\nc\nd\n";
+        let after = "a\nB\nX\nd\n";
+        let changed = hunk(before, after);
+        assert_eq!(
+            changed,
+            Hunk {
+                start: 1,
+                removed: vec!["b\n".to_owned(), "c\n".to_owned()],
+                added: vec!["B\n".to_owned(), "X\n".to_owned()],
+            }
+        );
+        assert_eq!(revert(after, &changed), before);
+    }
+
+    #[test]
+    fn hunk_reverts_insertions_and_deletions() {
+        for (before, after) in [
+            ("a\nc\n", "a\nb\nc\n"),
+            ("a\nb\nc\n", "a\nc\n"),
+            ("", "a\n"),
+            ("a\na\n", "a\na\na\n"),
+            ("a", "a\n"),
+        ] {
+            assert_eq!(revert(after, &hunk(before, after)), before, "{after:?}");
+        }
+    }
+
+    #[test]
+    fn undo_restores_the_last_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let github = dir.path().join(".github");
+        fs::create_dir(&github).unwrap();
+        let lock = github.join("gx.lock");
+        let created = github.join("gx.toml");
+        fs::write(&lock, "one\ntwo\n").unwrap();
+
+        let snapshot = Snapshot::take(dir.path(), vec![lock.clone(), created.clone()]);
+        fs::write(&lock, "one\n2\n").unwrap();
+        fs::write(&created, "[actions]\n").unwrap();
+        record(
+            dir.path(),
+            Run {
+                command: "tidy".to_owned(),
+                changes: snapshot.changes(),
+            },
+        )
+        .unwrap();
+
+        let run = undo(dir.path()).unwrap().unwrap();
+        assert_eq!(run.command, "tidy");
+        assert_eq!(fs::read_to_string(&lock).unwrap(), "one\ntwo\n");
+        assert!(!created.exists());
+        assert_eq!(undo(dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn restore_puts_back_changed_files_and_removes_created_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = dir.path().join("gx.lock");
+        let workflow = dir.path().join("ci.yml");
+        let created = dir.path().join("gx.toml");
+        fs::write(&lock, "old\n").unwrap();
+        fs::write(&workflow, "untouched\n").unwrap();
+
+        let snapshot = Snapshot::take(
+            dir.path(),
+            vec![lock.clone(), workflow.clone(), created.clone()],
+        );
+        fs::write(&lock, "new\n").unwrap();
+        fs::write(&created, "[actions]\n").unwrap();
+        snapshot.restore().unwrap();
+
+        assert_eq!(fs::read_to_string(&lock).unwrap(), "old\n");
+        assert_eq!(fs::read_to_string(&workflow).unwrap(), "untouched\n");
+        assert!(!created.exists());
+    }
+
+    #[test]
+    fn undo_refuses_files_edited_after_the_run() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join(".github")).unwrap();
+        let lock = dir.path().join(".github").join("gx.lock");
+        fs::write(&lock, "one\n").unwrap();
+
+        let snapshot = Snapshot::take(dir.path(), vec![lock.clone()]);
+        fs::write(&lock, "two\n").unwrap();
+        record(
+            dir.path(),
+            Run {
+                command: "upgrade".to_owned(),
+                changes: snapshot.changes(),
+            },
+        )
+        .unwrap();
+        fs::write(&lock, "edited\n").unwrap();
+
+        let err = undo(dir.path()).unwrap_err();
+        assert!(matches!(err, Error::Modified { .. }), "{err}");
+        assert_eq!(fs::read_to_string(&lock).unwrap(), "edited\n");
+    }
+}
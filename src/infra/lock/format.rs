@@ -1,8 +1,10 @@
+use super::migration::LOCK_FILE_VERSION;
 use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
-use crate::domain::action::resolved::Commit;
+use crate::domain::action::resolved::{Commit, Provenance, Source};
 use crate::domain::action::spec::Spec;
 use crate::domain::action::specifier::Specifier;
 use crate::domain::action::uses_ref::RefType;
+use crate::domain::image::Digest;
 use crate::domain::lock::{Lock, LockEntry};
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -27,6 +29,12 @@ pub struct ActionCommitData {
     pub ref_type: String,
     /// ISO 8601 date of the resolved commit or release.
     pub date: String,
+    /// Where gx found the commit: `api`, `git`, `cache`, or `unknown`.
+    #[serde(default)]
+    pub source: String,
+    /// When gx resolved the commit, empty when unknown.
+    #[serde(default)]
+    pub resolved_at: String,
 }
 
 /// Internal structure for two-tier TOML deserialization.
@@ -38,19 +46,25 @@ pub struct TwoTierData {
     /// Map of action ID -> version -> commit data.
     #[serde(default)]
     pub actions: HashMap<String, HashMap<String, ActionCommitData>>,
+    /// Map of container image `name:tag` -> digest.
+    #[serde(default)]
+    pub images: HashMap<String, String>,
+    /// Hash of the manifest the lock was written for.
+    #[serde(default)]
+    pub manifest_hash: Option<String>,
 }
 
-/// Try to parse lock file content as the current two-tier format.
+/// Read a lock already migrated to the current schema by [`super::migration::migrate`].
 ///
-/// Returns `Ok(Some(lock))` if the content is two-tier format (contains `[resolutions`),
-/// `Ok(None)` if the content is not two-tier format, or `Err` if parsing fails.
-pub fn try_parse(content: &str, path: &Path) -> Result<Option<Lock>, super::Error> {
-    if !content.contains("[resolutions") {
-        return Ok(None);
-    }
-
-    let data: TwoTierData = super::parse_toml(content, path)?;
-    Ok(Some(lock_from_two_tier(&data)))
+/// # Errors
+///
+/// Returns [`super::Error::Parse`] if the tables do not hold the expected fields.
+pub fn parse(table: toml::Table, path: &Path) -> Result<Lock, super::Error> {
+    let data: TwoTierData = table.try_into().map_err(|source| super::Error::Parse {
+        path: path.to_path_buf(),
+        source: Box::new(source),
+    })?;
+    Ok(lock_from_two_tier(&data))
 }
 
 /// Convert deserialized two-tier lock data into a domain `Lock`.
@@ -86,6 +100,10 @@ fn lock_from_two_tier(data: &TwoTierData) -> Lock {
                             repository: Repository::from(commit_data.repository.as_str()),
                             ref_type: RefType::parse(&commit_data.ref_type),
                             date: CommitDate::from(commit_data.date.as_str()),
+                            provenance: Provenance {
+                                source: Source::parse(&commit_data.source),
+                                resolved_at: commit_data.resolved_at.clone(),
+                            },
                         },
                     },
                 );
@@ -93,7 +111,14 @@ fn lock_from_two_tier(data: &TwoTierData) -> Lock {
         }
     }
 
-    Lock::new(entries)
+    let mut lock = Lock::new(entries);
+    for (key, digest) in &data.images {
+        lock.set_image(key.clone(), Digest::from(digest.as_str()));
+    }
+    if let Some(hash) = &data.manifest_hash {
+        lock.set_manifest_hash(hash.clone());
+    }
+    lock
 }
 
 /// Serialize a `Lock` to the two-tier TOML format string.
@@ -101,14 +126,27 @@ pub(super) fn write(lock: &Lock) -> String {
     build_lock_document(lock).to_string()
 }
 
+/// Serialize a `Lock` to the tables of the two-tier format as a JSON object, with keys
+/// sorted.
+pub(super) fn write_json(lock: &Lock) -> String {
+    let table: toml::Table = toml::from_str(&write(lock)).unwrap_or_default();
+    let mut output = serde_json::to_string_pretty(&table).unwrap_or_default();
+    output.push('\n');
+    output
+}
+
 /// Build a `toml_edit::DocumentMut` from a `Lock` using the two-tier format.
 ///
 /// Writes `[resolutions]` and `[actions]` sections with nested TOML tables.
 /// Resolutions are sorted by action ID then specifier.
 /// Actions are sorted by action ID then version.
-/// No top-level `version` field is written.
+/// The schema `version` comes first, then `manifest_hash` when recorded.
 fn build_lock_document(lock: &Lock) -> DocumentMut {
     let mut doc = DocumentMut::new();
+    doc.insert("version", toml_edit::value(LOCK_FILE_VERSION));
+    if let Some(hash) = lock.manifest_hash() {
+        doc.insert("manifest_hash", toml_edit::value(hash));
+    }
 
     // Collect entries sorted by action ID then specifier.
     let mut sorted_entries: Vec<_> = lock.entries().collect();
@@ -179,6 +217,15 @@ fn build_lock_document(lock: &Lock) -> DocumentMut {
     }
 
     doc.insert("actions", toml_edit::Item::Table(actions));
+
+    // --- [images] ---
+    let mut images = toml_edit::Table::new();
+    for (key, digest) in lock.images() {
+        images.insert(key, toml_edit::value(digest.as_str()));
+    }
+    if !images.is_empty() {
+        doc.insert("images", toml_edit::Item::Table(images));
+    }
     doc
 }
 
@@ -201,7 +248,7 @@ fn ref_type_to_str(ref_type: Option<&RefType>) -> &'static str {
     })
 }
 
-/// Populate a TOML table with action commit metadata (4 fields).
+/// Populate a TOML table with action commit metadata and its provenance.
 fn populate_action_table(
     table: &mut toml_edit::Table,
     commit: &crate::domain::action::resolved::Commit,
@@ -213,12 +260,19 @@ fn populate_action_table(
         toml_edit::value(ref_type_to_str(commit.ref_type.as_ref())),
     );
     table.insert("date", toml_edit::value(commit.date.as_str()));
+    table.insert(
+        "source",
+        toml_edit::value(commit.provenance.source.map_or("unknown", Source::as_str)),
+    );
+    table.insert(
+        "resolved_at",
+        toml_edit::value(commit.provenance.resolved_at.as_str()),
+    );
 }
 
 #[cfg(test)]
 #[expect(
     clippy::unwrap_used,
-    clippy::expect_used,
     reason = "tests use unwrap, indexing, and other patterns freely"
 )]
 mod tests {
@@ -227,6 +281,10 @@ mod tests {
     use crate::domain::action::specifier::Specifier;
     use crate::domain::action::uses_ref::RefType;
 
+    fn reparse(output: &str) -> Lock {
+        parse(toml::from_str(output).unwrap(), Path::new("test.lock")).unwrap()
+    }
+
     fn set_resolved(lock: &mut Lock, action: &str, specifier: &str, sha: &str) {
         let spec = Spec::new(ActionId::from(action), Specifier::parse(specifier));
         let version = Version::from(Specifier::parse(specifier).to_lookup_tag());
@@ -238,6 +296,7 @@ mod tests {
                 repository: ActionId::from(action).base_repo(),
                 ref_type: Some(RefType::Tag),
                 date: CommitDate::from("2026-01-01T00:00:00Z"),
+                provenance: Provenance::default(),
             },
         );
     }
@@ -259,9 +318,7 @@ mod tests {
         );
 
         let output = write(&lock);
-        let parsed = try_parse(&output, Path::new("test.lock"))
-            .unwrap()
-            .expect("should parse as two-tier");
+        let parsed = reparse(&output);
 
         // Verify all entries survived the roundtrip
         let spec1 = Spec::new(ActionId::from("actions/checkout"), Specifier::parse("^4"));
@@ -287,14 +344,41 @@ mod tests {
     }
 
     #[test]
-    fn try_parse_returns_none_for_non_two_tier() {
-        let content = r#"version = "1.4"
-
-[actions]
-"actions/checkout@^4" = { sha = "abc123", version = "v4.0.0", comment = "v4", repository = "actions/checkout", ref_type = "tag", date = "" }
-"#;
-        let result = try_parse(content, Path::new("test.lock")).unwrap();
-        assert!(result.is_none(), "flat format should return None");
+    fn manifest_hash_roundtrips_and_comes_first() {
+        let mut lock = Lock::default();
+        lock.set_manifest_hash("0123abcd".to_owned());
+
+        let output = write(&lock);
+        let parsed = reparse(&output);
+
+        assert!(output.starts_with("version = 2\nmanifest_hash = \"0123abcd\"\n"));
+        assert_eq!(parsed.manifest_hash(), Some("0123abcd"));
+    }
+
+    #[test]
+    fn provenance_roundtrips_and_unknown_stays_unknown() {
+        let mut lock = Lock::default();
+        set_resolved(&mut lock, "actions/checkout", "^4", "abc123");
+        set_resolved(&mut lock, "actions/cache", "^3", "def456");
+        let checkout = Spec::new(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+        let cache = Spec::new(ActionId::from("actions/cache"), Specifier::parse("^3"));
+        let mut commit = lock.get(&checkout).unwrap().commit.clone();
+        commit.provenance = Provenance::at(Source::Api, 1_767_225_600);
+        lock.set(&checkout, Version::from("v4"), commit);
+
+        let output = write(&lock);
+        let parsed = reparse(&output);
+
+        assert!(output.contains("source = \"api\"\nresolved_at = \"2026-01-01T00:00:00Z\"\n"));
+        assert!(output.contains("source = \"unknown\"\nresolved_at = \"\"\n"));
+        assert_eq!(
+            parsed.get(&checkout).unwrap().commit.provenance,
+            Provenance::at(Source::Api, 1_767_225_600)
+        );
+        assert_eq!(
+            parsed.get(&cache).unwrap().commit.provenance,
+            Provenance::default()
+        );
     }
 
     #[test]
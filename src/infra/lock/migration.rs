@@ -1,111 +1,183 @@
-use crate::domain::action::identity::{CommitDate, CommitSha, Repository, Version};
-use crate::domain::action::resolved::Commit;
+use crate::domain::Parsed;
 use crate::domain::action::spec::Spec;
-use crate::domain::action::uses_ref::RefType;
-use crate::domain::lock::{Lock, LockEntry};
-use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::Path;
+use toml::{Table, Value};
 
-/// Action entry data in the flat lock format.
-#[derive(Debug, Clone, Deserialize)]
-pub struct FlatEntryData {
-    /// The full commit SHA.
-    pub sha: String,
-    /// The resolved version string (e.g. "v4.0.0").
-    #[serde(default)]
-    pub version: Option<String>,
-    /// Legacy comment field -- consumed by serde during deserialization but no longer used.
-    #[serde(default)]
-    #[expect(
-        dead_code,
-        reason = "consumed by serde for forward-compatible parsing of legacy lock files"
-    )]
-    pub comment: String,
-    /// The source repository (e.g. "actions/checkout").
-    pub repository: String,
-    /// The ref type that was resolved (tag, branch, commit, or release).
-    pub ref_type: String,
-    /// ISO 8601 date of the resolved commit or release.
-    pub date: String,
-}
+/// The lock schema gx writes. Older schemas are migrated to it when read:
+///
+/// - 0: the flat format, one `[actions."owner/repo@specifier"]` entry per spec, sometimes
+///   with a `version = "1.4"` string.
+/// - 1: the two-tier format of `[resolutions]` and `[actions]`, without a `version`.
+/// - 2: two-tier with `version = 2`, and the `source` and `resolved_at` of each commit.
+pub const LOCK_FILE_VERSION: i64 = 2;
 
-/// Internal structure for flat TOML deserialization (legacy format).
-#[derive(Debug, Deserialize, Default)]
-pub struct FlatData {
-    /// Schema version string (ignored by serde -- present in v1.4 files).
-    #[serde(default)]
-    #[expect(
-        dead_code,
-        reason = "version field is consumed by serde but not accessed directly"
-    )]
-    pub version: String,
-    /// Map of `"action@specifier"` composite keys to their entry data.
-    #[serde(default)]
-    pub actions: HashMap<String, FlatEntryData>,
-}
+/// The step upgrading each schema to the next, indexed by the schema it upgrades from.
+const MIGRATIONS: [fn(&mut Table); 2] = [split_tiers, add_provenance];
 
-/// Try to parse lock file content as the flat format.
+/// Text every lock file of a known schema contains, to tell one from any other TOML.
+const MARKERS: [&str; 5] = [
+    "[resolutions",
+    "[images",
+    "[actions",
+    "manifest_hash",
+    "version",
+];
+
+/// Parse lock file content of any known schema, in TOML or JSON, and migrate it to
+/// [`LOCK_FILE_VERSION`].
+///
+/// `migrated` is set when the content used an older schema, so writing the lock back
+/// upgrades the file in place.
+///
+/// # Errors
 ///
-/// Returns `Ok(Some(lock))` if the content is flat format (has `[actions` but no `[resolutions`),
-/// `Ok(None)` if the content is not flat format, or `Err` if parsing fails.
-pub fn try_parse(content: &str, path: &Path) -> Result<Option<Lock>, super::Error> {
-    if content.contains("[resolutions") {
-        return Ok(None);
+/// Returns [`super::Error::UnrecognizedFormat`] if the content is not a lock file.
+/// Returns [`super::Error::Parse`] or [`super::Error::ParseJson`] if the TOML or JSON is
+/// invalid.
+/// Returns [`super::Error::UnsupportedVersion`] if a newer gx wrote the file.
+pub fn migrate(content: &str, path: &Path) -> Result<Parsed<Table>, super::Error> {
+    let unrecognized = || super::Error::UnrecognizedFormat {
+        path: path.to_path_buf(),
+    };
+    let mut table: Table = if super::FileFormat::detect(content) == super::FileFormat::Json {
+        super::parse_json(content, path)?
+    } else if MARKERS.iter().any(|marker| content.contains(marker)) {
+        super::parse_toml(content, path)?
+    } else {
+        return Err(unrecognized());
+    };
+    let version = schema_version(&table).ok_or_else(unrecognized)?;
+    if version > LOCK_FILE_VERSION {
+        return Err(super::Error::UnsupportedVersion {
+            path: path.to_path_buf(),
+            version,
+        });
     }
-    if !content.contains("[actions") {
-        return Ok(None);
+    for step in MIGRATIONS
+        .iter()
+        .skip(usize::try_from(version).unwrap_or_default())
+    {
+        step(&mut table);
     }
-
-    let data: FlatData = super::parse_toml(content, path)?;
-    Ok(Some(lock_from_flat(data)))
+    Ok(Parsed {
+        value: table,
+        migrated: version < LOCK_FILE_VERSION,
+    })
 }
 
-/// Convert deserialized flat lock data into a domain `Lock`.
-fn lock_from_flat(data: FlatData) -> Lock {
-    let mut entries = HashMap::new();
+/// The schema a lock file was written with, or `None` when it is not a lock file.
+fn schema_version(table: &Table) -> Option<i64> {
+    match table.get("version") {
+        Some(Value::Integer(version)) => return Some(*version),
+        Some(Value::String(_)) => return Some(0),
+        Some(_) | None => {}
+    }
+    if ["resolutions", "images", "manifest_hash"]
+        .iter()
+        .any(|key| table.contains_key(*key))
+    {
+        Some(1)
+    } else {
+        table.contains_key("actions").then_some(0)
+    }
+}
 
-    for (k, entry_data) in data.actions {
-        let Some(spec) = Spec::parse(&k) else {
+/// Schema 0 to 1: split each flat `"owner/repo@specifier"` entry into the version its
+/// specifier resolved to, under `[resolutions]`, and the commit of that version, under
+/// `[actions]`. An entry without a `version` resolved to its specifier.
+fn split_tiers(table: &mut Table) {
+    table.remove("version");
+    let flat = table.remove("actions");
+    let mut resolutions: BTreeMap<String, Table> = BTreeMap::new();
+    let mut actions: BTreeMap<String, Table> = BTreeMap::new();
+    let entries = flat
+        .as_ref()
+        .and_then(Value::as_table)
+        .into_iter()
+        .flatten();
+    for (key, entry) in entries {
+        let (Some(spec), Some(fields)) = (Spec::parse(key), entry.as_table()) else {
             continue;
         };
-        let version_str = entry_data
-            .version
-            .as_deref()
-            .unwrap_or(spec.specifier.as_str());
-        let version = Version::from(version_str);
-        entries.insert(
-            spec,
-            LockEntry {
-                version,
-                commit: Commit {
-                    sha: CommitSha::from(entry_data.sha),
-                    repository: Repository::from(entry_data.repository),
-                    ref_type: RefType::parse(&entry_data.ref_type),
-                    date: CommitDate::from(entry_data.date),
-                },
-            },
-        );
+        let version = fields
+            .get("version")
+            .and_then(Value::as_str)
+            .unwrap_or(spec.specifier.as_str())
+            .to_owned();
+        let mut commit = fields.clone();
+        commit.remove("version");
+        commit.remove("comment");
+
+        let mut resolution = Table::new();
+        resolution.insert("version".to_owned(), Value::String(version.clone()));
+        resolutions
+            .entry(spec.id.as_str().to_owned())
+            .or_default()
+            .insert(spec.specifier.as_str().to_owned(), Value::Table(resolution));
+        actions
+            .entry(spec.id.as_str().to_owned())
+            .or_default()
+            .entry(version)
+            .or_insert(Value::Table(commit));
     }
+    let into_value = |tiers: BTreeMap<String, Table>| {
+        Value::Table(
+            tiers
+                .into_iter()
+                .map(|(id, tier)| (id, Value::Table(tier)))
+                .collect(),
+        )
+    };
+    table.insert("resolutions".to_owned(), into_value(resolutions));
+    table.insert("actions".to_owned(), into_value(actions));
+}
 
-    Lock::new(entries)
+/// Schema 1 to 2: mark the commits as resolved from an unknown source at an unknown time,
+/// since gx did not record where they came from.
+fn add_provenance(table: &mut Table) {
+    let commits = table
+        .get_mut("actions")
+        .and_then(Value::as_table_mut)
+        .into_iter()
+        .flat_map(|actions| actions.iter_mut().map(|(_, versions)| versions))
+        .filter_map(Value::as_table_mut)
+        .flat_map(|versions| versions.iter_mut().map(|(_, commit)| commit))
+        .filter_map(Value::as_table_mut);
+    for commit in commits {
+        commit
+            .entry("source")
+            .or_insert_with(|| Value::String("unknown".to_owned()));
+        commit
+            .entry("resolved_at")
+            .or_insert_with(|| Value::String(String::new()));
+    }
 }
 
 #[cfg(test)]
 #[expect(
     clippy::unwrap_used,
+    clippy::indexing_slicing,
     reason = "tests use unwrap, indexing, and other patterns freely"
 )]
 mod tests {
-    use super::*;
+    use super::{LOCK_FILE_VERSION, migrate};
     use crate::domain::action::identity::{ActionId, CommitSha};
     use crate::domain::action::spec::Spec;
     use crate::domain::action::specifier::Specifier;
+    use crate::domain::lock::Lock;
+    use std::path::Path;
 
     fn make_key(action: &str, specifier: &str) -> Spec {
         Spec::new(ActionId::from(action), Specifier::parse(specifier))
     }
 
+    fn load(content: &str) -> Lock {
+        let migrated = migrate(content, Path::new("test.lock")).unwrap();
+        assert!(migrated.migrated, "an older schema must be migrated");
+        crate::infra::lock::format::parse(migrated.value, Path::new("test.lock")).unwrap()
+    }
+
     #[test]
     fn flat_format_with_version_field_parses() {
         let content = r#"version = "1.4"
@@ -113,9 +185,7 @@ mod tests {
 [actions]
 "actions/checkout@^6" = { sha = "de0fac2e4500dabe0009e67214ff5f5447ce83dd", version = "v6.2.3", comment = "v6", repository = "actions/checkout", ref_type = "release", date = "2026-01-09T19:42:23Z" }
 "#;
-        let result = try_parse(content, Path::new("test.lock")).unwrap();
-        assert!(result.is_some(), "v1.4 format must be parsed as flat");
-        let lock = result.unwrap();
+        let lock = load(content);
         let entry = lock.get(&make_key("actions/checkout", "^6")).unwrap();
         assert_eq!(entry.version.as_str(), "v6.2.3");
         assert_eq!(
@@ -134,9 +204,7 @@ repository = "actions/checkout"
 ref_type = "tag"
 date = "2026-01-01T00:00:00Z"
 "#;
-        let result = try_parse(content, Path::new("test.lock")).unwrap();
-        assert!(result.is_some(), "flat format without version must parse");
-        let lock = result.unwrap();
+        let lock = load(content);
         let entry = lock.get(&make_key("actions/checkout", "^4")).unwrap();
         assert_eq!(entry.version.as_str(), "v4.0.0");
         assert_eq!(
@@ -154,9 +222,7 @@ repository = "actions/checkout"
 ref_type = "tag"
 date = ""
 "#;
-        let result = try_parse(content, Path::new("test.lock")).unwrap();
-        assert!(result.is_some());
-        let lock = result.unwrap();
+        let lock = load(content);
         let entry = lock.get(&make_key("actions/checkout", "^4")).unwrap();
         assert_eq!(
             entry.version.as_str(),
@@ -183,29 +249,19 @@ repository = "actions/checkout"
 ref_type = "tag"
 date = "2026-01-01T00:00:00Z"
 "#;
-        let result = try_parse(content, Path::new("test.lock")).unwrap();
-        assert!(result.is_some());
-        let lock = result.unwrap();
-
-        // Two entries
-        let spec1 = make_key("actions/checkout", "^4");
-        let spec2 = make_key("actions/checkout", "^4.2");
-        assert!(lock.has(&spec1));
-        assert!(lock.has(&spec2));
-
-        // Both have same version and SHA
-        let entry1 = lock.get(&spec1).unwrap();
-        let entry2 = lock.get(&spec2).unwrap();
+        let lock = load(content);
+
+        let entry1 = lock.get(&make_key("actions/checkout", "^4")).unwrap();
+        let entry2 = lock.get(&make_key("actions/checkout", "^4.2")).unwrap();
         assert_eq!(entry1.version.as_str(), "v4.2.1");
         assert_eq!(entry2.version.as_str(), "v4.2.1");
         assert_eq!(entry1.commit.sha, entry2.commit.sha);
     }
 
     #[test]
-    fn try_parse_returns_none_for_two_tier() {
+    fn two_tier_without_version_gains_unknown_provenance() {
         let content = r#"[resolutions."actions/checkout"."^4"]
 version = "v4.0.0"
-comment = "v4"
 
 [actions."actions/checkout"."v4.0.0"]
 sha = "abc123"
@@ -213,17 +269,21 @@ repository = "actions/checkout"
 ref_type = "tag"
 date = ""
 "#;
-        let result = try_parse(content, Path::new("test.lock")).unwrap();
-        assert!(result.is_none(), "two-tier format should return None");
+        let migrated = migrate(content, Path::new("test.lock")).unwrap();
+        let commit = &migrated.value["actions"]["actions/checkout"]["v4.0.0"];
+
+        assert!(migrated.migrated);
+        assert_eq!(commit["source"].as_str(), Some("unknown"));
+        assert_eq!(commit["resolved_at"].as_str(), Some(""));
     }
 
     #[test]
-    fn try_parse_returns_none_for_no_actions() {
-        let content = "# empty lock file\n";
-        let result = try_parse(content, Path::new("test.lock")).unwrap();
-        assert!(
-            result.is_none(),
-            "content without [actions] should return None"
-        );
+    fn current_schema_is_not_migrated_and_newer_ones_are_refused() {
+        let current = format!("version = {LOCK_FILE_VERSION}\n");
+        let newer = format!("version = {}\n", LOCK_FILE_VERSION.saturating_add(1));
+
+        assert!(!migrate(&current, Path::new("test.lock")).unwrap().migrated);
+        let error = migrate(&newer, Path::new("test.lock")).unwrap_err();
+        assert!(error.to_string().contains("newer"), "{error}");
     }
 }
@@ -1,11 +1,12 @@
 #![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
 
-/// Current two-tier format: read + write.
+/// Current schema: read + write.
 mod format;
-/// Legacy flat format reader.
+/// Lock schema versions and the migrations from each to the next.
 mod migration;
-/// Lock file store, error types, and TOML parsing.
+/// Lock file store, error types, and TOML and JSON parsing.
 mod store;
 
-use store::parse_toml;
-pub use store::{Error, LOCK_FILE_NAME, Store};
+pub use migration::LOCK_FILE_VERSION;
+pub use store::{Error, FileFormat, LOCK_FILE_NAME, Store, parse};
+use store::{parse_json, parse_toml};
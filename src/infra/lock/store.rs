@@ -1,5 +1,9 @@
+use super::migration::LOCK_FILE_VERSION;
+use crate::domain::Parsed;
 use crate::domain::lock::Lock;
-use serde::Deserialize;
+use crate::domain::trust::Trust;
+use crate::infra::{atomic, trust};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
@@ -23,6 +27,13 @@ pub enum Error {
         source: Box<toml::de::Error>,
     },
 
+    #[error("failed to parse lock file: {}", path.display())]
+    ParseJson {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
     #[error("failed to write lock file: {}", path.display())]
     Write {
         path: PathBuf,
@@ -32,21 +43,130 @@ pub enum Error {
 
     #[error("unrecognized lock file format: {}", path.display())]
     UnrecognizedFormat { path: PathBuf },
+
+    #[error(
+        "{} uses lock schema {version}, which is newer than this gx reads ({LOCK_FILE_VERSION}); upgrade gx",
+        path.display()
+    )]
+    UnsupportedVersion { path: PathBuf, version: i64 },
+
+    #[error(
+        "--trusted-only refuses {} commit(s) the trust database does not approve: {}",
+        entries.len(),
+        entries.join(", ")
+    )]
+    Untrusted { entries: Vec<String> },
+
+    #[error(transparent)]
+    Trust(#[from] trust::Error),
+}
+
+/// The syntax a lock file is written in, from `lock_format` in the `[files]` section of
+/// `gx.toml`. Either is read regardless.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileFormat {
+    #[default]
+    Toml,
+    /// The same tables as the TOML lock, as a JSON object.
+    Json,
+}
+
+impl FileFormat {
+    /// The format lock file `content` is in: JSON when it is an object, TOML otherwise.
+    #[must_use]
+    pub fn detect(content: &str) -> Self {
+        if content.trim_start().starts_with('{') {
+            Self::Json
+        } else {
+            Self::Toml
+        }
+    }
+
+    /// The name of the format in messages, such as `JSON`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Toml => "TOML",
+            Self::Json => "JSON",
+        }
+    }
+}
+
+/// Parse lock file `content` read from `path`, migrating older schemas to
+/// [`LOCK_FILE_VERSION`]. Empty content is an empty lock.
+///
+/// # Errors
+///
+/// Returns [`Error::Parse`] or [`Error::ParseJson`] if the TOML or JSON is invalid.
+/// Returns [`Error::UnrecognizedFormat`] if the content is not a recognized lock format.
+/// Returns [`Error::UnsupportedVersion`] if a newer gx wrote the content.
+pub fn parse(content: &str, path: &Path) -> Result<Parsed<Lock>, Error> {
+    if content.trim().is_empty() {
+        return Ok(Parsed {
+            value: Lock::default(),
+            migrated: false,
+        });
+    }
+    let migrated = super::migration::migrate(content, path)?;
+    Ok(Parsed {
+        value: super::format::parse(migrated.value, path)?,
+        migrated: migrated.migrated,
+    })
 }
 
 /// File-backed lock store. Reads from and writes to `.github/gx.lock`.
 pub struct Store {
     /// Path to the lock file on disk.
     path: PathBuf,
+    /// The format [`Store::save`] writes.
+    format: FileFormat,
+    /// What new commits must be in, under `--trusted-only`.
+    trust: Option<Approved>,
+}
+
+/// The commits a `--trusted-only` save may write, read once when the store is made.
+struct Approved {
+    /// The trust database.
+    database: Trust,
+    /// The lock as it was on disk; its commits stay trusted.
+    previous: Lock,
 }
 
 impl Store {
+    /// A store writing TOML.
     #[must_use]
     pub fn new(path: &Path) -> Self {
         Self {
             path: path.to_path_buf(),
+            format: FileFormat::Toml,
+            trust: None,
         }
     }
+
+    /// This store, writing in `format`.
+    #[must_use]
+    pub const fn with_format(mut self, format: FileFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// This store, refusing to save commits that neither the lock on disk now nor the
+    /// trust database at `trust` has.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Trust`] if the trust database cannot be read.
+    /// Returns the errors of [`Store::load`].
+    pub fn with_trust(mut self, trust: Option<PathBuf>) -> Result<Self, Error> {
+        if let Some(path) = trust {
+            self.trust = Some(Approved {
+                database: trust::read(&path)?,
+                previous: self.load()?,
+            });
+        }
+        Ok(self)
+    }
 }
 
 #[expect(
@@ -57,52 +177,77 @@ impl Store {
     /// Load a `Lock` from this file.
     ///
     /// Returns `Lock::default()` if the file does not exist or is empty.
-    /// Tries the current two-tier format first, then the legacy flat format.
     ///
     /// # Errors
     ///
-    /// Returns [`Error::Read`] if the file cannot be read.
-    /// Returns [`Error::Parse`] if the TOML is invalid.
-    /// Returns [`Error::UnrecognizedFormat`] if the content is not a recognized lock format.
+    /// Returns the errors of [`Store::parse`].
     pub fn load(&self) -> Result<Lock, Error> {
+        Ok(self.parse()?.value)
+    }
+
+    /// Load a `Lock` from this file, migrating older schemas to [`LOCK_FILE_VERSION`].
+    /// `migrated` is set when the file used an older schema, so that saving the lock
+    /// upgrades it in place.
+    ///
+    /// Returns `Lock::default()` if the file does not exist or is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Read`] if the file cannot be read.
+    /// Returns the errors of [`parse`].
+    pub fn parse(&self) -> Result<Parsed<Lock>, Error> {
         if !self.path.exists() {
-            return Ok(Lock::default());
+            return Ok(Parsed {
+                value: Lock::default(),
+                migrated: false,
+            });
         }
 
         let content = fs::read_to_string(&self.path).map_err(|source| Error::Read {
             path: self.path.clone(),
             source,
         })?;
+        parse(&content, &self.path)
+    }
 
-        if content.trim().is_empty() {
-            return Ok(Lock::default());
-        }
-
-        // Try current two-tier format first
-        if let Some(lock) = super::format::try_parse(&content, &self.path)? {
-            return Ok(lock);
-        }
-
-        // Try legacy flat format
-        if let Some(lock) = super::migration::try_parse(&content, &self.path)? {
-            return Ok(lock);
+    /// The format the file is written in now, or `None` when it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Read`] if the file cannot be read.
+    pub fn current_format(&self) -> Result<Option<FileFormat>, Error> {
+        if !self.path.exists() {
+            return Ok(None);
         }
-
-        Err(Error::UnrecognizedFormat {
-            path: self.path.clone(),
-        })
+        fs::read_to_string(&self.path)
+            .map(|content| Some(FileFormat::detect(&content)))
+            .map_err(|source| Error::Read {
+                path: self.path.clone(),
+                source,
+            })
     }
 
-    /// Save the given `Lock` to this file using the current two-tier format.
+    /// Save the given `Lock` to this file in the current schema and this store's format.
     ///
     /// Always writes the full lock (no diff-based patching).
     ///
     /// # Errors
     ///
+    /// Returns [`Error::Untrusted`] if the store has a trust database that does not
+    /// approve a commit `lock` adds.
     /// Returns [`Error::Write`] if the file cannot be written.
     pub fn save(&self, lock: &Lock) -> Result<(), Error> {
-        let output = super::format::write(lock);
-        fs::write(&self.path, output).map_err(|source| Error::Write {
+        if let Some(approved) = &self.trust {
+            let entries = approved.database.untrusted(&approved.previous, lock);
+            if !entries.is_empty() {
+                return Err(Error::Untrusted { entries });
+            }
+        }
+        let output = match self.format {
+            FileFormat::Toml => super::format::write(lock),
+            FileFormat::Json => super::format::write_json(lock),
+        };
+        atomic::write(&self.path, &output).map_err(|source| Error::Write {
             path: self.path.clone(),
             source,
         })?;
@@ -121,6 +266,17 @@ pub(super) fn parse_toml<T: for<'de> Deserialize<'de>>(
     })
 }
 
+/// Deserialize JSON content into the requested type, mapping errors to [`Error::ParseJson`].
+pub(super) fn parse_json<T: for<'de> Deserialize<'de>>(
+    content: &str,
+    path: &Path,
+) -> Result<T, Error> {
+    serde_json::from_str(content).map_err(|source| Error::ParseJson {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
 #[cfg(test)]
 #[expect(
     clippy::unwrap_used,
@@ -1,9 +1,11 @@
-use super::Store;
+use super::{FileFormat, Store};
 use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
-use crate::domain::action::resolved::Commit;
+use crate::domain::action::resolved::{Commit, Provenance};
 use crate::domain::action::spec::Spec;
 use crate::domain::action::specifier::Specifier;
 use crate::domain::action::uses_ref::RefType;
+use crate::domain::image::Digest;
+use crate::domain::trust::Trust;
 use std::io::Write as _;
 use std::path::Path;
 use tempfile::NamedTempFile;
@@ -23,6 +25,7 @@ fn set_resolved(lock: &mut crate::domain::lock::Lock, action: &str, specifier: &
             repository: ActionId::from(action).base_repo(),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         },
     );
 }
@@ -86,6 +89,30 @@ fn load_two_tier_format() {
     assert_eq!(entry.version.as_str(), "v4.0.0");
 }
 
+#[test]
+fn two_tier_lock_is_migrated_and_saved_as_the_current_schema() {
+    let content = two_tier_entry(
+        "actions/checkout@^4",
+        "abc123def456789012345678901234567890abcd",
+        "v4.0.0",
+        "actions/checkout",
+        "tag",
+        "2026-01-01T00:00:00Z",
+    );
+    let file = NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), content).unwrap();
+    let store = Store::new(file.path());
+
+    let parsed = store.parse().unwrap();
+    store.save(&parsed.value).unwrap();
+    let saved = std::fs::read_to_string(file.path()).unwrap();
+
+    assert!(parsed.migrated);
+    assert!(saved.starts_with("version = 2\n"), "{saved}");
+    assert!(saved.contains("source = \"unknown\""), "{saved}");
+    assert!(!store.parse().unwrap().migrated);
+}
+
 #[test]
 fn load_flat_format() {
     let content = r#"version = "1.4"
@@ -148,6 +175,36 @@ fn save_and_load_roundtrip() {
     );
 }
 
+#[test]
+fn save_with_trust_refuses_unapproved_commits_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let trust_path = dir.path().join("gx-trust.toml");
+    let lock_path = dir.path().join("gx.lock");
+    let store = || {
+        Store::new(&lock_path)
+            .with_trust(Some(trust_path.clone()))
+            .unwrap()
+    };
+    let mut lock = crate::domain::lock::Lock::default();
+    set_resolved(&mut lock, "actions/checkout", "^4", "aaa111");
+
+    let refused = store().save(&lock);
+
+    assert_eq!(
+        refused.unwrap_err().to_string(),
+        "--trusted-only refuses 1 commit(s) the trust database does not approve: actions/checkout@v4 (aaa111)"
+    );
+    crate::infra::trust::write(&trust_path, &Trust::from_lock(&lock)).unwrap();
+    store().save(&lock).unwrap();
+    set_resolved(&mut lock, "actions/setup-node", "^4", "bbb222");
+    std::fs::remove_file(&trust_path).unwrap();
+    let untrusted = store();
+    assert!(untrusted.save(&lock).is_err());
+    let mut kept = crate::domain::lock::Lock::default();
+    set_resolved(&mut kept, "actions/checkout", "^4", "aaa111");
+    untrusted.save(&kept).unwrap();
+}
+
 #[test]
 fn save_sorts_actions_alphabetically() {
     let file = NamedTempFile::new().unwrap();
@@ -222,6 +279,7 @@ fn save_roundtrip_preserves_all_fields() {
         repository: Repository::from("actions/checkout"),
         ref_type: Some(RefType::Release),
         date: CommitDate::from("2026-01-15T10:30:00Z"),
+        provenance: Provenance::default(),
     };
     lock.set(&spec, version.clone(), commit.clone());
 
@@ -238,3 +296,48 @@ fn save_roundtrip_preserves_all_fields() {
     assert_eq!(loaded_entry.commit.ref_type, commit.ref_type);
     assert_eq!(loaded_entry.commit.date.as_str(), commit.date.as_str());
 }
+
+#[test]
+fn save_and_load_roundtrip_keeps_image_digests() {
+    let file = NamedTempFile::new().unwrap();
+    let store = Store::new(file.path());
+    let digest = "sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    let mut lock = crate::domain::lock::Lock::default();
+    lock.set_image("alpine:3.19".to_owned(), Digest::from(digest));
+    store.save(&lock).unwrap();
+
+    let content = std::fs::read_to_string(file.path()).unwrap();
+    assert!(content.contains(&format!("[images]\n\"alpine:3.19\" = \"{digest}\"")));
+    let loaded = store.load().unwrap();
+    assert_eq!(loaded.image("alpine:3.19"), Some(&Digest::from(digest)));
+}
+
+#[test]
+fn json_lock_roundtrips_and_is_detected_on_load() {
+    let file = NamedTempFile::new().unwrap();
+    let mut lock = crate::domain::lock::Lock::default();
+    set_resolved(&mut lock, "actions/checkout", "^4", "abc123");
+    lock.set_manifest_hash("feed".to_owned());
+
+    Store::new(file.path())
+        .with_format(FileFormat::Json)
+        .save(&lock)
+        .unwrap();
+
+    let content = std::fs::read_to_string(file.path()).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(json["version"], 2);
+    assert_eq!(json["manifest_hash"], "feed");
+    let store = Store::new(file.path());
+    assert_eq!(store.current_format().unwrap(), Some(FileFormat::Json));
+    let parsed = store.parse().unwrap();
+    assert!(!parsed.migrated);
+    assert_eq!(
+        parsed
+            .value
+            .get(&make_key("actions/checkout", "^4"))
+            .map(|entry| entry.commit.sha.as_str()),
+        Some("abc123")
+    );
+}
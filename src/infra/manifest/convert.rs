@@ -1,11 +1,12 @@
 use super::Error as ManifestError;
-use crate::config::Rule;
+use crate::config::{Audit, Files, Level, Licenses, Projects, Registry, Rule, Workflows};
 use crate::domain::action::identity::ActionId;
 use crate::domain::action::spec::Spec as ActionSpec;
 use crate::domain::action::specifier::Specifier;
-use crate::domain::manifest::Manifest;
-use crate::domain::manifest::overrides::ActionOverride;
-use crate::domain::workflow_actions::{JobId, StepIndex, WorkflowPath};
+use crate::domain::manifest::aliases::Aliases;
+use crate::domain::manifest::overrides::{self, ActionOverride};
+use crate::domain::manifest::{Annotation, Manifest};
+use crate::domain::workflow_actions::{JobId, StepIndex, StepKey, WorkflowPath};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
@@ -29,19 +30,67 @@ pub struct TomlOverride {
     /// Optional job name to narrow the override scope.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub job: Option<String>,
-    /// Optional step index to narrow the override scope.
+    /// Optional step `id:`, `name:` or index to narrow the override scope.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub step: Option<usize>,
+    pub step: Option<TomlStep>,
     /// The version specifier for this override.
     pub version: String,
 }
 
-/// The [actions] section: flat string entries + optional [actions.overrides] sub-table.
+/// The `step` of an override entry: the step's `id:` or `name:`, or its 0-based index.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TomlStep {
+    /// `step = 2`.
+    Index(usize),
+    /// `step = "Deploy"`.
+    Name(String),
+}
+
+/// An [actions] entry: a bare specifier string or a table with per-action settings.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum TomlAction {
+    /// A bare specifier: `"owner/action" = "^4"`.
+    Version(String),
+    /// A table: `"owner/action" = { version = "^4", hold = true }`.
+    Table {
+        /// The version specifier.
+        version: String,
+        /// Never upgrade the action nor let tidy correct it.
+        #[serde(default)]
+        hold: bool,
+        /// Offer pre-release tags as upgrade candidates.
+        #[serde(default)]
+        allow_prerelease: bool,
+        /// Fail `gx verify --signatures` when the locked commit or tag is not signed.
+        #[serde(default)]
+        require_signed: bool,
+        /// Why the action is pinned as it is, shown when it is skipped or linted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+        /// Who looks after the pin.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        owner: Option<String>,
+    },
+}
+
+impl TomlAction {
+    /// The version specifier string of the entry.
+    #[must_use]
+    pub fn version(&self) -> &str {
+        match self {
+            Self::Version(version) | Self::Table { version, .. } => version,
+        }
+    }
+}
+
+/// The [actions] section: flat entries + optional [actions.overrides] sub-table.
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct TomlActions {
-    /// Flat map of action IDs to version specifier strings.
+    /// Flat map of action IDs to their entries.
     #[serde(default, flatten)]
-    pub versions: BTreeMap<String, String>,
+    pub versions: BTreeMap<String, TomlAction>,
     /// Per-action override lists keyed by action ID.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub overrides: BTreeMap<String, Vec<TomlOverride>>,
@@ -59,6 +108,30 @@ pub struct ManifestData {
     /// The [lint] section containing rule configuration.
     #[serde(default)]
     pub lint: LintData,
+    /// The [groups] section: group names mapped to the action IDs they contain.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub groups: BTreeMap<String, Vec<String>>,
+    /// The [registry] section: where actions are resolved.
+    #[serde(default)]
+    pub registry: Registry,
+    /// The [workflows] section: which files are read and rewritten as workflows.
+    #[serde(default)]
+    pub workflows: Workflows,
+    /// The [files] section: how rewritten files are treated.
+    #[serde(default)]
+    pub files: Files,
+    /// The [audit] section: the Scorecard policy of `gx audit`.
+    #[serde(default)]
+    pub audit: Audit,
+    /// The [licenses] section: the license denylist of `gx licenses`.
+    #[serde(default)]
+    pub licenses: Licenses,
+    /// The [projects] section: sub-projects with their own `.github/workflows`.
+    #[serde(default)]
+    pub projects: Projects,
+    /// The [aliases] section: short names mapped to the action IDs they stand for.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
 }
 
 /// The [lint] section of the manifest.
@@ -67,6 +140,9 @@ pub struct LintData {
     /// Map of rule names to their configuration.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub rules: BTreeMap<crate::lint::RuleName, Rule>,
+    /// Map of workflow glob patterns to per-rule level overrides.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub paths: BTreeMap<String, BTreeMap<crate::lint::RuleName, Level>>,
 }
 
 // ---- conversion ----
@@ -77,19 +153,27 @@ pub fn manifest_from_data(
     _path: &Path,
     is_v2: bool,
 ) -> Result<Manifest, ManifestError> {
-    // Build global actions map
+    let parse_specifier = |version: &str| {
+        if is_v2 {
+            Specifier::parse(version)
+        } else {
+            Specifier::from_v1(version)
+        }
+    };
+    let aliases: Aliases = data.aliases.into_iter().collect();
+
+    // Build global actions map, keeping the tables of entries with settings
+    let mut settings = Vec::new();
     let actions: HashMap<ActionId, ActionSpec> = data
         .actions
         .versions
         .into_iter()
         .map(|(k, v)| {
             let id = ActionId::from(k);
-            let specifier = if is_v2 {
-                Specifier::parse(&v)
-            } else {
-                Specifier::from_v1(&v)
-            };
-            let spec = ActionSpec::new(id.clone(), specifier);
+            let spec = ActionSpec::new(id.clone(), parse_specifier(v.version()));
+            if matches!(v, TomlAction::Table { .. }) {
+                settings.push((id.clone(), v));
+            }
             (id, spec)
         })
         .collect();
@@ -98,7 +182,7 @@ pub fn manifest_from_data(
     let mut overrides: HashMap<ActionId, Vec<ActionOverride>> = HashMap::new();
 
     for (action_str, toml_overrides) in data.actions.overrides {
-        let id = ActionId::from(action_str.clone());
+        let id = aliases.expand(&action_str);
 
         // Validation: override without global default is an error
         if !actions.contains_key(&id) {
@@ -107,7 +191,7 @@ pub fn manifest_from_data(
             )));
         }
 
-        let mut seen_scopes: Vec<(String, Option<String>, Option<usize>)> = Vec::new();
+        let mut seen_scopes: Vec<(String, Option<String>, Option<TomlStep>)> = Vec::new();
 
         let mut converted = Vec::new();
         for exc in toml_overrides {
@@ -119,8 +203,18 @@ pub fn manifest_from_data(
                 )));
             }
 
+            // Validation: workflow and job patterns must be valid globs
+            if let Some(pattern) = std::iter::once(&exc.workflow)
+                .chain(&exc.job)
+                .find(|pattern| glob::Pattern::new(pattern).is_err())
+            {
+                return Err(ManifestError::Validation(format!(
+                    "override for \"{action_str}\" has an invalid pattern \"{pattern}\""
+                )));
+            }
+
             // Validation: duplicate scope
-            let scope = (exc.workflow.clone(), exc.job.clone(), exc.step);
+            let scope = (exc.workflow.clone(), exc.job.clone(), exc.step.clone());
             if seen_scopes.contains(&scope) {
                 return Err(ManifestError::Validation(format!(
                     "duplicate override scope for \"{}\" in \"{}\"",
@@ -129,36 +223,72 @@ pub fn manifest_from_data(
             }
             seen_scopes.push(scope);
 
-            let specifier = if is_v2 {
-                Specifier::parse(&exc.version)
-            } else {
-                Specifier::from_v1(&exc.version)
+            let step = match exc.step {
+                Some(TomlStep::Index(index)) => Some(StepKey::Index(
+                    StepIndex::try_from(index).map_err(ManifestError::Validation)?,
+                )),
+                Some(TomlStep::Name(name)) => Some(StepKey::Name(name)),
+                None => None,
             };
 
-            let step_index = exc
-                .step
-                .map(StepIndex::try_from)
-                .transpose()
-                .map_err(ManifestError::Validation)?;
-
             converted.push(ActionOverride {
                 workflow: WorkflowPath::new(exc.workflow),
                 job: exc.job.map(JobId::from),
-                step: step_index,
-                version: specifier,
+                step,
+                version: parse_specifier(&exc.version),
             });
         }
         overrides.insert(id, converted);
     }
 
-    Ok(Manifest::with_overrides(actions, overrides))
+    let mut manifest = Manifest::with_overrides(actions, overrides);
+    for (name, members) in data.groups {
+        let ids = members
+            .iter()
+            .map(|member| aliases.expand(member))
+            .collect();
+        manifest.set_group(name, ids);
+    }
+    manifest.set_aliases(aliases);
+    for (id, entry) in settings {
+        apply_settings(&mut manifest, id, entry);
+    }
+    Ok(manifest)
+}
+
+/// Mark the action `id` as its `[actions]` table asks: held, open to pre-releases,
+/// required to be signed, and with its `reason` and `owner`.
+fn apply_settings(manifest: &mut Manifest, id: ActionId, entry: TomlAction) {
+    let TomlAction::Table {
+        hold,
+        allow_prerelease,
+        require_signed,
+        reason,
+        owner,
+        ..
+    } = entry
+    else {
+        return;
+    };
+    if hold {
+        manifest.hold(id.clone());
+    }
+    if allow_prerelease {
+        manifest.allow_prerelease(id.clone());
+    }
+    if require_signed {
+        manifest.require_signed(id.clone());
+    }
+    if reason.is_some() || owner.is_some() {
+        manifest.annotate(id, Annotation { reason, owner });
+    }
 }
 
 // ---- Building ----
 
 /// Build a `toml_edit::DocumentMut` from a `Manifest`.
 /// Output has no `[gx]` section. Sections: `[actions]`, optional `[actions.overrides]`,
-/// optional `[lint]`.
+/// optional `[lint]`, optional `[groups]`, optional `[aliases]`.
 pub fn build_manifest_document(manifest: &Manifest) -> DocumentMut {
     let mut doc = DocumentMut::new();
 
@@ -168,7 +298,7 @@ pub fn build_manifest_document(manifest: &Manifest) -> DocumentMut {
     specs.sort_by_key(|s| s.id.as_str().to_owned());
 
     for spec in &specs {
-        actions.insert(spec.id.as_str(), toml_edit::value(spec.specifier.as_str()));
+        actions.insert(spec.id.as_str(), action_entry(manifest, spec));
     }
 
     // Build [actions.overrides] if any overrides exist
@@ -184,161 +314,104 @@ pub fn build_manifest_document(manifest: &Manifest) -> DocumentMut {
             if ovrs.is_empty() {
                 continue;
             }
+            let mut sorted = (*ovrs).clone();
+            overrides::sort(&mut sorted);
             let mut arr = toml_edit::Array::new();
-            for ovr in *ovrs {
+            for ovr in &sorted {
                 let mut inline = toml_edit::InlineTable::new();
                 inline.insert("workflow", ovr.workflow.as_str().into());
                 if let Some(job) = &ovr.job {
                     inline.insert("job", toml_edit::Value::from(job.as_str()));
                 }
-                if let Some(step) = ovr.step {
-                    inline.insert("step", i64::from(step).into());
+                if let Some(step) = &ovr.step {
+                    inline.insert("step", step_value(step));
                 }
                 inline.insert("version", ovr.version.as_str().into());
                 arr.push(inline);
             }
-            overrides_table.insert(id.as_str(), toml_edit::value(arr));
+            overrides_table.insert(manifest.aliases().spelling(id), toml_edit::value(arr));
         }
         actions.insert("overrides", toml_edit::Item::Table(overrides_table));
     }
 
     doc.insert("actions", toml_edit::Item::Table(actions));
 
-    doc
-}
-
-#[cfg(test)]
-#[expect(
-    clippy::unwrap_used,
-    clippy::indexing_slicing,
-    reason = "tests use unwrap, indexing, and other patterns freely"
-)]
-mod tests {
-    use super::{Manifest, build_manifest_document};
-    use crate::domain::action::identity::ActionId;
-    use crate::domain::action::specifier::Specifier;
-    use crate::domain::manifest::overrides::ActionOverride;
-    use crate::domain::workflow_actions::WorkflowPath;
-    use crate::infra::manifest::{Store, parse};
-    use std::fs;
-    use std::io::Write as _;
-    use tempfile::NamedTempFile;
-
-    #[test]
-    fn file_manifest_save_and_load_roundtrip() {
-        let file = NamedTempFile::new().unwrap();
-        let store = Store::new(file.path());
-
-        let mut manifest = Manifest::default();
-        manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        manifest.set(ActionId::from("actions/setup-node"), Specifier::parse("^3"));
-
-        store.save(&manifest).unwrap();
-
-        let loaded = parse(file.path()).unwrap();
-        assert_eq!(
-            loaded.value.get(&ActionId::from("actions/checkout")),
-            Some(&Specifier::parse("^4"))
-        );
-        assert_eq!(
-            loaded.value.get(&ActionId::from("actions/setup-node")),
-            Some(&Specifier::parse("^3"))
-        );
+    if !manifest.groups().is_empty() {
+        let mut groups = toml_edit::Table::new();
+        for (name, members) in manifest.groups() {
+            let arr: toml_edit::Array = members
+                .iter()
+                .map(|id| manifest.aliases().spelling(id))
+                .collect();
+            groups.insert(name, toml_edit::value(arr));
+        }
+        doc.insert("groups", toml_edit::Item::Table(groups));
     }
 
-    #[test]
-    fn file_manifest_load_existing_toml() {
-        // v1 format (no [gx] section) — values like "v4" get converted via from_v1
-        let content = r#"
-[actions]
-"actions/checkout" = "v4"
-"actions/setup-node" = "v4"
-"#;
-        let mut file = NamedTempFile::new().unwrap();
-        file.write_all(content.as_bytes()).unwrap();
-
-        let loaded = parse(file.path()).unwrap();
-        assert_eq!(
-            loaded.value.get(&ActionId::from("actions/checkout")),
-            Some(&Specifier::from_v1("v4"))
-        );
+    if !manifest.aliases().is_empty() {
+        let mut aliases = toml_edit::Table::new();
+        for (name, id) in manifest.aliases().iter() {
+            aliases.insert(name, toml_edit::value(id.as_str()));
+        }
+        doc.insert("aliases", toml_edit::Item::Table(aliases));
     }
 
-    #[test]
-    fn file_manifest_save_sorts_actions_alphabetically() {
-        let file = NamedTempFile::new().unwrap();
-        let store = Store::new(file.path());
-
-        let mut manifest = Manifest::default();
-        manifest.set(
-            ActionId::from("docker/build-push-action"),
-            Specifier::parse("^5"),
-        );
-        manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        manifest.set(
-            ActionId::from("actions-rust-lang/rustfmt"),
-            Specifier::parse("^1"),
-        );
-
-        store.save(&manifest).unwrap();
-
-        let content = fs::read_to_string(file.path()).unwrap();
-        let action_lines: Vec<&str> = content
-            .lines()
-            .filter(|l| l.trim().starts_with('"') && l.contains(" = ") && !l.contains('['))
-            .collect();
+    doc
+}
 
-        let mut sorted = action_lines.clone();
-        sorted.sort_unstable();
-        assert_eq!(action_lines, sorted);
-        assert!(action_lines[0].contains("actions-rust-lang/rustfmt"));
-        assert!(action_lines[1].contains("actions/checkout"));
-        assert!(action_lines[2].contains("docker/build-push-action"));
+/// The `[actions]` entry of `spec`: its bare specifier, or a table when the action has
+/// settings or notes besides it.
+fn action_entry(manifest: &Manifest, spec: &ActionSpec) -> toml_edit::Item {
+    let held = manifest.is_held(&spec.id);
+    let prerelease = manifest.allows_prerelease(&spec.id);
+    let signed = manifest.requires_signed(&spec.id);
+    let annotation = manifest.annotation(&spec.id);
+    if !held && !prerelease && !signed && annotation.is_none() {
+        return toml_edit::value(spec.specifier.as_str());
+    }
+    let mut inline = toml_edit::InlineTable::new();
+    inline.insert("version", spec.specifier.as_str().into());
+    if held {
+        inline.insert("hold", true.into());
+    }
+    if prerelease {
+        inline.insert("allow_prerelease", true.into());
+    }
+    if signed {
+        inline.insert("require_signed", true.into());
     }
+    if let Some(Annotation { reason, owner }) = annotation {
+        for (key, note) in [("reason", reason), ("owner", owner)] {
+            if let Some(text) = note {
+                inline.insert(key, text.as_str().into());
+            }
+        }
+    }
+    toml_edit::value(inline)
+}
 
-    #[test]
-    fn save_no_gx_section() {
-        let file = NamedTempFile::new().unwrap();
-        let store = Store::new(file.path());
-
-        let mut manifest = Manifest::default();
-        manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        store.save(&manifest).unwrap();
-
-        let content = fs::read_to_string(file.path()).unwrap();
-        assert!(
-            !content.contains("[gx]"),
-            "Saved file must NOT contain [gx] section, got:\n{content}"
-        );
-        assert!(
-            !content.contains("min_version"),
-            "Saved file must NOT contain min_version, got:\n{content}"
-        );
-        assert!(
-            content.contains("[actions]"),
-            "Saved file must contain [actions] section, got:\n{content}"
-        );
+/// The TOML value an override's `step` is written as: a string for a step name, an
+/// integer for an index.
+pub(super) fn step_value(step: &StepKey) -> toml_edit::Value {
+    match step {
+        StepKey::Index(index) => i64::from(*index).into(),
+        StepKey::Name(name) => name.as_str().into(),
     }
+}
 
-    #[test]
-    fn build_manifest_document_with_overrides() {
-        let mut manifest = Manifest::default();
-        manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
-        manifest.add_override(
-            ActionId::from("actions/checkout"),
-            ActionOverride {
-                workflow: WorkflowPath::new(".github/workflows/ci.yml"),
-                job: None,
-                step: None,
-                version: Specifier::parse("^3"),
-            },
-        );
-
-        let output = build_manifest_document(&manifest).to_string();
-
-        assert!(output.contains("[actions]"));
-        assert!(output.contains("[actions.overrides]"));
-        assert!(output.contains("\"actions/checkout\" = \"^4\""));
-        assert!(!output.contains("[gx]"));
+/// The step an override entry's `step` value refers to, if it is a valid one.
+pub(super) fn step_key(value: &toml_edit::Value) -> Option<StepKey> {
+    if let Some(index) = value.as_integer() {
+        return StepIndex::try_from(index).ok().map(StepKey::Index);
     }
+    value.as_str().map(|name| StepKey::Name(name.to_owned()))
 }
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    clippy::indexing_slicing,
+    reason = "tests use unwrap, indexing, and other patterns freely"
+)]
+#[path = "convert_tests.rs"]
+mod tests;
@@ -0,0 +1,329 @@
+use super::{Manifest, build_manifest_document};
+use crate::domain::action::identity::ActionId;
+use crate::domain::action::specifier::Specifier;
+use crate::domain::manifest::overrides::ActionOverride;
+use crate::domain::workflow_actions::{JobId, StepKey, WorkflowPath};
+use crate::infra::manifest::{Store, parse};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write as _;
+use tempfile::NamedTempFile;
+
+#[test]
+fn file_manifest_save_and_load_roundtrip() {
+    let file = NamedTempFile::new().unwrap();
+    let store = Store::new(file.path());
+
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    manifest.set(ActionId::from("actions/setup-node"), Specifier::parse("^3"));
+
+    store.save(&manifest).unwrap();
+
+    let loaded = parse(file.path()).unwrap();
+    assert_eq!(
+        loaded.value.get(&ActionId::from("actions/checkout")),
+        Some(&Specifier::parse("^4"))
+    );
+    assert_eq!(
+        loaded.value.get(&ActionId::from("actions/setup-node")),
+        Some(&Specifier::parse("^3"))
+    );
+}
+
+#[test]
+fn file_manifest_load_existing_toml() {
+    // v1 format (no [gx] section) — values like "v4" get converted via from_v1
+    let content = r#"
+[actions]
+"actions/checkout" = "v4"
+"actions/setup-node" = "v4"
+"#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let loaded = parse(file.path()).unwrap();
+    assert_eq!(
+        loaded.value.get(&ActionId::from("actions/checkout")),
+        Some(&Specifier::from_v1("v4"))
+    );
+}
+
+#[test]
+fn file_manifest_save_sorts_actions_alphabetically() {
+    let file = NamedTempFile::new().unwrap();
+    let store = Store::new(file.path());
+
+    let mut manifest = Manifest::default();
+    manifest.set(
+        ActionId::from("docker/build-push-action"),
+        Specifier::parse("^5"),
+    );
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    manifest.set(
+        ActionId::from("actions-rust-lang/rustfmt"),
+        Specifier::parse("^1"),
+    );
+
+    store.save(&manifest).unwrap();
+
+    let content = fs::read_to_string(file.path()).unwrap();
+    let action_lines: Vec<&str> = content
+        .lines()
+        .filter(|l| l.trim().starts_with('"') && l.contains(" = ") && !l.contains('['))
+        .collect();
+
+    let mut sorted = action_lines.clone();
+    sorted.sort_unstable();
+    assert_eq!(action_lines, sorted);
+    assert!(action_lines[0].contains("actions-rust-lang/rustfmt"));
+    assert!(action_lines[1].contains("actions/checkout"));
+    assert!(action_lines[2].contains("docker/build-push-action"));
+}
+
+#[test]
+fn save_no_gx_section() {
+    let file = NamedTempFile::new().unwrap();
+    let store = Store::new(file.path());
+
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    store.save(&manifest).unwrap();
+
+    let content = fs::read_to_string(file.path()).unwrap();
+    assert!(
+        !content.contains("[gx]"),
+        "Saved file must NOT contain [gx] section, got:\n{content}"
+    );
+    assert!(
+        !content.contains("min_version"),
+        "Saved file must NOT contain min_version, got:\n{content}"
+    );
+    assert!(
+        content.contains("[actions]"),
+        "Saved file must contain [actions] section, got:\n{content}"
+    );
+}
+
+#[test]
+fn build_manifest_document_with_overrides() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    manifest.add_override(
+        ActionId::from("actions/checkout"),
+        ActionOverride {
+            workflow: WorkflowPath::new(".github/workflows/ci.yml"),
+            job: None,
+            step: None,
+            version: Specifier::parse("^3"),
+        },
+    );
+
+    let output = build_manifest_document(&manifest).to_string();
+
+    assert!(output.contains("[actions]"));
+    assert!(output.contains("[actions.overrides]"));
+    assert!(output.contains("\"actions/checkout\" = \"^4\""));
+    assert!(!output.contains("[gx]"));
+}
+
+#[test]
+fn held_action_loads_and_saves_as_table() {
+    let content = r#"
+[actions]
+"actions/checkout" = { version = "^3", hold = true }
+"actions/setup-node" = "^4"
+"#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let loaded = parse(file.path()).unwrap();
+    let checkout = ActionId::from("actions/checkout");
+    assert!(!loaded.migrated);
+    assert_eq!(loaded.value.get(&checkout), Some(&Specifier::parse("^3")));
+    assert!(loaded.value.is_held(&checkout));
+    assert!(!loaded.value.is_held(&ActionId::from("actions/setup-node")));
+
+    let output = build_manifest_document(&loaded.value).to_string();
+    assert!(
+        output.contains("\"actions/checkout\" = { version = \"^3\", hold = true }"),
+        "got:\n{output}"
+    );
+    assert!(output.contains("\"actions/setup-node\" = \"^4\""));
+}
+
+#[test]
+fn prerelease_action_loads_and_saves_as_table() {
+    let content = r#"
+[actions]
+"actions/checkout" = { version = "^5", allow_prerelease = true }
+"actions/setup-node" = "^4"
+"#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let loaded = parse(file.path()).unwrap();
+    let checkout = ActionId::from("actions/checkout");
+    assert!(loaded.value.allows_prerelease(&checkout));
+    assert!(!loaded.value.is_held(&checkout));
+    assert!(
+        !loaded
+            .value
+            .allows_prerelease(&ActionId::from("actions/setup-node"))
+    );
+
+    let output = build_manifest_document(&loaded.value).to_string();
+    assert!(
+        output.contains("\"actions/checkout\" = { version = \"^5\", allow_prerelease = true }"),
+        "got:\n{output}"
+    );
+}
+
+#[test]
+fn signed_action_loads_and_saves_as_table() {
+    let content = r#"
+[actions]
+"actions/checkout" = { version = "^4", require_signed = true }
+"#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let loaded = parse(file.path()).unwrap();
+    assert!(
+        loaded
+            .value
+            .requires_signed(&ActionId::from("actions/checkout"))
+    );
+
+    let output = build_manifest_document(&loaded.value).to_string();
+    assert!(
+        output.contains("\"actions/checkout\" = { version = \"^4\", require_signed = true }"),
+        "got:\n{output}"
+    );
+}
+
+#[test]
+fn override_with_an_invalid_job_pattern_is_rejected() {
+    let content = r#"
+[actions]
+"actions/checkout" = "v4"
+
+[actions.overrides]
+"actions/checkout" = [{ workflow = "*-windows.yml", job = "test-[", version = "v3" }]
+"#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let err = parse(file.path()).unwrap_err();
+    assert!(
+        err.to_string().contains("invalid pattern \"test-[\""),
+        "{err}"
+    );
+}
+
+#[test]
+fn aliases_expand_on_load_and_are_kept_on_save() {
+    let content = r#"
+[actions]
+"actions/checkout" = "^4"
+
+[actions.overrides]
+checkout = [{ workflow = ".github/workflows/ci.yml", version = "^3" }]
+
+[groups]
+core = ["checkout"]
+
+[aliases]
+checkout = "actions/checkout"
+"#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+    let checkout = ActionId::from("actions/checkout");
+
+    let manifest = parse(file.path()).unwrap().value;
+
+    assert_eq!(manifest.overrides_for(&checkout).len(), 1);
+    assert_eq!(manifest.group("core"), Some([checkout].as_slice()));
+    let written = build_manifest_document(&manifest).to_string();
+    assert!(written.contains("checkout = [{"), "{written}");
+    assert!(written.contains("core = [\"checkout\"]"), "{written}");
+    assert!(
+        written.contains("[aliases]\ncheckout = \"actions/checkout\""),
+        "{written}"
+    );
+}
+
+#[test]
+fn manifest_document_does_not_depend_on_insertion_order() {
+    let ids = ["docker/login-action", "actions/checkout", "actions/cache"];
+    let overrides = [
+        (
+            "ci.yml",
+            Some("test"),
+            Some(StepKey::Name("Check out".to_owned())),
+        ),
+        ("ci.yml", None, None),
+        ("deploy.yml", Some("release"), None),
+        ("ci.yml", Some("lint"), None),
+    ]
+    .map(|(workflow, job, step)| ActionOverride {
+        workflow: WorkflowPath::new(format!(".github/workflows/{workflow}")),
+        job: job.map(JobId::from),
+        step,
+        version: Specifier::parse("^3"),
+    });
+    let build = |reversed: bool| {
+        // Each HashMap hashes with its own random seed, so every manifest iterates its
+        // actions in a different order
+        let actions: HashMap<_, _> = ids.iter().map(|id| (*id, Specifier::parse("^4"))).collect();
+        let mut manifest = Manifest::default();
+        for (id, specifier) in actions {
+            manifest.set(ActionId::from(id), specifier);
+        }
+        let mut ordered = overrides.to_vec();
+        if reversed {
+            ordered.reverse();
+        }
+        for ovr in ordered {
+            manifest.add_override(ActionId::from("actions/checkout"), ovr);
+        }
+        build_manifest_document(&manifest).to_string()
+    };
+
+    let written = build(false);
+
+    assert_eq!(written, build(true));
+    let workflows: Vec<usize> = [
+        "workflow = \".github/workflows/ci.yml\", version",
+        "job = \"lint\"",
+        "job = \"test\"",
+        "deploy.yml",
+    ]
+    .iter()
+    .map(|needle| written.find(needle).unwrap())
+    .collect();
+    assert!(workflows.is_sorted(), "{written}");
+}
+
+#[test]
+fn reason_and_owner_of_an_action_round_trip() {
+    let content = r#"
+[actions]
+"actions/cache" = { version = "^3", hold = true, reason = "v4 breaks cache keys", owner = "@platform-team" }
+"actions/checkout" = "^4"
+"#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+    let cache = ActionId::from("actions/cache");
+
+    let manifest = parse(file.path()).unwrap().value;
+
+    assert_eq!(
+        manifest.hold_note(&cache).as_deref(),
+        Some("held in gx.toml: v4 breaks cache keys")
+    );
+    assert_eq!(
+        build_manifest_document(&manifest).to_string(),
+        content.trim_start()
+    );
+}
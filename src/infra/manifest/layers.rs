@@ -0,0 +1,289 @@
+use super::Error;
+use crate::lint::RuleName;
+use std::env;
+use std::path::PathBuf;
+use toml::{Table, Value};
+
+/// The sections the user config gives defaults for. The others describe one repository.
+const SECTIONS: [&str; 3] = ["registry", "lint", "files"];
+
+/// The keys of `[files]` that locate one repository's files, which only it can set.
+const REPOSITORY_FILES: [&str; 2] = ["manifest", "lock"];
+
+/// The user's gx config: `$XDG_CONFIG_HOME/gx/config.toml`, or `~/.config/gx/config.toml`.
+#[must_use]
+pub fn config_path() -> Option<PathBuf> {
+    let root = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(root.join("gx").join("config.toml"))
+}
+
+/// The part of a user config that applies to every repository: its `[registry]`, `[lint]`,
+/// and `[files]` sections, without the `manifest` and `lock` keys of `[files]`.
+pub(super) fn defaults(mut user: Table) -> Table {
+    user.retain(|key, _| SECTIONS.contains(&key));
+    if let Some(Value::Table(files)) = user.get_mut("files") {
+        files.retain(|key, _| !REPOSITORY_FILES.contains(&key));
+    }
+    user
+}
+
+/// `repo` layered over `defaults`: tables are merged key by key, and any other value set
+/// in `repo` replaces the default.
+pub(super) fn merge(mut defaults: Table, repo: Table) -> Table {
+    for (key, theirs) in repo {
+        let merged = match (defaults.remove(&key), theirs) {
+            (Some(Value::Table(ours)), Value::Table(table)) => Value::Table(merge(ours, table)),
+            (_, value) => value,
+        };
+        defaults.insert(key, merged);
+    }
+    defaults
+}
+
+/// What a variable's value is read as.
+#[derive(Clone, Copy)]
+enum Kind {
+    /// Any string.
+    Text,
+    /// A whole number.
+    Number,
+    /// A boolean, as [`flag`] reads it.
+    Flag,
+    /// One of the given words, in any case.
+    OneOf(&'static [&'static str]),
+}
+
+/// The `GX_` variables for keys of gx.toml, with the section and key each one sets.
+const KEYS: [(&str, &[&str], &str, Kind); 7] = [
+    ("GX_REGISTRY_API_URL", &["registry"], "api_url", Kind::Text),
+    (
+        "GX_REGISTRY_RESOLVER",
+        &["registry"],
+        "resolver",
+        Kind::OneOf(&["auto", "api", "git"]),
+    ),
+    (
+        "GX_REGISTRY_CONCURRENCY",
+        &["registry"],
+        "concurrency",
+        Kind::Number,
+    ),
+    (
+        "GX_REGISTRY_CA_BUNDLE",
+        &["registry"],
+        "ca_bundle",
+        Kind::Text,
+    ),
+    (
+        "GX_REGISTRY_TIMEOUT_SECS",
+        &["registry"],
+        "timeout_secs",
+        Kind::Number,
+    ),
+    (
+        "GX_REGISTRY_MAX_REQUESTS",
+        &["registry"],
+        "max_requests",
+        Kind::Number,
+    ),
+    ("GX_FILES_BACKUP", &["files"], "backup", Kind::Flag),
+];
+
+/// The tables `GX_LINT_` variables set a rule of.
+const LINT_RULES: &[&str] = &["lint", "rules"];
+
+/// The levels a `GX_LINT_` variable can set.
+const LEVELS: [&str; 3] = ["error", "warn", "off"];
+
+/// The prefix of the variables setting the level of a lint rule, as in `GX_LINT_UNPINNED`.
+const LINT_PREFIX: &str = "GX_LINT_";
+
+/// Whether a variable such as `GX_OFFLINE` turns its setting on: `1`, `true`, `yes`, or
+/// `on`, in any case. `None` when it is none of these nor their opposites.
+#[must_use]
+pub fn flag(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" | "" => Some(false),
+        _ => None,
+    }
+}
+
+/// The settings of gx.toml that the `GX_` variables among `vars` set, as a table to
+/// layer over the files. Other variables are ignored.
+///
+/// # Errors
+///
+/// Returns [`Error::Environment`] naming a variable whose value is not of its key's type,
+/// or a `GX_LINT_` variable that names no rule or no level.
+pub(super) fn overrides(vars: impl IntoIterator<Item = (String, String)>) -> Result<Table, Error> {
+    let mut table = Table::new();
+    for (name, value) in vars {
+        let (sections, key, setting) = if let Some(rule) = name.strip_prefix(LINT_PREFIX) {
+            lint_rule(&name, rule, &value)?
+        } else if let Some(&(_, sections, key, kind)) =
+            KEYS.iter().find(|(variable, ..)| *variable == name)
+        {
+            (sections, key.to_owned(), read(&name, &value, kind)?)
+        } else {
+            continue;
+        };
+        let mut layer = Table::from_iter([(key, setting)]);
+        for section in sections.iter().rev() {
+            layer = Table::from_iter([((*section).to_owned(), Value::Table(layer))]);
+        }
+        table = merge(table, layer);
+    }
+    Ok(table)
+}
+
+/// The `[lint.rules]` entry of a `GX_LINT_` variable: the rule its name ends with, in
+/// uppercase with `_` for `-`, at the level of its value.
+fn lint_rule(
+    name: &str,
+    rule: &str,
+    value: &str,
+) -> Result<(&'static [&'static str], String, Value), Error> {
+    let rule_name = rule.to_ascii_lowercase().replace('_', "-");
+    if rule_name.parse::<RuleName>().is_err() {
+        return Err(Error::Environment(format!("{name} names no lint rule")));
+    }
+    let mut setting = Table::new();
+    setting.insert("level".to_owned(), read(name, value, Kind::OneOf(&LEVELS))?);
+    Ok((LINT_RULES, rule_name, Value::Table(setting)))
+}
+
+/// The value of the variable `name` as a TOML value of `kind`.
+fn read(name: &str, value: &str, kind: Kind) -> Result<Value, Error> {
+    match kind {
+        Kind::Text => Ok(Value::String(value.to_owned())),
+        Kind::Number => value
+            .parse()
+            .map(Value::Integer)
+            .map_err(|_| Error::Environment(format!("{name} must be a number, not \"{value}\""))),
+        Kind::Flag => flag(value)
+            .map(Value::Boolean)
+            .ok_or_else(|| Error::Environment(format!("{name} must be 1 or 0, not \"{value}\""))),
+        Kind::OneOf(choices) => choices
+            .iter()
+            .find(|choice| choice.eq_ignore_ascii_case(value))
+            .map(|choice| Value::String((*choice).to_owned()))
+            .ok_or_else(|| {
+                Error::Environment(format!(
+                    "{name} must be one of {}, not \"{value}\"",
+                    choices.join(", ")
+                ))
+            }),
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    clippy::indexing_slicing,
+    reason = "tests use unwrap and indexing freely"
+)]
+mod tests {
+    use super::{defaults, flag, merge, overrides};
+    use crate::config::Level;
+    use crate::infra::manifest::convert::ManifestData;
+    use crate::lint::RuleName;
+    use toml::Table;
+
+    #[test]
+    fn repository_settings_win_over_the_user_defaults_they_share() {
+        let user: Table = toml::from_str(
+            r#"
+            [actions]
+            "actions/checkout" = "^4"
+
+            [registry]
+            api_url = "https://ghe.example.com/api/v3"
+
+            [lint.rules]
+            unpinned = { level = "off" }
+            stale-comment = { level = "error" }
+
+            [files]
+            backup = true
+            lock = "elsewhere/gx.lock"
+            "#,
+        )
+        .unwrap();
+        let repo: Table = toml::from_str(
+            r#"
+            [lint.rules]
+            unpinned = { level = "error" }
+            "#,
+        )
+        .unwrap();
+
+        let data: ManifestData = toml::Value::Table(merge(defaults(user), repo))
+            .try_into()
+            .unwrap();
+
+        assert!(
+            data.actions.versions.is_empty(),
+            "actions are per repository"
+        );
+        assert_eq!(
+            data.registry.api_url.as_deref(),
+            Some("https://ghe.example.com/api/v3")
+        );
+        assert_eq!(data.lint.rules[&RuleName::Unpinned].level, Level::Error);
+        assert_eq!(data.lint.rules[&RuleName::StaleComment].level, Level::Error);
+        assert!(data.files.backup);
+        assert_eq!(data.files.lock, None, "only a repository locates its lock");
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| ((*name).to_owned(), (*value).to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn gx_variables_become_the_keys_they_name() {
+        let table = overrides(vars(&[
+            ("GX_REGISTRY_API_URL", "https://ghes.example.com"),
+            ("GX_REGISTRY_CONCURRENCY", "2"),
+            ("GX_REGISTRY_RESOLVER", "Git"),
+            ("GX_LINT_STALE_COMMENT", "OFF"),
+            ("GX_FILES_BACKUP", "true"),
+            ("GX_OFFLINE", "1"),
+            ("PATH", "/usr/bin"),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            table["registry"]["api_url"].as_str(),
+            Some("https://ghes.example.com")
+        );
+        assert_eq!(table["registry"]["concurrency"].as_integer(), Some(2));
+        assert_eq!(table["registry"]["resolver"].as_str(), Some("git"));
+        assert_eq!(
+            table["lint"]["rules"]["stale-comment"]["level"].as_str(),
+            Some("off")
+        );
+        assert_eq!(table["files"]["backup"].as_bool(), Some(true));
+        assert_eq!(table.len(), 3, "{table:?}");
+        assert_eq!(flag("Yes"), Some(true));
+    }
+
+    #[test]
+    fn malformed_gx_variables_are_named_in_the_error() {
+        for (name, value) in [
+            ("GX_REGISTRY_TIMEOUT_SECS", "soon"),
+            ("GX_LINT_UNPINNED", "loud"),
+            ("GX_LINT_NO_SUCH_RULE", "off"),
+            ("GX_FILES_BACKUP", "maybe"),
+        ] {
+            let error = overrides(vars(&[(name, value)])).unwrap_err();
+            assert!(error.to_string().contains(name), "{error}");
+        }
+    }
+}
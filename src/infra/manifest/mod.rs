@@ -2,8 +2,16 @@
 
 /// TOML serialization, deserialization, and document building for manifests.
 mod convert;
+/// The user-level config layered under each manifest, and `GX_` environment variables
+/// layered over it.
+mod layers;
 /// Manifest file parsing, creation, and store.
 mod parse;
 pub mod patch;
 
-pub use parse::{Error, MANIFEST_FILE_NAME, Store, create, parse, parse_lint_config};
+pub use layers::flag as env_flag;
+pub use parse::{
+    Document, Error, MANIFEST_FILE_NAME, Store, combined_hash, create, hash, hash_file, parse,
+    parse_audit_config, parse_files_config, parse_licenses_config, parse_lint_config,
+    parse_projects_config, parse_registry_config, parse_workflows_config, unknown_keys,
+};
@@ -1,11 +1,19 @@
-use super::convert::{ManifestData, build_manifest_document, manifest_from_data};
-use crate::config::Lint;
+use super::convert::{ManifestData, TomlAction, build_manifest_document, manifest_from_data};
+use super::layers;
+use super::patch::merge_manifest;
+use crate::config::schema::MANIFEST;
+use crate::config::{Audit, Files, Licenses, Lint, Projects, Registry, Workflows};
 use crate::domain::Parsed;
 use crate::domain::diff::ManifestDiff;
 use crate::domain::manifest::Manifest;
+use crate::infra::atomic;
+use serde::de::DeserializeOwned;
+use sha1::{Digest as _, Sha1};
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
+use toml::{Table, Value};
+use toml_edit::DocumentMut;
 
 pub const MANIFEST_FILE_NAME: &str = "gx.toml";
 
@@ -38,6 +46,9 @@ pub enum Error {
 
     #[error("invalid manifest: {0}")]
     Validation(String),
+
+    #[error("invalid environment variable: {0}")]
+    Environment(String),
 }
 
 // ---- Store ----
@@ -62,14 +73,25 @@ impl Store {
     reason = "constructor and methods are in separate impl blocks for readability"
 )]
 impl Store {
-    /// Save the given `Manifest` to this file.
+    /// Save the given `Manifest` to this file. An existing file is edited in place, so
+    /// its comments, key order, and other sections are kept.
     ///
     /// # Errors
     ///
     /// Returns [`Error::Write`] if the file cannot be written.
     pub fn save(&self, manifest: &Manifest) -> Result<(), Error> {
-        let doc = build_manifest_document(manifest);
-        fs::write(&self.path, doc.to_string()).map_err(|source| Error::Write {
+        let fresh = build_manifest_document(manifest);
+        let doc = match fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| content.parse::<DocumentMut>().ok())
+        {
+            Some(mut doc) if !doc.is_empty() => {
+                merge_manifest(&mut doc, &fresh);
+                doc
+            }
+            _ => fresh,
+        };
+        atomic::write(&self.path, &doc.to_string()).map_err(|source| Error::Write {
             path: self.path.clone(),
             source,
         })?;
@@ -120,12 +142,17 @@ pub fn parse(path: &Path) -> Result<Parsed<Manifest>, Error> {
     } else {
         // No [gx] section — could be v1 (old "v4" style) or current format ("^4" style)
         // Detect v1 by checking if any value looks like v1 format
-        let is_v1 = data.actions.versions.values().any(|v| {
-            v.starts_with('v')
-                && v.get(1..)
-                    .and_then(|s| s.chars().next())
-                    .is_some_and(|c| c.is_ascii_digit())
-        });
+        let is_v1 = data
+            .actions
+            .versions
+            .values()
+            .map(TomlAction::version)
+            .any(|v| {
+                v.starts_with('v')
+                    && v.get(1..)
+                        .and_then(|s| s.chars().next())
+                        .is_some_and(|c| c.is_ascii_digit())
+            });
 
         let manifest = manifest_from_data(data, path, !is_v1)?;
         Ok(Parsed {
@@ -135,15 +162,222 @@ pub fn parse(path: &Path) -> Result<Parsed<Manifest>, Error> {
     }
 }
 
-/// Load lint configuration from a manifest file. Returns `Lint::default()` if the file does not exist or has no `[lint]` section.
+/// Hash of `manifest` for gx.lock: the hex SHA-1 of the document gx would write for it, so
+/// comments, formatting, and key order do not change it.
+#[must_use]
+pub fn hash(manifest: &Manifest) -> String {
+    let canonical = build_manifest_document(manifest).to_string();
+    format!("{:x}", Sha1::digest(canonical.as_bytes()))
+}
+
+/// One hash for a lock several manifests share, from the [`hash`] of each: the hex SHA-1
+/// of them sorted, so it does not depend on the order of the manifests.
+#[must_use]
+pub fn combined_hash(hashes: &[String]) -> String {
+    let mut sorted: Vec<&str> = hashes.iter().map(String::as_str).collect();
+    sorted.sort_unstable();
+    format!("{:x}", Sha1::digest(sorted.join("\n").as_bytes()))
+}
+
+/// [`hash`] of the manifest file at `path`, as it is on disk now.
 ///
 /// # Errors
 ///
-/// Returns [`Error::Read`] if the file cannot be read.
-/// Returns [`Error::Parse`] if the TOML is invalid.
-pub fn parse_lint_config(path: &Path) -> Result<Lint, Error> {
-    if !path.exists() {
+/// Returns the errors of [`parse`].
+pub fn hash_file(path: &Path) -> Result<String, Error> {
+    Ok(hash(&parse(path)?.value))
+}
+
+/// A manifest file read once for the settings in it: as written, and layered over the user
+/// config with the `GX_` environment variables over both, which the `parse_*_config`
+/// functions take their section from.
+#[derive(Debug, Default)]
+pub struct Document {
+    /// The file as written, or `None` if it does not exist.
+    written: Option<Table>,
+    /// The layered settings, or `None` if there is no file, user config, or variable.
+    data: Option<ManifestData>,
+}
+
+impl Document {
+    /// Read the manifest file at `path`, with the user config and `GX_` variables.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Read`] if a file cannot be read.
+    /// Returns [`Error::Parse`] if the TOML is invalid.
+    /// Returns [`Error::Environment`] if a `GX_` variable is invalid.
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let written = read::<Table>(path)?;
+        let vars = std::env::vars_os().filter_map(|(name, value)| {
+            Some((name.into_string().ok()?, value.into_string().ok()?))
+        });
+        let data = read_layered(
+            path,
+            written.as_ref(),
+            layers::config_path().as_deref(),
+            layers::overrides(vars)?,
+        )?;
+        Ok(Self { written, data })
+    }
+}
+
+/// The keys of the manifest `document` that gx does not know, as dotted paths such as
+/// `lint.rulse`. Empty if the file does not exist.
+#[must_use]
+pub fn unknown_keys(document: &Document) -> Vec<String> {
+    document
+        .written
+        .as_ref()
+        .map(|table| MANIFEST.unknown_keys(table))
+        .unwrap_or_default()
+}
+
+/// Load lint configuration from a manifest document. Returns `Lint::default()` if the file does not exist or has no `[lint]` section.
+///
+/// # Errors
+///
+/// Returns [`Error::Validation`] if a `[lint.paths]` key is not a valid glob pattern.
+pub fn parse_lint_config(document: &Document) -> Result<Lint, Error> {
+    let Some(data) = &document.data else {
         return Ok(Lint::default());
+    };
+
+    if let Some(bad) = data
+        .lint
+        .paths
+        .keys()
+        .find(|pattern| glob::Pattern::new(pattern).is_err())
+    {
+        return Err(Error::Validation(format!(
+            "invalid [lint.paths] pattern \"{bad}\""
+        )));
+    }
+
+    Ok(Lint {
+        rules: data.lint.rules.clone(),
+        paths: data.lint.paths.clone(),
+    })
+}
+
+/// Load the `[registry]` section from a manifest document. Returns `Registry::default()` if
+/// the file does not exist or has no `[registry]` section.
+#[must_use]
+pub fn parse_registry_config(document: &Document) -> Registry {
+    document
+        .data
+        .as_ref()
+        .map(|data| data.registry.clone())
+        .unwrap_or_default()
+}
+
+/// Load the `[files]` section from a manifest document. Returns `Files::default()` if the
+/// file does not exist or has no `[files]` section.
+#[must_use]
+pub fn parse_files_config(document: &Document) -> Files {
+    document
+        .data
+        .as_ref()
+        .map(|data| data.files.clone())
+        .unwrap_or_default()
+}
+
+/// Load the `[audit]` section from a manifest document. Returns `Audit::default()` if the
+/// file does not exist or has no `[audit]` section.
+#[must_use]
+pub fn parse_audit_config(document: &Document) -> Audit {
+    document
+        .data
+        .as_ref()
+        .map(|data| data.audit.clone())
+        .unwrap_or_default()
+}
+
+/// Load the `[licenses]` section from a manifest document. Returns `Licenses::default()`
+/// if the file does not exist or has no `[licenses]` section.
+#[must_use]
+pub fn parse_licenses_config(document: &Document) -> Licenses {
+    document
+        .data
+        .as_ref()
+        .map(|data| data.licenses.clone())
+        .unwrap_or_default()
+}
+
+/// Load the `[workflows]` section from a manifest document. Returns `Workflows::default()` if
+/// the file does not exist or has no `[workflows]` section.
+///
+/// # Errors
+///
+/// Returns [`Error::Validation`] if an `include` or `exclude` entry is not a valid glob pattern.
+pub fn parse_workflows_config(document: &Document) -> Result<Workflows, Error> {
+    let workflows = document
+        .data
+        .as_ref()
+        .map(|data| data.workflows.clone())
+        .unwrap_or_default();
+    if let Some(bad) = workflows.invalid_pattern() {
+        return Err(Error::Validation(format!(
+            "invalid [workflows] pattern \"{bad}\""
+        )));
+    }
+    Ok(workflows)
+}
+
+/// Load the `[projects]` section from a manifest document. Returns `Projects::default()` if
+/// the file does not exist or has no `[projects]` section.
+///
+/// # Errors
+///
+/// Returns [`Error::Validation`] if a `paths` entry is not a valid glob pattern.
+pub fn parse_projects_config(document: &Document) -> Result<Projects, Error> {
+    let projects = document
+        .data
+        .as_ref()
+        .map(|data| data.projects.clone())
+        .unwrap_or_default();
+    if let Some(bad) = projects.invalid_pattern() {
+        return Err(Error::Validation(format!(
+            "invalid [projects] path \"{bad}\""
+        )));
+    }
+    Ok(projects)
+}
+
+/// Deserialize `written`, the manifest file at `path`, over the settings of the user config
+/// at `user_path`, as [`user::merge`] layers them, and `overrides` over both.
+fn read_layered(
+    path: &Path,
+    written: Option<&Table>,
+    user_path: Option<&Path>,
+    overrides: Table,
+) -> Result<Option<ManifestData>, Error> {
+    let found = match user_path {
+        Some(config) => read::<Table>(config)?.map(|user| (config, user)),
+        None => None,
+    };
+    if found.is_none() && overrides.is_empty() {
+        return written
+            .map(|table| deserialize(table.clone(), path))
+            .transpose();
+    }
+    let mut defaults = Table::new();
+    if let Some((config, user)) = found {
+        defaults = layers::defaults(user);
+        deserialize::<ManifestData>(defaults.clone(), config)?;
+    }
+    let repo = written.cloned().unwrap_or_default();
+    deserialize(
+        layers::merge(layers::merge(defaults, repo), overrides),
+        path,
+    )
+    .map(Some)
+}
+
+/// Read and deserialize a TOML file, or `None` if it does not exist.
+fn read<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, Error> {
+    if !path.exists() {
+        return Ok(None);
     }
 
     let content = fs::read_to_string(path).map_err(|source| Error::Read {
@@ -151,14 +385,22 @@ pub fn parse_lint_config(path: &Path) -> Result<Lint, Error> {
         source,
     })?;
 
-    let data: ManifestData = toml::from_str(&content).map_err(|source| Error::Parse {
-        path: path.to_path_buf(),
-        source: Box::new(source),
-    })?;
+    toml::from_str(&content)
+        .map(Some)
+        .map_err(|source| Error::Parse {
+            path: path.to_path_buf(),
+            source: Box::new(source),
+        })
+}
 
-    Ok(Lint {
-        rules: data.lint.rules,
-    })
+/// Deserialize a TOML table read from the file at `path`.
+fn deserialize<T: DeserializeOwned>(table: Table, path: &Path) -> Result<T, Error> {
+    Value::Table(table)
+        .try_into()
+        .map_err(|source| Error::Parse {
+            path: path.to_path_buf(),
+            source: Box::new(source),
+        })
 }
 
 /// Create a new manifest file from a `ManifestDiff`.
@@ -180,7 +422,7 @@ pub fn create(path: &Path, diff: &ManifestDiff) -> Result<(), Error> {
     }
 
     let doc = build_manifest_document(&manifest);
-    fs::write(path, doc.to_string()).map_err(|source| Error::Write {
+    atomic::write(path, &doc.to_string()).map_err(|source| Error::Write {
         path: path.to_path_buf(),
         source,
     })?;
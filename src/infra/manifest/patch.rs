@@ -1,8 +1,9 @@
 use super::Error as ManifestError;
+use super::convert::{step_key, step_value};
 use crate::domain::action::identity::ActionId;
 use crate::domain::diff::ManifestDiff;
 use crate::domain::manifest::overrides::ActionOverride;
-use crate::domain::workflow_actions::StepIndex;
+use crate::infra::atomic;
 use std::fs;
 use std::path::Path;
 use toml_edit::DocumentMut;
@@ -47,16 +48,19 @@ pub fn apply_manifest_diff(path: &Path, diff: &ManifestDiff) -> Result<(), Manif
         actions.remove(id.as_str());
     }
 
-    // Add actions (sorted insertion for consistency)
+    // Add actions, keeping the file's own order unless it was already sorted
+    let sorted = is_sorted(actions);
     for (id, version) in &diff.added {
-        actions.insert(id.as_str(), toml_edit::value(version.as_str()));
+        set_action_version(actions, id, version.as_str());
     }
 
     // Update existing action versions
     for (id, version) in &diff.updated {
-        actions.insert(id.as_str(), toml_edit::value(version.as_str()));
+        set_action_version(actions, id, version.as_str());
+    }
+    if sorted {
+        actions.sort_values();
     }
-    actions.sort_values();
 
     // Handle override removals
     if !diff.overrides_removed.is_empty() {
@@ -68,7 +72,7 @@ pub fn apply_manifest_diff(path: &Path, diff: &ManifestDiff) -> Result<(), Manif
         apply_override_additions(actions, &diff.overrides_added)?;
     }
 
-    fs::write(path, doc.to_string()).map_err(|source| ManifestError::Write {
+    atomic::write(path, &doc.to_string()).map_err(|source| ManifestError::Write {
         path: path.to_path_buf(),
         source,
     })?;
@@ -76,11 +80,113 @@ pub fn apply_manifest_diff(path: &Path, diff: &ManifestDiff) -> Result<(), Manif
     Ok(())
 }
 
+/// Set an action's version, keeping the other keys of a `{ version = ..., hold = ... }` entry.
+fn set_action_version(actions: &mut toml_edit::Table, id: &ActionId, version: &str) {
+    if let Some(table) = actions
+        .get_mut(id.as_str())
+        .and_then(toml_edit::Item::as_inline_table_mut)
+    {
+        table.insert("version", version.into());
+    } else if let Some(value) = actions
+        .get_mut(id.as_str())
+        .and_then(toml_edit::Item::as_value_mut)
+    {
+        replace_value(value, version.into());
+    } else {
+        actions.insert(id.as_str(), toml_edit::value(version));
+    }
+}
+
+/// Replace `value` with `new`, keeping the whitespace and trailing comment around it.
+fn replace_value(value: &mut toml_edit::Value, new: toml_edit::Value) {
+    let decor = value.decor().clone();
+    *value = new;
+    *value.decor_mut() = decor;
+}
+
+/// Whether the key-value pairs of `table` are in the order `sort_values` would put them.
+fn is_sorted(table: &toml_edit::Table) -> bool {
+    let keys: Vec<&str> = table
+        .iter()
+        .filter(|(_, item)| item.is_value())
+        .map(|(key, _)| key)
+        .collect();
+    keys.is_sorted()
+}
+
+/// Write the `[actions]` and `[groups]` of `fresh` into `doc`, editing only the entries
+/// that differ so the comments, order, and formatting of the rest of `doc` are kept.
+pub(super) fn merge_manifest(doc: &mut DocumentMut, fresh: &DocumentMut) {
+    doc.remove("gx");
+    for name in ["actions", "groups"] {
+        match (
+            doc.get_mut(name).and_then(toml_edit::Item::as_table_mut),
+            fresh.get(name).and_then(toml_edit::Item::as_table),
+        ) {
+            (Some(existing), Some(table)) => reconcile(existing, table),
+            (_, Some(table)) => {
+                doc.insert(name, toml_edit::Item::Table(table.clone()));
+            }
+            (_, None) => {
+                doc.remove(name);
+            }
+        }
+    }
+}
+
+/// Make `existing` hold the entries of `fresh`. Entries that are equal keep their
+/// formatting, changed values keep their decor, and new keys are appended unless the
+/// table was sorted.
+fn reconcile(existing: &mut toml_edit::Table, fresh: &toml_edit::Table) {
+    let sorted = is_sorted(existing);
+    let stale: Vec<String> = existing
+        .iter()
+        .map(|(key, _)| key)
+        .filter(|key| !fresh.contains_key(key))
+        .map(ToOwned::to_owned)
+        .collect();
+    for key in &stale {
+        existing.remove(key);
+    }
+    for (key, item) in fresh {
+        if let Some(table) = item.as_table()
+            && let Some(old) = existing
+                .get_mut(key)
+                .and_then(toml_edit::Item::as_table_mut)
+        {
+            reconcile(old, table);
+        } else if let Some(value) = item.as_value()
+            && let Some(old) = existing
+                .get_mut(key)
+                .and_then(toml_edit::Item::as_value_mut)
+        {
+            if !same_value(old, value) {
+                replace_value(old, value.clone());
+            }
+        } else {
+            existing.insert(key, item.clone());
+        }
+    }
+    if sorted {
+        existing.sort_values();
+    }
+}
+
+/// Whether two values hold the same data, however each is written.
+fn same_value(left: &toml_edit::Value, right: &toml_edit::Value) -> bool {
+    let data = |value: &toml_edit::Value| {
+        format!("value = {}", value.to_string().trim())
+            .parse::<toml::Table>()
+            .ok()
+    };
+    data(left).is_some_and(|table| Some(table) == data(right))
+}
+
 /// Check if an override entry matches a given `ActionOverride` by comparing workflow/job/step.
 fn override_entry_matches(
     workflow: Option<&str>,
     job: Option<&str>,
-    step: Option<i64>,
+    step: Option<&toml_edit::Value>,
     ovr: &ActionOverride,
 ) -> bool {
     workflow == Some(ovr.workflow.as_str())
@@ -89,7 +195,7 @@ fn override_entry_matches(
                 .job
                 .as_ref()
                 .map(crate::domain::workflow_actions::JobId::as_str)
-        && step.and_then(|s| StepIndex::try_from(s).ok()) == ovr.step
+        && step.and_then(step_key) == ovr.step
 }
 
 /// Remove matching overrides from the `[actions.overrides]` table.
@@ -146,7 +252,7 @@ fn collect_override_removal_indices(
         for (i, entry) in arr.iter().enumerate() {
             let wf = entry.get("workflow").and_then(toml_edit::Item::as_str);
             let job = entry.get("job").and_then(toml_edit::Item::as_str);
-            let step = entry.get("step").and_then(toml_edit::Item::as_integer);
+            let step = entry.get("step").and_then(toml_edit::Item::as_value);
             for ovr in removed_list {
                 if override_entry_matches(wf, job, step, ovr) {
                     indices.push(i);
@@ -159,7 +265,7 @@ fn collect_override_removal_indices(
             if let Some(tbl) = entry.as_inline_table() {
                 let wf = tbl.get("workflow").and_then(toml_edit::Value::as_str);
                 let job = tbl.get("job").and_then(toml_edit::Value::as_str);
-                let step = tbl.get("step").and_then(toml_edit::Value::as_integer);
+                let step = tbl.get("step");
                 for ovr in removed_list {
                     if override_entry_matches(wf, job, step, ovr) {
                         indices.push(i);
@@ -210,8 +316,8 @@ fn apply_override_additions(
         if let Some(job) = &ovr.job {
             inline.insert("job", toml_edit::Value::from(job.as_str()));
         }
-        if let Some(step) = ovr.step {
-            inline.insert("step", i64::from(step).into());
+        if let Some(step) = &ovr.step {
+            inline.insert("step", step_value(step));
         }
         inline.insert("version", ovr.version.as_str().into());
 
@@ -227,272 +333,5 @@ fn apply_override_additions(
     clippy::indexing_slicing,
     reason = "tests use unwrap, indexing, and other patterns freely"
 )]
-mod tests {
-    use super::apply_manifest_diff;
-    use crate::domain::action::identity::ActionId;
-    use crate::domain::action::specifier::Specifier;
-    use crate::domain::diff::ManifestDiff;
-    use crate::domain::manifest::overrides::ActionOverride;
-    use crate::domain::workflow_actions::{JobId, WorkflowPath};
-    use std::fs;
-    use std::io::Write as _;
-    use tempfile::NamedTempFile;
-
-    use crate::infra::manifest::parse;
-
-    #[test]
-    fn apply_empty_diff_does_not_modify_file() {
-        let content = "[actions]\n\"actions/checkout\" = \"v4\"\n";
-        let mut file = NamedTempFile::new().unwrap();
-        file.write_all(content.as_bytes()).unwrap();
-
-        let diff = ManifestDiff::default();
-        apply_manifest_diff(file.path(), &diff).unwrap();
-
-        let after = fs::read_to_string(file.path()).unwrap();
-        assert_eq!(content, after, "Empty diff must not modify file");
-    }
-
-    #[test]
-    fn apply_add_one_action_preserves_existing() {
-        let content = "[actions]\n\"actions/checkout\" = \"^4\"\n";
-        let mut file = NamedTempFile::new().unwrap();
-        file.write_all(content.as_bytes()).unwrap();
-
-        let diff = ManifestDiff {
-            added: vec![(ActionId::from("actions/setup-node"), Specifier::parse("^3"))],
-            ..Default::default()
-        };
-        apply_manifest_diff(file.path(), &diff).unwrap();
-
-        let after = fs::read_to_string(file.path()).unwrap();
-        assert!(
-            after.contains("\"actions/checkout\" = \"^4\""),
-            "Existing entry must be preserved, got:\n{after}"
-        );
-        assert!(
-            after.contains("\"actions/setup-node\" = \"^3\""),
-            "New entry must be added, got:\n{after}"
-        );
-
-        // Round-trip
-        let loaded = parse(file.path()).unwrap();
-        assert_eq!(
-            loaded.value.get(&ActionId::from("actions/checkout")),
-            Some(&Specifier::parse("^4"))
-        );
-        assert_eq!(
-            loaded.value.get(&ActionId::from("actions/setup-node")),
-            Some(&Specifier::parse("^3"))
-        );
-    }
-
-    #[test]
-    fn apply_remove_one_action() {
-        let content = "[actions]\n\"actions/checkout\" = \"v4\"\n\"actions/setup-node\" = \"v3\"\n";
-        let mut file = NamedTempFile::new().unwrap();
-        file.write_all(content.as_bytes()).unwrap();
-
-        let diff = ManifestDiff {
-            removed: vec![ActionId::from("actions/checkout")],
-            ..Default::default()
-        };
-        apply_manifest_diff(file.path(), &diff).unwrap();
-
-        let after = fs::read_to_string(file.path()).unwrap();
-        assert!(
-            !after.contains("actions/checkout"),
-            "Removed entry must be gone, got:\n{after}"
-        );
-        assert!(
-            after.contains("\"actions/setup-node\" = \"v3\""),
-            "Other entry must be preserved, got:\n{after}"
-        );
-    }
-
-    #[test]
-    fn apply_add_override_creates_section_if_missing() {
-        let content = "[actions]\n\"actions/checkout\" = \"^4\"\n";
-        let mut file = NamedTempFile::new().unwrap();
-        file.write_all(content.as_bytes()).unwrap();
-
-        let diff = ManifestDiff {
-            overrides_added: vec![(
-                ActionId::from("actions/checkout"),
-                ActionOverride {
-                    workflow: WorkflowPath::new(".github/workflows/deploy.yml"),
-                    job: None,
-                    step: None,
-                    version: Specifier::parse("^3"),
-                },
-            )],
-            ..Default::default()
-        };
-        apply_manifest_diff(file.path(), &diff).unwrap();
-
-        // Round-trip (v1 format since no [gx] section — "^4" parsed via from_v1 yields Ref("^4") but that's fine)
-        let loaded = parse(file.path()).unwrap();
-        let overrides = loaded
-            .value
-            .overrides_for(&ActionId::from("actions/checkout"));
-        assert_eq!(overrides.len(), 1);
-        assert_eq!(
-            overrides[0].workflow,
-            WorkflowPath::new(".github/workflows/deploy.yml")
-        );
-        assert_eq!(overrides[0].version.as_str(), "^3");
-    }
-
-    #[test]
-    fn apply_add_override_to_existing_section() {
-        let content = r#"[actions]
-"actions/checkout" = "v4"
-
-[actions.overrides]
-"actions/checkout" = [
-  { workflow = ".github/workflows/deploy.yml", version = "v3" },
-]
-"#;
-        let mut file = NamedTempFile::new().unwrap();
-        file.write_all(content.as_bytes()).unwrap();
-
-        let diff = ManifestDiff {
-            overrides_added: vec![(
-                ActionId::from("actions/checkout"),
-                ActionOverride {
-                    workflow: WorkflowPath::new(".github/workflows/ci.yml"),
-                    job: Some(JobId::from("legacy")),
-                    step: None,
-                    version: Specifier::parse("^2"),
-                },
-            )],
-            ..Default::default()
-        };
-        apply_manifest_diff(file.path(), &diff).unwrap();
-
-        let loaded = parse(file.path()).unwrap();
-        let overrides = loaded
-            .value
-            .overrides_for(&ActionId::from("actions/checkout"));
-        assert_eq!(overrides.len(), 2, "Should have 2 overrides now");
-    }
-
-    #[test]
-    fn apply_remove_all_overrides_removes_action_entry() {
-        let content = r#"[actions]
-"actions/checkout" = "v4"
-
-[actions.overrides]
-"actions/checkout" = [
-  { workflow = ".github/workflows/deploy.yml", version = "v3" },
-]
-"#;
-        let mut file = NamedTempFile::new().unwrap();
-        file.write_all(content.as_bytes()).unwrap();
-
-        let diff = ManifestDiff {
-            overrides_removed: vec![(
-                ActionId::from("actions/checkout"),
-                vec![ActionOverride {
-                    workflow: WorkflowPath::new(".github/workflows/deploy.yml"),
-                    job: None,
-                    step: None,
-                    version: Specifier::parse("^3"),
-                }],
-            )],
-            ..Default::default()
-        };
-        apply_manifest_diff(file.path(), &diff).unwrap();
-
-        let loaded = parse(file.path()).unwrap();
-        assert!(
-            loaded
-                .value
-                .overrides_for(&ActionId::from("actions/checkout"))
-                .is_empty()
-        );
-    }
-
-    #[test]
-    fn apply_remove_last_override_removes_section() {
-        let content = r#"[actions]
-"actions/checkout" = "v4"
-
-[actions.overrides]
-"actions/checkout" = [
-  { workflow = ".github/workflows/deploy.yml", version = "v3" },
-]
-"#;
-        let mut file = NamedTempFile::new().unwrap();
-        file.write_all(content.as_bytes()).unwrap();
-
-        let diff = ManifestDiff {
-            overrides_removed: vec![(
-                ActionId::from("actions/checkout"),
-                vec![ActionOverride {
-                    workflow: WorkflowPath::new(".github/workflows/deploy.yml"),
-                    job: None,
-                    step: None,
-                    version: Specifier::parse("^3"),
-                }],
-            )],
-            ..Default::default()
-        };
-        apply_manifest_diff(file.path(), &diff).unwrap();
-
-        let after = fs::read_to_string(file.path()).unwrap();
-        assert!(
-            !after.contains("overrides"),
-            "Overrides section must be removed when empty, got:\n{after}"
-        );
-    }
-
-    #[test]
-    fn apply_roundtrip_domain_state_matches() {
-        let content = r#"[actions]
-"actions/checkout" = "^4"
-"actions/setup-node" = "^3"
-"#;
-        let mut file = NamedTempFile::new().unwrap();
-        file.write_all(content.as_bytes()).unwrap();
-
-        let diff = ManifestDiff {
-            added: vec![(ActionId::from("actions/cache"), Specifier::parse("^3"))],
-            removed: vec![ActionId::from("actions/setup-node")],
-            overrides_added: vec![(
-                ActionId::from("actions/checkout"),
-                ActionOverride {
-                    workflow: WorkflowPath::new(".github/workflows/windows.yml"),
-                    job: None,
-                    step: None,
-                    version: Specifier::parse("^3"),
-                },
-            )],
-            ..Default::default()
-        };
-        apply_manifest_diff(file.path(), &diff).unwrap();
-
-        let loaded = parse(file.path()).unwrap();
-        assert!(
-            loaded
-                .value
-                .get(&ActionId::from("actions/checkout"))
-                .is_some()
-        );
-        assert!(loaded.value.get(&ActionId::from("actions/cache")).is_some());
-        assert!(
-            loaded
-                .value
-                .get(&ActionId::from("actions/setup-node"))
-                .is_none()
-        );
-        let overrides = loaded
-            .value
-            .overrides_for(&ActionId::from("actions/checkout"));
-        assert_eq!(overrides.len(), 1);
-        assert_eq!(
-            overrides[0].workflow,
-            WorkflowPath::new(".github/workflows/windows.yml")
-        );
-    }
-}
+#[path = "patch_tests.rs"]
+mod tests;
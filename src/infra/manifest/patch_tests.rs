@@ -0,0 +1,365 @@
+use super::apply_manifest_diff;
+use crate::domain::action::identity::ActionId;
+use crate::domain::action::specifier::Specifier;
+use crate::domain::diff::ManifestDiff;
+use crate::domain::manifest::overrides::ActionOverride;
+use crate::domain::workflow_actions::{JobId, WorkflowPath};
+use std::fs;
+use std::io::Write as _;
+use tempfile::NamedTempFile;
+
+use crate::infra::manifest::{Store, parse};
+
+#[test]
+fn apply_empty_diff_does_not_modify_file() {
+    let content = "[actions]\n\"actions/checkout\" = \"v4\"\n";
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let diff = ManifestDiff::default();
+    apply_manifest_diff(file.path(), &diff).unwrap();
+
+    let after = fs::read_to_string(file.path()).unwrap();
+    assert_eq!(content, after, "Empty diff must not modify file");
+}
+
+#[test]
+fn apply_update_keeps_hold_table() {
+    let content = "[actions]\n\"actions/checkout\" = { version = \"^3\", hold = true }\n";
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let diff = ManifestDiff {
+        updated: vec![(ActionId::from("actions/checkout"), Specifier::parse("^4"))],
+        ..Default::default()
+    };
+    apply_manifest_diff(file.path(), &diff).unwrap();
+
+    let after = fs::read_to_string(file.path()).unwrap();
+    assert!(
+        after.contains("\"actions/checkout\" = { version = \"^4\", hold = true }"),
+        "got:\n{after}"
+    );
+}
+
+#[test]
+fn apply_add_one_action_preserves_existing() {
+    let content = "[actions]\n\"actions/checkout\" = \"^4\"\n";
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let diff = ManifestDiff {
+        added: vec![(ActionId::from("actions/setup-node"), Specifier::parse("^3"))],
+        ..Default::default()
+    };
+    apply_manifest_diff(file.path(), &diff).unwrap();
+
+    let after = fs::read_to_string(file.path()).unwrap();
+    assert!(
+        after.contains("\"actions/checkout\" = \"^4\""),
+        "Existing entry must be preserved, got:\n{after}"
+    );
+    assert!(
+        after.contains("\"actions/setup-node\" = \"^3\""),
+        "New entry must be added, got:\n{after}"
+    );
+
+    // Round-trip
+    let loaded = parse(file.path()).unwrap();
+    assert_eq!(
+        loaded.value.get(&ActionId::from("actions/checkout")),
+        Some(&Specifier::parse("^4"))
+    );
+    assert_eq!(
+        loaded.value.get(&ActionId::from("actions/setup-node")),
+        Some(&Specifier::parse("^3"))
+    );
+}
+
+#[test]
+fn apply_remove_one_action() {
+    let content = "[actions]\n\"actions/checkout\" = \"v4\"\n\"actions/setup-node\" = \"v3\"\n";
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let diff = ManifestDiff {
+        removed: vec![ActionId::from("actions/checkout")],
+        ..Default::default()
+    };
+    apply_manifest_diff(file.path(), &diff).unwrap();
+
+    let after = fs::read_to_string(file.path()).unwrap();
+    assert!(
+        !after.contains("actions/checkout"),
+        "Removed entry must be gone, got:\n{after}"
+    );
+    assert!(
+        after.contains("\"actions/setup-node\" = \"v3\""),
+        "Other entry must be preserved, got:\n{after}"
+    );
+}
+
+#[test]
+fn apply_add_override_creates_section_if_missing() {
+    let content = "[actions]\n\"actions/checkout\" = \"^4\"\n";
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let diff = ManifestDiff {
+        overrides_added: vec![(
+            ActionId::from("actions/checkout"),
+            ActionOverride {
+                workflow: WorkflowPath::new(".github/workflows/deploy.yml"),
+                job: None,
+                step: None,
+                version: Specifier::parse("^3"),
+            },
+        )],
+        ..Default::default()
+    };
+    apply_manifest_diff(file.path(), &diff).unwrap();
+
+    // Round-trip (v1 format since no [gx] section — "^4" parsed via from_v1 yields Ref("^4") but that's fine)
+    let loaded = parse(file.path()).unwrap();
+    let overrides = loaded
+        .value
+        .overrides_for(&ActionId::from("actions/checkout"));
+    assert_eq!(overrides.len(), 1);
+    assert_eq!(
+        overrides[0].workflow,
+        WorkflowPath::new(".github/workflows/deploy.yml")
+    );
+    assert_eq!(overrides[0].version.as_str(), "^3");
+}
+
+#[test]
+fn apply_add_override_to_existing_section() {
+    let content = r#"[actions]
+"actions/checkout" = "v4"
+
+[actions.overrides]
+"actions/checkout" = [
+  { workflow = ".github/workflows/deploy.yml", version = "v3" },
+]
+"#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let diff = ManifestDiff {
+        overrides_added: vec![(
+            ActionId::from("actions/checkout"),
+            ActionOverride {
+                workflow: WorkflowPath::new(".github/workflows/ci.yml"),
+                job: Some(JobId::from("legacy")),
+                step: None,
+                version: Specifier::parse("^2"),
+            },
+        )],
+        ..Default::default()
+    };
+    apply_manifest_diff(file.path(), &diff).unwrap();
+
+    let loaded = parse(file.path()).unwrap();
+    let overrides = loaded
+        .value
+        .overrides_for(&ActionId::from("actions/checkout"));
+    assert_eq!(overrides.len(), 2, "Should have 2 overrides now");
+}
+
+#[test]
+fn apply_remove_all_overrides_removes_action_entry() {
+    let content = r#"[actions]
+"actions/checkout" = "v4"
+
+[actions.overrides]
+"actions/checkout" = [
+  { workflow = ".github/workflows/deploy.yml", version = "v3" },
+]
+"#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let diff = ManifestDiff {
+        overrides_removed: vec![(
+            ActionId::from("actions/checkout"),
+            vec![ActionOverride {
+                workflow: WorkflowPath::new(".github/workflows/deploy.yml"),
+                job: None,
+                step: None,
+                version: Specifier::parse("^3"),
+            }],
+        )],
+        ..Default::default()
+    };
+    apply_manifest_diff(file.path(), &diff).unwrap();
+
+    let loaded = parse(file.path()).unwrap();
+    assert!(
+        loaded
+            .value
+            .overrides_for(&ActionId::from("actions/checkout"))
+            .is_empty()
+    );
+}
+
+#[test]
+fn apply_remove_last_override_removes_section() {
+    let content = r#"[actions]
+"actions/checkout" = "v4"
+
+[actions.overrides]
+"actions/checkout" = [
+  { workflow = ".github/workflows/deploy.yml", version = "v3" },
+]
+"#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let diff = ManifestDiff {
+        overrides_removed: vec![(
+            ActionId::from("actions/checkout"),
+            vec![ActionOverride {
+                workflow: WorkflowPath::new(".github/workflows/deploy.yml"),
+                job: None,
+                step: None,
+                version: Specifier::parse("^3"),
+            }],
+        )],
+        ..Default::default()
+    };
+    apply_manifest_diff(file.path(), &diff).unwrap();
+
+    let after = fs::read_to_string(file.path()).unwrap();
+    assert!(
+        !after.contains("overrides"),
+        "Overrides section must be removed when empty, got:\n{after}"
+    );
+}
+
+#[test]
+fn apply_roundtrip_domain_state_matches() {
+    let content = r#"[actions]
+"actions/checkout" = "^4"
+"actions/setup-node" = "^3"
+"#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let diff = ManifestDiff {
+        added: vec![(ActionId::from("actions/cache"), Specifier::parse("^3"))],
+        removed: vec![ActionId::from("actions/setup-node")],
+        overrides_added: vec![(
+            ActionId::from("actions/checkout"),
+            ActionOverride {
+                workflow: WorkflowPath::new(".github/workflows/windows.yml"),
+                job: None,
+                step: None,
+                version: Specifier::parse("^3"),
+            },
+        )],
+        ..Default::default()
+    };
+    apply_manifest_diff(file.path(), &diff).unwrap();
+
+    let loaded = parse(file.path()).unwrap();
+    assert!(
+        loaded
+            .value
+            .get(&ActionId::from("actions/checkout"))
+            .is_some()
+    );
+    assert!(loaded.value.get(&ActionId::from("actions/cache")).is_some());
+    assert!(
+        loaded
+            .value
+            .get(&ActionId::from("actions/setup-node"))
+            .is_none()
+    );
+    let overrides = loaded
+        .value
+        .overrides_for(&ActionId::from("actions/checkout"));
+    assert_eq!(overrides.len(), 1);
+    assert_eq!(
+        overrides[0].workflow,
+        WorkflowPath::new(".github/workflows/windows.yml")
+    );
+}
+
+/// A hand-edited manifest: comments, unsorted keys, a lint section, and odd spacing.
+const COMMENTED: &str = r#"# Pinned for the release pipeline.
+[actions]
+# Checkout first, it is what every job needs.
+"actions/checkout" = "^4" # keep on v4 until the runners update
+"actions/cache"    = "^3"
+"actions/setup-node" = { version = "^4", hold = true } # node 20
+
+[lint.rules]
+sha-mismatch = { level = "error" }
+"#;
+
+#[test]
+fn apply_update_keeps_comments_order_and_untouched_lines_byte_for_byte() {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(COMMENTED.as_bytes()).unwrap();
+
+    let diff = ManifestDiff {
+        added: vec![(
+            ActionId::from("actions/upload-artifact"),
+            Specifier::parse("^4"),
+        )],
+        updated: vec![(ActionId::from("actions/checkout"), Specifier::parse("^5"))],
+        ..Default::default()
+    };
+    apply_manifest_diff(file.path(), &diff).unwrap();
+
+    let expected = COMMENTED.replace("\"^4\" # keep", "\"^5\" # keep").replace(
+        "# node 20\n",
+        "# node 20\n\"actions/upload-artifact\" = \"^4\"\n",
+    );
+    assert_eq!(fs::read_to_string(file.path()).unwrap(), expected);
+}
+
+#[test]
+fn apply_to_sorted_actions_keeps_them_sorted() {
+    let content = "[actions]\n\"actions/cache\" = \"^3\"\n\"actions/setup-node\" = \"^4\"\n";
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let diff = ManifestDiff {
+        added: vec![(ActionId::from("actions/checkout"), Specifier::parse("^4"))],
+        ..Default::default()
+    };
+    apply_manifest_diff(file.path(), &diff).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(file.path()).unwrap(),
+        "[actions]\n\"actions/cache\" = \"^3\"\n\"actions/checkout\" = \"^4\"\n\"actions/setup-node\" = \"^4\"\n"
+    );
+}
+
+#[test]
+fn save_of_unchanged_manifest_is_byte_stable() {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(COMMENTED.as_bytes()).unwrap();
+
+    let loaded = parse(file.path()).unwrap().value;
+    Store::new(file.path()).save(&loaded).unwrap();
+
+    assert_eq!(fs::read_to_string(file.path()).unwrap(), COMMENTED);
+}
+
+#[test]
+fn save_edits_only_the_changed_entries() {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(COMMENTED.as_bytes()).unwrap();
+
+    let mut manifest = parse(file.path()).unwrap().value;
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^5"));
+    manifest.remove(&ActionId::from("actions/cache"));
+    Store::new(file.path()).save(&manifest).unwrap();
+
+    let expected = COMMENTED
+        .replace("\"^4\" # keep", "\"^5\" # keep")
+        .replace("\"actions/cache\"    = \"^3\"\n", "");
+    assert_eq!(fs::read_to_string(file.path()).unwrap(), expected);
+}
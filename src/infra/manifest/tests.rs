@@ -1,9 +1,13 @@
-use super::{Manifest, Store, create, parse, parse_lint_config};
+use super::{
+    Document, Manifest, Store, create, parse, parse_lint_config, parse_registry_config,
+    parse_workflows_config,
+};
+use crate::config::Level;
 use crate::domain::action::identity::ActionId;
 use crate::domain::action::specifier::Specifier;
 use crate::domain::diff::ManifestDiff;
 use crate::domain::manifest::overrides::ActionOverride;
-use crate::domain::workflow_actions::{JobId, StepIndex, WorkflowPath};
+use crate::domain::workflow_actions::{JobId, StepIndex, StepKey, WorkflowPath};
 use crate::lint::RuleName;
 use std::fs;
 use std::io::Write as _;
@@ -41,6 +45,7 @@ fn load_manifest_with_overrides() {
 "actions/checkout" = [
   { workflow = ".github/workflows/deploy.yml", version = "v3" },
   { workflow = ".github/workflows/ci.yml", job = "legacy-build", version = "v2" },
+  { workflow = ".github/workflows/ci.yml", job = "build", step = "Deploy", version = "v1" },
 ]
 "#;
     let mut file = NamedTempFile::new().unwrap();
@@ -56,7 +61,7 @@ fn load_manifest_with_overrides() {
     let overrides = loaded
         .value
         .overrides_for(&ActionId::from("actions/checkout"));
-    assert_eq!(overrides.len(), 2);
+    assert_eq!(overrides.len(), 3);
     assert_eq!(
         overrides[0].workflow,
         WorkflowPath::new(".github/workflows/deploy.yml")
@@ -67,6 +72,7 @@ fn load_manifest_with_overrides() {
         Some(&JobId::from("legacy-build"))
     );
     assert_eq!(overrides[1].version.as_str(), "^2");
+    assert_eq!(overrides[2].step, Some(StepKey::Name("Deploy".to_owned())));
 }
 
 #[test]
@@ -188,7 +194,7 @@ fn save_and_load_roundtrip_generates_correct_toml_format() {
         ActionOverride {
             workflow: WorkflowPath::new(".github/workflows/windows.yml"),
             job: Some(JobId::from("test_windows")),
-            step: Some(StepIndex::from(0_u16)),
+            step: Some(StepIndex::from(0_u16).into()),
             version: Specifier::parse("^5"),
         },
     );
@@ -230,13 +236,14 @@ fn save_and_load_roundtrip_generates_correct_toml_format() {
         overrides[0].job.as_ref(),
         Some(&JobId::from("test_windows"))
     );
-    assert_eq!(overrides[0].step, Some(StepIndex::from(0_u16)));
+    assert_eq!(overrides[0].step, Some(StepIndex::from(0_u16).into()));
     assert_eq!(overrides[0].version.as_str(), "^5");
 }
 
 #[test]
 fn parse_lint_config_missing_file_returns_default() {
-    let config = parse_lint_config(Path::new("/nonexistent/gx.toml")).unwrap();
+    let config =
+        parse_lint_config(&Document::read(Path::new("/nonexistent/gx.toml")).unwrap()).unwrap();
     assert!(config.rules.is_empty());
 }
 
@@ -249,10 +256,23 @@ fn parse_lint_config_no_lint_section_returns_default() {
     let mut file = NamedTempFile::new().unwrap();
     file.write_all(content.as_bytes()).unwrap();
 
-    let config = parse_lint_config(file.path()).unwrap();
+    let config = parse_lint_config(&Document::read(file.path()).unwrap()).unwrap();
     assert!(config.rules.is_empty());
 }
 
+#[test]
+fn parse_registry_config_reads_api_url() {
+    let content = "[registry]\napi_url = \"https://ghes.example.com/api/v3\"\n";
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let config = parse_registry_config(&Document::read(file.path()).unwrap());
+    assert_eq!(
+        config.api_url.as_deref(),
+        Some("https://ghes.example.com/api/v3")
+    );
+}
+
 #[test]
 fn parse_lint_config_with_rules() {
     let content = r#"
@@ -269,13 +289,61 @@ stale-comment = { level = "off" }
     let mut file = NamedTempFile::new().unwrap();
     file.write_all(content.as_bytes()).unwrap();
 
-    let config = parse_lint_config(file.path()).unwrap();
+    let config = parse_lint_config(&Document::read(file.path()).unwrap()).unwrap();
     assert_eq!(config.rules.len(), 3);
     assert!(config.rules.contains_key(&RuleName::ShaMismatch));
     assert!(config.rules.contains_key(&RuleName::Unpinned));
     assert!(config.rules.contains_key(&RuleName::StaleComment));
 }
 
+#[test]
+fn parse_lint_config_with_path_overrides() {
+    let content = r#"
+[lint.rules]
+unpinned = { level = "error" }
+
+[lint.paths."workflows/experimental-*.yml"]
+unpinned = "warn"
+missing-concurrency = "off"
+    "#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let config = parse_lint_config(&Document::read(file.path()).unwrap()).unwrap();
+    let levels = &config.paths["workflows/experimental-*.yml"];
+    assert_eq!(levels[&RuleName::Unpinned], Level::Warn);
+    assert_eq!(levels[&RuleName::MissingConcurrency], Level::Off);
+}
+
+#[test]
+fn parse_lint_config_rejects_invalid_path_pattern() {
+    let content = r#"
+[lint.paths."workflows/[broken.yml"]
+unpinned = "warn"
+    "#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let err = parse_lint_config(&Document::read(file.path()).unwrap()).unwrap_err();
+    assert!(err.to_string().contains("workflows/[broken.yml"));
+}
+
+#[test]
+fn parse_workflows_config_reads_patterns_and_rejects_invalid_ones() {
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(b"[workflows]\ninclude = [\"ci/**.yml\"]\nexclude = [\"**/generated-*.yml\"]\n")
+        .unwrap();
+    let config = parse_workflows_config(&Document::read(file.path()).unwrap()).unwrap();
+    assert_eq!(config.include, vec!["ci/**.yml"]);
+    assert_eq!(config.exclude, vec!["**/generated-*.yml"]);
+
+    let mut bad = NamedTempFile::new().unwrap();
+    bad.write_all(b"[workflows]\nexclude = [\"ci/[broken.yml\"]\n")
+        .unwrap();
+    let err = parse_workflows_config(&Document::read(bad.path()).unwrap()).unwrap_err();
+    assert!(err.to_string().contains("ci/[broken.yml"));
+}
+
 #[test]
 fn parse_lint_config_ignore_targets() {
     let content = r#"
@@ -292,7 +360,7 @@ unpinned = { level = "warn", ignore = [
     let mut file = NamedTempFile::new().unwrap();
     file.write_all(content.as_bytes()).unwrap();
 
-    let config = parse_lint_config(file.path()).unwrap();
+    let config = parse_lint_config(&Document::read(file.path()).unwrap()).unwrap();
     let unpinned = &config.rules[&RuleName::Unpinned];
     assert_eq!(unpinned.ignore.len(), 3);
     assert_eq!(
@@ -450,3 +518,30 @@ min_version = "0.5.10"
         Some(&Specifier::parse("^4"))
     );
 }
+
+#[test]
+fn parse_reads_groups_and_save_keeps_them() {
+    let content = r#"
+[actions]
+"actions/checkout" = "^4"
+"actions/create-release" = "^1"
+"softprops/action-gh-release" = "^2"
+
+[groups]
+release = ["softprops/action-gh-release", "actions/create-release"]
+"#;
+    let mut file = NamedTempFile::new().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+
+    let loaded = parse(file.path()).unwrap().value;
+    let expected = [
+        ActionId::from("softprops/action-gh-release"),
+        ActionId::from("actions/create-release"),
+    ];
+    assert_eq!(loaded.group("release"), Some(expected.as_slice()));
+    assert!(loaded.group("missing").is_none());
+
+    Store::new(file.path()).save(&loaded).unwrap();
+    let reloaded = parse(file.path()).unwrap().value;
+    assert_eq!(reloaded.group("release"), Some(expected.as_slice()));
+}
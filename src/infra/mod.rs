@@ -1,7 +1,19 @@
+pub mod atomic;
+pub mod auth;
+pub mod cache;
+pub mod git;
 pub mod github;
+#[cfg(feature = "gitlab")]
+pub mod gitlab;
+pub mod journal;
 pub mod lock;
 pub mod manifest;
+pub mod oci;
+pub mod plan_file;
 pub mod repo;
+pub mod scorecard;
 pub mod shellcheck;
+pub mod trace;
+pub mod trust;
 pub mod workflow_scan;
 pub mod workflow_update;
@@ -0,0 +1,257 @@
+use super::github::{Error as ClientError, Http, SendError};
+use crate::config::Settings;
+use crate::domain::image::{Digest, DigestRegistry, Error as ImageError, Reference};
+use reqwest::blocking::Response;
+use reqwest::header::{ACCEPT, AUTHORIZATION, WWW_AUTHENTICATE};
+use reqwest::{Method, StatusCode};
+use serde::Deserialize;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Timeout in seconds for each registry request when `[registry] timeout_secs` is not set.
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Manifest media types accepted, so multi-platform images resolve to their index digest.
+const MANIFEST_TYPES: &str = "application/vnd.oci.image.index.v1+json, \
+    application/vnd.docker.distribution.manifest.list.v2+json, \
+    application/vnd.oci.image.manifest.v1+json, \
+    application/vnd.docker.distribution.manifest.v2+json";
+
+/// Errors that can occur when talking to an OCI registry.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to create HTTP client")]
+    ClientInit(#[source] ClientError),
+
+    #[error("failed to fetch {url}")]
+    Request {
+        url: String,
+        #[source]
+        source: SendError,
+    },
+
+    #[error("registry returned status {status} for {url}")]
+    Status { status: u16, url: String },
+
+    #[error("registry sent no usable digest for {url}")]
+    NoDigest { url: String },
+}
+
+/// Anonymous token from a registry's token endpoint.
+#[derive(Deserialize)]
+struct TokenResponse {
+    /// The bearer token; some registries call it `access_token`.
+    #[serde(alias = "access_token")]
+    token: String,
+}
+
+/// Client for the OCI distribution API, resolving image tags to manifest digests.
+///
+/// Tags are resolved with `HEAD` requests, which Docker Hub does not count as pulls.
+/// Registries that answer `401` with a bearer challenge get an anonymous pull token.
+pub struct Registry {
+    /// HTTP client sharing the proxy, CA bundle, and request limit of the GitHub one.
+    http: Http,
+    /// `https`, or `http` for test registries.
+    scheme: &'static str,
+    /// When set, every lookup fails without sending a request.
+    offline: bool,
+}
+
+impl Registry {
+    /// A client using the `[registry]` timeout, CA bundle, and request limit, and the
+    /// offline mode of `settings`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ClientInit`] if the HTTP client cannot be created.
+    pub fn from_settings(settings: &Settings) -> Result<Self, Error> {
+        let timeout = Duration::from_secs(DEFAULT_TIMEOUT_SECS);
+        Ok(Self {
+            http: Http::from_settings(settings, timeout).map_err(Error::ClientInit)?,
+            scheme: "https",
+            offline: settings.offline,
+        })
+    }
+
+    /// The digest of the manifest `image`'s tag points to.
+    fn manifest_digest(&self, image: &Reference, tag: &str) -> Result<Digest, Error> {
+        let url = format!(
+            "{}://{}/v2/{}/manifests/{tag}",
+            self.scheme,
+            image.registry(),
+            image.repository()
+        );
+        let mut response = self.head(&url, None)?;
+        if response.status() == StatusCode::UNAUTHORIZED
+            && let Some(token) = self.anonymous_token(&response)
+        {
+            response = self.head(&url, Some(&token))?;
+        }
+        if !response.status().is_success() {
+            return Err(Error::Status {
+                status: response.status().as_u16(),
+                url,
+            });
+        }
+        response
+            .headers()
+            .get("docker-content-digest")
+            .and_then(|value| value.to_str().ok())
+            .filter(|digest| Digest::is_valid(digest))
+            .map(Digest::from)
+            .ok_or(Error::NoDigest { url })
+    }
+
+    /// Send a `HEAD` request for a manifest, with a bearer `token` when given.
+    fn head(&self, url: &str, token: Option<&str>) -> Result<Response, Error> {
+        let request = self
+            .http
+            .request(Method::HEAD, url)
+            .header(ACCEPT, MANIFEST_TYPES);
+        self.http
+            .send(match token {
+                Some(bearer) => request.header(AUTHORIZATION, format!("Bearer {bearer}")),
+                None => request,
+            })
+            .map_err(|source| Error::Request {
+                url: url.to_owned(),
+                source,
+            })
+    }
+
+    /// An anonymous token for the bearer challenge of a `401` response, if it has one.
+    fn anonymous_token(&self, unauthorized: &Response) -> Option<String> {
+        let challenge = unauthorized
+            .headers()
+            .get(WWW_AUTHENTICATE)?
+            .to_str()
+            .ok()?
+            .strip_prefix("Bearer ")?;
+        let param = |name: &str| {
+            challenge.split(',').find_map(|pair| {
+                let (key, value) = pair.trim().split_once('=')?;
+                (key == name).then(|| value.trim_matches('"').to_owned())
+            })
+        };
+        let query: Vec<(&str, String)> = ["service", "scope"]
+            .into_iter()
+            .filter_map(|name| param(name).map(|value| (name, value)))
+            .collect();
+        let realm = reqwest::Url::parse_with_params(&param("realm")?, &query).ok()?;
+        self.http
+            .send(self.http.request(Method::GET, realm))
+            .ok()
+            .filter(|response| response.status().is_success())?
+            .json::<TokenResponse>()
+            .ok()
+            .map(|response| response.token)
+    }
+}
+
+impl DigestRegistry for Registry {
+    fn digest(&self, image: &Reference) -> Result<Digest, ImageError> {
+        if self.offline {
+            return Err(ImageError::Offline);
+        }
+        let failed = |reason: String| ImageError::ResolveFailed {
+            image: image.to_string(),
+            reason,
+        };
+        let tag = image.tag().ok_or_else(|| failed("no tag".to_owned()))?;
+        self.manifest_digest(image, tag)
+            .map_err(|e| failed(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{DigestRegistry as _, Error, Http, Reference, Registry, SendError, Settings};
+    use std::io::{BufRead as _, BufReader, Write as _};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    const DIGEST: &str = "sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn lookups_count_against_max_requests() {
+        let settings = Settings {
+            max_requests: Some(0),
+            ..Settings::default()
+        };
+        let registry = Registry::from_settings(&settings).unwrap();
+        let image = Reference::parse("ghcr.io/org/tool:1.0", None).unwrap();
+
+        let refused = registry.manifest_digest(&image, "1.0");
+
+        assert!(
+            matches!(
+                refused,
+                Err(Error::Request {
+                    source: SendError::RequestLimit { limit: 0 },
+                    ..
+                })
+            ),
+            "{refused:?}"
+        );
+    }
+
+    #[test]
+    fn digest_fetches_an_anonymous_token_when_challenged() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = listener.local_addr().unwrap().to_string();
+        let challenge = format!(
+            "Bearer realm=\"http://{host}/token\",service=\"test\",scope=\"repository:org/tool:pull\""
+        );
+        let responses = [
+            format!(
+                "HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: {challenge}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            ),
+            "HTTP/1.1 200 OK\r\nContent-Length: 15\r\nConnection: close\r\n\r\n{\"token\":\"abc\"}"
+                .to_owned(),
+            format!(
+                "HTTP/1.1 200 OK\r\nDocker-Content-Digest: {DIGEST}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            ),
+        ];
+        let server = thread::spawn(move || {
+            responses
+                .iter()
+                .map(|response| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut head = String::new();
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        if line.trim().is_empty() {
+                            break;
+                        }
+                        head.push_str(&line.to_lowercase());
+                    }
+                    stream.write_all(response.as_bytes()).unwrap();
+                    head
+                })
+                .collect::<Vec<_>>()
+        });
+        let registry = Registry {
+            http: Http::from_settings(&Settings::default(), Duration::from_secs(5)).unwrap(),
+            scheme: "http",
+            offline: false,
+        };
+        let image = Reference::parse(&format!("{host}/org/tool:1.0"), None).unwrap();
+
+        let digest = registry.digest(&image).unwrap();
+
+        assert_eq!(digest.as_str(), DIGEST);
+        let requests = server.join().unwrap();
+        assert!(matches!(
+            requests.as_slice(),
+            [manifest, token, retried]
+                if manifest.starts_with("head /v2/org/tool/manifests/1.0")
+                    && token.starts_with("get /token?service=test&scope=repository%3aorg%2ftool%3apull")
+                    && retried.contains("authorization: bearer abc")
+        ));
+    }
+}
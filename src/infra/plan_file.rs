@@ -0,0 +1,529 @@
+use super::atomic;
+use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
+use crate::domain::action::resolved::{Commit, Provenance, Source};
+use crate::domain::action::spec::Spec;
+use crate::domain::action::specifier::Specifier;
+use crate::domain::action::upgrade::{Action as UpgradeAction, Candidate as UpgradeCandidate};
+use crate::domain::action::uses_ref::RefType;
+use crate::domain::diff::{LockDiff, ManifestDiff};
+use crate::domain::lock::LockEntry;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest as _, Sha1};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Schema version written to every saved plan.
+const PLAN_FORMAT_VERSION: u32 = 2;
+
+/// Errors that can occur when reading or writing a saved upgrade plan.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to read plan file: {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse plan file: {}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("failed to serialize plan file: {}", path.display())]
+    Serialize {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("failed to write plan file: {}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("unsupported plan format version {found} in {} (expected {PLAN_FORMAT_VERSION})", path.display())]
+    UnsupportedVersion { path: PathBuf, found: u32 },
+
+    #[error(
+        "{} changed since the plan was saved; run `gx upgrade --save-plan` again, or pass --force to apply it anyway",
+        file.display()
+    )]
+    Stale { file: PathBuf },
+}
+
+/// An upgrade plan computed by `gx upgrade --save-plan` and executed verbatim by `gx apply`.
+#[derive(Debug, Default)]
+pub struct SavedPlan {
+    pub upgrades: Vec<UpgradeCandidate>,
+    pub manifest: ManifestDiff,
+    pub lock_changes: LockDiff,
+    /// Workflow files, relative to the repository root, that reference an upgraded action.
+    pub workflows: Vec<PathBuf>,
+    /// Hex SHA-1 of gx.toml when the plan was computed; `None` if it did not exist.
+    pub manifest_hash: Option<String>,
+    /// Hex SHA-1 of gx.lock when the plan was computed; `None` if it did not exist.
+    pub lock_hash: Option<String>,
+}
+
+impl SavedPlan {
+    /// Record the manifest and lock at `manifest` and `lock` as the files the plan was
+    /// computed from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Read`] if either file exists but cannot be read.
+    pub fn stamp(&mut self, manifest: &Path, lock: &Path) -> Result<(), Error> {
+        self.manifest_hash = file_hash(manifest)?;
+        self.lock_hash = file_hash(lock)?;
+        Ok(())
+    }
+
+    /// Check that the manifest and lock at `manifest` and `lock` are still the files the
+    /// plan was computed from, so applying it cannot overwrite later edits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Stale`] if either file changed, and [`Error::Read`] if one cannot
+    /// be read.
+    pub fn ensure_current(&self, manifest: &Path, lock: &Path) -> Result<(), Error> {
+        for (path, recorded) in [(manifest, &self.manifest_hash), (lock, &self.lock_hash)] {
+            if file_hash(path)? != *recorded {
+                return Err(Error::Stale {
+                    file: path.to_path_buf(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Hex SHA-1 of the file at `path`, or `None` if it does not exist.
+fn file_hash(path: &Path) -> Result<Option<String>, Error> {
+    match fs::read(path) {
+        Ok(content) => Ok(Some(format!("{:x}", Sha1::digest(&content)))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(source) => Err(Error::Read {
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+/// Top-level JSON document.
+#[derive(Serialize, Deserialize)]
+struct PlanData {
+    /// Schema version, see [`PLAN_FORMAT_VERSION`].
+    version: u32,
+    /// Selected upgrade candidates.
+    upgrades: Vec<CandidateData>,
+    /// Manifest entries to write.
+    manifest: ManifestData,
+    /// Lock entries to write and remove.
+    lock: LockData,
+    /// Workflow files that will be rewritten.
+    workflows: Vec<PathBuf>,
+    /// Hash of gx.toml the plan was computed from.
+    manifest_hash: Option<String>,
+    /// Hash of gx.lock the plan was computed from.
+    lock_hash: Option<String>,
+}
+
+/// A single upgrade candidate.
+#[derive(Serialize, Deserialize)]
+struct CandidateData {
+    /// Action identifier.
+    action: String,
+    /// Current manifest specifier.
+    current: String,
+    /// Concrete tag the upgrade resolves to.
+    candidate: String,
+    /// New manifest specifier for upgrades that cross the current range.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    new_specifier: Option<String>,
+}
+
+/// Manifest changes.
+#[derive(Serialize, Deserialize, Default)]
+struct ManifestData {
+    /// Actions added to the manifest.
+    #[serde(default)]
+    added: Vec<SpecData>,
+    /// Actions whose specifier changes.
+    #[serde(default)]
+    updated: Vec<SpecData>,
+    /// Actions removed from the manifest.
+    #[serde(default)]
+    removed: Vec<String>,
+}
+
+/// Lock changes.
+#[derive(Serialize, Deserialize, Default)]
+struct LockData {
+    /// Entries to insert, with their target SHAs.
+    #[serde(default)]
+    added: Vec<LockEntryData>,
+    /// Entries to drop.
+    #[serde(default)]
+    removed: Vec<SpecData>,
+}
+
+/// An action paired with a specifier.
+#[derive(Serialize, Deserialize)]
+struct SpecData {
+    /// Action identifier.
+    action: String,
+    /// Manifest specifier.
+    specifier: String,
+}
+
+/// A fully resolved lock entry.
+#[derive(Serialize, Deserialize)]
+struct LockEntryData {
+    /// Action identifier.
+    action: String,
+    /// Manifest specifier the entry is keyed by.
+    specifier: String,
+    /// Resolved tag.
+    version: String,
+    /// Target commit SHA.
+    sha: String,
+    /// Repository the SHA belongs to.
+    repository: String,
+    /// How the version was resolved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ref_type: Option<RefType>,
+    /// Commit or release date.
+    date: String,
+    /// Where the commit was found.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<Source>,
+    /// When the commit was resolved.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    resolved_at: String,
+}
+
+/// Serialize `plan` as pretty-printed JSON at `path`.
+///
+/// # Errors
+///
+/// Returns [`Error::Serialize`] or [`Error::Write`] if the file cannot be written.
+pub fn write(path: &Path, plan: &SavedPlan) -> Result<(), Error> {
+    let data = PlanData {
+        version: PLAN_FORMAT_VERSION,
+        upgrades: plan.upgrades.iter().map(candidate_to_data).collect(),
+        manifest: ManifestData {
+            added: plan.manifest.added.iter().map(spec_pair_to_data).collect(),
+            updated: plan
+                .manifest
+                .updated
+                .iter()
+                .map(spec_pair_to_data)
+                .collect(),
+            removed: plan
+                .manifest
+                .removed
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        },
+        lock: LockData {
+            added: plan
+                .lock_changes
+                .added
+                .iter()
+                .map(|(spec, entry)| entry_to_data(spec, entry))
+                .collect(),
+            removed: plan.lock_changes.removed.iter().map(spec_to_data).collect(),
+        },
+        workflows: plan.workflows.clone(),
+        manifest_hash: plan.manifest_hash.clone(),
+        lock_hash: plan.lock_hash.clone(),
+    };
+    let mut json = serde_json::to_string_pretty(&data).map_err(|source| Error::Serialize {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    json.push('\n');
+    atomic::write(path, &json).map_err(|source| Error::Write {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Read a plan previously written by [`write`].
+///
+/// # Errors
+///
+/// Returns [`Error::Read`] or [`Error::Parse`] if the file cannot be loaded, and
+/// [`Error::UnsupportedVersion`] if it was written by an incompatible gx version.
+pub fn read(path: &Path) -> Result<SavedPlan, Error> {
+    let content = fs::read_to_string(path).map_err(|source| Error::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let data: PlanData = serde_json::from_str(&content).map_err(|source| Error::Parse {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    if data.version != PLAN_FORMAT_VERSION {
+        return Err(Error::UnsupportedVersion {
+            path: path.to_path_buf(),
+            found: data.version,
+        });
+    }
+
+    Ok(SavedPlan {
+        upgrades: data.upgrades.into_iter().map(candidate_from_data).collect(),
+        manifest: ManifestDiff {
+            added: data
+                .manifest
+                .added
+                .iter()
+                .map(spec_pair_from_data)
+                .collect(),
+            updated: data
+                .manifest
+                .updated
+                .iter()
+                .map(spec_pair_from_data)
+                .collect(),
+            removed: data
+                .manifest
+                .removed
+                .iter()
+                .map(|id| ActionId::from(id.as_str()))
+                .collect(),
+            ..ManifestDiff::default()
+        },
+        lock_changes: LockDiff {
+            added: data.lock.added.into_iter().map(entry_from_data).collect(),
+            removed: data.lock.removed.iter().map(spec_from_data).collect(),
+            updated: vec![],
+        },
+        workflows: data.workflows,
+        manifest_hash: data.manifest_hash,
+        lock_hash: data.lock_hash,
+    })
+}
+
+/// Convert a domain candidate to its JSON form.
+fn candidate_to_data(candidate: &UpgradeCandidate) -> CandidateData {
+    let new_specifier = match &candidate.action {
+        UpgradeAction::InRange { .. } => None,
+        UpgradeAction::CrossRange { new_specifier, .. } => Some(new_specifier.to_string()),
+    };
+    CandidateData {
+        action: candidate.id.to_string(),
+        current: candidate.current.to_string(),
+        candidate: candidate.candidate().to_string(),
+        new_specifier,
+    }
+}
+
+/// Convert a JSON candidate back to its domain form.
+fn candidate_from_data(data: CandidateData) -> UpgradeCandidate {
+    let candidate = Version::from(data.candidate.as_str());
+    let action = match data.new_specifier {
+        Some(new_specifier) => UpgradeAction::CrossRange {
+            candidate,
+            new_specifier: Specifier::parse(&new_specifier),
+        },
+        None => UpgradeAction::InRange { candidate },
+    };
+    UpgradeCandidate {
+        id: ActionId::from(data.action.as_str()),
+        current: Specifier::parse(&data.current),
+        action,
+    }
+}
+
+/// Convert an `(ActionId, Specifier)` pair to its JSON form.
+fn spec_pair_to_data((id, specifier): &(ActionId, Specifier)) -> SpecData {
+    SpecData {
+        action: id.to_string(),
+        specifier: specifier.to_string(),
+    }
+}
+
+/// Convert a JSON spec back to an `(ActionId, Specifier)` pair.
+fn spec_pair_from_data(data: &SpecData) -> (ActionId, Specifier) {
+    (
+        ActionId::from(data.action.as_str()),
+        Specifier::parse(&data.specifier),
+    )
+}
+
+/// Convert a lock key to its JSON form.
+fn spec_to_data(spec: &Spec) -> SpecData {
+    SpecData {
+        action: spec.id.to_string(),
+        specifier: spec.specifier.to_string(),
+    }
+}
+
+/// Convert a JSON spec back to a lock key.
+fn spec_from_data(data: &SpecData) -> Spec {
+    let (id, specifier) = spec_pair_from_data(data);
+    Spec::new(id, specifier)
+}
+
+/// Convert a lock entry to its JSON form.
+fn entry_to_data(spec: &Spec, entry: &LockEntry) -> LockEntryData {
+    LockEntryData {
+        action: spec.id.to_string(),
+        specifier: spec.specifier.to_string(),
+        version: entry.version.to_string(),
+        sha: entry.commit.sha.to_string(),
+        repository: entry.commit.repository.to_string(),
+        ref_type: entry.commit.ref_type.clone(),
+        date: entry.commit.date.to_string(),
+        source: entry.commit.provenance.source,
+        resolved_at: entry.commit.provenance.resolved_at.clone(),
+    }
+}
+
+/// Convert a JSON lock entry back to its domain form.
+fn entry_from_data(data: LockEntryData) -> (Spec, LockEntry) {
+    let spec = Spec::new(
+        ActionId::from(data.action.as_str()),
+        Specifier::parse(&data.specifier),
+    );
+    let entry = LockEntry {
+        version: Version::from(data.version.as_str()),
+        commit: Commit {
+            sha: CommitSha::from(data.sha),
+            repository: Repository::from(data.repository.as_str()),
+            ref_type: data.ref_type,
+            date: CommitDate::from(data.date.as_str()),
+            provenance: Provenance {
+                source: data.source,
+                resolved_at: data.resolved_at,
+            },
+        },
+    };
+    (spec, entry)
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    clippy::indexing_slicing,
+    reason = "tests use unwrap, indexing, and other patterns freely"
+)]
+mod tests {
+    use super::*;
+
+    fn sample_plan() -> SavedPlan {
+        let spec = Spec::new(ActionId::from("actions/checkout"), Specifier::parse("^5"));
+        SavedPlan {
+            upgrades: vec![UpgradeCandidate {
+                id: ActionId::from("actions/checkout"),
+                current: Specifier::parse("^4"),
+                action: UpgradeAction::CrossRange {
+                    candidate: Version::from("v5.0.0"),
+                    new_specifier: Specifier::parse("^5"),
+                },
+            }],
+            manifest: ManifestDiff {
+                updated: vec![(ActionId::from("actions/checkout"), Specifier::parse("^5"))],
+                ..ManifestDiff::default()
+            },
+            lock_changes: LockDiff {
+                added: vec![(
+                    spec,
+                    LockEntry {
+                        version: Version::from("v5.0.0"),
+                        commit: Commit {
+                            sha: CommitSha::from("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"),
+                            repository: Repository::from("actions/checkout"),
+                            ref_type: Some(RefType::Release),
+                            date: CommitDate::from("2026-01-01T00:00:00Z"),
+                            provenance: Provenance::default(),
+                        },
+                    },
+                )],
+                removed: vec![Spec::new(
+                    ActionId::from("actions/checkout"),
+                    Specifier::parse("^4"),
+                )],
+                updated: vec![],
+            },
+            workflows: vec![PathBuf::from(".github/workflows/ci.yml")],
+            manifest_hash: None,
+            lock_hash: None,
+        }
+    }
+
+    #[test]
+    fn roundtrip_preserves_plan() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plan.json");
+        write(&path, &sample_plan()).unwrap();
+
+        let plan = read(&path).unwrap();
+        assert_eq!(plan.upgrades.len(), 1);
+        assert_eq!(
+            plan.upgrades[0].manifest_specifier(),
+            &Specifier::parse("^5")
+        );
+        assert_eq!(plan.upgrades[0].candidate(), &Version::from("v5.0.0"));
+        assert_eq!(plan.manifest.updated, sample_plan().manifest.updated);
+        assert_eq!(plan.lock_changes.added, sample_plan().lock_changes.added);
+        assert_eq!(
+            plan.lock_changes.removed,
+            sample_plan().lock_changes.removed
+        );
+        assert_eq!(
+            plan.workflows,
+            vec![PathBuf::from(".github/workflows/ci.yml")]
+        );
+    }
+
+    #[test]
+    fn a_plan_is_stale_once_the_manifest_or_lock_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let (manifest, lock) = (dir.path().join("gx.toml"), dir.path().join("gx.lock"));
+        fs::write(&manifest, "[actions]\n").unwrap();
+        let mut plan = sample_plan();
+        plan.stamp(&manifest, &lock).unwrap();
+        let path = dir.path().join("plan.json");
+        write(&path, &plan).unwrap();
+        let saved = read(&path).unwrap();
+        saved.ensure_current(&manifest, &lock).unwrap();
+
+        fs::write(&manifest, "[actions]\n\"actions/checkout\" = \"^5\"\n").unwrap();
+        let stale = saved.ensure_current(&manifest, &lock).unwrap_err();
+        assert!(
+            matches!(&stale, Error::Stale { file } if *file == manifest),
+            "{stale:?}"
+        );
+
+        plan.stamp(&manifest, &lock).unwrap();
+        fs::write(&lock, "version = \"1.4\"\n").unwrap();
+        let relocked = plan.ensure_current(&manifest, &lock).unwrap_err();
+        assert!(
+            matches!(&relocked, Error::Stale { file } if *file == lock),
+            "{relocked:?}"
+        );
+    }
+
+    #[test]
+    fn read_rejects_unknown_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plan.json");
+        fs::write(
+            &path,
+            r#"{"version": 99, "upgrades": [], "manifest": {}, "lock": {}, "workflows": []}"#,
+        )
+        .unwrap();
+
+        let err = read(&path).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedVersion { found: 99, .. }));
+    }
+}
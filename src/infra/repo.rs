@@ -1,6 +1,17 @@
-use std::path::PathBuf;
+use super::manifest::MANIFEST_FILE_NAME;
+use std::ffi::OsString;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read as _, Write as _};
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+/// Directory of the starter workflows an organization's `.github` repository shares.
+pub const WORKFLOW_TEMPLATES_DIR: &str = "workflow-templates";
+
+/// File in the git directory that a gx run rewriting files holds a lock on, with its
+/// process id.
+pub const RUN_LOCK_FILE_NAME: &str = ".gx.lock.pid";
+
 /// Errors that can occur when interacting with the local repository.
 #[derive(Debug, Error)]
 pub enum Error {
@@ -12,23 +23,205 @@ pub enum Error {
 
     #[error("no valid git repository could be found")]
     GitRepository(#[source] gix_discover::upwards::Error),
+
+    #[error("GIT_DIR {} is not a git repository", path.display())]
+    GitDir {
+        path: PathBuf,
+        #[source]
+        source: gix_discover::is_git::Error,
+    },
+
+    #[error("repository root {} is not a directory", path.display())]
+    NotADirectory { path: PathBuf },
+
+    #[error(
+        "another gx run (process {pid}) is rewriting files in this repository; wait for it to finish"
+    )]
+    Busy { pid: String },
+
+    #[error("failed to lock {}", path.display())]
+    RunLock {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
 }
 
 /// Find the root of the git repository containing the given path.
 ///
+/// The work tree of the nearest `.git` directory or file upward wins, so a linked
+/// worktree resolves to its own checkout. As in git, `GIT_WORK_TREE` names the root
+/// outright, and a `GIT_DIR` without it makes `start` the root.
+///
 /// # Errors
 ///
-/// Returns an error if no git repository is found, the repository is bare, or the `.github` folder is missing.
-pub fn find_root(start: &std::path::Path) -> Result<PathBuf, Error> {
-    let (repo_path, _trust) = gix_discover::upwards(start).map_err(Error::GitRepository)?;
-
-    let (_git_dir, work_tree) = repo_path.into_repository_and_work_tree_directories();
+/// Returns an error if no git repository is found, `GIT_DIR` is not one, the repository
+/// is bare, or it has no `.github` folder, no `gx.toml`, and no `workflow-templates`
+/// folder as an organization's `.github` repository has.
+pub fn find_root(start: &Path) -> Result<PathBuf, Error> {
+    find_root_with_env(
+        start,
+        std::env::var_os("GIT_DIR"),
+        std::env::var_os("GIT_WORK_TREE"),
+    )
+}
 
-    let root = work_tree.ok_or(Error::BareRepository)?;
+/// [`find_root`], with the values of `GIT_DIR` and `GIT_WORK_TREE` passed in.
+fn find_root_with_env(
+    start: &Path,
+    git_dir_var: Option<OsString>,
+    work_tree_var: Option<OsString>,
+) -> Result<PathBuf, Error> {
+    let root = if let Some(work_tree) = work_tree_var {
+        start.join(work_tree)
+    } else if let Some(git_dir) = git_dir_var {
+        let path = start.join(git_dir);
+        gix_discover::is_git(&path).map_err(|source| Error::GitDir { path, source })?;
+        start.to_path_buf()
+    } else {
+        let (repo_path, _trust) = gix_discover::upwards(start).map_err(Error::GitRepository)?;
+        let (_git_dir, discovered) = repo_path.into_repository_and_work_tree_directories();
+        discovered.ok_or(Error::BareRepository)?
+    };
+    open_root(&root)
+}
 
-    if root.join(".github").is_dir() {
-        Ok(root)
+/// Use `root` as the repository root as given, as `--repo-root` does, without looking
+/// for git.
+///
+/// # Errors
+///
+/// Returns an error if `root` is not a directory, or has no `.github` folder,
+/// `workflow-templates` folder, or `gx.toml`.
+pub fn open_root(root: &Path) -> Result<PathBuf, Error> {
+    if !root.is_dir() {
+        return Err(Error::NotADirectory {
+            path: root.to_path_buf(),
+        });
+    }
+    if root.join(".github").is_dir()
+        || root.join(WORKFLOW_TEMPLATES_DIR).is_dir()
+        || root.join(MANIFEST_FILE_NAME).is_file()
+    {
+        Ok(root.to_path_buf())
     } else {
         Err(Error::GithubFolder)
     }
 }
+
+/// An exclusive lock on a repository, taken by a gx run before it rewrites gx.toml,
+/// gx.lock, or workflows, so two runs never interleave their writes. The operating system
+/// releases it when the lock is dropped or the process exits, even on a crash.
+///
+/// The lock file lives in the git directory, so it never shows up as an untracked file;
+/// outside git it is kept in the root.
+#[derive(Debug)]
+pub struct RunLock {
+    /// The locked file; closing it releases the lock.
+    _file: File,
+}
+
+impl RunLock {
+    /// Take the lock of the repository at `root` without waiting, recording this
+    /// process's id in the lock file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Busy`] if another gx run holds the lock.
+    /// Returns [`Error::RunLock`] if the lock file cannot be opened, locked, or written.
+    pub fn acquire(root: &Path) -> Result<Self, Error> {
+        let path = gix_discover::upwards(root)
+            .map_or_else(
+                |_| root.to_path_buf(),
+                |(repo_path, _trust)| repo_path.into_repository_and_work_tree_directories().0,
+            )
+            .join(RUN_LOCK_FILE_NAME);
+        let io_error = |source| Error::RunLock {
+            path: path.clone(),
+            source,
+        };
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(io_error)?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let mut pid = String::new();
+                file.read_to_string(&mut pid).map_err(io_error)?;
+                return Err(Error::Busy {
+                    pid: pid.trim().to_owned(),
+                });
+            }
+            Err(TryLockError::Error(source)) => return Err(io_error(source)),
+        }
+        file.set_len(0).map_err(io_error)?;
+        write!(file, "{}", std::process::id()).map_err(io_error)?;
+        Ok(Self { _file: file })
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{Error, RUN_LOCK_FILE_NAME, RunLock, find_root_with_env, open_root};
+    use std::ffi::OsString;
+
+    #[test]
+    fn a_second_run_is_refused_until_the_first_releases_the_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".github")).unwrap();
+
+        let first = RunLock::acquire(dir.path()).unwrap();
+        let err = RunLock::acquire(dir.path()).unwrap_err();
+        assert!(
+            matches!(&err, Error::Busy { pid } if *pid == std::process::id().to_string()),
+            "{err:?}"
+        );
+
+        drop(first);
+        RunLock::acquire(dir.path()).unwrap();
+    }
+
+    #[test]
+    fn the_run_lock_lives_in_the_git_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let status = std::process::Command::new("git")
+            .args(["init", "--quiet"])
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let _lock = RunLock::acquire(dir.path()).unwrap();
+
+        assert!(dir.path().join(".git").join(RUN_LOCK_FILE_NAME).is_file());
+        assert!(!dir.path().join(".github").exists());
+    }
+
+    #[test]
+    fn git_work_tree_and_git_dir_pick_the_root_without_walking_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkout = dir.path().join("checkout");
+        std::fs::create_dir_all(checkout.join(".github")).unwrap();
+
+        let root = find_root_with_env(dir.path(), None, Some(OsString::from("checkout")));
+        assert_eq!(root.unwrap(), checkout);
+
+        let err = find_root_with_env(&checkout, Some(OsString::from("../repo.git")), None);
+        assert!(matches!(err, Err(Error::GitDir { .. })), "{err:?}");
+    }
+
+    #[test]
+    fn a_root_gx_toml_marks_the_root_without_a_github_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(open_root(dir.path()), Err(Error::GithubFolder)));
+
+        std::fs::write(dir.path().join("gx.toml"), "").unwrap();
+
+        assert_eq!(open_root(dir.path()).unwrap(), dir.path());
+    }
+}
@@ -0,0 +1,125 @@
+use super::github::{Error as ClientError, Http, SendError};
+use crate::config::Settings;
+use crate::domain::action::identity::Repository;
+use reqwest::{Method, StatusCode};
+use serde::Deserialize;
+use std::time::Duration;
+use thiserror::Error;
+
+/// The public API serving the Scorecard results the Open Source Security Foundation publishes.
+const API_URL: &str = "https://api.securityscorecards.dev";
+
+/// Timeout in seconds for each request when `[registry] timeout_secs` is not set.
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Errors that can occur when fetching Scorecard results.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to create HTTP client")]
+    ClientInit(#[source] ClientError),
+
+    #[error("failed to fetch {url}")]
+    Request {
+        url: String,
+        #[source]
+        source: SendError,
+    },
+
+    #[error("Scorecard API returned status {status} for {url}")]
+    Status { status: u16, url: String },
+
+    #[error("failed to parse the Scorecard result from {url}")]
+    Parse {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+}
+
+/// The published Scorecard result of a repository.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Scorecard {
+    /// The aggregate score, from 0 to 10.
+    pub score: f64,
+    /// The result of each check.
+    #[serde(default)]
+    pub checks: Vec<Check>,
+}
+
+/// One check of a [`Scorecard`], such as `Branch-Protection`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Check {
+    /// The check's name.
+    pub name: String,
+    /// Its score from 0 to 10, or `-1` when it does not apply.
+    pub score: i32,
+    /// Why it scored so.
+    #[serde(default)]
+    pub reason: String,
+}
+
+impl Scorecard {
+    /// The checks that apply and scored below half marks, lowest first.
+    #[must_use]
+    pub fn failing_checks(&self) -> Vec<&Check> {
+        let mut failing: Vec<&Check> = self
+            .checks
+            .iter()
+            .filter(|check| (0..5).contains(&check.score))
+            .collect();
+        failing.sort_by_key(|check| check.score);
+        failing
+    }
+}
+
+/// Client for the Scorecard API.
+pub struct Client {
+    /// HTTP client sharing the proxy, CA bundle, and request limit of the GitHub one.
+    http: Http,
+}
+
+impl Client {
+    /// A client using the `[registry]` timeout, CA bundle, and request limit of
+    /// `settings`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ClientInit`] if the HTTP client cannot be created.
+    pub fn from_settings(settings: &Settings) -> Result<Self, Error> {
+        let timeout = Duration::from_secs(DEFAULT_TIMEOUT_SECS);
+        Ok(Self {
+            http: Http::from_settings(settings, timeout).map_err(Error::ClientInit)?,
+        })
+    }
+
+    /// The published result of the GitHub repository `repo`, or `None` when the
+    /// repository has not been scored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the API answers with another error, or the
+    /// result cannot be parsed.
+    pub fn result(&self, repo: &Repository) -> Result<Option<Scorecard>, Error> {
+        let url = format!("{API_URL}/projects/github.com/{repo}");
+        let response = self
+            .http
+            .send(self.http.request(Method::GET, &url))
+            .map_err(|source| Error::Request {
+                url: url.clone(),
+                source,
+            })?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(Error::Status {
+                status: response.status().as_u16(),
+                url,
+            });
+        }
+        response
+            .json()
+            .map(Some)
+            .map_err(|source| Error::Parse { url, source })
+    }
+}
@@ -1,6 +1,6 @@
 //! The real [`ShellChecker`] adapter: spawns the `shellcheck` binary and parses its
-//! `-f json` output into [`Finding`]s. This is the only place in gx that runs an external
-//! process, so all process I/O and JSON shape live here behind the trait.
+//! `-f json` output into [`Finding`]s. All shellcheck process I/O and JSON shape live here
+//! behind the trait.
 
 use super::{Finding, Severity, Sh, ShellChecker};
 use serde::Deserialize;
@@ -0,0 +1,144 @@
+use serde::Serialize;
+
+/// Version of the HAR format written.
+const HAR_VERSION: &str = "1.2";
+
+/// The top-level object of a HAR file.
+#[derive(Debug, Serialize)]
+pub struct Har<'entries> {
+    /// The archive itself.
+    pub log: Log<'entries>,
+}
+
+/// The archive: the tool that wrote it and the requests it recorded.
+#[derive(Debug, Serialize)]
+pub struct Log<'entries> {
+    /// The HAR format version.
+    version: &'static str,
+    /// The tool that wrote the archive.
+    creator: Creator,
+    /// The recorded requests, in the order they completed.
+    entries: &'entries [Entry],
+}
+
+impl<'entries> Log<'entries> {
+    /// An archive of `entries` written by this version of gx.
+    pub const fn new(entries: &'entries [Entry]) -> Self {
+        Self {
+            version: HAR_VERSION,
+            creator: Creator {
+                name: "gx",
+                version: env!("CARGO_PKG_VERSION"),
+            },
+            entries,
+        }
+    }
+}
+
+/// The tool that wrote an archive.
+#[derive(Debug, Serialize)]
+struct Creator {
+    /// Tool name.
+    name: &'static str,
+    /// Tool version.
+    version: &'static str,
+}
+
+/// One request and its response.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entry {
+    /// When the request was sent, in RFC 3339.
+    pub started_date_time: String,
+    /// Milliseconds from sending the request to receiving the response headers.
+    pub time: u64,
+    /// The request, with credentials redacted.
+    pub request: Request,
+    /// The response; status `0` when the request failed without one.
+    pub response: Response,
+    /// Cache state; gx's on-disk cache is not reported here.
+    pub cache: Empty,
+    /// Time spent per phase; all of `time` is counted as waiting for the server.
+    pub timings: Timings,
+}
+
+/// A recorded request.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// HTTP method, e.g. `GET`.
+    pub method: String,
+    /// The URL, with credentials redacted.
+    pub url: String,
+    /// HTTP version, e.g. `HTTP/1.1`.
+    pub http_version: String,
+    /// Always empty: gx sends no cookies.
+    pub cookies: Vec<Header>,
+    /// Request headers, with credentials redacted.
+    pub headers: Vec<Header>,
+    /// The query parameters of `url`.
+    pub query_string: Vec<Header>,
+    /// Unknown, so `-1`.
+    pub headers_size: i64,
+    /// Unknown, so `-1`.
+    pub body_size: i64,
+}
+
+/// A recorded response.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    /// HTTP status, or `0` when no response arrived.
+    pub status: u16,
+    /// The reason phrase, or the error when no response arrived.
+    pub status_text: String,
+    /// HTTP version, e.g. `HTTP/1.1`.
+    pub http_version: String,
+    /// Always empty: gx keeps no cookies.
+    pub cookies: Vec<Header>,
+    /// Response headers, with credentials redacted.
+    pub headers: Vec<Header>,
+    /// Size and type of the body, which is not recorded.
+    pub content: Content,
+    /// The `Location` header, if any.
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: String,
+    /// Unknown, so `-1`.
+    pub headers_size: i64,
+    /// The `Content-Length`, or `-1` when unknown.
+    pub body_size: i64,
+}
+
+/// A header or query parameter.
+#[derive(Debug, Serialize)]
+pub struct Header {
+    /// Header or parameter name.
+    pub name: String,
+    /// Its value, redacted for credentials.
+    pub value: String,
+}
+
+/// Size and media type of a response body.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Content {
+    /// The `Content-Length`, or `-1` when unknown.
+    pub size: i64,
+    /// The `Content-Type`, or empty.
+    pub mime_type: String,
+}
+
+/// An object without fields, for parts of the format gx has nothing to report in.
+#[derive(Debug, Serialize)]
+pub struct Empty {}
+
+/// Time spent per phase of a request, in milliseconds.
+#[derive(Debug, Serialize)]
+pub struct Timings {
+    /// Sending the request; not measured separately, so `0`.
+    pub send: u64,
+    /// Waiting for the response headers.
+    pub wait: u64,
+    /// Receiving the body; not measured separately, so `0`.
+    pub receive: u64,
+}
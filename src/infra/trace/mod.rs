@@ -0,0 +1,8 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// The HTTP Archive (HAR 1.2) format written by `--har`.
+mod har;
+/// Tracing HTTP requests to stderr and into a HAR dump, with credentials redacted.
+mod tracer;
+
+pub use tracer::Tracer;
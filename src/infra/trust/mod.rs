@@ -0,0 +1,249 @@
+use super::atomic;
+use crate::domain::action::identity::{ActionId, CommitSha, Version};
+use crate::domain::trust::Trust;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use thiserror::Error;
+
+/// The trust database of a repository, beside gx.toml.
+pub const TRUST_FILE_NAME: &str = "gx-trust.toml";
+
+/// The `ssh-keygen -Y` namespace trust databases are signed in, so a signature made for
+/// another purpose does not verify one.
+const SIGNATURE_NAMESPACE: &str = "gx-trust";
+
+/// Errors that can occur when reading, writing, signing, or verifying a trust database.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to read trust database: {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse trust database: {}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: Box<toml::de::Error>,
+    },
+
+    #[error("failed to serialize trust database")]
+    Serialize {
+        #[source]
+        source: toml::ser::Error,
+    },
+
+    #[error("failed to write trust database: {}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to run ssh-keygen")]
+    Spawn {
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("ssh-keygen could not sign {}: {stderr}", path.display())]
+    Sign { path: PathBuf, stderr: String },
+
+    #[error("no signature at {}: sign it with `gx trust export --key`, or pass --insecure-skip-verify", signature.display())]
+    Unsigned { signature: PathBuf },
+
+    #[error("the signature of {} does not verify: {stderr}", path.display())]
+    Verify { path: PathBuf, stderr: String },
+}
+
+/// The trust database file.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct TrustData {
+    /// The approved commits.
+    #[serde(default)]
+    approved: Vec<ApprovedData>,
+}
+
+/// An `[[approved]]` entry.
+#[derive(Debug, Deserialize, Serialize)]
+struct ApprovedData {
+    /// The action, such as `actions/checkout`.
+    action: String,
+    /// The approved version.
+    version: String,
+    /// The approved commit SHA.
+    sha: String,
+}
+
+/// The trust database at `path`, or an empty one when the file does not exist.
+///
+/// # Errors
+///
+/// Returns [`Error::Read`] if the file cannot be read.
+/// Returns [`Error::Parse`] if it is not a trust database.
+pub fn read(path: &Path) -> Result<Trust, Error> {
+    if !path.exists() {
+        return Ok(Trust::default());
+    }
+    let content = fs::read_to_string(path).map_err(|source| Error::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let data: TrustData = toml::from_str(&content).map_err(|source| Error::Parse {
+        path: path.to_path_buf(),
+        source: Box::new(source),
+    })?;
+    let mut trust = Trust::default();
+    for approved in data.approved {
+        trust.approve(
+            &ActionId::from(approved.action),
+            &Version::from(approved.version),
+            &CommitSha::from(approved.sha),
+        );
+    }
+    Ok(trust)
+}
+
+/// `trust` as the content of a trust database file.
+///
+/// # Errors
+///
+/// Returns [`Error::Serialize`] if the entries cannot be serialized.
+pub fn render(trust: &Trust) -> Result<String, Error> {
+    let data = TrustData {
+        approved: trust
+            .entries()
+            .map(|(action, version, sha)| ApprovedData {
+                action: action.to_owned(),
+                version: version.to_owned(),
+                sha: sha.to_owned(),
+            })
+            .collect(),
+    };
+    let body = toml::to_string(&data).map_err(|source| Error::Serialize { source })?;
+    Ok(format!(
+        "# Actions approved for `gx --trusted-only`. Review every entry before signing.\n\n{body}"
+    ))
+}
+
+/// Write `trust` to `path`.
+///
+/// # Errors
+///
+/// Returns [`Error::Serialize`] if the entries cannot be serialized.
+/// Returns [`Error::Write`] if the file cannot be written.
+pub fn write(path: &Path, trust: &Trust) -> Result<(), Error> {
+    atomic::write(path, &render(trust)?).map_err(|source| Error::Write {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Sign the file at `path` with the SSH private key `key`, writing `<path>.sig`, and
+/// return the signature's path.
+///
+/// # Errors
+///
+/// Returns [`Error::Spawn`] if `ssh-keygen` cannot run.
+/// Returns [`Error::Sign`] if it fails to sign.
+pub fn sign(path: &Path, key: &Path) -> Result<PathBuf, Error> {
+    let output = Command::new("ssh-keygen")
+        .args(["-Y", "sign", "-n", SIGNATURE_NAMESPACE, "-f"])
+        .arg(key)
+        .arg(path)
+        .output()
+        .map_err(|source| Error::Spawn { source })?;
+    if !output.status.success() {
+        return Err(Error::Sign {
+            path: path.to_path_buf(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        });
+    }
+    Ok(signature_path(path))
+}
+
+/// Check that `<path>.sig` is a signature of the file at `path` by `identity`, one of
+/// the keys in the `allowed_signers` file.
+///
+/// # Errors
+///
+/// Returns [`Error::Unsigned`] if there is no `<path>.sig`.
+/// Returns [`Error::Read`] if the file cannot be opened.
+/// Returns [`Error::Spawn`] if `ssh-keygen` cannot run.
+/// Returns [`Error::Verify`] if the signature does not verify.
+pub fn verify(path: &Path, allowed_signers: &Path, identity: &str) -> Result<(), Error> {
+    let signature = signature_path(path);
+    if !signature.is_file() {
+        return Err(Error::Unsigned { signature });
+    }
+    let content = File::open(path).map_err(|source| Error::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let output = Command::new("ssh-keygen")
+        .args(["-Y", "verify", "-n", SIGNATURE_NAMESPACE, "-f"])
+        .arg(allowed_signers)
+        .args(["-I", identity, "-s"])
+        .arg(&signature)
+        .stdin(content)
+        .output()
+        .map_err(|source| Error::Spawn { source })?;
+    if !output.status.success() {
+        return Err(Error::Verify {
+            path: path.to_path_buf(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        });
+    }
+    Ok(())
+}
+
+/// Where the signature of the file at `path` is kept: beside it, with `.sig` appended.
+fn signature_path(path: &Path) -> PathBuf {
+    let mut signature = path.as_os_str().to_owned();
+    signature.push(".sig");
+    PathBuf::from(signature)
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{Error, read, verify, write};
+    use crate::domain::action::identity::{ActionId, CommitSha, Version};
+    use crate::domain::trust::Trust;
+
+    #[test]
+    fn trust_database_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gx-trust.toml");
+        let mut trust = Trust::default();
+        trust.approve(
+            &ActionId::from("actions/checkout"),
+            &Version::from("v4.2.2"),
+            &CommitSha::from("aaa111"),
+        );
+
+        write(&path, &trust).unwrap();
+
+        assert_eq!(read(&path).unwrap(), trust);
+        assert!(read(&dir.path().join("missing.toml")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn verify_refuses_a_database_without_a_signature() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trust.toml");
+        write(&path, &Trust::default()).unwrap();
+
+        let unsigned = verify(
+            &path,
+            &dir.path().join("allowed_signers"),
+            "security@example.com",
+        );
+
+        assert!(matches!(unsigned, Err(Error::Unsigned { .. })));
+    }
+}
@@ -0,0 +1,62 @@
+use super::ignored::Ignores;
+use crate::config::Workflows;
+use crate::domain::workflow::Error as WorkflowError;
+use glob::glob;
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Find the workflow files under `repo_root` that `workflows` includes and does not
+/// exclude, sorted. Only `.yml` and `.yaml` files count, whatever a pattern matches, and
+/// files that git ignores or `.gxignore` lists are skipped.
+///
+/// Symlinks are followed, but a file whose target lies outside the repository is
+/// skipped, and a file reached through several links is returned once.
+///
+/// # Errors
+///
+/// Returns an error if an include pattern is not a valid glob.
+pub fn find_workflow_files(
+    repo_root: &Path,
+    workflows: &Workflows,
+) -> Result<Vec<PathBuf>, WorkflowError> {
+    let root = glob::Pattern::escape(&repo_root.to_string_lossy());
+    let real_root = repo_root
+        .canonicalize()
+        .unwrap_or_else(|_| repo_root.to_path_buf());
+    let mut ignores = Ignores::new(repo_root);
+    let mut targets = HashSet::new();
+    let mut found = BTreeSet::new();
+    for pattern in workflows.include_patterns() {
+        let entries =
+            glob(&format!("{root}/{pattern}")).map_err(|e| WorkflowError::ScanFailed {
+                reason: format!("invalid [workflows] pattern \"{pattern}\": {e}"),
+            })?;
+        for path in entries.flatten() {
+            let relative = path
+                .strip_prefix(repo_root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            if !path.is_file()
+                || !is_yaml(&path)
+                || workflows.excludes(&relative)
+                || ignores.is_ignored(&path)
+            {
+                continue;
+            }
+            let Ok(target) = path.canonicalize() else {
+                continue;
+            };
+            if target.starts_with(&real_root) && targets.insert(target) {
+                found.insert(path);
+            }
+        }
+    }
+    Ok(found.into_iter().collect())
+}
+
+/// Whether `path` has a `.yml` or `.yaml` extension.
+fn is_yaml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "yml" || extension == "yaml")
+}
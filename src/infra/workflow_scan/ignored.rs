@@ -0,0 +1,108 @@
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// The file at the repository root listing, in gitignore syntax, workflows gx skips.
+const GXIGNORE_FILE_NAME: &str = ".gxignore";
+
+/// Which files under a repository are ignored, by `.gxignore` or by git.
+pub(super) struct Ignores {
+    /// The repository root every ignore file is read under.
+    root: PathBuf,
+    /// The rules of `.gxignore`.
+    gxignore: Gitignore,
+    /// The rules of `.git/info/exclude`.
+    exclude: Gitignore,
+    /// The rules of each directory's `.gitignore`, read the first time a file below it
+    /// is checked.
+    gitignores: HashMap<PathBuf, Gitignore>,
+}
+
+impl Ignores {
+    /// The ignore rules of the repository at `root`. Missing ignore files are empty.
+    pub(super) fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            gxignore: read(root, &root.join(GXIGNORE_FILE_NAME)),
+            exclude: read(root, &root.join(".git").join("info").join("exclude")),
+            gitignores: HashMap::new(),
+        }
+    }
+
+    /// Whether `path`, a file under the root, is ignored.
+    ///
+    /// `.gxignore` decides first, so a `!` entry there brings back a workflow git
+    /// ignores. Then, as in git, the `.gitignore` nearest to the file with a matching
+    /// entry decides, and `.git/info/exclude` last.
+    pub(super) fn is_ignored(&mut self, path: &Path) -> bool {
+        if let Some(ignored) = decision(&self.gxignore.matched_path_or_any_parents(path, false)) {
+            return ignored;
+        }
+        let dirs: Vec<PathBuf> = path
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(&self.root))
+            .map(Path::to_path_buf)
+            .collect();
+        for dir in dirs {
+            let gitignore = self
+                .gitignores
+                .entry(dir)
+                .or_insert_with_key(|key| read(key, &key.join(".gitignore")));
+            if let Some(ignored) = decision(&gitignore.matched_path_or_any_parents(path, false)) {
+                return ignored;
+            }
+        }
+        self.exclude
+            .matched_path_or_any_parents(path, false)
+            .is_ignore()
+    }
+}
+
+/// Whether a matching entry ignores the file, or `None` when no entry matched.
+fn decision<T>(matched: &Match<T>) -> Option<bool> {
+    (!matched.is_none()).then(|| matched.is_ignore())
+}
+
+/// The rules of the ignore file `file`, relative to `dir`. A missing or unreadable file
+/// has no rules, and an invalid line is skipped as git skips it.
+fn read(dir: &Path, file: &Path) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(dir);
+    if file.is_file() {
+        // A partial error only reports the invalid lines; the valid ones still apply.
+        builder.add(file);
+    }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{GXIGNORE_FILE_NAME, Ignores};
+    use std::fs;
+
+    #[test]
+    fn gitignore_files_and_gxignore_decide_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let workflows = root.join(".github").join("workflows");
+        fs::create_dir_all(workflows.join("generated")).unwrap();
+        fs::write(root.join(".gitignore"), "generated/\n*.local.yml\n").unwrap();
+        fs::write(workflows.join(".gitignore"), "!keep.local.yml\n").unwrap();
+        fs::write(
+            root.join(GXIGNORE_FILE_NAME),
+            "experimental-*.yml\n!.github/workflows/generated/ci.yml\n",
+        )
+        .unwrap();
+
+        let mut ignores = Ignores::new(root);
+
+        assert!(ignores.is_ignored(&workflows.join("generated").join("deploy.yml")));
+        assert!(ignores.is_ignored(&workflows.join("dev.local.yml")));
+        assert!(ignores.is_ignored(&workflows.join("experimental-a.yml")));
+        assert!(!ignores.is_ignored(&workflows.join("keep.local.yml")));
+        assert!(!ignores.is_ignored(&workflows.join("generated").join("ci.yml")));
+        assert!(!ignores.is_ignored(&workflows.join("ci.yml")));
+    }
+}
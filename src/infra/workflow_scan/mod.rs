@@ -1,6 +1,11 @@
 #![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
 
+/// Finding workflow files from the `[workflows]` include and exclude patterns.
+mod discovery;
+/// Skipping the workflow files `.gitignore` or `.gxignore` ignore.
+mod ignored;
 /// Workflow file scanning and action extraction.
 mod scanner;
 
+pub use discovery::find_workflow_files;
 pub use scanner::FileScanner;
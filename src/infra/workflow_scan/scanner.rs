@@ -1,23 +1,17 @@
+use super::discovery::find_workflow_files;
+use crate::config::Workflows;
 use crate::domain::action::uses_ref::UsesRef;
+use crate::domain::image::{Located as LocatedImage, Reference as ImageRef};
 use crate::domain::workflow::Error as WorkflowError;
-use crate::domain::workflow_actions::{JobId, StepIndex, WorkflowPath};
+use crate::domain::workflow_actions::{JobId, Location, StepIndex, WorkflowPath};
 use crate::domain::workflow_parsed::Parsed;
-use crate::regex::static_regex;
-use glob::glob;
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
-// Splits an action reference into `owner/repo` (or path) and its `@ref`.
-static_regex!(USES_RE, r"^([^@\s]+)@([^\s#]+)");
-
 /// Internal I/O errors for workflow operations.
 #[derive(Debug, Error)]
 enum IoWorkflowError {
-    /// A glob pattern could not be compiled.
-    #[error("glob pattern error")]
-    Glob(#[from] glob::PatternError),
-
     /// A workflow file could not be read from disk.
     #[error("read error: {}", path.display())]
     Read {
@@ -44,9 +38,6 @@ enum IoWorkflowError {
 impl From<IoWorkflowError> for WorkflowError {
     fn from(err: IoWorkflowError) -> Self {
         match err {
-            IoWorkflowError::Glob(e) => WorkflowError::ScanFailed {
-                reason: e.to_string(),
-            },
             IoWorkflowError::Read { path, source } => WorkflowError::ScanFailed {
                 reason: format!("failed to read {}: {}", path.display(), source),
             },
@@ -72,37 +63,12 @@ struct ExtractedAction {
     location: crate::domain::workflow_actions::Location,
 }
 
-/// Find all workflow files in a workflows directory.
-///
-/// # Errors
-///
-/// Returns an error if the glob pattern is invalid or file access fails.
-fn find_workflow_files(workflows_dir: &Path) -> Result<Vec<PathBuf>, IoWorkflowError> {
-    let mut workflows = Vec::new();
-
-    for extension in &["yml", "yaml"] {
-        let pattern = workflows_dir
-            .join(format!("*.{extension}"))
-            .to_string_lossy()
-            .to_string();
-
-        for entry in glob(&pattern)? {
-            match entry {
-                Ok(path) => workflows.push(path),
-                Err(_e) => {}
-            }
-        }
-    }
-
-    Ok(workflows)
-}
-
 /// Parser for extracting action information from workflow files.
 pub struct FileScanner {
     /// Root directory of the repository.
     repo_root: PathBuf,
-    /// Path to the `.github/workflows` directory.
-    workflows_dir: PathBuf,
+    /// Which files are scanned as workflows.
+    workflows: Workflows,
 }
 
 impl FileScanner {
@@ -110,10 +76,18 @@ impl FileScanner {
     pub fn new(repo_root: &Path) -> Self {
         Self {
             repo_root: repo_root.to_path_buf(),
-            workflows_dir: repo_root.join(".github").join("workflows"),
+            workflows: Workflows::default(),
         }
     }
 
+    /// Scan the files the `[workflows]` section of `gx.toml` selects instead of
+    /// every workflow under `.github/workflows`.
+    #[must_use]
+    pub fn with_workflows(mut self, workflows: Workflows) -> Self {
+        self.workflows = workflows;
+        self
+    }
+
     /// Compute the path relative to the repo root for use in `WorkflowLocation`.
     fn rel_path(&self, workflow_path: &Path) -> WorkflowPath {
         WorkflowPath::new(
@@ -125,17 +99,18 @@ impl FileScanner {
         )
     }
 
-    /// Find all workflow files in the repository's `.github/workflows` folder.
+    /// Find all workflow files the scanner's `[workflows]` patterns select.
     ///
     /// # Errors
     ///
-    /// Returns an error if the glob pattern is invalid.
+    /// Returns an error if a glob pattern is invalid.
     pub fn find_workflows(&self) -> Result<Vec<PathBuf>, WorkflowError> {
-        find_workflow_files(&self.workflows_dir).map_err(Into::into)
+        find_workflow_files(&self.repo_root, &self.workflows)
     }
 
-    /// Parse a workflow file once and return both the structural `Parsed` model and
-    /// the list of `uses:` action references with their location metadata.
+    /// Parse a workflow file once and return both the structural `Parsed` model of each of
+    /// its documents and the list of `uses:` action references with their location
+    /// metadata.
     ///
     /// The action list is derived from `parsed.jobs[].steps[].uses`, each carrying its
     /// inline version comment (e.g. `# v4`).
@@ -146,14 +121,15 @@ impl FileScanner {
     fn extract_workflow(
         workflow_path: &Path,
         workflow_rel_path: &WorkflowPath,
-    ) -> Result<(Parsed, Vec<ExtractedAction>), IoWorkflowError> {
-        let content =
-            fs::read_to_string(workflow_path).map_err(|source| IoWorkflowError::Read {
-                path: workflow_path.to_path_buf(),
-                source,
-            })?;
+    ) -> Result<(Vec<Parsed>, Vec<ExtractedAction>), IoWorkflowError> {
+        let raw = fs::read_to_string(workflow_path).map_err(|source| IoWorkflowError::Read {
+            path: workflow_path.to_path_buf(),
+            source,
+        })?;
+        // Span offsets start after a byte-order mark, so locate them in the text without it.
+        let content = raw.strip_prefix('\u{feff}').unwrap_or(&raw);
 
-        let parsed = Parsed::from_yaml(workflow_rel_path.clone(), &content).map_err(|source| {
+        let parsed = Parsed::documents(workflow_rel_path, content).map_err(|source| {
             IoWorkflowError::Parse {
                 path: workflow_path.to_path_buf(),
                 source,
@@ -162,29 +138,36 @@ impl FileScanner {
 
         let mut actions = Vec::new();
 
-        for job in &parsed.jobs {
+        for job in parsed.iter().flat_map(|document| &document.jobs) {
             for (step_idx, step) in job.steps.iter().enumerate() {
-                let Some(uses) = step.uses_ref() else {
+                let Some(uses) = step.uses_ref().filter(|_| !step.uses_is_dynamic()) else {
                     continue;
                 };
-                let Some(cap) = USES_RE.captures(uses) else {
+                let Some((action_name, uses_ref)) = uses.split_once('@') else {
                     continue;
                 };
-                let action_name = cap[1].to_string();
-                let uses_ref = cap[2].to_string();
-
-                if action_name.starts_with('.') || action_name.starts_with("docker://") {
+                if action_name.is_empty()
+                    || uses_ref.is_empty()
+                    || action_name.starts_with('.')
+                    || action_name.starts_with("docker://")
+                {
                     continue;
                 }
 
-                let comment = step.uses_comment().map(ToOwned::to_owned);
+                // saphyr drops the comment of a scalar reached through an alias, so fall
+                // back to the one written after the anchored scalar.
+                let comment = step
+                    .uses_comment()
+                    .or_else(|| step.uses_span(content)?.comment_text(content))
+                    .map(ToOwned::to_owned);
 
                 actions.push(ExtractedAction {
-                    uses_ref: UsesRef::new(action_name, uses_ref, comment),
+                    uses_ref: UsesRef::new(action_name.to_owned(), uses_ref.to_owned(), comment),
                     location: crate::domain::workflow_actions::Location {
                         workflow: workflow_rel_path.clone(),
                         job: Some(JobId::from(job.id.clone())),
                         step: StepIndex::try_from(step_idx).ok(),
+                        step_name: step.key().map(ToOwned::to_owned),
                         line: step.uses_line(),
                     },
                 });
@@ -212,6 +195,56 @@ impl FileScanner {
         Ok(action_set)
     }
 
+    /// Find the container images that steps run with `uses: docker://image:tag`, and
+    /// those of job `container:` and `services:` entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a workflow file cannot be read or parsed.
+    pub fn scan_images(&self) -> Result<Vec<LocatedImage>, WorkflowError> {
+        let mut images = Vec::new();
+        for workflow_path in self.find_workflows()? {
+            let rel = self.rel_path(&workflow_path);
+            let (parsed, _) = Self::extract_workflow(&workflow_path, &rel)?;
+            for job in parsed.iter().flat_map(|document| &document.jobs) {
+                let containers = job.container.iter().chain(job.services.values());
+                images.extend(containers.filter_map(|container| {
+                    Some(LocatedImage {
+                        image: ImageRef::parse(container.image()?, container.image_comment())?,
+                        location: Location {
+                            workflow: rel.clone(),
+                            job: Some(JobId::from(job.id.clone())),
+                            step: None,
+                            step_name: None,
+                            line: container.image_line(),
+                        },
+                    })
+                }));
+                for (step_idx, step) in job.steps.iter().enumerate() {
+                    let Some(image) = step
+                        .uses_ref()
+                        .filter(|_| !step.uses_is_dynamic())
+                        .and_then(|uses| uses.strip_prefix("docker://"))
+                        .and_then(|reference| ImageRef::parse(reference, step.uses_comment()))
+                    else {
+                        continue;
+                    };
+                    images.push(LocatedImage {
+                        image,
+                        location: Location {
+                            workflow: rel.clone(),
+                            job: Some(JobId::from(job.id.clone())),
+                            step: StepIndex::try_from(step_idx).ok(),
+                            step_name: step.key().map(ToOwned::to_owned),
+                            line: step.uses_line(),
+                        },
+                    });
+                }
+            }
+        }
+        Ok(images)
+    }
+
     /// Convert extracted actions from a single file into `LocatedAction` items.
     fn located_from_file(
         workflow_path: &Path,
@@ -281,7 +314,7 @@ impl crate::domain::workflow::Scanner for FileScanner {
                         location: a.location,
                     }),
             );
-            parsed.push(p);
+            parsed.extend(p);
         }
         Ok((located, parsed))
     }
@@ -473,3 +473,68 @@ fn scan_iterator_yields_error_for_malformed_file_without_aborting() {
         "Expected at least one Err result from bad.yml"
     );
 }
+
+#[test]
+fn scan_images_finds_docker_steps_containers_and_services() {
+    let temp_dir = TempDir::new().unwrap();
+    let content = "jobs:
+  build:
+    container:
+      image: node:20
+    services:
+      db:
+        image: postgres@sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef # 16
+    steps:
+      - uses: docker://alpine:3.19
+";
+    create_test_workflow(temp_dir.path(), "ci.yml", content);
+
+    let scanner = FileWorkflowScanner::new(temp_dir.path());
+    let images = scanner.scan_images().unwrap();
+
+    let keys: Vec<(Option<String>, Option<u32>)> = images
+        .iter()
+        .map(|located| (located.image.lock_key(), located.location.line))
+        .collect();
+    assert_eq!(
+        keys,
+        vec![
+            (Some("node:20".to_owned()), Some(4)),
+            (Some("postgres:16".to_owned()), Some(7)),
+            (Some("alpine:3.19".to_owned()), Some(9)),
+        ]
+    );
+}
+
+#[test]
+fn scan_reads_quoted_flow_style_spaced_and_aliased_uses_values() {
+    let temp_dir = TempDir::new().unwrap();
+    let content = "x-setup: &setup
+  uses: actions/setup-node@0123456789abcdef0123456789abcdef01234567 # v4.0.2
+jobs:
+  build:
+    steps:
+      - uses:   \"actions/checkout@v4\"   #v4.1.0
+      - { uses: 'actions/cache@v3', with: { key: x } } # cache
+      - *setup
+      - uses: actions/checkout@${{ inputs.ref }}
+";
+    create_test_workflow(temp_dir.path(), "ci.yml", content);
+
+    let located = FileWorkflowScanner::new(temp_dir.path())
+        .scan_all_located()
+        .unwrap();
+
+    let found: Vec<(String, String)> = located
+        .iter()
+        .map(|a| (a.action.id.to_string(), a.action.version.to_string()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("actions/checkout".to_owned(), "v4.1.0".to_owned()),
+            ("actions/cache".to_owned(), "v3".to_owned()),
+            ("actions/setup-node".to_owned(), "v4.0.2".to_owned()),
+        ]
+    );
+}
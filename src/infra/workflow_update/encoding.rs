@@ -0,0 +1,67 @@
+/// The UTF-8 byte-order mark some Windows editors write at the start of a file.
+const BOM: char = '\u{feff}';
+
+/// How a workflow file was written on disk, beyond its YAML text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Encoding {
+    /// The file starts with a byte-order mark.
+    bom: bool,
+    /// Every line of the file ends in `\r\n`.
+    crlf: bool,
+}
+
+impl Encoding {
+    /// Detect how `raw`, the file as read, is written, and return the text to edit:
+    /// `raw` without its byte-order mark, so byte offsets match what the parser sees.
+    pub(super) fn detect(raw: &str) -> (Self, &str) {
+        let text = raw.strip_prefix(BOM).unwrap_or(raw);
+        let newlines = text.matches('\n').count();
+        let encoding = Self {
+            bom: text.len() < raw.len(),
+            crlf: newlines > 0 && text.matches("\r\n").count() == newlines,
+        };
+        (encoding, text)
+    }
+
+    /// `content`, edited from the text [`Encoding::detect`] returned, as it should be
+    /// written back: with the byte-order mark restored and, in a `\r\n` file, every
+    /// line ending in `\r\n`. A file with mixed line endings is left as it is.
+    pub(super) fn restore(self, content: &str) -> String {
+        let body = if self.crlf {
+            content.replace("\r\n", "\n").replace('\n', "\r\n")
+        } else {
+            content.to_owned()
+        };
+        if self.bom {
+            format!("{BOM}{body}")
+        } else {
+            body
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Encoding;
+
+    #[test]
+    fn crlf_and_bom_survive_a_round_trip() {
+        let raw = "\u{feff}on: push\r\njobs: {}\r\n";
+        let (encoding, text) = Encoding::detect(raw);
+
+        assert_eq!(text, "on: push\r\njobs: {}\r\n");
+        let edited = text.replace("jobs: {}\r\n", "jobs: {}\r\n# added\n");
+        assert_eq!(
+            encoding.restore(&edited),
+            "\u{feff}on: push\r\njobs: {}\r\n# added\r\n"
+        );
+    }
+
+    #[test]
+    fn mixed_line_endings_are_left_alone() {
+        let raw = "on: push\r\njobs: {}\n";
+        let (encoding, text) = Encoding::detect(raw);
+
+        assert_eq!(encoding.restore(text), raw);
+    }
+}
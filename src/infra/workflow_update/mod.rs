@@ -0,0 +1,12 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// Byte-order mark and line endings of a workflow file, kept across a rewrite.
+mod encoding;
+/// Rewriting `uses:` references and container images at their exact byte spans.
+mod spans;
+/// Checking a rewritten workflow before it replaces the original.
+mod verify;
+/// Workflow file updates: pins, renames, and image digests.
+mod writer;
+
+pub use writer::WorkflowWriter;
@@ -0,0 +1,229 @@
+use crate::domain::action::identity::ActionId;
+use crate::domain::action::resolved::ResolvedAction;
+use crate::domain::action::uses_ref::{RATCHET_PREFIX, pinned_version};
+use crate::domain::diff::WorkflowPatch;
+use crate::domain::image::{Digest, Reference as ImageRef};
+use crate::domain::workflow_actions::{StepIndex, WorkflowPath};
+use crate::domain::workflow_parsed::{Parsed, ScalarSpan, Step};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::Path;
+
+/// Format a `ResolvedAction` into the workflow ref string.
+///
+/// This is the **single place** where `"SHA # version"` formatting exists.
+pub(super) fn format_uses_ref(action: &ResolvedAction) -> String {
+    match &action.version {
+        Some(v) => format!("{} # {v}", action.sha),
+        None => action.sha.to_string(),
+    }
+}
+
+/// The new content of a workflow file and a label per change made.
+type Rewritten = (String, Vec<String>);
+
+/// Bytes of the source to replace, and what with.
+struct Edit {
+    /// The replaced bytes.
+    range: Range<usize>,
+    /// Their replacement.
+    text: String,
+}
+
+/// Pin the `uses:` of each step `patch` covers. A step pin applies to its own step only;
+/// an action pin to every step using the action that has no step pin of its own.
+///
+/// # Errors
+///
+/// Returns the underlying `serde_saphyr` error if the workflow cannot be parsed.
+pub(super) fn pin_steps(
+    content: &str,
+    patch: &WorkflowPatch,
+) -> Result<Rewritten, Box<serde_saphyr::Error>> {
+    let documents = parse(&patch.path, content)?;
+    let by_action: HashMap<&ActionId, &ResolvedAction> =
+        patch.pins.iter().map(|pin| (&pin.id, pin)).collect();
+    let by_step: HashMap<(&str, StepIndex), &ResolvedAction> = patch
+        .steps
+        .iter()
+        .map(|step| ((step.job.as_str(), step.step), &step.pin))
+        .collect();
+
+    let mut edits = Vec::new();
+    let mut changes = Vec::new();
+    for job in documents.iter().flat_map(|document| &document.jobs) {
+        for (index, step) in job.steps.iter().enumerate() {
+            let Some((name, span)) = located_uses(step, content) else {
+                continue;
+            };
+            let id = ActionId::from(name);
+            let Some(pin) = StepIndex::try_from(index)
+                .ok()
+                .and_then(|step_index| by_step.get(&(job.id.as_str(), step_index)))
+                .filter(|step_pin| step_pin.id == id)
+                .or_else(|| by_action.get(&id))
+            else {
+                continue;
+            };
+            let reference = format!("{name}@{}", pin.sha);
+            let version = pin.version.as_ref().map(ToString::to_string);
+            let label = format!("{id}@{}", format_uses_ref(pin));
+            let found = replace(content, &span, reference, version.as_deref());
+            add_edits(&mut edits, &mut changes, label, found);
+        }
+    }
+    Ok((apply(content, edits), changes))
+}
+
+/// Pin `uses: docker://name:tag` steps and job `container:`/`services:` images written
+/// as one of `pins` to its digest, keeping the tag as a comment.
+///
+/// # Errors
+///
+/// Returns the underlying `serde_saphyr` error if the workflow cannot be parsed.
+pub(super) fn pin_images(
+    path: &Path,
+    content: &str,
+    pins: &[(ImageRef, Digest)],
+) -> Result<Rewritten, Box<serde_saphyr::Error>> {
+    let documents = parse(path, content)?;
+    let mut edits = Vec::new();
+    let mut changes = Vec::new();
+    for job in documents.iter().flat_map(|document| &document.jobs) {
+        let containers = job
+            .container
+            .iter()
+            .chain(job.services.values())
+            .filter_map(|container| Some(("", container.image()?, container.image_span(content)?)));
+        let steps = job.steps.iter().filter_map(|step| {
+            let image = step.uses_ref()?.strip_prefix("docker://")?;
+            Some(("docker://", image, step.uses_span(content)?))
+        });
+        for (prefix, written, span) in containers.chain(steps) {
+            let Some((image, tag, digest)) = pins.iter().find_map(|(image, digest)| {
+                let tag = image.tag()?;
+                (written == format!("{}:{tag}", image.name())).then_some((image, tag, digest))
+            }) else {
+                continue;
+            };
+            let pinned = image.pinned(digest);
+            let label = format!("{image} → {pinned}");
+            let found = replace(content, &span, format!("{prefix}{pinned}"), Some(tag));
+            add_edits(&mut edits, &mut changes, label, found);
+        }
+    }
+    Ok((apply(content, edits), changes))
+}
+
+/// Rewrite the ratchet comment after each pinned `uses:`, such as
+/// `# ratchet:actions/checkout@v4`, into the version alone: `# v4`. References ratchet
+/// was told to leave unpinned keep their `# ratchet:exclude`.
+///
+/// # Errors
+///
+/// Returns the underlying `serde_saphyr` error if the workflow cannot be parsed.
+pub(super) fn migrate_ratchet_comments(
+    path: &Path,
+    content: &str,
+) -> Result<Rewritten, Box<serde_saphyr::Error>> {
+    let documents = parse(path, content)?;
+    let mut edits = Vec::new();
+    let mut changes = Vec::new();
+    for job in documents.iter().flat_map(|document| &document.jobs) {
+        for step in &job.steps {
+            let Some((name, span)) = located_uses(step, content) else {
+                continue;
+            };
+            let Some(comment) = span
+                .comment_text(content)
+                .filter(|text| text.starts_with(RATCHET_PREFIX))
+            else {
+                continue;
+            };
+            let (Some(range), Some(version)) = (span.comment.clone(), pinned_version(comment))
+            else {
+                continue;
+            };
+            let label = format!("{name}: # {comment} → # {version}");
+            let found = vec![Edit {
+                range,
+                text: format!("# {version}"),
+            }];
+            add_edits(&mut edits, &mut changes, label, found);
+        }
+    }
+    Ok((apply(content, edits), changes))
+}
+
+/// Parse every document of `content`, read from the workflow file at `path`.
+fn parse(path: &Path, content: &str) -> Result<Vec<Parsed>, Box<serde_saphyr::Error>> {
+    Parsed::documents(&WorkflowPath::new(path.to_string_lossy()), content)
+}
+
+/// The action name and byte span of a step's remote `uses:` reference.
+fn located_uses<'step>(step: &'step Step, content: &str) -> Option<(&'step str, ScalarSpan)> {
+    if step.uses_is_dynamic() {
+        return None;
+    }
+    let (name, _) = step.uses_ref()?.split_once('@')?;
+    Some((name, step.uses_span(content)?))
+}
+
+/// Queue `found`, recording `label` the first time edits under it change anything.
+fn add_edits(edits: &mut Vec<Edit>, changes: &mut Vec<String>, label: String, found: Vec<Edit>) {
+    if !found.is_empty() && !changes.contains(&label) {
+        changes.push(label);
+    }
+    edits.extend(found);
+}
+
+/// The edits writing `reference` into the scalar at `span` and `comment` after it,
+/// leaving its quotes, the spacing before an existing comment, and unchanged bytes alone.
+fn replace(
+    content: &str,
+    span: &ScalarSpan,
+    reference: String,
+    comment: Option<&str>,
+) -> Vec<Edit> {
+    let mut edits = vec![Edit {
+        range: span.unquoted(),
+        text: reference,
+    }];
+    match (&span.comment, comment) {
+        (Some(range), Some(text)) => edits.push(Edit {
+            range: range.clone(),
+            text: format!("# {text}"),
+        }),
+        (Some(range), None) => edits.push(Edit {
+            range: span.value.end..range.end,
+            text: String::new(),
+        }),
+        (None, Some(text)) if span.ends_line(content) => edits.push(Edit {
+            range: span.value.end..span.value.end,
+            text: format!(" # {text}"),
+        }),
+        (None, _) => {}
+    }
+    edits.retain(|edit| content.get(edit.range.clone()) != Some(edit.text.as_str()));
+    edits
+}
+
+/// Apply `edits` to `content`. Steps reached through the same anchor produce the same
+/// edits; only the first edit of any overlapping bytes is kept.
+fn apply(content: &str, mut edits: Vec<Edit>) -> String {
+    edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+    let mut kept: Vec<Edit> = Vec::new();
+    for edit in edits {
+        let overlaps = kept.last().is_some_and(|last| {
+            edit.range.start < last.range.end || edit.range.start == last.range.start
+        });
+        if !overlaps {
+            kept.push(edit);
+        }
+    }
+    let mut updated = content.to_owned();
+    for edit in kept.into_iter().rev() {
+        updated.replace_range(edit.range, &edit.text);
+    }
+    updated
+}
@@ -0,0 +1,512 @@
+use super::WorkflowWriter;
+use crate::config::Workflows;
+use crate::domain::action::identity::{ActionId, CommitSha, Repository, Version};
+use crate::domain::action::resolved::ResolvedAction;
+use crate::domain::diff::{StepPin, WorkflowPatch};
+use crate::domain::image::{Digest, Reference as ImageRef};
+use crate::domain::workflow_actions::{JobId, StepIndex};
+use crate::infra::workflow_update::spans::format_uses_ref;
+use std::fs;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn create_test_workflow(dir: &Path, name: &str, content: &str) -> PathBuf {
+    let workflows_dir = dir.join(".github").join("workflows");
+    fs::create_dir_all(&workflows_dir).unwrap();
+    let file_path = workflows_dir.join(name);
+    let mut file = fs::File::create(&file_path).unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+    file_path
+}
+
+#[test]
+fn find_workflows_honors_include_and_exclude_patterns() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let ci = create_test_workflow(root, "ci.yml", "on: push\n");
+    create_test_workflow(root, "generated-release.yml", "on: push\n");
+    fs::create_dir_all(root.join("ci").join("nested")).unwrap();
+    fs::write(
+        root.join("ci").join("nested").join("deploy.yml"),
+        "on: push\n",
+    )
+    .unwrap();
+    fs::write(root.join("ci").join("notes.md"), "").unwrap();
+
+    let default = WorkflowWriter::new(root).find_workflows().unwrap();
+    assert_eq!(default.len(), 2);
+
+    let workflows = Workflows {
+        include: vec![".github/workflows/**".to_owned(), "ci/**.yml".to_owned()],
+        exclude: vec!["**/generated-*.yml".to_owned()],
+    };
+    let found = WorkflowWriter::new(root)
+        .with_workflows(workflows)
+        .find_workflows()
+        .unwrap();
+    assert_eq!(
+        found,
+        vec![ci, root.join("ci").join("nested").join("deploy.yml")]
+    );
+}
+
+#[test]
+fn find_workflows_includes_org_workflow_templates() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let ci = create_test_workflow(root, "ci.yml", "on: push\n");
+    let templates = root.join("workflow-templates");
+    fs::create_dir_all(&templates).unwrap();
+    fs::write(templates.join("node.yml"), "on: push\n").unwrap();
+    fs::write(templates.join("node.properties.json"), "{}").unwrap();
+
+    let found = WorkflowWriter::new(root).find_workflows().unwrap();
+
+    assert_eq!(found, vec![ci, templates.join("node.yml")]);
+}
+
+#[cfg(unix)]
+#[test]
+fn find_workflows_descends_into_subdirectories_and_skips_links_out_of_the_repo() {
+    use std::os::unix::fs::symlink;
+
+    let temp_dir = TempDir::new().unwrap();
+    let outside = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let ci = create_test_workflow(root, "ci.yml", "on: push\n");
+    let nested = root
+        .join(".github")
+        .join("workflows")
+        .join("synced")
+        .join("deploy.yaml");
+    fs::create_dir_all(nested.parent().unwrap()).unwrap();
+    fs::write(&nested, "on: push\n").unwrap();
+    let foreign = outside.path().join("foreign.yml");
+    fs::write(&foreign, "on: push\n").unwrap();
+    let workflows_dir = root.join(".github").join("workflows");
+    symlink(&foreign, workflows_dir.join("foreign.yml")).unwrap();
+    symlink(&ci, workflows_dir.join("zz-ci-link.yml")).unwrap();
+
+    let found = WorkflowWriter::new(root).find_workflows().unwrap();
+
+    assert_eq!(found, vec![ci, nested]);
+}
+
+#[test]
+fn workflows_using_matches_step_references_only() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    let ci = create_test_workflow(
+        root,
+        "ci.yml",
+        "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - uses: actions/checkout@v4\n",
+    );
+    create_test_workflow(
+        root,
+        "docs.yml",
+        "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      # was actions/checkout@v3\n      - uses: my-actions/checkout@v4\n      - run: echo actions/checkout@v4\n",
+    );
+
+    let using = WorkflowWriter::new(root)
+        .workflows_using(&[ActionId::from("actions/checkout")])
+        .unwrap();
+
+    assert_eq!(using, vec![ci]);
+}
+
+#[test]
+fn apply_patches_updates_workflow() {
+    let temp_dir = TempDir::new().unwrap();
+    let content = "name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v3
+      - uses: actions/setup-node@v3
+";
+    let workflow_path = create_test_workflow(temp_dir.path(), "ci.yml", content);
+
+    let writer = WorkflowWriter::new(temp_dir.path());
+    let patches = vec![WorkflowPatch {
+        path: workflow_path.clone(),
+        pins: vec![ResolvedAction {
+            id: ActionId::from("actions/checkout"),
+            sha: CommitSha::from("abc123def456"),
+            version: Some(Version::from("v4")),
+        }],
+        steps: vec![],
+    }];
+
+    let results = writer.apply_patches(&patches).unwrap();
+
+    assert_eq!(results.len(), 1);
+    assert!(results[0].changes[0].contains("actions/checkout@abc123def456 # v4"));
+
+    let updated_workflow = fs::read_to_string(&workflow_path).unwrap();
+    assert!(updated_workflow.contains("actions/checkout@abc123def456 # v4"));
+    assert!(updated_workflow.contains("actions/setup-node@v3")); // unchanged
+}
+
+#[test]
+fn apply_patches_uses_commit_sha_with_comment() {
+    let temp_dir = TempDir::new().unwrap();
+    let content = "name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+";
+    let workflow_path = create_test_workflow(temp_dir.path(), "ci.yml", content);
+
+    let writer = WorkflowWriter::new(temp_dir.path());
+    let patches = vec![WorkflowPatch {
+        path: workflow_path.clone(),
+        pins: vec![ResolvedAction {
+            id: ActionId::from("actions/checkout"),
+            sha: CommitSha::from("abc123def456"),
+            version: Some(Version::from("v4")),
+        }],
+        steps: vec![],
+    }];
+
+    let results = writer.apply_patches(&patches).unwrap();
+
+    assert_eq!(results.len(), 1);
+
+    // Verify the workflow was updated with the SHA and comment
+    let updated = fs::read_to_string(&workflow_path).unwrap();
+    assert!(
+        updated.contains("actions/checkout@abc123def456 # v4"),
+        "Expected SHA with comment, got: {updated}"
+    );
+}
+
+#[test]
+fn apply_patches_no_duplicate_comments() {
+    let temp_dir = TempDir::new().unwrap();
+    // Start with a workflow that already has a comment
+    let content = "name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v3 # v3
+      - uses: actions/setup-node@old_sha # v2
+";
+    let workflow_path = create_test_workflow(temp_dir.path(), "ci.yml", content);
+
+    let writer = WorkflowWriter::new(temp_dir.path());
+    let patches = vec![WorkflowPatch {
+        path: workflow_path.clone(),
+        pins: vec![
+            ResolvedAction {
+                id: ActionId::from("actions/checkout"),
+                sha: CommitSha::from("abc123def456"),
+                version: Some(Version::from("v4")),
+            },
+            ResolvedAction {
+                id: ActionId::from("actions/setup-node"),
+                sha: CommitSha::from("xyz789012345"),
+                version: Some(Version::from("v3")),
+            },
+        ],
+        steps: vec![],
+    }];
+
+    let results = writer.apply_patches(&patches).unwrap();
+
+    assert_eq!(results.len(), 1);
+
+    // Verify no duplicate comments
+    let updated = fs::read_to_string(&workflow_path).unwrap();
+
+    // Should have the new SHA with new comment
+    assert!(
+        updated.contains("actions/checkout@abc123def456 # v4"),
+        "Expected new SHA with comment, got: {updated}"
+    );
+
+    // Should NOT have duplicate comments like "# v4 # v3"
+    assert!(
+        !updated.contains("# v4 # v3"),
+        "Found duplicate comment in: {updated}"
+    );
+    assert!(
+        !updated.contains("# v3 # v3"),
+        "Found duplicate comment in: {updated}"
+    );
+
+    // Verify setup-node was also updated correctly
+    assert!(
+        updated.contains("actions/setup-node@xyz789012345 # v3"),
+        "Expected setup-node with new SHA and comment, got: {updated}"
+    );
+    assert!(
+        !updated.contains("# v3 # v2"),
+        "Found duplicate comment in: {updated}"
+    );
+}
+
+#[test]
+fn format_uses_ref_bare_sha() {
+    let action = ResolvedAction {
+        id: ActionId::from("actions/checkout"),
+        sha: CommitSha::from("abc123"),
+        version: None,
+    };
+    assert_eq!(format_uses_ref(&action), "abc123");
+}
+
+#[test]
+fn format_uses_ref_with_version() {
+    let action = ResolvedAction {
+        id: ActionId::from("actions/checkout"),
+        sha: CommitSha::from("abc123"),
+        version: Some(Version::from("v4.2.1")),
+    };
+    assert_eq!(format_uses_ref(&action), "abc123 # v4.2.1");
+}
+
+#[test]
+fn rename_repositories_keeps_subpaths_and_refs() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = create_test_workflow(
+        temp_dir.path(),
+        "ci.yml",
+        "jobs:\n  build:\n    steps:\n      - uses: Old-Org/tool@abc123 # v1\n      - uses: old-org/tool/setup@v1\n      - uses: old-org/toolkit@v2\n",
+    );
+    let writer = WorkflowWriter::new(temp_dir.path());
+
+    let results = writer
+        .rename_repositories(&[(
+            Repository::from("old-org/tool"),
+            Repository::from("new-org/tool"),
+        )])
+        .unwrap();
+
+    assert_eq!(results.len(), 1);
+    let updated = fs::read_to_string(&path).unwrap();
+    assert!(
+        updated.contains("uses: new-org/tool@abc123 # v1"),
+        "{updated}"
+    );
+    assert!(updated.contains("uses: new-org/tool/setup@v1"), "{updated}");
+    assert!(updated.contains("uses: old-org/toolkit@v2"), "{updated}");
+}
+
+#[test]
+fn pin_images_pins_docker_steps_and_containers_keeping_the_tag_as_comment() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = create_test_workflow(
+        temp_dir.path(),
+        "ci.yml",
+        "jobs:\n  build:\n    container: alpine:3.19\n    steps:\n      - uses: docker://alpine:3.19\n      - uses: docker://alpine:3.20\n",
+    );
+    let writer = WorkflowWriter::new(temp_dir.path());
+    let digest =
+        Digest::from("sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef");
+
+    let results = writer
+        .pin_images(&[(ImageRef::parse("alpine:3.19", None).unwrap(), digest)])
+        .unwrap();
+
+    assert_eq!(results.len(), 1);
+    let updated = fs::read_to_string(&path).unwrap();
+    assert!(
+            updated.contains("uses: docker://alpine@sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef # 3.19"),
+            "{updated}"
+        );
+    assert!(
+            updated.contains("container: alpine@sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef # 3.19"),
+            "{updated}"
+        );
+    assert!(
+        updated.contains("uses: docker://alpine:3.20\n"),
+        "{updated}"
+    );
+}
+
+#[test]
+fn apply_patches_pins_two_steps_of_one_action_to_different_versions() {
+    let temp_dir = TempDir::new().unwrap();
+    let content = "jobs:
+  build:
+    steps:
+      - uses: \"actions/checkout@v3\"   # v3
+      - run: 'echo uses: actions/checkout@v3'
+      - uses: actions/checkout@v3
+";
+    let workflow_path = create_test_workflow(temp_dir.path(), "ci.yml", content);
+    let step_pin = |step: u16, sha: &str, version: &str| StepPin {
+        job: JobId::from("build"),
+        step: StepIndex::from(step),
+        pin: ResolvedAction {
+            id: ActionId::from("actions/checkout"),
+            sha: CommitSha::from(sha),
+            version: Some(Version::from(version)),
+        },
+    };
+    let patches = vec![WorkflowPatch {
+        path: workflow_path.clone(),
+        pins: vec![],
+        steps: vec![step_pin(0, "aaa111", "v4"), step_pin(2, "bbb222", "v3.6.0")],
+    }];
+
+    let results = WorkflowWriter::new(temp_dir.path())
+        .apply_patches(&patches)
+        .unwrap();
+
+    assert_eq!(results[0].changes.len(), 2);
+    assert_eq!(
+        fs::read_to_string(&workflow_path).unwrap(),
+        "jobs:
+  build:
+    steps:
+      - uses: \"actions/checkout@aaa111\"   # v4
+      - run: 'echo uses: actions/checkout@v3'
+      - uses: actions/checkout@bbb222 # v3.6.0
+"
+    );
+}
+
+#[test]
+fn apply_patches_preserves_anchors_aliases_quoting_and_layout() {
+    let temp_dir = TempDir::new().unwrap();
+    let content = "x-steps:
+  checkout: &checkout
+    uses: 'actions/checkout@v3'    # v3
+    with: { fetch-depth: 0 }
+  cache: &cache
+    uses: actions/cache@v3
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+        -   *checkout
+        -   <<: *cache
+            with:
+              key: build # keep this comment
+        -   { uses: \"actions/setup-node@v3\", with: { node-version: 20 } }
+  test:
+    steps:
+      - *checkout
+";
+    let workflow_path = create_test_workflow(temp_dir.path(), "ci.yml", content);
+    let pin = |id: &str, sha: &str| ResolvedAction {
+        id: ActionId::from(id),
+        sha: CommitSha::from(sha),
+        version: Some(Version::from("v4")),
+    };
+    let patches = vec![WorkflowPatch {
+        path: workflow_path.clone(),
+        pins: vec![
+            pin("actions/checkout", "aaa111"),
+            pin("actions/cache", "bbb222"),
+            pin("actions/setup-node", "ccc333"),
+        ],
+        steps: vec![],
+    }];
+
+    let results = WorkflowWriter::new(temp_dir.path())
+        .apply_patches(&patches)
+        .unwrap();
+
+    assert_eq!(results[0].changes.len(), 3);
+    assert_eq!(
+        fs::read_to_string(&workflow_path).unwrap(),
+        content
+            .replace(
+                "'actions/checkout@v3'    # v3",
+                "'actions/checkout@aaa111'    # v4"
+            )
+            .replace("actions/cache@v3", "actions/cache@bbb222 # v4")
+            .replace("actions/setup-node@v3", "actions/setup-node@ccc333")
+    );
+}
+
+#[test]
+fn apply_patches_keeps_crlf_line_endings_and_bom() {
+    let temp_dir = TempDir::new().unwrap();
+    let content = "\u{feff}on: push\r\njobs:\r\n  build:\r\n    steps:\r\n      - uses: actions/checkout@v3\r\n      - uses: old-org/tool@v1 # v1\r\n";
+    let workflow_path = create_test_workflow(temp_dir.path(), "ci.yml", content);
+    let writer = WorkflowWriter::new(temp_dir.path());
+
+    writer
+        .apply_patches(&[WorkflowPatch {
+            path: workflow_path.clone(),
+            pins: vec![ResolvedAction {
+                id: ActionId::from("actions/checkout"),
+                sha: CommitSha::from("aaa111"),
+                version: Some(Version::from("v4")),
+            }],
+            steps: vec![],
+        }])
+        .unwrap();
+    writer
+        .rename_repositories(&[(
+            Repository::from("old-org/tool"),
+            Repository::from("new-org/tool"),
+        )])
+        .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&workflow_path).unwrap(),
+        "\u{feff}on: push\r\njobs:\r\n  build:\r\n    steps:\r\n      - uses: actions/checkout@aaa111 # v4\r\n      - uses: new-org/tool@v1 # v1\r\n"
+    );
+}
+
+#[test]
+fn apply_patches_pins_every_document_and_keeps_the_separators() {
+    let temp_dir = TempDir::new().unwrap();
+    let document = "on: push\njobs:\n  build:\n    steps:\n      - uses: actions/checkout@v3\n";
+    let content = format!("---\n{document}---\n# generated\n{document}...\n");
+    let workflow_path = create_test_workflow(temp_dir.path(), "ci.yml", &content);
+
+    let writer = WorkflowWriter::new(temp_dir.path());
+    let patches = vec![WorkflowPatch {
+        path: workflow_path.clone(),
+        pins: vec![ResolvedAction {
+            id: ActionId::from("actions/checkout"),
+            sha: CommitSha::from("abc123def456"),
+            version: Some(Version::from("v4")),
+        }],
+        steps: vec![],
+    }];
+
+    writer.apply_patches(&patches).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&workflow_path).unwrap(),
+        content.replace("actions/checkout@v3", "actions/checkout@abc123def456 # v4")
+    );
+}
+
+#[test]
+fn migrate_ratchet_keeps_the_version_and_drops_the_ratchet_reference() {
+    let temp_dir = TempDir::new().unwrap();
+    let sha = "abc123def456789012345678901234567890abcd";
+    let content = format!(
+        "on: push\njobs:\n  build:\n    steps:\n      - uses: actions/checkout@{sha} # ratchet:actions/checkout@v4\n      - uses: actions/cache@main # ratchet:exclude\n      - uses: actions/setup-go@{sha} # v5\n"
+    );
+    let workflow_path = create_test_workflow(temp_dir.path(), "ci.yml", &content);
+
+    let results = WorkflowWriter::new(temp_dir.path())
+        .migrate_ratchet()
+        .unwrap();
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(
+        results[0].changes,
+        vec!["actions/checkout: # ratchet:actions/checkout@v4 → # v4".to_owned()]
+    );
+    assert_eq!(
+        fs::read_to_string(&workflow_path).unwrap(),
+        content.replace("# ratchet:actions/checkout@v4", "# v4")
+    );
+}
@@ -0,0 +1,118 @@
+use crate::domain::workflow::Error as WorkflowError;
+use crate::domain::workflow_actions::WorkflowPath;
+use crate::domain::workflow_parsed::{Parsed, Step};
+use std::path::Path;
+
+/// Check a rewrite of the workflow at `file` before it is written. `updated` must parse
+/// to the same documents, jobs, and steps as `original`, and each `uses:` the rewrite
+/// changed must read as `expected(before, after)` allows, with no more `#` in its
+/// trailing comment than before.
+///
+/// # Errors
+///
+/// Returns [`WorkflowError::UpdateFailed`] naming the first problem, and the file is left
+/// as it was.
+pub(super) fn verify(
+    file: &Path,
+    original: &str,
+    updated: &str,
+    expected: impl Fn(&str, &str) -> bool,
+) -> Result<(), WorkflowError> {
+    let fail = |reason: String| WorkflowError::UpdateFailed {
+        path: file.to_string_lossy().into_owned(),
+        reason: format!("the rewrite was not written because {reason}"),
+    };
+    let path = WorkflowPath::new(file.to_string_lossy());
+    let before = Parsed::documents(&path, original)
+        .map_err(|source| fail(format!("the original does not parse: {source}")))?;
+    let after = Parsed::documents(&path, updated)
+        .map_err(|source| fail(format!("it is not valid YAML: {source}")))?;
+    if before.len() != after.len() {
+        return Err(fail("it changed the number of documents".to_owned()));
+    }
+    for (old, new) in before.iter().zip(&after) {
+        let jobs = |parsed: &Parsed| -> Vec<(String, usize)> {
+            parsed
+                .jobs
+                .iter()
+                .map(|job| (job.id.clone(), job.steps.len()))
+                .collect()
+        };
+        if jobs(old) != jobs(new) {
+            return Err(fail("it changed the jobs or their steps".to_owned()));
+        }
+        let steps = old
+            .jobs
+            .iter()
+            .zip(&new.jobs)
+            .flat_map(|(old_job, new_job)| old_job.steps.iter().zip(&new_job.steps));
+        for (old_step, new_step) in steps {
+            check_step((old_step, original), (new_step, updated), &expected).map_err(fail)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether the `uses:` of a step, each with the content it was parsed from, changed only
+/// as `expected` allows. Returns what went wrong otherwise.
+fn check_step(
+    (before, original): (&Step, &str),
+    (after, updated): (&Step, &str),
+    expected: impl Fn(&str, &str) -> bool,
+) -> Result<(), String> {
+    if before.uses_ref() == after.uses_ref() {
+        return Ok(());
+    }
+    let (Some(old_uses), Some(new_uses)) = (before.uses_ref(), after.uses_ref()) else {
+        return Err("it added or removed a `uses:`".to_owned());
+    };
+    if !expected(old_uses, new_uses) {
+        return Err(format!("`uses: {old_uses}` became `{new_uses}`"));
+    }
+    if comment_marks(after, updated) > comment_marks(before, original) {
+        return Err(format!("`uses: {new_uses}` has a duplicated comment"));
+    }
+    Ok(())
+}
+
+/// How many `#` the trailing comment of a step's `uses:` holds after its leading one.
+fn comment_marks(step: &Step, content: &str) -> usize {
+    step.uses_span(content)
+        .and_then(|span| {
+            span.comment_text(content)
+                .map(|text| text.matches('#').count())
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::verify;
+    use std::path::Path;
+
+    const ORIGINAL: &str = "jobs:\n  build:\n    steps:\n      - uses: actions/checkout@v4 # v4\n";
+
+    fn pinned(before: &str, after: &str) -> bool {
+        before.split_once('@').map(|(name, _)| name) == after.split_once('@').map(|(name, _)| name)
+    }
+
+    #[test]
+    fn verify_accepts_the_expected_rewrite_and_rejects_corrupt_ones() {
+        let file = Path::new(".github/workflows/ci.yml");
+        let good = ORIGINAL.replace("@v4 # v4", "@abc123 # v4.2.0");
+
+        verify(file, ORIGINAL, &good, pinned).unwrap();
+        for corrupt in [
+            ORIGINAL.replace("@v4 # v4", "@abc123 # v4.2.0 # v4"),
+            ORIGINAL.replace("actions/checkout@v4", "actions/setup-node@abc123"),
+            ORIGINAL.replace("      - uses", "    - uses: x\n  - uses"),
+            ORIGINAL.replace("steps:\n", "steps: [\n"),
+        ] {
+            assert!(
+                verify(file, ORIGINAL, &corrupt, pinned).is_err(),
+                "{corrupt}"
+            );
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use super::encoding::Encoding;
+use super::spans::{migrate_ratchet_comments, pin_images, pin_steps};
+use super::verify::verify;
+use crate::config::Workflows;
+use crate::domain::action::identity::{ActionId, Repository};
+use crate::domain::action::resolved::ResolvedAction;
+use crate::domain::diff::WorkflowPatch;
+use crate::domain::image::{Digest, Reference as ImageRef};
+use crate::domain::workflow::{Error as WorkflowError, UpdateResult};
+use crate::infra::atomic;
+use crate::infra::workflow_scan::{FileScanner, find_workflow_files};
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Writer for updating action versions in workflow files.
+pub struct WorkflowWriter {
+    /// Root directory of the repository.
+    repo_root: PathBuf,
+    /// Which files are rewritten as workflows.
+    workflows: Workflows,
+    /// When set, the only files the writer touches, such as those staged in git.
+    only: Option<Vec<PathBuf>>,
+    /// Report the changes without writing them.
+    dry_run: bool,
+}
+
+impl WorkflowWriter {
+    #[must_use]
+    pub fn new(repo_root: &Path) -> Self {
+        Self {
+            repo_root: repo_root.to_path_buf(),
+            workflows: Workflows::default(),
+            only: None,
+            dry_run: false,
+        }
+    }
+
+    /// Rewrite the files the `[workflows]` section of `gx.toml` selects instead of
+    /// every workflow under `.github/workflows`.
+    #[must_use]
+    pub fn with_workflows(mut self, workflows: Workflows) -> Self {
+        self.workflows = workflows;
+        self
+    }
+
+    /// Leave every workflow file not in `files` untouched.
+    #[must_use]
+    pub fn limited_to(mut self, files: Vec<PathBuf>) -> Self {
+        self.only = Some(files);
+        self
+    }
+
+    /// Report what would change in each file without writing anything.
+    #[must_use]
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Find all workflow files the writer's `[workflows]` patterns select.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a glob pattern is invalid.
+    pub fn find_workflows(&self) -> Result<Vec<PathBuf>, WorkflowError> {
+        let mut workflows = find_workflow_files(&self.repo_root, &self.workflows)?;
+        workflows.retain(|workflow| self.touches(workflow));
+        Ok(workflows)
+    }
+
+    /// Whether the writer may rewrite the file at `path`.
+    fn touches(&self, path: &Path) -> bool {
+        self.only
+            .as_ref()
+            .is_none_or(|files| files.iter().any(|file| file == path))
+    }
+
+    /// Find workflow files with a step whose `uses:` reference is to any of the given
+    /// actions, as the workflow scanner reads them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a workflow file cannot be read or parsed.
+    pub fn workflows_using(&self, ids: &[ActionId]) -> Result<Vec<PathBuf>, WorkflowError> {
+        let scanner = FileScanner::new(&self.repo_root).with_workflows(self.workflows.clone());
+        let mut using = Vec::new();
+        for workflow in self.find_workflows()? {
+            if scanner
+                .scan_file(&workflow)?
+                .action_ids()
+                .any(|id| ids.contains(id))
+            {
+                using.push(workflow);
+            }
+        }
+        Ok(using)
+    }
+
+    /// Apply a set of workflow patches, writing pin changes to workflow files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any workflow file cannot be updated.
+    pub fn apply_patches(
+        &self,
+        patches: &[WorkflowPatch],
+    ) -> Result<Vec<UpdateResult>, WorkflowError> {
+        let mut results = Vec::new();
+        for patch in patches.iter().filter(|patch| self.touches(&patch.path)) {
+            let result = self.pin_file(patch)?;
+            if !result.changes.is_empty() {
+                results.push(result);
+            }
+        }
+        Ok(results)
+    }
+
+    /// Update all workflow files with the same set of pins.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any workflow file cannot be processed.
+    pub fn update_all_with_pins(
+        &self,
+        pins: &[ResolvedAction],
+    ) -> Result<Vec<UpdateResult>, WorkflowError> {
+        let workflows = self.find_workflows()?;
+        let mut results = Vec::new();
+
+        for workflow in workflows {
+            let result = self.pin_file(&WorkflowPatch {
+                path: workflow,
+                pins: pins.to_vec(),
+                steps: Vec::new(),
+            })?;
+            if !result.changes.is_empty() {
+                results.push(result);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Rewrite `uses:` references to each renamed repository under its new name, keeping
+    /// subpaths and refs, e.g. `old-org/action/sub@v1` → `new-org/action/sub@v1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any workflow file cannot be read or written.
+    pub fn rename_repositories(
+        &self,
+        renamed: &[(Repository, Repository)],
+    ) -> Result<Vec<UpdateResult>, WorkflowError> {
+        let compiled: Vec<(Regex, String, String)> = renamed
+            .iter()
+            .map(|(old, new)| {
+                let escaped = regex::escape(old.as_str());
+                let pattern = format!(r#"(?i)(uses:\s*["']?){escaped}([/@])"#);
+                Regex::new(&pattern)
+                    .map_err(|e| WorkflowError::UpdateFailed {
+                        path: String::new(),
+                        reason: e.to_string(),
+                    })
+                    .map(|re| (re, format!("${{1}}{new}${{2}}"), format!("{old} → {new}")))
+            })
+            .collect::<Result<_, WorkflowError>>()?;
+
+        let mut results = Vec::new();
+        for workflow in self.find_workflows()? {
+            let result = self.rewrite(&workflow, &compiled)?;
+            if !result.changes.is_empty() {
+                results.push(result);
+            }
+        }
+        Ok(results)
+    }
+
+    /// Pin `uses: docker://name:tag` steps and job `container:`/`services:` images to
+    /// their digest, keeping the tag as a comment:
+    /// `docker://alpine:3.19` → `docker://alpine@sha256:… # 3.19`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any workflow file cannot be read or written.
+    pub fn pin_images(
+        &self,
+        pins: &[(ImageRef, Digest)],
+    ) -> Result<Vec<UpdateResult>, WorkflowError> {
+        let mut results = Vec::new();
+        for workflow in self.find_workflows()? {
+            let result = self.edit_file(
+                &workflow,
+                |content| pin_images(&workflow, content, pins),
+                |_, after| {
+                    pins.iter().any(|(image, digest)| {
+                        after == format!("docker://{}", image.pinned(digest))
+                    })
+                },
+            )?;
+            if !result.changes.is_empty() {
+                results.push(result);
+            }
+        }
+        Ok(results)
+    }
+
+    /// Rewrite ratchet's `# ratchet:owner/action@v4` comments into gx's `# v4`, leaving
+    /// every `uses:` reference as it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any workflow file cannot be read, parsed, or written.
+    pub fn migrate_ratchet(&self) -> Result<Vec<UpdateResult>, WorkflowError> {
+        let mut results = Vec::new();
+        for workflow in self.find_workflows()? {
+            let result = self.edit_file(
+                &workflow,
+                |content| migrate_ratchet_comments(&workflow, content),
+                |before, after| before == after,
+            )?;
+            if !result.changes.is_empty() {
+                results.push(result);
+            }
+        }
+        Ok(results)
+    }
+
+    /// Pin the steps of one workflow file as `patch` describes.
+    fn pin_file(&self, patch: &WorkflowPatch) -> Result<UpdateResult, WorkflowError> {
+        let shas: HashSet<&str> = patch
+            .pins
+            .iter()
+            .chain(patch.steps.iter().map(|step| &step.pin))
+            .map(|pin| pin.sha.as_str())
+            .collect();
+        self.edit_file(
+            &patch.path,
+            |content| pin_steps(content, patch),
+            |before, after| match (before.split_once('@'), after.split_once('@')) {
+                (Some((name, _)), Some((pinned, sha))) => name == pinned && shas.contains(sha),
+                _ => false,
+            },
+        )
+    }
+
+    /// Rewrite the workflow file at `file` with `edit`, which returns the new content and
+    /// its changes, writing it back if anything changed.
+    fn edit_file(
+        &self,
+        file: &Path,
+        edit: impl FnOnce(&str) -> Result<(String, Vec<String>), Box<serde_saphyr::Error>>,
+        expected: impl Fn(&str, &str) -> bool,
+    ) -> Result<UpdateResult, WorkflowError> {
+        let raw = fs::read_to_string(file).map_err(|source| WorkflowError::ScanFailed {
+            reason: format!("failed to read {}: {}", file.display(), source),
+        })?;
+        let (encoding, content) = Encoding::detect(&raw);
+        let (updated, changes) = edit(content).map_err(|source| WorkflowError::ParseFailed {
+            path: file.to_string_lossy().to_string(),
+            reason: source.to_string(),
+        })?;
+        if !changes.is_empty() {
+            verify(file, content, &updated, expected)?;
+            self.write(file, &encoding.restore(&updated))?;
+        }
+        Ok(UpdateResult {
+            file: file.to_path_buf(),
+            changes,
+        })
+    }
+
+    /// Apply each `(regex, replacement, change label)` to the workflow file, writing it
+    /// back if anything changed.
+    fn rewrite(
+        &self,
+        workflow_path: &Path,
+        compiled: &[(Regex, String, String)],
+    ) -> Result<UpdateResult, WorkflowError> {
+        let raw =
+            fs::read_to_string(workflow_path).map_err(|source| WorkflowError::ScanFailed {
+                reason: format!("failed to read {}: {}", workflow_path.display(), source),
+            })?;
+        let (encoding, content) = Encoding::detect(&raw);
+
+        let mut updated_content = content.to_owned();
+        let mut changes = Vec::new();
+
+        for (re, replacement, change_label) in compiled {
+            if re.is_match(&updated_content) {
+                let new_content = re.replace_all(&updated_content, replacement.as_str());
+                if new_content != updated_content {
+                    changes.push(change_label.clone());
+                    updated_content = new_content.to_string();
+                }
+            }
+        }
+
+        if !changes.is_empty() {
+            verify(workflow_path, content, &updated_content, |before, after| {
+                let renamed = compiled.iter().fold(
+                    format!("uses: {before}"),
+                    |text, (re, replacement, _)| {
+                        re.replace_all(&text, replacement.as_str()).into_owned()
+                    },
+                );
+                renamed == format!("uses: {after}")
+            })?;
+            self.write(workflow_path, &encoding.restore(&updated_content))?;
+        }
+
+        Ok(UpdateResult {
+            file: workflow_path.to_path_buf(),
+            changes,
+        })
+    }
+
+    /// Write `content` to the workflow file at `path`, unless this is a dry run.
+    fn write(&self, path: &Path, content: &str) -> Result<(), WorkflowError> {
+        if self.dry_run {
+            return Ok(());
+        }
+        atomic::write(path, content).map_err(|source| WorkflowError::UpdateFailed {
+            path: path.to_string_lossy().to_string(),
+            reason: format!("write error: {source}"),
+        })
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    clippy::indexing_slicing,
+    reason = "tests use unwrap, indexing, and other patterns freely"
+)]
+#[path = "tests.rs"]
+mod tests;
@@ -45,16 +45,16 @@ impl Command for Init {
             return Err(Error::AlreadyInitialized);
         }
         on_progress("Reading actions from workflows into the manifest...");
-        if config.settings.github_token.is_none() {
+        let registry = GithubRegistry::from_settings(&config.settings)?;
+        if registry.token.is_none() && !config.settings.offline {
             on_progress(
-                "Warning: No GITHUB_TOKEN set — using unauthenticated GitHub API (60 requests/hour limit).",
+                "Warning: No GitHub token found (GITHUB_TOKEN, GH_TOKEN, gh CLI, or OS keyring) — using unauthenticated GitHub API (60 requests/hour limit).",
             );
         }
-        let registry = GithubRegistry::new(config.settings.github_token)?;
-        let scanner = FileWorkflowScanner::new(repo_root);
-        let updater = WorkflowWriter::new(repo_root);
+        let scanner = FileWorkflowScanner::new(repo_root).with_workflows(config.workflows.clone());
+        let updater = WorkflowWriter::new(repo_root).with_workflows(config.workflows.clone());
 
-        let plan = crate::tidy::plan(
+        let mut plan = crate::tidy::plan(
             &config.manifest,
             &config.lock,
             &registry,
@@ -64,7 +64,10 @@ impl Command for Init {
 
         if !plan.is_empty() {
             crate::infra::manifest::create(&config.manifest_path, &plan.manifest)?;
-            let lock_store = crate::infra::lock::Store::new(&config.lock_path);
+            plan.lock.set_manifest_hash(
+                config.lock_hash(crate::infra::manifest::hash_file(&config.manifest_path)?),
+            );
+            let lock_store = config.lock_store()?;
             lock_store.save(&plan.lock)?;
             crate::tidy::apply_workflow_patches(&updater, &plan.workflows)?;
         }
@@ -3,13 +3,31 @@
     reason = "dev-dependencies are only used in integration tests"
 )]
 
+pub mod audit;
+pub mod auth;
+pub mod cache;
 pub mod command;
 pub mod config;
+pub mod configuration;
 pub mod domain;
+pub mod export;
+#[cfg(feature = "gitlab")]
+pub mod gitlab;
+pub mod hash;
+pub mod hook;
+pub mod import;
 pub mod infra;
 pub mod init;
+pub mod licenses;
 pub mod lint;
+pub mod lock;
+pub mod migrate;
 pub mod output;
 pub(crate) mod regex;
+pub mod sbom;
 pub mod tidy;
+pub mod tree;
+pub mod trust;
+pub mod undo;
 pub mod upgrade;
+pub mod verify;
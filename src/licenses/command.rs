@@ -0,0 +1,132 @@
+use super::report::{Report, Usage};
+use crate::command::Command;
+use crate::config::{Config, Level};
+use crate::domain::action::identity::Repository;
+use crate::domain::lock::Lock;
+use crate::infra::github::{Error as GithubError, Registry as GithubRegistry};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use thiserror::Error;
+
+/// Errors that can occur during `gx licenses`.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(
+        "gx licenses reads the license of each repository from GitHub, which --offline disables"
+    )]
+    Offline,
+    #[error(transparent)]
+    Registry(#[from] GithubError),
+}
+
+/// `gx licenses`: list the license GitHub detects in the repository of every action
+/// gx.lock pins, reporting those `[licenses] deny` names as diagnostics.
+pub struct Licenses;
+
+impl Command for Licenses {
+    type Report = Report;
+    type Error = Error;
+
+    fn run(
+        &self,
+        _repo_root: &Path,
+        config: Config,
+        on_progress: &mut dyn FnMut(&str),
+    ) -> Result<Report, Error> {
+        if config.settings.offline {
+            return Err(Error::Offline);
+        }
+        let registry = GithubRegistry::from_settings(&config.settings)?;
+        let mut report = Report {
+            level: config.licenses.level,
+            ..Report::default()
+        };
+        for (repository, actions) in repositories(&config.lock) {
+            on_progress(&format!("Reading the license of {repository}..."));
+            let license = registry.license(&Repository::from(repository.clone()))?;
+            let spdx_id = license
+                .as_ref()
+                .and_then(|found| found.spdx_id.clone())
+                .filter(|id| id != "NOASSERTION");
+            let denied = spdx_id
+                .as_deref()
+                .and_then(|id| config.licenses.denied_by(id))
+                .filter(|_| config.licenses.level != Level::Off)
+                .map(str::to_owned);
+            report.usages.push(Usage {
+                repository,
+                actions: actions.into_iter().collect(),
+                spdx_id,
+                name: license.map(|found| found.name),
+                denied,
+            });
+        }
+        Ok(report)
+    }
+}
+
+/// The repositories of the actions gx.lock pins, each with those actions as
+/// `action@version`, sorted.
+fn repositories(lock: &Lock) -> BTreeMap<String, BTreeSet<String>> {
+    let mut repositories: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for (spec, entry) in lock.entries() {
+        repositories
+            .entry(entry.commit.repository.to_string())
+            .or_default()
+            .insert(format!("{}@{}", spec.id, entry.version));
+    }
+    repositories
+}
+
+#[cfg(test)]
+mod tests {
+    use super::repositories;
+    use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Version};
+    use crate::domain::action::resolved::{Commit, Provenance};
+    use crate::domain::action::spec::Spec;
+    use crate::domain::action::specifier::Specifier;
+    use crate::domain::lock::Lock;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn repositories_group_the_actions_they_hold() {
+        let mut lock = Lock::default();
+        for (action, specifier, version) in [
+            ("github/codeql-action/init", "^3", "v3.28.0"),
+            ("github/codeql-action/analyze", "^3", "v3.28.0"),
+            ("actions/checkout", "^4", "v4.2.2"),
+            ("actions/checkout", "^4.2", "v4.2.2"),
+        ] {
+            lock.set(
+                &Spec::new(ActionId::from(action), Specifier::parse(specifier)),
+                Version::from(version),
+                Commit {
+                    sha: CommitSha::from("aaa111"),
+                    repository: ActionId::from(action).base_repo(),
+                    ref_type: None,
+                    date: CommitDate::from("2026-01-01T00:00:00Z"),
+                    provenance: Provenance::default(),
+                },
+            );
+        }
+
+        let grouped: Vec<(String, BTreeSet<String>)> = repositories(&lock).into_iter().collect();
+
+        assert_eq!(
+            grouped,
+            [
+                (
+                    "actions/checkout".to_owned(),
+                    BTreeSet::from(["actions/checkout@v4.2.2".to_owned()])
+                ),
+                (
+                    "github/codeql-action".to_owned(),
+                    BTreeSet::from([
+                        "github/codeql-action/analyze@v3.28.0".to_owned(),
+                        "github/codeql-action/init@v3.28.0".to_owned(),
+                    ])
+                ),
+            ]
+        );
+    }
+}
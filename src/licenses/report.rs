@@ -0,0 +1,146 @@
+use crate::command::CommandReport;
+use crate::config::Level;
+use crate::output::lines::Line as OutputLine;
+
+/// The license of a repository some locked actions come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Usage {
+    /// The repository, as `owner/repo`.
+    pub repository: String,
+    /// The locked actions from it, as `action@version`.
+    pub actions: Vec<String>,
+    /// The SPDX identifier of its license, when GitHub matched one.
+    pub spdx_id: Option<String>,
+    /// The name of its license; `None` when it has no license file.
+    pub name: Option<String>,
+    /// The `[licenses] deny` entry the license matches.
+    pub denied: Option<String>,
+}
+
+/// Report from `gx licenses`.
+#[derive(Debug)]
+pub struct Report {
+    /// The license of each repository, sorted.
+    pub usages: Vec<Usage>,
+    /// The level of a denied license, from `[licenses] level`.
+    pub level: Level,
+}
+
+impl Default for Report {
+    fn default() -> Self {
+        Self {
+            usages: Vec::new(),
+            level: Level::Error,
+        }
+    }
+}
+
+impl CommandReport for Report {
+    fn render(&self) -> Vec<OutputLine> {
+        let mut lines = Vec::new();
+        if !self.usages.is_empty() {
+            lines.push(OutputLine::Table {
+                headers: vec![
+                    "repository".to_owned(),
+                    "license".to_owned(),
+                    "actions".to_owned(),
+                ],
+                rows: self
+                    .usages
+                    .iter()
+                    .map(|usage| {
+                        vec![
+                            usage.repository.clone(),
+                            license(usage),
+                            usage.actions.join(", "),
+                        ]
+                    })
+                    .collect(),
+            });
+        }
+        lines.extend(self.usages.iter().filter_map(|usage| {
+            let denied = usage.denied.as_ref()?;
+            Some(OutputLine::LintDiag {
+                level: self.level,
+                workflow: None,
+                line: None,
+                rule: "license".to_owned(),
+                message: format!(
+                    "{} is licensed under {}, which `[licenses] deny` lists as {denied}; used by {}",
+                    usage.repository,
+                    license(usage),
+                    usage.actions.join(", ")
+                ),
+            })
+        }));
+        let unlicensed = self
+            .usages
+            .iter()
+            .filter(|usage| usage.name.is_none())
+            .count();
+        let denied = self
+            .usages
+            .iter()
+            .filter(|usage| usage.denied.is_some())
+            .count();
+        lines.push(OutputLine::Summary {
+            text: format!(
+                "{} repository(ies) checked · {denied} denied · {unlicensed} without a license",
+                self.usages.len()
+            ),
+        });
+        lines
+    }
+
+    fn exit_code(&self) -> i32 {
+        i32::from(
+            self.level == Level::Error && self.usages.iter().any(|usage| usage.denied.is_some()),
+        )
+    }
+}
+
+/// The license of `usage` as shown: its SPDX identifier, else its name, else `none`.
+fn license(usage: &Usage) -> String {
+    usage
+        .spdx_id
+        .clone()
+        .or_else(|| usage.name.clone())
+        .unwrap_or_else(|| "none".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Report, Usage};
+    use crate::command::CommandReport as _;
+    use crate::config::Level;
+
+    fn usage(repository: &str, spdx_id: Option<&str>, denied: Option<&str>) -> Usage {
+        Usage {
+            repository: repository.to_owned(),
+            actions: vec![format!("{repository}@v1")],
+            spdx_id: spdx_id.map(str::to_owned),
+            name: spdx_id.map(str::to_owned),
+            denied: denied.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn denied_licenses_fail_only_at_error_level() {
+        let mut report = Report {
+            usages: vec![
+                usage("actions/checkout", Some("MIT"), None),
+                usage("acme/deploy", Some("AGPL-3.0"), Some("AGPL")),
+                usage("acme/scripts", None, None),
+            ],
+            level: Level::Error,
+        };
+
+        assert_eq!(report.exit_code(), 1);
+        report.level = Level::Warn;
+        assert_eq!(report.exit_code(), 0);
+        assert_eq!(
+            report.usages.iter().map(super::license).collect::<Vec<_>>(),
+            ["MIT", "AGPL-3.0", "none"]
+        );
+    }
+}
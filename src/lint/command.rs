@@ -1,6 +1,6 @@
 use super::report::Report;
 use super::rule::{
-    Context, Diagnostic, Rule as _, RuleName, format_and_report, is_ignored, matches_ignore,
+    Context, Diagnostic, Rule, RuleName, format_and_report, is_ignored, matches_ignore,
     run_workflow_rule,
 };
 use super::run_shellcheck::RunShellcheckRule;
@@ -12,7 +12,7 @@ use super::workflow_security::{
     DangerousTriggerRule, ExcessivePermissionsRule, MissingConcurrencyRule, MissingPermissionsRule,
     PrHeadCheckoutRule, UnprotectedSecretsRule,
 };
-use super::workflow_validity::{DanglingReferenceRule, InvalidExpressionRule};
+use super::workflow_validity::{DanglingReferenceRule, DynamicRefRule, InvalidExpressionRule};
 use crate::command::Command;
 use crate::config::{Config, Level, Lint as LintConfig};
 use crate::domain::lock::Lock;
@@ -21,6 +21,7 @@ use crate::domain::workflow::{Error as WorkflowError, Scanner as WorkflowScanner
 use crate::domain::workflow_actions::{
     ActionSet as WorkflowActionSet, JobId, StepIndex, WorkflowPath,
 };
+use crate::infra::git::{Changes, Error as GitError, Git};
 use crate::infra::workflow_scan::FileScanner as FileWorkflowScanner;
 use std::path::Path;
 use thiserror::Error;
@@ -31,6 +32,10 @@ pub enum Error {
     /// A workflow parsing or I/O error occurred.
     #[error(transparent)]
     Workflow(#[from] WorkflowError),
+
+    /// The changed files could not be listed.
+    #[error(transparent)]
+    Git(#[from] GitError),
 }
 
 /// Run lint checks by scanning workflows and return diagnostics.
@@ -49,14 +54,6 @@ pub fn collect_diagnostics(
     on_progress: &mut dyn FnMut(&str),
 ) -> Result<Vec<Diagnostic>, Error> {
     on_progress("Scanning workflows...");
-    let sha_mismatch_level = lint_config
-        .get_rule(RuleName::ShaMismatch, Level::Error)
-        .level;
-    let unpinned_level = lint_config.get_rule(RuleName::Unpinned, Level::Error).level;
-    let stale_comment_level = lint_config
-        .get_rule(RuleName::StaleComment, Level::Warn)
-        .level;
-
     let mut all_diagnostics = Vec::new();
     let mut action_set = WorkflowActionSet::new();
 
@@ -64,8 +61,17 @@ pub fn collect_diagnostics(
     // structural Parsed view the workflow-security rules consume.
     let (located, parsed_workflows) = scanner.scan_all_with_parsed()?;
 
-    // Phase 1: per-action rules
+    // Phase 1: per-action rules. Levels are resolved per workflow so `[lint.paths]`
+    // overrides can relax or tighten a rule for matching files.
     for action in &located {
+        let first = all_diagnostics.len();
+        let workflow = Some(action.location.workflow.as_str());
+        let sha_mismatch_level =
+            lint_config.level_for(RuleName::ShaMismatch, Level::Error, workflow);
+        let unpinned_level = lint_config.level_for(RuleName::Unpinned, Level::Error, workflow);
+        let stale_comment_level =
+            lint_config.level_for(RuleName::StaleComment, Level::Warn, workflow);
+
         if sha_mismatch_level != Level::Off
             && let Some(mut diag) = ShaMismatchRule::check_action(action, lock)
         {
@@ -102,14 +108,16 @@ pub fn collect_diagnostics(
                 all_diagnostics.push(diag);
             }
         }
+        if let Some(reason) = manifest.reason(&action.action.id) {
+            for diag in all_diagnostics.iter_mut().skip(first) {
+                diag.message = format!("{} (reason: {reason})", diag.message);
+            }
+        }
 
         action_set.add(&action.action);
     }
 
     // Phase 2: action-aggregate rules
-    let unsynced_level = lint_config
-        .get_rule(RuleName::UnsyncedManifest, Level::Error)
-        .level;
     let ctx = Context {
         manifest,
         lock,
@@ -117,10 +125,17 @@ pub fn collect_diagnostics(
         workflows_full: &parsed_workflows,
         action_set: &action_set,
     };
-    if unsynced_level != Level::Off {
+    if lint_config.is_active(RuleName::UnsyncedManifest, Level::Error) {
         let rule = UnsyncedManifestRule;
         for mut diag in rule.check(&ctx) {
-            diag.level = unsynced_level;
+            diag.level = lint_config.level_for(
+                RuleName::UnsyncedManifest,
+                Level::Error,
+                diag.workflow.as_ref().map(WorkflowPath::as_str),
+            );
+            if diag.level == Level::Off {
+                continue;
+            }
             let ignored = lint_config
                 .get_rule(RuleName::UnsyncedManifest, Level::Error)
                 .ignore
@@ -132,23 +147,10 @@ pub fn collect_diagnostics(
         }
     }
 
-    // Phase 3: workflow-security rules. Each runs against ctx.workflows_full and emits
-    // diagnostics carrying workflow + (optionally) job/step location.
-    run_workflow_security_rules(&ctx, lint_config, &mut all_diagnostics);
-
-    // Phase 4: workflow-validity rules. Same parse, same run_workflow_rule path; these
-    // catch structurally broken references (dangling needs:, unresolved expressions).
-    run_workflow_validity_rules(&ctx, lint_config, &mut all_diagnostics);
-
-    // Phase 5: shellcheck over bash/sh run: bodies. The rule probes for the binary once
-    // on construction and degrades gracefully (single skip diagnostic) when it is absent.
-    run_workflow_rule(
-        &RunShellcheckRule::new(),
-        Level::Warn,
-        &ctx,
-        lint_config,
-        &mut all_diagnostics,
-    );
+    // Phases 3-5: workflow-security, workflow-validity, and shellcheck rules. They are
+    // independent reads of the same parse, so they run concurrently and are merged back
+    // in registration order before the final sort.
+    all_diagnostics.extend(run_workflow_rules(&ctx, lint_config));
 
     // Stable, location-first ordering so findings for one file read together.
     all_diagnostics.sort_by(|a, b| diagnostic_sort_key(a).cmp(&diagnostic_sort_key(b)));
@@ -169,80 +171,60 @@ fn diagnostic_sort_key(diag: &Diagnostic) -> (&str, &str, u16, RuleName) {
     )
 }
 
-/// Run all workflow-security rules and append their diagnostics.
-fn run_workflow_security_rules(
-    ctx: &Context,
-    lint_config: &LintConfig,
-    all_diagnostics: &mut Vec<Diagnostic>,
-) {
-    run_workflow_rule(
-        &MissingPermissionsRule,
-        Level::Error,
-        ctx,
-        lint_config,
-        all_diagnostics,
-    );
-    run_workflow_rule(
-        &ExcessivePermissionsRule,
-        Level::Error,
-        ctx,
-        lint_config,
-        all_diagnostics,
-    );
-    run_workflow_rule(
-        &DangerousTriggerRule,
-        Level::Error,
-        ctx,
-        lint_config,
-        all_diagnostics,
-    );
-    run_workflow_rule(
-        &PrHeadCheckoutRule,
-        Level::Error,
-        ctx,
-        lint_config,
-        all_diagnostics,
-    );
-    run_workflow_rule(
-        &MissingConcurrencyRule,
-        Level::Warn,
-        ctx,
-        lint_config,
-        all_diagnostics,
-    );
-    run_workflow_rule(
-        &UnprotectedSecretsRule,
-        Level::Error,
-        ctx,
-        lint_config,
-        all_diagnostics,
-    );
-}
+/// Run every workflow-scoped rule on its own scoped thread and return their diagnostics
+/// concatenated in registration order, so the merged output does not depend on which
+/// thread finishes first.
+fn run_workflow_rules(ctx: &Context, lint_config: &LintConfig) -> Vec<Diagnostic> {
+    // The shellcheck rule probes for the binary once on construction and degrades
+    // gracefully (single skip diagnostic) when it is absent.
+    let shellcheck = RunShellcheckRule::new();
+    let rules: [(&(dyn Rule + Sync), Level); 10] = [
+        // Workflow-security: each emits diagnostics carrying workflow + (optionally)
+        // job/step location.
+        (&MissingPermissionsRule, Level::Error),
+        (&ExcessivePermissionsRule, Level::Error),
+        (&DangerousTriggerRule, Level::Error),
+        (&PrHeadCheckoutRule, Level::Error),
+        (&MissingConcurrencyRule, Level::Warn),
+        (&UnprotectedSecretsRule, Level::Error),
+        // Workflow-validity: structurally broken references (dangling needs:,
+        // unresolved expressions) and references only resolved at run time.
+        (&DanglingReferenceRule, Level::Error),
+        (&InvalidExpressionRule, Level::Error),
+        (&DynamicRefRule, Level::Warn),
+        // Shellcheck over bash/sh run: bodies.
+        (&shellcheck, Level::Warn),
+    ];
 
-/// Run all workflow-validity rules and append their diagnostics.
-fn run_workflow_validity_rules(
-    ctx: &Context,
-    lint_config: &LintConfig,
-    all_diagnostics: &mut Vec<Diagnostic>,
-) {
-    run_workflow_rule(
-        &DanglingReferenceRule,
-        Level::Error,
-        ctx,
-        lint_config,
-        all_diagnostics,
-    );
-    run_workflow_rule(
-        &InvalidExpressionRule,
-        Level::Error,
-        ctx,
-        lint_config,
-        all_diagnostics,
-    );
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = rules
+            .iter()
+            .map(|&(rule, default_level)| {
+                scope.spawn(move || {
+                    let mut out = Vec::new();
+                    run_workflow_rule(rule, default_level, ctx, lint_config, &mut out);
+                    out
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+            })
+            .collect()
+    })
 }
 
 /// The lint command struct.
-pub struct Lint;
+#[derive(Debug, Default)]
+pub struct Lint {
+    /// When set, only diagnostics in the workflow files with these changes in git are
+    /// reported, along with those about gx.toml and gx.lock as a whole.
+    pub changes: Option<Changes>,
+}
 
 impl Command for Lint {
     type Report = Report;
@@ -254,15 +236,23 @@ impl Command for Lint {
         config: Config,
         on_progress: &mut dyn FnMut(&str),
     ) -> Result<Report, Error> {
-        let scanner = FileWorkflowScanner::new(repo_root);
+        let scanner = FileWorkflowScanner::new(repo_root).with_workflows(config.workflows.clone());
 
-        let diagnostics = collect_diagnostics(
+        let mut diagnostics = collect_diagnostics(
             &config.manifest,
             &config.lock,
             &scanner,
             &config.lint_config,
             on_progress,
         )?;
+        if let Some(changes) = &self.changes {
+            let files = Git::new(repo_root).changed_files(changes)?;
+            diagnostics.retain(|diag| {
+                diag.workflow
+                    .as_ref()
+                    .is_none_or(|workflow| files.contains(&repo_root.join(workflow.as_str())))
+            });
+        }
 
         Ok(format_and_report(diagnostics))
     }
@@ -29,6 +29,7 @@ pub enum RuleName {
     UnprotectedSecrets,
     DanglingReference,
     InvalidExpression,
+    DynamicRef,
     RunShellcheck,
 }
 
@@ -47,6 +48,7 @@ impl std::fmt::Display for RuleName {
             Self::UnprotectedSecrets => write!(f, "unprotected-secrets"),
             Self::DanglingReference => write!(f, "dangling-reference"),
             Self::InvalidExpression => write!(f, "invalid-expression"),
+            Self::DynamicRef => write!(f, "dynamic-ref"),
             Self::RunShellcheck => write!(f, "run-shellcheck"),
         }
     }
@@ -69,6 +71,7 @@ impl FromStr for RuleName {
             "unprotected-secrets" => Ok(Self::UnprotectedSecrets),
             "dangling-reference" => Ok(Self::DanglingReference),
             "invalid-expression" => Ok(Self::InvalidExpression),
+            "dynamic-ref" => Ok(Self::DynamicRef),
             "run-shellcheck" => Ok(Self::RunShellcheck),
             other => Err(format!("unrecognized rule name: {other}")),
         }
@@ -175,21 +178,29 @@ pub fn format_and_report(diagnostics: Vec<Diagnostic>) -> Report {
 }
 
 /// Run a workflow-scoped rule. Filters its diagnostics through the per-rule `ignore`
-/// list using the new workflow/job-aware matcher, applies the configured severity, and
-/// pushes the survivors onto `out`.
-pub(super) fn run_workflow_rule<R: Rule>(
+/// list using the new workflow/job-aware matcher, applies the configured severity
+/// (including any `[lint.paths]` override for the diagnostic's workflow), and pushes
+/// the survivors onto `out`.
+pub(super) fn run_workflow_rule<R: Rule + ?Sized>(
     rule: &R,
     default_level: Level,
     ctx: &Context<'_>,
     lint_config: &LintConfig,
     out: &mut Vec<Diagnostic>,
 ) {
-    let configured = lint_config.get_rule(rule.name(), default_level);
-    if configured.level == Level::Off {
+    if !lint_config.is_active(rule.name(), default_level) {
         return;
     }
+    let configured = lint_config.get_rule(rule.name(), default_level);
     for mut diag in rule.check(ctx) {
-        diag.level = configured.level;
+        diag.level = lint_config.level_for(
+            rule.name(),
+            default_level,
+            diag.workflow.as_ref().map(WorkflowPath::as_str),
+        );
+        if diag.level == Level::Off {
+            continue;
+        }
         let ignored = configured
             .ignore
             .iter()
@@ -351,6 +362,7 @@ mod tests {
             RuleName::UnprotectedSecrets,
             RuleName::DanglingReference,
             RuleName::InvalidExpression,
+            RuleName::DynamicRef,
             RuleName::RunShellcheck,
         ] {
             let s = name.to_string();
@@ -405,6 +417,7 @@ mod tests {
             RuleName::from_str("invalid-expression"),
             Ok(RuleName::InvalidExpression)
         );
+        assert_eq!(RuleName::from_str("dynamic-ref"), Ok(RuleName::DynamicRef));
         assert_eq!(
             RuleName::from_str("run-shellcheck"),
             Ok(RuleName::RunShellcheck)
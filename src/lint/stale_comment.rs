@@ -64,7 +64,7 @@ impl Rule for StaleCommentRule {
 mod tests {
     use super::{Level, Rule as _, RuleName, StaleCommentRule};
     use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Version};
-    use crate::domain::action::resolved::Commit;
+    use crate::domain::action::resolved::{Commit, Provenance};
     use crate::domain::action::spec::Spec;
     use crate::domain::action::specifier::Specifier;
     use crate::domain::action::uses_ref::RefType;
@@ -82,6 +82,7 @@ mod tests {
                 repository: ActionId::from(action).base_repo(),
                 ref_type: Some(RefType::Tag),
                 date: CommitDate::from("2026-01-01T00:00:00Z"),
+                provenance: Provenance::default(),
             },
         );
         lock
@@ -99,6 +100,7 @@ mod tests {
                 workflow: WorkflowPath::new(workflow),
                 job: None,
                 step: None,
+                step_name: None,
                 line: None,
             },
         }
@@ -73,6 +73,7 @@ mod tests {
                 workflow: WorkflowPath::new(".github/workflows/ci.yml"),
                 job: None,
                 step: None,
+                step_name: None,
                 line,
             },
         }
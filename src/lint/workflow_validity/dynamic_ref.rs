@@ -0,0 +1,100 @@
+use crate::config::Level;
+use crate::domain::workflow_actions::{JobId, StepIndex};
+use crate::domain::workflow_parsed::Parsed;
+use crate::lint::{Context, Diagnostic, Rule, RuleName};
+
+/// `dynamic-ref` rule: flags a step whose `uses:` is built from a `${{ }}` expression,
+/// such as `actions/checkout@${{ inputs.ref }}`. The reference resolves only at run time,
+/// so gx can neither lock nor pin it, and the step runs whatever the expression yields.
+pub struct DynamicRefRule;
+
+impl DynamicRefRule {
+    /// Returns one diagnostic per step with an expression in its `uses:`.
+    pub fn check_workflow(workflow: &Parsed) -> Vec<Diagnostic> {
+        let mut out = Vec::new();
+        for job in &workflow.jobs {
+            for (index, step) in job.steps.iter().enumerate() {
+                let Some(uses) = step.uses_ref().filter(|_| step.uses_is_dynamic()) else {
+                    continue;
+                };
+                let msg = format!(
+                    "`uses: {uses}` is resolved at run time from an expression, so gx cannot pin it"
+                );
+                let mut diag = Diagnostic::new(RuleName::DynamicRef, Level::Warn, msg)
+                    .with_workflow(workflow.path.clone())
+                    .with_job(JobId::from(job.id.clone()))
+                    .with_line(step.uses_line());
+                if let Ok(step_index) = StepIndex::try_from(index) {
+                    diag = diag.with_step(step_index);
+                }
+                out.push(diag);
+            }
+        }
+        out
+    }
+}
+
+impl Rule for DynamicRefRule {
+    fn name(&self) -> RuleName {
+        RuleName::DynamicRef
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warn
+    }
+
+    fn check(&self, ctx: &Context) -> Vec<Diagnostic> {
+        ctx.workflows_full
+            .iter()
+            .flat_map(Self::check_workflow)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    clippy::indexing_slicing,
+    reason = "tests use unwrap and indexing freely"
+)]
+mod tests {
+    use super::*;
+    use crate::domain::workflow_actions::WorkflowPath;
+
+    fn parse(content: &str) -> Parsed {
+        Parsed::from_yaml(WorkflowPath::new(".github/workflows/ci.yml"), content).unwrap()
+    }
+
+    #[test]
+    fn rule_metadata() {
+        let r = DynamicRefRule;
+        assert_eq!(r.name(), RuleName::DynamicRef);
+        assert_eq!(r.default_level(), Level::Warn);
+    }
+
+    #[test]
+    fn expression_refs_are_flagged_and_static_refs_are_not() {
+        let p = parse(
+            "on: push
+jobs:
+  build:
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@${{ matrix.version }}
+      - uses: ${{ inputs.action }}@v1
+",
+        );
+
+        let diags = DynamicRefRule::check_workflow(&p);
+
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].step, Some(StepIndex::from(1_u16)));
+        assert_eq!(diags[0].line, Some(6));
+        assert!(
+            diags[0]
+                .message
+                .contains("actions/setup-node@${{ matrix.version }}")
+        );
+        assert_eq!(diags[1].step, Some(StepIndex::from(2_u16)));
+    }
+}
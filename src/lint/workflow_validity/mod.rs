@@ -1,13 +1,17 @@
 //! Workflow-validity lint rules. Each rule consumes the structural `Parsed` view of a
 //! workflow (via `Context::workflows_full`) and flags references that GitHub Actions
-//! accepts at parse time but that fail or silently resolve to nothing at run time.
+//! accepts at parse time but that fail or silently resolve to nothing at run time, or
+//! that resolve only at run time, out of gx's reach.
 
 #![expect(clippy::pub_use, reason = "reexport rule structs to lint::command")]
 
 /// Workflow-validity: flags `needs:` entries that name a job absent from the workflow.
 mod dangling_reference;
+/// Workflow-validity: flags `uses:` refs built from `${{ }}` expressions, which gx cannot pin.
+mod dynamic_ref;
 /// Workflow-validity: flags `needs.*`/`steps.*` expression refs that resolve to nothing.
 mod invalid_expression;
 
 pub use dangling_reference::DanglingReferenceRule;
+pub use dynamic_ref::DynamicRefRule;
 pub use invalid_expression::InvalidExpressionRule;
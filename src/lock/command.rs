@@ -0,0 +1,289 @@
+use super::merge::merge;
+use super::report::{
+    Change, Convert as ConvertReport, Diff as DiffReport, Entry, Kind, List as ListReport,
+    Merge as MergeReport,
+};
+use crate::command::Command;
+use crate::config::Config;
+use crate::domain::lock::Lock;
+use crate::infra::git::{Error as GitError, Git};
+use crate::infra::lock::{self, Error as LockFileError, Store as LockStore};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Errors that can occur during the lock commands.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Git(#[from] GitError),
+
+    #[error(transparent)]
+    Lock(#[from] LockFileError),
+
+    #[error("failed to read {location}")]
+    Read {
+        location: String,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// `gx lock diff`: list the actions whose lock entry differs between two lock files.
+///
+/// Each side is a file, or a git object such as `HEAD:.github/gx.lock` when no file has
+/// that name. `old` defaults to gx.lock at `HEAD`, and `new` to gx.lock in the work tree.
+#[derive(Debug, Default)]
+pub struct Diff {
+    /// The lock before the changes.
+    pub old: Option<String>,
+    /// The lock after the changes.
+    pub new: Option<String>,
+}
+
+impl Command for Diff {
+    type Report = DiffReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<DiffReport, Error> {
+        let relative = config
+            .lock_path
+            .strip_prefix(repo_root)
+            .unwrap_or(&config.lock_path);
+        let head = format!("HEAD:./{}", relative.display());
+        let old = read(repo_root, self.old.as_deref().unwrap_or(&head))?;
+        let new = match &self.new {
+            Some(location) => read(repo_root, location)?,
+            None => config.lock,
+        };
+        Ok(DiffReport {
+            changes: compare(&old, &new),
+        })
+    }
+}
+
+/// `gx lock list`: show each entry of gx.lock with the day its commit was released and
+/// the day gx resolved it.
+#[derive(Debug, Default)]
+pub struct List;
+
+impl Command for List {
+    type Report = ListReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        _repo_root: &Path,
+        config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<ListReport, Error> {
+        let mut entries: Vec<Entry> = config
+            .lock
+            .entries()
+            .map(|(spec, entry)| Entry {
+                action: spec.id.to_string(),
+                specifier: spec.specifier.to_string(),
+                version: entry.version.to_string(),
+                sha: entry.commit.sha.to_string(),
+                released: entry.commit.date.to_string(),
+                resolved_at: entry.commit.provenance.resolved_at.clone(),
+            })
+            .collect();
+        entries.sort_by(|a, b| (&a.action, &a.specifier).cmp(&(&b.action, &b.specifier)));
+        Ok(ListReport { entries })
+    }
+}
+
+/// `gx lock convert`: rewrite gx.lock in the format `[files] lock_format` names, so a
+/// lock written as TOML becomes JSON once the manifest asks for it, and back.
+#[derive(Debug, Default)]
+pub struct Convert;
+
+impl Command for Convert {
+    type Report = ConvertReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        _repo_root: &Path,
+        config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<ConvertReport, Error> {
+        let store = config.lock_store()?;
+        let format = config.files.lock_format;
+        let before = store.current_format()?;
+        let converted = before.is_some_and(|current| current != format);
+        if converted {
+            store.save(&store.load()?)?;
+        }
+        Ok(ConvertReport {
+            path: config.lock_path,
+            format: format.name(),
+            converted,
+            missing: before.is_none(),
+        })
+    }
+}
+
+/// `gx lock merge`: merge two changes of a lock file, as git calls a merge driver with
+/// `%O %A %B`. The merged lock replaces `ours`, and the actions gx.toml lists that it has
+/// no entry for are reported for `gx tidy` to resolve.
+#[derive(Debug)]
+pub struct Merge {
+    /// The lock both sides changed.
+    pub base: PathBuf,
+    /// The lock of the current branch, overwritten with the merged lock.
+    pub ours: PathBuf,
+    /// The lock of the branch being merged.
+    pub theirs: PathBuf,
+}
+
+impl Command for Merge {
+    type Report = MergeReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        _repo_root: &Path,
+        config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<MergeReport, Error> {
+        let load = |path: &Path| LockStore::new(path).load();
+        let merged = merge(&load(&self.base)?, &load(&self.ours)?, &load(&self.theirs)?);
+        let ours = LockStore::new(&self.ours);
+        let format = ours.current_format()?.unwrap_or_default();
+        ours.with_format(format).save(&merged.lock)?;
+        let mut unlocked: Vec<String> = config
+            .manifest
+            .lock_keys()
+            .iter()
+            .filter(|spec| !merged.lock.is_complete(spec))
+            .map(ToString::to_string)
+            .collect();
+        unlocked.sort();
+        Ok(MergeReport {
+            path: self.ours.clone(),
+            both_changed: merged.both_changed,
+            unlocked,
+        })
+    }
+}
+
+/// The lock at `location`: a file when one exists there, otherwise a git object.
+fn read(repo_root: &Path, location: &str) -> Result<Lock, Error> {
+    let path = Path::new(location);
+    let content = if path.exists() || !location.contains(':') {
+        fs::read_to_string(path).map_err(|source| Error::Read {
+            location: location.to_owned(),
+            source,
+        })?
+    } else {
+        Git::new(repo_root).show(location)?
+    };
+    Ok(lock::parse(&content, path)?.value)
+}
+
+/// The entries of `new` that differ from `old`, sorted by action and specifier.
+fn compare(old: &Lock, new: &Lock) -> Vec<Change> {
+    let specs: BTreeSet<(String, String)> = old
+        .entries()
+        .chain(new.entries())
+        .map(|(spec, _)| (spec.id.to_string(), spec.specifier.to_string()))
+        .collect();
+    let find = |lock: &Lock, action: &str, specifier: &str| {
+        lock.entries()
+            .find(|(spec, _)| spec.id.as_str() == action && spec.specifier.as_str() == specifier)
+            .map(|(_, entry)| (entry.version.to_string(), entry.commit.sha.to_string()))
+    };
+    specs
+        .into_iter()
+        .filter_map(|(action, specifier)| {
+            let before = find(old, &action, &specifier);
+            let after = find(new, &action, &specifier);
+            let kind = match (&before, &after) {
+                (None, Some(_)) => Kind::Added,
+                (Some(_), None) => Kind::Removed,
+                (Some(was), Some(now)) if was.0 != now.0 => Kind::VersionChanged,
+                (Some(was), Some(now)) if was.1 != now.1 => Kind::ShaChanged,
+                (Some(_) | None, Some(_) | None) => return None,
+            };
+            let (old_version, old_sha) = before.unzip();
+            let (new_version, new_sha) = after.unzip();
+            Some(Change {
+                action,
+                specifier,
+                kind,
+                old_version,
+                new_version,
+                old_sha,
+                new_sha,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compare;
+    use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
+    use crate::domain::action::resolved::{Commit, Provenance};
+    use crate::domain::action::spec::Spec;
+    use crate::domain::action::specifier::Specifier;
+    use crate::domain::lock::Lock;
+    use crate::lock::report::Kind;
+
+    fn lock(entries: &[(&str, &str, &str)]) -> Lock {
+        let mut lock = Lock::default();
+        for (action, version, sha) in entries {
+            lock.set(
+                &Spec::new(ActionId::from(*action), Specifier::parse("^4")),
+                Version::from(*version),
+                Commit {
+                    sha: CommitSha::from(*sha),
+                    repository: Repository::from(*action),
+                    ref_type: None,
+                    date: CommitDate::from(""),
+                    provenance: Provenance::default(),
+                },
+            );
+        }
+        lock
+    }
+
+    #[test]
+    fn compare_classifies_each_changed_entry() {
+        let old = lock(&[
+            ("actions/cache", "v4.0.0", "aaa"),
+            ("actions/checkout", "v4.1.0", "bbb"),
+            ("actions/setup-go", "v4.0.0", "ccc"),
+            ("actions/setup-node", "v4.0.0", "ddd"),
+        ]);
+        let new = lock(&[
+            ("actions/checkout", "v4.2.0", "eee"),
+            ("actions/setup-go", "v4.0.0", "fff"),
+            ("actions/setup-node", "v4.0.0", "ddd"),
+            ("actions/upload-artifact", "v4.0.0", "ggg"),
+        ]);
+
+        let kinds: Vec<(String, Kind)> = compare(&old, &new)
+            .into_iter()
+            .map(|change| (change.action, change.kind))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            [
+                ("actions/cache".to_owned(), Kind::Removed),
+                ("actions/checkout".to_owned(), Kind::VersionChanged),
+                ("actions/setup-go".to_owned(), Kind::ShaChanged),
+                ("actions/upload-artifact".to_owned(), Kind::Added),
+            ]
+        );
+    }
+}
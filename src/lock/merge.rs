@@ -0,0 +1,187 @@
+use crate::domain::action::identity::Version;
+use crate::domain::action::spec::Spec;
+use crate::domain::lock::{Lock, LockEntry};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// The three-way merge of two lock files that changed the same `base`.
+#[derive(Debug, Default)]
+pub(super) struct Merged {
+    /// The merged lock, with the manifest hash of `ours`.
+    pub lock: Lock,
+    /// The `action@specifier` of each entry both sides changed, with the side kept.
+    pub both_changed: Vec<(String, String)>,
+}
+
+/// Merge `ours` and `theirs`, each a change of `base`.
+///
+/// An entry only one side changed takes that change, and an entry one side removed is
+/// kept when the other changed it. When both sides changed an entry, the newer
+/// resolution wins: the higher version, then the later `resolved_at`, then `ours`.
+/// Images follow the same rules, keeping `ours` when both sides changed a digest.
+pub(super) fn merge(base: &Lock, ours: &Lock, theirs: &Lock) -> Merged {
+    let index = |lock: &Lock| -> HashMap<Spec, LockEntry> {
+        lock.entries()
+            .map(|(spec, entry)| (spec.clone(), entry.clone()))
+            .collect()
+    };
+    let (base_entries, our_entries, their_entries) = (index(base), index(ours), index(theirs));
+    let mut specs: Vec<&Spec> = base_entries
+        .keys()
+        .chain(our_entries.keys())
+        .chain(their_entries.keys())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    specs.sort_by_key(ToString::to_string);
+
+    let mut merged = Merged::default();
+    for spec in specs {
+        let (was, mine, yours) = (
+            base_entries.get(spec),
+            our_entries.get(spec),
+            their_entries.get(spec),
+        );
+        let kept = match (mine, yours) {
+            _ if mine == yours || yours == was => mine,
+            _ if mine == was => yours,
+            (Some(our_entry), Some(their_entry)) => {
+                let newer = newer(our_entry, their_entry);
+                merged
+                    .both_changed
+                    .push((spec.to_string(), newer.version.to_string()));
+                Some(newer)
+            }
+            (Some(_) | None, None) | (None, Some(_)) => mine.or(yours),
+        };
+        if let Some(entry) = kept {
+            merged
+                .lock
+                .set(spec, entry.version.clone(), entry.commit.clone());
+        }
+    }
+
+    let images: BTreeSet<&String> = base
+        .images()
+        .chain(ours.images())
+        .chain(theirs.images())
+        .map(|(key, _)| key)
+        .collect();
+    for key in images {
+        let (was, mine, yours) = (base.image(key), ours.image(key), theirs.image(key));
+        let kept = if mine == was { yours } else { mine.or(yours) };
+        if let Some(digest) = kept {
+            merged.lock.set_image(key.clone(), digest.clone());
+        }
+    }
+    if let Some(hash) = ours.manifest_hash() {
+        merged.lock.set_manifest_hash(hash.to_owned());
+    }
+    merged
+}
+
+/// The newer of two resolutions of the same spec, `ours` on a tie.
+fn newer<'entry>(ours: &'entry LockEntry, theirs: &'entry LockEntry) -> &'entry LockEntry {
+    if ours.version != theirs.version {
+        let highest = Version::highest(&[ours.version.clone(), theirs.version.clone()]);
+        return if highest.as_ref() == Some(&theirs.version) {
+            theirs
+        } else {
+            ours
+        };
+    }
+    if theirs.commit.provenance.resolved_at > ours.commit.provenance.resolved_at {
+        theirs
+    } else {
+        ours
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::merge;
+    use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
+    use crate::domain::action::resolved::{Commit, Provenance};
+    use crate::domain::action::spec::Spec;
+    use crate::domain::action::specifier::Specifier;
+    use crate::domain::lock::Lock;
+
+    fn spec(action: &str) -> Spec {
+        Spec::new(ActionId::from(action), Specifier::parse("^4"))
+    }
+
+    fn lock(entries: &[(&str, &str, &str)]) -> Lock {
+        let mut lock = Lock::default();
+        for (action, version, resolved_at) in entries {
+            lock.set(
+                &spec(action),
+                Version::from(*version),
+                Commit {
+                    sha: CommitSha::from(format!("{action}@{version}@{resolved_at}")),
+                    repository: Repository::from(*action),
+                    ref_type: None,
+                    date: CommitDate::from(""),
+                    provenance: Provenance {
+                        source: None,
+                        resolved_at: (*resolved_at).to_owned(),
+                    },
+                },
+            );
+        }
+        lock
+    }
+
+    fn version(lock: &Lock, action: &str) -> Option<String> {
+        lock.get(&spec(action))
+            .map(|entry| entry.version.to_string())
+    }
+
+    #[test]
+    fn merge_takes_each_sides_changes_and_the_newer_of_both() {
+        let base = lock(&[
+            ("actions/cache", "v4.0.0", ""),
+            ("actions/checkout", "v4.0.0", ""),
+            ("actions/setup-go", "v4.0.0", ""),
+            ("actions/setup-node", "v4.0.0", ""),
+        ]);
+        let ours = lock(&[
+            ("actions/checkout", "v4.2.0", ""),
+            ("actions/setup-go", "v4.0.0", "2026-02-01T00:00:00Z"),
+            ("actions/setup-node", "v4.0.0", ""),
+            ("actions/upload-artifact", "v4.0.0", ""),
+        ]);
+        let theirs = lock(&[
+            ("actions/cache", "v4.0.0", ""),
+            ("actions/checkout", "v4.1.0", ""),
+            ("actions/setup-go", "v4.0.0", "2026-03-01T00:00:00Z"),
+            ("actions/setup-node", "v4.1.0", ""),
+        ]);
+
+        let merged = merge(&base, &ours, &theirs);
+
+        assert_eq!(version(&merged.lock, "actions/cache"), None);
+        assert_eq!(version(&merged.lock, "actions/checkout").unwrap(), "v4.2.0");
+        assert_eq!(
+            version(&merged.lock, "actions/setup-node").unwrap(),
+            "v4.1.0"
+        );
+        assert!(version(&merged.lock, "actions/upload-artifact").is_some());
+        assert_eq!(
+            merged
+                .lock
+                .get(&spec("actions/setup-go"))
+                .unwrap()
+                .commit
+                .provenance
+                .resolved_at,
+            "2026-03-01T00:00:00Z"
+        );
+        assert_eq!(
+            merged.both_changed,
+            [
+                ("actions/checkout@^4".to_owned(), "v4.2.0".to_owned()),
+                ("actions/setup-go@^4".to_owned(), "v4.0.0".to_owned()),
+            ]
+        );
+    }
+}
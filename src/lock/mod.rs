@@ -0,0 +1,9 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// Lock commands: error types, structs, and `Command` implementations.
+mod command;
+/// Three-way merge of lock files.
+mod merge;
+pub mod report;
+
+pub use command::{Convert, Diff, Error, List, Merge};
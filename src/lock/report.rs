@@ -0,0 +1,288 @@
+use crate::command::CommandReport;
+use crate::output::events::Event;
+use crate::output::lines::Line as OutputLine;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// How a lock entry changed between two lock files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Kind {
+    /// The specifier is only in the new lock.
+    Added,
+    /// The specifier is only in the old lock.
+    Removed,
+    /// The specifier resolves to a different commit of the same version.
+    ShaChanged,
+    /// The specifier resolves to a different version.
+    VersionChanged,
+}
+
+/// One lock entry that differs between two lock files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Change {
+    pub action: String,
+    pub specifier: String,
+    pub kind: Kind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_sha: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_sha: Option<String>,
+}
+
+/// Report from `gx lock diff`.
+#[derive(Debug, Default, Serialize)]
+pub struct Diff {
+    /// The changed entries, sorted by action and specifier.
+    pub changes: Vec<Change>,
+}
+
+impl CommandReport for Diff {
+    fn render(&self) -> Vec<OutputLine> {
+        if self.changes.is_empty() {
+            return vec![OutputLine::Summary {
+                text: "The lock files have the same entries".to_owned(),
+            }];
+        }
+        self.changes
+            .iter()
+            .map(|change| {
+                let action = format!("{}@{}", change.action, change.specifier);
+                let version = |version: &Option<String>| version.clone().unwrap_or_default();
+                let sha = |sha: &Option<String>| short(sha.as_deref());
+                match change.kind {
+                    Kind::Added => OutputLine::Added {
+                        action,
+                        version: format!(
+                            "{} ({})",
+                            version(&change.new_version),
+                            sha(&change.new_sha)
+                        ),
+                    },
+                    Kind::Removed => OutputLine::Removed { action },
+                    Kind::VersionChanged => OutputLine::Upgraded {
+                        action,
+                        from: version(&change.old_version),
+                        to: version(&change.new_version),
+                    },
+                    Kind::ShaChanged => OutputLine::Changed {
+                        action,
+                        detail: format!(
+                            "{} moved from {} to {}",
+                            version(&change.new_version),
+                            sha(&change.old_sha),
+                            sha(&change.new_sha)
+                        ),
+                    },
+                }
+            })
+            .collect()
+    }
+
+    fn events(&self) -> Vec<Event> {
+        self.changes
+            .iter()
+            .map(|change| {
+                let field = |value: &Option<String>| value.clone().unwrap_or_default();
+                match change.kind {
+                    Kind::Added => Event::Resolved {
+                        action: change.action.clone(),
+                        specifier: change.specifier.clone(),
+                        version: field(&change.new_version),
+                        sha: field(&change.new_sha),
+                    },
+                    Kind::Removed => Event::Removed {
+                        action: change.action.clone(),
+                    },
+                    Kind::VersionChanged => Event::Upgraded {
+                        action: change.action.clone(),
+                        from: field(&change.old_version),
+                        to: field(&change.new_version),
+                    },
+                    Kind::ShaChanged => Event::TagMoved {
+                        action: change.action.clone(),
+                        tag: field(&change.new_version),
+                        locked: field(&change.old_sha),
+                        current: field(&change.new_sha),
+                        repinned: true,
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+/// Report from `gx lock merge`.
+#[derive(Debug, Default)]
+pub struct Merge {
+    /// The file the merged lock was written to.
+    pub path: PathBuf,
+    /// The entries both sides changed: (`action@specifier`, the version kept).
+    pub both_changed: Vec<(String, String)>,
+    /// The `action@specifier` of each action in gx.toml the merged lock has no entry for.
+    pub unlocked: Vec<String>,
+}
+
+impl CommandReport for Merge {
+    fn render(&self) -> Vec<OutputLine> {
+        let mut lines: Vec<OutputLine> = self
+            .both_changed
+            .iter()
+            .map(|(action, version)| OutputLine::Changed {
+                action: action.clone(),
+                detail: format!("changed on both sides, kept {version}"),
+            })
+            .collect();
+        lines.extend(self.unlocked.iter().map(|spec| OutputLine::Warning {
+            message: format!("{spec} is not locked; run `gx tidy` after the merge"),
+        }));
+        lines.push(OutputLine::Summary {
+            text: format!("Merged gx.lock into {}", self.path.display()),
+        });
+        lines
+    }
+}
+
+/// Report from `gx lock convert`.
+#[derive(Debug, Default)]
+pub struct Convert {
+    /// The lock file.
+    pub path: PathBuf,
+    /// The name of the format the lock is written in now, such as `JSON`.
+    pub format: &'static str,
+    /// Whether the lock was rewritten from the other format.
+    pub converted: bool,
+    /// Whether there is no lock to convert.
+    pub missing: bool,
+}
+
+impl CommandReport for Convert {
+    fn render(&self) -> Vec<OutputLine> {
+        let path = self.path.display();
+        let text = if self.missing {
+            format!(
+                "{path} does not exist; gx tidy writes it as {}",
+                self.format
+            )
+        } else if self.converted {
+            format!("Converted {path} to {}", self.format)
+        } else {
+            format!("{path} is already {}", self.format)
+        };
+        vec![OutputLine::Summary { text }]
+    }
+}
+
+/// One entry of gx.lock, as `gx lock list` shows it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Entry {
+    pub action: String,
+    pub specifier: String,
+    pub version: String,
+    pub sha: String,
+    /// The date of the commit, as its tag or commit upstream has it.
+    pub released: String,
+    /// When gx resolved the commit; empty for entries locked before gx recorded it.
+    pub resolved_at: String,
+}
+
+/// Report from `gx lock list`.
+#[derive(Debug, Default, Serialize)]
+pub struct List {
+    /// The entries, sorted by action and specifier.
+    pub entries: Vec<Entry>,
+}
+
+impl CommandReport for List {
+    fn render(&self) -> Vec<OutputLine> {
+        if self.entries.is_empty() {
+            return vec![OutputLine::Summary {
+                text: "gx.lock has no entries".to_owned(),
+            }];
+        }
+        let day = |timestamp: &str| timestamp.get(..10).unwrap_or(timestamp).to_owned();
+        vec![OutputLine::Table {
+            headers: ["ACTION", "VERSION", "SHA", "RELEASED", "RESOLVED"]
+                .map(str::to_owned)
+                .to_vec(),
+            rows: self
+                .entries
+                .iter()
+                .map(|entry| {
+                    vec![
+                        format!("{}@{}", entry.action, entry.specifier),
+                        entry.version.clone(),
+                        short(Some(&entry.sha)),
+                        day(&entry.released),
+                        day(&entry.resolved_at),
+                    ]
+                })
+                .collect(),
+        }]
+    }
+}
+
+/// The first 7 characters of a commit SHA, as git abbreviates it.
+fn short(sha: Option<&str>) -> String {
+    sha.unwrap_or_default().chars().take(7).collect()
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{Change, CommandReport as _, Diff, Entry, Kind, List, OutputLine};
+
+    #[test]
+    fn changes_serialize_with_kebab_case_kind_and_only_their_sides() {
+        let moved = Change {
+            action: "actions/checkout".to_owned(),
+            specifier: "^4".to_owned(),
+            kind: Kind::ShaChanged,
+            old_version: Some("v4.2.0".to_owned()),
+            new_version: Some("v4.2.0".to_owned()),
+            old_sha: Some("aaa".to_owned()),
+            new_sha: Some("bbb".to_owned()),
+        };
+        let removed = Change {
+            kind: Kind::Removed,
+            new_version: None,
+            new_sha: None,
+            ..moved.clone()
+        };
+        let report = Diff {
+            changes: vec![moved],
+        };
+
+        assert_eq!(
+            serde_json::to_string(&report).unwrap(),
+            r#"{"changes":[{"action":"actions/checkout","specifier":"^4","kind":"sha-changed","old_version":"v4.2.0","new_version":"v4.2.0","old_sha":"aaa","new_sha":"bbb"}]}"#
+        );
+        assert!(!serde_json::to_string(&removed).unwrap().contains("new_"));
+    }
+
+    #[test]
+    fn list_shows_release_and_resolution_days() {
+        let report = List {
+            entries: vec![Entry {
+                action: "actions/checkout".to_owned(),
+                specifier: "^4".to_owned(),
+                version: "v4.2.0".to_owned(),
+                sha: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_owned(),
+                released: "2024-03-01T10:00:00Z".to_owned(),
+                resolved_at: "2026-01-02T08:30:00Z".to_owned(),
+            }],
+        };
+
+        let lines = report.render();
+
+        assert!(matches!(
+            lines.as_slice(),
+            [OutputLine::Table { rows, .. }]
+                if rows == &[["actions/checkout@^4", "v4.2.0", "aaaaaaa", "2024-03-01", "2026-01-02"]]
+        ));
+    }
+}
@@ -3,21 +3,37 @@
     reason = "dev-dependencies are only used in integration tests"
 )]
 
-use clap::{Parser, Subcommand};
-use gx::command::{Command as _, CommandReport as _};
-use gx::config::{Config, Error as ConfigError};
+use args::{Cli, Commands, Format};
+use clap::Parser as _;
+use gx::command::{Command, CommandReport as _};
+use gx::config::{Config, Error as ConfigError, ManifestScope, pin_conflicts, project_manifest};
+use gx::infra::trace::Tracer;
 use gx::infra::{repo, repo::Error as RepoError};
 use gx::init::Error as InitError;
 use gx::lint::Error as LintError;
 use gx::output::lines::Line as OutputLine;
 use gx::output::log_file::LogFile;
 use gx::output::printer::Printer;
+use gx::output::progress;
 use gx::tidy::RunError as TidyRunError;
 use gx::upgrade::command::RunError as UpgradeRunError;
-use gx::{init, lint, tidy, upgrade};
+use gx::{
+    audit, auth, cache, configuration, export, hash, hook, import, licenses, lock, migrate, sbom,
+    tree, trust, undo, upgrade, verify,
+};
 use indicatif::ProgressBar;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+/// Command-line arguments.
+mod args;
+/// Dispatching each subcommand to the command that runs it.
+mod dispatch;
+/// The actions of the subcommands that group several, such as `gx hook install`.
+mod subcommands;
+/// The arguments of `gx upgrade`.
+mod upgrade_args;
+
 /// Top-level error type for the gx CLI binary.
 #[derive(Debug, Error)]
 enum GxError {
@@ -52,107 +68,288 @@ enum GxError {
     /// I/O error.
     #[error(transparent)]
     Io(#[from] std::io::Error),
+
+    /// Undo command failed.
+    #[error(transparent)]
+    Undo(#[from] undo::Error),
+
+    /// Auth command failed.
+    #[error(transparent)]
+    Auth(#[from] auth::Error),
+
+    /// Cache command failed.
+    #[error(transparent)]
+    Cache(#[from] cache::Error),
+
+    /// Hook command failed.
+    #[error(transparent)]
+    Hook(#[from] hook::Error),
+
+    /// Lock command failed.
+    #[error(transparent)]
+    Lock(#[from] lock::Error),
+
+    /// Tree command failed.
+    #[error(transparent)]
+    Tree(#[from] tree::Error),
+
+    /// Export command failed.
+    #[error(transparent)]
+    Export(#[from] export::Error),
+
+    /// Import command failed.
+    #[error(transparent)]
+    Import(#[from] import::Error),
+
+    /// Audit command failed.
+    #[error(transparent)]
+    Audit(#[from] audit::Error),
+
+    /// Licenses command failed.
+    #[error(transparent)]
+    Licenses(#[from] licenses::Error),
+
+    /// Sbom command failed.
+    #[error(transparent)]
+    Sbom(#[from] sbom::Error),
+
+    /// Trust command failed.
+    #[error(transparent)]
+    Trust(#[from] trust::Error),
+
+    /// Migrate command failed.
+    #[error(transparent)]
+    Migrate(#[from] migrate::Error),
+
+    /// Hash command failed.
+    #[error(transparent)]
+    Hash(#[from] hash::Error),
+
+    /// Verify command failed.
+    #[error(transparent)]
+    Verify(#[from] verify::Error),
+
+    /// Config command failed.
+    #[error(transparent)]
+    Configuration(#[from] configuration::Error),
+
+    /// GitLab command failed.
+    #[cfg(feature = "gitlab")]
+    #[error(transparent)]
+    Gitlab(#[from] gx::gitlab::Error),
+
+    /// Writing the JSON report failed.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// Append the log file path to the output lines if a log file exists.
+fn append_log_path(log_file: Option<&LogFile>, lines: &mut Vec<OutputLine>) {
+    if let Some(lf) = log_file {
+        lines.push(OutputLine::LogPath {
+            path: lf.path().clone(),
+        });
+    }
 }
 
-#[derive(Parser)]
-#[command(name = "gx")]
-#[command(about = "CLI to manage Github Actions dependencies", long_about = None)]
-#[command(version)]
-/// CLI argument parser for the gx binary.
-struct Cli {
-    /// The subcommand to execute.
-    #[command(subcommand)]
-    command: Commands,
+/// Run a command behind a spinner, print its report, and return its exit code.
+fn run_and_print<C: Command>(
+    command: &C,
+    spinner: Option<ProgressBar>,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, C::Error> {
+    let report = {
+        let mut cb = progress::callback(spinner.as_ref(), log_file, printer.is_ci);
+        command.run(repo_root, config, &mut cb)?
+    };
+    progress::finish(spinner);
+    let mut lines = report.render();
+    append_log_path(log_file.as_ref(), &mut lines);
+    printer.print_lines(&lines);
+    Ok(report.exit_code())
 }
 
-/// Available subcommands for the gx CLI.
-#[derive(Subcommand)]
-enum Commands {
-    /// Ensure the manifest and lock matches the workflow code.
-    Tidy,
-    /// Create manifest and lock files from current workflows.
-    Init,
-    /// Upgrade actions to newer versions.
-    Upgrade {
-        /// Optional action identifier to upgrade (e.g., `actions/checkout`).
-        #[arg(value_name = "ACTION")]
-        action: Option<String>,
-        /// Upgrade to the latest version instead of safe update.
-        #[arg(long)]
-        latest: bool,
-    },
-    /// Run lint checks on workflows.
-    ///
-    /// Reports action-hygiene issues (sha-mismatch, unpinned, stale-comment,
-    /// unsynced-manifest) and workflow-security issues (missing-permissions,
-    /// excessive-permissions, dangerous-trigger, pr-head-checkout,
-    /// missing-concurrency, unprotected-secrets). Configure per-rule severity
-    /// and ignores under `[lint.rules]` in `.github/gx.toml`. See
-    /// `docs/lint-rules.md`.
-    Lint,
+/// Run a command behind a spinner, print its report in `format`, and return its exit code.
+///
+/// JSON keeps progress off stdout so the output stays machine-readable; NDJSON streams it
+/// there as `progress` events while the command runs, ahead of the report's events.
+fn run_and_print_as<C: Command>(
+    command: &C,
+    format: Format,
+    spinner: Option<ProgressBar>,
+    printer: &Printer,
+    repo_root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError>
+where
+    C::Report: serde::Serialize,
+    GxError: From<C::Error>,
+{
+    if format == Format::Text {
+        return Ok(run_and_print(
+            command, spinner, printer, repo_root, config, log_file,
+        )?);
+    }
+    let report = if format == Format::Json {
+        let mut cb = progress::callback(spinner.as_ref(), log_file, false);
+        command.run(repo_root, config, &mut cb)?
+    } else {
+        let mut cb = progress::streaming(spinner.as_ref(), log_file, printer);
+        command.run(repo_root, config, &mut cb)?
+    };
+    progress::finish(spinner);
+    if format == Format::Json {
+        printer.print_json(&report)?;
+    } else {
+        printer.print_ndjson(&report.events())?;
+    }
+    Ok(report.exit_code())
 }
 
-/// Create a progress callback that updates the spinner, log file, and CI output.
-fn make_cb<'cb>(
-    spinner: Option<&'cb ProgressBar>,
-    log_file: &'cb mut Option<LogFile>,
-    is_ci: bool,
-) -> impl FnMut(&str) + 'cb {
-    move |msg: &str| {
-        if let Some(pb) = spinner {
-            pb.set_message(msg.to_owned());
-        }
-        if let Some(lf) = log_file.as_mut() {
-            lf.write(msg);
-        }
-        if is_ci {
-            use std::time::{SystemTime, UNIX_EPOCH};
-            let secs = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs();
-            let h = (secs / 3600) % 24;
-            let m = (secs / 60) % 60;
-            let s = secs % 60;
-            #[expect(
-                clippy::print_stdout,
-                reason = "CI verbose mode outputs directly to stdout"
-            )]
-            {
-                println!(" [{h:02}:{m:02}:{s:02}] {msg}");
-            }
+/// Find the repository root: `repo_root` when `--repo-root` gave one, otherwise the
+/// repository around the working directory.
+///
+/// Returns `None`, after telling the user, when there is no `.github` folder and
+/// `command` needs one; commands that don't need a repository run from the given or
+/// working directory instead.
+fn find_repo_root(
+    printer: &Printer,
+    command: &Commands,
+    repo_root: Option<&Path>,
+) -> Result<Option<PathBuf>, GxError> {
+    let (start, found) = if let Some(root) = repo_root {
+        (root.to_path_buf(), repo::open_root(root))
+    } else {
+        let cwd = std::env::current_dir()?;
+        let found = repo::find_root(&cwd);
+        (cwd, found)
+    };
+    match found {
+        Ok(root) => Ok(Some(root)),
+        Err(_) if !command.needs_repo() => Ok(Some(start)),
+        Err(RepoError::GithubFolder) => {
+            printer.print_lines(&[OutputLine::Summary {
+                text: ".github folder not found. gx didn't modify any file.".to_owned(),
+            }]);
+            Ok(None)
         }
+        Err(e) => Err(e.into()),
     }
 }
 
-/// Clear and finish the spinner if present.
-fn finish_spinner(spinner: Option<ProgressBar>) {
-    if let Some(pb) = spinner {
-        pb.finish_and_clear();
+/// Load the configuration of `root` with the global flags of `cli` applied.
+///
+/// Keys of gx.toml that gx does not know are warned about, or fail the run under
+/// `--strict`.
+fn load_config(
+    root: &Path,
+    cli: &Cli,
+    tracer: &Tracer,
+    printer: &Printer,
+) -> Result<Config, GxError> {
+    let mut config = Config::load(root)?;
+    if cli.strict {
+        config.deny_unknown_keys()?;
+    } else if cli.command.format() == Format::Text
+        && !matches!(cli.command, Commands::Config { .. })
+    {
+        let warnings: Vec<OutputLine> = config
+            .unknown_key_warnings()
+            .into_iter()
+            .map(|message| OutputLine::Warning { message })
+            .collect();
+        printer.print_lines(&warnings);
     }
+    config.settings.offline |= cli.offline;
+    config.settings.trusted_only |= cli.trusted_only;
+    config.files.backup |= cli.backup;
+    config.settings.tracer = tracer.clone();
+    Ok(config)
 }
 
-/// Append the log file path to the output lines if a log file exists.
-fn append_log_path(log_file: Option<&LogFile>, lines: &mut Vec<OutputLine>) {
-    if let Some(lf) = log_file {
-        lines.push(OutputLine::LogPath {
-            path: lf.path().clone(),
-        });
+/// Make `config`, of the project at `root`, use the gx.lock at `lock_path` shared with the
+/// other `members`, as `[projects] manifest = "shared-lock"` asks. The others' manifests
+/// are read as they are now, so those rewritten by an earlier run count as rewritten.
+fn share_lock(
+    config: &mut Config,
+    root: &Path,
+    lock_path: &Path,
+    members: &[PathBuf],
+) -> Result<(), GxError> {
+    let others = members
+        .iter()
+        .filter(|member| member.as_path() != root)
+        .map(|member| project_manifest(member))
+        .collect::<Result<Vec<_>, _>>()?;
+    config.share_lock(lock_path, &others.iter().collect::<Vec<_>>())?;
+    Ok(())
+}
+
+/// A warning for each action the manifests of `members`, under `repo_root`, pin to
+/// different specifiers while sharing one gx.lock.
+fn lock_conflict_warnings(
+    repo_root: &Path,
+    members: &[PathBuf],
+) -> Result<Vec<OutputLine>, GxError> {
+    let manifests = members
+        .iter()
+        .map(|member| project_manifest(member))
+        .collect::<Result<Vec<_>, _>>()?;
+    let labelled: Vec<(String, &_)> = members
+        .iter()
+        .map(|member| project_label(repo_root, member))
+        .zip(&manifests)
+        .collect();
+    Ok(pin_conflicts(&labelled)
+        .into_iter()
+        .map(|message| OutputLine::Warning { message })
+        .collect())
+}
+
+/// How the project at `root` is named in output: its path under `repo_root`, or `.`.
+fn project_label(repo_root: &Path, root: &Path) -> String {
+    if root == repo_root {
+        ".".to_owned()
+    } else {
+        root.strip_prefix(repo_root)
+            .unwrap_or(root)
+            .display()
+            .to_string()
     }
 }
 
+/// Run the command of `cli` in the repository or project at `root`, and return its exit
+/// code.
+///
+/// The run locks `root` and snapshots the files the command may rewrite, so no other run
+/// interleaves writes, `gx undo` can revert the run, and a failed run is rolled back.
+fn run_in(
+    cli: &Cli,
+    printer: &Printer,
+    root: &Path,
+    config: Config,
+    log_file: &mut Option<LogFile>,
+) -> Result<i32, GxError> {
+    let session = cli
+        .command
+        .writes_files()
+        .then(|| undo::begin(root, &config))
+        .transpose()?;
+    let result = dispatch::run_command(cli.command.clone(), printer, root, config, log_file);
+    undo::settle(root, cli.command.name(), session.as_ref(), result)
+}
+
 fn main() -> Result<(), GxError> {
     let cli = Cli::parse();
 
     let printer = Printer::new();
     let is_ci = printer.is_ci;
 
-    let cmd_name = match &cli.command {
-        Commands::Tidy => "tidy",
-        Commands::Init => "init",
-        Commands::Upgrade { .. } => "upgrade",
-        Commands::Lint => "lint",
-    };
+    let cmd_name = cli.command.name();
 
     // Create log file for local runs (not CI)
     let mut log_file: Option<LogFile> = if is_ci {
@@ -161,94 +358,62 @@ fn main() -> Result<(), GxError> {
         LogFile::new(cmd_name).ok()
     };
 
-    if is_ci {
+    if is_ci && cli.command.format() == Format::Text {
         printer.print_lines(&[OutputLine::CiNotice {
             message: "CI detected, running in verbose mode".to_owned(),
         }]);
     }
 
-    let cwd = std::env::current_dir()?;
-    let repo_root = match repo::find_root(&cwd) {
-        Ok(root) => root,
-        Err(RepoError::GithubFolder) => {
-            printer.print_lines(&[OutputLine::Summary {
-                text: ".github folder not found. gx didn't modify any file.".to_owned(),
-            }]);
-            return Ok(());
-        }
-        Err(e) => return Err(e.into()),
+    let Some(repo_root) = find_repo_root(&printer, &cli.command, cli.repo_root.as_deref())? else {
+        return Ok(());
     };
 
-    let config = Config::load(&repo_root)?;
-
-    match cli.command {
-        Commands::Tidy => {
-            let spinner = printer.spinner("Running tidy...");
-            let mut lf = log_file.take();
-            let report = {
-                let mut cb = make_cb(spinner.as_ref(), &mut lf, is_ci);
-                tidy::Tidy.run(&repo_root, config, &mut cb)?
-            };
-            finish_spinner(spinner);
-            let mut lines = report.render();
-            append_log_path(lf.as_ref(), &mut lines);
-            printer.print_lines(&lines);
-            if report.exit_code() != 0 {
-                std::process::exit(report.exit_code());
-            }
-            log_file = lf;
-        }
-        Commands::Init => {
-            let spinner = printer.spinner("Initializing...");
-            let mut lf = log_file.take();
-            let report = {
-                let mut cb = make_cb(spinner.as_ref(), &mut lf, is_ci);
-                init::Init.run(&repo_root, config, &mut cb)?
-            };
-            finish_spinner(spinner);
-            let mut lines = report.render();
-            append_log_path(lf.as_ref(), &mut lines);
-            printer.print_lines(&lines);
-            if report.exit_code() != 0 {
-                std::process::exit(report.exit_code());
-            }
-            log_file = lf;
+    let tracer = Tracer::new(cli.verbose, cli.har.is_some(), printer.trace_sink());
+    // Sub-projects that keep their own gx.toml and gx.lock get a run of their own
+    let mut config = load_config(&repo_root, &cli, &tracer, &printer)?;
+    let projects = if cli.command.covers_projects() {
+        config.projects.separate_roots(&repo_root)
+    } else {
+        Vec::new()
+    };
+    // Under "shared-lock", every project resolves into the root's gx.lock
+    let lock_path = config.lock_path.clone();
+    let members: Vec<PathBuf> = if config.projects.manifest == ManifestScope::SharedLock {
+        std::iter::once(repo_root.clone())
+            .chain(projects.iter().cloned())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    if members.len() > 1 {
+        share_lock(&mut config, &repo_root, &lock_path, &members)?;
+        if cli.command.format() == Format::Text {
+            printer.print_lines(&lock_conflict_warnings(&repo_root, &members)?);
         }
-        Commands::Upgrade { action, latest } => {
-            let request = upgrade::cli::resolve_upgrade_mode(action.as_deref(), latest)?;
-            let spinner = printer.spinner("Checking actions...");
-            let mut lf = log_file.take();
-            let report = {
-                let mut cb = make_cb(spinner.as_ref(), &mut lf, is_ci);
-                upgrade::command::Upgrade { request }.run(&repo_root, config, &mut cb)?
-            };
-            finish_spinner(spinner);
-            let mut lines = report.render();
-            append_log_path(lf.as_ref(), &mut lines);
-            printer.print_lines(&lines);
-            if report.exit_code() != 0 {
-                std::process::exit(report.exit_code());
-            }
-            log_file = lf;
+    }
+    let heading = |root: &Path| {
+        if !projects.is_empty() && cli.command.format() == Format::Text {
+            let path = project_label(&repo_root, root);
+            printer.print_lines(&[OutputLine::Project { path }]);
         }
-        Commands::Lint => {
-            let spinner = printer.spinner("Linting...");
-            let mut lf = log_file.take();
-            let report = {
-                let mut cb = make_cb(spinner.as_ref(), &mut lf, is_ci);
-                lint::Lint.run(&repo_root, config, &mut cb)?
-            };
-            finish_spinner(spinner);
-            let mut lines = report.render();
-            append_log_path(lf.as_ref(), &mut lines);
-            printer.print_lines(&lines);
-            if report.exit_code() != 0 {
-                std::process::exit(report.exit_code());
-            }
-            log_file = lf;
+    };
+    heading(&repo_root);
+    let mut exit_code = run_in(&cli, &printer, &repo_root, config, &mut log_file)?;
+    for project in &projects {
+        heading(project);
+        let mut project_config = load_config(project, &cli, &tracer, &printer)?;
+        if members.len() > 1 {
+            share_lock(&mut project_config, project, &lock_path, &members)?;
         }
+        let code = run_in(&cli, &printer, project, project_config, &mut log_file)?;
+        exit_code = exit_code.max(code);
+    }
+    if let Some(path) = &cli.har {
+        tracer.write_har(path)?;
     }
-
     drop(log_file);
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
     Ok(())
 }
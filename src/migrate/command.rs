@@ -0,0 +1,64 @@
+use super::report::Report;
+use crate::command::{Command, CommandReport as _};
+use crate::config::Config;
+use crate::domain::workflow::Error as WorkflowError;
+use crate::infra::workflow_update::WorkflowWriter;
+use crate::init::{Error as InitError, Init};
+use crate::tidy::{RunError as TidyError, Tidy};
+use std::path::Path;
+use thiserror::Error;
+
+/// Errors that can occur during `gx migrate`.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Workflow(#[from] WorkflowError),
+    #[error(transparent)]
+    Init(#[from] InitError),
+    #[error(transparent)]
+    Tidy(#[from] TidyError),
+}
+
+/// `gx migrate ratchet`: rewrite ratchet's `# ratchet:owner/action@v4` comments into
+/// gx's `SHA # v4`, then record the actions in gx.toml and gx.lock as `gx init` does,
+/// or as `gx tidy` does when gx.toml already exists.
+pub struct Ratchet;
+
+impl Command for Ratchet {
+    type Report = Report;
+    type Error = Error;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        config: Config,
+        on_progress: &mut dyn FnMut(&str),
+    ) -> Result<Report, Error> {
+        on_progress("Rewriting ratchet comments...");
+        let rewritten = WorkflowWriter::new(repo_root)
+            .with_workflows(config.workflows.clone())
+            .migrate_ratchet()?;
+        let comments = rewritten
+            .iter()
+            .flat_map(|result| {
+                let file = result.file.strip_prefix(repo_root).unwrap_or(&result.file);
+                result
+                    .changes
+                    .iter()
+                    .map(move |change| (file.display().to_string(), change.clone()))
+            })
+            .collect();
+        let recorded = if config.manifest_path.exists() {
+            let tidy = Tidy {
+                commit_message: None,
+                fix: false,
+                changes: None,
+                check: false,
+            };
+            tidy.run(repo_root, config, on_progress)?.render()
+        } else {
+            Init.run(repo_root, config, on_progress)?.render()
+        };
+        Ok(Report { comments, recorded })
+    }
+}
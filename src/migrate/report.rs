@@ -0,0 +1,29 @@
+use crate::command::CommandReport;
+use crate::output::lines::Line as OutputLine;
+
+/// Report from `gx migrate ratchet`.
+#[derive(Debug, Default)]
+pub struct Report {
+    /// The comments rewritten: (workflow file, `action: # old → # new`).
+    pub comments: Vec<(String, String)>,
+    /// What recording the actions in gx.toml and gx.lock reported.
+    pub recorded: Vec<OutputLine>,
+}
+
+impl CommandReport for Report {
+    fn render(&self) -> Vec<OutputLine> {
+        let mut lines: Vec<OutputLine> = self
+            .comments
+            .iter()
+            .map(|(file, change)| OutputLine::Changed {
+                action: file.clone(),
+                detail: change.clone(),
+            })
+            .collect();
+        lines.push(OutputLine::Summary {
+            text: format!("Rewrote {} ratchet comment(s)", self.comments.len()),
+        });
+        lines.extend(self.recorded.iter().cloned());
+        lines
+    }
+}
@@ -0,0 +1,85 @@
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// One machine-readable event of a command run, printed as a JSON line by `--format ndjson`.
+///
+/// Each object carries its kind in the `event` field, e.g.
+/// `{"event":"upgraded","action":"actions/checkout","from":"v4.1.0","to":"v4.2.0"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum Event {
+    /// A step of the run in progress, printed as it happens.
+    Progress { message: String },
+    /// An action was added to the manifest.
+    Added { action: String, specifier: String },
+    /// An action was removed from the manifest.
+    Removed { action: String },
+    /// A specifier was locked to a version and commit SHA.
+    Resolved {
+        action: String,
+        specifier: String,
+        version: String,
+        sha: String,
+    },
+    /// An action moved to a newer version.
+    Upgraded {
+        action: String,
+        from: String,
+        to: String,
+    },
+    /// A manifest entry was corrected to match the workflows.
+    Corrected {
+        action: String,
+        from: String,
+        to: String,
+    },
+    /// A floating tag points to a different commit than the lock.
+    TagMoved {
+        action: String,
+        tag: String,
+        locked: String,
+        current: String,
+        repinned: bool,
+    },
+    /// An action was left alone.
+    Skipped { action: String, reason: String },
+    /// An action's repository was renamed or transferred.
+    Renamed {
+        from: String,
+        to: String,
+        fixed: bool,
+    },
+    /// A workflow file was rewritten.
+    FileUpdated { path: PathBuf },
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "tests use unwrap, indexing, and other patterns freely"
+)]
+mod tests {
+    use super::Event;
+
+    #[test]
+    fn event_serializes_with_kebab_case_kind() {
+        let file_updated = serde_json::to_string(&Event::FileUpdated {
+            path: ".github/workflows/ci.yml".into(),
+        })
+        .unwrap();
+        assert_eq!(
+            file_updated,
+            r#"{"event":"file-updated","path":".github/workflows/ci.yml"}"#
+        );
+
+        let skipped = serde_json::to_string(&Event::Skipped {
+            action: "actions/checkout".to_owned(),
+            reason: "held in gx.toml".to_owned(),
+        })
+        .unwrap();
+        assert_eq!(
+            skipped,
+            r#"{"event":"skipped","action":"actions/checkout","reason":"held in gx.toml"}"#
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::table;
 use crate::config::Level;
 use console::style;
 use std::path::PathBuf;
@@ -12,6 +13,13 @@ pub enum Line {
         from: String,
         to: String,
     },
+    /// Aligned columns with a header row.
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    /// An indented detail line shown under the preceding action line.
+    Detail { text: String },
     /// An action was added.
     Added { action: String, version: String },
     /// An action was removed.
@@ -37,8 +45,12 @@ pub enum Line {
     LogPath { path: PathBuf },
     /// CI mode notice.
     CiNotice { message: String },
+    /// The heading of the output for one project of a monorepo.
+    Project { path: String },
     /// A blank separator line.
     Blank,
+    /// Text printed as it is, such as a generated configuration to copy.
+    Text { text: String },
 }
 
 impl Line {
@@ -54,6 +66,8 @@ impl Line {
                 };
                 format!(" {arrow} {action:<30} {from} → {to}")
             }
+            Line::Table { headers, rows } => format_table(headers, rows, use_color),
+            Line::Detail { text } => format!("     {text}"),
             Line::Added { action, version } => {
                 let plus = if use_color {
                     style("+").green().to_string()
@@ -91,23 +105,6 @@ impl Line {
                 rule,
                 message,
             } => {
-                let colored_symbol = match level {
-                    Level::Error => {
-                        if use_color {
-                            style("✗").red().to_string()
-                        } else {
-                            "✗".to_owned()
-                        }
-                    }
-                    Level::Warn => {
-                        if use_color {
-                            style("⚠").yellow().to_string()
-                        } else {
-                            "⚠".to_owned()
-                        }
-                    }
-                    Level::Off => String::new(),
-                };
                 let location = workflow
                     .as_ref()
                     .map(|w| match line {
@@ -115,7 +112,10 @@ impl Line {
                         None => format!("{w}: "),
                     })
                     .unwrap_or_default();
-                format!(" {colored_symbol} {location}{rule}: {message}")
+                format!(
+                    " {} {location}{rule}: {message}",
+                    level_symbol(*level, use_color)
+                )
             }
             Line::Summary { text } => {
                 let check = if use_color {
@@ -141,11 +141,47 @@ impl Line {
                 };
                 format!(" {prefix} {message}")
             }
+            Line::Project { path } => {
+                let heading = format!("▸ {path}");
+                if use_color {
+                    format!("\n{}", style(heading).bold())
+                } else {
+                    format!("\n{heading}")
+                }
+            }
             Line::Blank => String::new(),
+            Line::Text { text } => text.clone(),
         }
     }
 }
 
+/// The symbol a lint diagnostic of `level` starts with.
+fn level_symbol(level: Level, use_color: bool) -> String {
+    match level {
+        Level::Error if use_color => style("✗").red().to_string(),
+        Level::Error => "✗".to_owned(),
+        Level::Warn if use_color => style("⚠").yellow().to_string(),
+        Level::Warn => "⚠".to_owned(),
+        Level::Off => String::new(),
+    }
+}
+
+/// Render an aligned table, indented like other lines, with a bold header row when colored.
+fn format_table(headers: &[String], rows: &[Vec<String>], use_color: bool) -> String {
+    let mut lines = table::render(headers, rows).into_iter();
+    let plain_header = lines.next().unwrap_or_default();
+    let header = if use_color {
+        style(plain_header).bold().to_string()
+    } else {
+        plain_header
+    };
+    std::iter::once(header)
+        .chain(lines)
+        .map(|line| format!(" {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Level, Line};
@@ -165,6 +201,18 @@ mod tests {
         assert!(result.contains("v4"));
     }
 
+    #[test]
+    fn format_line_table_indents_every_row() {
+        let line = Line::Table {
+            headers: vec!["ACTION".to_owned(), "SHA".to_owned()],
+            rows: vec![vec!["actions/checkout".to_owned(), "08c6903".to_owned()]],
+        };
+        assert_eq!(
+            line.format_line(false),
+            " ACTION            SHA\n actions/checkout  08c6903"
+        );
+    }
+
     #[test]
     fn format_line_lint_diag_no_color() {
         let line = Line::LintDiag {
@@ -235,6 +283,14 @@ mod tests {
         assert_eq!(result, "");
     }
 
+    #[test]
+    fn format_line_text_is_verbatim() {
+        let line = Line::Text {
+            text: "version: 2\nupdates: []".to_owned(),
+        };
+        assert_eq!(line.format_line(true), "version: 2\nupdates: []");
+    }
+
     #[test]
     fn format_line_added_no_color() {
         let line = Line::Added {
@@ -1,3 +1,6 @@
+pub mod events;
 pub mod lines;
 pub mod log_file;
 pub mod printer;
+pub mod progress;
+pub mod table;
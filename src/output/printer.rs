@@ -1,6 +1,8 @@
+use super::events::Event;
 use super::lines::Line as OutputLine;
 use console::Term;
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::Duration;
 
 /// Terminal output handler: detects CI/TTY/color and prints `OutputLine` values.
@@ -9,6 +11,8 @@ pub struct Printer {
     pub use_color: bool,
     /// Whether we're running in CI mode.
     pub is_ci: bool,
+    /// The spinner last started, suspended while trace lines are printed over it.
+    active: Arc<Mutex<Option<ProgressBar>>>,
 }
 
 impl Printer {
@@ -19,7 +23,11 @@ impl Printer {
         let is_tty = Term::stdout().is_term();
         let no_color = std::env::var("NO_COLOR").is_ok();
         let use_color = is_tty && !no_color && !is_ci;
-        Self { use_color, is_ci }
+        Self {
+            use_color,
+            is_ci,
+            active: Arc::default(),
+        }
     }
 
     /// Create a spinner for long operations. Returns `None` if CI or not a TTY.
@@ -38,9 +46,17 @@ impl Printer {
         );
         pb.enable_steady_tick(Duration::from_millis(80));
         pb.set_message(message.to_owned());
+        *self.active.lock().unwrap_or_else(PoisonError::into_inner) = Some(pb.clone());
         Some(pb)
     }
 
+    /// A function printing trace lines to stderr, hiding the spinner while it does so the
+    /// two don't garble each other.
+    pub fn trace_sink(&self) -> impl Fn(&str) + Send + Sync + 'static {
+        let active = Arc::clone(&self.active);
+        move |line: &str| suspended(&active, || eprintln!("{line}"))
+    }
+
     /// Print a list of `OutputLine` values to stdout with optional color.
     /// # Panics
     ///
@@ -53,6 +69,61 @@ impl Printer {
             drop(writeln!(handle, "{}", line.format_line(self.use_color)));
         }
     }
+
+    /// Print `value` to stdout as pretty JSON (`--format json`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` cannot be serialized or stdout cannot be written.
+    pub fn print_json<T: serde::Serialize>(&self, value: &T) -> Result<(), serde_json::Error> {
+        use std::io::Write as _;
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        serde_json::to_writer_pretty(&mut handle, value)?;
+        writeln!(handle).map_err(serde_json::Error::io)
+    }
+
+    /// Print `events` to stdout as one JSON object per line (`--format ndjson`), hiding
+    /// the spinner meanwhile since events are printed while it runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an event cannot be serialized or stdout cannot be written.
+    pub fn print_ndjson(&self, events: &[Event]) -> Result<(), serde_json::Error> {
+        use std::io::Write as _;
+        suspended(&self.active, || {
+            let stdout = std::io::stdout();
+            let mut handle = stdout.lock();
+            for event in events {
+                serde_json::to_writer(&mut handle, event)?;
+                writeln!(handle).map_err(serde_json::Error::io)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Run `print` with the spinner in `active`, if any, hidden.
+fn suspended<R, F: FnOnce() -> R>(active: &Mutex<Option<ProgressBar>>, print: F) -> R {
+    let spinner = active
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    match spinner {
+        Some(pb) => pb.suspend(print),
+        None => print(),
+    }
+}
+
+/// Ask a yes/no question on the terminal. Anything other than `y`/`yes` is a no.
+#[must_use]
+pub fn confirm(prompt: &str) -> bool {
+    let term = Term::stderr();
+    if term.write_str(&format!("{prompt} [y/N] ")).is_err() {
+        return false;
+    }
+    term.read_line()
+        .is_ok_and(|answer| matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
 }
 
 impl Default for Printer {
@@ -83,6 +154,7 @@ mod tests {
         let printer = Printer {
             use_color: false,
             is_ci: false,
+            active: Arc::default(),
         };
         assert!(!printer.use_color);
     }
@@ -92,6 +164,7 @@ mod tests {
         let printer = Printer {
             use_color: false,
             is_ci: true,
+            active: Arc::default(),
         };
         // spinner() returns None when is_ci = true
         // (can't test the TTY check in unit tests, but CI path is deterministic)
@@ -0,0 +1,61 @@
+use super::events::Event;
+use super::log_file::LogFile;
+use super::printer::Printer;
+use indicatif::ProgressBar;
+
+/// Create a progress callback that updates the spinner, log file, and CI output.
+pub fn callback<'cb>(
+    spinner: Option<&'cb ProgressBar>,
+    log_file: &'cb mut Option<LogFile>,
+    is_ci: bool,
+) -> impl FnMut(&str) + 'cb {
+    move |msg: &str| {
+        if let Some(pb) = spinner {
+            pb.set_message(msg.to_owned());
+        }
+        if let Some(lf) = log_file.as_mut() {
+            lf.write(msg);
+        }
+        if is_ci {
+            use std::time::{SystemTime, UNIX_EPOCH};
+            let secs = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let h = (secs / 3600) % 24;
+            let m = (secs / 60) % 60;
+            let s = secs % 60;
+            #[expect(
+                clippy::print_stdout,
+                reason = "CI verbose mode outputs directly to stdout"
+            )]
+            {
+                println!(" [{h:02}:{m:02}:{s:02}] {msg}");
+            }
+        }
+    }
+}
+
+/// Create a progress callback for `--format ndjson`: like [`callback`], and also printing
+/// each message as a `progress` event on stdout the moment it happens.
+pub fn streaming<'cb>(
+    spinner: Option<&'cb ProgressBar>,
+    log_file: &'cb mut Option<LogFile>,
+    printer: &'cb Printer,
+) -> impl FnMut(&str) + 'cb {
+    let mut update = callback(spinner, log_file, false);
+    move |msg: &str| {
+        update(msg);
+        // A reader that went away is noticed when the report's own events are printed
+        drop(printer.print_ndjson(&[Event::Progress {
+            message: msg.to_owned(),
+        }]));
+    }
+}
+
+/// Clear and finish the spinner if present.
+pub fn finish(spinner: Option<ProgressBar>) {
+    if let Some(pb) = spinner {
+        pb.finish_and_clear();
+    }
+}
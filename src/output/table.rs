@@ -0,0 +1,57 @@
+/// Align `rows` under `headers` into columns separated by two spaces.
+///
+/// Widths are measured in characters; the last column is not padded.
+#[must_use]
+pub fn render(headers: &[String], rows: &[Vec<String>]) -> Vec<String> {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    std::iter::once(headers)
+        .chain(rows.iter().map(Vec::as_slice))
+        .map(|cells| {
+            let padded: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect();
+            padded.join("  ").trim_end().to_owned()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+
+    fn cells(values: &[&str]) -> Vec<String> {
+        values.iter().map(|&value| value.to_owned()).collect()
+    }
+
+    #[test]
+    fn render_aligns_columns_to_widest_cell() {
+        let lines = render(
+            &cells(&["ACTION", "SHA"]),
+            &[
+                cells(&["actions/checkout", "08c6903"]),
+                cells(&["jdx/mise-action", "-"]),
+            ],
+        );
+        assert_eq!(
+            lines,
+            vec![
+                "ACTION            SHA",
+                "actions/checkout  08c6903",
+                "jdx/mise-action   -",
+            ]
+        );
+    }
+
+    #[test]
+    fn render_counts_characters_not_bytes() {
+        let lines = render(&cells(&["FROM", "TO"]), &[cells(&["→", "v2"])]);
+        assert_eq!(lines, vec!["FROM  TO", "→     v2"]);
+    }
+}
@@ -0,0 +1,160 @@
+use super::document::{Component, cyclonedx, spdx};
+use super::report::Report;
+use crate::command::Command;
+use crate::config::Config;
+use crate::domain::lock::Lock;
+use crate::infra::atomic;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use time::OffsetDateTime;
+
+/// Errors that can occur during `gx sbom`.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("gx.lock has no actions to list; run `gx tidy` first")]
+    EmptyLock,
+
+    #[error("failed to write {}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// The SBOM standard `gx sbom` writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A `CycloneDX` 1.5 JSON document.
+    CycloneDx,
+    /// An SPDX 2.3 JSON document.
+    Spdx,
+}
+
+/// `gx sbom`: list every action gx.lock pins, with its version, commit, and repository,
+/// in a software bill of materials, so the dependencies of the workflows reach the
+/// same tooling as those of the code.
+#[derive(Debug)]
+pub struct Sbom {
+    /// The standard of the document.
+    pub format: Format,
+    /// Where to write the document; it is printed when unset.
+    pub file: Option<PathBuf>,
+}
+
+impl Command for Sbom {
+    type Report = Report;
+    type Error = Error;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<Report, Error> {
+        let components = components(&config.lock);
+        if components.is_empty() {
+            return Err(Error::EmptyLock);
+        }
+        let name = repo_root.file_name().map_or_else(
+            || "workflows".to_owned(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        let created = timestamp();
+        let document = match self.format {
+            Format::CycloneDx => cyclonedx(&components, &created),
+            Format::Spdx => spdx(&name, &components, &created),
+        };
+        let mut text = serde_json::to_string_pretty(&document).unwrap_or_default();
+        text.push('\n');
+        let written = self.file.as_ref().map(|file| repo_root.join(file));
+        if let Some(path) = &written {
+            atomic::write(path, &text).map_err(|source| Error::Write {
+                path: path.clone(),
+                source,
+            })?;
+        }
+        Ok(Report {
+            text,
+            written,
+            actions: components.len(),
+        })
+    }
+}
+
+/// The actions gx.lock pins, one per action, version, and commit, sorted.
+fn components(lock: &Lock) -> Vec<Component> {
+    let components: BTreeSet<Component> = lock
+        .entries()
+        .map(|(spec, entry)| Component {
+            action: spec.id.to_string(),
+            repository: entry.commit.repository.to_string(),
+            version: entry.version.to_string(),
+            sha: entry.commit.sha.to_string(),
+        })
+        .collect();
+    components.into_iter().collect()
+}
+
+/// The current time in UTC, as RFC 3339.
+fn timestamp() -> String {
+    let now = OffsetDateTime::now_utc();
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        now.year(),
+        u8::from(now.month()),
+        now.day(),
+        now.hour(),
+        now.minute(),
+        now.second()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::components;
+    use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Version};
+    use crate::domain::action::resolved::{Commit, Provenance};
+    use crate::domain::action::spec::Spec;
+    use crate::domain::action::specifier::Specifier;
+    use crate::domain::lock::Lock;
+
+    #[test]
+    fn components_list_each_locked_commit_once() {
+        let mut lock = Lock::default();
+        for (action, specifier, version, sha) in [
+            ("actions/checkout", "^4", "v4.2.2", "aaa111"),
+            ("actions/checkout", "^4.2", "v4.2.2", "aaa111"),
+            ("github/codeql-action/init", "^3", "v3.28.0", "bbb222"),
+        ] {
+            lock.set(
+                &Spec::new(ActionId::from(action), Specifier::parse(specifier)),
+                Version::from(version),
+                Commit {
+                    sha: CommitSha::from(sha),
+                    repository: ActionId::from(action).base_repo(),
+                    ref_type: None,
+                    date: CommitDate::from("2026-01-01T00:00:00Z"),
+                    provenance: Provenance::default(),
+                },
+            );
+        }
+
+        let listed: Vec<(String, String)> = components(&lock)
+            .into_iter()
+            .map(|component| (component.action, component.repository))
+            .collect();
+
+        assert_eq!(
+            listed,
+            [
+                ("actions/checkout".to_owned(), "actions/checkout".to_owned()),
+                (
+                    "github/codeql-action/init".to_owned(),
+                    "github/codeql-action".to_owned()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use serde_json::{Value, json};
+
+/// An action gx.lock pins, as the SBOM lists it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Component {
+    /// The action, such as `github/codeql-action/init`.
+    pub action: String,
+    /// The repository of its commit, such as `github/codeql-action`.
+    pub repository: String,
+    /// The locked version.
+    pub version: String,
+    /// The locked commit SHA.
+    pub sha: String,
+}
+
+impl Component {
+    /// The package URL of the action, with an action in a subdirectory of its repository
+    /// as the subpath: `pkg:githubactions/github/codeql-action@v3#init`.
+    fn purl(&self) -> String {
+        let purl = format!("pkg:githubactions/{}@{}", self.repository, self.version);
+        match self
+            .action
+            .strip_prefix(self.repository.as_str())
+            .and_then(|path| path.strip_prefix('/'))
+        {
+            Some(subpath) if !subpath.is_empty() => format!("{purl}#{subpath}"),
+            _ => purl,
+        }
+    }
+
+    /// The URL of the repository the action comes from.
+    fn url(&self) -> String {
+        format!("https://github.com/{}", self.repository)
+    }
+}
+
+/// A `CycloneDX` 1.5 document listing `components`, created at `created`.
+pub fn cyclonedx(components: &[Component], created: &str) -> Value {
+    let entries: Vec<Value> = components
+        .iter()
+        .map(|component| {
+            json!({
+                "type": "library",
+                "bom-ref": component.purl(),
+                "name": component.action,
+                "version": component.version,
+                "purl": component.purl(),
+                "hashes": [{ "alg": "SHA-1", "content": component.sha }],
+                "externalReferences": [{ "type": "vcs", "url": component.url() }],
+            })
+        })
+        .collect();
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "timestamp": created,
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": "gx",
+                    "version": env!("CARGO_PKG_VERSION"),
+                }],
+            },
+        },
+        "components": entries,
+    })
+}
+
+/// An SPDX 2.3 document named after `name` listing `components`, created at `created`.
+pub fn spdx(name: &str, components: &[Component], created: &str) -> Value {
+    let ids: Vec<String> = (1..=components.len())
+        .map(|index| format!("SPDXRef-Action-{index}"))
+        .collect();
+    let packages: Vec<Value> = components
+        .iter()
+        .zip(&ids)
+        .map(|(component, id)| {
+            json!({
+                "SPDXID": id,
+                "name": component.action,
+                "versionInfo": component.version,
+                "downloadLocation": format!("git+{}@{}", component.url(), component.sha),
+                "filesAnalyzed": false,
+                "checksums": [{ "algorithm": "SHA1", "checksumValue": component.sha }],
+                "externalRefs": [{
+                    "referenceCategory": "PACKAGE-MANAGER",
+                    "referenceType": "purl",
+                    "referenceLocator": component.purl(),
+                }],
+            })
+        })
+        .collect();
+    let relationships: Vec<Value> = ids
+        .iter()
+        .map(|id| {
+            json!({
+                "spdxElementId": "SPDXRef-DOCUMENT",
+                "relationshipType": "DESCRIBES",
+                "relatedSpdxElement": id,
+            })
+        })
+        .collect();
+    json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": format!("{name} GitHub Actions"),
+        "documentNamespace": format!("https://spdx.org/spdxdocs/gx-{name}-{created}"),
+        "creationInfo": {
+            "created": created,
+            "creators": [format!("Tool: gx-{}", env!("CARGO_PKG_VERSION"))],
+        },
+        "packages": packages,
+        "relationships": relationships,
+    })
+}
+
+#[cfg(test)]
+#[expect(clippy::indexing_slicing, reason = "tests use indexing freely")]
+mod tests {
+    use super::{Component, cyclonedx, spdx};
+
+    /// The `github/codeql-action/init` action, locked at `v3.28.0`.
+    fn codeql_init() -> Component {
+        Component {
+            action: "github/codeql-action/init".to_owned(),
+            repository: "github/codeql-action".to_owned(),
+            version: "v3.28.0".to_owned(),
+            sha: "bbb222".to_owned(),
+        }
+    }
+
+    #[test]
+    fn purl_names_the_subdirectory_of_an_action_as_its_subpath() {
+        let checkout = Component {
+            action: "actions/checkout".to_owned(),
+            repository: "actions/checkout".to_owned(),
+            version: "v4.2.2".to_owned(),
+            sha: "aaa111".to_owned(),
+        };
+
+        assert_eq!(checkout.purl(), "pkg:githubactions/actions/checkout@v4.2.2");
+        assert_eq!(
+            codeql_init().purl(),
+            "pkg:githubactions/github/codeql-action@v3.28.0#init"
+        );
+    }
+
+    #[test]
+    fn documents_carry_version_commit_and_repository() {
+        let components = [codeql_init()];
+
+        let bom = cyclonedx(&components, "2026-01-01T00:00:00Z");
+        let document = spdx("repo", &components, "2026-01-01T00:00:00Z");
+
+        let component = &bom["components"][0];
+        assert_eq!(component["version"], "v3.28.0");
+        assert_eq!(component["hashes"][0]["content"], "bbb222");
+        assert_eq!(
+            component["externalReferences"][0]["url"],
+            "https://github.com/github/codeql-action"
+        );
+        let package = &document["packages"][0];
+        assert_eq!(package["SPDXID"], "SPDXRef-Action-1");
+        assert_eq!(
+            package["downloadLocation"],
+            "git+https://github.com/github/codeql-action@bbb222"
+        );
+        assert_eq!(
+            document["relationships"][0]["relatedSpdxElement"],
+            "SPDXRef-Action-1"
+        );
+    }
+}
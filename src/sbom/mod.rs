@@ -0,0 +1,9 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// Sbom command: error types, struct, and `Command` implementation.
+mod command;
+/// The `CycloneDX` and SPDX documents the command writes.
+mod document;
+pub mod report;
+
+pub use command::{Error, Format, Sbom};
@@ -0,0 +1,31 @@
+use crate::command::CommandReport;
+use crate::output::lines::Line as OutputLine;
+use std::path::PathBuf;
+
+/// Report from `gx sbom`.
+#[derive(Debug, Default)]
+pub struct Report {
+    /// The generated document.
+    pub text: String,
+    /// The file it was written to, when it was not printed.
+    pub written: Option<PathBuf>,
+    /// How many actions it lists.
+    pub actions: usize,
+}
+
+impl CommandReport for Report {
+    fn render(&self) -> Vec<OutputLine> {
+        match &self.written {
+            Some(path) => vec![OutputLine::Summary {
+                text: format!(
+                    "Wrote an SBOM of {} action(s) to {}",
+                    self.actions,
+                    path.display()
+                ),
+            }],
+            None => vec![OutputLine::Text {
+                text: self.text.trim_end().to_owned(),
+            }],
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use super::args::Format;
+use clap::Subcommand;
+use gx::{sbom, verify};
+use std::path::PathBuf;
+
+/// Subcommands of `gx auth`.
+#[derive(Clone, Subcommand)]
+pub enum AuthAction {
+    /// Log in with the OAuth device flow and store the token in the OS keyring.
+    ///
+    /// Needs the client ID of a GitHub OAuth app with device flow enabled.
+    Login {
+        /// Client ID of the OAuth app to log in with (default: `GX_OAUTH_CLIENT_ID`);
+        /// required, as gx ships none.
+        #[arg(long, value_name = "ID")]
+        client_id: Option<String>,
+    },
+    /// Show which credential gx would use.
+    Status,
+}
+
+/// Subcommands of `gx cache`.
+#[derive(Clone, Copy, Subcommand)]
+pub enum CacheAction {
+    /// Show where the cache is and how much it holds.
+    Info,
+    /// Remove every cached API response and resolved version.
+    Clean,
+}
+
+/// Subcommands of `gx hook`.
+#[derive(Clone, Copy, Subcommand)]
+pub enum HookAction {
+    /// Write a git pre-commit hook that runs `gx lint --changed` and
+    /// `gx tidy --check --staged`.
+    Install {
+        /// Add the checks to `.pre-commit-config.yaml` for the pre-commit framework instead.
+        #[arg(long)]
+        pre_commit_config: bool,
+    },
+    /// Remove the hook and the `.pre-commit-config.yaml` entry `gx hook install` added.
+    Uninstall,
+}
+
+/// Subcommands of `gx import`.
+#[derive(Clone, Subcommand)]
+pub enum ImportAction {
+    /// Turn the `github-actions` ignore rules and groups of a Dependabot configuration
+    /// into holds and `[groups]` in gx.toml.
+    Dependabot {
+        /// The configuration to read (default: `.github/dependabot.yml`).
+        #[arg(long, value_name = "FILE")]
+        file: Option<PathBuf>,
+    },
+}
+
+/// Subcommands of `gx trust`.
+#[derive(Clone, Subcommand)]
+pub enum TrustAction {
+    /// Print the approved action, version, and commit triples of gx-trust.toml and
+    /// gx.lock, for review.
+    Export {
+        /// Write the database to FILE instead of printing it.
+        #[arg(long, value_name = "FILE")]
+        file: Option<PathBuf>,
+        /// Sign FILE with this SSH private key, writing `FILE.sig`.
+        #[arg(long, value_name = "KEY", requires = "file")]
+        key: Option<PathBuf>,
+    },
+    /// Add the triples of a reviewed database to gx-trust.toml.
+    Import {
+        /// The database to import.
+        file: PathBuf,
+        /// The `allowed_signers` file to check `FILE.sig` against.
+        #[arg(
+            long,
+            value_name = "FILE",
+            requires = "identity",
+            required_unless_present = "insecure_skip_verify"
+        )]
+        allowed_signers: Option<PathBuf>,
+        /// The signer identity in the allowed signers file.
+        #[arg(
+            long,
+            value_name = "ID",
+            requires = "allowed_signers",
+            required_unless_present = "insecure_skip_verify"
+        )]
+        identity: Option<String>,
+        /// Import FILE without checking its signature.
+        #[arg(long, conflicts_with_all = ["allowed_signers", "identity"])]
+        insecure_skip_verify: bool,
+    },
+}
+
+/// Subcommands of `gx migrate`.
+#[derive(Clone, Copy, Subcommand)]
+pub enum MigrateAction {
+    /// Rewrite `# ratchet:owner/action@v4` comments into gx's `SHA # v4` and record the
+    /// actions in gx.toml and gx.lock.
+    Ratchet,
+}
+
+/// Subcommands of `gx export`.
+#[derive(Clone, Subcommand)]
+pub enum ExportAction {
+    /// Generate `.github/dependabot.yml` ignore rules for the actions gx.toml manages.
+    Dependabot {
+        /// Write the configuration to this file instead of printing it.
+        #[arg(long, value_name = "FILE")]
+        file: Option<PathBuf>,
+    },
+    /// Generate a Renovate package rule disabling updates of the actions gx.toml manages.
+    Renovate {
+        /// Write the configuration to this file instead of printing it.
+        #[arg(long, value_name = "FILE")]
+        file: Option<PathBuf>,
+    },
+    /// List the `owner/action@sha` of every action gx.lock pins, as the "Allow specified
+    /// actions" policy of an organization or repository takes them.
+    AllowedActions {
+        /// Write the list to this file instead of printing it.
+        #[arg(long, value_name = "FILE")]
+        file: Option<PathBuf>,
+        /// Set the policy through the GitHub API, replacing its allowed actions
+        /// (default target: the repository of the `origin` remote).
+        #[arg(long)]
+        apply: bool,
+        /// Set the policy of this organization.
+        #[arg(long, value_name = "ORG", requires = "apply", conflicts_with = "repo")]
+        org: Option<String>,
+        /// Set the policy of this repository, as `owner/repo`.
+        #[arg(long, value_name = "OWNER/REPO", requires = "apply")]
+        repo: Option<String>,
+    },
+}
+
+/// Subcommands of `gx lock`.
+#[derive(Clone, Subcommand)]
+pub enum LockAction {
+    /// List the entries of gx.lock with when each commit was released and resolved.
+    List {
+        /// Output format of the report.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t)]
+        format: Format,
+    },
+    /// List the actions added, removed, or re-pinned between two lock files.
+    Diff {
+        /// The old lock: a file, or a git object such as `main:.github/gx.lock`
+        /// (default: gx.lock at HEAD).
+        #[arg(value_name = "OLD")]
+        old: Option<String>,
+        /// The new lock, in the same forms (default: gx.lock in the work tree).
+        #[arg(value_name = "NEW")]
+        new: Option<String>,
+        /// Output format of the report.
+        #[arg(long, visible_alias = "output", value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Rewrite gx.lock in the format `[files] lock_format` names, TOML or JSON.
+    Convert,
+    /// Merge two changes of a lock file, as a git merge driver: `gx lock merge %O %A %B`.
+    Merge {
+        /// The common ancestor of both changes.
+        #[arg(value_name = "BASE")]
+        base: PathBuf,
+        /// The current branch's lock, replaced with the merged lock.
+        #[arg(value_name = "OURS")]
+        ours: PathBuf,
+        /// The merged branch's lock.
+        #[arg(value_name = "THEIRS")]
+        theirs: PathBuf,
+    },
+}
+
+/// Subcommands of `gx config`.
+#[derive(Clone, Copy, Subcommand)]
+pub enum ConfigAction {
+    /// Check gx.toml for unknown keys and lint ignores that name no workflow.
+    Validate,
+    /// Print the JSON Schema of gx.toml, for editor completion.
+    Schema,
+}
+
+/// Subcommands of `gx gitlab`.
+#[cfg(feature = "gitlab")]
+#[derive(Clone, Copy, Subcommand)]
+pub enum GitlabAction {
+    /// Pin `include:component` and `include:project` refs in `.gitlab-ci.yml`.
+    Pin,
+}
+
+/// Flags of `gx verify`.
+#[derive(Clone, clap::Args)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each bool is an independent CLI flag"
+)]
+pub struct VerifyArgs {
+    /// Also fail when gx.toml changed since gx.lock was written.
+    #[arg(long)]
+    frozen: bool,
+    /// Also check through the GitHub API that each locked tag still leads to its
+    /// locked commit, as the tag itself or in its history.
+    #[arg(long)]
+    remote: bool,
+    /// Also check that an attestation vouches for each locked commit, failing when
+    /// an immutable release attests another one.
+    #[arg(long)]
+    provenance: bool,
+    /// Also check that each locked commit, or its annotated tag, carries a signature
+    /// GitHub verified, failing for actions marked `require_signed = true`.
+    #[arg(long)]
+    signatures: bool,
+}
+
+impl VerifyArgs {
+    /// The `gx verify` run the flags ask for.
+    #[must_use]
+    pub fn into_command(self) -> verify::Verify {
+        verify::Verify {
+            frozen: self.frozen,
+            remote: self.remote,
+            provenance: self.provenance,
+            signatures: self.signatures,
+        }
+    }
+}
+
+/// Flags of `gx sbom`.
+#[derive(Clone, clap::Args)]
+pub struct SbomArgs {
+    /// The SBOM standard to write.
+    #[arg(long, value_enum, default_value_t)]
+    format: SbomFormat,
+    /// Write the document to this file instead of printing it.
+    #[arg(long, value_name = "FILE")]
+    file: Option<PathBuf>,
+}
+
+/// The SBOM standard `gx sbom` writes.
+#[derive(Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+enum SbomFormat {
+    /// `CycloneDX` 1.5 JSON.
+    #[default]
+    Cyclonedx,
+    /// SPDX 2.3 JSON.
+    Spdx,
+}
+
+impl SbomArgs {
+    /// The `gx sbom` run the flags ask for.
+    #[must_use]
+    pub fn into_command(self) -> sbom::Sbom {
+        sbom::Sbom {
+            format: match self.format {
+                SbomFormat::Cyclonedx => sbom::Format::CycloneDx,
+                SbomFormat::Spdx => sbom::Format::Spdx,
+            },
+            file: self.file,
+        }
+    }
+}
@@ -9,17 +9,20 @@ use crate::domain::manifest::Manifest;
 use crate::domain::resolution::{ActionResolver, VersionRegistry};
 use crate::domain::workflow::{Error as WorkflowError, Scanner as WorkflowScanner};
 use crate::domain::workflow_actions::ActionSet as WorkflowActionSet;
+use crate::infra::git::{Changes, Error as GitError, Git};
 use crate::infra::github::{Error as GithubError, Registry as GithubRegistry};
-use crate::infra::lock::{Error as LockFileError, Store as LockStore};
-use crate::infra::manifest::Error as ManifestError;
+use crate::infra::lock::Error as LockFileError;
 use crate::infra::manifest::patch::apply_manifest_diff;
+use crate::infra::manifest::{self, Error as ManifestError};
+use crate::infra::oci::Error as OciError;
 use crate::infra::workflow_scan::FileScanner as FileWorkflowScanner;
 use crate::infra::workflow_update::WorkflowWriter;
-use report::Report;
+use report::{Renamed, Report, Resolved};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use super::images::{lock_images, pin_workflows, pinned_image};
 use super::lock_sync;
 use super::manifest_sync;
 use super::patches;
@@ -34,6 +37,8 @@ pub struct Plan {
     /// The diff between the original and planned lock — for reporting only.
     pub lock_changes: LockDiff,
     pub workflows: Vec<WorkflowPatch>,
+    /// Held actions the run would have changed: (action, why it was left alone).
+    pub skipped: Vec<(String, String)>,
 }
 
 impl Plan {
@@ -50,6 +55,21 @@ pub enum Error {
     #[error("failed to resolve {count} action(s):\n  {specs}")]
     ResolutionFailed { count: usize, specs: String },
 
+    /// The GitHub API requests allowed per run ran out before every action was resolved.
+    #[error(
+        "stopped after {limit} GitHub API requests ([registry] max_requests): resolved {resolved} action(s), {count} not resolved:\n  {specs}"
+    )]
+    RequestLimit {
+        limit: usize,
+        resolved: usize,
+        count: usize,
+        specs: String,
+    },
+
+    /// Offline, some actions are missing from the lock and would need the registry.
+    #[error("{count} action(s) missing from gx.lock, which --offline cannot resolve:\n  {specs}")]
+    Offline { count: usize, specs: String },
+
     /// Workflow files could not be scanned or updated.
     #[error(transparent)]
     Workflow(#[from] WorkflowError),
@@ -64,6 +84,8 @@ pub enum Error {
 ///
 /// Returns [`Error::Workflow`] if workflows cannot be scanned.
 /// Returns [`Error::ResolutionFailed`] if actions cannot be resolved.
+/// Returns [`Error::RequestLimit`] if the registry's request limit is reached.
+/// Returns [`Error::Offline`] if the registry is offline and the lock is missing entries.
 pub fn plan<R, P, F>(
     manifest: &Manifest,
     lock: &Lock,
@@ -84,7 +106,10 @@ where
         located.push(action);
     }
     if located.is_empty() {
-        return Ok(Plan::default());
+        return Ok(Plan {
+            lock: lock.clone(),
+            ..Plan::default()
+        });
     }
 
     // Work on clones to compute the planned state
@@ -118,6 +143,16 @@ where
     planned_manifest.sync_overrides(&located, &action_set);
     planned_manifest.prune_stale_overrides(&located);
 
+    // Held actions keep their manifest entry no matter what the workflows say
+    let skipped: Vec<(String, String)> = planned_manifest
+        .restore_held(manifest)
+        .into_iter()
+        .filter_map(|id| Some((id.to_string(), manifest.hold_note(&id)?)))
+        .collect();
+    for (id, note) in &skipped {
+        on_progress(&format!("Skipping {id} ({note})"));
+    }
+
     // Build SHA map: workflow SHA for each (action, manifest_version) pair
     let workflow_shas: HashMap<Spec, CommitSha> = located
         .iter()
@@ -156,10 +191,13 @@ where
         lock: planned_lock,
         lock_changes: lock_diff,
         workflows: workflow_patches,
+        skipped,
     })
 }
 
-/// Apply workflow patches: write pin changes to workflow files and log results.
+/// Apply workflow patches: write pin changes to workflow files.
+///
+/// Returns the workflow files that changed.
 ///
 /// # Errors
 ///
@@ -167,9 +205,42 @@ where
 pub fn apply_workflow_patches(
     writer: &WorkflowWriter,
     patches: &[WorkflowPatch],
-) -> Result<usize, Error> {
+) -> Result<Vec<PathBuf>, Error> {
     let results = writer.apply_patches(patches)?;
-    Ok(results.len())
+    Ok(results.into_iter().map(|result| result.file).collect())
+}
+
+/// The repositories the registry was redirected for during the run, as renamed.
+///
+/// Renamed repositories still resolve through the API's redirect. With `fix`, the
+/// workflows that use one are rewritten to the new name, and returned.
+///
+/// # Errors
+///
+/// Returns [`Error::Workflow`] if a workflow file cannot be rewritten.
+fn fix_renames(
+    registry: &GithubRegistry,
+    updater: &WorkflowWriter,
+    fix: bool,
+) -> Result<(Vec<Renamed>, Vec<PathBuf>), Error> {
+    let renamed = registry.renamed_repositories();
+    let rewritten = if fix && !renamed.is_empty() {
+        updater.rename_repositories(&renamed)?
+    } else {
+        Vec::new()
+    };
+    let report = renamed
+        .into_iter()
+        .map(|(from, to)| Renamed {
+            from: from.to_string(),
+            to: to.to_string(),
+            fixed: fix,
+        })
+        .collect();
+    Ok((
+        report,
+        rewritten.into_iter().map(|result| result.file).collect(),
+    ))
 }
 
 /// Errors that can occur during the tidy command's run phase (I/O + domain).
@@ -182,11 +253,75 @@ pub enum RunError {
     #[error(transparent)]
     Lock(#[from] LockFileError),
     #[error(transparent)]
+    Git(#[from] GitError),
+    #[error(transparent)]
+    Oci(#[from] OciError),
+    #[error(transparent)]
     Tidy(#[from] Error),
 }
 
+/// Commit message used by `gx tidy --commit` when `--commit-message` is not given.
+pub const DEFAULT_COMMIT_MESSAGE: &str = "chore(gx): tidy pinned actions";
+
 /// The tidy command struct.
-pub struct Tidy;
+#[derive(Debug, Default)]
+pub struct Tidy {
+    /// When set, commit the changed files to the current branch with this message.
+    pub commit_message: Option<String>,
+    /// Rewrite workflows that use a renamed repository to use its new name.
+    pub fix: bool,
+    /// When set, only the workflow files with these changes in git are rewritten. Every
+    /// workflow is still read, so gx.toml and gx.lock keep the actions of the others.
+    pub changes: Option<Changes>,
+    /// Report what would change and write nothing, failing when anything would.
+    pub check: bool,
+}
+
+impl Tidy {
+    /// The writer for the workflows tidy may rewrite: those `[workflows]` selects, limited
+    /// to the ones with `changes` when set, and writing nothing with `check`.
+    fn writer(&self, repo_root: &Path, config: &Config) -> Result<WorkflowWriter, GitError> {
+        let mut writer = WorkflowWriter::new(repo_root).with_workflows(config.workflows.clone());
+        if let Some(changes) = &self.changes {
+            writer = writer.limited_to(Git::new(repo_root).changed_files(changes)?);
+        }
+        Ok(if self.check { writer.dry_run() } else { writer })
+    }
+}
+
+/// Write the manifest changes of `tidy_plan` to gx.toml, then its lock to gx.lock along
+/// with the hash of the gx.toml it was written for.
+fn save(config: &Config, tidy_plan: &mut Plan) -> Result<(), RunError> {
+    apply_manifest_diff(&config.manifest_path, &tidy_plan.manifest)?;
+    tidy_plan
+        .lock
+        .set_manifest_hash(config.lock_hash(manifest::hash_file(&config.manifest_path)?));
+    config.lock_store()?.save(&tidy_plan.lock)?;
+    Ok(())
+}
+
+/// The lock entries `lock_changes` adds, as the report lists them.
+fn resolved(lock_changes: &LockDiff) -> Vec<Resolved> {
+    lock_changes
+        .added
+        .iter()
+        .map(|(spec, entry)| Resolved {
+            action: spec.id.to_string(),
+            specifier: spec.specifier.to_string(),
+            version: entry.version.to_string(),
+            sha: entry.commit.sha.to_string(),
+        })
+        .collect()
+}
+
+/// Whether gx.lock needs rewriting even when no entry changed: it was written for another
+/// gx.toml, or in an older schema.
+fn lock_stale(config: &Config) -> bool {
+    config.lock_migrated
+        || (config.manifest_path.exists()
+            && config.lock.manifest_hash()
+                != Some(config.lock_hash(manifest::hash(&config.manifest)).as_str()))
+}
 
 impl Command for Tidy {
     type Report = Report;
@@ -199,39 +334,79 @@ impl Command for Tidy {
         on_progress: &mut dyn FnMut(&str),
     ) -> Result<Report, RunError> {
         let has_manifest = config.manifest_path.exists();
-        if config.manifest_migrated {
-            on_progress("migrated gx.toml → semver specifiers");
+        for notice in config.migration_notices() {
+            on_progress(&notice);
         }
-        if config.settings.github_token.is_none() {
+        let registry = GithubRegistry::from_settings(&config.settings)?;
+        if registry.token.is_none() && !config.settings.offline {
             on_progress(
-                "Warning: No GITHUB_TOKEN set — using unauthenticated GitHub API (60 requests/hour limit).",
+                "Warning: No GitHub token found (GITHUB_TOKEN, GH_TOKEN, gh CLI, or OS keyring) — using unauthenticated GitHub API (60 requests/hour limit).",
             );
         }
-        let registry = GithubRegistry::new(config.settings.github_token)?;
-        let scanner = FileWorkflowScanner::new(repo_root);
-        let updater = WorkflowWriter::new(repo_root);
+        let scanner = FileWorkflowScanner::new(repo_root).with_workflows(config.workflows.clone());
+        let updater = self.writer(repo_root, &config)?;
 
         let original_manifest = config.manifest.clone();
 
-        let tidy_plan = plan(
+        let mut tidy_plan = plan(
             &config.manifest,
             &config.lock,
             &registry,
             &scanner,
-            on_progress,
+            &mut *on_progress,
         )?;
+        if let Some(warning) = registry.rate_limit_warning() {
+            on_progress(&format!("Warning: {warning}"));
+        }
 
-        if tidy_plan.is_empty() {
-            return Ok(Report::default());
+        // With `--fix`, workflows move to the new name and tidy carries it into gx.toml
+        let (renamed, renamed_workflows) = fix_renames(&registry, &updater, self.fix)?;
+        if !renamed_workflows.is_empty() {
+            tidy_plan = plan(
+                &config.manifest,
+                &config.lock,
+                &registry,
+                &scanner,
+                &mut *on_progress,
+            )?;
         }
 
-        if has_manifest {
-            apply_manifest_diff(&config.manifest_path, &tidy_plan.manifest)?;
-            let lock_store = LockStore::new(&config.lock_path);
-            lock_store.save(&tidy_plan.lock)?;
+        // Container images are pinned to digests, which the lock keeps under [images]
+        let image_pins = lock_images(&scanner, &config.settings, &mut tidy_plan.lock)?;
+        let images_changed =
+            !image_pins.is_empty() || !tidy_plan.lock.images().eq(config.lock.images());
+
+        let unknown_group_members =
+            manifest_sync::unknown_group_members(&config.manifest, &tidy_plan.manifest);
+        let unchanged = tidy_plan.is_empty() && renamed_workflows.is_empty() && !images_changed;
+        if unchanged && !lock_stale(&config) {
+            return Ok(Report {
+                renamed,
+                unknown_group_members,
+                ..Report::default()
+            });
         }
 
-        let workflows_updated = apply_workflow_patches(&updater, &tidy_plan.workflows)?;
+        if has_manifest && !self.check {
+            save(&config, &mut tidy_plan)?;
+        }
+
+        let mut workflows = apply_workflow_patches(&updater, &tidy_plan.workflows)?;
+        let pinned_workflows = pin_workflows(&updater, &image_pins)?;
+        for file in renamed_workflows.into_iter().chain(pinned_workflows) {
+            if !workflows.contains(&file) {
+                workflows.push(file);
+            }
+        }
+
+        // `--check` writes nothing to commit
+        if let Some(message) = self.commit_message.as_ref().filter(|_| !self.check) {
+            let mut files = workflows.clone();
+            if has_manifest {
+                files.extend([config.manifest_path.clone(), config.lock_path.clone()]);
+            }
+            Git::new(repo_root).commit_paths(&files, message)?;
+        }
 
         let report = Report {
             removed: tidy_plan.manifest.removed,
@@ -247,7 +422,13 @@ impl Command for Tidy {
                     (id, old_v, new_v)
                 })
                 .collect(),
-            workflows_updated,
+            resolved: resolved(&tidy_plan.lock_changes),
+            skipped: tidy_plan.skipped,
+            images: image_pins.iter().map(pinned_image).collect(),
+            workflows,
+            renamed,
+            unknown_group_members,
+            out_of_date: self.check,
         };
 
         Ok(report)
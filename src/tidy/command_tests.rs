@@ -2,7 +2,7 @@
 
 use super::{Error as TidyError, apply_workflow_patches, plan};
 use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
-use crate::domain::action::resolved::Commit;
+use crate::domain::action::resolved::{Commit, Provenance};
 use crate::domain::action::spec::Spec;
 use crate::domain::action::specifier::Specifier;
 use crate::domain::action::uses_ref::RefType;
@@ -107,6 +107,7 @@ jobs:
             repository: Repository::from("actions/checkout"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         },
     );
     seeded_lock.set(
@@ -117,6 +118,7 @@ jobs:
             repository: Repository::from("actions/checkout"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         },
     );
     let lock_store = lock::Store::new(&lock_path);
@@ -209,6 +211,7 @@ jobs:
             repository: Repository::from("actions/checkout"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         },
     );
 
@@ -275,6 +278,7 @@ fn plan_one_new_action_produces_added_entries() {
             repository: Repository::from("actions/checkout"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         },
     );
 
@@ -323,6 +327,7 @@ fn plan_removed_action_produces_removed_entries() {
             repository: Repository::from("actions/checkout"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         },
     );
     lock.set(
@@ -336,6 +341,7 @@ fn plan_removed_action_produces_removed_entries() {
             repository: Repository::from("actions/setup-node"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         },
     );
 
@@ -398,6 +404,7 @@ fn plan_everything_in_sync_returns_empty_plan() {
             repository: Repository::from("actions/checkout"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         },
     );
 
@@ -0,0 +1,212 @@
+use super::command::{Error, RunError};
+use super::report::Resolved;
+use crate::config::Settings;
+use crate::domain::image::{
+    Digest, DigestRegistry, Error as ImageError, Located as LocatedImage, Reference,
+};
+use crate::domain::lock::Lock;
+use crate::infra::oci::Registry as OciRegistry;
+use crate::infra::workflow_scan::FileScanner as FileWorkflowScanner;
+use crate::infra::workflow_update::WorkflowWriter;
+use std::path::PathBuf;
+
+/// Lock the images of `docker://` steps, job containers, and services, and pick the
+/// digest each tagged image is pinned to.
+///
+/// An image already pinned to a digest, with its tag in the comment, locks that
+/// digest. An image that names only a tag takes the locked digest, or the one `registry`
+/// resolves the tag to. Images no step uses any more are dropped from the lock.
+///
+/// Returns the images to pin in the workflows, with their digests.
+///
+/// # Errors
+///
+/// Returns [`Error::Offline`] if the registry is offline and the lock has no digest
+/// for an image.
+/// Returns [`Error::ResolutionFailed`] if an image tag cannot be resolved.
+pub fn sync_images<R: DigestRegistry>(
+    images: &[LocatedImage],
+    lock: &mut Lock,
+    registry: &R,
+) -> Result<Vec<(Reference, Digest)>, Error> {
+    let mut pins: Vec<(Reference, Digest)> = Vec::new();
+    let (mut offline, mut failed) = (Vec::new(), Vec::new());
+    let mut keys = Vec::new();
+    for located in images {
+        let image = &located.image;
+        let Some(key) = image.lock_key() else {
+            continue;
+        };
+        keys.push(key.clone());
+        if let Some(digest) = image.digest() {
+            if lock.image(&key).is_none() {
+                lock.set_image(key, digest.clone());
+            }
+            continue;
+        }
+        if pins.iter().any(|(pinned, _)| pinned == image) {
+            continue;
+        }
+        let digest = match lock.image(&key) {
+            Some(digest) => digest.clone(),
+            None => match registry.digest(image) {
+                Ok(digest) => {
+                    lock.set_image(key, digest.clone());
+                    digest
+                }
+                Err(ImageError::Offline) => {
+                    offline.push(format!("docker://{image}"));
+                    continue;
+                }
+                Err(e) => {
+                    failed.push(e.to_string());
+                    continue;
+                }
+            },
+        };
+        pins.push((image.clone(), digest));
+    }
+    if !failed.is_empty() {
+        return Err(Error::ResolutionFailed {
+            count: failed.len(),
+            specs: failed.join("\n  "),
+        });
+    }
+    if !offline.is_empty() {
+        return Err(Error::Offline {
+            count: offline.len(),
+            specs: offline.join("\n  "),
+        });
+    }
+    lock.retain_images(&keys);
+    Ok(pins)
+}
+
+/// Scan the container images of the workflows and [`sync_images`] them against the
+/// OCI registries they name.
+///
+/// # Errors
+///
+/// Returns an error if the workflows cannot be scanned, the registry client cannot be
+/// created, or an image cannot be locked.
+pub fn lock_images(
+    scanner: &FileWorkflowScanner,
+    settings: &Settings,
+    lock: &mut Lock,
+) -> Result<Vec<(Reference, Digest)>, RunError> {
+    let images = scanner.scan_images().map_err(Error::from)?;
+    let registry = OciRegistry::from_settings(settings)?;
+    Ok(sync_images(&images, lock, &registry)?)
+}
+
+/// Pin each image of `pins` to its digest in the workflows, returning the files changed.
+///
+/// # Errors
+///
+/// Returns [`Error::Workflow`] if a workflow cannot be read or written.
+pub fn pin_workflows(
+    updater: &WorkflowWriter,
+    pins: &[(Reference, Digest)],
+) -> Result<Vec<PathBuf>, Error> {
+    Ok(updater
+        .pin_images(pins)?
+        .into_iter()
+        .map(|result| result.file)
+        .collect())
+}
+
+/// The report entry for an image pinned to `digest`.
+pub fn pinned_image((image, digest): &(Reference, Digest)) -> Resolved {
+    let tag = image.tag().unwrap_or_default();
+    Resolved {
+        action: format!("docker://{}", image.name()),
+        specifier: tag.to_owned(),
+        version: tag.to_owned(),
+        sha: digest.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{Error, sync_images};
+    use crate::domain::image::{
+        Digest, DigestRegistry, Error as ImageError, Located as LocatedImage, Reference,
+    };
+    use crate::domain::lock::Lock;
+    use crate::domain::workflow_actions::{Location, WorkflowPath};
+
+    const DIGEST: &str = "sha256:1111111111111111111111111111111111111111111111111111111111111111";
+    const LOCKED: &str = "sha256:2222222222222222222222222222222222222222222222222222222222222222";
+
+    /// Resolves every tag to [`DIGEST`], or fails as offline.
+    struct FakeRegistry {
+        offline: bool,
+    }
+
+    impl DigestRegistry for FakeRegistry {
+        fn digest(&self, _image: &Reference) -> Result<Digest, ImageError> {
+            if self.offline {
+                return Err(ImageError::Offline);
+            }
+            Ok(Digest::from(DIGEST))
+        }
+    }
+
+    fn docker_step(reference: &str, comment: Option<&str>) -> LocatedImage {
+        LocatedImage {
+            image: Reference::parse(reference, comment).unwrap(),
+            location: Location {
+                workflow: WorkflowPath::new(".github/workflows/ci.yml".to_owned()),
+                job: None,
+                step: None,
+                step_name: None,
+                line: None,
+            },
+        }
+    }
+
+    #[test]
+    fn sync_images_pins_tags_from_the_lock_or_the_registry_and_prunes_the_rest() {
+        let mut lock = Lock::default();
+        lock.set_image("node:20".to_owned(), Digest::from(LOCKED));
+        lock.set_image("gone:1".to_owned(), Digest::from(LOCKED));
+        let images = [
+            docker_step("alpine:3.19", None),
+            docker_step("node:20", None),
+            docker_step(&format!("ruby@{LOCKED}"), Some("3.3")),
+        ];
+
+        let pins = sync_images(&images, &mut lock, &FakeRegistry { offline: false }).unwrap();
+
+        let pinned: Vec<(String, &str)> = pins
+            .iter()
+            .map(|(image, digest)| (image.to_string(), digest.as_str()))
+            .collect();
+        assert_eq!(
+            pinned,
+            vec![
+                ("alpine:3.19".to_owned(), DIGEST),
+                ("node:20".to_owned(), LOCKED)
+            ]
+        );
+        let locked: Vec<&str> = lock.images().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(locked, vec!["alpine:3.19", "node:20", "ruby:3.3"]);
+    }
+
+    #[test]
+    fn sync_images_offline_names_images_missing_from_the_lock() {
+        let mut lock = Lock::default();
+
+        let result = sync_images(
+            &[docker_step("alpine:3.19", None)],
+            &mut lock,
+            &FakeRegistry { offline: true },
+        );
+
+        assert!(matches!(
+            result,
+            Err(Error::Offline { count: 1, specs }) if specs == "docker://alpine:3.19"
+        ));
+    }
+}
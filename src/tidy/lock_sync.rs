@@ -7,15 +7,24 @@ use crate::domain::action::tag_selection::ShaIndex;
 use crate::domain::event::Event as SyncEvent;
 use crate::domain::lock::Lock;
 use crate::domain::manifest::Manifest;
-use crate::domain::resolution::{ActionResolver, Error as ResolutionError, VersionRegistry};
+use crate::domain::resolution::{
+    ActionResolver, Error as ResolutionError, Pending, VersionRegistry,
+};
 
 /// Resolve all specs in the manifest into the lock.
 ///
+/// Missing and `latest` specs are resolved concurrently, then written to the lock in
+/// manifest order so the result does not depend on which lookup finishes first.
+///
 /// Returns events including skip/warning events for recoverable errors.
 ///
 /// # Errors
 ///
 /// Returns [`TidyError::ResolutionFailed`] if any actions could not be resolved with a strict error.
+/// Returns [`TidyError::RequestLimit`] if the registry ran out of requests, naming the
+/// specs that were left unresolved.
+/// Returns [`TidyError::Offline`] if specs missing from the lock needed the registry
+/// while offline; offline, `latest` specs keep their locked version.
 pub(super) fn update_lock<R: VersionRegistry>(
     lock: &mut Lock,
     manifest: &mut Manifest,
@@ -26,6 +35,10 @@ pub(super) fn update_lock<R: VersionRegistry>(
     let mut events: Vec<SyncEvent> = Vec::new();
     let mut unresolved = Vec::new();
     let mut recoverable_count: usize = 0;
+    let mut resolved_count: usize = 0;
+    let mut over_limit = Vec::new();
+    let mut request_limit = None;
+    let mut not_locked = Vec::new();
 
     // Build all specs in one pass: global + override versions
     let all_specs: Vec<ActionSpec> = manifest
@@ -38,26 +51,68 @@ pub(super) fn update_lock<R: VersionRegistry>(
         }))
         .collect();
 
-    let needs_resolving = all_specs.iter().any(|spec| !lock.has(spec));
+    let needs_resolving = all_specs
+        .iter()
+        .any(|spec| !lock.has(spec) || spec.specifier.is_latest());
 
     if !needs_resolving {
         return Ok(events);
     }
 
-    for spec in &all_specs {
-        if let Err(e) = populate_lock_entry(lock, resolver, spec, workflow_shas, sha_index) {
-            if e.is_recoverable() {
+    // `latest` specs are always re-resolved so the lock tracks the newest stable release.
+    let pending: Vec<Pending> = all_specs
+        .into_iter()
+        .filter(|spec| spec.specifier.is_latest() || !lock.has(spec))
+        .map(|spec| {
+            let sha = if spec.specifier.is_latest() {
+                None
+            } else {
+                workflow_shas.get(&spec).cloned()
+            };
+            Pending { spec, sha }
+        })
+        .collect();
+
+    let results = resolver.resolve_all(&pending, sha_index);
+    for (Pending { spec, .. }, result) in pending.iter().zip(results) {
+        match result {
+            Ok(action) => {
+                lock.set(spec, action.version, action.commit);
+                resolved_count = resolved_count.saturating_add(1);
+            }
+            Err(ResolutionError::RequestLimit { limit }) => {
+                request_limit = Some(limit);
+                over_limit.push(spec.to_string());
+            }
+            Err(ResolutionError::Offline) if lock.has(spec) => {}
+            Err(ResolutionError::Offline) => not_locked.push(spec.to_string()),
+            Err(e) if e.is_recoverable() => {
                 events.push(SyncEvent::ResolutionSkipped {
                     spec: spec.clone(),
                     reason: e.to_string(),
                 });
                 recoverable_count = recoverable_count.saturating_add(1);
-            } else {
-                unresolved.push(format!("{spec}: {e}"));
             }
+            Err(e) => unresolved.push(format!("{spec}: {e}")),
         }
     }
 
+    if let Some(limit) = request_limit {
+        return Err(TidyError::RequestLimit {
+            limit,
+            resolved: resolved_count,
+            count: over_limit.len(),
+            specs: over_limit.join("\n  "),
+        });
+    }
+
+    if !not_locked.is_empty() {
+        return Err(TidyError::Offline {
+            count: not_locked.len(),
+            specs: not_locked.join("\n  "),
+        });
+    }
+
     if recoverable_count > 0 {
         events.push(SyncEvent::RecoverableWarning {
             count: recoverable_count,
@@ -74,45 +129,6 @@ pub(super) fn update_lock<R: VersionRegistry>(
     Ok(events)
 }
 
-/// Resolve a single spec into the lock if missing, then populate version/specifier fields.
-///
-/// Returns `Ok(())` on success or when no population was needed.
-/// Returns `Err(ResolutionError)` if resolution fails.
-fn populate_lock_entry<R: VersionRegistry>(
-    lock: &mut Lock,
-    resolver: &ActionResolver<'_, R>,
-    spec: &ActionSpec,
-    workflow_shas: &HashMap<ActionSpec, CommitSha>,
-    sha_index: &mut ShaIndex,
-) -> Result<(), ResolutionError> {
-    let needs_population = !lock.is_complete(spec);
-
-    if !needs_population {
-        return Ok(());
-    }
-
-    if !lock.has(spec) {
-        let result = if let Some(sha) = workflow_shas.get(spec) {
-            resolver
-                .resolve_from_sha(&spec.id, sha, sha_index)
-                .or_else(|_| resolver.resolve(spec))
-        } else {
-            resolver.resolve(spec)
-        };
-
-        match result {
-            Ok(action) => {
-                lock.set(spec, action.version, action.commit);
-            }
-            Err(e) => {
-                return Err(e);
-            }
-        }
-    }
-
-    Ok(())
-}
-
 #[cfg(test)]
 #[expect(
     clippy::unwrap_used,
@@ -122,7 +138,7 @@ fn populate_lock_entry<R: VersionRegistry>(
 mod tests {
     use super::*;
     use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Version};
-    use crate::domain::action::resolved::Commit;
+    use crate::domain::action::resolved::{Commit, Provenance};
     use crate::domain::action::spec::Spec as ActionSpec;
     use crate::domain::action::specifier::Specifier;
     use crate::domain::action::tag_selection::ShaIndex;
@@ -133,43 +149,46 @@ mod tests {
     use crate::domain::resolution::{
         ActionResolver, Error as ResolutionError, ShaDescription, VersionRegistry,
     };
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     // ---------------------------------------------------------------------------
     // Registry helpers
     // ---------------------------------------------------------------------------
 
-    /// Registry where `actions/checkout` fails with `AuthRequired` but all other actions resolve.
-    #[derive(Clone)]
-    struct MixedRegistry;
-    impl VersionRegistry for MixedRegistry {
+    /// Registry whose lookups fail with `error`, except the SHA of `resolves`.
+    struct FailingRegistry {
+        error: ResolutionError,
+        resolves: &'static str,
+    }
+    impl VersionRegistry for FailingRegistry {
         fn lookup_sha(&self, id: &ActionId, _version: &Version) -> Result<Commit, ResolutionError> {
-            if id.as_str() == "actions/checkout" {
-                Err(ResolutionError::AuthRequired)
-            } else {
-                Ok(Commit {
-                    sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
-                    repository: id.base_repo(),
-                    ref_type: Some(RefType::Tag),
-                    date: CommitDate::from("2026-01-01T00:00:00Z"),
-                })
+            if id.as_str() != self.resolves {
+                return Err(self.error.clone());
             }
+            Ok(Commit {
+                sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                repository: id.base_repo(),
+                ref_type: Some(RefType::Tag),
+                date: CommitDate::from("2026-01-01T00:00:00Z"),
+                provenance: Provenance::default(),
+            })
         }
         fn tags_for_sha(
             &self,
             _id: &ActionId,
             _sha: &CommitSha,
         ) -> Result<Vec<Version>, ResolutionError> {
-            Err(ResolutionError::AuthRequired)
+            Err(self.error.clone())
         }
         fn all_tags(&self, _id: &ActionId) -> Result<Vec<Version>, ResolutionError> {
-            Err(ResolutionError::AuthRequired)
+            Err(self.error.clone())
         }
         fn describe_sha(
             &self,
             _id: &ActionId,
             _sha: &CommitSha,
         ) -> Result<ShaDescription, ResolutionError> {
-            Err(ResolutionError::AuthRequired)
+            Err(self.error.clone())
         }
     }
 
@@ -281,6 +300,46 @@ mod tests {
         );
     }
 
+    /// `latest` specs are re-resolved even when the lock already has an entry.
+    #[test]
+    fn latest_spec_is_re_resolved_when_already_locked() {
+        let mut manifest = make_manifest_with("actions/checkout", "latest");
+        let key = ActionSpec::new(ActionId::from("actions/checkout"), Specifier::Latest);
+        let mut lock = Lock::default();
+        lock.set(
+            &key,
+            Version::from("v4.0.0"),
+            Commit {
+                sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                repository: ActionId::from("actions/checkout").base_repo(),
+                ref_type: Some(RefType::Tag),
+                date: CommitDate::from("2026-01-01T00:00:00Z"),
+                provenance: Provenance::default(),
+            },
+        );
+
+        let registry = FakeRegistry::new()
+            .with_all_tags("actions/checkout", vec!["v4.0.0", "v5.1.0"])
+            .with_fixed_sha("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+        let resolver = ActionResolver::new(&registry);
+        let mut sha_index = ShaIndex::new();
+        update_lock(
+            &mut lock,
+            &mut manifest,
+            &resolver,
+            &HashMap::new(),
+            &mut sha_index,
+        )
+        .unwrap();
+
+        let entry = lock.get(&key).expect("lock entry must exist");
+        assert_eq!(entry.version.as_str(), "v5.1.0");
+        assert_eq!(
+            entry.commit.sha.as_str(),
+            "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
+        );
+    }
+
     // ---------------------------------------------------------------------------
     // Recoverable errors
     // ---------------------------------------------------------------------------
@@ -297,7 +356,11 @@ mod tests {
         let mut lock = Lock::default();
         let workflow_shas = HashMap::new();
 
-        let resolver = ActionResolver::new(&MixedRegistry);
+        let registry = FailingRegistry {
+            error: ResolutionError::AuthRequired,
+            resolves: "actions/setup-node",
+        };
+        let resolver = ActionResolver::new(&registry);
         let mut sha_index = ShaIndex::new();
         // Should not error — checkout is recoverable (AuthRequired), setup-node succeeds
         update_lock(
@@ -325,4 +388,162 @@ mod tests {
             "checkout must be skipped (AuthRequired)"
         );
     }
+
+    /// Registry that records how many lookups run at once.
+    #[derive(Default)]
+    struct SlowRegistry {
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+    impl VersionRegistry for SlowRegistry {
+        fn lookup_sha(&self, id: &ActionId, _version: &Version) -> Result<Commit, ResolutionError> {
+            let now = self
+                .in_flight
+                .fetch_add(1, Ordering::SeqCst)
+                .saturating_add(1);
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(Commit {
+                sha: CommitSha::from(format!("{:a>40}", id.as_str().len())),
+                repository: id.base_repo(),
+                ref_type: Some(RefType::Tag),
+                date: CommitDate::from("2026-01-01T00:00:00Z"),
+                provenance: Provenance::default(),
+            })
+        }
+        fn tags_for_sha(
+            &self,
+            _id: &ActionId,
+            _sha: &CommitSha,
+        ) -> Result<Vec<Version>, ResolutionError> {
+            Ok(Vec::new())
+        }
+        fn all_tags(&self, _id: &ActionId) -> Result<Vec<Version>, ResolutionError> {
+            Ok(Vec::new())
+        }
+        fn describe_sha(
+            &self,
+            _id: &ActionId,
+            _sha: &CommitSha,
+        ) -> Result<ShaDescription, ResolutionError> {
+            Err(ResolutionError::AuthRequired)
+        }
+        fn concurrency(&self) -> usize {
+            4
+        }
+    }
+
+    #[test]
+    fn update_lock_resolves_specs_concurrently() {
+        let mut manifest = Manifest::default();
+        let names = ["a/b", "a/bc", "a/bcd", "a/bcde", "a/bcdef", "a/bcdefg"];
+        for name in names {
+            manifest.set(ActionId::from(name), Specifier::from_v1("v1"));
+        }
+        let mut lock = Lock::default();
+        let registry = SlowRegistry::default();
+        let resolver = ActionResolver::new(&registry);
+        update_lock(
+            &mut lock,
+            &mut manifest,
+            &resolver,
+            &HashMap::new(),
+            &mut ShaIndex::new(),
+        )
+        .unwrap();
+
+        let peak = registry.peak.load(Ordering::SeqCst);
+        assert!(peak > 1 && peak <= 4, "peak concurrency was {peak}");
+        for name in names {
+            let key = ActionSpec::new(ActionId::from(name), Specifier::from_v1("v1"));
+            let entry = lock.get(&key).expect("lock entry must exist");
+            assert_eq!(entry.commit.sha.as_str(), format!("{:a>40}", name.len()));
+        }
+    }
+
+    #[test]
+    fn update_lock_stops_at_the_request_limit_naming_unresolved_specs() {
+        let mut manifest = make_manifest_with("actions/checkout", "v4");
+        manifest.set(
+            ActionId::from("actions/setup-node"),
+            Specifier::from_v1("v3"),
+        );
+        let mut lock = Lock::default();
+        let registry = FailingRegistry {
+            error: ResolutionError::RequestLimit { limit: 10 },
+            resolves: "actions/checkout",
+        };
+        let resolver = ActionResolver::new(&registry);
+
+        let err = update_lock(
+            &mut lock,
+            &mut manifest,
+            &resolver,
+            &HashMap::new(),
+            &mut ShaIndex::new(),
+        )
+        .unwrap_err();
+
+        let message = err.to_string();
+        assert!(matches!(
+            err,
+            TidyError::RequestLimit {
+                limit: 10,
+                resolved: 1,
+                count: 1,
+                ..
+            }
+        ));
+        assert!(message.contains("actions/setup-node"), "{message}");
+        assert!(!message.contains("actions/checkout"), "{message}");
+    }
+
+    #[test]
+    fn update_lock_offline_keeps_locked_specs_and_names_missing_ones() {
+        let mut manifest = make_manifest_with("actions/checkout", "latest");
+        manifest.set(
+            ActionId::from("actions/setup-node"),
+            Specifier::from_v1("v3"),
+        );
+        let checkout = ActionSpec::new(
+            ActionId::from("actions/checkout"),
+            Specifier::from_v1("latest"),
+        );
+        let mut lock = Lock::default();
+        lock.set(
+            &checkout,
+            Version::from("v4.2.0"),
+            Commit {
+                sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                repository: ActionId::from("actions/checkout").base_repo(),
+                ref_type: Some(RefType::Tag),
+                date: CommitDate::from("2026-01-01T00:00:00Z"),
+                provenance: Provenance::default(),
+            },
+        );
+        let registry = FailingRegistry {
+            error: ResolutionError::Offline,
+            resolves: "",
+        };
+        let resolver = ActionResolver::new(&registry);
+
+        let err = update_lock(
+            &mut lock,
+            &mut manifest,
+            &resolver,
+            &HashMap::new(),
+            &mut ShaIndex::new(),
+        )
+        .unwrap_err();
+
+        let message = err.to_string();
+        assert!(
+            matches!(err, TidyError::Offline { count: 1, .. }),
+            "{message}"
+        );
+        assert!(message.contains("actions/setup-node"), "{message}");
+        assert!(!message.contains("actions/checkout"), "{message}");
+        assert!(lock.has(&checkout));
+    }
 }
@@ -2,6 +2,7 @@ use crate::domain::action::identity::{ActionId, CommitSha, Version};
 use crate::domain::action::spec::Spec as ActionSpec;
 use crate::domain::action::specifier::Specifier;
 use crate::domain::action::tag_selection::{ShaIndex, select_most_specific_tag};
+use crate::domain::diff::ManifestDiff;
 use crate::domain::event::Event as SyncEvent;
 use crate::domain::manifest::Manifest;
 use crate::domain::resolution::{ActionResolver, VersionRegistry};
@@ -109,6 +110,25 @@ pub(super) fn upgrade_sha_versions_to_tags<R: VersionRegistry>(
     events
 }
 
+/// The `[groups]` members of `manifest` that are not among its actions once `diff` is
+/// applied, by group: misspelled, or no longer used by any workflow.
+pub(super) fn unknown_group_members(
+    manifest: &Manifest,
+    diff: &ManifestDiff,
+) -> Vec<(String, ActionId)> {
+    let known = |id: &ActionId| {
+        (manifest.has(id) && !diff.removed.contains(id))
+            || diff.added.iter().any(|(added, _)| added == id)
+    };
+    manifest
+        .groups()
+        .iter()
+        .flat_map(|(name, members)| members.iter().map(move |id| (name, id)))
+        .filter(|(_, id)| !known(id))
+        .map(|(name, id)| (name.clone(), id.clone()))
+        .collect()
+}
+
 /// Select the highest version from a non-empty slice of versions.
 pub(super) fn select_version(versions: &[Version]) -> Version {
     #[expect(
@@ -131,10 +151,11 @@ pub(super) fn select_dominant_version(
 
 #[cfg(test)]
 mod tests {
-    use super::{Version, select_version, upgrade_sha_versions_to_tags};
+    use super::{Version, select_version, unknown_group_members, upgrade_sha_versions_to_tags};
     use crate::domain::action::identity::ActionId;
     use crate::domain::action::specifier::Specifier;
     use crate::domain::action::tag_selection::ShaIndex;
+    use crate::domain::diff::ManifestDiff;
     use crate::domain::manifest::Manifest;
     use crate::domain::resolution::ActionResolver;
     use crate::domain::resolution::testutil::{AuthRequiredRegistry, FakeRegistry};
@@ -155,6 +176,38 @@ mod tests {
         assert_eq!(select_version(&versions), Version::from("v4"));
     }
 
+    #[test]
+    fn group_members_outside_the_tidied_manifest_are_unknown() {
+        let checkout = ActionId::from("actions/checkout");
+        let cache = ActionId::from("actions/cache");
+        let node = ActionId::from("actions/setup-node");
+        let mut manifest = Manifest::default();
+        manifest.set(checkout.clone(), Specifier::parse("^4"));
+        manifest.set(cache.clone(), Specifier::parse("^4"));
+        manifest.set_group(
+            "ci".to_owned(),
+            vec![
+                checkout,
+                cache.clone(),
+                node.clone(),
+                ActionId::from("actions/chekout"),
+            ],
+        );
+        let diff = ManifestDiff {
+            removed: vec![cache.clone()],
+            added: vec![(node, Specifier::parse("^4"))],
+            ..ManifestDiff::default()
+        };
+
+        assert_eq!(
+            unknown_group_members(&manifest, &diff),
+            [
+                ("ci".to_owned(), cache),
+                ("ci".to_owned(), ActionId::from("actions/chekout")),
+            ]
+        );
+    }
+
     // ---------------------------------------------------------------------------
     // SHA-to-tag upgrade tests (migrated from tidy/tests.rs)
     // ---------------------------------------------------------------------------
@@ -2,6 +2,8 @@
 
 /// Tidy command: error types, struct, and `Command` implementation.
 mod command;
+/// Container images of steps, jobs, and services: locking their digests and pinning them.
+mod images;
 /// Lock file synchronization: resolving and updating lock entries.
 mod lock_sync;
 /// Manifest synchronization: adding, removing, and upgrading action specs.
@@ -10,4 +12,6 @@ mod manifest_sync;
 mod patches;
 pub mod report;
 
-pub use command::{Error, Plan, RunError, Tidy, apply_workflow_patches, plan};
+pub use command::{
+    DEFAULT_COMMIT_MESSAGE, Error, Plan, RunError, Tidy, apply_workflow_patches, plan,
+};
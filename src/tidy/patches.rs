@@ -1,8 +1,7 @@
 use super::Error as TidyError;
-use crate::domain::action::identity::ActionId;
 use crate::domain::action::resolved::ResolvedAction;
 use crate::domain::action::spec::Spec;
-use crate::domain::diff::WorkflowPatch;
+use crate::domain::diff::{StepPin, WorkflowPatch};
 use crate::domain::lock::Lock;
 use crate::domain::manifest::Manifest;
 use crate::domain::workflow::Scanner as WorkflowScanner;
@@ -44,7 +43,8 @@ pub(super) fn compute_workflow_patches<P: WorkflowScanner>(
         if !pins.is_empty() {
             patches.push(WorkflowPatch {
                 path: workflow_path.clone(),
-                pins,
+                pins: Vec::new(),
+                steps: pins,
             });
         }
     }
@@ -52,16 +52,25 @@ pub(super) fn compute_workflow_patches<P: WorkflowScanner>(
     Ok(patches)
 }
 
-/// Build the per-file pins: resolves each step's version via override hierarchy.
-fn build_pins(manifest: &Manifest, lock: &Lock, steps: &[&LocatedAction]) -> Vec<ResolvedAction> {
-    let mut map = HashMap::<ActionId, ResolvedAction>::new();
-    for action in steps {
+/// Build the per-step pins: resolves each step's version via override hierarchy, so two
+/// steps of the same action can be pinned to different versions.
+/// Steps using a held action are left as they are.
+fn build_pins(manifest: &Manifest, lock: &Lock, steps: &[&LocatedAction]) -> Vec<StepPin> {
+    let mut pins = Vec::new();
+    for action in steps
+        .iter()
+        .filter(|action| !manifest.is_held(&action.action.id))
+    {
+        let (Some(job), Some(step)) = (&action.location.job, action.location.step) else {
+            continue;
+        };
         if let Some(version) = manifest.resolve_version(&action.action.id, &action.location) {
             let key = Spec::new(action.action.id.clone(), version.clone());
             if let Some(entry) = lock.get(&key) {
-                map.insert(
-                    action.action.id.clone(),
-                    ResolvedAction {
+                pins.push(StepPin {
+                    job: job.clone(),
+                    step,
+                    pin: ResolvedAction {
                         id: action.action.id.clone(),
                         sha: entry.commit.sha.clone(),
                         version: if version.is_sha() {
@@ -70,11 +79,11 @@ fn build_pins(manifest: &Manifest, lock: &Lock, steps: &[&LocatedAction]) -> Vec
                             Some(entry.version.clone())
                         },
                     },
-                );
+                });
             }
         }
     }
-    map.into_values().collect()
+    pins
 }
 
 #[cfg(test)]
@@ -85,7 +94,7 @@ fn build_pins(manifest: &Manifest, lock: &Lock, steps: &[&LocatedAction]) -> Vec
 mod tests {
     use super::{Lock, Manifest, build_pins};
     use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
-    use crate::domain::action::resolved::Commit;
+    use crate::domain::action::resolved::{Commit, Provenance};
     use crate::domain::action::spec::Spec;
     use crate::domain::action::specifier::Specifier;
     use crate::domain::action::uses_ref::RefType;
@@ -114,6 +123,7 @@ mod tests {
                 repository: Repository::from("actions/checkout"),
                 ref_type: Some(RefType::Tag),
                 date: CommitDate::from("2026-01-01T00:00:00Z"),
+                provenance: Provenance::default(),
             },
         );
 
@@ -128,6 +138,7 @@ mod tests {
                 workflow: WorkflowPath::new(".github/workflows/ci.yml"),
                 job: Some(JobId::from("build")),
                 step: Some(StepIndex::from(0_u16)),
+                step_name: None,
                 line: None,
             },
         };
@@ -136,7 +147,8 @@ mod tests {
 
         let pin = pins
             .iter()
-            .find(|p| p.id == ActionId::from("actions/checkout"))
+            .find(|p| p.pin.id == ActionId::from("actions/checkout"))
+            .map(|p| &p.pin)
             .unwrap();
         // Must be just the SHA, no version annotation
         assert_eq!(
@@ -1,10 +1,31 @@
 use crate::command::CommandReport;
 use crate::domain::action::identity::ActionId;
 use crate::domain::action::specifier::Specifier;
+use crate::output::events::Event;
 use crate::output::lines::Line as OutputLine;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// A specifier newly locked to a version and commit SHA.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Resolved {
+    pub action: String,
+    pub specifier: String,
+    pub version: String,
+    pub sha: String,
+}
+
+/// A repository the API redirected to a new name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Renamed {
+    pub from: String,
+    pub to: String,
+    /// Whether `--fix` rewrote the workflows to the new name.
+    pub fixed: bool,
+}
 
 /// Report from the tidy command.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize)]
 pub struct Report {
     /// Actions removed.
     pub removed: Vec<ActionId>,
@@ -12,23 +33,114 @@ pub struct Report {
     pub added: Vec<(ActionId, Specifier)>,
     /// Actions upgraded (sha→tag or version bump): (action, from, to).
     pub upgraded: Vec<(ActionId, String, Specifier)>,
-    /// Number of workflow files updated.
-    pub workflows_updated: usize,
+    /// Lock entries that were resolved.
+    pub resolved: Vec<Resolved>,
+    /// Container images of `docker://` steps pinned to a digest, by tag.
+    pub images: Vec<Resolved>,
+    /// Actions that were left alone: (action, reason).
+    pub skipped: Vec<(String, String)>,
+    /// The workflow files that were updated.
+    pub workflows: Vec<PathBuf>,
+    /// Repositories that were renamed or transferred.
+    pub renamed: Vec<Renamed>,
+    /// `[groups]` members that are not actions of gx.toml: (group, action).
+    pub unknown_group_members: Vec<(String, ActionId)>,
+    /// With `--check`, whether tidy would change files it left as they are.
+    pub out_of_date: bool,
 }
 
 impl CommandReport for Report {
+    fn events(&self) -> Vec<Event> {
+        let mut events: Vec<Event> = self
+            .removed
+            .iter()
+            .map(|action| Event::Removed {
+                action: action.to_string(),
+            })
+            .collect();
+        events.extend(self.added.iter().map(|(action, specifier)| Event::Added {
+            action: action.to_string(),
+            specifier: specifier.to_string(),
+        }));
+        events.extend(
+            self.upgraded
+                .iter()
+                .map(|(action, from, to)| Event::Corrected {
+                    action: action.to_string(),
+                    from: from.clone(),
+                    to: to.to_string(),
+                }),
+        );
+        events.extend(
+            self.resolved
+                .iter()
+                .chain(&self.images)
+                .map(|resolved| Event::Resolved {
+                    action: resolved.action.clone(),
+                    specifier: resolved.specifier.clone(),
+                    version: resolved.version.clone(),
+                    sha: resolved.sha.clone(),
+                }),
+        );
+        events.extend(self.skipped.iter().map(|(action, reason)| Event::Skipped {
+            action: action.clone(),
+            reason: reason.clone(),
+        }));
+        events.extend(self.renamed.iter().map(|renamed| Event::Renamed {
+            from: renamed.from.clone(),
+            to: renamed.to.clone(),
+            fixed: renamed.fixed,
+        }));
+        events.extend(
+            self.workflows
+                .iter()
+                .map(|path| Event::FileUpdated { path: path.clone() }),
+        );
+        events
+    }
+
     fn render(&self) -> Vec<OutputLine> {
-        let has_changes =
-            !self.removed.is_empty() || !self.added.is_empty() || !self.upgraded.is_empty();
+        let has_changes = !self.removed.is_empty()
+            || !self.added.is_empty()
+            || !self.upgraded.is_empty()
+            || !self.images.is_empty()
+            || self.out_of_date;
+
+        let mut lines: Vec<OutputLine> = self
+            .renamed
+            .iter()
+            .map(|renamed| OutputLine::Warning {
+                message: if renamed.fixed {
+                    format!(
+                        "{} was renamed to {}; workflows now use the new name",
+                        renamed.from, renamed.to
+                    )
+                } else {
+                    format!(
+                        "{} was renamed to {}; run `gx tidy --fix` to use the new name",
+                        renamed.from, renamed.to
+                    )
+                },
+            })
+            .collect();
+        lines.extend(self.unknown_group_members.iter().map(|(group, action)| {
+            OutputLine::Warning {
+                message: format!("group {group} lists {action}, which is not an action of gx.toml"),
+            }
+        }));
+        if self.out_of_date {
+            lines.push(OutputLine::Warning {
+                message: "gx.toml, gx.lock, or workflows are out of date; run `gx tidy`".to_owned(),
+            });
+        }
 
         if !has_changes {
-            return vec![OutputLine::Summary {
+            lines.push(OutputLine::Summary {
                 text: "Up to date".to_owned(),
-            }];
+            });
+            return lines;
         }
 
-        let mut lines = Vec::new();
-
         for action in &self.removed {
             lines.push(OutputLine::Removed {
                 action: action.to_string(),
@@ -50,6 +162,13 @@ impl CommandReport for Report {
             });
         }
 
+        for image in &self.images {
+            lines.push(OutputLine::Changed {
+                action: format!("{}:{}", image.action, image.version),
+                detail: format!("pinned to {}", image.sha),
+            });
+        }
+
         lines.push(OutputLine::Blank);
 
         let mut parts = Vec::new();
@@ -62,7 +181,10 @@ impl CommandReport for Report {
         if !self.upgraded.is_empty() {
             parts.push(format!("{} upgraded", self.upgraded.len()));
         }
-        let wf = self.workflows_updated;
+        if !self.images.is_empty() {
+            parts.push(format!("{} image(s) pinned", self.images.len()));
+        }
+        let wf = self.workflows.len();
         parts.push(format!("{} workflow{}", wf, if wf == 1 { "" } else { "s" }));
 
         lines.push(OutputLine::Summary {
@@ -71,6 +193,10 @@ impl CommandReport for Report {
 
         lines
     }
+
+    fn exit_code(&self) -> i32 {
+        i32::from(self.out_of_date)
+    }
 }
 
 #[cfg(test)]
@@ -102,7 +228,11 @@ mod tests {
                 "sha".to_owned(),
                 Specifier::from_v1("v6.0.2"),
             )],
-            workflows_updated: 2,
+            workflows: vec![
+                ".github/workflows/ci.yml".into(),
+                ".github/workflows/release.yml".into(),
+            ],
+            ..Default::default()
         };
         let lines = report.render();
 
@@ -117,4 +247,22 @@ mod tests {
             text: "1 removed · 2 added · 1 upgraded · 2 workflows".to_owned(),
         }));
     }
+
+    #[test]
+    fn render_tidy_check_warns_and_fails_when_out_of_date() {
+        let report = Report {
+            out_of_date: true,
+            ..Default::default()
+        };
+        let lines = report.render();
+
+        assert!(
+            matches!(&lines[0], OutputLine::Warning { message } if message.contains("gx tidy"))
+        );
+        assert!(!lines.contains(&OutputLine::Summary {
+            text: "Up to date".to_owned(),
+        }));
+        assert_eq!(report.exit_code(), 1);
+        assert_eq!(Report::default().exit_code(), 0);
+    }
 }
@@ -0,0 +1,208 @@
+use super::report::{Dependency, Pinning, Tree as TreeReport};
+use crate::command::Command;
+use crate::config::Config;
+use crate::domain::action::identity::{ActionId, CommitSha};
+use crate::domain::workflow_parsed::ActionMetadata;
+use crate::infra::github::{Error as GithubError, Registry as GithubRegistry};
+use std::collections::HashSet;
+use std::path::Path;
+use thiserror::Error;
+
+/// How many levels of composite actions below a locked action are followed.
+const MAX_DEPTH: usize = 4;
+
+/// File names GitHub reads action metadata from, in order.
+const METADATA_FILES: [&str; 2] = ["action.yml", "action.yaml"];
+
+/// Errors that can occur during `gx tree`.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("gx tree reads action metadata from the GitHub API, which --offline disables")]
+    Offline,
+    #[error(transparent)]
+    Registry(#[from] GithubError),
+}
+
+/// Reads the metadata of an action at a commit; `Ok(None)` when it has none, as for a
+/// reusable workflow.
+type Fetch<'registry> =
+    dyn Fn(&ActionId, &CommitSha) -> Result<Option<ActionMetadata>, String> + 'registry;
+
+/// `gx tree`: read the `action.yml` of each locked action at its locked commit and list
+/// the actions its composite steps use, following those pinned to a commit.
+pub struct Tree;
+
+impl Command for Tree {
+    type Report = TreeReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        _repo_root: &Path,
+        config: Config,
+        on_progress: &mut dyn FnMut(&str),
+    ) -> Result<TreeReport, Error> {
+        if config.settings.offline {
+            return Err(Error::Offline);
+        }
+        let registry = GithubRegistry::from_settings(&config.settings)?;
+        let fetch = |id: &ActionId, sha: &CommitSha| metadata(&registry, id, sha);
+
+        let mut roots: Vec<(String, ActionId, CommitSha)> = config
+            .lock
+            .entries()
+            .map(|(spec, entry)| {
+                (
+                    format!("{}@{}", spec.id, entry.version),
+                    spec.id.clone(),
+                    entry.commit.sha.clone(),
+                )
+            })
+            .collect();
+        roots.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut report = TreeReport::default();
+        let mut visited = HashSet::new();
+        for (root, id, sha) in roots {
+            on_progress(&format!("Reading {root}..."));
+            report.checked = report.checked.saturating_add(1);
+            let before = report.dependencies.len();
+            walk(&fetch, &root, &id, &sha, 0, &mut visited, &mut report);
+            if report.dependencies.len() > before {
+                report.composite = report.composite.saturating_add(1);
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Add the dependencies of the action `id` at `sha` to `report`, and theirs in turn for
+/// those pinned to a commit, up to [`MAX_DEPTH`]. Each action and commit is read once.
+fn walk(
+    fetch: &Fetch<'_>,
+    root: &str,
+    id: &ActionId,
+    sha: &CommitSha,
+    depth: usize,
+    visited: &mut HashSet<(ActionId, CommitSha)>,
+    report: &mut TreeReport,
+) {
+    if depth > MAX_DEPTH || !visited.insert((id.clone(), sha.clone())) {
+        return;
+    }
+    let metadata = match fetch(id, sha) {
+        Ok(Some(metadata)) => metadata,
+        Ok(None) => return,
+        Err(reason) => {
+            report.failed.push((format!("{id}@{sha}"), reason));
+            return;
+        }
+    };
+    for (uses, comment) in metadata.uses() {
+        let pinning = Pinning::of(uses);
+        report.dependencies.push(Dependency {
+            root: root.to_owned(),
+            uses: uses.to_owned(),
+            comment: comment.map(str::to_owned),
+            depth,
+            pinning,
+        });
+        if pinning == Pinning::Pinned
+            && let Some((name, reference)) = uses.split_once('@')
+            && !uses.starts_with("docker://")
+        {
+            let next = depth.saturating_add(1);
+            let (action, commit) = (ActionId::from(name), CommitSha::from(reference));
+            walk(fetch, root, &action, &commit, next, visited, report);
+        }
+    }
+}
+
+/// The metadata of the action `id` at `sha`, from `action.yml` or `action.yaml` in its
+/// directory of the repository.
+fn metadata(
+    registry: &GithubRegistry,
+    id: &ActionId,
+    sha: &CommitSha,
+) -> Result<Option<ActionMetadata>, String> {
+    let repo = id.base_repo();
+    let dir = id
+        .as_str()
+        .strip_prefix(repo.as_str())
+        .unwrap_or_default()
+        .trim_matches('/');
+    for file in METADATA_FILES {
+        let path = if dir.is_empty() {
+            file.to_owned()
+        } else {
+            format!("{dir}/{file}")
+        };
+        if let Some(content) = registry
+            .get_file(&repo, sha, &path)
+            .map_err(|e| e.to_string())?
+        {
+            return ActionMetadata::from_yaml(&content)
+                .map(Some)
+                .map_err(|e| format!("invalid {path}: {e}"));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{ActionId, ActionMetadata, CommitSha, TreeReport, walk};
+    use crate::tree::report::Pinning;
+    use std::collections::HashSet;
+
+    const INNER_SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+
+    #[test]
+    fn walk_follows_pinned_composite_dependencies() {
+        let fetch = |id: &ActionId, _sha: &CommitSha| {
+            let content = match id.as_str() {
+                "org/setup" => format!(
+                    "runs:\n  using: composite\n  steps:\n    - uses: org/inner@{INNER_SHA} # v2\n    - uses: actions/cache@v4\n"
+                ),
+                "org/inner" => {
+                    "runs:\n  using: composite\n  steps:\n    - uses: ./tool\n".to_owned()
+                }
+                _ => return Ok(None),
+            };
+            Ok(Some(ActionMetadata::from_yaml(&content).unwrap()))
+        };
+        let mut report = TreeReport::default();
+
+        walk(
+            &fetch,
+            "org/setup@v1",
+            &ActionId::from("org/setup"),
+            &CommitSha::from("f".repeat(40)),
+            0,
+            &mut HashSet::new(),
+            &mut report,
+        );
+
+        let found: Vec<(&str, usize, Pinning)> = report
+            .dependencies
+            .iter()
+            .map(|dependency| {
+                (
+                    dependency.uses.as_str(),
+                    dependency.depth,
+                    dependency.pinning,
+                )
+            })
+            .collect();
+        let inner = format!("org/inner@{INNER_SHA}");
+        assert_eq!(
+            found,
+            vec![
+                (inner.as_str(), 0, Pinning::Pinned),
+                ("./tool", 1, Pinning::Local),
+                ("actions/cache@v4", 0, Pinning::Unpinned),
+            ]
+        );
+    }
+}
@@ -0,0 +1,7 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// Tree command: error types, struct, and `Command` implementation.
+mod command;
+pub mod report;
+
+pub use command::{Error, Tree};
@@ -0,0 +1,197 @@
+use crate::command::CommandReport;
+use crate::output::lines::Line as OutputLine;
+
+/// How a nested `uses:` reference is pinned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pinning {
+    /// A commit SHA, or an image digest for `docker://`.
+    Pinned,
+    /// A local action of the same repository, fixed by the commit of its parent.
+    Local,
+    /// A tag or branch, which can be moved to other code.
+    Unpinned,
+}
+
+impl Pinning {
+    /// How `uses` (without its comment) is pinned.
+    #[must_use]
+    pub fn of(uses: &str) -> Self {
+        if uses.starts_with("./") {
+            return Self::Local;
+        }
+        let pinned = match uses.strip_prefix("docker://") {
+            Some(image) => image.contains("@sha256:"),
+            None => uses.rsplit_once('@').is_some_and(|(_, reference)| {
+                reference.len() == 40 && reference.chars().all(|c| c.is_ascii_hexdigit())
+            }),
+        };
+        if pinned { Self::Pinned } else { Self::Unpinned }
+    }
+
+    /// The label shown in the PINNED column.
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Pinned => "yes",
+            Self::Local => "local",
+            Self::Unpinned => "no",
+        }
+    }
+}
+
+/// A `uses:` of a composite action, reached from a locked action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    /// The locked action it was reached from, as `action@version`.
+    pub root: String,
+    /// The reference as written, without its comment.
+    pub uses: String,
+    /// The version comment after the reference, if any.
+    pub comment: Option<String>,
+    /// `0` for a step of the locked action, `1` for a step of one of its dependencies, …
+    pub depth: usize,
+    /// How the reference is pinned.
+    pub pinning: Pinning,
+}
+
+/// Report from `gx tree`.
+#[derive(Debug, Default)]
+pub struct Tree {
+    /// Locked actions whose metadata was read.
+    pub checked: usize,
+    /// Locked actions that are composite actions using other actions.
+    pub composite: usize,
+    /// Nested dependencies, each right after the one whose step uses it.
+    pub dependencies: Vec<Dependency>,
+    /// Actions whose metadata could not be read, with the reason.
+    pub failed: Vec<(String, String)>,
+}
+
+impl CommandReport for Tree {
+    fn render(&self) -> Vec<OutputLine> {
+        let mut lines = Vec::new();
+        if !self.dependencies.is_empty() {
+            let rows = self
+                .dependencies
+                .iter()
+                .enumerate()
+                .map(|(index, dependency)| {
+                    let first = index
+                        .checked_sub(1)
+                        .and_then(|previous| self.dependencies.get(previous))
+                        .is_none_or(|previous| previous.root != dependency.root);
+                    let comment = dependency
+                        .comment
+                        .as_ref()
+                        .map(|comment| format!(" # {comment}"))
+                        .unwrap_or_default();
+                    vec![
+                        if first {
+                            dependency.root.clone()
+                        } else {
+                            String::new()
+                        },
+                        format!(
+                            "{}{}{comment}",
+                            "  ".repeat(dependency.depth),
+                            dependency.uses
+                        ),
+                        dependency.pinning.label().to_owned(),
+                    ]
+                })
+                .collect();
+            lines.push(OutputLine::Table {
+                headers: vec!["ACTION".to_owned(), "USES".to_owned(), "PINNED".to_owned()],
+                rows,
+            });
+        }
+        lines.extend(
+            self.failed
+                .iter()
+                .map(|(action, reason)| OutputLine::Skipped {
+                    action: action.clone(),
+                    reason: reason.clone(),
+                }),
+        );
+        let unpinned = self
+            .dependencies
+            .iter()
+            .filter(|dependency| dependency.pinning == Pinning::Unpinned)
+            .count();
+        lines.push(OutputLine::Summary {
+            text: format!(
+                "{} locked action(s), {} composite, {} nested dependencies, {unpinned} not pinned",
+                self.checked,
+                self.composite,
+                self.dependencies.len()
+            ),
+        });
+        lines
+    }
+
+    fn exit_code(&self) -> i32 {
+        i32::from(!self.failed.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandReport as _, Dependency, OutputLine, Pinning, Tree};
+
+    #[test]
+    fn pinning_of_tells_shas_and_digests_from_movable_refs() {
+        assert_eq!(
+            Pinning::of("actions/cache@0123456789abcdef0123456789abcdef01234567"),
+            Pinning::Pinned
+        );
+        assert_eq!(Pinning::of("actions/cache@v4"), Pinning::Unpinned);
+        assert_eq!(Pinning::of("./helper"), Pinning::Local);
+        assert_eq!(Pinning::of("docker://alpine@sha256:abc"), Pinning::Pinned);
+        assert_eq!(Pinning::of("docker://alpine:3.19"), Pinning::Unpinned);
+    }
+
+    #[test]
+    fn render_groups_dependencies_under_their_locked_action() {
+        let dependency = |uses: &str, depth, pinning| Dependency {
+            root: "org/setup@v1".to_owned(),
+            uses: uses.to_owned(),
+            comment: None,
+            depth,
+            pinning,
+        };
+        let report = Tree {
+            checked: 3,
+            composite: 1,
+            dependencies: vec![
+                dependency("org/inner@0123", 0, Pinning::Pinned),
+                dependency("actions/cache@v4", 1, Pinning::Unpinned),
+            ],
+            failed: Vec::new(),
+        };
+
+        assert_eq!(
+            report.render(),
+            vec![
+                OutputLine::Table {
+                    headers: vec!["ACTION".to_owned(), "USES".to_owned(), "PINNED".to_owned()],
+                    rows: vec![
+                        vec![
+                            "org/setup@v1".to_owned(),
+                            "org/inner@0123".to_owned(),
+                            "yes".to_owned()
+                        ],
+                        vec![
+                            String::new(),
+                            "  actions/cache@v4".to_owned(),
+                            "no".to_owned()
+                        ],
+                    ],
+                },
+                OutputLine::Summary {
+                    text: "3 locked action(s), 1 composite, 2 nested dependencies, 1 not pinned"
+                        .to_owned(),
+                },
+            ]
+        );
+        assert_eq!(report.exit_code(), 0);
+    }
+}
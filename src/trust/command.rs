@@ -0,0 +1,154 @@
+use super::report::{Exported, Imported};
+use crate::command::Command;
+use crate::config::Config;
+use crate::domain::trust::Trust;
+use crate::infra::trust::{self, Error as TrustError};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Errors that can occur during `gx trust`.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("nothing to export: gx.lock and the trust database are empty; run `gx tidy` first")]
+    Empty,
+
+    #[error(transparent)]
+    Trust(#[from] TrustError),
+}
+
+/// `gx trust export`: write the approved action, version, and commit triples, those of
+/// the trust database and of gx.lock, for a security team to review and sign.
+#[derive(Debug)]
+pub struct Export {
+    /// Where to write the database; it is printed when unset.
+    pub file: Option<PathBuf>,
+    /// The SSH private key to sign the written file with, as `<file>.sig`.
+    pub key: Option<PathBuf>,
+}
+
+impl Command for Export {
+    type Report = Exported;
+    type Error = Error;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        config: Config,
+        _on_progress: &mut dyn FnMut(&str),
+    ) -> Result<Exported, Error> {
+        let mut database = trust::read(&config.trust_path())?;
+        database.merge(&Trust::from_lock(&config.lock));
+        if database.is_empty() {
+            return Err(Error::Empty);
+        }
+        let Some(file) = &self.file else {
+            return Ok(Exported {
+                text: trust::render(&database)?,
+                approved: database.len(),
+                ..Exported::default()
+            });
+        };
+        let path = repo_root.join(file);
+        trust::write(&path, &database)?;
+        let signature = self
+            .key
+            .as_ref()
+            .map(|key| trust::sign(&path, &repo_root.join(key)))
+            .transpose()?;
+        Ok(Exported {
+            text: String::new(),
+            approved: database.len(),
+            written: Some(path),
+            signature,
+        })
+    }
+}
+
+/// `gx trust import`: add the triples of a reviewed trust database to the repository's
+/// gx-trust.toml, after checking its signature unless told not to.
+#[derive(Debug)]
+pub struct Import {
+    /// The trust database to import.
+    pub file: PathBuf,
+    /// The `allowed_signers` file and the identity in it that must have signed `file`;
+    /// `None` skips the check, as `--insecure-skip-verify` asks.
+    pub signer: Option<(PathBuf, String)>,
+}
+
+impl Command for Import {
+    type Report = Imported;
+    type Error = Error;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        config: Config,
+        on_progress: &mut dyn FnMut(&str),
+    ) -> Result<Imported, Error> {
+        let path = repo_root.join(&self.file);
+        if let Some((allowed_signers, identity)) = &self.signer {
+            on_progress("Verifying signature...");
+            trust::verify(&path, &repo_root.join(allowed_signers), identity)?;
+        }
+        let imported = trust::read(&path)?;
+        let trust_path = config.trust_path();
+        let mut database = trust::read(&trust_path)?;
+        let added = database.merge(&imported);
+        if added > 0 {
+            trust::write(&trust_path, &database)?;
+        }
+        Ok(Imported {
+            added,
+            approved: database.len(),
+            verified: self.signer.is_some(),
+            path: trust_path,
+        })
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{Command as _, Export, Import};
+    use crate::config::Config;
+    use crate::domain::action::identity::{ActionId, CommitSha, Version};
+    use crate::domain::trust::Trust;
+    use crate::infra::trust;
+    use std::fs;
+
+    #[test]
+    fn import_merges_an_exported_database_into_gx_trust() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".github")).unwrap();
+        let mut reviewed = Trust::default();
+        reviewed.approve(
+            &ActionId::from("actions/checkout"),
+            &Version::from("v4.2.2"),
+            &CommitSha::from("aaa111"),
+        );
+        trust::write(&dir.path().join("reviewed.toml"), &reviewed).unwrap();
+        let import = Import {
+            file: "reviewed.toml".into(),
+            signer: None,
+        };
+
+        let first = import
+            .run(dir.path(), Config::load(dir.path()).unwrap(), &mut |_| {})
+            .unwrap();
+        let again = import
+            .run(dir.path(), Config::load(dir.path()).unwrap(), &mut |_| {})
+            .unwrap();
+
+        assert_eq!((first.added, again.added), (1, 0));
+        assert!(!first.verified);
+        let config = Config::load(dir.path()).unwrap();
+        assert_eq!(trust::read(&config.trust_path()).unwrap(), reviewed);
+        let exported = Export {
+            file: None,
+            key: None,
+        }
+        .run(dir.path(), config, &mut |_| {})
+        .unwrap();
+        assert!(exported.text.contains("sha = \"aaa111\""));
+    }
+}
@@ -0,0 +1,71 @@
+use crate::command::CommandReport;
+use crate::output::lines::Line as OutputLine;
+use std::path::PathBuf;
+
+/// Report from `gx trust export`.
+#[derive(Debug, Default)]
+pub struct Exported {
+    /// The database, when it was printed rather than written.
+    pub text: String,
+    /// How many triples it approves.
+    pub approved: usize,
+    /// The file it was written to.
+    pub written: Option<PathBuf>,
+    /// The signature written beside it.
+    pub signature: Option<PathBuf>,
+}
+
+impl CommandReport for Exported {
+    fn render(&self) -> Vec<OutputLine> {
+        let Some(path) = &self.written else {
+            return vec![OutputLine::Text {
+                text: self.text.trim_end().to_owned(),
+            }];
+        };
+        let signed = self
+            .signature
+            .as_ref()
+            .map(|signature| format!(", signed in {}", signature.display()))
+            .unwrap_or_default();
+        vec![OutputLine::Summary {
+            text: format!(
+                "Exported {} approved commit(s) to {}{signed}",
+                self.approved,
+                path.display()
+            ),
+        }]
+    }
+}
+
+/// Report from `gx trust import`.
+#[derive(Debug, Default)]
+pub struct Imported {
+    /// How many triples were not approved before.
+    pub added: usize,
+    /// How many triples the trust database approves now.
+    pub approved: usize,
+    /// Whether the signature of the imported file was checked.
+    pub verified: bool,
+    /// The trust database of the repository.
+    pub path: PathBuf,
+}
+
+impl CommandReport for Imported {
+    fn render(&self) -> Vec<OutputLine> {
+        let mut lines = Vec::new();
+        if !self.verified {
+            lines.push(OutputLine::Warning {
+                message: "imported without checking a signature; pass --allowed-signers and --identity to verify it".to_owned(),
+            });
+        }
+        lines.push(OutputLine::Summary {
+            text: format!(
+                "Approved {} new commit(s); {} now trusts {}",
+                self.added,
+                self.path.display(),
+                self.approved
+            ),
+        });
+        lines
+    }
+}
@@ -0,0 +1,78 @@
+use super::command::{Error, rewritable};
+use super::report::Backups as BackupsReport;
+use crate::command::Command;
+use crate::config::Config;
+use crate::infra::journal::{Error as JournalError, backup_path};
+use crate::infra::repo::RunLock;
+use std::fs;
+use std::path::Path;
+
+/// `gx clean-backups`: remove the `.bak` copies `--backup` saved beside gx.toml, gx.lock,
+/// and the workflows.
+pub struct CleanBackups;
+
+impl Command for CleanBackups {
+    type Report = BackupsReport;
+    type Error = Error;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        config: Config,
+        on_progress: &mut dyn FnMut(&str),
+    ) -> Result<BackupsReport, Error> {
+        let _lock = RunLock::acquire(repo_root)?;
+        on_progress("Removing backups...");
+        let mut report = BackupsReport::default();
+        for backup in rewritable(repo_root, &config)
+            .iter()
+            .map(|path| backup_path(path))
+            .filter(|backup| backup.is_file())
+        {
+            fs::remove_file(&backup).map_err(|source| JournalError::Write {
+                path: backup.clone(),
+                source,
+            })?;
+            report.removed.push(
+                backup
+                    .strip_prefix(repo_root)
+                    .unwrap_or(&backup)
+                    .to_path_buf(),
+            );
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{CleanBackups, Command as _};
+    use crate::config::Config;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn removes_only_the_backups_of_files_gx_rewrites() {
+        let dir = tempfile::tempdir().unwrap();
+        let workflows = dir.path().join(".github").join("workflows");
+        fs::create_dir_all(&workflows).unwrap();
+        fs::write(workflows.join("ci.yml"), "on: push\n").unwrap();
+        fs::write(workflows.join("ci.yml.bak"), "on: pull_request\n").unwrap();
+        fs::write(dir.path().join(".github").join("gx.lock.bak"), "").unwrap();
+        fs::write(dir.path().join("notes.bak"), "").unwrap();
+        let config = Config::load(dir.path()).unwrap();
+
+        let report = CleanBackups.run(dir.path(), config, &mut |_| {}).unwrap();
+
+        assert_eq!(
+            report.removed,
+            vec![
+                PathBuf::from(".github/gx.lock.bak"),
+                PathBuf::from(".github/workflows/ci.yml.bak"),
+            ]
+        );
+        assert!(workflows.join("ci.yml").exists());
+        assert!(dir.path().join("notes.bak").exists());
+    }
+}
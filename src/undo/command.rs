@@ -0,0 +1,150 @@
+use super::report::Report;
+use super::rollback::{self, Guard, Rollback};
+use crate::command::Command;
+use crate::config::Config;
+use crate::infra::journal::{self, Error as JournalError, Run, Snapshot};
+use crate::infra::repo::{Error as RepoError, RunLock};
+use crate::infra::workflow_update::WorkflowWriter;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Errors that can occur during the undo command.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Journal(#[from] JournalError),
+
+    #[error(transparent)]
+    Lock(#[from] RepoError),
+
+    #[error("{error}; putting back the files the run changed also failed")]
+    Rollback {
+        error: String,
+        #[source]
+        source: JournalError,
+    },
+}
+
+/// A run of a command that rewrites files. It holds the repository's run lock, so no
+/// other gx run writes at the same time, and the snapshot its changes are diffed against,
+/// which is put back if the run is interrupted or panics.
+pub struct Session {
+    /// Disarmed when the session is dropped, before the lock is released.
+    _guard: Guard,
+    /// Released when the session is dropped.
+    _lock: RunLock,
+    /// The files the command may rewrite, as they were before it ran.
+    rollback: Arc<Rollback>,
+    /// Save a `.bak` copy of each file the command rewrites.
+    backup: bool,
+}
+
+/// The undo command struct.
+pub struct Undo;
+
+impl Command for Undo {
+    type Report = Report;
+    type Error = Error;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        _config: Config,
+        on_progress: &mut dyn FnMut(&str),
+    ) -> Result<Report, Error> {
+        let _lock = RunLock::acquire(repo_root)?;
+        on_progress("Reverting the last gx run...");
+        let report = journal::undo(repo_root)?.map_or_else(Report::default, |run| Report {
+            command: Some(run.command),
+            files: run.changes.into_iter().map(|change| change.path).collect(),
+        });
+        Ok(report)
+    }
+}
+
+/// Start a run of a command that rewrites files: take the repository's run lock, then
+/// snapshot the files the command may rewrite.
+///
+/// # Errors
+///
+/// Returns [`Error::Lock`] if another gx run holds the lock or it cannot be taken.
+pub fn begin(repo_root: &Path, config: &Config) -> Result<Session, Error> {
+    let lock = RunLock::acquire(repo_root)?;
+    let before = Snapshot::take(repo_root, rewritable(repo_root, config));
+    let rollback = Arc::new(Rollback::new(repo_root, before));
+    Ok(Session {
+        _guard: rollback::arm(Arc::clone(&rollback)),
+        _lock: lock,
+        rollback,
+        backup: config.files.backup,
+    })
+}
+
+/// The files a gx command may rewrite: the manifest, the lock, and every workflow.
+pub(super) fn rewritable(repo_root: &Path, config: &Config) -> Vec<PathBuf> {
+    let mut paths = vec![config.manifest_path.clone(), config.lock_path.clone()];
+    paths.extend(
+        WorkflowWriter::new(repo_root)
+            .with_workflows(config.workflows.clone())
+            .find_workflows()
+            .unwrap_or_default(),
+    );
+    #[cfg(feature = "gitlab")]
+    paths.push(repo_root.join(crate::infra::gitlab::CI_FILE));
+    paths
+}
+
+/// Finish a run of `command` in `session`: record its changes for `gx undo` and save the
+/// backups `--backup` asks for when `result` is a success, or put every file back when it
+/// is a failure, so a run that fails part way never leaves the lock updated but the
+/// workflows half-rewritten. Files a run has committed with `--commit` or `--pr` stay.
+///
+/// # Errors
+///
+/// Returns the error in `result`, as [`Error::Rollback`] if a file cannot be put back, or
+/// [`Error::Journal`] if the journal or a backup cannot be written.
+pub fn settle<T, E: From<Error> + Display>(
+    repo_root: &Path,
+    command: &str,
+    session: Option<&Session>,
+    result: Result<T, E>,
+) -> Result<T, E> {
+    let Some(run) = session else {
+        return result;
+    };
+    match result {
+        Ok(value) => {
+            if run.backup {
+                run.rollback.before().backup().map_err(Error::from)?;
+            }
+            record(repo_root, command, run.rollback.before())?;
+            Ok(value)
+        }
+        Err(e) => match run.rollback.restore() {
+            Ok(()) => Err(e),
+            Err(source) => Err(Error::Rollback {
+                error: e.to_string(),
+                source,
+            }
+            .into()),
+        },
+    }
+}
+
+/// Record the files `command` changed since `before` in the journal, for `gx undo`.
+///
+/// # Errors
+///
+/// Returns [`Error::Journal`] if the journal cannot be read or written.
+pub fn record(repo_root: &Path, command: &str, before: &Snapshot) -> Result<(), Error> {
+    journal::record(
+        repo_root,
+        Run {
+            command: command.to_owned(),
+            changes: before.changes(),
+        },
+    )?;
+    Ok(())
+}
@@ -0,0 +1,12 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// `gx clean-backups`: removing the copies `--backup` saves.
+mod backups;
+/// Undo command: error types, struct, and `Command` implementation.
+mod command;
+pub mod report;
+/// Putting files back after a run fails, panics, or is interrupted.
+mod rollback;
+
+pub use backups::CleanBackups;
+pub use command::{Error, Session, Undo, begin, record, settle};
@@ -0,0 +1,101 @@
+use crate::command::CommandReport;
+use crate::output::lines::Line as OutputLine;
+use std::path::PathBuf;
+
+/// Report from the undo command.
+#[derive(Debug, Default)]
+pub struct Report {
+    /// The subcommand whose run was reverted; `None` when there was nothing to undo.
+    pub command: Option<String>,
+    /// Files restored, relative to the repository root.
+    pub files: Vec<PathBuf>,
+}
+
+impl CommandReport for Report {
+    fn render(&self) -> Vec<OutputLine> {
+        let Some(command) = &self.command else {
+            return vec![OutputLine::Summary {
+                text: "Nothing to undo".to_owned(),
+            }];
+        };
+
+        let mut lines: Vec<OutputLine> = self
+            .files
+            .iter()
+            .map(|file| OutputLine::Changed {
+                action: file.display().to_string(),
+                detail: "restored".to_owned(),
+            })
+            .collect();
+        lines.push(OutputLine::Blank);
+
+        let n = self.files.len();
+        lines.push(OutputLine::Summary {
+            text: format!(
+                "Reverted gx {command} · {n} file{}",
+                if n == 1 { "" } else { "s" }
+            ),
+        });
+        lines
+    }
+}
+
+/// Report from `gx clean-backups`.
+#[derive(Debug, Default)]
+pub struct Backups {
+    /// Backups removed, relative to the repository root.
+    pub removed: Vec<PathBuf>,
+}
+
+impl CommandReport for Backups {
+    fn render(&self) -> Vec<OutputLine> {
+        if self.removed.is_empty() {
+            return vec![OutputLine::Summary {
+                text: "No backups to remove".to_owned(),
+            }];
+        }
+        let mut lines: Vec<OutputLine> = self
+            .removed
+            .iter()
+            .map(|file| OutputLine::Changed {
+                action: file.display().to_string(),
+                detail: "removed".to_owned(),
+            })
+            .collect();
+        lines.push(OutputLine::Blank);
+        let n = self.removed.len();
+        lines.push(OutputLine::Summary {
+            text: format!("Removed {n} backup{}", if n == 1 { "" } else { "s" }),
+        });
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandReport as _, OutputLine, Report};
+
+    #[test]
+    fn render_nothing_to_undo() {
+        assert_eq!(
+            Report::default().render(),
+            vec![OutputLine::Summary {
+                text: "Nothing to undo".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn render_reverted_run() {
+        let report = Report {
+            command: Some("tidy".to_owned()),
+            files: vec![".github/gx.lock".into()],
+        };
+        assert_eq!(
+            report.render().last(),
+            Some(&OutputLine::Summary {
+                text: "Reverted gx tidy · 1 file".to_owned(),
+            })
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use crate::infra::atomic;
+use crate::infra::git::Git;
+use crate::infra::journal::{Error as JournalError, Snapshot};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Once, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+/// Exit code of a run stopped by a signal, as shells report Ctrl-C.
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// How often the watcher checks whether the run was interrupted.
+const WATCH_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Set by the signal handler; the handler may do nothing else safely.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// The rollback of the run in progress, if any, for the watcher to carry out.
+static ARMED: Mutex<Option<Arc<Rollback>>> = Mutex::new(None);
+
+/// Installs the signal handler and starts the watcher, once per process.
+static WATCH: Once = Once::new();
+
+/// How a run that fails part way is undone: the files it may rewrite as they were before,
+/// and the commit checked out then.
+pub struct Rollback {
+    /// The files the command may rewrite, as they were before it ran.
+    before: Snapshot,
+    /// The repository the run commits to with `--commit` or `--pr`.
+    repo_root: PathBuf,
+    /// The commit checked out when the run started; `None` outside a repository with one.
+    head: Option<String>,
+}
+
+impl Rollback {
+    /// The rollback of a run in `repo_root` that may rewrite the files of `before`.
+    #[must_use]
+    pub fn new(repo_root: &Path, before: Snapshot) -> Self {
+        Self {
+            before,
+            repo_root: repo_root.to_path_buf(),
+            head: Git::new(repo_root).head().ok(),
+        }
+    }
+
+    /// The files the command may rewrite, as they were before it ran.
+    #[must_use]
+    pub const fn before(&self) -> &Snapshot {
+        &self.before
+    }
+
+    /// Put every file back as it was, unless the run has committed: the files then match
+    /// the new commit, and putting them back would leave the work tree disagreeing with it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JournalError::Write`] if a file cannot be written back or removed.
+    pub fn restore(&self) -> Result<(), JournalError> {
+        if Git::new(&self.repo_root).head().ok() != self.head {
+            return Ok(());
+        }
+        self.before.restore()
+    }
+}
+
+/// Carries out a [`Rollback`] when the run is interrupted by Ctrl-C or `kill`, or panics,
+/// before the guard is dropped.
+pub struct Guard {
+    /// The rollback to carry out.
+    rollback: Arc<Rollback>,
+}
+
+/// Arm `rollback` until the returned guard is dropped.
+pub fn arm(rollback: Arc<Rollback>) -> Guard {
+    WATCH.call_once(watch);
+    *ARMED.lock().unwrap_or_else(PoisonError::into_inner) = Some(Arc::clone(&rollback));
+    Guard { rollback }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        ARMED.lock().unwrap_or_else(PoisonError::into_inner).take();
+        if thread::panicking() {
+            // The panic is what gets reported; putting the files back is best effort
+            self.rollback.restore().unwrap_or_default();
+        }
+    }
+}
+
+/// Route SIGINT and SIGTERM to [`on_signal`], and start a thread that, once one arrives,
+/// stops further writes, carries out the armed rollback, and exits.
+fn watch() {
+    #[expect(
+        clippy::as_conversions,
+        reason = "libc takes the handler as an address"
+    )]
+    let handler = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    for signal in [libc::SIGINT, libc::SIGTERM] {
+        // SAFETY: the handler only stores to an atomic, which is async-signal-safe.
+        unsafe {
+            libc::signal(signal, handler);
+        }
+    }
+    thread::spawn(|| {
+        while !INTERRUPTED.load(Ordering::SeqCst) {
+            thread::sleep(WATCH_INTERVAL);
+        }
+        // Held until the process exits, so the run writes nothing after the rollback
+        let _writes = atomic::hold();
+        let armed = ARMED.lock().unwrap_or_else(PoisonError::into_inner).take();
+        if let Some(Err(e)) = armed.map(|rollback| rollback.restore()) {
+            eprintln!("gx was interrupted, and putting back the files it changed failed: {e}");
+        }
+        process::exit(INTERRUPTED_EXIT_CODE);
+    });
+}
+
+/// Record that the run was interrupted, for the watcher.
+extern "C" fn on_signal(_signal: libc::c_int) {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+#[cfg(test)]
+#[expect(clippy::unwrap_used, reason = "tests use unwrap freely")]
+mod tests {
+    use super::{Rollback, arm};
+    use crate::infra::journal::Snapshot;
+    use std::fs;
+    use std::panic::{self, AssertUnwindSafe};
+    use std::path::Path;
+    use std::process::Command;
+    use std::sync::Arc;
+
+    fn git(root: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["-c", "user.name=gx", "-c", "user.email=gx@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn restore_keeps_the_files_a_run_committed() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = dir.path().join("gx.lock");
+        fs::write(&lock, "old\n").unwrap();
+        git(dir.path(), &["init", "-q"]);
+        git(dir.path(), &["add", "gx.lock"]);
+        git(dir.path(), &["commit", "-q", "-m", "init"]);
+        let rollback = Rollback::new(dir.path(), Snapshot::take(dir.path(), vec![lock.clone()]));
+
+        fs::write(&lock, "new\n").unwrap();
+        git(dir.path(), &["commit", "-q", "-am", "upgrade"]);
+        rollback.restore().unwrap();
+
+        assert_eq!(fs::read_to_string(&lock).unwrap(), "new\n");
+    }
+
+    #[test]
+    fn a_panic_puts_the_files_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = dir.path().join("gx.lock");
+        fs::write(&lock, "old\n").unwrap();
+        let rollback = Rollback::new(dir.path(), Snapshot::take(dir.path(), vec![lock.clone()]));
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _guard = arm(Arc::new(rollback));
+            fs::write(&lock, "half\n").unwrap();
+            panic::resume_unwind(Box::new("interrupted"));
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&lock).unwrap(), "old\n");
+    }
+}
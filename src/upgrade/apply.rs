@@ -0,0 +1,121 @@
+use std::path::{Path, PathBuf};
+
+use super::command::{RunError, rows};
+use super::plan::{self, Plan, UpgradeError};
+use super::report::Report as UpgradeReport;
+use crate::command::Command;
+use crate::config::{Config, Workflows};
+use crate::domain::action::identity::ActionId;
+use crate::domain::diff::WorkflowPatch;
+use crate::infra::plan_file::{self, SavedPlan};
+use crate::infra::workflow_update::WorkflowWriter;
+
+/// The apply command: execute a plan saved by `gx upgrade --save-plan`.
+pub struct Apply {
+    /// Path to the saved plan JSON.
+    pub plan_path: PathBuf,
+    /// Apply the plan even if gx.toml or gx.lock changed since it was saved.
+    pub force: bool,
+}
+
+impl Command for Apply {
+    type Report = UpgradeReport;
+    type Error = RunError;
+
+    fn run(
+        &self,
+        repo_root: &Path,
+        config: Config,
+        on_progress: &mut dyn FnMut(&str),
+    ) -> Result<UpgradeReport, RunError> {
+        on_progress(&format!("Reading plan {}", self.plan_path.display()));
+        let saved = plan_file::read(&self.plan_path)?;
+        if !self.force {
+            saved.ensure_current(&config.manifest_path, &config.lock_path)?;
+        }
+
+        let mut lock = config.lock.clone();
+        lock.apply(&saved.lock_changes);
+        if config.manifest_path.exists() {
+            crate::infra::manifest::patch::apply_manifest_diff(
+                &config.manifest_path,
+                &saved.manifest,
+            )?;
+            lock.set_manifest_hash(
+                config.lock_hash(crate::infra::manifest::hash_file(&config.manifest_path)?),
+            );
+            config.lock_store()?.save(&lock)?;
+        }
+
+        let workflows = rewrite_workflows(repo_root, &saved)?;
+
+        Ok(UpgradeReport {
+            upgrades: rows(&config.lock, &lock, &saved.upgrades),
+            workflows,
+            ..Default::default()
+        })
+    }
+}
+
+/// Capture a computed plan in its saved form, recording the workflow files it will rewrite.
+///
+/// # Errors
+///
+/// Returns [`UpgradeError::Workflow`] if the workflow files cannot be scanned.
+pub fn to_saved(
+    repo_root: &Path,
+    workflows: &Workflows,
+    upgrade_plan: Plan,
+) -> Result<SavedPlan, UpgradeError> {
+    let ids: Vec<ActionId> = upgrade_plan
+        .lock_changes
+        .added
+        .iter()
+        .map(|(spec, _)| spec.id.clone())
+        .collect();
+    let using = WorkflowWriter::new(repo_root)
+        .with_workflows(workflows.clone())
+        .workflows_using(&ids)?
+        .into_iter()
+        .map(|path| {
+            path.strip_prefix(repo_root)
+                .map_or_else(|_| path.clone(), Path::to_path_buf)
+        })
+        .collect();
+
+    Ok(SavedPlan {
+        upgrades: upgrade_plan.upgrades,
+        manifest: upgrade_plan.manifest,
+        lock_changes: upgrade_plan.lock_changes,
+        workflows: using,
+        ..SavedPlan::default()
+    })
+}
+
+/// Rewrite exactly the workflow files recorded in a saved plan with its lock pins.
+///
+/// Returns the workflow files that changed.
+///
+/// # Errors
+///
+/// Returns [`UpgradeError::Workflow`] if a workflow file cannot be updated.
+pub fn rewrite_workflows(
+    repo_root: &Path,
+    saved: &SavedPlan,
+) -> Result<Vec<PathBuf>, UpgradeError> {
+    let pins = plan::lock_pins(&saved.lock_changes);
+    if pins.is_empty() {
+        return Ok(Vec::new());
+    }
+    let patches: Vec<WorkflowPatch> = saved
+        .workflows
+        .iter()
+        .map(|path| WorkflowPatch {
+            path: repo_root.join(path),
+            pins: pins.clone(),
+            steps: Vec::new(),
+        })
+        .collect();
+    let results = WorkflowWriter::new(repo_root).apply_patches(&patches)?;
+    Ok(results.into_iter().map(|result| result.file).collect())
+}
@@ -10,6 +10,10 @@ pub enum Scope {
     Single(ActionId),
     /// Pin a specific action to an exact version.
     Pinned(ActionId, Version),
+    /// Upgrade the actions of a group declared under `[groups]` in the manifest.
+    Group(String),
+    /// Upgrade every action published by an owner (user or organization).
+    Owner(String),
 }
 
 /// How the upgrade command should find new versions.
@@ -19,6 +23,8 @@ pub enum Mode {
     Safe,
     /// Upgrade to the absolute latest version, including major versions.
     Latest,
+    /// Refresh lock SHAs for the current manifest specifiers without changing them.
+    LockOnly,
 }
 
 /// A request to upgrade actions with a specific mode and scope.
@@ -26,16 +32,44 @@ pub enum Mode {
 pub struct Request {
     pub mode: Mode,
     pub scope: Scope,
+    /// Only upgrade actions whose locked commit is from before this day (`--max-age`).
+    pub locked_before: Option<time::Date>,
+    /// Re-pin actions whose floating tag moved to another commit (`--repin`).
+    pub repin: bool,
 }
 
 impl Request {
     /// Create a new upgrade request.
     #[must_use]
     pub fn new(mode: Mode, scope: Scope) -> Self {
-        Self { mode, scope }
+        Self {
+            mode,
+            scope,
+            locked_before: None,
+            repin: false,
+        }
+    }
+
+    /// Narrow the scope to a `--group` or `--owner`, when one is given.
+    pub fn narrow(&mut self, group: Option<String>, owner: Option<String>) {
+        if let Some(name) = group {
+            self.scope = Scope::Group(name);
+        }
+        if let Some(name) = owner {
+            self.scope = Scope::Owner(name);
+        }
     }
 }
 
+/// The first day an action's locked commit counts as fresh under `--max-age DAYS`.
+#[must_use]
+pub fn max_age_cutoff(days: u16) -> time::Date {
+    let today = time::OffsetDateTime::now_utc().date();
+    today
+        .checked_sub(time::Duration::days(days.into()))
+        .unwrap_or(time::Date::MIN)
+}
+
 /// Errors from resolving CLI arguments into an [`Request`].
 #[derive(Debug, Error)]
 pub enum Error {
@@ -46,6 +80,13 @@ pub enum Error {
     )]
     LatestWithVersionPin,
 
+    /// `--only-lock` was combined with an exact version pin (`ACTION@VERSION`).
+    #[error(
+        "--only-lock cannot be combined with an exact version pin (ACTION@VERSION). \
+         Use --only-lock ACTION to refresh a single action."
+    )]
+    LockOnlyWithVersionPin,
+
     /// The action string could not be parsed as `ACTION@VERSION`.
     #[error("invalid format: expected ACTION@VERSION (e.g., actions/checkout@v5), got: {input}")]
     InvalidActionFormat { input: String },
@@ -87,13 +128,29 @@ pub fn resolve_upgrade_mode(action: Option<&str>, latest: bool) -> Result<Reques
     }
 }
 
+/// Resolve CLI arguments for `--only-lock` into an [`Request`].
+///
+/// # Errors
+///
+/// Returns [`Error::LockOnlyWithVersionPin`] if the action carries an `@VERSION` pin.
+pub fn resolve_lock_only(action: Option<&str>) -> Result<Request, Error> {
+    match action {
+        Some(action_str) if action_str.contains('@') => Err(Error::LockOnlyWithVersionPin),
+        Some(action_str) => Ok(Request::new(
+            Mode::LockOnly,
+            Scope::Single(ActionId::from(action_str)),
+        )),
+        None => Ok(Request::new(Mode::LockOnly, Scope::All)),
+    }
+}
+
 #[cfg(test)]
 #[expect(
     clippy::unwrap_used,
     reason = "tests use unwrap, indexing, and other patterns freely"
 )]
 mod tests {
-    use super::{Error, Mode, Request, Scope, resolve_upgrade_mode};
+    use super::{Error, Mode, Request, Scope, resolve_lock_only, resolve_upgrade_mode};
     use crate::domain::action::identity::{ActionId, Version};
 
     #[test]
@@ -137,6 +194,19 @@ mod tests {
         assert!(matches!(err, Error::LatestWithVersionPin));
     }
 
+    #[test]
+    fn resolve_lock_only_scopes() {
+        let all = resolve_lock_only(None).unwrap();
+        assert!(matches!(all.mode, Mode::LockOnly));
+        assert!(matches!(all.scope, Scope::All));
+
+        let single = resolve_lock_only(Some("actions/checkout")).unwrap();
+        assert!(matches!(single.scope, Scope::Single(_)));
+
+        let err = resolve_lock_only(Some("actions/checkout@v5")).unwrap_err();
+        assert!(matches!(err, Error::LockOnlyWithVersionPin));
+    }
+
     #[test]
     fn new_should_accept_pinned_scope() {
         let req = Request::new(
@@ -1,14 +1,24 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use super::apply;
 use super::cli::Request as UpgradeRequest;
-use super::plan::{self, UpgradeError};
-use super::report::Report as UpgradeReport;
+use super::commit::{self, Mode as CommitMode, Options as CommitOptions};
+use super::plan::{self, MovedTag, Plan, UpgradeError};
+use super::pull_request::{self, Change};
+use super::release_notes;
+use super::report::{Moved, Report as UpgradeReport, Row as UpgradeRow};
 use crate::command::Command;
 use crate::config::Config;
-use crate::domain::action::upgrade::Action;
+use crate::domain::action::identity::ActionId;
+use crate::domain::action::spec::Spec as ActionSpec;
+use crate::domain::action::specifier::parse_semver;
+use crate::domain::action::upgrade::{Action, Candidate as UpgradeCandidate};
+use crate::domain::lock::Lock;
+use crate::infra::git::Error as GitError;
 use crate::infra::github::Registry;
 use crate::infra::lock::Error as LockFileError;
 use crate::infra::manifest::Error as ManifestError;
+use crate::infra::plan_file::{self, Error as PlanFileError};
 use crate::infra::workflow_update::WorkflowWriter;
 use thiserror::Error;
 
@@ -22,12 +32,30 @@ pub enum RunError {
     #[error(transparent)]
     Lock(#[from] LockFileError),
     #[error(transparent)]
+    PlanFile(#[from] PlanFileError),
+    #[error(transparent)]
+    Git(#[from] GitError),
+    #[error(transparent)]
     Upgrade(#[from] UpgradeError),
+    #[error("gx upgrade looks up new versions on GitHub, so it cannot run with --offline")]
+    Offline,
 }
 
+/// Decides whether an upgrade to another major version should be applied.
+pub type ConfirmFn = Box<dyn Fn(&UpgradeCandidate) -> bool>;
+
 /// The upgrade command struct.
 pub struct Upgrade {
     pub request: UpgradeRequest,
+    /// When set, write the computed plan to this path instead of applying it.
+    pub save_plan: Option<PathBuf>,
+    /// Asked once per upgrade to another major version; `None` applies them all without
+    /// asking.
+    pub confirm: Option<ConfirmFn>,
+    /// After applying, commit the changes on a new branch and open a pull request.
+    pub pull_request: bool,
+    /// Commit the changes to the current branch after writing them.
+    pub commit: Option<CommitOptions>,
 }
 
 impl Command for Upgrade {
@@ -40,11 +68,10 @@ impl Command for Upgrade {
         config: Config,
         on_progress: &mut dyn FnMut(&str),
     ) -> Result<UpgradeReport, RunError> {
-        let has_manifest = config.manifest_path.exists();
-        let registry = Registry::new(config.settings.github_token)?;
-        let updater = WorkflowWriter::new(repo_root);
+        let registry = online_registry(&config)?;
+        let updater = WorkflowWriter::new(repo_root).with_workflows(config.workflows.clone());
 
-        let upgrade_plan = plan::plan(
+        let mut upgrade_plan = plan::plan(
             &config.manifest,
             &config.lock,
             &registry,
@@ -52,52 +79,392 @@ impl Command for Upgrade {
             &mut *on_progress,
         )?;
 
+        let warnings: Vec<String> = registry.rate_limit_warning().into_iter().collect();
+        let mut skipped = Vec::new();
+        if let Some(confirm) = &self.confirm {
+            let declined: Vec<ActionId> = upgrade_plan
+                .upgrades
+                .iter()
+                .filter(|u| crosses_major(&config.lock, u) && !confirm(u))
+                .map(|u| u.id.clone())
+                .collect();
+            for id in declined {
+                upgrade_plan.decline(&config.lock, &id);
+                skipped.push((
+                    id.to_string(),
+                    "cross-major upgrade not confirmed".to_owned(),
+                ));
+            }
+        }
+
+        let moved_tags = moved(&upgrade_plan.moved_tags, self.request.repin);
         if upgrade_plan.is_empty() {
             return Ok(UpgradeReport {
-                up_to_date: true,
+                up_to_date: skipped.is_empty() && moved_tags.is_empty() && warnings.is_empty(),
+                skipped,
+                moved_tags,
+                warnings,
                 ..Default::default()
             });
         }
 
-        if has_manifest {
-            crate::infra::manifest::patch::apply_manifest_diff(
-                &config.manifest_path,
-                &upgrade_plan.manifest,
-            )?;
-            let lock_store = crate::infra::lock::Store::new(&config.lock_path);
-            lock_store.save(&upgrade_plan.lock)?;
-        }
+        let upgrades = rows(&config.lock, &upgrade_plan.lock, &upgrade_plan.upgrades);
+        let (changes, release_notes) = describe(&registry, &config, &upgrade_plan);
 
-        let workflows_updated = plan::apply_upgrade_workflows(
-            &updater,
-            &upgrade_plan.lock_changes,
-            &upgrade_plan.upgrades,
-        )?;
+        if let Some(path) = &self.save_plan {
+            let mut saved = apply::to_saved(repo_root, &config.workflows, upgrade_plan)?;
+            saved.stamp(&config.manifest_path, &config.lock_path)?;
+            plan_file::write(path, &saved)?;
+            return Ok(UpgradeReport {
+                upgrades,
+                release_notes,
+                skipped,
+                moved_tags,
+                warnings,
+                saved_plan: Some(path.clone()),
+                changed: true,
+                ..Default::default()
+            });
+        }
 
-        if config.manifest_migrated {
-            on_progress("migrated gx.toml → semver specifiers");
+        let per_action = self
+            .commit
+            .as_ref()
+            .filter(|options| options.mode == CommitMode::PerAction);
+        let (workflows, files) = match per_action {
+            Some(options) => {
+                let workflows = commit::apply_per_action(
+                    repo_root,
+                    &config,
+                    &upgrade_plan,
+                    &changes,
+                    &options.template,
+                )?;
+                (workflows, vec![])
+            }
+            None => write_plan(&updater, &config, &upgrade_plan)?,
+        };
+        if let Some(options) = self
+            .commit
+            .as_ref()
+            .filter(|options| options.mode == CommitMode::Batch)
+        {
+            commit::batch(repo_root, &files, &changes, &options.template)?;
         }
 
-        let upgrades = upgrade_plan
-            .upgrades
-            .iter()
-            .map(|u| {
-                let from = u.current.to_string();
-                let to = match &u.action {
-                    Action::InRange { candidate } => candidate.to_string(),
-                    Action::CrossRange { new_specifier, .. } => new_specifier.to_string(),
-                };
-                (u.id.to_string(), from, to)
-            })
-            .collect();
+        let pull_request = if self.pull_request {
+            on_progress("Opening pull request...");
+            Some(pull_request::open(repo_root, &registry, &files, &changes)?)
+        } else {
+            None
+        };
+
+        for notice in config.migration_notices() {
+            on_progress(&notice);
+        }
 
         let report = UpgradeReport {
             upgrades,
-            workflows_updated,
+            release_notes,
+            skipped,
+            moved_tags,
+            warnings,
+            workflows,
             up_to_date: false,
+            changed: true,
+            pull_request,
             ..Default::default()
         };
 
         Ok(report)
     }
 }
+
+/// The registry to look up new versions with; an upgrade cannot run offline.
+///
+/// # Errors
+///
+/// Returns [`RunError::Offline`] with `--offline`, and [`RunError::Github`] if the
+/// registry cannot be created.
+fn online_registry(config: &Config) -> Result<Registry, RunError> {
+    if config.settings.offline {
+        return Err(RunError::Offline);
+    }
+    Ok(Registry::from_settings(&config.settings)?)
+}
+
+/// Write the whole plan to the manifest, lock, and workflows.
+///
+/// Returns the workflow files that changed, and every file written.
+///
+/// # Errors
+///
+/// Returns [`RunError`] if a file cannot be written.
+fn write_plan(
+    updater: &WorkflowWriter,
+    config: &Config,
+    upgrade_plan: &Plan,
+) -> Result<(Vec<PathBuf>, Vec<PathBuf>), RunError> {
+    let has_manifest = config.manifest_path.exists();
+    if has_manifest {
+        crate::infra::manifest::patch::apply_manifest_diff(
+            &config.manifest_path,
+            &upgrade_plan.manifest,
+        )?;
+        let mut lock = upgrade_plan.lock.clone();
+        lock.set_manifest_hash(
+            config.lock_hash(crate::infra::manifest::hash_file(&config.manifest_path)?),
+        );
+        config.lock_store()?.save(&lock)?;
+    }
+
+    let workflows =
+        plan::apply_upgrade_workflows(updater, &upgrade_plan.lock_changes, &upgrade_plan.upgrades)?;
+    let mut files = workflows.clone();
+    if has_manifest {
+        files.extend([config.manifest_path.clone(), config.lock_path.clone()]);
+    }
+    Ok((workflows, files))
+}
+
+/// Describe each upgrade for commit messages and pull requests, along with the
+/// release notes shown in the report, linking to compare pages on the configured host.
+fn describe(
+    registry: &Registry,
+    config: &Config,
+    upgrade_plan: &Plan,
+) -> (Vec<Change>, Vec<(String, Vec<String>)>) {
+    let excerpts: Vec<Option<Vec<String>>> = upgrade_plan
+        .upgrades
+        .iter()
+        .map(|u| {
+            registry
+                .release_notes(&u.id, u.candidate())
+                .map(|body| release_notes::summarize(&body, None))
+        })
+        .collect();
+    let web_url = config.settings.github_web_url();
+    let changes = changes(&web_url, &config.lock, upgrade_plan, &excerpts);
+    let notes = notes(&changes, excerpts);
+    (changes, notes)
+}
+
+/// The release notes shown in the report: the compare link of each upgrade, after the
+/// excerpt of its release notes when its release has a body.
+fn notes(changes: &[Change], excerpts: Vec<Option<Vec<String>>>) -> Vec<(String, Vec<String>)> {
+    changes
+        .iter()
+        .zip(excerpts)
+        .filter_map(|(change, excerpt)| {
+            let mut lines = excerpt.unwrap_or_default();
+            lines.extend(change.compare_url.clone());
+            (!lines.is_empty()).then(|| (change.action.clone(), lines))
+        })
+        .collect()
+}
+
+/// One [`Change`] per upgrade, with the locked versions and SHAs before and after.
+fn changes(
+    web_url: &str,
+    lock: &Lock,
+    upgrade_plan: &Plan,
+    excerpts: &[Option<Vec<String>>],
+) -> Vec<Change> {
+    upgrade_plan
+        .upgrades
+        .iter()
+        .zip(change_lines(&upgrade_plan.upgrades))
+        .zip(excerpts)
+        .map(|((u, (action, from, to)), excerpt)| {
+            let old = lock.get(&ActionSpec::new(u.id.clone(), u.current.clone()));
+            let new = upgrade_plan.lock.get(&ActionSpec::new(
+                u.id.clone(),
+                u.manifest_specifier().clone(),
+            ));
+            Change {
+                action,
+                from,
+                to,
+                old_version: old.map(|entry| entry.version.to_string()),
+                old_sha: old.map(|entry| entry.commit.sha.to_string()),
+                new_version: new.map(|entry| entry.version.to_string()),
+                new_sha: new.map(|entry| entry.commit.sha.to_string()),
+                compare_url: compare_url(web_url, lock, u),
+                release_notes: excerpt.clone().unwrap_or_default(),
+                crosses_major: crosses_major(lock, u),
+            }
+        })
+        .collect()
+}
+
+/// Whether `upgrade` changes the semver major version from the one `lock` holds for its
+/// specifier, or from the specifier itself when nothing is locked. A cross-range bump of
+/// `~4.1` to `v4.2.0` stays on major 4.
+fn crosses_major(lock: &Lock, upgrade: &UpgradeCandidate) -> bool {
+    let locked = lock.get(&ActionSpec::new(
+        upgrade.id.clone(),
+        upgrade.current.clone(),
+    ));
+    let from = locked.map_or_else(
+        || upgrade.current.to_lookup_tag(),
+        |entry| entry.version.to_string(),
+    );
+    match (
+        parse_semver(&from),
+        parse_semver(upgrade.candidate().as_str()),
+    ) {
+        (Some(old), Some(new)) => old.major != new.major,
+        _ => matches!(upgrade.action, Action::CrossRange { .. }),
+    }
+}
+
+/// Compare link on the host at `web_url` from the version locked before the upgrade to the
+/// candidate.
+fn compare_url(web_url: &str, lock: &Lock, upgrade: &UpgradeCandidate) -> Option<String> {
+    lock.get(&ActionSpec::new(
+        upgrade.id.clone(),
+        upgrade.current.clone(),
+    ))
+    .map(|entry| {
+        release_notes::compare_url(web_url, &upgrade.id, &entry.version, upgrade.candidate())
+    })
+}
+
+/// Summary table rows for upgrade candidates, reading versions and SHAs from the lock
+/// `before` and `after` the upgrade.
+pub(super) fn rows(before: &Lock, after: &Lock, upgrades: &[UpgradeCandidate]) -> Vec<UpgradeRow> {
+    upgrades
+        .iter()
+        .map(|u| {
+            let locked = after.get(&ActionSpec::new(
+                u.id.clone(),
+                u.manifest_specifier().clone(),
+            ));
+            UpgradeRow {
+                action: u.id.to_string(),
+                current: before
+                    .get(&ActionSpec::new(u.id.clone(), u.current.clone()))
+                    .map_or_else(|| u.current.to_string(), |entry| entry.version.to_string()),
+                candidate: u.candidate().to_string(),
+                range: u.manifest_specifier().to_string(),
+                sha: locked.map(|entry| entry.commit.sha.to_string()),
+            }
+        })
+        .collect()
+}
+
+/// Report entries for the floating tags that moved, marked re-pinned under `--repin`.
+fn moved(tags: &[MovedTag], repinned: bool) -> Vec<Moved> {
+    tags.iter()
+        .map(|tag| Moved {
+            action: tag.spec.id.to_string(),
+            tag: tag.tag.to_string(),
+            locked: tag.locked.to_string(),
+            current: tag.current.to_string(),
+            repinned,
+        })
+        .collect()
+}
+
+/// Commit message lines for upgrade candidates: (action, `from_version`, `to_version`).
+fn change_lines(upgrades: &[UpgradeCandidate]) -> Vec<(String, String, String)> {
+    upgrades
+        .iter()
+        .map(|u| {
+            let from = u.current.to_string();
+            let to = match &u.action {
+                Action::InRange { candidate } => candidate.to_string(),
+                Action::CrossRange { new_specifier, .. } => new_specifier.to_string(),
+            };
+            (u.id.to_string(), from, to)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Change, crosses_major, notes};
+    use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Version};
+    use crate::domain::action::resolved::{Commit, Provenance};
+    use crate::domain::action::spec::Spec;
+    use crate::domain::action::specifier::Specifier;
+    use crate::domain::action::upgrade::{Action, Candidate};
+    use crate::domain::lock::Lock;
+
+    fn cross_range(specifier: &str, candidate: &str, new_specifier: &str) -> Candidate {
+        Candidate {
+            id: ActionId::from("actions/checkout"),
+            current: Specifier::parse(specifier),
+            action: Action::CrossRange {
+                candidate: Version::from(candidate),
+                new_specifier: Specifier::parse(new_specifier),
+            },
+        }
+    }
+
+    #[test]
+    fn notes_keep_the_compare_link_of_a_release_without_a_body() {
+        let change = |action: &str, url: Option<&str>| Change {
+            action: action.to_owned(),
+            compare_url: url.map(str::to_owned),
+            ..Change::default()
+        };
+        let changes = [
+            change(
+                "actions/checkout",
+                Some("https://github.com/compare/v4...v5"),
+            ),
+            change("actions/cache", None),
+            change(
+                "actions/setup-node",
+                Some("https://github.com/compare/v3...v4"),
+            ),
+        ];
+
+        let shown = notes(&changes, vec![None, None, Some(vec!["* Fix".to_owned()])]);
+
+        assert_eq!(
+            shown,
+            vec![
+                (
+                    "actions/checkout".to_owned(),
+                    vec!["https://github.com/compare/v4...v5".to_owned()]
+                ),
+                (
+                    "actions/setup-node".to_owned(),
+                    vec![
+                        "* Fix".to_owned(),
+                        "https://github.com/compare/v3...v4".to_owned()
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn crosses_major_only_when_the_major_version_changes() {
+        let mut lock = Lock::default();
+        lock.set(
+            &Spec::new(ActionId::from("actions/checkout"), Specifier::parse("~4.1")),
+            Version::from("v4.1.7"),
+            Commit {
+                sha: CommitSha::from("aaa111"),
+                repository: ActionId::from("actions/checkout").base_repo(),
+                ref_type: None,
+                date: CommitDate::from("2026-01-01T00:00:00Z"),
+                provenance: Provenance::default(),
+            },
+        );
+
+        assert!(!crosses_major(
+            &lock,
+            &cross_range("~4.1", "v4.2.0", "~4.2")
+        ));
+        assert!(crosses_major(&lock, &cross_range("~4.1", "v5.0.0", "~5.0")));
+        assert!(crosses_major(&lock, &cross_range("^4", "v5.1.0", "^5")));
+        assert!(!crosses_major(
+            &lock,
+            &cross_range("~3.9", "v3.10.0", "~3.10")
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+use std::path::{Path, PathBuf};
+
+use super::command::RunError;
+use super::plan::{self, Plan};
+use super::pull_request::{Change, changelog};
+use crate::config::Config;
+use crate::domain::action::identity::ActionId;
+use crate::domain::diff::{LockDiff, ManifestDiff};
+use crate::infra::git::Git;
+use crate::infra::workflow_update::WorkflowWriter;
+
+/// Commit message template used when `--commit-message` is not given.
+pub const DEFAULT_TEMPLATE: &str = "chore(gx): upgrade {action} {old} -> {new}";
+
+/// Message for the commit holding lock refreshes that belong to no upgraded action.
+const REFRESH_MESSAGE: &str = "chore(gx): refresh locked actions";
+
+/// How `--commit` groups the changes of an upgrade into commits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// One commit with every change.
+    Batch,
+    /// One commit per upgraded action.
+    PerAction,
+}
+
+/// Settings for committing the changes an upgrade writes.
+#[derive(Debug)]
+pub struct Options {
+    pub mode: Mode,
+    /// Per-action message template with `{action}`, `{old}`, and `{new}` placeholders.
+    pub template: String,
+}
+
+impl Options {
+    /// Options for `--commit`, using [`DEFAULT_TEMPLATE`] when no template is given.
+    #[must_use]
+    pub fn new(per_action: bool, template: Option<String>) -> Self {
+        Self {
+            mode: if per_action {
+                Mode::PerAction
+            } else {
+                Mode::Batch
+            },
+            template: template.unwrap_or_else(|| DEFAULT_TEMPLATE.to_owned()),
+        }
+    }
+}
+
+/// Render the message template for one upgraded action.
+#[must_use]
+pub fn render(template: &str, change: &Change) -> String {
+    template
+        .replace("{action}", &change.action)
+        .replace("{old}", &change.from)
+        .replace("{new}", &change.to)
+}
+
+/// Commit message for one upgraded action: the rendered template, then its changelog.
+#[must_use]
+pub fn action_message(template: &str, change: &Change) -> String {
+    format!(
+        "{}\n\n{}",
+        render(template, change),
+        changelog(std::slice::from_ref(change))
+    )
+}
+
+/// Message for a single commit holding every change: the action message when one
+/// action changed, otherwise a summary subject with one rendered line per action
+/// followed by the changelog.
+#[must_use]
+pub fn batch_message(template: &str, changes: &[Change]) -> String {
+    match changes {
+        [] => REFRESH_MESSAGE.to_owned(),
+        [change] => action_message(template, change),
+        _ => {
+            let lines: Vec<String> = changes
+                .iter()
+                .map(|change| format!("- {}", render(template, change)))
+                .collect();
+            format!(
+                "chore(gx): upgrade {} actions\n\n{}\n\n{}",
+                changes.len(),
+                lines.join("\n"),
+                changelog(changes)
+            )
+        }
+    }
+}
+
+/// Stage `files` and commit them as one commit.
+///
+/// # Errors
+///
+/// Returns [`RunError::Git`] if the files cannot be staged or committed.
+pub fn batch(
+    repo_root: &Path,
+    files: &[PathBuf],
+    changes: &[Change],
+    template: &str,
+) -> Result<(), RunError> {
+    let git = Git::new(repo_root);
+    git.add(files)?;
+    git.commit(&batch_message(template, changes))?;
+    Ok(())
+}
+
+/// Write the plan one upgraded action at a time, committing after each.
+///
+/// Lock refreshes that belong to no upgraded action are written and committed last.
+/// Returns the workflow files that changed.
+///
+/// # Errors
+///
+/// Returns [`RunError`] if a file cannot be written or git fails.
+pub fn apply_per_action(
+    repo_root: &Path,
+    config: &Config,
+    upgrade_plan: &Plan,
+    changes: &[Change],
+    template: &str,
+) -> Result<Vec<PathBuf>, RunError> {
+    let git = Git::new(repo_root);
+    let writer = WorkflowWriter::new(repo_root).with_workflows(config.workflows.clone());
+    let has_manifest = config.manifest_path.exists();
+    let mut lock = config.lock.clone();
+    let mut workflows: Vec<PathBuf> = Vec::new();
+
+    let mut steps: Vec<(ManifestDiff, LockDiff, String)> = upgrade_plan
+        .upgrades
+        .iter()
+        .zip(changes)
+        .map(|(upgrade, change)| {
+            let is_action = |id: &ActionId| *id == upgrade.id;
+            (
+                manifest_diff_for(&upgrade_plan.manifest, is_action),
+                lock_diff_for(&upgrade_plan.lock_changes, is_action),
+                action_message(template, change),
+            )
+        })
+        .collect();
+    let is_rest = |id: &ActionId| !upgrade_plan.upgrades.iter().any(|u| u.id == *id);
+    let rest = lock_diff_for(&upgrade_plan.lock_changes, is_rest);
+    if !rest.is_empty() {
+        steps.push((ManifestDiff::default(), rest, REFRESH_MESSAGE.to_owned()));
+    }
+
+    for (manifest_diff, lock_diff, message) in steps {
+        let mut files = plan::apply_upgrade_workflows(&writer, &lock_diff, &upgrade_plan.upgrades)?;
+        if has_manifest {
+            crate::infra::manifest::patch::apply_manifest_diff(
+                &config.manifest_path,
+                &manifest_diff,
+            )?;
+            lock.apply(&lock_diff);
+            lock.set_manifest_hash(
+                config.lock_hash(crate::infra::manifest::hash_file(&config.manifest_path)?),
+            );
+            config.lock_store()?.save(&lock)?;
+        }
+        for file in &files {
+            if !workflows.contains(file) {
+                workflows.push(file.clone());
+            }
+        }
+        if has_manifest {
+            files.extend([config.manifest_path.clone(), config.lock_path.clone()]);
+        }
+        git.add(&files)?;
+        git.commit(&message)?;
+    }
+
+    Ok(workflows)
+}
+
+/// The manifest changes for the actions selected by `keep`.
+fn manifest_diff_for(diff: &ManifestDiff, keep: impl Fn(&ActionId) -> bool) -> ManifestDiff {
+    ManifestDiff {
+        updated: diff
+            .updated
+            .iter()
+            .filter(|(id, _)| keep(id))
+            .cloned()
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// The lock changes for the actions selected by `keep`.
+fn lock_diff_for(diff: &LockDiff, keep: impl Fn(&ActionId) -> bool) -> LockDiff {
+    LockDiff {
+        added: diff
+            .added
+            .iter()
+            .filter(|(spec, _)| keep(&spec.id))
+            .cloned()
+            .collect(),
+        removed: diff
+            .removed
+            .iter()
+            .filter(|spec| keep(&spec.id))
+            .cloned()
+            .collect(),
+        updated: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Change, DEFAULT_TEMPLATE, batch_message, render};
+
+    fn change(action: &str) -> Change {
+        Change {
+            action: action.to_owned(),
+            from: "^4".to_owned(),
+            to: "^5".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn render_fills_placeholders() {
+        assert_eq!(
+            render(DEFAULT_TEMPLATE, &change("actions/checkout")),
+            "chore(gx): upgrade actions/checkout ^4 -> ^5"
+        );
+        assert_eq!(
+            render("deps: bump {action} to {new}", &change("actions/cache")),
+            "deps: bump actions/cache to ^5"
+        );
+    }
+
+    #[test]
+    fn batch_message_summarizes_several_actions() {
+        assert!(
+            batch_message(DEFAULT_TEMPLATE, &[change("actions/checkout")]).starts_with(
+                "chore(gx): upgrade actions/checkout ^4 -> ^5\n\n### `actions/checkout` ^4 → ^5"
+            )
+        );
+        let message = batch_message(
+            DEFAULT_TEMPLATE,
+            &[change("actions/checkout"), change("actions/cache")],
+        );
+        assert!(message.starts_with(
+            "chore(gx): upgrade 2 actions\n\n\
+             - chore(gx): upgrade actions/checkout ^4 -> ^5\n\
+             - chore(gx): upgrade actions/cache ^4 -> ^5\n\n\
+             ### `actions/checkout`"
+        ));
+        assert!(message.contains("\n\n### `actions/cache` ^4 → ^5"));
+    }
+}
@@ -1,5 +1,13 @@
+/// Execution of upgrade plans saved with `--save-plan`.
+pub mod apply;
 pub mod cli;
 /// Command orchestration for the upgrade workflow.
 pub mod command;
+/// Committing upgrade changes with a message template (`--commit`).
+pub mod commit;
 pub mod plan;
+/// Branch, commit, push, and pull request creation for `--pr`.
+pub mod pull_request;
+/// Release-note summaries shown after the upgrade table.
+pub mod release_notes;
 pub mod report;
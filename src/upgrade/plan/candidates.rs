@@ -0,0 +1,312 @@
+use super::policy::{UpgradeFilter, Verdict};
+use super::{MovedTag, UpgradeError};
+use crate::domain::action::identity::{ActionId, Version};
+use crate::domain::action::spec::Spec as ActionSpec;
+use crate::domain::action::specifier::Specifier;
+use crate::domain::action::upgrade::{
+    Candidate as UpgradeCandidate, find_locked_candidate, is_downgrade, pinned_action,
+};
+use crate::domain::lock::Lock;
+use crate::domain::manifest::Manifest;
+use crate::domain::resolution::{ActionResolver, VersionRegistry};
+use crate::upgrade::cli::{Mode as UpgradeMode, Request as UpgradeRequest, Scope as UpgradeScope};
+
+/// Result type for the `determine_upgrades` function.
+pub(super) type DetermineResult = Option<(Vec<UpgradeCandidate>, Vec<ActionSpec>)>;
+
+/// # Errors
+///
+/// Returns [`UpgradeError::ActionNotInManifest`] if the target action is not in the manifest.
+/// Returns [`UpgradeError::GroupNotInManifest`] if the target group is not declared.
+/// Returns [`UpgradeError::OwnerNotInManifest`] if no action belongs to the target owner.
+/// Returns [`UpgradeError::TagNotFound`] if the pinned version tag does not exist.
+/// Returns [`UpgradeError::TagFetchFailed`] if tags cannot be fetched from the registry.
+pub(super) fn determine_upgrades<R: VersionRegistry>(
+    manifest: &Manifest,
+    lock: &Lock,
+    service: &ActionResolver<'_, R>,
+    request: &UpgradeRequest,
+    filter: &dyn UpgradeFilter,
+    on_progress: &mut dyn FnMut(&str),
+) -> Result<DetermineResult, UpgradeError> {
+    match &request.scope {
+        UpgradeScope::Pinned(name, version) => {
+            let id = manifest.aliases().expand(name.as_str());
+            pinned_upgrade(manifest, lock, service, &id, version, on_progress).map(Some)
+        }
+        UpgradeScope::All
+        | UpgradeScope::Single(_)
+        | UpgradeScope::Group(_)
+        | UpgradeScope::Owner(_) => {
+            let specs = scoped_specs(manifest, &request.scope, on_progress)?;
+            if specs.is_empty() {
+                return Ok(None);
+            }
+
+            on_progress("Checking for upgrades...");
+            let mut upgrades = Vec::new();
+            let mut repins: Vec<ActionSpec> = Vec::new();
+
+            for spec in &specs {
+                if let Some(reason) = skip_reason(manifest, lock, request, spec) {
+                    on_progress(&format!("Skipping {spec} ({reason})"));
+                    continue;
+                }
+                if matches!(request.mode, UpgradeMode::LockOnly) {
+                    if !spec.specifier.is_sha() {
+                        on_progress(&format!("Refreshing {spec}"));
+                        repins.push((*spec).clone());
+                    }
+                    continue;
+                }
+                if spec.specifier.precision().is_none() {
+                    if spec.specifier.is_sha() {
+                        on_progress(&format!("Skipping {spec} (bare SHA)"));
+                    } else if matches!(spec.specifier, Specifier::Range { .. } | Specifier::Latest)
+                    {
+                        on_progress(&format!("Re-resolving {spec} (range)"));
+                        repins.push((*spec).clone());
+                    } else {
+                        on_progress(&format!("Re-pinning {spec} (non-semver ref)"));
+                        repins.push((*spec).clone());
+                    }
+                    continue;
+                }
+
+                match service.registry().all_tags(&spec.id) {
+                    Ok(tags) => {
+                        let allow_major = matches!(request.mode, UpgradeMode::Latest);
+                        let action = find_locked_candidate(
+                            &spec.specifier,
+                            locked_resolution(manifest, lock, spec),
+                            &tags,
+                            allow_major,
+                            manifest.allows_prerelease(&spec.id),
+                        );
+
+                        let candidate = action.map(|upgrade_action| UpgradeCandidate {
+                            id: spec.id.clone(),
+                            current: spec.specifier.clone(),
+                            action: upgrade_action,
+                        });
+                        match candidate.map(|c| filter.filter(c)) {
+                            Some(Verdict::Keep(kept)) => upgrades.push(kept),
+                            Some(Verdict::Veto(reason)) => {
+                                on_progress(&format!("Skipping {spec} ({reason})"));
+                            }
+                            None => {}
+                        }
+                    }
+                    Err(e) => {
+                        on_progress(&format!(
+                            "Warning: could not check upgrades for {spec}: {e}"
+                        ));
+                    }
+                }
+            }
+
+            if upgrades.is_empty() && repins.is_empty() {
+                return Ok(None);
+            }
+
+            Ok(Some((upgrades, repins)))
+        }
+    }
+}
+
+/// The single candidate for an explicit `ACTION@VERSION` pin, warning on downgrades.
+///
+/// # Errors
+///
+/// Returns [`UpgradeError::ActionNotInManifest`] if the action is not in the manifest.
+/// Returns [`UpgradeError::TagNotFound`] if the pinned version tag does not exist.
+/// Returns [`UpgradeError::TagFetchFailed`] if tags cannot be fetched from the registry.
+fn pinned_upgrade<R: VersionRegistry>(
+    manifest: &Manifest,
+    lock: &Lock,
+    service: &ActionResolver<'_, R>,
+    id: &ActionId,
+    version: &Version,
+    on_progress: &mut dyn FnMut(&str),
+) -> Result<(Vec<UpgradeCandidate>, Vec<ActionSpec>), UpgradeError> {
+    let current = manifest
+        .get(id)
+        .ok_or_else(|| UpgradeError::ActionNotInManifest(id.clone()))?;
+
+    match service.registry().all_tags(id) {
+        Ok(tags) => {
+            let tag_exists = tags.iter().any(|t| t.as_str() == version.as_str());
+            if !tag_exists {
+                return Err(UpgradeError::TagNotFound {
+                    id: id.clone(),
+                    version: version.clone(),
+                });
+            }
+        }
+        Err(e) => {
+            return Err(UpgradeError::TagFetchFailed {
+                id: id.clone(),
+                source: Box::new(e),
+            });
+        }
+    }
+
+    let floor = lock.get(&ActionSpec::new(id.clone(), current.clone()));
+    if let Some(entry) = floor.filter(|entry| is_downgrade(&entry.version, version)) {
+        on_progress(&format!(
+            "Warning: {id}@{version} is lower than the locked {} (downgrade)",
+            entry.version
+        ));
+    }
+
+    Ok((
+        vec![UpgradeCandidate {
+            id: id.clone(),
+            current: current.clone(),
+            action: pinned_action(current, version),
+        }],
+        vec![],
+    ))
+}
+
+/// Floating tags of the specs in scope that moved to another commit since they were locked.
+///
+/// Specs with an upgrade are left out, since the upgrade re-pins them anyway. Pinned and
+/// lock-only requests are never checked: they already resolve every tag they touch.
+pub(super) fn moved_tags<R: VersionRegistry>(
+    manifest: &Manifest,
+    lock: &Lock,
+    service: &ActionResolver<'_, R>,
+    request: &UpgradeRequest,
+    upgrades: &[UpgradeCandidate],
+    on_progress: &mut dyn FnMut(&str),
+) -> Vec<MovedTag> {
+    if matches!(request.scope, UpgradeScope::Pinned(..))
+        || matches!(request.mode, UpgradeMode::LockOnly)
+    {
+        return vec![];
+    }
+    let specs = scoped_specs(manifest, &request.scope, &mut |_| {}).unwrap_or_default();
+    let mut moved = Vec::new();
+    for spec in specs {
+        if !spec.specifier.is_floating()
+            || upgrades.iter().any(|u| u.id == spec.id)
+            || skip_reason(manifest, lock, request, spec).is_some()
+        {
+            continue;
+        }
+        let Some(entry) = lock.get(spec) else {
+            continue;
+        };
+        let tag = Version::from(spec.specifier.to_lookup_tag());
+        match service.registry().lookup_sha(&spec.id, &tag) {
+            Ok(commit) if commit.sha != entry.commit.sha => {
+                on_progress(&format!("Floating tag {tag} of {} moved", spec.id));
+                moved.push(MovedTag {
+                    spec: spec.clone(),
+                    tag,
+                    locked: entry.commit.sha.clone(),
+                    current: commit.sha,
+                });
+            }
+            Ok(_) => {}
+            Err(e) => {
+                on_progress(&format!("Warning: could not check {tag} of {spec}: {e}"));
+            }
+        }
+    }
+    moved
+}
+
+/// Why `spec` is left out of the upgrade, if it is.
+fn skip_reason(
+    manifest: &Manifest,
+    lock: &Lock,
+    request: &UpgradeRequest,
+    spec: &ActionSpec,
+) -> Option<String> {
+    manifest.hold_note(&spec.id).or_else(|| {
+        request
+            .locked_before
+            .is_some_and(|cutoff| is_fresh(lock, spec, cutoff))
+            .then(|| "locked recently, --max-age".to_owned())
+    })
+}
+
+/// Whether `spec` was locked on `cutoff` or later, going by when gx resolved it rather
+/// than when the commit was made.
+///
+/// Unlocked actions and entries without a readable date are never fresh.
+fn is_fresh(lock: &Lock, spec: &ActionSpec, cutoff: time::Date) -> bool {
+    lock.get(spec)
+        .and_then(|entry| entry.commit.locked_on())
+        .is_some_and(|day| day >= cutoff)
+}
+
+/// The specifier and version the lock resolved `spec`'s action under.
+///
+/// Normally the lock entry for `spec` itself. When there is none but the lock still holds
+/// exactly one entry for the action under a specifier the manifest no longer uses (the
+/// manifest's precision drifted), that entry is returned instead.
+fn locked_resolution<'lock>(
+    manifest: &Manifest,
+    lock: &'lock Lock,
+    spec: &'lock ActionSpec,
+) -> Option<(&'lock Specifier, &'lock Version)> {
+    if let Some(entry) = lock.get(spec) {
+        return Some((&spec.specifier, &entry.version));
+    }
+    let overrides = manifest.all_overrides().get(&spec.id);
+    let mut drifted = lock.entries().filter(|(locked, _)| {
+        locked.id == spec.id
+            && !overrides.is_some_and(|list| list.iter().any(|o| o.version == locked.specifier))
+    });
+    match (drifted.next(), drifted.next()) {
+        (Some((locked, entry)), None) => Some((&locked.specifier, &entry.version)),
+        _ => None,
+    }
+}
+
+/// The manifest specs an `All`, `Single`, `Group`, or `Owner` scope covers.
+///
+/// # Errors
+///
+/// Returns [`UpgradeError::ActionNotInManifest`] if the target action is not in the manifest.
+/// Returns [`UpgradeError::GroupNotInManifest`] if the target group is not declared.
+/// Returns [`UpgradeError::OwnerNotInManifest`] if no action belongs to the target owner.
+fn scoped_specs<'manifest>(
+    manifest: &'manifest Manifest,
+    scope: &UpgradeScope,
+    on_progress: &mut dyn FnMut(&str),
+) -> Result<Vec<&'manifest ActionSpec>, UpgradeError> {
+    let mut specs: Vec<&ActionSpec> = manifest.specs().collect();
+    match scope {
+        UpgradeScope::Single(name) => {
+            let target_id = manifest.aliases().expand(name.as_str());
+            specs.retain(|s| s.id == target_id);
+            if specs.is_empty() {
+                return Err(UpgradeError::ActionNotInManifest(target_id));
+            }
+        }
+        UpgradeScope::Group(name) => {
+            let members = manifest
+                .group(name)
+                .ok_or_else(|| UpgradeError::GroupNotInManifest(name.clone()))?;
+            for missing in members.iter().filter(|id| !manifest.has(id)) {
+                on_progress(&format!(
+                    "Skipping {missing} (group member not in manifest)"
+                ));
+            }
+            specs.retain(|s| members.contains(&s.id));
+        }
+        UpgradeScope::Owner(owner) => {
+            // GitHub owner names are case-insensitive
+            specs.retain(|s| s.id.owner().eq_ignore_ascii_case(owner));
+            if specs.is_empty() {
+                return Err(UpgradeError::OwnerNotInManifest(owner.clone()));
+            }
+        }
+        UpgradeScope::All | UpgradeScope::Pinned(..) => {}
+    }
+    Ok(specs)
+}
@@ -0,0 +1,353 @@
+use crate::domain::action::identity::{ActionId, CommitSha, Version};
+use crate::domain::action::resolved::ResolvedAction;
+use crate::domain::action::spec::Spec as ActionSpec;
+use crate::domain::action::tag_selection::ShaIndex;
+use crate::domain::action::upgrade::{Action as UpgradeAction, Candidate as UpgradeCandidate};
+use crate::domain::action::uses_ref::RefType;
+use crate::domain::diff::{LockDiff, ManifestDiff, WorkflowPatch};
+use crate::domain::lock::Lock;
+use crate::domain::manifest::Manifest;
+use crate::domain::resolution::{ActionResolver, Error as ResolutionError, VersionRegistry};
+use crate::domain::workflow::Error as WorkflowError;
+use crate::infra::workflow_update::WorkflowWriter;
+use std::path::PathBuf;
+use thiserror::Error;
+
+use super::cli::{Mode as UpgradeMode, Request as UpgradeRequest};
+use candidates::{determine_upgrades, moved_tags};
+use policy::{ManifestFilter, UpgradeFilter};
+
+/// Selection of upgrade candidates and re-pins for a request's scope.
+mod candidates;
+/// Policy hook that keeps, adjusts, or vetoes each upgrade candidate.
+pub mod policy;
+
+/// The complete plan produced by an upgrade operation.
+#[derive(Debug)]
+pub struct Plan {
+    pub manifest: ManifestDiff,
+    /// The final lock state — written by `Store::save()`.
+    pub lock: Lock,
+    /// The diff between the original and planned lock — for reporting only.
+    pub lock_changes: LockDiff,
+    pub workflows: Vec<WorkflowPatch>,
+    pub upgrades: Vec<UpgradeCandidate>,
+    /// Floating tags that moved since they were locked; re-pinned when the request asks to.
+    pub moved_tags: Vec<MovedTag>,
+}
+
+/// A floating tag (`v4` for `^4`) that now points to a different commit than the locked one.
+#[derive(Debug, Clone)]
+pub struct MovedTag {
+    pub spec: ActionSpec,
+    /// The floating tag the spec resolves through.
+    pub tag: Version,
+    /// Commit SHA stored in the lock.
+    pub locked: CommitSha,
+    /// Commit SHA the tag points to now.
+    pub current: CommitSha,
+}
+
+impl Plan {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.manifest.is_empty() && self.lock_changes.is_empty() && self.workflows.is_empty()
+    }
+
+    /// Drop the upgrade for `id` from the plan, restoring its original manifest
+    /// specifier and its entry from `lock` (the lock the plan was computed from).
+    pub fn decline(&mut self, lock: &Lock, id: &ActionId) {
+        let (declined, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.upgrades)
+            .into_iter()
+            .partition(|u| &u.id == id);
+        self.upgrades = kept;
+
+        for upgrade in &declined {
+            self.manifest.updated.retain(|(updated, _)| updated != id);
+
+            let planned = ActionSpec::new(id.clone(), upgrade.manifest_specifier().clone());
+            let keep: Vec<ActionSpec> = self
+                .lock
+                .entries()
+                .map(|(spec, _)| spec.clone())
+                .filter(|spec| spec != &planned)
+                .collect();
+            self.lock.retain(&keep);
+
+            let original = ActionSpec::new(id.clone(), upgrade.current.clone());
+            if let Some(entry) = lock.get(&original) {
+                self.lock
+                    .set(&original, entry.version.clone(), entry.commit.clone());
+            }
+        }
+        self.lock_changes = lock.diff(&self.lock);
+    }
+}
+
+/// Errors that can occur during the upgrade command.
+#[derive(Debug, Error)]
+pub enum UpgradeError {
+    /// The specified action was not found in the manifest.
+    #[error("{0} not found in manifest")]
+    ActionNotInManifest(ActionId),
+
+    /// The specified group is not declared under `[groups]` in the manifest.
+    #[error("group \"{0}\" not found in manifest")]
+    GroupNotInManifest(String),
+
+    /// No action in the manifest belongs to the specified owner.
+    #[error("no actions owned by \"{0}\" found in manifest")]
+    OwnerNotInManifest(String),
+
+    /// The specified version tag does not exist in the registry for the action.
+    #[error("{version} not found in registry for {id}")]
+    TagNotFound { id: ActionId, version: Version },
+
+    /// Could not fetch tags from the registry for the action.
+    #[error("could not fetch tags for {id}")]
+    TagFetchFailed {
+        id: ActionId,
+        #[source]
+        source: Box<ResolutionError>,
+    },
+
+    /// Workflow files could not be updated.
+    #[error(transparent)]
+    Workflow(#[from] WorkflowError),
+}
+
+/// Compute an `UpgradePlan` describing all changes without modifying the original manifest or lock.
+///
+/// # Errors
+///
+/// Returns [`UpgradeError::ActionNotInManifest`] if the target action is not in the manifest.
+/// Returns [`UpgradeError::GroupNotInManifest`] if the target group is not declared.
+/// Returns [`UpgradeError::OwnerNotInManifest`] if no action belongs to the target owner.
+/// Returns [`UpgradeError::TagNotFound`] if the pinned version tag does not exist.
+/// Returns [`UpgradeError::TagFetchFailed`] if tags cannot be fetched from the registry.
+pub fn plan<R, F: FnMut(&str)>(
+    manifest: &Manifest,
+    lock: &Lock,
+    registry: &R,
+    request: &UpgradeRequest,
+    on_progress: F,
+) -> Result<Plan, UpgradeError>
+where
+    R: VersionRegistry,
+{
+    let filter = ManifestFilter::new(manifest);
+    with_filter(manifest, lock, registry, request, &filter, on_progress)
+}
+
+/// Compute an `UpgradePlan` like [`plan`], passing every candidate through `filter`
+/// instead of the default [`ManifestFilter`].
+///
+/// # Errors
+///
+/// Returns [`UpgradeError`] in the same cases as [`plan`].
+pub fn with_filter<R, F: FnMut(&str)>(
+    manifest: &Manifest,
+    lock: &Lock,
+    registry: &R,
+    request: &UpgradeRequest,
+    filter: &dyn UpgradeFilter,
+    mut on_progress: F,
+) -> Result<Plan, UpgradeError>
+where
+    R: VersionRegistry,
+{
+    let service = ActionResolver::new(registry);
+
+    let (upgrades, repins) =
+        determine_upgrades(manifest, lock, &service, request, filter, &mut on_progress)?
+            .unwrap_or_default();
+    let moved = moved_tags(
+        manifest,
+        lock,
+        &service,
+        request,
+        &upgrades,
+        &mut on_progress,
+    );
+    if upgrades.is_empty() && repins.is_empty() && (moved.is_empty() || !request.repin) {
+        return Ok(Plan {
+            manifest: ManifestDiff::default(),
+            lock: lock.clone(),
+            lock_changes: LockDiff::default(),
+            workflows: vec![],
+            upgrades: vec![],
+            moved_tags: moved,
+        });
+    }
+
+    // Work on clones to compute the planned state
+    let mut planned_manifest = manifest.clone();
+    let mut planned_lock = lock.clone();
+
+    for upgrade in &upgrades {
+        if let UpgradeAction::CrossRange { new_specifier, .. } = &upgrade.action {
+            planned_manifest.set(upgrade.id.clone(), new_specifier.clone());
+        }
+    }
+
+    for upgrade in &upgrades {
+        let version_to_resolve = match &upgrade.action {
+            UpgradeAction::InRange { .. } => upgrade.current.clone(),
+            UpgradeAction::CrossRange { new_specifier, .. } => new_specifier.clone(),
+        };
+        let spec = ActionSpec::new(upgrade.id.clone(), version_to_resolve);
+        resolve_and_store(
+            &service,
+            &spec,
+            &mut planned_lock,
+            "Could not resolve",
+            &mut on_progress,
+        );
+    }
+
+    let mut sha_index = ShaIndex::new();
+    for spec in &repins {
+        if matches!(request.mode, UpgradeMode::LockOnly) {
+            refresh_and_store(
+                &service,
+                spec,
+                &mut planned_lock,
+                &mut sha_index,
+                &mut on_progress,
+            );
+        } else {
+            resolve_and_store(
+                &service,
+                spec,
+                &mut planned_lock,
+                "Could not re-pin",
+                &mut on_progress,
+            );
+        }
+    }
+    if request.repin {
+        for tag in &moved {
+            refresh_and_store(
+                &service,
+                &tag.spec,
+                &mut planned_lock,
+                &mut sha_index,
+                &mut on_progress,
+            );
+        }
+    }
+
+    let keys_to_retain: Vec<ActionSpec> = planned_manifest.specs().cloned().collect();
+    planned_lock.retain(&keys_to_retain);
+
+    // Diff original vs planned
+    let manifest_diff = manifest.diff(&planned_manifest);
+    let lock_diff = lock.diff(&planned_lock);
+
+    Ok(Plan {
+        manifest: manifest_diff,
+        lock: planned_lock,
+        lock_changes: lock_diff,
+        workflows: vec![], // Workflow patches computed during apply phase
+        upgrades,
+        moved_tags: moved,
+    })
+}
+
+/// Resolve an action and store the result in the upgrade plan.
+pub(super) fn resolve_and_store<R: VersionRegistry>(
+    service: &ActionResolver<'_, R>,
+    spec: &ActionSpec,
+    lock: &mut Lock,
+    unresolved_msg: &str,
+    on_progress: &mut dyn FnMut(&str),
+) {
+    match service.resolve(spec) {
+        Ok(resolved) => {
+            lock.set(spec, resolved.version, resolved.commit);
+        }
+        Err(e) => {
+            on_progress(&format!("{unresolved_msg} {spec}: {e}"));
+        }
+    }
+}
+
+/// Re-resolve a spec's SHA and store it under the most specific tag pointing at that SHA.
+///
+/// Leaves the lock untouched when the SHA has not moved, so an unchanged tag produces no diff.
+fn refresh_and_store<R: VersionRegistry>(
+    service: &ActionResolver<'_, R>,
+    spec: &ActionSpec,
+    lock: &mut Lock,
+    sha_index: &mut ShaIndex,
+    on_progress: &mut dyn FnMut(&str),
+) {
+    let resolved = match service.resolve(spec) {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            on_progress(&format!("Could not refresh {spec}: {e}"));
+            return;
+        }
+    };
+    if lock
+        .get(spec)
+        .is_some_and(|entry| entry.commit.sha == resolved.commit.sha)
+    {
+        return;
+    }
+    let refined = service
+        .resolve_from_sha(&spec.id, &resolved.commit.sha, sha_index)
+        .ok()
+        .filter(|described| described.commit.ref_type == Some(RefType::Tag))
+        .map_or(resolved.version, |described| described.version);
+    lock.set(spec, refined, resolved.commit);
+}
+
+/// Workflow pins for every lock entry added by an upgrade.
+pub(super) fn lock_pins(lock_diff: &LockDiff) -> Vec<ResolvedAction> {
+    lock_diff
+        .added
+        .iter()
+        .map(|(key, entry)| ResolvedAction {
+            id: key.id.clone(),
+            sha: entry.commit.sha.clone(),
+            version: if key.specifier.is_sha() {
+                None
+            } else {
+                Some(entry.version.clone())
+            },
+        })
+        .collect()
+}
+
+/// Apply upgrade plan's workflow updates: update all workflow files with new lock entries.
+///
+/// Returns the workflow files that changed.
+///
+/// # Errors
+///
+/// Returns [`UpgradeError::Workflow`] if workflow files cannot be updated.
+pub fn apply_upgrade_workflows(
+    writer: &WorkflowWriter,
+    lock_diff: &LockDiff,
+    upgrades: &[UpgradeCandidate],
+) -> Result<Vec<PathBuf>, UpgradeError> {
+    let pins = lock_pins(lock_diff);
+
+    if pins.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let results = writer.update_all_with_pins(&pins)?;
+
+    let _: &[UpgradeCandidate] = upgrades;
+
+    Ok(results.into_iter().map(|result| result.file).collect())
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "tests use unwrap, indexing, and other patterns freely"
+)]
+mod tests;
@@ -0,0 +1,126 @@
+use crate::domain::action::upgrade::Candidate as UpgradeCandidate;
+use crate::domain::manifest::Manifest;
+
+/// What an [`UpgradeFilter`] decided for one candidate.
+#[derive(Debug)]
+pub enum Verdict {
+    /// Apply this candidate; it may differ from the one the filter received.
+    Keep(UpgradeCandidate),
+    /// Leave the action alone, for the given reason.
+    Veto(String),
+}
+
+/// Policy that sees every upgrade candidate before it enters the plan.
+///
+/// Explicit `ACTION@VERSION` pins bypass the filter: the user named the version.
+pub trait UpgradeFilter {
+    /// Keep, adjust, or veto `candidate`.
+    fn filter(&self, candidate: UpgradeCandidate) -> Verdict;
+}
+
+/// The default filter, driven by `gx.toml`: vetoes upgrades of held actions.
+#[derive(Debug, Clone, Copy)]
+pub struct ManifestFilter<'manifest> {
+    /// The manifest whose policy is applied.
+    manifest: &'manifest Manifest,
+}
+
+impl<'manifest> ManifestFilter<'manifest> {
+    #[must_use]
+    pub fn new(manifest: &'manifest Manifest) -> Self {
+        Self { manifest }
+    }
+}
+
+impl UpgradeFilter for ManifestFilter<'_> {
+    fn filter(&self, candidate: UpgradeCandidate) -> Verdict {
+        match self.manifest.hold_note(&candidate.id) {
+            Some(note) => Verdict::Veto(note),
+            None => Verdict::Keep(candidate),
+        }
+    }
+}
+
+#[cfg(test)]
+#[expect(
+    clippy::unwrap_used,
+    reason = "tests use unwrap, indexing, and other patterns freely"
+)]
+mod tests {
+    use super::{ManifestFilter, UpgradeCandidate, UpgradeFilter, Verdict};
+    use crate::domain::action::identity::ActionId;
+    use crate::domain::action::specifier::Specifier;
+    use crate::domain::action::upgrade::Action;
+    use crate::domain::lock::Lock;
+    use crate::domain::manifest::Manifest;
+    use crate::domain::resolution::testutil::FakeRegistry;
+    use crate::upgrade::cli::{Mode as UpgradeMode, Request as UpgradeRequest, Scope};
+    use crate::upgrade::plan::with_filter;
+
+    /// Vetoes every major-version bump.
+    struct NoMajor;
+
+    impl UpgradeFilter for NoMajor {
+        fn filter(&self, candidate: UpgradeCandidate) -> Verdict {
+            match candidate.action {
+                Action::CrossRange { .. } => Verdict::Veto("major bump".to_owned()),
+                Action::InRange { .. } => Verdict::Keep(candidate),
+            }
+        }
+    }
+
+    #[test]
+    fn manifest_filter_vetoes_held_actions() {
+        let mut manifest = Manifest::default();
+        manifest.hold(ActionId::from("actions/checkout"));
+        let candidate = |id: &str| UpgradeCandidate {
+            id: ActionId::from(id),
+            current: Specifier::parse("^4"),
+            action: Action::InRange {
+                candidate: "v4.1.0".into(),
+            },
+        };
+
+        let filter = ManifestFilter::new(&manifest);
+        assert!(matches!(
+            filter.filter(candidate("actions/checkout")),
+            Verdict::Veto(reason) if reason == "held in gx.toml"
+        ));
+        assert!(matches!(
+            filter.filter(candidate("actions/cache")),
+            Verdict::Keep(kept) if kept.id == ActionId::from("actions/cache")
+        ));
+    }
+
+    #[test]
+    fn with_filter_drops_vetoed_candidates() {
+        let mut manifest = Manifest::default();
+        manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+        manifest.set(ActionId::from("actions/cache"), Specifier::parse("^4"));
+
+        let registry = FakeRegistry::new()
+            .with_all_tags("actions/checkout", vec!["v4.0.0", "v5.0.0"])
+            .with_all_tags("actions/cache", vec!["v4.0.0", "v4.1.0"]);
+        let request = UpgradeRequest::new(UpgradeMode::Latest, Scope::All);
+
+        let mut messages = Vec::new();
+        let result = with_filter(
+            &manifest,
+            &Lock::default(),
+            &registry,
+            &request,
+            &NoMajor,
+            |message| messages.push(message.to_owned()),
+        )
+        .unwrap();
+
+        let upgraded: Vec<String> = result.upgrades.iter().map(|u| u.id.to_string()).collect();
+        assert_eq!(upgraded, vec!["actions/cache"]);
+        assert!(
+            messages
+                .iter()
+                .any(|m| m == "Skipping actions/checkout@^4 (major bump)"),
+            "{messages:?}"
+        );
+    }
+}
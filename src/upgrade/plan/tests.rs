@@ -0,0 +1,546 @@
+// Unit tests for upgrade planning — exercises plan() against a fake registry
+
+use super::{Lock, Manifest, UpgradeError, UpgradeMode, UpgradeRequest, plan};
+use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
+use crate::domain::action::resolved::{Commit, Provenance};
+use crate::domain::action::spec::Spec as ActionSpec;
+use crate::domain::action::specifier::Specifier;
+use crate::domain::action::uses_ref::RefType;
+use crate::domain::resolution::testutil::FakeRegistry;
+use crate::upgrade::cli::Scope as UpgradeScope;
+
+#[test]
+fn plan_no_upgradable_actions_returns_empty() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+
+    let mut lock = Lock::default();
+    lock.set(
+        &ActionSpec::new(ActionId::from("actions/checkout"), Specifier::parse("^4")),
+        Version::from("v4"),
+        Commit {
+            sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            repository: Repository::from("actions/checkout"),
+            ref_type: Some(RefType::Tag),
+            date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
+        },
+    );
+
+    // Registry returns no tags → nothing to upgrade
+    let registry = FakeRegistry::new();
+    let request = UpgradeRequest::new(UpgradeMode::Safe, UpgradeScope::All);
+
+    let result = plan(&manifest, &lock, &registry, &request, |_| {}).unwrap();
+    assert!(
+        result.is_empty(),
+        "Plan with no upgradable actions must be empty"
+    );
+}
+
+#[test]
+fn plan_one_upgradable_action_produces_diffs() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+
+    let mut lock = Lock::default();
+    lock.set(
+        &ActionSpec::new(ActionId::from("actions/checkout"), Specifier::parse("^4")),
+        Version::from("v4"),
+        Commit {
+            sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            repository: Repository::from("actions/checkout"),
+            ref_type: Some(RefType::Tag),
+            date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
+        },
+    );
+    lock.set_version(
+        &ActionSpec::new(ActionId::from("actions/checkout"), Specifier::parse("^4")),
+        Some("v4.1.0".to_owned()),
+    );
+
+    // Registry has v4.2.0 available (in-range upgrade from v4)
+    let registry =
+        FakeRegistry::new().with_all_tags("actions/checkout", vec!["v4", "v4.1.0", "v4.2.0"]);
+
+    let request = UpgradeRequest::new(UpgradeMode::Safe, UpgradeScope::All);
+
+    let result = plan(&manifest, &lock, &registry, &request, |_| {}).unwrap();
+
+    // Should have upgrade candidate
+    assert!(
+        !result.upgrades.is_empty(),
+        "Plan must include upgrade candidates, got none"
+    );
+
+    // Lock changes should have a new entry for the upgraded version
+    assert!(
+        !result.lock_changes.added.is_empty(),
+        "Plan must include lock additions for resolved upgrade, got: {:?}",
+        result.lock_changes
+    );
+}
+
+#[test]
+fn plan_latest_mode_produces_major_version_bump() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^3"));
+
+    let mut lock = Lock::default();
+    lock.set(
+        &ActionSpec::new(ActionId::from("actions/checkout"), Specifier::parse("^3")),
+        Version::from("v3"),
+        Commit {
+            sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            repository: Repository::from("actions/checkout"),
+            ref_type: Some(RefType::Tag),
+            date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
+        },
+    );
+    lock.set_version(
+        &ActionSpec::new(ActionId::from("actions/checkout"), Specifier::parse("^3")),
+        Some("v3.0.0".to_owned()),
+    );
+
+    // Registry has v4 available (cross-range)
+    let registry =
+        FakeRegistry::new().with_all_tags("actions/checkout", vec!["v3", "v3.0.0", "v4", "v4.0.0"]);
+
+    let request = UpgradeRequest::new(UpgradeMode::Latest, UpgradeScope::All);
+
+    let result = plan(&manifest, &lock, &registry, &request, |_| {}).unwrap();
+
+    // Should have upgrade candidates
+    assert!(
+        !result.upgrades.is_empty(),
+        "Latest mode plan must include upgrade candidates"
+    );
+
+    // Manifest should show the version change (^3 → ^4)
+    let has_manifest_change =
+        result.manifest.updated.iter().any(|(id, v)| {
+            id == &ActionId::from("actions/checkout") && v == &Specifier::parse("^4")
+        });
+    assert!(
+        has_manifest_change,
+        "Latest mode plan must include manifest version bump to v4, got: {:?}",
+        result.manifest.updated
+    );
+}
+
+#[test]
+fn declined_cross_range_upgrade_leaves_plan_empty() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^3"));
+
+    let mut lock = Lock::default();
+    lock.set(
+        &ActionSpec::new(ActionId::from("actions/checkout"), Specifier::parse("^3")),
+        Version::from("v3.0.0"),
+        Commit {
+            sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            repository: Repository::from("actions/checkout"),
+            ref_type: Some(RefType::Tag),
+            date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
+        },
+    );
+
+    let registry =
+        FakeRegistry::new().with_all_tags("actions/checkout", vec!["v3", "v3.0.0", "v4", "v4.0.0"]);
+    let request = UpgradeRequest::new(UpgradeMode::Latest, UpgradeScope::All);
+
+    let mut result = plan(&manifest, &lock, &registry, &request, |_| {}).unwrap();
+    assert!(!result.is_empty());
+
+    result.decline(&lock, &ActionId::from("actions/checkout"));
+    assert!(result.upgrades.is_empty());
+    assert!(
+        result.is_empty(),
+        "declining the only upgrade must leave nothing to apply: {result:?}"
+    );
+    assert!(
+        result
+            .lock
+            .get(&ActionSpec::new(
+                ActionId::from("actions/checkout"),
+                Specifier::parse("^3")
+            ))
+            .is_some(),
+        "original lock entry must be kept"
+    );
+}
+
+#[test]
+fn lock_only_refreshes_moved_tag_without_touching_manifest() {
+    let id = ActionId::from("actions/checkout");
+    let mut manifest = Manifest::default();
+    manifest.set(id.clone(), Specifier::parse("^4"));
+
+    let spec = ActionSpec::new(id, Specifier::parse("^4"));
+    let mut lock = Lock::default();
+    lock.set(
+        &spec,
+        Version::from("v4"),
+        Commit {
+            sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            repository: Repository::from("actions/checkout"),
+            ref_type: Some(RefType::Tag),
+            date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
+        },
+    );
+
+    // v4 now points at a new commit, and v5 exists but must not be picked up
+    let registry = FakeRegistry::new().with_sha_tags(
+        "actions/checkout",
+        "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+        vec!["v4", "v4.2.0", "v5"],
+    );
+    let request = UpgradeRequest::new(UpgradeMode::LockOnly, UpgradeScope::All);
+
+    let result = plan(&manifest, &lock, &registry, &request, |_| {}).unwrap();
+
+    assert!(result.upgrades.is_empty(), "lock-only never upgrades");
+    assert!(
+        result.manifest.updated.is_empty(),
+        "manifest must not change"
+    );
+    assert_eq!(
+        result.lock.get(&spec).unwrap().commit.sha,
+        CommitSha::from("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb")
+    );
+}
+
+#[test]
+fn lock_only_leaves_unmoved_tag_alone() {
+    let id = ActionId::from("actions/checkout");
+    let mut manifest = Manifest::default();
+    manifest.set(id.clone(), Specifier::parse("^4"));
+
+    let sha = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    let mut lock = Lock::default();
+    lock.set(
+        &ActionSpec::new(id, Specifier::parse("^4")),
+        Version::from("v4"),
+        Commit {
+            sha: CommitSha::from(sha),
+            repository: Repository::from("actions/checkout"),
+            ref_type: Some(RefType::Tag),
+            date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
+        },
+    );
+
+    let registry = FakeRegistry::new().with_sha_tags("actions/checkout", sha, vec!["v4", "v5"]);
+    let request = UpgradeRequest::new(UpgradeMode::LockOnly, UpgradeScope::All);
+
+    let result = plan(&manifest, &lock, &registry, &request, |_| {}).unwrap();
+    assert!(
+        result.is_empty(),
+        "unchanged SHA must produce an empty plan"
+    );
+}
+
+#[test]
+fn group_scope_only_upgrades_group_members() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    manifest.set(
+        ActionId::from("softprops/action-gh-release"),
+        Specifier::parse("^2"),
+    );
+    manifest.set_group(
+        "release".to_owned(),
+        vec![ActionId::from("softprops/action-gh-release")],
+    );
+
+    let registry = FakeRegistry::new()
+        .with_all_tags("actions/checkout", vec!["v4", "v4.2.0"])
+        .with_all_tags("softprops/action-gh-release", vec!["v2", "v2.1.0"]);
+    let request = UpgradeRequest::new(UpgradeMode::Safe, UpgradeScope::Group("release".to_owned()));
+
+    let result = plan(&manifest, &Lock::default(), &registry, &request, |_| {}).unwrap();
+    let ids: Vec<&str> = result.upgrades.iter().map(|u| u.id.as_str()).collect();
+    assert_eq!(ids, vec!["softprops/action-gh-release"]);
+}
+
+#[test]
+fn single_scope_accepts_an_alias() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    manifest.set(ActionId::from("actions/cache"), Specifier::parse("^4"));
+    manifest.set_aliases([("checkout", "actions/checkout")].into_iter().collect());
+    let registry = FakeRegistry::new()
+        .with_all_tags("actions/checkout", vec!["v4", "v4.2.0"])
+        .with_all_tags("actions/cache", vec!["v4", "v4.1.0"]);
+    let request = UpgradeRequest::new(UpgradeMode::Safe, UpgradeScope::Single("checkout".into()));
+
+    let result = plan(&manifest, &Lock::default(), &registry, &request, |_| {}).unwrap();
+    let ids: Vec<&str> = result.upgrades.iter().map(|u| u.id.as_str()).collect();
+    assert_eq!(ids, vec!["actions/checkout"]);
+}
+
+#[test]
+fn group_scope_rejects_unknown_group() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+
+    let request = UpgradeRequest::new(UpgradeMode::Safe, UpgradeScope::Group("nope".to_owned()));
+    let err = plan(
+        &manifest,
+        &Lock::default(),
+        &FakeRegistry::new(),
+        &request,
+        |_| {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, UpgradeError::GroupNotInManifest(name) if name == "nope"));
+}
+
+#[test]
+fn pinned_downgrade_updates_manifest_and_prunes_higher_lock_entry() {
+    let id = ActionId::from("actions/checkout");
+    let mut manifest = Manifest::default();
+    manifest.set(id.clone(), Specifier::parse("^4"));
+
+    let old_spec = ActionSpec::new(id.clone(), Specifier::parse("^4"));
+    let mut lock = Lock::default();
+    lock.set(
+        &old_spec,
+        Version::from("v4.2.0"),
+        Commit {
+            sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            repository: Repository::from("actions/checkout"),
+            ref_type: Some(RefType::Tag),
+            date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
+        },
+    );
+
+    let registry =
+        FakeRegistry::new().with_all_tags("actions/checkout", vec!["v3", "v4", "v4.2.0"]);
+    let request = UpgradeRequest::new(
+        UpgradeMode::Safe,
+        UpgradeScope::Pinned(id.clone(), Version::from("v3")),
+    );
+
+    let mut messages = Vec::new();
+    let result = plan(&manifest, &lock, &registry, &request, |msg| {
+        messages.push(msg.to_owned());
+    })
+    .unwrap();
+
+    assert!(
+        messages.iter().any(|m| m.contains("downgrade")),
+        "expected a downgrade warning, got: {messages:?}"
+    );
+    assert_eq!(
+        result.manifest.updated,
+        vec![(id.clone(), Specifier::parse("^3"))]
+    );
+    assert!(
+        result.lock.get(&old_spec).is_none(),
+        "higher ^4 entry must be pruned"
+    );
+    assert!(
+        result
+            .lock
+            .get(&ActionSpec::new(id, Specifier::parse("^3")))
+            .is_some()
+    );
+}
+
+#[test]
+fn safe_upgrade_stays_within_drifted_lock_specifier() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+
+    // The lock was resolved while the manifest still said ~4.1.0
+    let mut lock = Lock::default();
+    lock.set(
+        &ActionSpec::new(
+            ActionId::from("actions/checkout"),
+            Specifier::parse("~4.1.0"),
+        ),
+        Version::from("v4.1.0"),
+        Commit {
+            sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            repository: Repository::from("actions/checkout"),
+            ref_type: Some(RefType::Tag),
+            date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
+        },
+    );
+
+    let registry =
+        FakeRegistry::new().with_all_tags("actions/checkout", vec!["v4.1.0", "v4.1.3", "v4.6.0"]);
+    let request = UpgradeRequest::new(UpgradeMode::Safe, UpgradeScope::All);
+
+    let result = plan(&manifest, &lock, &registry, &request, |_| {}).unwrap();
+
+    let candidates: Vec<&Version> = result
+        .upgrades
+        .iter()
+        .map(crate::domain::action::upgrade::Candidate::candidate)
+        .collect();
+    assert_eq!(candidates, vec![&Version::from("v4.1.3")]);
+}
+
+#[test]
+fn max_age_skips_recently_locked_actions() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    manifest.set(ActionId::from("actions/cache"), Specifier::parse("^4"));
+
+    let mut lock = Lock::default();
+    for (action, date) in [
+        ("actions/checkout", "2026-01-01T00:00:00Z"),
+        ("actions/cache", "2026-06-01T00:00:00Z"),
+    ] {
+        lock.set(
+            &ActionSpec::new(ActionId::from(action), Specifier::parse("^4")),
+            Version::from("v4.0.0"),
+            Commit {
+                sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                repository: Repository::from(action),
+                ref_type: Some(RefType::Tag),
+                date: CommitDate::from(date),
+                provenance: Provenance::default(),
+            },
+        );
+    }
+
+    let registry = FakeRegistry::new()
+        .with_all_tags("actions/checkout", vec!["v4.0.0", "v4.1.0"])
+        .with_all_tags("actions/cache", vec!["v4.0.0", "v4.1.0"]);
+    let mut request = UpgradeRequest::new(UpgradeMode::Safe, UpgradeScope::All);
+    request.locked_before = time::Date::from_calendar_date(2026, time::Month::March, 1).ok();
+
+    let result = plan(&manifest, &lock, &registry, &request, |_| {}).unwrap();
+
+    let upgraded: Vec<String> = result.upgrades.iter().map(|u| u.id.to_string()).collect();
+    assert_eq!(upgraded, vec!["actions/checkout"]);
+}
+
+#[test]
+fn held_action_is_never_upgraded() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^3"));
+    manifest.hold(ActionId::from("actions/checkout"));
+
+    let registry = FakeRegistry::new().with_all_tags("actions/checkout", vec!["v3.0.0", "v4.0.0"]);
+    let request = UpgradeRequest::new(UpgradeMode::Latest, UpgradeScope::All);
+
+    let result = plan(&manifest, &Lock::default(), &registry, &request, |_| {}).unwrap();
+
+    assert!(result.is_empty(), "held action must be skipped: {result:?}");
+}
+
+#[test]
+fn prerelease_candidates_only_for_opted_in_actions() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    manifest.set(ActionId::from("actions/cache"), Specifier::parse("^4"));
+    manifest.allow_prerelease(ActionId::from("actions/checkout"));
+
+    let registry = FakeRegistry::new()
+        .with_all_tags("actions/checkout", vec!["v4.0.0", "v4.1.0-beta.1"])
+        .with_all_tags("actions/cache", vec!["v4.0.0", "v4.1.0-beta.1"]);
+    let request = UpgradeRequest::new(UpgradeMode::Safe, UpgradeScope::All);
+
+    let result = plan(&manifest, &Lock::default(), &registry, &request, |_| {}).unwrap();
+
+    let upgraded: Vec<String> = result
+        .upgrades
+        .iter()
+        .map(|u| format!("{} {}", u.id, u.candidate()))
+        .collect();
+    assert_eq!(upgraded, vec!["actions/checkout v4.1.0-beta.1"]);
+}
+
+#[test]
+fn moved_floating_tag_is_reported_and_repinned_on_request() {
+    let spec = ActionSpec::new(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    let mut manifest = Manifest::default();
+    manifest.set(spec.id.clone(), spec.specifier.clone());
+    let mut lock = Lock::default();
+    lock.set(
+        &spec,
+        Version::from("v4.1.0"),
+        Commit {
+            sha: CommitSha::from("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+            repository: Repository::from("actions/checkout"),
+            ref_type: Some(RefType::Tag),
+            date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
+        },
+    );
+    // v4 was force-pushed to a new commit without a new version
+    let moved_sha = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+    let registry =
+        FakeRegistry::new().with_sha_tags("actions/checkout", moved_sha, vec!["v4", "v4.1.0"]);
+
+    let mut request = UpgradeRequest::new(UpgradeMode::Safe, UpgradeScope::All);
+    let reported = plan(&manifest, &lock, &registry, &request, |_| {}).unwrap();
+    assert!(reported.is_empty(), "without --repin the lock is untouched");
+    let moved: Vec<String> = reported
+        .moved_tags
+        .iter()
+        .map(|m| format!("{} {} {}", m.tag, m.locked, m.current))
+        .collect();
+    assert_eq!(
+        moved,
+        vec![format!(
+            "v4 aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa {moved_sha}"
+        )]
+    );
+
+    request.repin = true;
+    let repinned = plan(&manifest, &lock, &registry, &request, |_| {}).unwrap();
+    let entry = repinned.lock.get(&spec).unwrap();
+    assert_eq!(entry.commit.sha, CommitSha::from(moved_sha));
+    assert_eq!(entry.version, Version::from("v4.1.0"));
+    assert!(repinned.upgrades.is_empty());
+}
+
+#[test]
+fn owner_scope_upgrades_only_that_owners_actions() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+    manifest.set(ActionId::from("Actions/cache"), Specifier::parse("^4"));
+    manifest.set(
+        ActionId::from("softprops/action-gh-release"),
+        Specifier::parse("^2"),
+    );
+
+    let registry = FakeRegistry::new()
+        .with_all_tags("actions/checkout", vec!["v4", "v4.2.0"])
+        .with_all_tags("Actions/cache", vec!["v4", "v4.1.0"])
+        .with_all_tags("softprops/action-gh-release", vec!["v2", "v2.1.0"]);
+    let request = UpgradeRequest::new(UpgradeMode::Safe, UpgradeScope::Owner("actions".to_owned()));
+
+    let result = plan(&manifest, &Lock::default(), &registry, &request, |_| {}).unwrap();
+    let mut ids: Vec<&str> = result.upgrades.iter().map(|u| u.id.as_str()).collect();
+    ids.sort_unstable();
+    assert_eq!(ids, vec!["Actions/cache", "actions/checkout"]);
+}
+
+#[test]
+fn owner_scope_rejects_owner_without_actions() {
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::parse("^4"));
+
+    let request = UpgradeRequest::new(UpgradeMode::Safe, UpgradeScope::Owner("nope".to_owned()));
+    let err = plan(
+        &manifest,
+        &Lock::default(),
+        &FakeRegistry::new(),
+        &request,
+        |_| {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, UpgradeError::OwnerNotInManifest(owner) if owner == "nope"));
+}
@@ -0,0 +1,198 @@
+use std::path::{Path, PathBuf};
+
+use super::command::RunError;
+use crate::infra::git::Git;
+use crate::infra::github::{PullRequest, Registry};
+
+/// Remote that upgrade branches are pushed to.
+const REMOTE: &str = "origin";
+
+/// One upgraded action as described in commit messages and pull requests.
+#[derive(Debug, Default)]
+pub struct Change {
+    pub action: String,
+    /// Manifest specifier before the upgrade.
+    pub from: String,
+    /// Manifest specifier or pinned version after the upgrade.
+    pub to: String,
+    /// Locked version and commit SHA before the upgrade, when the action was locked.
+    pub old_version: Option<String>,
+    pub old_sha: Option<String>,
+    /// Locked version and commit SHA after the upgrade.
+    pub new_version: Option<String>,
+    pub new_sha: Option<String>,
+    /// Link to the diff between the previously locked and the new version, when known.
+    pub compare_url: Option<String>,
+    /// Leading lines of the new version's release notes.
+    pub release_notes: Vec<String>,
+    /// Whether the upgrade moved the action to a new major range.
+    pub crosses_major: bool,
+}
+
+/// Commit `files` on a new branch, push it, and open a pull request describing `changes`.
+///
+/// Returns the pull request's web URL.
+///
+/// # Errors
+///
+/// Returns [`RunError::Git`] if the branch cannot be created, committed, or pushed.
+/// Returns [`RunError::Github`] if the pull request cannot be opened.
+pub fn open(
+    repo_root: &Path,
+    registry: &Registry,
+    files: &[PathBuf],
+    changes: &[Change],
+) -> Result<String, RunError> {
+    let git = Git::new(repo_root);
+    let repository = git.github_repository(REMOTE)?;
+    let base = git.current_branch()?;
+    let branch = format!(
+        "gx/upgrade-{}",
+        time::OffsetDateTime::now_utc().unix_timestamp()
+    );
+    let title = title(changes);
+
+    git.checkout_new_branch(&branch)?;
+    git.add(files)?;
+    git.commit(&title)?;
+    git.push(REMOTE, &branch)?;
+
+    let url = registry.create_pull_request(
+        &repository,
+        &PullRequest {
+            title,
+            head: branch,
+            base,
+            body: body(changes),
+        },
+    )?;
+    Ok(url)
+}
+
+/// Pull request title and commit subject: names the action when there is only one.
+fn title(changes: &[Change]) -> String {
+    match changes {
+        [change] => format!("Upgrade {} to {}", change.action, change.to),
+        _ => format!("Upgrade {} GitHub Actions", changes.len()),
+    }
+}
+
+/// Pull request description: the changelog of every upgrade.
+fn body(changes: &[Change]) -> String {
+    format!("Upgraded by `gx upgrade --pr`.\n\n{}\n", changelog(changes))
+}
+
+/// Markdown changelog with one section per upgraded action: versions, SHAs, compare
+/// link, and a release-notes excerpt. Shared by pull request bodies and commit messages.
+#[must_use]
+pub fn changelog(changes: &[Change]) -> String {
+    let sections: Vec<String> = changes.iter().map(section).collect();
+    sections.join("\n\n")
+}
+
+/// The changelog section for one upgraded action.
+fn section(change: &Change) -> String {
+    let old = change.old_version.as_deref().unwrap_or(&change.from);
+    let new = change.new_version.as_deref().unwrap_or(&change.to);
+    let major = if change.crosses_major { " (major)" } else { "" };
+    let mut lines = vec![
+        format!("### `{}` {old} → {new}{major}", change.action),
+        String::new(),
+        format!("- Specifier: `{}` → `{}`", change.from, change.to),
+    ];
+    if let Some(new_sha) = &change.new_sha {
+        let old_sha = change.old_sha.as_deref().unwrap_or("unlocked");
+        lines.push(format!("- SHA: `{old_sha}` → `{new_sha}`"));
+    }
+    if let Some(url) = &change.compare_url {
+        lines.push(format!("- Compare: {url}"));
+    }
+    if !change.release_notes.is_empty() {
+        lines.push(String::new());
+        lines.extend(change.release_notes.iter().map(|line| format!("> {line}")));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Change, body, changelog, title};
+
+    fn change(action: &str, compare_url: Option<&str>) -> Change {
+        Change {
+            action: action.to_owned(),
+            from: "^4".to_owned(),
+            to: "v4.2.0".to_owned(),
+            compare_url: compare_url.map(str::to_owned),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn title_names_a_single_action() {
+        assert_eq!(
+            title(&[change("actions/checkout", None)]),
+            "Upgrade actions/checkout to v4.2.0"
+        );
+        assert_eq!(
+            title(&[
+                change("actions/checkout", None),
+                change("actions/cache", None)
+            ]),
+            "Upgrade 2 GitHub Actions"
+        );
+    }
+
+    #[test]
+    fn body_lists_upgrades_with_compare_links() {
+        let text = body(&[
+            change(
+                "actions/checkout",
+                Some("https://github.com/actions/checkout/compare/v4.1.0...v4.2.0"),
+            ),
+            change("actions/cache", None),
+        ]);
+        assert!(text.starts_with("Upgraded by `gx upgrade --pr`.\n\n### `actions/checkout`"));
+        assert!(
+            text.contains(
+                "- Compare: https://github.com/actions/checkout/compare/v4.1.0...v4.2.0\n"
+            )
+        );
+        assert!(text.contains("### `actions/cache` ^4 → v4.2.0\n"));
+    }
+
+    #[test]
+    fn changelog_lists_versions_shas_and_release_notes() {
+        let text = changelog(&[Change {
+            action: "actions/checkout".to_owned(),
+            from: "^4".to_owned(),
+            to: "^5".to_owned(),
+            old_version: Some("v4.1.0".to_owned()),
+            old_sha: Some("aaa".to_owned()),
+            new_version: Some("v5.0.0".to_owned()),
+            new_sha: Some("bbb".to_owned()),
+            compare_url: Some("https://example.com/compare".to_owned()),
+            release_notes: vec!["## What's changed".to_owned(), "* Node 24".to_owned()],
+            crosses_major: true,
+        }]);
+        assert_eq!(
+            text,
+            "### `actions/checkout` v4.1.0 → v5.0.0 (major)\n\n\
+             - Specifier: `^4` → `^5`\n\
+             - SHA: `aaa` → `bbb`\n\
+             - Compare: https://example.com/compare\n\n\
+             > ## What's changed\n\
+             > * Node 24"
+        );
+    }
+
+    #[test]
+    fn changelog_marks_previously_unlocked_sha() {
+        let text = changelog(&[Change {
+            new_sha: Some("bbb".to_owned()),
+            ..change("actions/cache", None)
+        }]);
+        assert!(text.contains("- SHA: `unlocked` → `bbb`"));
+        assert!(!text.contains("(major)"));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::domain::action::identity::{ActionId, Version};
+
+/// Maximum number of release-note lines shown under each upgraded action.
+const MAX_LINES: usize = 5;
+
+/// Trim a release body to its first `MAX_LINES` non-blank lines, then append the compare URL.
+///
+/// A trailing `…` marks notes that were cut short.
+#[must_use]
+pub fn summarize(body: &str, compare_url: Option<String>) -> Vec<String> {
+    let mut non_blank = body.lines().map(str::trim).filter(|line| !line.is_empty());
+    let mut lines: Vec<String> = non_blank
+        .by_ref()
+        .take(MAX_LINES)
+        .map(str::to_owned)
+        .collect();
+    if non_blank.next().is_some() {
+        lines.push("…".to_owned());
+    }
+    lines.extend(compare_url);
+    lines
+}
+
+/// GitHub compare URL between the previously locked tag and the upgrade candidate, on the
+/// host whose web root is `web_url`.
+#[must_use]
+pub fn compare_url(web_url: &str, id: &ActionId, from: &Version, to: &Version) -> String {
+    format!(
+        "{web_url}/{}/compare/{}...{}",
+        id.base_repo(),
+        from.as_str(),
+        to.as_str()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ActionId, Version, compare_url, summarize};
+
+    #[test]
+    fn summarize_keeps_short_notes_and_appends_url() {
+        let lines = summarize(
+            "## What's changed\n\n* Fix checkout of tags\n",
+            Some("https://example.com".to_owned()),
+        );
+        assert_eq!(
+            lines,
+            vec![
+                "## What's changed",
+                "* Fix checkout of tags",
+                "https://example.com"
+            ]
+        );
+    }
+
+    #[test]
+    fn summarize_truncates_long_notes() {
+        let body = "1\n2\n3\n4\n5\n6\n7";
+        let lines = summarize(body, None);
+        assert_eq!(lines, vec!["1", "2", "3", "4", "5", "…"]);
+    }
+
+    #[test]
+    fn compare_url_uses_base_repo() {
+        let url = compare_url(
+            "https://github.com",
+            &ActionId::from("github/codeql-action/upload-sarif"),
+            &Version::from("v3.1.0"),
+            &Version::from("v3.2.0"),
+        );
+        assert_eq!(
+            url,
+            "https://github.com/github/codeql-action/compare/v3.1.0...v3.2.0"
+        );
+    }
+
+    #[test]
+    fn compare_url_links_to_the_enterprise_server() {
+        let url = compare_url(
+            "https://ghes.example.com",
+            &ActionId::from("my-org/deploy"),
+            &Version::from("v1.0.0"),
+            &Version::from("v1.1.0"),
+        );
+        assert_eq!(
+            url,
+            "https://ghes.example.com/my-org/deploy/compare/v1.0.0...v1.1.0"
+        );
+    }
+}
@@ -1,22 +1,147 @@
 use crate::command::CommandReport;
+use crate::output::events::Event;
 use crate::output::lines::Line as OutputLine;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Column headers of the upgrade summary table.
+const HEADERS: [&str; 5] = ["ACTION", "CURRENT", "CANDIDATE", "RANGE", "SHA"];
+
+/// Length of the SHA prefix shown in the summary table.
+const SHA_PREFIX: usize = 7;
+
+/// Exit code of a run that changed files, or would have with `--save-plan`.
+pub const CHANGED_EXIT_CODE: i32 = 2;
+
+/// One upgraded action: a row of the summary table and an entry of `--format json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Row {
+    pub action: String,
+    /// Locked version before the upgrade, or the manifest specifier when it was not locked.
+    pub current: String,
+    /// Version the action is upgraded to.
+    pub candidate: String,
+    /// Manifest specifier after the upgrade.
+    pub range: String,
+    /// Commit SHA the action is locked to after the upgrade.
+    pub sha: Option<String>,
+}
+
+impl Row {
+    /// Table cells, with the SHA shortened to its prefix.
+    fn cells(&self) -> Vec<String> {
+        let sha = self
+            .sha
+            .as_deref()
+            .map_or("-", |sha| sha.get(..SHA_PREFIX).unwrap_or(sha));
+        vec![
+            self.action.clone(),
+            self.current.clone(),
+            self.candidate.clone(),
+            self.range.clone(),
+            sha.to_owned(),
+        ]
+    }
+}
+
+/// A floating tag that points to a different commit than the lock.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Moved {
+    pub action: String,
+    pub tag: String,
+    /// Commit SHA stored in the lock.
+    pub locked: String,
+    /// Commit SHA the tag points to now.
+    pub current: String,
+    /// Whether the lock and workflows were re-pinned to the new commit (`--repin`).
+    pub repinned: bool,
+}
+
+impl Moved {
+    /// One-line description of the move.
+    fn detail(&self) -> String {
+        let short = |sha: &str| sha.get(..SHA_PREFIX).unwrap_or(sha).to_owned();
+        let outcome = if self.repinned {
+            "re-pinned"
+        } else {
+            "run with --repin to update"
+        };
+        format!(
+            "tag {} moved {} → {} ({outcome})",
+            self.tag,
+            short(&self.locked),
+            short(&self.current)
+        )
+    }
+}
 
 /// Report from the upgrade command.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize)]
 pub struct Report {
-    /// Actions that were upgraded: (action, `from_version`, `to_version`).
-    pub upgrades: Vec<(String, String, String)>,
+    /// Actions that were upgraded.
+    pub upgrades: Vec<Row>,
+    /// Release-note summaries keyed by upgraded action: (action, lines).
+    pub release_notes: Vec<(String, Vec<String>)>,
     /// Actions that were skipped: (action, reason).
     pub skipped: Vec<(String, String)>,
+    /// Floating tags that moved since they were locked.
+    pub moved_tags: Vec<Moved>,
     /// Warnings encountered during upgrade.
     pub warnings: Vec<String>,
-    /// Number of workflow files updated.
-    pub workflows_updated: usize,
+    /// The workflow files that were updated.
+    pub workflows: Vec<PathBuf>,
     /// True if everything was already up to date.
     pub up_to_date: bool,
+    /// True if the upgrade changed files, or the saved plan would.
+    pub changed: bool,
+    /// Where the plan was saved instead of being applied (`--save-plan`).
+    pub saved_plan: Option<PathBuf>,
+    /// URL of the pull request opened for the upgrade (`--pr`).
+    pub pull_request: Option<String>,
 }
 
 impl CommandReport for Report {
+    /// `0` when nothing needed to change, [`CHANGED_EXIT_CODE`] when something did.
+    fn exit_code(&self) -> i32 {
+        if self.changed { CHANGED_EXIT_CODE } else { 0 }
+    }
+
+    fn events(&self) -> Vec<Event> {
+        let mut events = Vec::new();
+        for row in &self.upgrades {
+            if let Some(sha) = &row.sha {
+                events.push(Event::Resolved {
+                    action: row.action.clone(),
+                    specifier: row.range.clone(),
+                    version: row.candidate.clone(),
+                    sha: sha.clone(),
+                });
+            }
+            events.push(Event::Upgraded {
+                action: row.action.clone(),
+                from: row.current.clone(),
+                to: row.candidate.clone(),
+            });
+        }
+        events.extend(self.moved_tags.iter().map(|moved| Event::TagMoved {
+            action: moved.action.clone(),
+            tag: moved.tag.clone(),
+            locked: moved.locked.clone(),
+            current: moved.current.clone(),
+            repinned: moved.repinned,
+        }));
+        events.extend(self.skipped.iter().map(|(action, reason)| Event::Skipped {
+            action: action.clone(),
+            reason: reason.clone(),
+        }));
+        events.extend(
+            self.workflows
+                .iter()
+                .map(|path| Event::FileUpdated { path: path.clone() }),
+        );
+        events
+    }
+
     fn render(&self) -> Vec<OutputLine> {
         if self.up_to_date {
             return vec![OutputLine::Summary {
@@ -24,7 +149,11 @@ impl CommandReport for Report {
             }];
         }
 
-        if self.upgrades.is_empty() && self.skipped.is_empty() && self.warnings.is_empty() {
+        if self.upgrades.is_empty()
+            && self.skipped.is_empty()
+            && self.moved_tags.is_empty()
+            && self.warnings.is_empty()
+        {
             return vec![OutputLine::Summary {
                 text: "All actions up to date".to_owned(),
             }];
@@ -32,11 +161,29 @@ impl CommandReport for Report {
 
         let mut lines = Vec::new();
 
-        for (action, from, to) in &self.upgrades {
-            lines.push(OutputLine::Upgraded {
-                action: action.clone(),
-                from: from.clone(),
-                to: to.clone(),
+        if !self.upgrades.is_empty() {
+            lines.push(OutputLine::Table {
+                headers: HEADERS.map(str::to_owned).to_vec(),
+                rows: self.upgrades.iter().map(Row::cells).collect(),
+            });
+        }
+
+        for (action, notes) in &self.release_notes {
+            lines.push(OutputLine::Blank);
+            lines.push(OutputLine::Detail {
+                text: format!("{action}:"),
+            });
+            for text in notes {
+                lines.push(OutputLine::Detail {
+                    text: format!("  {text}"),
+                });
+            }
+        }
+
+        for moved in &self.moved_tags {
+            lines.push(OutputLine::Changed {
+                action: moved.action.clone(),
+                detail: moved.detail(),
             });
         }
 
@@ -56,7 +203,17 @@ impl CommandReport for Report {
         lines.push(OutputLine::Blank);
 
         let upgrade_count = self.upgrades.len();
-        let wf = self.workflows_updated;
+        if let Some(path) = &self.saved_plan {
+            lines.push(OutputLine::Summary {
+                text: format!(
+                    "{upgrade_count} upgrade{} planned · saved to {}",
+                    if upgrade_count == 1 { "" } else { "s" },
+                    path.display()
+                ),
+            });
+            return lines;
+        }
+        let wf = self.workflows.len();
         let summary = format!(
             "{} upgraded · {} workflow{}",
             upgrade_count,
@@ -64,6 +221,11 @@ impl CommandReport for Report {
             if wf == 1 { "" } else { "s" }
         );
         lines.push(OutputLine::Summary { text: summary });
+        if let Some(url) = &self.pull_request {
+            lines.push(OutputLine::Summary {
+                text: format!("Opened pull request {url}"),
+            });
+        }
 
         lines
     }
@@ -72,10 +234,11 @@ impl CommandReport for Report {
 #[cfg(test)]
 #[expect(
     clippy::indexing_slicing,
+    clippy::unwrap_used,
     reason = "tests use unwrap, indexing, and other patterns freely"
 )]
 mod tests {
-    use super::{CommandReport as _, OutputLine, Report};
+    use super::{CHANGED_EXIT_CODE, CommandReport as _, Event, Moved, OutputLine, Report, Row};
 
     #[test]
     fn render_upgrade_up_to_date() {
@@ -90,38 +253,202 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exit_code_signals_changes() {
+        let up_to_date = Report {
+            up_to_date: true,
+            ..Default::default()
+        };
+        assert_eq!(up_to_date.exit_code(), 0);
+
+        let skipped_only = Report {
+            skipped: vec![("actions/cache".to_owned(), "held in gx.toml".to_owned())],
+            ..Default::default()
+        };
+        assert_eq!(skipped_only.exit_code(), 0);
+
+        let changed = Report {
+            upgrades: vec![row("actions/checkout", "v6", "v6.0.2")],
+            changed: true,
+            ..Default::default()
+        };
+        assert_eq!(changed.exit_code(), CHANGED_EXIT_CODE);
+    }
+
+    fn row(action: &str, current: &str, candidate: &str) -> Row {
+        Row {
+            action: action.to_owned(),
+            current: current.to_owned(),
+            candidate: candidate.to_owned(),
+            range: current.to_owned(),
+            sha: Some("08c6903cd8c0fde910a37f88322edcfb5dd907a8".to_owned()),
+        }
+    }
+
     #[test]
     fn render_upgrade_with_upgrades() {
         let report = Report {
             upgrades: vec![
-                (
-                    "actions/checkout".to_owned(),
-                    "v6".to_owned(),
-                    "v6.0.2".to_owned(),
-                ),
-                (
-                    "jdx/mise-action".to_owned(),
-                    "v3".to_owned(),
-                    "v3.6.2".to_owned(),
-                ),
+                row("actions/checkout", "v6", "v6.0.2"),
+                Row {
+                    sha: None,
+                    ..row("jdx/mise-action", "v3", "v3.6.2")
+                },
             ],
-            workflows_updated: 1,
+            workflows: vec![".github/workflows/ci.yml".into()],
             ..Default::default()
         };
         let lines = report.render();
 
-        assert!(lines.contains(&OutputLine::Upgraded {
-            action: "actions/checkout".to_owned(),
-            from: "v6".to_owned(),
-            to: "v6.0.2".to_owned(),
-        }));
-        assert!(lines.contains(&OutputLine::Upgraded {
-            action: "jdx/mise-action".to_owned(),
-            from: "v3".to_owned(),
-            to: "v3.6.2".to_owned(),
-        }));
+        assert_eq!(
+            lines[0],
+            OutputLine::Table {
+                headers: vec![
+                    "ACTION".to_owned(),
+                    "CURRENT".to_owned(),
+                    "CANDIDATE".to_owned(),
+                    "RANGE".to_owned(),
+                    "SHA".to_owned(),
+                ],
+                rows: vec![
+                    vec![
+                        "actions/checkout".to_owned(),
+                        "v6".to_owned(),
+                        "v6.0.2".to_owned(),
+                        "v6".to_owned(),
+                        "08c6903".to_owned(),
+                    ],
+                    vec![
+                        "jdx/mise-action".to_owned(),
+                        "v3".to_owned(),
+                        "v3.6.2".to_owned(),
+                        "v3".to_owned(),
+                        "-".to_owned(),
+                    ],
+                ],
+            }
+        );
         assert!(lines.contains(&OutputLine::Summary {
             text: "2 upgraded · 1 workflow".to_owned(),
         }));
     }
+
+    #[test]
+    fn render_upgrade_release_notes_after_table() {
+        let report = Report {
+            upgrades: vec![row("actions/checkout", "v6", "v6.0.2")],
+            release_notes: vec![(
+                "actions/checkout".to_owned(),
+                vec!["* Fix sparse checkout".to_owned()],
+            )],
+            workflows: vec![".github/workflows/ci.yml".into()],
+            ..Default::default()
+        };
+        let lines = report.render();
+
+        assert!(matches!(&lines[0], OutputLine::Table { .. }));
+        assert_eq!(
+            lines[2..4],
+            [
+                OutputLine::Detail {
+                    text: "actions/checkout:".to_owned(),
+                },
+                OutputLine::Detail {
+                    text: "  * Fix sparse checkout".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn report_serializes_rows_as_json() {
+        let report = Report {
+            upgrades: vec![row("actions/checkout", "v6", "v6.0.2")],
+            workflows: vec![".github/workflows/ci.yml".into()],
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["upgrades"][0]["candidate"], "v6.0.2");
+        assert_eq!(
+            json["upgrades"][0]["sha"],
+            "08c6903cd8c0fde910a37f88322edcfb5dd907a8"
+        );
+        assert_eq!(json["workflows"][0], ".github/workflows/ci.yml");
+    }
+
+    #[test]
+    fn render_upgrade_pull_request_after_summary() {
+        let report = Report {
+            upgrades: vec![row("actions/checkout", "v6", "v6.0.2")],
+            workflows: vec![".github/workflows/ci.yml".into()],
+            pull_request: Some("https://github.com/o/r/pull/7".to_owned()),
+            ..Default::default()
+        };
+        let lines = report.render();
+
+        assert_eq!(
+            lines.last(),
+            Some(&OutputLine::Summary {
+                text: "Opened pull request https://github.com/o/r/pull/7".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn render_upgrade_moved_tags() {
+        let moved = Moved {
+            action: "actions/checkout".to_owned(),
+            tag: "v4".to_owned(),
+            locked: "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_owned(),
+            current: "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb".to_owned(),
+            repinned: false,
+        };
+        let report = Report {
+            moved_tags: vec![moved],
+            ..Default::default()
+        };
+        let lines = report.render();
+
+        assert_eq!(
+            lines[0],
+            OutputLine::Changed {
+                action: "actions/checkout".to_owned(),
+                detail: "tag v4 moved aaaaaaa → bbbbbbb (run with --repin to update)".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn events_list_resolutions_upgrades_and_files() {
+        let report = Report {
+            upgrades: vec![row("actions/checkout", "v6", "v6.0.2")],
+            skipped: vec![("actions/cache".to_owned(), "held in gx.toml".to_owned())],
+            workflows: vec![".github/workflows/ci.yml".into()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            report.events(),
+            vec![
+                Event::Resolved {
+                    action: "actions/checkout".to_owned(),
+                    specifier: "v6".to_owned(),
+                    version: "v6.0.2".to_owned(),
+                    sha: "08c6903cd8c0fde910a37f88322edcfb5dd907a8".to_owned(),
+                },
+                Event::Upgraded {
+                    action: "actions/checkout".to_owned(),
+                    from: "v6".to_owned(),
+                    to: "v6.0.2".to_owned(),
+                },
+                Event::Skipped {
+                    action: "actions/cache".to_owned(),
+                    reason: "held in gx.toml".to_owned(),
+                },
+                Event::FileUpdated {
+                    path: ".github/workflows/ci.yml".into(),
+                },
+            ]
+        );
+    }
 }
@@ -0,0 +1,110 @@
+use super::args::Format;
+use gx::domain::action::upgrade::Candidate as UpgradeCandidate;
+use gx::output::printer::confirm;
+use gx::upgrade;
+use gx::upgrade::command::ConfirmFn;
+use indicatif::ProgressBar;
+use std::io::IsTerminal as _;
+use std::path::PathBuf;
+
+/// Arguments for `gx upgrade`.
+#[derive(Clone, clap::Args)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each bool is an independent CLI flag"
+)]
+pub struct UpgradeArgs {
+    /// Optional action identifier to upgrade (e.g., `actions/checkout`).
+    #[arg(value_name = "ACTION")]
+    action: Option<String>,
+    /// Upgrade to the latest version instead of safe update.
+    #[arg(long)]
+    latest: bool,
+    /// Upgrade only the actions of a group declared under `[groups]` in gx.toml.
+    #[arg(long, value_name = "NAME", conflicts_with = "action")]
+    group: Option<String>,
+    /// Upgrade only the actions published by this owner (e.g. `actions`).
+    #[arg(long, value_name = "OWNER", conflicts_with_all = ["action", "group"])]
+    owner: Option<String>,
+    /// Only upgrade actions whose locked commit is older than this many days.
+    #[arg(long, value_name = "DAYS")]
+    max_age: Option<u16>,
+    /// Re-pin actions whose floating tag (e.g. `v4`) moved to a different commit.
+    #[arg(long, conflicts_with = "only_lock")]
+    repin: bool,
+    /// Only refresh lock SHAs for the current manifest versions; never change the manifest.
+    #[arg(long, conflicts_with = "latest")]
+    only_lock: bool,
+    /// Apply cross-major upgrades in `--latest` mode without asking for confirmation.
+    #[arg(long, short = 'y')]
+    yes: bool,
+    /// Commit the changes on a new branch, push it, and open a pull request.
+    #[arg(long, conflicts_with = "save_plan")]
+    pr: bool,
+    /// Commit the changes to the current branch after writing them.
+    #[arg(long, conflicts_with_all = ["save_plan", "pr"])]
+    commit: bool,
+    /// Make one commit per upgraded action instead of a single commit.
+    #[arg(long, requires = "commit")]
+    per_action: bool,
+    /// Commit message template; `{action}`, `{old}`, and `{new}` are filled per action.
+    #[arg(long, value_name = "TEMPLATE", requires = "commit")]
+    commit_message: Option<String>,
+    /// Write the computed plan as JSON to this path instead of applying it.
+    #[arg(long, value_name = "PATH")]
+    save_plan: Option<PathBuf>,
+    /// Output format of the report.
+    #[arg(long, visible_alias = "output", value_enum, default_value_t)]
+    pub format: Format,
+}
+
+impl UpgradeArgs {
+    /// Build the upgrade command, wiring the cross-major confirmation to `spinner`.
+    pub fn into_command(
+        self,
+        spinner: Option<ProgressBar>,
+        is_ci: bool,
+    ) -> Result<upgrade::command::Upgrade, upgrade::cli::Error> {
+        let mut request = if self.only_lock {
+            upgrade::cli::resolve_lock_only(self.action.as_deref())?
+        } else {
+            upgrade::cli::resolve_upgrade_mode(self.action.as_deref(), self.latest)?
+        };
+        request.narrow(self.group, self.owner);
+        request.locked_before = self.max_age.map(upgrade::cli::max_age_cutoff);
+        request.repin = self.repin;
+        let confirm = (self.latest && !self.yes).then(|| cross_range_confirm(spinner, is_ci));
+        Ok(upgrade::command::Upgrade {
+            request,
+            save_plan: self.save_plan,
+            confirm,
+            pull_request: self.pr,
+            commit: self
+                .commit
+                .then(|| upgrade::commit::Options::new(self.per_action, self.commit_message)),
+        })
+    }
+}
+
+/// Build the confirmation hook for cross-major upgrades.
+///
+/// Prompts on an interactive terminal; otherwise declines, so unattended runs
+/// need `--yes` to cross a major version.
+fn cross_range_confirm(spinner: Option<ProgressBar>, is_ci: bool) -> ConfirmFn {
+    let interactive = !is_ci && std::io::stdin().is_terminal();
+    Box::new(move |candidate: &UpgradeCandidate| {
+        if !interactive {
+            return false;
+        }
+        let prompt = format!(
+            "Upgrade {} {} → {}?",
+            candidate.id,
+            candidate.current,
+            candidate.manifest_specifier()
+        );
+        match &spinner {
+            Some(pb) => pb.suspend(|| confirm(&prompt)),
+            None => confirm(&prompt),
+        }
+    })
+}
@@ -0,0 +1,102 @@
+use super::remote::{diverged, tagged, unattested, unsigned, untagged};
+use super::report::Report;
+use crate::command::Command;
+use crate::config::Config;
+use crate::infra::github::{Error as GithubError, Registry as GithubRegistry};
+use crate::infra::manifest;
+use std::path::Path;
+use thiserror::Error;
+
+/// Errors that can occur during `gx verify`.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("gx verify checks gx.lock against gx.toml, which does not exist; run `gx init` first")]
+    NoManifest,
+
+    #[error(
+        "gx verify --remote, --provenance, and --signatures check gx.lock through the GitHub API, which --offline disables"
+    )]
+    Offline,
+
+    #[error(transparent)]
+    Github(#[from] GithubError),
+}
+
+/// `gx verify`: check, without network calls, that gx.lock holds a complete entry for
+/// every action in gx.toml, and with `frozen` that it was written for gx.toml as it is.
+/// With `remote`, it also checks through the GitHub API that each locked commit still
+/// exists and its tag still leads to it, with `provenance` that an attestation vouches for it, and with
+/// `signatures` that it is signed.
+#[derive(Debug, Default)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each bool turns on an independent check"
+)]
+pub struct Verify {
+    /// Also fail when gx.toml changed since gx.lock was written.
+    pub frozen: bool,
+    /// Also fail when a locked commit no longer exists, or is neither the commit of its
+    /// tag nor in its history.
+    pub remote: bool,
+    /// Also check the attestation of each locked commit, failing when an immutable
+    /// release attests another commit.
+    pub provenance: bool,
+    /// Also check that each locked commit, or its tag, is signed, failing for the
+    /// actions marked `require_signed = true`.
+    pub signatures: bool,
+}
+
+impl Command for Verify {
+    type Report = Report;
+    type Error = Error;
+
+    fn run(
+        &self,
+        _repo_root: &Path,
+        config: Config,
+        on_progress: &mut dyn FnMut(&str),
+    ) -> Result<Report, Error> {
+        if !config.manifest_path.exists() {
+            return Err(Error::NoManifest);
+        }
+        let mut unlocked: Vec<String> = config
+            .manifest
+            .lock_keys()
+            .iter()
+            .filter(|spec| !config.lock.is_complete(spec))
+            .map(ToString::to_string)
+            .collect();
+        unlocked.sort();
+        let stale = self.frozen
+            && config.lock.manifest_hash()
+                != Some(config.lock_hash(manifest::hash(&config.manifest)).as_str());
+        let mut report = Report {
+            unlocked,
+            stale,
+            ..Report::default()
+        };
+        if !self.remote && !self.provenance && !self.signatures {
+            return Ok(report);
+        }
+        if config.settings.offline {
+            return Err(Error::Offline);
+        }
+        let registry = GithubRegistry::from_settings(&config.settings)?;
+        let tags = tagged(&config.lock);
+        if self.remote {
+            let other = untagged(&config.lock);
+            report.diverged = diverged(&registry, &tags, &other, on_progress)?;
+            report.compared = Some(tags.len().saturating_add(other.len()));
+        }
+        if self.provenance {
+            report.unattested = unattested(&registry, &tags, on_progress)?;
+            report.attested = Some(tags.len());
+        }
+        if self.signatures {
+            let (locked, found) = unsigned(&registry, &config.lock, &config.manifest, on_progress)?;
+            report.signed = Some(locked);
+            report.unsigned = found;
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,10 @@
+#![expect(clippy::pub_use, reason = "reexport from extracted submodule")]
+
+/// Verify command: error types, struct, and `Command` implementation.
+mod command;
+/// Checks of gx.lock against the repositories of its actions, through the GitHub API.
+mod remote;
+pub mod report;
+
+pub use command::{Error, Verify};
+pub use remote::{Divergence, Drift, Unattested, Unsigned};
@@ -0,0 +1,253 @@
+use crate::domain::action::identity::{ActionId, CommitSha, Repository, Version};
+use crate::domain::action::uses_ref::RefType;
+use crate::domain::lock::Lock;
+use crate::domain::manifest::Manifest;
+use crate::infra::github::{Attestation, Error as GithubError, Registry as GithubRegistry};
+use std::collections::HashSet;
+
+/// How a locked ref drifted away from its locked commit in the remote repository.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Drift {
+    /// The tag was moved to a commit whose history does not hold the locked one.
+    Moved,
+    /// The tag was deleted.
+    DeletedTag,
+    /// The commit is gone, as after a force push rewrote the history that held it.
+    MissingCommit,
+}
+
+/// A lock entry whose commit or tag changed in the remote repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// The repository of the ref and the commit.
+    pub repository: Repository,
+    /// The locked version.
+    pub version: Version,
+    /// The locked commit.
+    pub sha: CommitSha,
+    /// What changed.
+    pub drift: Drift,
+}
+
+/// A tag gx.lock pins whose locked commit no attestation vouches for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unattested {
+    /// The repository of the tag and the commit.
+    pub repository: Repository,
+    /// The locked tag.
+    pub version: Version,
+    /// The locked commit.
+    pub sha: CommitSha,
+    /// Whether the tag is an immutable release, whose attestation names another commit.
+    pub immutable: bool,
+}
+
+/// A locked commit without a signature GitHub verified, on it or on its tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsigned {
+    /// The action.
+    pub action: ActionId,
+    /// The locked version.
+    pub version: Version,
+    /// The locked commit.
+    pub sha: CommitSha,
+    /// Whether gx.toml marks the action `require_signed = true`.
+    pub required: bool,
+}
+
+/// The repository, tag, and commit of each entry gx.lock resolved from a tag, sorted and
+/// deduplicated. Branches move by design and commit SHAs have no tag to compare with.
+#[must_use]
+pub fn tagged(lock: &Lock) -> Vec<(Repository, Version, CommitSha)> {
+    locked(lock, true)
+}
+
+/// The repository, version, and commit of each entry gx.lock did not resolve from a tag:
+/// branches and commit SHAs, sorted and deduplicated.
+#[must_use]
+pub fn untagged(lock: &Lock) -> Vec<(Repository, Version, CommitSha)> {
+    locked(lock, false)
+}
+
+/// The repository, version, and commit of each entry gx.lock resolved from a tag, or of
+/// each entry it did not when `tags` is false, sorted and deduplicated.
+fn locked(lock: &Lock, tags: bool) -> Vec<(Repository, Version, CommitSha)> {
+    let mut tagged: Vec<_> = lock
+        .entries()
+        .filter(|(_, entry)| {
+            matches!(entry.commit.ref_type, Some(RefType::Release | RefType::Tag)) == tags
+        })
+        .map(|(_, entry)| {
+            (
+                entry.commit.repository.clone(),
+                entry.version.clone(),
+                entry.commit.sha.clone(),
+            )
+        })
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    tagged.sort_by(|a, b| (a.0.as_str(), a.1.as_str()).cmp(&(b.0.as_str(), b.1.as_str())));
+    tagged
+}
+
+/// The entries of `tags` and `untagged` whose ref or commit changed in the remote
+/// repository, per the GitHub API: a tag whose history no longer holds its locked commit,
+/// a deleted tag, or a commit that no longer exists.
+///
+/// # Errors
+///
+/// Returns an error if a comparison, ref, or commit cannot be fetched.
+pub fn diverged(
+    registry: &GithubRegistry,
+    tags: &[(Repository, Version, CommitSha)],
+    untagged: &[(Repository, Version, CommitSha)],
+    on_progress: &mut dyn FnMut(&str),
+) -> Result<Vec<Divergence>, GithubError> {
+    let mut diverged = Vec::new();
+    for (repository, version, sha) in tags {
+        on_progress(&format!("Comparing {repository}@{version}..."));
+        let drift = match registry.is_ancestor(repository, sha, version.as_str())? {
+            Some(true) => continue,
+            None if !registry.has_commit(repository, sha)? => Drift::MissingCommit,
+            None if !registry.has_tag(repository, version)? => Drift::DeletedTag,
+            Some(false) | None => Drift::Moved,
+        };
+        diverged.push(Divergence {
+            repository: repository.clone(),
+            version: version.clone(),
+            sha: sha.clone(),
+            drift,
+        });
+    }
+    for (repository, version, sha) in untagged {
+        on_progress(&format!("Looking up {repository}@{sha}..."));
+        if !registry.has_commit(repository, sha)? {
+            diverged.push(Divergence {
+                repository: repository.clone(),
+                version: version.clone(),
+                sha: sha.clone(),
+                drift: Drift::MissingCommit,
+            });
+        }
+    }
+    Ok(diverged)
+}
+
+/// The `tags` whose locked commit no attestation vouches for.
+///
+/// # Errors
+///
+/// Returns an error if an attestation or release cannot be fetched.
+pub fn unattested(
+    registry: &GithubRegistry,
+    tags: &[(Repository, Version, CommitSha)],
+    on_progress: &mut dyn FnMut(&str),
+) -> Result<Vec<Unattested>, GithubError> {
+    let mut unattested = Vec::new();
+    for (repository, version, sha) in tags {
+        on_progress(&format!(
+            "Checking the provenance of {repository}@{version}..."
+        ));
+        let attestation = registry.attestation(repository, version, sha)?;
+        if attestation != Attestation::Attested {
+            unattested.push(Unattested {
+                repository: repository.clone(),
+                version: version.clone(),
+                sha: sha.clone(),
+                immutable: attestation == Attestation::Mismatch,
+            });
+        }
+    }
+    Ok(unattested)
+}
+
+/// How many commits gx.lock pins, and those without a signature GitHub verified, on the
+/// commit or on the annotated tag pointing to it.
+///
+/// # Errors
+///
+/// Returns an error if a commit, ref, or tag cannot be fetched.
+pub fn unsigned(
+    registry: &GithubRegistry,
+    lock: &Lock,
+    manifest: &Manifest,
+    on_progress: &mut dyn FnMut(&str),
+) -> Result<(usize, Vec<Unsigned>), GithubError> {
+    let mut locked: Vec<_> = lock
+        .entries()
+        .map(|(spec, entry)| (spec.id.clone(), entry.clone()))
+        .collect::<Vec<_>>();
+    locked.sort_by(|a, b| {
+        (a.0.as_str(), a.1.version.as_str()).cmp(&(b.0.as_str(), b.1.version.as_str()))
+    });
+    locked.dedup_by(|a, b| a.0 == b.0 && a.1.version == b.1.version);
+    let mut unsigned = Vec::new();
+    for (id, entry) in &locked {
+        on_progress(&format!(
+            "Checking the signature of {id}@{}...",
+            entry.version
+        ));
+        let tag = matches!(entry.commit.ref_type, Some(RefType::Release | RefType::Tag))
+            .then_some(&entry.version);
+        if !registry.is_signed(&entry.commit.repository, tag, &entry.commit.sha)? {
+            unsigned.push(Unsigned {
+                action: id.clone(),
+                version: entry.version.clone(),
+                sha: entry.commit.sha.clone(),
+                required: manifest.requires_signed(id),
+            });
+        }
+    }
+    Ok((locked.len(), unsigned))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tagged, untagged};
+    use crate::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
+    use crate::domain::action::resolved::{Commit, Provenance};
+    use crate::domain::action::spec::Spec;
+    use crate::domain::action::specifier::Specifier;
+    use crate::domain::action::uses_ref::RefType;
+    use crate::domain::lock::Lock;
+
+    #[test]
+    fn tagged_and_untagged_split_the_lock_and_list_each_commit_once() {
+        let mut lock = Lock::default();
+        for (action, specifier, version, ref_type) in [
+            ("actions/checkout", "^4", "v4", RefType::Tag),
+            ("actions/checkout", "v4", "v4", RefType::Release),
+            ("actions/cache", "main", "main", RefType::Branch),
+        ] {
+            lock.set(
+                &Spec::new(ActionId::from(action), Specifier::parse(specifier)),
+                Version::from(version),
+                Commit {
+                    sha: CommitSha::from("aaa111"),
+                    repository: Repository::from(action),
+                    ref_type: Some(ref_type),
+                    date: CommitDate::from("2026-01-01T00:00:00Z"),
+                    provenance: Provenance::default(),
+                },
+            );
+        }
+
+        assert_eq!(
+            tagged(&lock),
+            [(
+                Repository::from("actions/checkout"),
+                Version::from("v4"),
+                CommitSha::from("aaa111")
+            )]
+        );
+        assert_eq!(
+            untagged(&lock),
+            [(
+                Repository::from("actions/cache"),
+                Version::from("main"),
+                CommitSha::from("aaa111")
+            )]
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use super::remote::{Divergence, Drift, Unattested, Unsigned};
+use crate::command::CommandReport;
+use crate::config::Level;
+use crate::output::lines::Line as OutputLine;
+
+/// Report from `gx verify`.
+#[derive(Debug, Default)]
+pub struct Report {
+    /// The `action@specifier` of each action in gx.toml without a complete lock entry.
+    pub unlocked: Vec<String>,
+    /// Whether gx.lock was written for another gx.toml, checked with `--frozen`.
+    pub stale: bool,
+    /// How many locked commits were looked up in their repositories, with `--remote`.
+    pub compared: Option<usize>,
+    /// The lock entries whose ref or commit changed in the remote repository.
+    pub diverged: Vec<Divergence>,
+    /// How many locked tags had their provenance checked, with `--provenance`.
+    pub attested: Option<usize>,
+    /// The locked tags whose commit no attestation vouches for.
+    pub unattested: Vec<Unattested>,
+    /// How many locked commits had their signature checked, with `--signatures`.
+    pub signed: Option<usize>,
+    /// The locked commits without a verified signature on them or their tag.
+    pub unsigned: Vec<Unsigned>,
+}
+
+impl CommandReport for Report {
+    fn render(&self) -> Vec<OutputLine> {
+        let mut lines: Vec<OutputLine> = self
+            .unlocked
+            .iter()
+            .map(|spec| OutputLine::Warning {
+                message: format!("{spec} is not locked in gx.lock"),
+            })
+            .collect();
+        lines.extend(self.diverged.iter().map(divergence_line));
+        lines.extend(self.unattested.iter().map(unattested_line));
+        lines.extend(self.unsigned.iter().map(unsigned_line));
+        if self.stale {
+            lines.push(OutputLine::Warning {
+                message: "gx.toml changed since gx.lock was written".to_owned(),
+            });
+        }
+        lines.push(if self.unlocked.is_empty() && !self.stale {
+            OutputLine::Summary {
+                text: "gx.lock is up to date with gx.toml".to_owned(),
+            }
+        } else {
+            OutputLine::Warning {
+                message: "gx.lock is out of date; run `gx tidy`".to_owned(),
+            }
+        });
+        if let Some(compared) = self.compared {
+            lines.push(if self.diverged.is_empty() {
+                OutputLine::Summary {
+                    text: format!("{compared} locked commit(s) match their repositories"),
+                }
+            } else {
+                OutputLine::Warning {
+                    message: format!(
+                        "{} of {compared} locked commit(s) changed in their repositories",
+                        self.diverged.len()
+                    ),
+                }
+            });
+        }
+        if let Some(tags) = self.attested {
+            lines.push(OutputLine::Summary {
+                text: format!(
+                    "{} of {tags} locked tag(s) have an attestation of their commit",
+                    tags.saturating_sub(self.unattested.len())
+                ),
+            });
+        }
+        if let Some(locked) = self.signed {
+            lines.push(OutputLine::Summary {
+                text: format!(
+                    "{} of {locked} locked commit(s) are signed",
+                    locked.saturating_sub(self.unsigned.len())
+                ),
+            });
+        }
+        lines
+    }
+
+    fn exit_code(&self) -> i32 {
+        i32::from(!self.is_ok())
+    }
+}
+
+impl Report {
+    /// Whether gx.lock passed every check.
+    fn is_ok(&self) -> bool {
+        self.unlocked.is_empty()
+            && !self.stale
+            && self.diverged.is_empty()
+            && !self
+                .unattested
+                .iter()
+                .any(|unattested| unattested.immutable)
+            && !self.unsigned.iter().any(|unsigned| unsigned.required)
+    }
+}
+
+/// The line reporting a lock entry whose ref or commit changed in the remote repository.
+fn divergence_line(divergence: &Divergence) -> OutputLine {
+    let Divergence {
+        repository,
+        version,
+        sha,
+        drift,
+    } = divergence;
+    let (rule, message) = match drift {
+        Drift::Moved => (
+            "moved-tag",
+            format!("{repository}@{version} was moved off the locked commit {sha}"),
+        ),
+        Drift::DeletedTag => (
+            "deleted-tag",
+            format!("{repository}@{version} was deleted; the locked commit is {sha}"),
+        ),
+        Drift::MissingCommit => (
+            "missing-commit",
+            format!(
+                "{repository}@{version}: the locked commit {sha} no longer exists, so workflows pinned to it fail"
+            ),
+        ),
+    };
+    OutputLine::LintDiag {
+        level: Level::Error,
+        workflow: None,
+        line: None,
+        rule: rule.to_owned(),
+        message,
+    }
+}
+
+/// The line reporting a locked tag whose commit no attestation vouches for.
+fn unattested_line(unattested: &Unattested) -> OutputLine {
+    let Unattested {
+        repository,
+        version,
+        sha,
+        immutable,
+    } = unattested;
+    OutputLine::Warning {
+        message: if *immutable {
+            format!(
+                "{repository}@{version} is an immutable release that attests another commit than {sha}"
+            )
+        } else {
+            format!("{repository}@{version} publishes no provenance for {sha}")
+        },
+    }
+}
+
+/// The line reporting a locked commit without a verified signature.
+fn unsigned_line(unsigned: &Unsigned) -> OutputLine {
+    let Unsigned {
+        action,
+        version,
+        sha,
+        required,
+    } = unsigned;
+    OutputLine::LintDiag {
+        level: if *required { Level::Error } else { Level::Warn },
+        workflow: None,
+        line: None,
+        rule: "signature".to_owned(),
+        message: format!("{action}@{version} is not signed, on commit {sha} or its tag"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Divergence, Drift, Report, Unattested, Unsigned};
+    use crate::command::CommandReport as _;
+    use crate::domain::action::identity::{ActionId, CommitSha, Repository, Version};
+
+    #[test]
+    fn stale_or_unlocked_lock_fails() {
+        let stale = Report {
+            stale: true,
+            ..Report::default()
+        };
+        let unlocked = Report {
+            unlocked: vec!["actions/checkout@^4".to_owned()],
+            ..Report::default()
+        };
+
+        assert_eq!(Report::default().exit_code(), 0);
+        assert_eq!(stale.exit_code(), 1);
+        assert_eq!(unlocked.exit_code(), 1);
+        assert_eq!(unlocked.render().len(), 2);
+    }
+
+    #[test]
+    fn diverged_tag_fails_with_a_line_each() {
+        let diverged = Report {
+            compared: Some(2),
+            diverged: vec![Divergence {
+                repository: Repository::from("actions/checkout"),
+                version: Version::from("v4"),
+                sha: CommitSha::from("aaa111"),
+                drift: Drift::MissingCommit,
+            }],
+            ..Report::default()
+        };
+
+        assert_eq!(diverged.exit_code(), 1);
+        assert_eq!(diverged.render().len(), 3);
+    }
+
+    #[test]
+    fn only_an_immutable_release_attesting_another_commit_fails() {
+        let unattested = |immutable: bool| Report {
+            attested: Some(1),
+            unattested: vec![Unattested {
+                repository: Repository::from("actions/checkout"),
+                version: Version::from("v4"),
+                sha: CommitSha::from("aaa111"),
+                immutable,
+            }],
+            ..Report::default()
+        };
+
+        assert_eq!(unattested(false).exit_code(), 0);
+        assert_eq!(unattested(true).exit_code(), 1);
+        assert_eq!(unattested(false).render().len(), 3);
+    }
+
+    #[test]
+    fn only_an_unsigned_commit_gx_toml_requires_signed_fails() {
+        let unsigned = |required: bool| Report {
+            signed: Some(1),
+            unsigned: vec![Unsigned {
+                action: ActionId::from("actions/checkout"),
+                version: Version::from("v4"),
+                sha: CommitSha::from("aaa111"),
+                required,
+            }],
+            ..Report::default()
+        };
+
+        assert_eq!(unsigned(false).exit_code(), 0);
+        assert_eq!(unsigned(true).exit_code(), 1);
+    }
+}
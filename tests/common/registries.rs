@@ -3,7 +3,7 @@
     reason = "shared test helpers: not every integration test crate uses every item"
 )]
 use gx::domain::action::identity::{ActionId, CommitDate, CommitSha, Version};
-use gx::domain::action::resolved::Commit;
+use gx::domain::action::resolved::{Commit, Provenance};
 use gx::domain::action::spec::Spec as ActionSpec;
 use gx::domain::action::specifier::Specifier;
 use gx::domain::action::uses_ref::RefType;
@@ -67,6 +67,7 @@ impl VersionRegistry for FakeRegistry {
             repository: id.base_repo(),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         })
     }
 
@@ -115,6 +116,7 @@ impl VersionRegistry for FakeRegistry {
             tags,
             repository: id.base_repo(),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         })
     }
 }
@@ -161,6 +163,7 @@ impl VersionRegistry for EmptyDateRegistry {
             repository: id.base_repo(),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from(""),
+            provenance: Provenance::default(),
         })
     }
 
@@ -185,6 +188,7 @@ impl VersionRegistry for EmptyDateRegistry {
             tags: vec![],
             repository: id.base_repo(),
             date: CommitDate::from(""),
+            provenance: Provenance::default(),
         })
     }
 }
@@ -201,6 +205,7 @@ impl VersionRegistry for FailingDescribeRegistry {
             repository: id.base_repo(),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         })
     }
 
@@ -7,12 +7,12 @@
 
 use gx::config::{Level, Lint};
 use gx::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
-use gx::domain::action::resolved::Commit;
+use gx::domain::action::resolved::{Commit, Provenance};
 use gx::domain::action::spec::Spec as ActionSpec;
 use gx::domain::action::specifier::Specifier;
 use gx::domain::action::uses_ref::RefType;
 use gx::domain::lock::Lock;
-use gx::domain::manifest::Manifest;
+use gx::domain::manifest::{Annotation, Manifest};
 use gx::infra::workflow_scan::FileScanner as FileWorkflowScanner;
 use gx::lint;
 use std::fs;
@@ -84,6 +84,48 @@ jobs:
     );
 }
 
+#[test]
+fn lint_diagnostics_carry_the_reason_of_the_action() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let repo_root = temp_dir.path();
+    let workflows_dir = repo_root.join(".github").join("workflows");
+    fs::create_dir_all(&workflows_dir).unwrap();
+    fs::write(
+        workflows_dir.join("ci.yml"),
+        "on: [push]\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - uses: actions/cache@v3\n",
+    )
+    .unwrap();
+    let cache = ActionId::from("actions/cache");
+    let mut manifest = Manifest::default();
+    manifest.set(cache.clone(), Specifier::from_v1("v3"));
+    manifest.annotate(
+        cache,
+        Annotation {
+            reason: Some("v4 breaks cache keys".to_owned()),
+            owner: None,
+        },
+    );
+
+    let diagnostics = lint::collect_diagnostics(
+        &manifest,
+        &Lock::default(),
+        &FileWorkflowScanner::new(repo_root),
+        &Lint::default(),
+        &mut |_| {},
+    )
+    .unwrap();
+
+    let unpinned = diagnostics
+        .iter()
+        .find(|d| d.rule == gx::lint::RuleName::Unpinned)
+        .expect("actions/cache@v3 is unpinned");
+    assert!(
+        unpinned.message.ends_with("(reason: v4 breaks cache keys)"),
+        "{}",
+        unpinned.message
+    );
+}
+
 #[test]
 fn lint_detects_unsynced_manifest() {
     let temp_dir = tempfile::tempdir().unwrap();
@@ -283,6 +325,7 @@ jobs:
             repository: Repository::from("actions/checkout"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         },
     );
 
@@ -338,6 +381,7 @@ jobs:
             repository: Repository::from("actions/setup-node"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         },
     );
 
@@ -398,6 +442,7 @@ jobs:
             repository: Repository::from("actions/setup-node"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         },
     );
 
@@ -505,6 +550,7 @@ jobs:
             repository: Repository::from("actions/checkout"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from("2026-01-01T00:00:00Z"),
+            provenance: Provenance::default(),
         },
     );
 
@@ -612,6 +658,56 @@ jobs:
     assert!(test_unpinned > 0, "test.yml unpinned should not be ignored");
 }
 
+#[test]
+fn lint_path_override_relaxes_rule_for_matching_workflows() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let repo_root = temp_dir.path();
+    let workflows_dir = repo_root.join(".github").join("workflows");
+    fs::create_dir_all(&workflows_dir).unwrap();
+
+    let content = "
+name: CI
+on: [push]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+";
+    fs::write(workflows_dir.join("ci.yml"), content).unwrap();
+    fs::write(workflows_dir.join("experimental-build.yml"), content).unwrap();
+
+    let mut manifest = Manifest::default();
+    manifest.set(ActionId::from("actions/checkout"), Specifier::from_v1("v4"));
+
+    let lock = Lock::default();
+    let scanner = FileWorkflowScanner::new(repo_root);
+
+    let mut lint_config = Lint::default();
+    lint_config.paths.insert(
+        "workflows/experimental-*.yml".to_owned(),
+        [(gx::lint::RuleName::Unpinned, Level::Warn)].into(),
+    );
+
+    let diagnostics =
+        lint::collect_diagnostics(&manifest, &lock, &scanner, &lint_config, &mut |_| {})
+            .expect("Should succeed");
+
+    let unpinned_level = |file: &str| {
+        diagnostics
+            .iter()
+            .find(|d| {
+                d.rule == gx::lint::RuleName::Unpinned
+                    && d.workflow
+                        .as_ref()
+                        .is_some_and(|w| w.as_str().ends_with(file))
+            })
+            .map(|d| d.level)
+    };
+    assert_eq!(unpinned_level("/ci.yml"), Some(Level::Error));
+    assert_eq!(unpinned_level("/experimental-build.yml"), Some(Level::Warn));
+}
+
 // ---------------------------------------------------------------------------
 // Workflow-security rules: per-rule `level = "off"` smoke tests.
 //
@@ -847,6 +943,43 @@ jobs:
     assert_eq!(off, 0, "level = off must suppress invalid-expression");
 }
 
+#[test]
+fn parallel_rule_execution_is_deterministic_across_runs() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let repo_root = temp_dir.path();
+    let workflows_dir = repo_root.join(".github").join("workflows");
+    fs::create_dir_all(&workflows_dir).unwrap();
+
+    let workflow = "
+name: X
+on: [push, pull_request_target]
+permissions: write-all
+jobs:
+  build:
+    needs: [missing]
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo ${{ steps.nope.outputs.x }}
+";
+    for name in ["a.yml", "b.yml", "c.yml"] {
+        fs::write(workflows_dir.join(name), workflow).unwrap();
+    }
+
+    let manifest = Manifest::default();
+    let lock = Lock::default();
+    let scanner = FileWorkflowScanner::new(repo_root);
+    let lint_config = Lint::default();
+
+    let first =
+        lint::collect_diagnostics(&manifest, &lock, &scanner, &lint_config, &mut |_| {}).unwrap();
+    for _ in 0..5 {
+        let again =
+            lint::collect_diagnostics(&manifest, &lock, &scanner, &lint_config, &mut |_| {})
+                .unwrap();
+        assert_eq!(first, again, "parallel rule merge must be deterministic");
+    }
+}
+
 #[test]
 fn diagnostics_are_stably_sorted_across_workflows_jobs_and_rules() {
     let temp_dir = tempfile::tempdir().unwrap();
@@ -954,3 +1087,38 @@ fn lint_config_parses_all_six_new_rule_names() {
         Level::Off
     );
 }
+
+#[test]
+fn config_validate_reports_unknown_keys_and_ignores_of_missing_workflows() {
+    use gx::command::Command as _;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let repo_root = temp_dir.path();
+    let workflows_dir = repo_root.join(".github").join("workflows");
+    fs::create_dir_all(&workflows_dir).unwrap();
+    fs::write(workflows_dir.join("ci.yml"), "on: push\njobs: {}\n").unwrap();
+    fs::write(
+        repo_root.join(".github").join("gx.toml"),
+        r#"
+[lint.rules]
+unpinned = { level = "error", ignore = [{ workflow = "ci.yml" }, { workflow = "gone.yml" }] }
+
+[lint.rulse]
+stale-comment = { level = "off" }
+"#,
+    )
+    .unwrap();
+
+    let config = gx::config::Config::load(repo_root).unwrap();
+    let report = gx::configuration::Validate
+        .run(repo_root, config, &mut |_| {})
+        .unwrap();
+
+    assert_eq!(
+        report.problems,
+        [
+            "unknown key lint.rulse",
+            "lint.rules.unpinned ignores workflow gone.yml, which matches no workflow",
+        ]
+    );
+}
@@ -40,3 +40,61 @@ fn find_root_without_github_folder() {
     assert!(result.is_err());
     assert!(matches!(result.unwrap_err(), repo::Error::GithubFolder));
 }
+
+#[test]
+fn find_root_accepts_org_github_repository_with_workflow_templates() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+
+    init_git_repo(root);
+    fs::create_dir(root.join("workflow-templates")).unwrap();
+
+    assert_eq!(repo::find_root(root).unwrap(), root);
+}
+
+#[test]
+fn find_root_of_a_linked_worktree_is_the_worktree_checkout() {
+    let temp_dir = TempDir::new().unwrap();
+    let main = temp_dir.path().join("main");
+    let linked = temp_dir.path().join("linked");
+    fs::create_dir_all(main.join(".github")).unwrap();
+    fs::write(main.join(".github/gx.toml"), "").unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&main)
+            .args(["-c", "user.name=gx", "-c", "user.email=gx@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    };
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "init"]);
+    git(&["worktree", "add", "-q", linked.to_str().unwrap()]);
+
+    let found = repo::find_root(&linked.join(".github")).unwrap();
+
+    assert_eq!(
+        found.canonicalize().unwrap(),
+        linked.canonicalize().unwrap()
+    );
+}
+
+#[test]
+fn open_root_takes_the_given_checkout_without_git() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+
+    assert!(matches!(
+        repo::open_root(&root.join("missing")),
+        Err(repo::Error::NotADirectory { .. })
+    ));
+    assert!(matches!(
+        repo::open_root(root),
+        Err(repo::Error::GithubFolder)
+    ));
+    fs::create_dir(root.join(".github")).unwrap();
+    assert_eq!(repo::open_root(root).unwrap(), root);
+}
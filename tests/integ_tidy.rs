@@ -1,5 +1,6 @@
 #![expect(
     clippy::unwrap_used,
+    clippy::indexing_slicing,
     clippy::string_slice,
     clippy::assertions_on_result_states,
     clippy::shadow_unrelated,
@@ -10,14 +11,19 @@ mod common;
 
 use common::registries::{AuthRequiredRegistry, FakeRegistry};
 use common::setup::{create_empty_manifest, create_test_repo};
+use gx::command::{Command as _, CommandReport as _};
+use gx::config::Config;
+use gx::domain::action::identity::CommitSha;
+use gx::domain::lock::Lock;
 use gx::domain::manifest::Manifest;
 use gx::domain::resolution::VersionRegistry;
+use gx::infra::git::{Changes, Git};
 use gx::infra::lock::Store as LockStore;
 use gx::infra::manifest::patch::apply_manifest_diff;
 use gx::infra::manifest::{self};
 use gx::infra::workflow_scan::FileScanner as FileWorkflowScanner;
 use gx::infra::workflow_update::WorkflowWriter;
-use gx::tidy;
+use gx::{lock, tidy, verify};
 use std::fs;
 use std::io::Write as _;
 use std::path::Path;
@@ -226,6 +232,49 @@ jobs:
     assert!(!updated_manifest.contains("actions/unused-action"));
 }
 
+#[test]
+fn gx_tidy_leaves_held_actions_alone() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = create_test_repo(&temp_dir);
+
+    // checkout is held at ^4 while the workflow uses v3; cache is held and unused
+    let manifest_content = r#"[actions]
+"actions/cache" = { version = "^4", hold = true }
+"actions/checkout" = { version = "^4", hold = true }
+"actions/setup-node" = "^4"
+"#;
+    let manifest_path = root.join(".github").join("gx.toml");
+    fs::write(&manifest_path, manifest_content).unwrap();
+
+    let workflow_content = "name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v3
+      - uses: actions/setup-node@v3
+";
+    let workflow_path = root.join(".github").join("workflows").join("ci.yml");
+    fs::write(&workflow_path, workflow_content).unwrap();
+
+    run_tidy(&root).unwrap();
+
+    let updated_content = fs::read_to_string(&workflow_path).unwrap();
+    assert!(
+        updated_content.contains("actions/checkout@v3\n"),
+        "Held action must not be re-pinned, got:\n{updated_content}"
+    );
+    let node_sha = FakeRegistry::fake_sha("actions/setup-node", "v4");
+    assert!(updated_content.contains(&format!("actions/setup-node@{node_sha}")));
+
+    let updated_manifest = fs::read_to_string(&manifest_path).unwrap();
+    assert!(
+        updated_manifest.starts_with(manifest_content),
+        "Held entries must be kept as written, got:\n{updated_manifest}"
+    );
+}
+
 #[test]
 fn gx_tidy_adds_missing_actions() {
     let temp_dir = TempDir::new().unwrap();
@@ -789,3 +838,202 @@ fn gx_tidy_removes_stale_override() {
         "Stale override should be removed, got:\n{manifest_content}"
     );
 }
+
+#[test]
+fn gx_tidy_limited_to_staged_workflows_leaves_the_others_untouched() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = create_test_repo(&temp_dir);
+    create_empty_manifest(&root);
+    let ci_path = root.join(".github/workflows/ci.yml");
+    let deploy_path = root.join(".github/workflows/deploy.yml");
+    let deploy_content =
+        "name: Deploy\njobs:\n  deploy:\n    steps:\n      - uses: actions/checkout@v4\n";
+    fs::write(&ci_path, "name: CI\njobs:\n  build:\n    steps: []\n").unwrap();
+    fs::write(&deploy_path, deploy_content).unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&root)
+            .args(["-c", "user.name=gx", "-c", "user.email=gx@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    };
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "init"]);
+    fs::write(
+        &ci_path,
+        "name: CI\njobs:\n  build:\n    steps:\n      - uses: actions/setup-node@v4\n",
+    )
+    .unwrap();
+    git(&["add", ".github/workflows/ci.yml"]);
+
+    let staged = Git::new(&root).changed_files(&Changes::Staged).unwrap();
+    assert_eq!(staged, std::slice::from_ref(&ci_path));
+    let manifest = manifest::parse(&root.join(".github/gx.toml"))
+        .unwrap()
+        .value;
+    let scanner = FileWorkflowScanner::new(&root);
+    let plan = tidy::plan(
+        &manifest,
+        &Lock::default(),
+        &FakeRegistry::new(),
+        &scanner,
+        |_| {},
+    )
+    .unwrap();
+    let writer = WorkflowWriter::new(&root).limited_to(staged);
+    let rewritten = tidy::apply_workflow_patches(&writer, &plan.workflows).unwrap();
+
+    assert_eq!(rewritten, std::slice::from_ref(&ci_path));
+    assert!(
+        fs::read_to_string(&ci_path)
+            .unwrap()
+            .contains(&FakeRegistry::fake_sha("actions/setup-node", "v4"))
+    );
+    assert_eq!(fs::read_to_string(&deploy_path).unwrap(), deploy_content);
+    assert!(
+        plan.manifest
+            .added
+            .iter()
+            .any(|(id, _)| id.as_str() == "actions/checkout")
+    );
+}
+
+#[test]
+fn gx_verify_frozen_fails_once_gx_toml_changes_after_the_lock() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = create_test_repo(&temp_dir);
+    create_empty_manifest(&root);
+    let manifest_path = root.join(".github/gx.toml");
+    let lock_path = root.join(".github/gx.lock");
+    fs::write(
+        root.join(".github/workflows/ci.yml"),
+        "name: CI\njobs:\n  build:\n    steps:\n      - uses: actions/checkout@v4\n",
+    )
+    .unwrap();
+    run_tidy(&root).unwrap();
+    let mut lock = LockStore::new(&lock_path).load().unwrap();
+    lock.set_manifest_hash(manifest::hash_file(&manifest_path).unwrap());
+    LockStore::new(&lock_path).save(&lock).unwrap();
+    let verify = |frozen: bool| {
+        verify::Verify {
+            frozen,
+            remote: false,
+            provenance: false,
+            signatures: false,
+        }
+        .run(&root, Config::load(&root).unwrap(), &mut |_| {})
+        .unwrap()
+    };
+
+    assert_eq!(verify(true).exit_code(), 0);
+
+    let manifest = fs::read_to_string(&manifest_path).unwrap();
+    fs::write(
+        &manifest_path,
+        manifest.replace("\"^4\"", "{ version = \"^4\", hold = true }"),
+    )
+    .unwrap();
+
+    assert_eq!(verify(false).exit_code(), 0);
+    let stale = verify(true);
+    assert!(stale.stale && stale.unlocked.is_empty());
+    assert_eq!(stale.exit_code(), 1);
+}
+
+#[test]
+fn gx_lock_diff_compares_the_work_tree_lock_with_head() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = create_test_repo(&temp_dir);
+    create_empty_manifest(&root);
+    fs::write(
+        root.join(".github/workflows/ci.yml"),
+        "name: CI\njobs:\n  build:\n    steps:\n      - uses: actions/checkout@v4\n",
+    )
+    .unwrap();
+    run_tidy(&root).unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&root)
+            .args(["-c", "user.name=gx", "-c", "user.email=gx@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    };
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "init"]);
+    let store = LockStore::new(&root.join(".github/gx.lock"));
+    let mut lock = store.load().unwrap();
+    let (spec, entry) = lock
+        .entries()
+        .map(|(spec, entry)| (spec.clone(), entry.clone()))
+        .next()
+        .unwrap();
+    let mut commit = entry.commit.clone();
+    commit.sha = CommitSha::from("0000000000000000000000000000000000000000");
+    lock.set(&spec, entry.version, commit);
+    store.save(&lock).unwrap();
+
+    let report = lock::Diff::default()
+        .run(&root, Config::load(&root).unwrap(), &mut |_| {})
+        .unwrap();
+
+    assert_eq!(report.changes.len(), 1);
+    assert_eq!(report.changes[0].action, "actions/checkout");
+    assert_eq!(report.changes[0].kind, lock::report::Kind::ShaChanged);
+    assert_eq!(
+        report.changes[0].new_sha.as_deref(),
+        Some("0000000000000000000000000000000000000000")
+    );
+}
+
+#[test]
+fn gx_lock_merge_writes_the_union_of_both_sides_to_ours() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = create_test_repo(&temp_dir);
+    create_empty_manifest(&root);
+    fs::write(
+        root.join(".github/workflows/ci.yml"),
+        "name: CI\njobs:\n  build:\n    steps:\n      - uses: actions/checkout@v4\n      - uses: actions/setup-node@v4\n",
+    )
+    .unwrap();
+    run_tidy(&root).unwrap();
+    let locked = LockStore::new(&root.join(".github/gx.lock"))
+        .load()
+        .unwrap();
+    let entries: Vec<_> = locked
+        .entries()
+        .map(|(spec, entry)| (spec.clone(), entry.clone()))
+        .collect();
+    let side = |name: &str, keep: &str| {
+        let mut lock = Lock::default();
+        for (spec, entry) in &entries {
+            if spec.id.as_str() == keep {
+                lock.set(spec, entry.version.clone(), entry.commit.clone());
+            }
+        }
+        let path = temp_dir.path().join(name);
+        LockStore::new(&path).save(&lock).unwrap();
+        path
+    };
+    let command = lock::Merge {
+        base: side("base.lock", "none"),
+        ours: side("ours.lock", "actions/checkout"),
+        theirs: side("theirs.lock", "actions/setup-node"),
+    };
+
+    let report = command
+        .run(&root, Config::load(&root).unwrap(), &mut |_| {})
+        .unwrap();
+    let merged = LockStore::new(&command.ours).load().unwrap();
+
+    assert!(report.both_changed.is_empty());
+    assert!(report.unlocked.is_empty(), "{:?}", report.unlocked);
+    assert_eq!(merged.entries().count(), 2);
+}
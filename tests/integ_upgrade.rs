@@ -12,8 +12,9 @@ use common::registries::FakeRegistry;
 use common::setup::{
     create_test_repo, lock_path, manifest_path, write_lock, write_manifest, write_workflow,
 };
+use gx::config::Workflows;
 use gx::domain::action::identity::{ActionId, CommitDate, CommitSha, Repository, Version};
-use gx::domain::action::resolved::{Commit, ResolvedAction};
+use gx::domain::action::resolved::{Commit, Provenance, ResolvedAction};
 use gx::domain::action::spec::Spec as ActionSpec;
 use gx::domain::action::specifier::Specifier;
 use gx::domain::action::uses_ref::RefType;
@@ -280,6 +281,7 @@ jobs:
             repository: Repository::from("actions/checkout"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from(""),
+            provenance: Provenance::default(),
         },
     );
 
@@ -343,6 +345,7 @@ fn upgrade_repins_branch_ref() {
             repository: Repository::from("my-org/my-action"),
             ref_type: Some(RefType::Branch),
             date: CommitDate::from(""),
+            provenance: Provenance::default(),
         },
     );
 
@@ -393,6 +396,7 @@ fn upgrade_latest_also_repins_branch_ref() {
             repository: Repository::from("my-org/my-action"),
             ref_type: Some(RefType::Branch),
             date: CommitDate::from(""),
+            provenance: Provenance::default(),
         },
     );
 
@@ -445,6 +449,7 @@ fn upgrade_targeted_does_not_repin_branch_ref() {
             repository: Repository::from("my-org/my-action"),
             ref_type: Some(RefType::Branch),
             date: CommitDate::from(""),
+            provenance: Provenance::default(),
         },
     );
     lock.set(
@@ -455,6 +460,7 @@ fn upgrade_targeted_does_not_repin_branch_ref() {
             repository: Repository::from("actions/checkout"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from(""),
+            provenance: Provenance::default(),
         },
     );
 
@@ -512,6 +518,7 @@ fn upgrade_mixed_semver_and_branch() {
             repository: Repository::from("my-org/my-action"),
             ref_type: Some(RefType::Branch),
             date: CommitDate::from(""),
+            provenance: Provenance::default(),
         },
     );
     lock.set(
@@ -522,6 +529,7 @@ fn upgrade_mixed_semver_and_branch() {
             repository: Repository::from("actions/checkout"),
             ref_type: Some(RefType::Tag),
             date: CommitDate::from(""),
+            provenance: Provenance::default(),
         },
     );
 
@@ -657,3 +665,166 @@ fn cli_rejection_latest_with_version() {
     let contains_at = action_str.contains('@');
     assert!(contains_at, "Test setup: action string should contain @");
 }
+
+#[test]
+fn saved_plan_applies_verbatim_without_registry() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = create_test_repo(&temp_dir);
+
+    let old_sha = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    write_workflow(
+        &root,
+        "ci.yml",
+        &format!(
+            "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - uses: my-org/my-action@{old_sha} # main\n"
+        ),
+    );
+    write_workflow(
+        &root,
+        "other.yml",
+        "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - run: echo hi\n",
+    );
+
+    let mut manifest = Manifest::default();
+    manifest.set(
+        ActionId::from("my-org/my-action"),
+        Specifier::from_v1("main"),
+    );
+    let mut lock = Lock::default();
+    lock.set(
+        &ActionSpec::new(
+            ActionId::from("my-org/my-action"),
+            Specifier::from_v1("main"),
+        ),
+        Version::from("main"),
+        Commit {
+            sha: CommitSha::from(old_sha),
+            repository: Repository::from("my-org/my-action"),
+            ref_type: Some(RefType::Branch),
+            date: CommitDate::from(""),
+            provenance: Provenance::default(),
+        },
+    );
+
+    let request = UpgradeRequest::new(UpgradeMode::Safe, UpgradeScope::All);
+    let plan =
+        upgrade::plan::plan(&manifest, &lock, &FakeRegistry::new(), &request, |_| {}).unwrap();
+    let plan_path = root.join("plan.json");
+    let saved = upgrade::apply::to_saved(&root, &Workflows::default(), plan).unwrap();
+    gx::infra::plan_file::write(&plan_path, &saved).unwrap();
+
+    let json = fs::read_to_string(&plan_path).unwrap();
+    let expected_sha = FakeRegistry::fake_sha("my-org/my-action", "main");
+    assert!(
+        json.contains(&expected_sha),
+        "plan must record target SHA:\n{json}"
+    );
+    assert!(
+        json.contains("ci.yml"),
+        "plan must list affected files:\n{json}"
+    );
+    assert!(
+        !json.contains("other.yml"),
+        "unaffected files stay out:\n{json}"
+    );
+
+    let loaded = gx::infra::plan_file::read(&plan_path).unwrap();
+    let updated = upgrade::apply::rewrite_workflows(&root, &loaded).unwrap();
+    assert_eq!(updated.len(), 1);
+
+    let updated_workflow =
+        fs::read_to_string(root.join(".github").join("workflows").join("ci.yml")).unwrap();
+    assert!(
+        updated_workflow.contains(&format!("my-org/my-action@{expected_sha} # main")),
+        "Expected saved plan to re-pin the workflow. Got:\n{updated_workflow}"
+    );
+}
+
+/// Run git in `root`, panicking with its stderr on failure.
+fn git(root: &Path, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["-c", "user.name=gx", "-c", "user.email=gx@example.com"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn per_action_commit_makes_one_commit_per_upgrade() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = create_test_repo(&temp_dir);
+
+    write_workflow(
+        &root,
+        "ci.yml",
+        "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - uses: actions/checkout@v4\n      - uses: actions/cache@v4\n",
+    );
+    write_manifest(
+        &root,
+        "[actions]\n\"actions/cache\" = \"^4\"\n\"actions/checkout\" = \"^4\"\n",
+    );
+    git(&root, &["init", "--quiet"]);
+    git(&root, &["add", "."]);
+    git(&root, &["commit", "--quiet", "-m", "initial"]);
+
+    let config = gx::config::Config::load(&root).unwrap();
+    let registry = FakeRegistry::new()
+        .with_all_tags("actions/checkout", vec!["v4", "v5"])
+        .with_all_tags("actions/cache", vec!["v4", "v5"]);
+    let request = UpgradeRequest::new(UpgradeMode::Latest, UpgradeScope::All);
+    let plan =
+        upgrade::plan::plan(&config.manifest, &config.lock, &registry, &request, |_| {}).unwrap();
+    let changes: Vec<upgrade::pull_request::Change> = plan
+        .upgrades
+        .iter()
+        .map(|u| upgrade::pull_request::Change {
+            action: u.id.to_string(),
+            from: u.current.to_string(),
+            to: u.manifest_specifier().to_string(),
+            new_sha: Some(format!("sha-{}", u.id)),
+            ..Default::default()
+        })
+        .collect();
+
+    // gx runs git without -c overrides, so the identity must be in the repo config
+    git(&root, &["config", "user.name", "gx"]);
+    git(&root, &["config", "user.email", "gx@example.com"]);
+    upgrade::commit::apply_per_action(
+        &root,
+        &config,
+        &plan,
+        &changes,
+        upgrade::commit::DEFAULT_TEMPLATE,
+    )
+    .unwrap();
+
+    let log = git(&root, &["log", "--format=%s"]);
+    let mut subjects: Vec<&str> = log.lines().collect();
+    assert_eq!(subjects.pop(), Some("initial"));
+    subjects.sort_unstable();
+    assert_eq!(
+        subjects,
+        vec![
+            "chore(gx): upgrade actions/cache ^4 -> ^5",
+            "chore(gx): upgrade actions/checkout ^4 -> ^5",
+        ]
+    );
+    assert!(
+        git(&root, &["log", "-1", "--format=%b"]).contains("- SHA: `unlocked` → `sha-actions/"),
+        "commit body carries the changelog"
+    );
+    assert!(
+        git(&root, &["status", "--porcelain"]).is_empty(),
+        "every written file must be committed"
+    );
+    let manifest = fs::read_to_string(manifest_path(&root)).unwrap();
+    assert!(manifest.contains("\"actions/checkout\" = \"^5\""));
+}